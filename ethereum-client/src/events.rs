@@ -13,6 +13,7 @@ pub enum EthereumEvent {
         chain: String,
         recipient: [u8; 32],
         amount: u128,
+        reference: Option<[u8; 32]>,
     },
     LockCash {
         sender: [u8; 20],
@@ -35,6 +36,10 @@ pub enum EthereumEvent {
         notice_hash: [u8; 32],
         result: Vec<u8>,
     },
+    NoticeRelayed {
+        notice_hash: [u8; 32],
+        relayer: [u8; 20],
+    },
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
@@ -82,6 +87,44 @@ lazy_static! {
         anonymous: false
     };
     static ref LOCK_EVENT_TOPIC: ethabi::Hash = LOCK_EVENT.signature();
+    static ref LOCK_WITH_REFERENCE_EVENT: ethabi::Event = ethabi::Event {
+        name: String::from("LockWithReference"),
+        inputs: vec![
+            ethabi::EventParam {
+                name: String::from("asset"),
+                kind: ethabi::param_type::ParamType::Address,
+                indexed: true
+            },
+            ethabi::EventParam {
+                name: String::from("sender"),
+                kind: ethabi::param_type::ParamType::Address,
+                indexed: true
+            },
+            ethabi::EventParam {
+                name: String::from("chain"),
+                kind: ethabi::param_type::ParamType::String,
+                indexed: false
+            },
+            ethabi::EventParam {
+                name: String::from("recipient"),
+                kind: ethabi::param_type::ParamType::FixedBytes(32),
+                indexed: true
+            },
+            ethabi::EventParam {
+                name: String::from("amount"),
+                kind: ethabi::param_type::ParamType::Uint(256),
+                indexed: false
+            },
+            ethabi::EventParam {
+                name: String::from("reference"),
+                kind: ethabi::param_type::ParamType::FixedBytes(32),
+                indexed: false
+            }
+        ],
+        anonymous: false
+    };
+    static ref LOCK_WITH_REFERENCE_EVENT_TOPIC: ethabi::Hash =
+        LOCK_WITH_REFERENCE_EVENT.signature();
     static ref LOCK_CASH_EVENT: ethabi::Event = ethabi::Event {
         name: String::from("LockCash"),
         inputs: vec![
@@ -177,6 +220,23 @@ lazy_static! {
         anonymous: false
     };
     static ref NOTICE_INVOKED_EVENT_TOPIC: ethabi::Hash = NOTICE_INVOKED_EVENT.signature();
+    static ref NOTICE_RELAYED_EVENT: ethabi::Event = ethabi::Event {
+        name: String::from("NoticeRelayed"),
+        inputs: vec![
+            ethabi::EventParam {
+                name: String::from("noticeHash"),
+                kind: ethabi::param_type::ParamType::FixedBytes(32),
+                indexed: true
+            },
+            ethabi::EventParam {
+                name: String::from("relayer"),
+                kind: ethabi::param_type::ParamType::Address,
+                indexed: true
+            },
+        ],
+        anonymous: false
+    };
+    static ref NOTICE_RELAYED_EVENT_TOPIC: ethabi::Hash = NOTICE_RELAYED_EVENT.signature();
 }
 
 fn parse_lock_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
@@ -204,6 +264,45 @@ fn parse_lock_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
                 .try_into()
                 .map_err(|_| EventError::InvalidRecipient)?,
             amount: (*amount).try_into().map_err(|_| EventError::Overflow)?,
+            reference: None,
+        }),
+        _ => Err(EventError::InvalidLogParams),
+    }
+}
+
+fn parse_lock_with_reference_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
+    match &log.params[..] {
+        [ethabi::LogParam {
+            value: ethabi::token::Token::Address(asset),
+            ..
+        }, ethabi::LogParam {
+            value: ethabi::token::Token::Address(sender),
+            ..
+        }, ethabi::LogParam {
+            value: ethabi::token::Token::String(chain),
+            ..
+        }, ethabi::LogParam {
+            value: ethabi::token::Token::FixedBytes(recipient),
+            ..
+        }, ethabi::LogParam {
+            value: ethabi::token::Token::Uint(amount),
+            ..
+        }, ethabi::LogParam {
+            value: ethabi::token::Token::FixedBytes(reference),
+            ..
+        }] => Ok(EthereumEvent::Lock {
+            asset: (*asset).into(),
+            sender: (*sender).into(),
+            chain: chain.into(),
+            recipient: recipient[..]
+                .try_into()
+                .map_err(|_| EventError::InvalidRecipient)?,
+            amount: (*amount).try_into().map_err(|_| EventError::Overflow)?,
+            reference: Some(
+                reference[..]
+                    .try_into()
+                    .map_err(|_| EventError::InvalidLogParams)?,
+            ),
         }),
         _ => Err(EventError::InvalidLogParams),
     }
@@ -307,6 +406,24 @@ fn parse_notice_invoked_log(log: ethabi::Log) -> Result<EthereumEvent, EventErro
     }
 }
 
+fn parse_notice_relayed_log(log: ethabi::Log) -> Result<EthereumEvent, EventError> {
+    match &log.params[..] {
+        [ethabi::LogParam {
+            value: ethabi::token::Token::FixedBytes(notice_hash),
+            ..
+        }, ethabi::LogParam {
+            value: ethabi::token::Token::Address(relayer),
+            ..
+        }] => Ok(EthereumEvent::NoticeRelayed {
+            notice_hash: notice_hash[..]
+                .try_into()
+                .map_err(|_| EventError::InvalidHash)?,
+            relayer: (*relayer).into(),
+        }),
+        _ => Err(EventError::InvalidLogParams),
+    }
+}
+
 pub fn decode_event(topics: Vec<String>, data: String) -> Result<EthereumEvent, EventError> {
     let topic_hashes = topics
         .iter()
@@ -324,6 +441,16 @@ pub fn decode_event(topics: Vec<String>, data: String) -> Result<EthereumEvent,
             parse_lock_log(log)
         }
 
+        t if *t == *LOCK_WITH_REFERENCE_EVENT_TOPIC => {
+            let log: ethabi::Log = LOCK_WITH_REFERENCE_EVENT
+                .parse_log(ethabi::RawLog {
+                    topics: topic_hashes,
+                    data: decode_hex(&data).ok_or(EventError::InvalidHex)?,
+                })
+                .map_err(|_| EventError::ErrorParsingLog)?;
+            parse_lock_with_reference_log(log)
+        }
+
         t if *t == *LOCK_CASH_EVENT_TOPIC => {
             let log: ethabi::Log = LOCK_CASH_EVENT
                 .parse_log(ethabi::RawLog {
@@ -364,6 +491,16 @@ pub fn decode_event(topics: Vec<String>, data: String) -> Result<EthereumEvent,
             parse_notice_invoked_log(log)
         }
 
+        t if *t == *NOTICE_RELAYED_EVENT_TOPIC => {
+            let log: ethabi::Log = NOTICE_RELAYED_EVENT
+                .parse_log(ethabi::RawLog {
+                    topics: topic_hashes,
+                    data: decode_hex(&data).ok_or(EventError::InvalidHex)?,
+                })
+                .map_err(|_| EventError::ErrorParsingLog)?;
+            parse_notice_relayed_log(log)
+        }
+
         t => Err(EventError::UnknownEventTopic(*t.as_fixed_bytes())),
     }
 }
@@ -400,7 +537,41 @@ mod tests {
                     190, 151, 67, 84, 196, 13, 110, 88, 88, 4, 176, 238, 53, 82, 241, 142, 194,
                     238, 225, 201, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
                 ],
-                amount: 1000000000000000000
+                amount: 1000000000000000000,
+                reference: None,
+            })
+        )
+    }
+
+    #[test]
+    fn test_decode_lock_with_reference_event() {
+        let topics = vec![
+            String::from("0x05dfd1630d60f52588adb758e24d7dbf4536e5daa370ca7a80768d7c3e377627"),
+            String::from("0x000000000000000000000000090c0328627d5cbd7e584c558694303d8ba6a239"),
+            String::from("0x000000000000000000000000be974354c40d6e585804b0ee3552f18ec2eee1c9"),
+            String::from("0xbe974354c40d6e585804b0ee3552f18ec2eee1c9000000000000000000000000"),
+        ];
+
+        let data =
+            String::from("0x00000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000de0b6b3a7640000070707070707070707070707070707070707070707070707070707070707070700000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000");
+        assert_eq!(
+            decode_event(topics, data),
+            Ok(EthereumEvent::Lock {
+                asset: [
+                    9, 12, 3, 40, 98, 125, 92, 189, 126, 88, 76, 85, 134, 148, 48, 61, 139, 166,
+                    162, 57
+                ],
+                sender: [
+                    190, 151, 67, 84, 196, 13, 110, 88, 88, 4, 176, 238, 53, 82, 241, 142, 194,
+                    238, 225, 201
+                ],
+                chain: String::from("ETH"),
+                recipient: [
+                    190, 151, 67, 84, 196, 13, 110, 88, 88, 4, 176, 238, 53, 82, 241, 142, 194,
+                    238, 225, 201, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+                ],
+                amount: 1000000000000000000,
+                reference: Some([7; 32]),
             })
         )
     }
@@ -497,4 +668,27 @@ mod tests {
             })
         )
     }
+
+    #[test]
+    fn test_decode_notice_relayed_event() {
+        let topics = vec![
+            String::from("0x63ed2bdb9b90b3cabb13b19a3c08699b39695491dcb6e47f52daa9753ec7ed94"),
+            String::from("0x1dcbdf2a45eb25eff04bf9f436341cecf99b05e5d1d2925991a7a2906c97a7b5"),
+            String::from("0x000000000000000000000000be974354c40d6e585804b0ee3552f18ec2eee1c9"),
+        ];
+        let data = String::from("0x");
+        assert_eq!(
+            decode_event(topics, data),
+            Ok(EthereumEvent::NoticeRelayed {
+                notice_hash: [
+                    29, 203, 223, 42, 69, 235, 37, 239, 240, 75, 249, 244, 54, 52, 28, 236, 249,
+                    155, 5, 229, 209, 210, 146, 89, 145, 167, 162, 144, 108, 151, 167, 181
+                ],
+                relayer: [
+                    190, 151, 67, 84, 196, 13, 110, 88, 88, 4, 176, 238, 53, 82, 241, 142, 194,
+                    238, 225, 201
+                ],
+            })
+        )
+    }
 }
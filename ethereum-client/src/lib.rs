@@ -6,6 +6,8 @@ use hex_buffer_serde::{ConstHex, ConstHexForm};
 use sp_runtime::offchain::{http, Duration};
 use sp_runtime_interface::pass_by::PassByCodec;
 
+use runtime_interfaces::metrics_interface;
+
 use our_std::{debug, error, info, trace, warn, Deserialize, RuntimeDebug, Serialize};
 use types_derive::{type_alias, Types};
 
@@ -23,6 +25,15 @@ pub type EthereumHash = [u8; 32];
 
 const ETH_FETCH_DEADLINE: u64 = 10_000;
 
+/// The function selector for `getLastChildBlock()` on the Polygon `RootChain` contract.
+const GET_LAST_CHILD_BLOCK_SELECTOR: &str = "0xb87e1b66";
+
+/// The function selector for `latestRoundData()` on a Chainlink aggregator contract.
+const LATEST_ROUND_DATA_SELECTOR: &str = "0xfeaf968c";
+
+/// The function selector for `observe(uint32[])` on a Uniswap V3 pool contract.
+const OBSERVE_SELECTOR: &str = "883bdbfd";
+
 #[derive(Clone, RuntimeDebug)]
 pub enum EthereumBlockId {
     Hash(EthereumHash),
@@ -37,6 +48,9 @@ pub struct EthereumBlock {
     #[serde(with = "ConstHexForm")]
     pub parent_hash: EthereumHash,
     pub number: EthereumBlockNumber,
+    /// The block timestamp, in seconds since the epoch.
+    #[serde(default)]
+    pub timestamp: u64,
     #[serde(skip)]
     pub events: Vec<EthereumEvent>,
 }
@@ -127,6 +141,16 @@ pub struct BlockNumberResponse {
     pub error: Option<ResponseError>,
 }
 
+/// The latest answer of a Chainlink aggregator, as returned by `latestRoundData()`.
+#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct ChainLinkLatestRoundData {
+    pub round_id: u128,
+    pub answer: u128,
+    pub started_at: u64,
+    pub updated_at: u64,
+    pub answered_in_round: u128,
+}
+
 fn parse_error(data: &str) -> EthereumClientError {
     error!("Error Parsing: {}", data);
     EthereumClientError::JsonParseError
@@ -162,7 +186,25 @@ pub fn send_rpc(
     method: serde_json::Value,
     params: Vec<serde_json::Value>,
 ) -> Result<String, EthereumClientError> {
-    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(ETH_FETCH_DEADLINE));
+    let start = sp_io::offchain::timestamp();
+    let result = request_rpc(server, method, params);
+    let elapsed = sp_io::offchain::timestamp().diff(&start);
+    metrics_interface::observe_ms("eth_rpc", elapsed.millis());
+    if result.is_err() {
+        metrics_interface::inc_counter("eth_rpc_errors", 1);
+    }
+    result
+}
+
+fn request_rpc(
+    server: &str,
+    method: serde_json::Value,
+    params: Vec<serde_json::Value>,
+) -> Result<String, EthereumClientError> {
+    let (timeout, headers) =
+        runtime_interfaces::validator_config_interface::get_http_options(server);
+    let deadline = sp_io::offchain::timestamp()
+        .add(Duration::from_millis(timeout.unwrap_or(ETH_FETCH_DEADLINE)));
     let data = serde_json::json!({
         "jsonrpc": "2.0",
         "method": method,
@@ -172,11 +214,13 @@ pub fn send_rpc(
     .to_string();
     trace!("RPC: {}", &data);
 
-    let request = http::Request::post(server, vec![data]);
+    let request = headers.iter().fold(
+        http::Request::post(server, vec![data]).add_header("Content-Type", "application/json"),
+        |request, (name, value)| request.add_header(name, value),
+    );
 
     let pending = request
         .deadline(deadline)
-        .add_header("Content-Type", "application/json")
         .send()
         .map_err(|_| EthereumClientError::HttpIoError)?;
 
@@ -249,6 +293,9 @@ pub fn get_block(
         }
     }
 
+    metrics_interface::inc_counter("blocks_fetched", 1);
+    metrics_interface::inc_counter("events_decoded", events.len() as u64);
+
     // note these error messages are imperfect as they don't show the broken data
     //  but also should never happen and not worth fixing for now
     Ok(EthereumBlock {
@@ -256,6 +303,7 @@ pub fn get_block(
         parent_hash: parse_word(block_obj.parentHash)
             .ok_or_else(|| parse_error("bad parent hash"))?,
         number: parse_u64(block_obj.number).ok_or_else(|| parse_error("bad block number"))?,
+        timestamp: parse_u64(block_obj.timestamp).ok_or_else(|| parse_error("bad timestamp"))?,
         events,
     })
 }
@@ -287,6 +335,138 @@ pub fn get_latest_block_number(server: &str) -> Result<u64, EthereumClientError>
         .ok_or(EthereumClientError::JsonParseError)
 }
 
+/// Estimate the gas used by sending the given calldata to a contract.
+pub fn estimate_gas(server: &str, to: &[u8; 20], data: &[u8]) -> Result<u64, EthereumClientError> {
+    let params = vec![serde_json::json!({
+        "to": format!("0x{}", ::hex::encode(&to[..])),
+        "data": format!("0x{}", ::hex::encode(data)),
+    })];
+    let response_str: String = send_rpc(server, "eth_estimateGas".into(), params)?;
+    let response = deserialize_block_number_response(&response_str)?;
+    debug!("eth_estimateGas response: {:?}", response.result.clone());
+    parse_u64(Some(response.result.ok_or(EthereumClientError::NoResult)?))
+        .ok_or(EthereumClientError::JsonParseError)
+}
+
+/// Get the last Polygon block number included in a Heimdall checkpoint on Ethereum.
+pub fn get_last_child_block(
+    server: &str,
+    root_chain_address: &[u8; 20],
+) -> Result<u64, EthereumClientError> {
+    let params = vec![
+        serde_json::json!({
+            "to": format!("0x{}", ::hex::encode(&root_chain_address[..])),
+            "data": GET_LAST_CHILD_BLOCK_SELECTOR,
+        }),
+        "latest".into(),
+    ];
+    let response_str: String = send_rpc(server, "eth_call".into(), params)?;
+    let response = deserialize_block_number_response(&response_str)?;
+    debug!("getLastChildBlock response: {:?}", response.result.clone());
+    parse_u64(Some(response.result.ok_or(EthereumClientError::NoResult)?))
+        .ok_or(EthereumClientError::JsonParseError)
+}
+
+/// Get the latest round of a Chainlink aggregator, rejecting negative answers.
+pub fn get_latest_round_data(
+    server: &str,
+    aggregator_address: &[u8; 20],
+) -> Result<ChainLinkLatestRoundData, EthereumClientError> {
+    let params = vec![
+        serde_json::json!({
+            "to": format!("0x{}", ::hex::encode(&aggregator_address[..])),
+            "data": LATEST_ROUND_DATA_SELECTOR,
+        }),
+        "latest".into(),
+    ];
+    let response_str: String = send_rpc(server, "eth_call".into(), params)?;
+    let response = deserialize_block_number_response(&response_str)?;
+    debug!("latestRoundData response: {:?}", response.result.clone());
+    let data = hex::decode_hex(&response.result.ok_or(EthereumClientError::NoResult)?)
+        .ok_or(EthereumClientError::JsonParseError)?;
+    parse_latest_round_data(&data[..]).ok_or(EthereumClientError::DecodeError)
+}
+
+fn parse_latest_round_data(data: &[u8]) -> Option<ChainLinkLatestRoundData> {
+    use our_std::convert::TryInto;
+    let types = [
+        ethabi::ParamType::Uint(80),
+        ethabi::ParamType::Int(256),
+        ethabi::ParamType::Uint(256),
+        ethabi::ParamType::Uint(256),
+        ethabi::ParamType::Uint(80),
+    ];
+    match &ethabi::decode(&types, data).ok()?[..] {
+        [ethabi::token::Token::Uint(round_id), ethabi::token::Token::Int(answer), ethabi::token::Token::Uint(started_at), ethabi::token::Token::Uint(updated_at), ethabi::token::Token::Uint(answered_in_round)] =>
+        {
+            // answers are two's complement, the high bit set means a negative price
+            if answer.bit(255) {
+                return None;
+            }
+            Some(ChainLinkLatestRoundData {
+                round_id: (*round_id).try_into().ok()?,
+                answer: (*answer).try_into().ok()?,
+                started_at: (*started_at).try_into().ok()?,
+                updated_at: (*updated_at).try_into().ok()?,
+                answered_in_round: (*answered_in_round).try_into().ok()?,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Get the tick cumulatives of a Uniswap V3 pool, `seconds_ago` in the past and now.
+pub fn get_uniswap_tick_cumulatives(
+    server: &str,
+    pool_address: &[u8; 20],
+    seconds_ago: u32,
+) -> Result<(i64, i64), EthereumClientError> {
+    let args = ethabi::encode(&[ethabi::token::Token::Array(vec![
+        ethabi::token::Token::Uint(seconds_ago.into()),
+        ethabi::token::Token::Uint(0.into()),
+    ])]);
+    let params = vec![
+        serde_json::json!({
+            "to": format!("0x{}", ::hex::encode(&pool_address[..])),
+            "data": format!("0x{}{}", OBSERVE_SELECTOR, ::hex::encode(&args)),
+        }),
+        "latest".into(),
+    ];
+    let response_str: String = send_rpc(server, "eth_call".into(), params)?;
+    let response = deserialize_block_number_response(&response_str)?;
+    debug!("observe response: {:?}", response.result.clone());
+    let data = hex::decode_hex(&response.result.ok_or(EthereumClientError::NoResult)?)
+        .ok_or(EthereumClientError::JsonParseError)?;
+    parse_tick_cumulatives(&data[..]).ok_or(EthereumClientError::DecodeError)
+}
+
+fn parse_tick_cumulatives(data: &[u8]) -> Option<(i64, i64)> {
+    let types = [
+        ethabi::ParamType::Array(Box::new(ethabi::ParamType::Int(56))),
+        ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(160))),
+    ];
+    match &ethabi::decode(&types, data).ok()?[..] {
+        [ethabi::token::Token::Array(ticks), _] => match &ticks[..] {
+            [ethabi::token::Token::Int(past), ethabi::token::Token::Int(now)] => {
+                Some((int_to_i64(*past)?, int_to_i64(*now)?))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Convert a two's complement word into a signed integer.
+fn int_to_i64(word: ethabi::Int) -> Option<i64> {
+    use our_std::convert::{TryFrom, TryInto};
+    if word.bit(255) {
+        let magnitude: u64 = (!word + 1).try_into().ok()?;
+        Some(-i64::try_from(magnitude).ok()?)
+    } else {
+        i64::try_from(u64::try_from(word).ok()?).ok()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -347,6 +527,7 @@ mod tests {
                 ]
             );
             assert_eq!(block.number, 1286);
+            assert_eq!(block.timestamp, 1521387961);
             assert_eq!(
                 block.events,
                 vec![EthereumEvent::Lock {
@@ -363,7 +544,8 @@ mod tests {
                         211, 163, 141, 75, 208, 123, 135, 228, 81, 111, 48, 238, 70, 207, 232, 236,
                         78, 139, 115, 164, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
                     ],
-                    amount: 500000000000000000
+                    amount: 500000000000000000,
+                    reference: None,
                 }]
             );
         });
@@ -394,6 +576,64 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_last_child_block() {
+        let (offchain, state) = testing::TestOffchainExt::new();
+        let mut t = sp_io::TestExternalities::default();
+        t.register_extension(OffchainDbExt::new(offchain.clone()));
+        t.register_extension(OffchainWorkerExt::new(offchain));
+        {
+            let mut s = state.write();
+            s.expect_request(testing::PendingRequest {
+                method: "POST".into(),
+                uri: "https://mainnet-eth.compound.finance".into(),
+                headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+                body: br#"{"jsonrpc":"2.0","method":"eth_call","params":[{"to":"0x86e4dc95c7fbdbf52e33d563bbdb00823894c287","data":"0xb87e1b66"},"latest"],"id":1}"#.to_vec(),
+                response: Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000f4a4ff"}"#.to_vec()),
+                sent: true,
+                ..Default::default()
+            });
+        }
+        t.execute_with(|| {
+            let result = get_last_child_block(
+                "https://mainnet-eth.compound.finance",
+                &[
+                    134, 228, 220, 149, 199, 251, 219, 245, 46, 51, 213, 99, 187, 219, 0, 130, 56,
+                    148, 194, 135,
+                ],
+            );
+            assert_eq!(result, Ok(16033023));
+        });
+    }
+
+    #[test]
+    fn test_estimate_gas() {
+        let (offchain, state) = testing::TestOffchainExt::new();
+        let mut t = sp_io::TestExternalities::default();
+        t.register_extension(OffchainDbExt::new(offchain.clone()));
+        t.register_extension(OffchainWorkerExt::new(offchain));
+        {
+            let mut s = state.write();
+            s.expect_request(testing::PendingRequest {
+                method: "POST".into(),
+                uri: "https://mainnet-eth.compound.finance".into(),
+                headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+                body: br#"{"jsonrpc":"2.0","method":"eth_estimateGas","params":[{"to":"0x7777777777777777777777777777777777777777","data":"0x010203"}],"id":1}"#.to_vec(),
+                response: Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x5208"}"#.to_vec()),
+                sent: true,
+                ..Default::default()
+            });
+        }
+        t.execute_with(|| {
+            let result = estimate_gas(
+                "https://mainnet-eth.compound.finance",
+                &[0x77; 20],
+                &[1, 2, 3],
+            );
+            assert_eq!(result, Ok(21000));
+        });
+    }
+
     #[test]
     fn test_get_block_object() {
         let (offchain, state) = testing::TestOffchainExt::new();
@@ -538,4 +778,82 @@ mod tests {
     fn test_encode_block_number_hex() {
         assert_eq!(encode_block_number_hex(0xb27467 + 1), "0xB27468");
     }
+
+    #[test]
+    fn test_get_latest_round_data() {
+        let (offchain, state) = testing::TestOffchainExt::new();
+        let mut t = sp_io::TestExternalities::default();
+        t.register_extension(OffchainDbExt::new(offchain.clone()));
+        t.register_extension(OffchainWorkerExt::new(offchain));
+        {
+            let mut s = state.write();
+            s.expect_request(testing::PendingRequest {
+                method: "POST".into(),
+                uri: "https://mainnet-eth.compound.finance".into(),
+                headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+                body: br#"{"jsonrpc":"2.0","method":"eth_call","params":[{"to":"0x5f4ec3df9cbd43714fe2740f5e3616155c5b8419","data":"0xfeaf968c"},"latest"],"id":1}"#.to_vec(),
+                response: Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000050000000000001a2b0000000000000000000000000000000000000000000000000000003b9aca000000000000000000000000000000000000000000000000000000000000608f3c0f00000000000000000000000000000000000000000000000000000000608f3c1f0000000000000000000000000000000000000000000000050000000000001a2b"}"#.to_vec()),
+                sent: true,
+                ..Default::default()
+            });
+        }
+        t.execute_with(|| {
+            let result = get_latest_round_data(
+                "https://mainnet-eth.compound.finance",
+                &[
+                    95, 78, 195, 223, 156, 189, 67, 113, 79, 226, 116, 15, 94, 54, 22, 21, 92, 91,
+                    132, 25,
+                ],
+            );
+            assert_eq!(
+                result,
+                Ok(ChainLinkLatestRoundData {
+                    round_id: 0x50000000000001a2b,
+                    answer: 256_000_000_000,
+                    started_at: 0x608f3c0f,
+                    updated_at: 0x608f3c1f,
+                    answered_in_round: 0x50000000000001a2b,
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_latest_round_data_negative() {
+        let mut data = vec![0u8; 160];
+        data[32..64].copy_from_slice(&[0xff; 32]);
+        assert_eq!(parse_latest_round_data(&data[..]), None);
+        assert_eq!(parse_latest_round_data(&data[..64]), None);
+    }
+
+    #[test]
+    fn test_get_uniswap_tick_cumulatives() {
+        let (offchain, state) = testing::TestOffchainExt::new();
+        let mut t = sp_io::TestExternalities::default();
+        t.register_extension(OffchainDbExt::new(offchain.clone()));
+        t.register_extension(OffchainWorkerExt::new(offchain));
+        {
+            let mut s = state.write();
+            s.expect_request(testing::PendingRequest {
+                method: "POST".into(),
+                uri: "https://mainnet-eth.compound.finance".into(),
+                headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+                body: br#"{"jsonrpc":"2.0","method":"eth_call","params":[{"to":"0x8ad599c3a0ff1de082011efddc58f1908eb6e6d8","data":"0x883bdbfd0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000007080000000000000000000000000000000000000000000000000000000000000000"},"latest"],"id":1}"#.to_vec(),
+                response: Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000000000002ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffc91180ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffc39340000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002"}"#.to_vec()),
+                sent: true,
+                ..Default::default()
+            });
+        }
+        t.execute_with(|| {
+            let result = get_uniswap_tick_cumulatives(
+                "https://mainnet-eth.compound.finance",
+                &[
+                    138, 213, 153, 195, 160, 255, 29, 224, 130, 1, 30, 253, 220, 88, 241, 144, 142,
+                    182, 230, 216,
+                ],
+                1800,
+            );
+            assert_eq!(result, Ok((-3_600_000, -3_960_000)));
+        });
+    }
 }
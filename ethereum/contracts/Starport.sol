@@ -24,6 +24,7 @@ contract Starport {
 
     event NoticeInvoked(uint32 indexed eraId, uint32 indexed eraIndex, bytes32 indexed noticeHash, bytes result);
     event NoticeReplay(bytes32 indexed noticeHash);
+    event NoticeRelayed(bytes32 indexed noticeHash, address indexed relayer);
 
     event Lock(address indexed asset, address indexed sender, string chain, bytes32 indexed recipient, uint amount);
     event LockCash(address indexed sender, string chain, bytes32 indexed recipient, uint amount, uint128 principal);
@@ -276,6 +277,7 @@ contract Starport {
         }
 
         emit NoticeInvoked(uint32(noticeEraId), uint32(noticeEraIndex), noticeHash, callResult);
+        emit NoticeRelayed(noticeHash, msg.sender);
 
         return callResult;
     }
@@ -291,6 +293,26 @@ contract Starport {
     function unlock(address asset, uint amount, address payable account) external {
         require(msg.sender == address(this), "Call must originate locally");
 
+        unlockInternal(asset, amount, account);
+    }
+
+    /**
+     * @notice Unlock a batch of assets from the Starport
+     * @dev This must be called from `invoke` via passing in a signed notice from Gateway.
+     * @param assets The Assets to unlock
+     * @param amounts The amounts of each asset to unlock in its native token units
+     * @param accounts The accounts to transfer each asset to
+     */
+    function unlockBatch(address[] calldata assets, uint[] calldata amounts, address payable[] calldata accounts) external {
+        require(msg.sender == address(this), "Call must originate locally");
+        require(assets.length == amounts.length && assets.length == accounts.length, "Batch lengths must match");
+
+        for (uint i = 0; i < assets.length; i++) {
+            unlockInternal(assets[i], amounts[i], accounts[i]);
+        }
+    }
+
+    function unlockInternal(address asset, uint amount, address payable account) internal {
         emit Unlock(account, amount, asset);
 
         if (asset == ETH_ADDRESS) {
@@ -43,6 +43,11 @@ contract StarportHarness is Starport {
 		Starport(this).unlock(asset, amount, account);
 	}
 
+	/// Harness to call `unlockBatch` with this as `msg.sender`
+	function unlockBatch_(address[] calldata assets, uint[] calldata amounts, address payable[] calldata accounts) external {
+		Starport(this).unlockBatch(assets, amounts, accounts);
+	}
+
 	/// Harness to call `unlockCash` with this as `msg.sender`
 	function unlockCash_(address account, uint128 principal) external {
 		Starport(this).unlockCash(account, principal);
@@ -761,6 +761,17 @@ describe('Starport', () => {
       });
     });
 
+    it('should emit the relayer of an invoked notice', async () => {
+      let notice = buildNotice(starport.methods.count_());
+      let signatures = signAll(notice, authorityWallets);
+
+      let tx = await send(starport, 'invoke', [notice, signatures], { from: account1 });
+      expect(tx.events.NoticeRelayed.returnValues).toMatchObject({
+        noticeHash: hashNotice(notice),
+        relayer: account1
+      });
+    });
+
     it('should invoke simple signed message to start next era', async () => {
       let notice = buildNotice(starport.methods.count_(), { newEra: true });
       let signatures = signAll(notice, authorityWallets);
@@ -1329,6 +1340,29 @@ describe('Starport', () => {
       await expect(call(starport, 'unlock', [tokenA._address, 1000, account1])).rejects.toRevert('revert Call must originate locally');
     });
 
+    it('should unlock batch', async () => {
+      await tokenA.methods.transfer(starport._address, 1500).send({ from: root });
+
+      const tx = await send(starport, 'unlockBatch_', [[tokenA._address, tokenA._address], [1000, 300], [account2, account1]]);
+
+      expect(tx.events.Unlock.map(e => e.returnValues)).toMatchObject([
+        { asset: tokenA._address, account: account2, amount: '1000' },
+        { asset: tokenA._address, account: account1, amount: '300' }
+      ]);
+
+      expect(Number(await tokenA.methods.balanceOf(starport._address).call())).toEqual(200);
+      expect(Number(await tokenA.methods.balanceOf(account2).call())).toEqual(1000);
+      expect(Number(await tokenA.methods.balanceOf(account1).call())).toEqual(300);
+    });
+
+    it('should not unlock batch with mismatched lengths', async () => {
+      await expect(call(starport, 'unlockBatch_', [[tokenA._address], [1000, 300], [account2]])).rejects.toRevert('revert Batch lengths must match');
+    });
+
+    it('should fail batch when not called by self', async () => {
+      await expect(call(starport, 'unlockBatch', [[tokenA._address], [1000], [account1]])).rejects.toRevert('revert Call must originate locally');
+    });
+
     it('should fail when insufficient token balance', async () => {
       await expect(call(starport, 'unlock_', [tokenA._address, 1000, account1])).rejects.toRevert('revert Transfer: insufficient balance');
     });
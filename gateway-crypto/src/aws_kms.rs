@@ -1,13 +1,11 @@
 use crate::std::*;
 use crate::{
-    eth_keccak_for_signature, tagged_public_key_slice_to_raw, CryptoError, HashedMessageBytes,
-    PublicKeyBytes, SignatureBytes, ETH_ADD_TO_V,
+    eth_keccak_for_signature, CryptoError, Ed25519PublicKeyBytes, Ed25519SignatureBytes,
+    HashedMessageBytes, PublicKeyBytes, SignatureBytes,
 };
-use der_parser::parse_der;
+use our_std::convert::TryInto;
 use rusoto_core::{Region, RusotoError};
 use rusoto_kms::{GetPublicKeyRequest, Kms, KmsClient, SignError, SignRequest, SignResponse};
-use secp256k1::util::{FULL_PUBLIC_KEY_SIZE, TAG_PUBKEY_FULL};
-use secp256k1::{PublicKey, PublicKeyFormat, RecoveryId, Signature};
 
 /// Store your keys in AWS Key Management Service (KMS) for increased security. KMS is implemented
 /// using Hardware Signing Modules (HSMs) for the highest level of security. It is relatively inexpensive
@@ -45,10 +43,31 @@ impl Keyring for KmsKeyring {
         let mut rt = Runtime::new().unwrap();
         rt.block_on(self.get_public_key_async(key_id))
     }
+
+    /// Sign messages with an ECC_NIST_EDWARDS25519 key using the AWS KMS HSM system.
+    fn sign_ed25519(
+        self: &Self,
+        messages: Vec<&[u8]>,
+        key_id: &KeyId,
+    ) -> Result<Vec<Result<Ed25519SignatureBytes, CryptoError>>, CryptoError> {
+        let mut rt = Runtime::new().unwrap();
+        rt.block_on(self.sign_ed25519_async(messages, key_id))
+    }
+
+    /// Get the Ed25519 public key corresponding to the provided key ID.
+    fn get_ed25519_public_key(
+        self: &Self,
+        key_id: &KeyId,
+    ) -> Result<Ed25519PublicKeyBytes, CryptoError> {
+        let mut rt = Runtime::new().unwrap();
+        rt.block_on(self.get_ed25519_public_key_async(key_id))
+    }
 }
 
 const KMS_SIGNING_ALGORITHM_ECDSA_SHA_256: &str = "ECDSA_SHA_256";
+const KMS_SIGNING_ALGORITHM_ED25519_SHA_512: &str = "ED25519_SHA_512";
 const KMS_MESSAGE_TYPE_DIGEST: &str = "DIGEST";
+const KMS_MESSAGE_TYPE_RAW: &str = "RAW";
 
 impl KmsKeyring {
     /// Create a new KMS keyring. Standard methods of configuring AWS clients within the
@@ -78,26 +97,64 @@ impl KmsKeyring {
             .await
             .map_err(|_| CryptoError::KeyNotFound)?;
         let public_key = result.public_key.ok_or(CryptoError::KeyNotFound)?;
-        let (_, decoded) = parse_der(&public_key).map_err(|_| CryptoError::ParseError)?;
-        let sequence = decoded.as_sequence().map_err(|_| CryptoError::ParseError)?;
-        if sequence.len() != 2 {
-            return Err(CryptoError::ParseError);
-        }
-        let actual_public_key = sequence[1]
-            .content
-            .as_slice()
-            .map_err(|_| CryptoError::ParseError)?;
-        if actual_public_key.len() != FULL_PUBLIC_KEY_SIZE {
-            return Err(CryptoError::ParseError);
+        der_public_key_to_raw(&public_key)
+    }
+
+    /// Get the Ed25519 public key corresponding to the key_id from KMS.
+    async fn get_ed25519_public_key_async(
+        self: &Self,
+        key_id: &KeyId,
+    ) -> Result<Ed25519PublicKeyBytes, CryptoError> {
+        let request: GetPublicKeyRequest = GetPublicKeyRequest {
+            key_id: key_id.clone().into(),
+            ..Default::default()
+        };
+        let result = self
+            .client
+            .get_public_key(request)
+            .await
+            .map_err(|_| CryptoError::KeyNotFound)?;
+        let public_key = result.public_key.ok_or(CryptoError::KeyNotFound)?;
+        der_ed25519_public_key_to_raw(&public_key)
+    }
+
+    /// Sign the messages asynchronously with an Ed25519 key. The messages are signed as they are,
+    /// KMS only supports Ed25519 signatures of raw messages, up to 4096 bytes.
+    async fn sign_ed25519_async(
+        self: &Self,
+        messages: Vec<&[u8]>,
+        key_id: &KeyId,
+    ) -> Result<Vec<Result<Ed25519SignatureBytes, CryptoError>>, CryptoError> {
+        // launch all of the tasks
+        let mut requests = Vec::new();
+        for message in messages {
+            let request = SignRequest {
+                key_id: key_id.into(),
+                message: bytes::Bytes::copy_from_slice(message),
+                message_type: Some(KMS_MESSAGE_TYPE_RAW.into()),
+                signing_algorithm: KMS_SIGNING_ALGORITHM_ED25519_SHA_512.into(),
+                ..Default::default()
+            };
+            requests.push(self.client.sign(request));
         }
-        // strip first byte due to https://tools.ietf.org/html/rfc5480#section-2.2 indicating "uncompressed"
-        // this could be considered "RAW" format
-        if actual_public_key[0] != TAG_PUBKEY_FULL {
-            return Err(CryptoError::ParseError);
+
+        // collect results
+        let mut all_results = Vec::new();
+        for fut in requests {
+            // note, we can await serially here because all tasks have already been launched
+            let signature = fut
+                .await
+                .map_err(|_| CryptoError::HSMError)
+                .and_then(|response| response.signature.ok_or(CryptoError::HSMError))
+                .and_then(|signature| {
+                    signature[..]
+                        .try_into()
+                        .map_err(|_| CryptoError::ParseError)
+                });
+            all_results.push(signature);
         }
 
-        // let address = public_key_bytes_to_eth_address(actual_public_key);
-        Ok(tagged_public_key_slice_to_raw(actual_public_key)?)
+        Ok(all_results)
     }
 
     /// Sign the messages asynchronously. This submits multiple requests to the HSM in parallel
@@ -154,32 +211,7 @@ impl KmsKeyring {
             .ok_or(CryptoError::HSMError)?
             .to_vec();
 
-        let mut sig =
-            Signature::parse_der(&der_encoded_signature).map_err(|_| CryptoError::ParseError)?;
-        // Because of EIP-2 not all elliptic curve signatures are accepted
-        // the value of s needs to be SMALLER than half of the curve
-        // i.e. we need to flip s if it's greater than half of the curve
-        if sig.s.is_high() {
-            sig.s = -sig.s;
-        }
-
-        // find the recovery id by guessing
-        let public_key = PublicKey::parse_slice(public_key, Some(PublicKeyFormat::Raw))
-            .map_err(|_| CryptoError::ParseError)?;
-        let message =
-            secp256k1::Message::parse_slice(&digested).map_err(|_| CryptoError::ParseError)?;
-        let mut recovery_id = RecoveryId::parse(0).map_err(|_| CryptoError::ParseError)?;
-        let recovered = secp256k1::recover(&message, &sig, &recovery_id)
-            .map_err(|_| CryptoError::RecoverError)?;
-        if recovered != public_key {
-            recovery_id = RecoveryId::parse(1).map_err(|_| CryptoError::ParseError)?
-        }
-
-        // combine signature with recovery ID so we can recover the public key of the signer later
-        Ok(combine_sig_and_recovery(
-            sig.serialize(),
-            recovery_id.serialize() + ETH_ADD_TO_V,
-        ))
+        der_signature_to_eth(&der_encoded_signature, public_key, digested)
     }
 }
 
@@ -1,13 +1,17 @@
 use crate::{
-    eth_decode_hex, CryptoError, InMemoryKeyring, KeyId, ETH_KEY_ID_ENV_VAR_DEV_DEFAULT,
-    ETH_PRIVATE_KEY_ENV_VAR,
+    eth_decode_hex, CryptoError, InMemoryKeyring, KeyId, ED25519_KEY_ID_ENV_VAR_DEV_DEFAULT,
+    ETH_KEY_ID_ENV_VAR_DEV_DEFAULT, ETH_PRIVATE_KEY_ENV_VAR,
 };
 use sp_core::ecdsa::Pair as EcdsaPair;
+use sp_core::ed25519::Pair as Ed25519Pair;
 use sp_core::Pair;
 
 pub(crate) const ETH_PRIVATE_KEY_DEFAULT_VALUE: &str =
     "50f05592dc31bfc65a77c4cc80f2764ba8f9a7cce29c94a51fe2d70cb5599374";
 
+pub(crate) const ED25519_SEED_DEFAULT_VALUE: &str =
+    "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+
 /// For dev - get the eth private key from an environment variable
 fn get_eth_private_key_from_environment_variable() -> Result<EcdsaPair, CryptoError> {
     let eth_private_key_string = std::env::var(ETH_PRIVATE_KEY_ENV_VAR)
@@ -26,23 +30,25 @@ fn get_eth_private_key_from_environment_variable() -> Result<EcdsaPair, CryptoEr
 }
 
 const ETH_KEY_ID_ENV_VAR: &str = "ETH_KEY_ID";
+const ED25519_KEY_ID_ENV_VAR: &str = "ED25519_KEY_ID";
+
+/// Get the key id from an environment variable, or the default if it is not set.
+fn get_key_id_from_environment_variable(env_var: &str, default: &str) -> KeyId {
+    match std::env::var(env_var) {
+        Ok(key_id_from_env) if key_id_from_env.len() > 0 => KeyId::from(key_id_from_env),
+        _ => default.into(),
+    }
+}
 
 /// Sets up the development keyring, an in memory keyring loaded with the default key
-/// for signing messages headed to ethereum.
+/// for signing messages headed to ethereum, and the default Ed25519 key for other chains.
 ///
 /// WARNING - This function will panic whenever a bad private key is set in the environment
 /// variable. That is "ok" because it should only be used during boot.
 pub fn dev_keyring() -> InMemoryKeyring {
     let mut keyring = InMemoryKeyring::new();
-    let eth_key_id: KeyId = if let Ok(eth_key_id_from_env) = std::env::var(ETH_KEY_ID_ENV_VAR) {
-        if eth_key_id_from_env.len() > 0 {
-            KeyId::from(eth_key_id_from_env)
-        } else {
-            ETH_KEY_ID_ENV_VAR_DEV_DEFAULT.into()
-        }
-    } else {
-        ETH_KEY_ID_ENV_VAR_DEV_DEFAULT.into()
-    };
+    let eth_key_id =
+        get_key_id_from_environment_variable(ETH_KEY_ID_ENV_VAR, ETH_KEY_ID_ENV_VAR_DEV_DEFAULT);
 
     let pair = match get_eth_private_key_from_environment_variable() {
         Ok(pair) => pair,
@@ -60,5 +66,13 @@ pub fn dev_keyring() -> InMemoryKeyring {
     };
     keyring.add(&eth_key_id, pair);
 
+    let ed25519_key_id = get_key_id_from_environment_variable(
+        ED25519_KEY_ID_ENV_VAR,
+        ED25519_KEY_ID_ENV_VAR_DEV_DEFAULT,
+    );
+    let ed25519_seed = hex::decode(ED25519_SEED_DEFAULT_VALUE).unwrap();
+    let ed25519_pair = Ed25519Pair::from_seed_slice(&ed25519_seed).unwrap();
+    keyring.add_ed25519(&ed25519_key_id, ed25519_pair);
+
     keyring
 }
@@ -0,0 +1,280 @@
+use crate::std::*;
+use crate::{
+    eth_keccak_for_signature, CryptoError, HashedMessageBytes, PublicKeyBytes, SignatureBytes,
+};
+use futures::future::join_all;
+use hyper::{client::HttpConnector, Body, Client, Request};
+use hyper_tls::HttpsConnector;
+use serde_json::{json, Value};
+use tokio::runtime::Runtime;
+
+/// The URI scheme of key ids for keys held in Google Cloud KMS.
+pub const GCP_KMS_SCHEME: &str = "gcpkms";
+
+const GCP_KMS_ENDPOINT: &str = "https://cloudkms.googleapis.com/v1";
+const GCP_ACCESS_TOKEN_ENV_VAR: &str = "GCP_ACCESS_TOKEN";
+const GCP_METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Store your keys in Google Cloud Key Management Service (KMS), for validators hosted on GCP.
+/// Keys must be asymmetric signing keys using the `EC_SIGN_SECP256K1_SHA256` algorithm, which may
+/// be held in software or in HSMs.
+///
+/// A key is identified by the resource name of a key version, in the `gcpkms` scheme, e.g.
+/// gcpkms://projects/my-project/locations/global/keyRings/my-ring/cryptoKeys/my-key/cryptoKeyVersions/1
+///
+/// https://cloud.google.com/kms/docs/reference/rest/v1/projects.locations.keyRings.cryptoKeys.cryptoKeyVersions/asymmetricSign
+pub struct GcpKmsKeyring {}
+
+impl Keyring for GcpKmsKeyring {
+    /// Sign messages using GCP KMS.
+    fn sign(
+        self: &Self,
+        messages: Vec<&[u8]>,
+        key_id: &KeyId,
+    ) -> Result<Vec<Result<SignatureBytes, CryptoError>>, CryptoError> {
+        let mut rt = Runtime::new().unwrap();
+        rt.block_on(self.sign_async(messages, key_id))
+    }
+
+    fn sign_one(self: &Self, messages: &[u8], key_id: &KeyId) -> Result<[u8; 65], CryptoError> {
+        // we will use the batch interface
+        self.sign(vec![messages], key_id)?
+            .drain(..)
+            .next()
+            .ok_or(CryptoError::Unknown)?
+    }
+
+    /// Get the public key corresponding to the provided key ID.
+    fn get_public_key(self: &Self, key_id: &KeyId) -> Result<PublicKeyBytes, CryptoError> {
+        let mut rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let client = Self::client();
+            let token = Self::get_access_token(&client).await?;
+            Self::get_public_key_async(&client, &token, key_id).await
+        })
+    }
+}
+
+impl GcpKmsKeyring {
+    /// Create a new GCP KMS keyring. Requests are authenticated with the OAuth access token in the
+    /// GCP_ACCESS_TOKEN environment variable if set, otherwise with a token for the instance's
+    /// default service account, fetched from the GCP metadata server.
+    pub fn new() -> GcpKmsKeyring {
+        GcpKmsKeyring {}
+    }
+
+    /// Create an HTTPS client, within the runtime it will be used on.
+    fn client() -> Client<HttpsConnector<HttpConnector>> {
+        Client::builder().build(HttpsConnector::new())
+    }
+
+    /// Get the resource name of the key version identified by the key id.
+    fn key_name(key_id: &KeyId) -> Result<&str, CryptoError> {
+        key_id
+            .strip_scheme(GCP_KMS_SCHEME)
+            .ok_or(CryptoError::InvalidKeyId)
+    }
+
+    /// Send a request and parse the JSON response, failing on any unsuccessful status.
+    async fn request_json(
+        client: &Client<HttpsConnector<HttpConnector>>,
+        request: Request<Body>,
+    ) -> Result<Value, CryptoError> {
+        let response = client
+            .request(request)
+            .await
+            .map_err(|_| CryptoError::HSMError)?;
+        if !response.status().is_success() {
+            return Err(CryptoError::HSMError);
+        }
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|_| CryptoError::HSMError)?;
+        serde_json::from_slice(&body).map_err(|_| CryptoError::ParseError)
+    }
+
+    /// Get an OAuth access token to authenticate requests to KMS with.
+    async fn get_access_token(
+        client: &Client<HttpsConnector<HttpConnector>>,
+    ) -> Result<String, CryptoError> {
+        if let Ok(token) = std::env::var(GCP_ACCESS_TOKEN_ENV_VAR) {
+            if token.len() > 0 {
+                return Ok(token);
+            }
+        }
+        let request = Request::get(GCP_METADATA_TOKEN_URL)
+            .header("Metadata-Flavor", "Google")
+            .body(Body::empty())
+            .map_err(|_| CryptoError::HSMError)?;
+        let response = Self::request_json(client, request).await?;
+        response["access_token"]
+            .as_str()
+            .map(String::from)
+            .ok_or(CryptoError::HSMError)
+    }
+
+    /// Get the public key corresponding to the key_id from KMS.
+    async fn get_public_key_async(
+        client: &Client<HttpsConnector<HttpConnector>>,
+        token: &str,
+        key_id: &KeyId,
+    ) -> Result<PublicKeyBytes, CryptoError> {
+        let request = Request::get(format!(
+            "{}/{}/publicKey",
+            GCP_KMS_ENDPOINT,
+            Self::key_name(key_id)?
+        ))
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .map_err(|_| CryptoError::KeyNotFound)?;
+        let response = Self::request_json(client, request)
+            .await
+            .map_err(|_| CryptoError::KeyNotFound)?;
+        let pem = response["pem"].as_str().ok_or(CryptoError::KeyNotFound)?;
+        der_public_key_to_raw(&pem_to_der(pem)?)
+    }
+
+    /// Sign a digest with the key version, returning the DER-encoded signature.
+    /// KMS signs the given digest as is, so it may be a keccak hash rather than a SHA-256 one.
+    async fn sign_digest(
+        client: &Client<HttpsConnector<HttpConnector>>,
+        token: &str,
+        name: &str,
+        digest: HashedMessageBytes,
+    ) -> Result<Vec<u8>, CryptoError> {
+        let body = json!({ "digest": { "sha256": base64::encode(digest) } });
+        let request = Request::post(format!("{}/{}:asymmetricSign", GCP_KMS_ENDPOINT, name))
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string()))
+            .map_err(|_| CryptoError::HSMError)?;
+        let response = Self::request_json(client, request).await?;
+        let signature = response["signature"]
+            .as_str()
+            .ok_or(CryptoError::HSMError)?;
+        base64::decode(signature).map_err(|_| CryptoError::ParseError)
+    }
+
+    /// Sign the messages asynchronously. This submits multiple requests to KMS in parallel
+    /// one for each message.
+    async fn sign_async(
+        self: &Self,
+        messages: Vec<&[u8]>,
+        key_id: &KeyId,
+    ) -> Result<Vec<Result<SignatureBytes, CryptoError>>, CryptoError> {
+        let client = Self::client();
+        let name = Self::key_name(key_id)?;
+        let token = Self::get_access_token(&client).await?;
+        let hashes: Vec<HashedMessageBytes> = messages
+            .iter()
+            .map(|message| eth_keccak_for_signature(message, false))
+            .collect();
+
+        // launch all of the tasks
+        let (public_key, signatures) = futures::join!(
+            Self::get_public_key_async(&client, &token, key_id),
+            join_all(
+                hashes
+                    .iter()
+                    .map(|hashed| Self::sign_digest(&client, &token, name, *hashed))
+            )
+        );
+        let public_key = public_key?;
+
+        Ok(signatures
+            .into_iter()
+            .zip(hashes)
+            .map(|(signature, hashed)| der_signature_to_eth(&signature?, &public_key, hashed))
+            .collect())
+    }
+}
+
+/// Decode the DER from a PEM-encoded key.
+fn pem_to_der(pem: &str) -> Result<Vec<u8>, CryptoError> {
+    let encoded: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    base64::decode(encoded).map_err(|_| CryptoError::ParseError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eth_recover, public_key_bytes_to_eth_address};
+
+    /// The DER prefix of a SubjectPublicKeyInfo for an uncompressed secp256k1 public key.
+    const SECP256K1_SPKI_PREFIX: &str = "3056301006072a8648ce3d020106052b8104000a034200";
+
+    fn get_test_setup() -> (GcpKmsKeyring, KeyId) {
+        let keyring = GcpKmsKeyring::new();
+        let key_id = KeyId::from(
+            "gcpkms://projects/gateway/locations/global/keyRings/gateway/cryptoKeys/eth/cryptoKeyVersions/1",
+        );
+
+        (keyring, key_id)
+    }
+
+    #[test]
+    fn test_key_name() {
+        let (_keyring, key_id) = get_test_setup();
+        assert_eq!(
+            GcpKmsKeyring::key_name(&key_id),
+            Ok("projects/gateway/locations/global/keyRings/gateway/cryptoKeys/eth/cryptoKeyVersions/1")
+        );
+        assert_eq!(
+            GcpKmsKeyring::key_name(&KeyId::from("my_eth_key_id")),
+            Err(CryptoError::InvalidKeyId)
+        );
+    }
+
+    #[test]
+    fn test_pem_public_key() {
+        let keyring = crate::dev_keyring();
+        let key_id = KeyId::from(crate::ETH_KEY_ID_ENV_VAR_DEV_DEFAULT);
+        let public_key = keyring.get_public_key(&key_id).unwrap();
+
+        let der = [
+            hex::decode(SECP256K1_SPKI_PREFIX).unwrap(),
+            vec![0x04],
+            public_key.to_vec(),
+        ]
+        .concat();
+        let encoded = base64::encode(der);
+        let pem = format!(
+            "-----BEGIN PUBLIC KEY-----\n{}\n{}\n-----END PUBLIC KEY-----\n",
+            &encoded[..64],
+            &encoded[64..]
+        );
+
+        let parsed = der_public_key_to_raw(&pem_to_der(&pem).unwrap()).unwrap();
+        assert_eq!(parsed.to_vec(), public_key.to_vec());
+    }
+
+    #[test]
+    #[ignore]
+    fn test_get_public_key() {
+        let (keyring, key_id) = get_test_setup();
+        let pk = keyring.get_public_key(&key_id).unwrap();
+        assert!(pk.len() > 0);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_sign() {
+        let (keyring, key_id) = get_test_setup();
+        let message: Vec<u8> = "hello".into();
+        let messages: Vec<&[u8]> = vec![&message];
+
+        let mut result = keyring.sign(messages, &key_id).unwrap();
+        assert_eq!(result.len(), 1);
+        let sig = result.drain(..).next().unwrap().unwrap();
+
+        // to verify, check that the address matches when you run recover
+        let expected_address = eth_recover(&message, &sig, false).unwrap();
+        let actual_public_key = keyring.get_public_key(&key_id).unwrap();
+        let actual_address = public_key_bytes_to_eth_address(&actual_public_key);
+        assert_eq!(expected_address, actual_address, "address mismatch");
+    }
+}
@@ -7,6 +7,14 @@ mod aws_kms;
 #[cfg(feature = "std")]
 pub use crate::aws_kms::*;
 #[cfg(feature = "std")]
+mod gcp_kms;
+#[cfg(feature = "std")]
+pub use crate::gcp_kms::*;
+#[cfg(feature = "std")]
+mod pkcs11_keyring;
+#[cfg(feature = "std")]
+pub use crate::pkcs11_keyring::*;
+#[cfg(feature = "std")]
 mod dev;
 #[cfg(feature = "std")]
 pub use crate::dev::*;
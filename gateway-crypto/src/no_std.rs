@@ -16,6 +16,10 @@ pub type TaggedPublicKeyBytes = [u8; 65];
 
 pub type HashedMessageBytes = [u8; 32];
 
+pub type Ed25519SignatureBytes = [u8; 64];
+
+pub type Ed25519PublicKeyBytes = [u8; 32];
+
 /// The crypto error type allows for various failure scenarios
 ///
 /// * The key id provided is unknown
@@ -39,6 +43,9 @@ pub enum CryptoError {
 /// The default key id for the eth authority key (l1)
 pub const ETH_KEY_ID_ENV_VAR_DEV_DEFAULT: &str = "my_eth_key_id";
 
+/// The default key id for the ed25519 authority key, for chains verifying Ed25519 signatures
+pub const ED25519_KEY_ID_ENV_VAR_DEV_DEFAULT: &str = "my_ed25519_key_id";
+
 /// For compatibility this is required.
 pub const ETH_MESSAGE_PREAMBLE: &[u8] = "\x19Ethereum Signed Message:\n".as_bytes();
 
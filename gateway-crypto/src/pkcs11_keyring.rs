@@ -0,0 +1,288 @@
+use crate::std::*;
+use crate::{
+    eth_keccak_for_signature, tagged_public_key_slice_to_raw, CryptoError, PublicKeyBytes,
+    SignatureBytes,
+};
+use pkcs11::types::{
+    CKA_CLASS, CKA_EC_POINT, CKA_LABEL, CKF_SERIAL_SESSION, CKM_ECDSA, CKO_PRIVATE_KEY,
+    CKO_PUBLIC_KEY, CKU_USER, CK_ATTRIBUTE, CK_MECHANISM, CK_OBJECT_CLASS, CK_OBJECT_HANDLE,
+    CK_SESSION_HANDLE, CK_SLOT_ID,
+};
+use pkcs11::Ctx;
+use secp256k1::util::{FULL_PUBLIC_KEY_SIZE, TAG_PUBKEY_FULL};
+use secp256k1::Signature;
+
+/// The URI scheme of key ids for keys held on a PKCS#11 token.
+pub const PKCS11_SCHEME: &str = "pkcs11";
+
+/// The DER header of an OCTET STRING holding an uncompressed public key.
+const EC_POINT_DER_HEADER: [u8; 2] = [0x04, FULL_PUBLIC_KEY_SIZE as u8];
+
+/// How to reach the token holding the keys, through the PKCS#11 module of the device.
+#[derive(Clone)]
+pub struct Pkcs11Config {
+    /// Path to the PKCS#11 module of the device, e.g. /usr/lib/softhsm/libsofthsm2.so
+    pub module: String,
+    /// The slot the token is in.
+    pub slot: u64,
+    /// The user PIN to log in to the token with.
+    pub pin: String,
+}
+
+/// Store your keys on an HSM through its PKCS#11 interface, such as a YubiHSM2 or a Thales Luna,
+/// or on SoftHSM for testing. The key must be a secp256k1 EC key pair, generated on the device,
+/// whose private and public key objects share a label.
+///
+/// A key is identified by a PKCS#11 URI (RFC 7512) giving the label of its objects, e.g.
+/// pkcs11:object=gateway-eth-key
+pub struct Pkcs11Keyring {
+    config: Pkcs11Config,
+}
+
+/// A session logged in to the token, closed when dropped.
+struct Pkcs11Session {
+    ctx: Ctx,
+    session: CK_SESSION_HANDLE,
+}
+
+impl Drop for Pkcs11Session {
+    fn drop(&mut self) {
+        let _ = self.ctx.close_session(self.session);
+    }
+}
+
+impl Keyring for Pkcs11Keyring {
+    /// Sign messages on the token, one after another within a single session.
+    fn sign(
+        self: &Self,
+        messages: Vec<&[u8]>,
+        key_id: &KeyId,
+    ) -> Result<Vec<Result<SignatureBytes, CryptoError>>, CryptoError> {
+        let label = Self::key_label(key_id)?;
+        let session = self.open_session()?;
+        let public_key = session.get_public_key(&label)?;
+        let private_key = session.find_object(CKO_PRIVATE_KEY, &label)?;
+
+        Ok(messages
+            .iter()
+            .map(|message| {
+                let hashed = eth_keccak_for_signature(message, false);
+                let raw = session.sign(private_key, &hashed)?;
+                let sig = Signature::parse_slice(&raw).map_err(|_| CryptoError::ParseError)?;
+                signature_to_eth(sig, &public_key, hashed)
+            })
+            .collect())
+    }
+
+    fn sign_one(self: &Self, message: &[u8], key_id: &KeyId) -> Result<[u8; 65], CryptoError> {
+        // we will use the batch interface
+        self.sign(vec![message], key_id)?
+            .drain(..)
+            .next()
+            .ok_or(CryptoError::Unknown)?
+    }
+
+    /// Get the public key corresponding to the provided key ID.
+    fn get_public_key(self: &Self, key_id: &KeyId) -> Result<PublicKeyBytes, CryptoError> {
+        let label = Self::key_label(key_id)?;
+        self.open_session()?.get_public_key(&label)
+    }
+}
+
+impl Pkcs11Keyring {
+    /// Create a new PKCS#11 keyring, for the token given by the config.
+    /// The module is only loaded, and the token logged in to, when a key is used.
+    pub fn new(config: Pkcs11Config) -> Pkcs11Keyring {
+        Pkcs11Keyring { config }
+    }
+
+    /// Whether the key id is a PKCS#11 URI.
+    pub fn is_pkcs11_key_id(key_id: &KeyId) -> bool {
+        let uri: String = key_id.into();
+        uri.starts_with(&format!("{}:", PKCS11_SCHEME))
+    }
+
+    /// Get the label of the key objects from the `object` attribute of the key id.
+    fn key_label(key_id: &KeyId) -> Result<String, CryptoError> {
+        let uri: String = key_id.into();
+        let path = uri
+            .strip_prefix(&format!("{}:", PKCS11_SCHEME))
+            .ok_or(CryptoError::InvalidKeyId)?;
+        path.split('?')
+            .next()
+            .unwrap_or("")
+            .split(';')
+            .find_map(|attribute| attribute.strip_prefix("object="))
+            .filter(|label| label.len() > 0)
+            .map(String::from)
+            .ok_or(CryptoError::InvalidKeyId)
+    }
+
+    /// Load the module and log in to the token.
+    fn open_session(self: &Self) -> Result<Pkcs11Session, CryptoError> {
+        let ctx =
+            Ctx::new_and_initialize(&self.config.module).map_err(|_| CryptoError::HSMError)?;
+        let session = ctx
+            .open_session(
+                self.config.slot as CK_SLOT_ID,
+                CKF_SERIAL_SESSION,
+                None,
+                None,
+            )
+            .map_err(|_| CryptoError::HSMError)?;
+        let session = Pkcs11Session { ctx, session };
+        session
+            .ctx
+            .login(session.session, CKU_USER, Some(&self.config.pin))
+            .map_err(|_| CryptoError::HSMError)?;
+        Ok(session)
+    }
+}
+
+impl Pkcs11Session {
+    /// Find the key object of the given class with the given label.
+    fn find_object(
+        self: &Self,
+        class: CK_OBJECT_CLASS,
+        label: &String,
+    ) -> Result<CK_OBJECT_HANDLE, CryptoError> {
+        let template = vec![
+            CK_ATTRIBUTE::new(CKA_CLASS).with_ck_ulong(&class),
+            CK_ATTRIBUTE::new(CKA_LABEL).with_string(label),
+        ];
+        self.ctx
+            .find_objects_init(self.session, &template)
+            .map_err(|_| CryptoError::HSMError)?;
+        let objects = self.ctx.find_objects(self.session, 1);
+        self.ctx
+            .find_objects_final(self.session)
+            .map_err(|_| CryptoError::HSMError)?;
+        objects
+            .map_err(|_| CryptoError::HSMError)?
+            .first()
+            .copied()
+            .ok_or(CryptoError::KeyNotFound)
+    }
+
+    /// Get the public key with the given label.
+    fn get_public_key(self: &Self, label: &String) -> Result<PublicKeyBytes, CryptoError> {
+        let key = self.find_object(CKO_PUBLIC_KEY, label)?;
+
+        // ask for the length of the point first, then for the point itself
+        let mut template = vec![CK_ATTRIBUTE::new(CKA_EC_POINT)];
+        let (_, attributes) = self
+            .ctx
+            .get_attribute_value(self.session, key, &mut template)
+            .map_err(|_| CryptoError::HSMError)?;
+        let ec_point = vec![0u8; attributes[0].ulValueLen as usize];
+        let mut template = vec![CK_ATTRIBUTE::new(CKA_EC_POINT).with_bytes(&ec_point)];
+        self.ctx
+            .get_attribute_value(self.session, key, &mut template)
+            .map_err(|_| CryptoError::HSMError)?;
+
+        ec_point_to_raw(&ec_point)
+    }
+
+    /// Sign a digest with the private key, returning the signature as r and s.
+    fn sign(self: &Self, key: CK_OBJECT_HANDLE, digest: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mechanism = CK_MECHANISM {
+            mechanism: CKM_ECDSA,
+            pParameter: std::ptr::null_mut(),
+            ulParameterLen: 0,
+        };
+        self.ctx
+            .sign_init(self.session, &mechanism, key)
+            .map_err(|_| CryptoError::HSMError)?;
+        self.ctx
+            .sign(self.session, digest)
+            .map_err(|_| CryptoError::HSMError)
+    }
+}
+
+/// Convert the EC point of a public key object to raw public key bytes.
+/// Tokens should DER-encode the point as an OCTET STRING, but some give the bare point.
+fn ec_point_to_raw(ec_point: &[u8]) -> Result<PublicKeyBytes, CryptoError> {
+    let point =
+        if ec_point.len() == FULL_PUBLIC_KEY_SIZE + 2 && ec_point[..2] == EC_POINT_DER_HEADER {
+            &ec_point[2..]
+        } else {
+            ec_point
+        };
+    if point.len() != FULL_PUBLIC_KEY_SIZE || point[0] != TAG_PUBKEY_FULL {
+        return Err(CryptoError::ParseError);
+    }
+
+    Ok(tagged_public_key_slice_to_raw(point)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eth_recover, public_key_bytes_to_eth_address};
+
+    fn get_test_setup() -> (Pkcs11Keyring, KeyId) {
+        let keyring = Pkcs11Keyring::new(Pkcs11Config {
+            module: "/usr/lib/softhsm/libsofthsm2.so".into(),
+            slot: 0,
+            pin: "1234".into(),
+        });
+        let key_id = KeyId::from("pkcs11:token=gateway;object=gateway-eth-key");
+
+        (keyring, key_id)
+    }
+
+    #[test]
+    fn test_key_label() {
+        let (_keyring, key_id) = get_test_setup();
+        assert!(Pkcs11Keyring::is_pkcs11_key_id(&key_id));
+        assert_eq!(
+            Pkcs11Keyring::key_label(&key_id),
+            Ok("gateway-eth-key".into())
+        );
+        assert_eq!(
+            Pkcs11Keyring::key_label(&KeyId::from("pkcs11:object=eth?pin-value=1234")),
+            Ok("eth".into())
+        );
+        assert_eq!(
+            Pkcs11Keyring::key_label(&KeyId::from("pkcs11:token=gateway")),
+            Err(CryptoError::InvalidKeyId)
+        );
+        assert!(!Pkcs11Keyring::is_pkcs11_key_id(&KeyId::from(
+            "my_eth_key_id"
+        )));
+    }
+
+    #[test]
+    fn test_ec_point_to_raw() {
+        let keyring = crate::dev_keyring();
+        let key_id = KeyId::from(crate::ETH_KEY_ID_ENV_VAR_DEV_DEFAULT);
+        let public_key = keyring.get_public_key(&key_id).unwrap();
+        let point = [vec![TAG_PUBKEY_FULL], public_key.to_vec()].concat();
+        let der = [EC_POINT_DER_HEADER.to_vec(), point.clone()].concat();
+
+        assert_eq!(ec_point_to_raw(&der).unwrap().to_vec(), public_key.to_vec());
+        assert_eq!(
+            ec_point_to_raw(&point).unwrap().to_vec(),
+            public_key.to_vec()
+        );
+        assert_eq!(
+            ec_point_to_raw(&der[1..]).map(|_| ()),
+            Err(CryptoError::ParseError)
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_sign() {
+        let (keyring, key_id) = get_test_setup();
+        let message: Vec<u8> = "hello".into();
+
+        let sig = keyring.sign_one(&message, &key_id).unwrap();
+
+        // to verify, check that the address matches when you run recover
+        let expected_address = eth_recover(&message, &sig, false).unwrap();
+        let actual_public_key = keyring.get_public_key(&key_id).unwrap();
+        let actual_address = public_key_bytes_to_eth_address(&actual_public_key);
+        assert_eq!(expected_address, actual_address, "address mismatch");
+    }
+}
@@ -1,8 +1,17 @@
 use crate::aws_kms;
 use crate::dev_keyring;
+use crate::gcp_kms;
 use crate::no_std::*;
-use secp256k1::SecretKey;
+use crate::pkcs11_keyring;
+use der_parser::parse_der;
+use our_std::convert::TryInto;
+use secp256k1::util::{FULL_PUBLIC_KEY_SIZE, TAG_PUBKEY_FULL};
+use secp256k1::{PublicKey, PublicKeyFormat, RecoveryId, SecretKey, Signature};
 use sp_core::ecdsa::Pair as EcdsaPair;
+use sp_core::ed25519::{
+    Pair as Ed25519Pair, Public as Ed25519Public, Signature as Ed25519Signature,
+};
+use sp_core::Pair;
 use std::collections::hash_map::HashMap;
 
 /// The crypto module for gateway.
@@ -54,6 +63,11 @@ impl KeyId {
         let data = String::from_utf8(source).map_err(|_| CryptoError::InvalidKeyId)?;
         Ok(KeyId { data })
     }
+
+    /// Get the rest of the key id after the given URI scheme, if the key id has the scheme.
+    pub fn strip_scheme(self: &Self, scheme: &str) -> Option<&str> {
+        self.data.strip_prefix(scheme)?.strip_prefix("://")
+    }
 }
 
 /// A keyring abstraction for HSMs
@@ -83,6 +97,38 @@ pub trait Keyring {
     /// Get the public key data for the key id provided.
     /// Fails whenever the key_id is not found in the keyring.
     fn get_public_key(self: &Self, key_id: &KeyId) -> Result<PublicKeyBytes, CryptoError>;
+
+    /// Batch sign messages with the given Ed25519 key, for chains verifying Ed25519 signatures.
+    ///
+    /// Unlike secp256k1 signatures, messages are signed as they are, without hashing them first.
+    /// Keyrings holding no Ed25519 keys need not implement this.
+    fn sign_ed25519(
+        self: &Self,
+        _messages: Vec<&[u8]>,
+        _key_id: &KeyId,
+    ) -> Result<Vec<Result<Ed25519SignatureBytes, CryptoError>>, CryptoError> {
+        Err(CryptoError::KeyNotFound)
+    }
+
+    fn sign_ed25519_one(
+        self: &Self,
+        message: &[u8],
+        key_id: &KeyId,
+    ) -> Result<Ed25519SignatureBytes, CryptoError> {
+        self.sign_ed25519(vec![message], key_id)?
+            .drain(..)
+            .next()
+            .ok_or(CryptoError::Unknown)?
+    }
+
+    /// Get the Ed25519 public key for the key id provided.
+    /// Fails whenever the key_id is not found in the keyring.
+    fn get_ed25519_public_key(
+        self: &Self,
+        _key_id: &KeyId,
+    ) -> Result<Ed25519PublicKeyBytes, CryptoError> {
+        Err(CryptoError::KeyNotFound)
+    }
 }
 
 pub(crate) fn combine_sig_and_recovery(
@@ -118,8 +164,10 @@ fn eth_sign(message: &[u8], private_key: &SecretKey, prepend_preamble: bool) ->
 
 /// In memory keyring
 pub struct InMemoryKeyring {
-    /// for now only support ECDSA with curve secp256k1
+    /// ECDSA keys with curve secp256k1
     keys: HashMap<String, EcdsaPair>,
+    /// EdDSA keys with curve 25519
+    ed25519_keys: HashMap<String, Ed25519Pair>,
 }
 
 /// The in memory keyring is designed for use in development and not encouraged for use in
@@ -129,6 +177,7 @@ impl InMemoryKeyring {
     pub fn new() -> InMemoryKeyring {
         InMemoryKeyring {
             keys: HashMap::new(),
+            ed25519_keys: HashMap::new(),
         }
     }
 
@@ -141,6 +190,18 @@ impl InMemoryKeyring {
         self.keys.insert(key_id.data.clone(), pair);
     }
 
+    /// Add an Ed25519 key to the keyring with the given key id
+    pub fn add_ed25519(self: &mut Self, key_id: &KeyId, pair: Ed25519Pair) {
+        self.ed25519_keys.insert(key_id.data.clone(), pair);
+    }
+
+    /// Get the Ed25519 keypair associated with the given key id
+    pub fn get_ed25519_keypair(self: &Self, key_id: &KeyId) -> Result<&Ed25519Pair, CryptoError> {
+        self.ed25519_keys
+            .get(&key_id.data)
+            .ok_or(CryptoError::KeyNotFound)
+    }
+
     /// Get the keypair associated with the given key id
     pub fn get_keypair(self: &Self, key_id: &KeyId) -> Result<&EcdsaPair, CryptoError> {
         self.keys.get(&key_id.data).ok_or(CryptoError::KeyNotFound)
@@ -196,19 +257,198 @@ impl Keyring for InMemoryKeyring {
         let public = secp256k1::PublicKey::from_secret_key(&private);
         Ok(public_key_to_bytes(public))
     }
+
+    /// Sign the messages with the given Ed25519 Key ID
+    fn sign_ed25519(
+        self: &Self,
+        messages: Vec<&[u8]>,
+        key_id: &KeyId,
+    ) -> Result<Vec<Result<Ed25519SignatureBytes, CryptoError>>, CryptoError> {
+        let pair = self.get_ed25519_keypair(key_id)?;
+        Ok(messages
+            .iter()
+            .map(|message| Ok(pair.sign(message).0))
+            .collect())
+    }
+
+    /// Get the Ed25519 public key associated with the given key id.
+    fn get_ed25519_public_key(
+        self: &Self,
+        key_id: &KeyId,
+    ) -> Result<Ed25519PublicKeyBytes, CryptoError> {
+        Ok(self.get_ed25519_keypair(key_id)?.public().0)
+    }
 }
 
-pub fn keyring() -> Box<dyn Keyring> {
+/// Get the default keyring, as selected by the KEYRING_TYPE environment variable.
+fn default_keyring() -> Box<dyn Keyring> {
     let keyring_type: Option<String> = std::env::var("KEYRING_TYPE").ok().into();
     let aws_kms = String::from("AWS_KMS");
+    let gcp_kms = String::from("GCP_KMS");
 
     if keyring_type == Some(aws_kms) {
         Box::new(aws_kms::KmsKeyring::new())
+    } else if keyring_type == Some(gcp_kms) {
+        Box::new(gcp_kms::GcpKmsKeyring::new())
     } else {
         Box::new(dev_keyring())
     }
 }
 
+/// A keyring which routes each key to the keyring holding it, by the URI scheme of its key id.
+/// Key ids in the `gcpkms://` scheme are held in Google Cloud KMS, those in the `pkcs11:` scheme
+/// on the configured PKCS#11 token, and any others in the default keyring.
+pub struct SchemeKeyring {
+    default: Box<dyn Keyring>,
+    gcp_kms: gcp_kms::GcpKmsKeyring,
+    pkcs11: Option<pkcs11_keyring::Pkcs11Keyring>,
+}
+
+impl SchemeKeyring {
+    fn route(self: &Self, key_id: &KeyId) -> Result<&dyn Keyring, CryptoError> {
+        if key_id.strip_scheme(gcp_kms::GCP_KMS_SCHEME).is_some() {
+            Ok(&self.gcp_kms)
+        } else if pkcs11_keyring::Pkcs11Keyring::is_pkcs11_key_id(key_id) {
+            match &self.pkcs11 {
+                Some(keyring) => Ok(keyring),
+                None => Err(CryptoError::KeyNotFound),
+            }
+        } else {
+            Ok(self.default.as_ref())
+        }
+    }
+}
+
+impl Keyring for SchemeKeyring {
+    fn sign(
+        self: &Self,
+        messages: Vec<&[u8]>,
+        key_id: &KeyId,
+    ) -> Result<Vec<Result<SignatureBytes, CryptoError>>, CryptoError> {
+        self.route(key_id)?.sign(messages, key_id)
+    }
+
+    fn sign_one(self: &Self, message: &[u8], key_id: &KeyId) -> Result<[u8; 65], CryptoError> {
+        self.route(key_id)?.sign_one(message, key_id)
+    }
+
+    fn get_public_key(self: &Self, key_id: &KeyId) -> Result<PublicKeyBytes, CryptoError> {
+        self.route(key_id)?.get_public_key(key_id)
+    }
+
+    fn sign_ed25519(
+        self: &Self,
+        messages: Vec<&[u8]>,
+        key_id: &KeyId,
+    ) -> Result<Vec<Result<Ed25519SignatureBytes, CryptoError>>, CryptoError> {
+        self.route(key_id)?.sign_ed25519(messages, key_id)
+    }
+
+    fn get_ed25519_public_key(
+        self: &Self,
+        key_id: &KeyId,
+    ) -> Result<Ed25519PublicKeyBytes, CryptoError> {
+        self.route(key_id)?.get_ed25519_public_key(key_id)
+    }
+}
+
+pub fn keyring() -> Box<dyn Keyring> {
+    keyring_with_pkcs11(None)
+}
+
+/// Get the keyring, able to sign with keys on the PKCS#11 token given by the config.
+pub fn keyring_with_pkcs11(config: Option<pkcs11_keyring::Pkcs11Config>) -> Box<dyn Keyring> {
+    Box::new(SchemeKeyring {
+        default: default_keyring(),
+        gcp_kms: gcp_kms::GcpKmsKeyring::new(),
+        pkcs11: config.map(pkcs11_keyring::Pkcs11Keyring::new),
+    })
+}
+
+/// Convert a DER-encoded SubjectPublicKeyInfo, as returned by HSMs, to raw public key bytes.
+pub(crate) fn der_public_key_to_raw(der: &[u8]) -> Result<PublicKeyBytes, CryptoError> {
+    let (_, decoded) = parse_der(der).map_err(|_| CryptoError::ParseError)?;
+    let sequence = decoded.as_sequence().map_err(|_| CryptoError::ParseError)?;
+    if sequence.len() != 2 {
+        return Err(CryptoError::ParseError);
+    }
+    let actual_public_key = sequence[1]
+        .content
+        .as_slice()
+        .map_err(|_| CryptoError::ParseError)?;
+    if actual_public_key.len() != FULL_PUBLIC_KEY_SIZE {
+        return Err(CryptoError::ParseError);
+    }
+    // strip first byte due to https://tools.ietf.org/html/rfc5480#section-2.2 indicating "uncompressed"
+    // this could be considered "RAW" format
+    if actual_public_key[0] != TAG_PUBKEY_FULL {
+        return Err(CryptoError::ParseError);
+    }
+
+    Ok(tagged_public_key_slice_to_raw(actual_public_key)?)
+}
+
+/// Convert a DER-encoded SubjectPublicKeyInfo of an Ed25519 key to raw public key bytes.
+pub(crate) fn der_ed25519_public_key_to_raw(
+    der: &[u8],
+) -> Result<Ed25519PublicKeyBytes, CryptoError> {
+    let (_, decoded) = parse_der(der).map_err(|_| CryptoError::ParseError)?;
+    let sequence = decoded.as_sequence().map_err(|_| CryptoError::ParseError)?;
+    if sequence.len() != 2 {
+        return Err(CryptoError::ParseError);
+    }
+    let actual_public_key = sequence[1]
+        .content
+        .as_slice()
+        .map_err(|_| CryptoError::ParseError)?;
+
+    actual_public_key
+        .try_into()
+        .map_err(|_| CryptoError::ParseError)
+}
+
+/// Convert a DER-encoded signature of a digest, as returned by HSMs, to our conventions.
+pub(crate) fn der_signature_to_eth(
+    der: &[u8],
+    public_key: &[u8],
+    digested: HashedMessageBytes,
+) -> Result<SignatureBytes, CryptoError> {
+    let sig = Signature::parse_der(der).map_err(|_| CryptoError::ParseError)?;
+    signature_to_eth(sig, public_key, digested)
+}
+
+/// Convert a signature of a digest, given as r and s, to our conventions.
+pub(crate) fn signature_to_eth(
+    mut sig: Signature,
+    public_key: &[u8],
+    digested: HashedMessageBytes,
+) -> Result<SignatureBytes, CryptoError> {
+    // Because of EIP-2 not all elliptic curve signatures are accepted
+    // the value of s needs to be SMALLER than half of the curve
+    // i.e. we need to flip s if it's greater than half of the curve
+    if sig.s.is_high() {
+        sig.s = -sig.s;
+    }
+
+    // find the recovery id by guessing
+    let public_key = PublicKey::parse_slice(public_key, Some(PublicKeyFormat::Raw))
+        .map_err(|_| CryptoError::ParseError)?;
+    let message =
+        secp256k1::Message::parse_slice(&digested).map_err(|_| CryptoError::ParseError)?;
+    let mut recovery_id = RecoveryId::parse(0).map_err(|_| CryptoError::ParseError)?;
+    let recovered =
+        secp256k1::recover(&message, &sig, &recovery_id).map_err(|_| CryptoError::RecoverError)?;
+    if recovered != public_key {
+        recovery_id = RecoveryId::parse(1).map_err(|_| CryptoError::ParseError)?
+    }
+
+    // combine signature with recovery ID so we can recover the public key of the signer later
+    Ok(combine_sig_and_recovery(
+        sig.serialize(),
+        recovery_id.serialize() + ETH_ADD_TO_V,
+    ))
+}
+
 pub(crate) const ETH_PRIVATE_KEY_ENV_VAR: &str = "ETH_KEY";
 
 /// Get the recovery id and chain from the last byte of the signature
@@ -221,6 +461,19 @@ fn eth_get_chain(recovery_id: u8) -> Result<(u8, Option<u8>), CryptoError> {
     }
 }
 
+/// Verify an Ed25519 signature of the given message by the given public key.
+pub fn ed25519_verify(
+    message: &[u8],
+    sig: &Ed25519SignatureBytes,
+    public_key: &Ed25519PublicKeyBytes,
+) -> bool {
+    Ed25519Pair::verify(
+        &Ed25519Signature(*sig),
+        message,
+        &Ed25519Public(*public_key),
+    )
+}
+
 /// Recovers the signer's address from the given signature and message. The message is _not_
 /// expected to be a digest and is hashed inside.
 pub fn eth_recover(
@@ -383,4 +636,35 @@ mod tests {
     fn test_public_key() {
         get_test_cases().drain(..).for_each(test_public_key_case);
     }
+
+    /// This test case comes from RFC 8032
+    #[test]
+    fn test_ed25519_sign() {
+        let keyring = dev_keyring();
+        let key_id = KeyId::from(ED25519_KEY_ID_ENV_VAR_DEV_DEFAULT);
+        let public_key = keyring.get_ed25519_public_key(&key_id).unwrap();
+        let signature = keyring.sign_ed25519_one(&[], &key_id).unwrap();
+
+        assert_eq!(
+            hex::encode(public_key),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        assert_eq!(
+            hex::encode(&signature[..]),
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+        assert!(ed25519_verify(&[], &signature, &public_key));
+        assert!(!ed25519_verify(&[1], &signature, &public_key));
+        assert_eq!(
+            keyring.sign_ed25519_one(&[], &KeyId::from(ETH_KEY_ID_ENV_VAR_DEV_DEFAULT)),
+            Err(CryptoError::KeyNotFound)
+        );
+
+        let der = [
+            hex::decode("302a300506032b6570032100").unwrap(),
+            public_key.to_vec(),
+        ]
+        .concat();
+        assert_eq!(der_ed25519_public_key_to_raw(&der), Ok(public_key));
+    }
 }
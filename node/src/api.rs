@@ -1,25 +1,33 @@
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use jsonrpc_core::{Error as RpcError, ErrorCode as RpcErrorCode, Result as RpcResult};
 use jsonrpc_derive::rpc;
+use sc_rpc_api::DenyUnsafe;
 
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
 use pallet_cash::{
-    chains::{ChainAccount, ChainAsset},
+    chains::{ChainAccount, ChainAsset, ChainId, ChainSignature, ChainSignatureList},
     core::BTreeMap,
+    notices::NoticeId,
     portfolio::Portfolio,
     rates::APR,
     reason::Reason,
-    types::{AssetAmount, AssetBalance, AssetInfo, InterestRateModel, Symbol, ValidatorKeys},
+    types::{
+        ActivityEntry, AssetAmount, AssetBalance, AssetInfo, CashOrChainAsset, Factor,
+        GovernanceResult, InterestRateModel, MarketSummary, SessionEarnings, SubstrateId, Symbol,
+        ValidatorKeys, ValidatorMetadata,
+    },
 };
 
+use gateway_crypto::CryptoError;
 use pallet_cash_runtime_api::CashApi as CashRuntimeApi;
-use pallet_oracle::types::AssetPrice;
+use pallet_oracle::types::{AssetPrice, Timestamp};
 
 use types_derive::{type_alias, Types};
 
@@ -46,6 +54,20 @@ pub struct ApiAssetData {
     price: String,
 }
 
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiMarketSummary {
+    asset: ChainAsset,
+    total_supply: String,
+    total_borrow: String,
+    total_value_locked: String,
+    utilization: String,
+    supply_rate: String,
+    borrow_rate: String,
+    price: String,
+    supply_cap: String,
+    liquidity_factor: String,
+}
+
 #[derive(Deserialize, Serialize, Types)]
 pub struct ApiAssetMeta {
     supplier_counts: BTreeMap<String, u32>,
@@ -65,6 +87,12 @@ pub enum ApiInterestRateModel {
     Fixed {
         rate: String,
     },
+    JumpRate {
+        base_rate: String,
+        multiplier: String,
+        jump_multiplier: String,
+        kink: String,
+    },
 }
 
 #[derive(Deserialize, Serialize, Types)]
@@ -95,6 +123,23 @@ pub struct ApiPortfolio {
     positions: Vec<(ChainAsset, String)>,
 }
 
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiLiquidatableAccount {
+    account: ChainAccount,
+    liquidity: String,
+    borrow: Option<String>,
+    collateral: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiActivityEntry {
+    block_number: u32,
+    kind: String,
+    asset: String,
+    amount: String,
+    counterparty: Option<ChainAccount>,
+}
+
 #[derive(Deserialize, Serialize, Types)]
 pub struct ApiValidators {
     current_block: String,
@@ -102,8 +147,191 @@ pub struct ApiValidators {
     miner_payouts: Vec<(ChainAccount, String)>,
 }
 
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiValidatorParticipation {
+    substrate_id: String,
+    eth_address: String,
+    blocks_attested: u32,
+    notices_signed: u32,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiSessionParticipation {
+    blocks_accepted: u32,
+    notices_dispatched: u32,
+    validators: Vec<ApiValidatorParticipation>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiValidatorMetadata {
+    substrate_id: String,
+    eth_address: String,
+    name: Option<String>,
+    website: Option<String>,
+    contact: Option<String>,
+    security_policy_url: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiSessionEarnings {
+    session_index: u32,
+    paid: String,
+    withheld: String,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiPendingNotice {
+    era_id: u32,
+    era_index: u32,
+    encoded_notice: String,
+    signatures: Vec<(String, String)>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiBalanceDelta {
+    account: ChainAccount,
+    asset: String,
+    delta: String,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiTrxRequestEstimate {
+    balance_deltas: Vec<ApiBalanceDelta>,
+    liquidity: String,
+    notices: Vec<ApiPendingNotice>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiNoticeSimulation {
+    starport: ChainAccount,
+    calldata: String,
+    gas_estimate: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiGovernanceResult {
+    success: bool,
+    error: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiKeyStatus {
+    key_id: Option<String>,
+    reachable: bool,
+    public_key: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiKeyringStatus {
+    eth_key: ApiKeyStatus,
+    eth_address: Option<String>,
+    validator: Option<String>,
+    ed25519_key: ApiKeyStatus,
+    threshold_key: ApiKeyStatus,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiChainHealth {
+    chain_id: ChainId,
+    last_processed_block: Option<u64>,
+    chain_head: Option<u64>,
+    blocks_behind: Option<u64>,
+    ingression_queue_depth: u32,
+    pending_notices: u32,
+}
+
+#[derive(Deserialize, Serialize, Types)]
+pub struct ApiHealth {
+    best_block: String,
+    best_hash: String,
+    finalized_block: String,
+    finalized_hash: String,
+    chains: Vec<ApiChainHealth>,
+}
+
+/// Checks a key configured for the validator, fetching its public key to see if the keyring is reachable.
+fn key_status<K: AsRef<[u8]>>(
+    key_id: Option<Vec<u8>>,
+    get_public_key: impl Fn(Vec<u8>) -> Result<K, CryptoError>,
+) -> (ApiKeyStatus, Option<K>) {
+    let key_id = match key_id {
+        Some(key_id) => key_id,
+        None => {
+            let status = ApiKeyStatus {
+                key_id: None,
+                reachable: false,
+                public_key: None,
+                error: None,
+            };
+            return (status, None);
+        }
+    };
+    let id = String::from_utf8_lossy(&key_id).into_owned();
+    match get_public_key(key_id) {
+        Ok(public_key) => {
+            let status = ApiKeyStatus {
+                key_id: Some(id),
+                reachable: true,
+                public_key: Some(hex::encode(&public_key)),
+                error: None,
+            };
+            (status, Some(public_key))
+        }
+        Err(err) => {
+            let status = ApiKeyStatus {
+                key_id: Some(id),
+                reachable: false,
+                public_key: None,
+                error: Some(format!("{:?}", err)),
+            };
+            (status, None)
+        }
+    }
+}
+
+/// Parses an asset given to an RPC, either "CASH" or a chain asset.
+pub fn parse_asset(asset: &str) -> Result<CashOrChainAsset, Reason> {
+    match asset {
+        "CASH" | "Cash" => Ok(CashOrChainAsset::Cash),
+        asset => Ok(CashOrChainAsset::ChainAsset(ChainAsset::from_str(asset)?)),
+    }
+}
+
+/// Formats an asset returned by an RPC, as parsed by `parse_asset`.
+pub fn api_asset(asset: CashOrChainAsset) -> String {
+    match asset {
+        CashOrChainAsset::Cash => String::from("CASH"),
+        CashOrChainAsset::ChainAsset(chain_asset) => String::from(chain_asset),
+    }
+}
+
+/// Formats the signers and signatures collected for a notice.
+pub fn api_signatures(signature_pairs: ChainSignatureList) -> Vec<(String, String)> {
+    match signature_pairs {
+        ChainSignatureList::Eth(pairs) | ChainSignatureList::Matic(pairs) => pairs
+            .iter()
+            .map(|(signer, signature)| {
+                (
+                    format!("0x{}", hex::encode(signer)),
+                    format!("0x{}", hex::encode(&signature[..])),
+                )
+            })
+            .collect(),
+        ChainSignatureList::Threshold(signer, Some(signature)) => match (signer, signature) {
+            (ChainAccount::Eth(signer), ChainSignature::Eth(signature))
+            | (ChainAccount::Matic(signer), ChainSignature::Matic(signature)) => vec![(
+                format!("0x{}", hex::encode(signer)),
+                format!("0x{}", hex::encode(&signature[..])),
+            )],
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
 /// Converts a runtime trap into an RPC error.
-fn runtime_err(err: impl std::fmt::Debug) -> RpcError {
+pub fn runtime_err(err: impl std::fmt::Debug) -> RpcError {
     RpcError {
         code: RpcErrorCode::ServerError(RUNTIME_ERROR),
         message: "Runtime trapped".into(),
@@ -112,7 +340,7 @@ fn runtime_err(err: impl std::fmt::Debug) -> RpcError {
 }
 
 /// Converts a chain failure into an RPC error.
-fn chain_err(reason: Reason) -> RpcError {
+pub fn chain_err(reason: Reason) -> RpcError {
     RpcError {
         code: RpcErrorCode::ServerError(CHAIN_ERROR),
         message: "Chain error".into(),
@@ -143,15 +371,55 @@ pub trait GatewayRpcApi<BlockHash> {
     #[rpc(name = "gateway_liquidity")]
     fn gateway_liquidity(&self, account: ChainAccount, at: Option<BlockHash>) -> RpcResult<String>;
 
+    #[rpc(name = "gateway_logicalCashBalance")]
+    fn gateway_logical_cash_balance(
+        &self,
+        account: ChainAccount,
+        at: Option<BlockHash>,
+    ) -> RpcResult<String>;
+
+    #[rpc(name = "gateway_logicalLiquidity")]
+    fn gateway_logical_liquidity(
+        &self,
+        account: ChainAccount,
+        at: Option<BlockHash>,
+    ) -> RpcResult<String>;
+
+    #[rpc(name = "gateway_healthFactor")]
+    fn gateway_health_factor(
+        &self,
+        account: ChainAccount,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<String>>;
+
+    #[rpc(name = "gateway_maxExtract")]
+    fn gateway_max_extract(
+        &self,
+        account: ChainAccount,
+        asset: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<String>;
+
     #[rpc(name = "gateway_price")]
     fn gateway_price(&self, ticker: String, at: Option<BlockHash>) -> RpcResult<String>;
 
+    #[rpc(name = "gateway_pricehistory")]
+    fn gateway_price_history(
+        &self,
+        ticker: String,
+        count: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(String, String)>>;
+
     #[rpc(name = "gateway_rates")]
     fn gateway_rates(&self, asset: ChainAsset, at: Option<BlockHash>) -> RpcResult<ApiRates>;
 
     #[rpc(name = "gateway_assets")]
     fn gateway_assets(&self, at: Option<BlockHash>) -> RpcResult<Vec<ApiAssetInfo>>;
 
+    #[rpc(name = "gateway_marketsSummary")]
+    fn gateway_markets_summary(&self, at: Option<BlockHash>) -> RpcResult<Vec<ApiMarketSummary>>;
+
     #[rpc(name = "gateway_chain_accounts")]
     fn chain_accounts(&self, at: Option<BlockHash>) -> RpcResult<Vec<ChainAccount>>;
 
@@ -161,31 +429,173 @@ pub trait GatewayRpcApi<BlockHash> {
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<(ChainAccount, String)>>;
 
+    #[rpc(name = "gateway_chainAccountsPage")]
+    fn chain_accounts_page(
+        &self,
+        start_key: Option<ChainAccount>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ChainAccount>>;
+
+    #[rpc(name = "gateway_chainAccountLiquiditiesPage")]
+    fn chain_account_liquidities_page(
+        &self,
+        start_key: Option<ChainAccount>,
+        limit: u32,
+        below: Option<String>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(ChainAccount, String)>>;
+
+    #[rpc(name = "gateway_liquidatableAccounts")]
+    fn liquidatable_accounts(
+        &self,
+        max_results: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiLiquidatableAccount>>;
+
     #[rpc(name = "gateway_chain_account_portfolio")]
+    fn liquidatable_accounts(
+        &self,
+        max_results: u32,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiLiquidatableAccount>> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let accounts = api
+            .get_liquidatable_accounts(&at, max_results)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(accounts
+            .into_iter()
+            .map(
+                |(account, liquidity, borrow, collateral)| ApiLiquidatableAccount {
+                    account,
+                    liquidity: format!("{}", liquidity),
+                    borrow: borrow.map(api_asset),
+                    collateral: collateral.map(api_asset),
+                },
+            )
+            .collect())
+    }
+
     fn chain_account_portfolio(
         &self,
         account: ChainAccount,
         at: Option<BlockHash>,
     ) -> RpcResult<ApiPortfolio>;
 
+    #[rpc(name = "gateway_chain_account_logical_portfolio")]
+    fn chain_account_logical_portfolio(
+        &self,
+        account: ChainAccount,
+        at: Option<BlockHash>,
+    ) -> RpcResult<ApiPortfolio>;
+
+    #[rpc(name = "gateway_validatorParticipation")]
+    fn validator_participation(&self, at: Option<BlockHash>) -> RpcResult<ApiSessionParticipation>;
+
+    #[rpc(name = "gateway_validatorMetadata")]
+    fn validator_metadata(&self, at: Option<BlockHash>) -> RpcResult<Vec<ApiValidatorMetadata>>;
+
+    #[rpc(name = "gateway_validatorEarnings")]
+    fn validator_earnings(
+        &self,
+        substrate_id: SubstrateId,
+        last_n_sessions: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiSessionEarnings>>;
+
+    #[rpc(name = "gateway_accountHistory")]
+    fn account_history(
+        &self,
+        account: ChainAccount,
+        from: u32,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiActivityEntry>>;
+
     #[rpc(name = "gateway_validators")]
     fn validators(&self, at: Option<BlockHash>) -> RpcResult<ApiValidators>;
+
+    #[rpc(name = "gateway_pendingNotices")]
+    fn pending_notices(
+        &self,
+        account: ChainAccount,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiPendingNotice>>;
+
+    #[rpc(name = "gateway_simulateNotice")]
+    fn simulate_notice(
+        &self,
+        chain_id: ChainId,
+        era_id: u32,
+        era_index: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<ApiNoticeSimulation>;
+
+    #[rpc(name = "gateway_estimateTrxRequest")]
+    fn estimate_trx_request(
+        &self,
+        request: String,
+        account: ChainAccount,
+        at: Option<BlockHash>,
+    ) -> RpcResult<ApiTrxRequestEstimate>;
+
+    #[rpc(name = "gateway_simulateGovernance")]
+    fn simulate_governance(
+        &self,
+        actions: Vec<String>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ApiGovernanceResult>>;
+
+    #[rpc(name = "gateway_keyringStatus")]
+    fn keyring_status(&self, at: Option<BlockHash>) -> RpcResult<ApiKeyringStatus>;
+
+    #[rpc(name = "gateway_health")]
+    fn health(&self) -> RpcResult<ApiHealth>;
+
+    #[rpc(name = "gateway_reloadConfig")]
+    fn reload_config(&self) -> RpcResult<Vec<String>>;
 }
 
 pub struct GatewayRpcHandler<C, B> {
     client: Arc<C>,
+    deny_unsafe: DenyUnsafe,
     _block: PhantomData<B>,
 }
 
 impl<C, B> GatewayRpcHandler<C, B> {
-    pub fn new(client: Arc<C>) -> Self {
+    pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
         Self {
             client,
+            deny_unsafe,
             _block: Default::default(),
         }
     }
 }
 
+impl<C, B> GatewayRpcHandler<C, B>
+where
+    B: BlockT,
+    C: HeaderBackend<B>,
+{
+    /// The block to query state at, either the given block or the best block.
+    /// Queries at older blocks need the node to keep their state, e.g. by running with `--pruning archive`.
+    fn block_at(&self, at: Option<B::Hash>) -> RpcResult<BlockId<B>> {
+        match at {
+            Some(hash) => match self.client.number(hash) {
+                Ok(Some(_)) => Ok(BlockId::hash(hash)),
+                _ => Err(RpcError::invalid_params(format!(
+                    "Unknown block: {:?}",
+                    hash
+                ))),
+            },
+            None => Ok(BlockId::hash(self.client.info().best_hash)),
+        }
+    }
+}
+
 impl<C, B> GatewayRpcApi<B::Hash> for GatewayRpcHandler<C, B>
 where
     B: BlockT,
@@ -199,7 +609,7 @@ where
         at: Option<<B as BlockT>::Hash>,
     ) -> RpcResult<ApiAssetData> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let asset_info: AssetInfo = api
             .get_asset(&at, asset)
             .map_err(runtime_err)?
@@ -243,7 +653,7 @@ where
         at: Option<<B as BlockT>::Hash>,
     ) -> RpcResult<ApiCashData> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let balance: AssetBalance = api
             .get_full_cash_balance(&at, account)
             .map_err(runtime_err)?
@@ -279,7 +689,7 @@ where
         at: Option<<B as BlockT>::Hash>,
     ) -> RpcResult<String> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let result: AssetBalance = api
             .get_liquidity(&at, account)
             .map_err(runtime_err)?
@@ -287,9 +697,67 @@ where
         Ok(format!("{}", result))
     }
 
+    fn gateway_logical_cash_balance(
+        &self,
+        account: ChainAccount,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<String> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let result: AssetBalance = api
+            .get_logical_cash_balance(&at, account)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(format!("{}", result))
+    }
+
+    fn gateway_logical_liquidity(
+        &self,
+        account: ChainAccount,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<String> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let result: AssetBalance = api
+            .get_logical_liquidity(&at, account)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(format!("{}", result))
+    }
+
+    fn gateway_health_factor(
+        &self,
+        account: ChainAccount,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Option<String>> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let result: Option<Factor> = api
+            .get_health_factor(&at, account)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(result.map(|factor| format!("{}", factor.0)))
+    }
+
+    fn gateway_max_extract(
+        &self,
+        account: ChainAccount,
+        asset: String,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<String> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let asset = parse_asset(&asset).map_err(chain_err)?;
+        let result: AssetAmount = api
+            .get_max_extract(&at, account, asset)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(format!("{}", result))
+    }
+
     fn gateway_price(&self, ticker: String, at: Option<<B as BlockT>::Hash>) -> RpcResult<String> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let result: AssetPrice = api
             .get_price(&at, ticker)
             .map_err(runtime_err)?
@@ -297,13 +765,31 @@ where
         Ok(format!("{}", result))
     }
 
+    fn gateway_price_history(
+        &self,
+        ticker: String,
+        count: u32,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(String, String)>> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let result: Vec<(Timestamp, AssetPrice)> = api
+            .get_price_history(&at, ticker, count)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(result
+            .into_iter()
+            .map(|(timestamp, price)| (format!("{}", timestamp), format!("{}", price)))
+            .collect())
+    }
+
     fn gateway_rates(
         &self,
         asset: ChainAsset,
         at: Option<<B as BlockT>::Hash>,
     ) -> RpcResult<ApiRates> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let (borrow_rate, supply_rate): (APR, APR) = api
             .get_rates(&at, asset)
             .map_err(runtime_err)?
@@ -313,7 +799,7 @@ where
 
     fn gateway_assets(&self, at: Option<<B as BlockT>::Hash>) -> RpcResult<Vec<ApiAssetInfo>> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let assets = api
             .get_assets(&at)
             .map_err(runtime_err)?
@@ -335,6 +821,17 @@ where
                 InterestRateModel::Fixed { rate } => ApiInterestRateModel::Fixed {
                     rate: String::from(rate),
                 },
+                InterestRateModel::JumpRate {
+                    base_rate,
+                    multiplier,
+                    jump_multiplier,
+                    kink,
+                } => ApiInterestRateModel::JumpRate {
+                    base_rate: format!("{:?}", base_rate),
+                    multiplier: format!("{:?}", multiplier),
+                    jump_multiplier: format!("{:?}", jump_multiplier),
+                    kink: format!("{:?}", kink),
+                },
             }
         }
 
@@ -355,9 +852,37 @@ where
         Ok(assets_lite) // XXX try_into?
     }
 
+    fn gateway_markets_summary(
+        &self,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiMarketSummary>> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let summaries: Vec<MarketSummary> = api
+            .get_markets_summary(&at)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(summaries
+            .into_iter()
+            .map(|summary| ApiMarketSummary {
+                asset: summary.asset,
+                total_supply: format!("{}", summary.total_supply),
+                total_borrow: format!("{}", summary.total_borrow),
+                total_value_locked: format!("{}", summary.total_value_locked),
+                utilization: format!("{}", summary.utilization.0),
+                supply_rate: format!("{}", summary.supply_rate.0),
+                borrow_rate: format!("{}", summary.borrow_rate.0),
+                price: format!("{}", summary.price),
+                supply_cap: format!("{}", summary.supply_cap),
+                liquidity_factor: format!("{}", summary.liquidity_factor.0),
+            })
+            .collect())
+    }
+
     fn chain_accounts(&self, at: Option<<B as BlockT>::Hash>) -> RpcResult<Vec<ChainAccount>> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let accounts = api
             .get_accounts(&at)
             .map_err(runtime_err)?
@@ -368,7 +893,7 @@ where
 
     fn chain_assets_meta(&self, at: Option<<B as BlockT>::Hash>) -> RpcResult<ApiAssetMeta> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let (supplier_counts, borrower_counts, combined_suppliers, combined_borrowers) = api
             .get_asset_meta(&at)
             .map_err(runtime_err)?
@@ -387,7 +912,7 @@ where
         at: Option<<B as BlockT>::Hash>,
     ) -> RpcResult<Vec<(ChainAccount, String)>> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let accounts = api
             .get_accounts_liquidity(&at)
             .map_err(runtime_err)?
@@ -395,13 +920,50 @@ where
         Ok(accounts) // XXX try_into?
     }
 
+    fn chain_accounts_page(
+        &self,
+        start_key: Option<ChainAccount>,
+        limit: u32,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ChainAccount>> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let accounts = api
+            .get_accounts_page(&at, start_key, limit)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(accounts)
+    }
+
+    fn chain_account_liquidities_page(
+        &self,
+        start_key: Option<ChainAccount>,
+        limit: u32,
+        below: Option<String>,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(ChainAccount, String)>> {
+        let below = below
+            .map(|below| {
+                AssetBalance::from_str(&below)
+                    .map_err(|_| RpcError::invalid_params(format!("Invalid balance: {}", below)))
+            })
+            .transpose()?;
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let accounts = api
+            .get_accounts_liquidity_page(&at, start_key, limit, below)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(accounts)
+    }
+
     fn chain_account_portfolio(
         &self,
         account: ChainAccount,
         at: Option<<B as BlockT>::Hash>,
     ) -> RpcResult<ApiPortfolio> {
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let at = self.block_at(at)?;
         let result: Portfolio = api
             .get_portfolio(&at, account)
             .map_err(runtime_err)?
@@ -419,13 +981,34 @@ where
         })
     }
 
+    fn chain_account_logical_portfolio(
+        &self,
+        account: ChainAccount,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<ApiPortfolio> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let result: Portfolio = api
+            .get_logical_portfolio(&at, account)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(ApiPortfolio {
+            cash: format!("{}", result.cash.value),
+            positions: result
+                .positions
+                .iter()
+                .map(|p| (p.0.asset, format!("{}", p.1.value)))
+                .collect(),
+        })
+    }
+
     fn validators(&self, at: Option<<B as BlockT>::Hash>) -> RpcResult<ApiValidators> {
         let api = self.client.runtime_api();
-        let hash = at.unwrap_or_else(|| self.client.info().best_hash);
-        let at = BlockId::hash(hash.clone());
+        let at = self.block_at(at)?;
         let current_block = self
             .client
-            .number(hash)
+            .block_number_from_id(&at)
             .unwrap_or_else(|_| None)
             .unwrap_or_else(|| self.client.info().best_number);
 
@@ -445,4 +1028,312 @@ where
             miner_payouts: miner_payouts,
         })
     }
+
+    fn validator_participation(
+        &self,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<ApiSessionParticipation> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let (expected, participations) = api
+            .get_validator_participation(&at)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(ApiSessionParticipation {
+            blocks_accepted: expected.blocks_attested,
+            notices_dispatched: expected.notices_signed,
+            validators: participations
+                .into_iter()
+                .map(|(keys, participation)| ApiValidatorParticipation {
+                    substrate_id: format!("{}", keys.substrate_id),
+                    eth_address: hex::encode(keys.eth_address),
+                    blocks_attested: participation.blocks_attested,
+                    notices_signed: participation.notices_signed,
+                })
+                .collect(),
+        })
+    }
+
+    fn validator_metadata(
+        &self,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiValidatorMetadata>> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let validators: Vec<(ValidatorKeys, Option<ValidatorMetadata>)> = api
+            .get_validator_metadata(&at)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        fn api_field(
+            metadata: &Option<ValidatorMetadata>,
+            field: fn(&ValidatorMetadata) -> &Vec<u8>,
+        ) -> Option<String> {
+            metadata
+                .as_ref()
+                .map(|metadata| String::from_utf8_lossy(field(metadata)).into_owned())
+        }
+
+        Ok(validators
+            .into_iter()
+            .map(|(keys, metadata)| ApiValidatorMetadata {
+                substrate_id: format!("{}", keys.substrate_id),
+                eth_address: hex::encode(keys.eth_address),
+                name: api_field(&metadata, |m| &m.name),
+                website: api_field(&metadata, |m| &m.website),
+                contact: api_field(&metadata, |m| &m.contact),
+                security_policy_url: api_field(&metadata, |m| &m.security_policy_url),
+            })
+            .collect())
+    }
+
+    fn validator_earnings(
+        &self,
+        substrate_id: SubstrateId,
+        last_n_sessions: u32,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiSessionEarnings>> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let earnings: Vec<SessionEarnings> = api
+            .get_validator_earnings(&at, substrate_id, last_n_sessions)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(earnings
+            .into_iter()
+            .map(|session| ApiSessionEarnings {
+                session_index: session.session_index,
+                paid: format!("{}", session.paid.0),
+                withheld: format!("{}", session.withheld.0),
+            })
+            .collect())
+    }
+
+    fn account_history(
+        &self,
+        account: ChainAccount,
+        from: u32,
+        limit: u32,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiActivityEntry>> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let entries: Vec<ActivityEntry> = api
+            .get_account_history(&at, account, from, limit)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| ApiActivityEntry {
+                block_number: entry.block_number,
+                kind: format!("{:?}", entry.kind),
+                asset: api_asset(entry.asset),
+                amount: format!("{}", entry.amount),
+                counterparty: entry.counterparty,
+            })
+            .collect())
+    }
+
+    fn pending_notices(
+        &self,
+        account: ChainAccount,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiPendingNotice>> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let notices = api
+            .get_pending_notices(&at, account)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(notices
+            .into_iter()
+            .map(
+                |(notice_id, encoded_notice, signature_pairs)| ApiPendingNotice {
+                    era_id: notice_id.era_id(),
+                    era_index: notice_id.era_index(),
+                    encoded_notice: format!("0x{}", hex::encode(encoded_notice)),
+                    signatures: api_signatures(signature_pairs),
+                },
+            )
+            .collect())
+    }
+
+    fn simulate_notice(
+        &self,
+        chain_id: ChainId,
+        era_id: u32,
+        era_index: u32,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<ApiNoticeSimulation> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let (starport, calldata, gas_estimate) = api
+            .simulate_notice(&at, chain_id, NoticeId(era_id, era_index))
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(ApiNoticeSimulation {
+            starport,
+            calldata: format!("0x{}", hex::encode(calldata)),
+            gas_estimate,
+        })
+    }
+
+    fn estimate_trx_request(
+        &self,
+        request: String,
+        account: ChainAccount,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<ApiTrxRequestEstimate> {
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let estimate = api
+            .estimate_trx_request(&at, request.into_bytes(), account)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(ApiTrxRequestEstimate {
+            balance_deltas: estimate
+                .balance_deltas
+                .into_iter()
+                .map(|(account, asset, delta)| ApiBalanceDelta {
+                    account,
+                    asset: api_asset(asset),
+                    delta: format!("{}", delta),
+                })
+                .collect(),
+            liquidity: format!("{}", estimate.liquidity),
+            notices: estimate
+                .notices
+                .into_iter()
+                .map(|(notice_id, encoded_notice)| ApiPendingNotice {
+                    era_id: notice_id.era_id(),
+                    era_index: notice_id.era_index(),
+                    encoded_notice: format!("0x{}", hex::encode(encoded_notice)),
+                    signatures: vec![],
+                })
+                .collect(),
+        })
+    }
+
+    fn simulate_governance(
+        &self,
+        actions: Vec<String>,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ApiGovernanceResult>> {
+        let actions = actions
+            .iter()
+            .map(|action| {
+                hex::decode(action.trim_start_matches("0x"))
+                    .map_err(|_| RpcError::invalid_params(format!("Invalid hex: {}", action)))
+            })
+            .collect::<RpcResult<Vec<Vec<u8>>>>()?;
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let results = api
+            .simulate_governance(&at, actions)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(results
+            .into_iter()
+            .map(|result| match result {
+                GovernanceResult::DispatchSuccess => ApiGovernanceResult {
+                    success: true,
+                    error: None,
+                },
+                GovernanceResult::FailedToDecodeCall => ApiGovernanceResult {
+                    success: false,
+                    error: Some("FailedToDecodeCall".into()),
+                },
+                GovernanceResult::DispatchFailure(err) => ApiGovernanceResult {
+                    success: false,
+                    error: Some(format!("{:?}", err)),
+                },
+            })
+            .collect())
+    }
+
+    fn keyring_status(&self, at: Option<<B as BlockT>::Hash>) -> RpcResult<ApiKeyringStatus> {
+        use runtime_interfaces::{keyring_interface, validator_config_interface};
+
+        let (eth_key, eth_public_key) = key_status(
+            validator_config_interface::get_eth_key_id(),
+            keyring_interface::get_public_key,
+        );
+        let (ed25519_key, _) = key_status(
+            validator_config_interface::get_ed25519_key_id(),
+            keyring_interface::get_ed25519_public_key,
+        );
+        let (threshold_key, _) = key_status(
+            validator_config_interface::get_threshold_key_id(),
+            keyring_interface::get_public_key,
+        );
+        let eth_address = eth_public_key
+            .map(|public_key| gateway_crypto::public_key_bytes_to_eth_address(&public_key));
+
+        let api = self.client.runtime_api();
+        let at = self.block_at(at)?;
+        let (validator_keys, _miner_payouts) = api
+            .get_validator_info(&at)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        let validator = eth_address.and_then(|eth_address| {
+            validator_keys
+                .iter()
+                .find(|v| v.eth_address == eth_address)
+                .map(|v| format!("{}", v.substrate_id))
+        });
+
+        Ok(ApiKeyringStatus {
+            eth_key,
+            eth_address: eth_address.map(hex::encode),
+            validator,
+            ed25519_key,
+            threshold_key,
+        })
+    }
+
+    fn health(&self) -> RpcResult<ApiHealth> {
+        let info = self.client.info();
+        let api = self.client.runtime_api();
+        let chains = api
+            .get_chain_health(&BlockId::hash(info.best_hash))
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        Ok(ApiHealth {
+            best_block: info.best_number.to_string(),
+            best_hash: format!("{:?}", info.best_hash),
+            finalized_block: info.finalized_number.to_string(),
+            finalized_hash: format!("{:?}", info.finalized_hash),
+            chains: chains
+                .into_iter()
+                .map(|health| ApiChainHealth {
+                    chain_id: health.chain_id,
+                    last_processed_block: health.last_processed_block,
+                    chain_head: health.chain_head,
+                    blocks_behind: match (health.chain_head, health.last_processed_block) {
+                        (Some(head), Some(last)) => Some(head.saturating_sub(last)),
+                        _ => None,
+                    },
+                    ingression_queue_depth: health.ingression_queue_depth,
+                    pending_notices: health.pending_notices,
+                })
+                .collect(),
+        })
+    }
+
+    fn reload_config(&self) -> RpcResult<Vec<String>> {
+        self.deny_unsafe.check_if_safe()?;
+        runtime_interfaces::reload_validator_config().map_err(|err| RpcError {
+            code: RpcErrorCode::InvalidRequest,
+            message: "Failed to reload config".into(),
+            data: Some(err.into()),
+        })
+    }
 }
@@ -158,7 +158,7 @@ pub fn local_testnet_config() -> ChainSpec {
 }
 
 /// Configure initial storage state for FRAME modules.
-fn testnet_genesis(
+pub(crate) fn testnet_genesis(
     initial_authorities: Vec<(AccountId, <Ethereum as Chain>::Address, AuraId, GrandpaId)>,
     reporters: Vec<&str>,
     assets: Vec<AssetInfo>,
@@ -206,6 +206,7 @@ fn testnet_genesis(
                 .map(|v| ValidatorKeys {
                     substrate_id: v.0.clone(),
                     eth_address: v.1,
+                    next_eth_key: None,
                 })
                 .collect::<Vec<_>>(),
             starports: starports,
@@ -1,6 +1,6 @@
 use crate::cli::{Cli, Subcommand};
-use crate::{chain_spec, service};
-#[cfg(feature = "runtime-benchmarks")]
+use crate::{chain_spec, service, snapshot, start_block};
+#[cfg(any(feature = "runtime-benchmarks", feature = "try-runtime"))]
 use gateway_runtime::Block;
 use sc_cli::{arg_enums::Database, ChainSpec, Role, RuntimeVersion, SubstrateCli};
 use sc_service::PartialComponents;
@@ -31,13 +31,20 @@ impl SubstrateCli for Cli {
     }
 
     fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
-        Ok(match id {
-            "dev" => Box::new(chain_spec::development_config()),
-            "" | "local" | "testnet" => Box::new(chain_spec::local_testnet_config()),
-            path => Box::new(chain_spec::ChainSpec::from_json_file(
-                std::path::PathBuf::from(path),
-            )?),
-        })
+        let spec = match id {
+            "dev" => chain_spec::development_config(),
+            "" | "local" | "testnet" => chain_spec::local_testnet_config(),
+            path => chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?,
+        };
+        let spec = if self.gateway.start_blocks.is_empty() {
+            spec
+        } else {
+            start_block::with_start_blocks(spec, &self.gateway.start_blocks)?
+        };
+        match &self.gateway.import_gateway_state {
+            Some(path) => Ok(Box::new(snapshot::with_gateway_state(spec, path)?)),
+            None => Ok(Box::new(spec)),
+        }
     }
 
     fn native_runtime_version(_: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
@@ -52,11 +59,18 @@ pub fn run() -> sc_cli::Result<()> {
         cli.run.import_params.database_params.database = Some(Database::ParityDb)
     }
 
+    // Note: loaded before the chain spec, which may need the RPC URLs to fetch start blocks
+    let gateway = cli.gateway.clone();
+    runtime_interfaces::initialize_validator_config_loader(Box::new(move || {
+        gateway.load_validator_config()
+    }))?;
+
     match &cli.subcommand {
         Some(Subcommand::BuildSpec(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
         }
+        Some(Subcommand::BuildSpecGateway(cmd)) => cmd.run(),
         Some(Subcommand::CheckBlock(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|config| {
@@ -91,6 +105,13 @@ pub fn run() -> sc_cli::Result<()> {
                 Ok((cmd.run(client, config.chain_spec), task_manager))
             })
         }
+        Some(Subcommand::ExportGatewayState(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.sync_run(|config| {
+                let PartialComponents { client, .. } = service::new_partial(&config)?;
+                cmd.run(client)
+            })
+        }
         Some(Subcommand::ImportBlocks(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|config| {
@@ -131,13 +152,25 @@ pub fn run() -> sc_cli::Result<()> {
                     .into())
             }
         }
+        #[cfg(feature = "try-runtime")]
+        Some(Subcommand::TryRuntime(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.async_run(|config| {
+                // Note: only a task manager is needed to run, not any of the components of new_partial
+                let registry = config.prometheus_config.as_ref().map(|cfg| &cfg.registry);
+                let task_manager =
+                    sc_service::TaskManager::new(config.task_executor.clone(), registry)
+                        .map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
+                Ok((cmd.run::<Block, service::Executor>(config), task_manager))
+            })
+        }
         None => {
             let runner = cli.create_runner(&cli.run)?;
-            runtime_interfaces::initialize_validator_config(cli.gateway.parse_cli_mapping());
+            let options = cli.gateway.clone();
             Ok(runner.run_node_until_exit(|config| async move {
                 match config.role {
                     Role::Light => service::new_light(config),
-                    _ => service::new_full(config),
+                    _ => service::new_full(config, options),
                 }
             })?)
         }
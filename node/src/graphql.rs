@@ -0,0 +1,392 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use juniper::{
+    graphql_object, http::GraphQLRequest, EmptyMutation, EmptySubscription, FieldError,
+    FieldResult, GraphQLObject, RootNode, Value,
+};
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+
+use gateway_runtime::opaque::Block;
+use our_std::{error, info};
+use pallet_cash::{chains::ChainAccount, reason::Reason};
+use pallet_cash_runtime_api::CashApi as CashRuntimeApi;
+
+use crate::api::api_signatures;
+use crate::service::FullClient;
+
+/// The number of items in a page, when not given.
+const DEFAULT_PAGE_SIZE: i32 = 100;
+
+/// The most items a page may have.
+const MAX_PAGE_SIZE: i32 = 1000;
+
+/// The state a query is resolved against, which is the best block when the query is received.
+pub struct Context {
+    client: Arc<FullClient>,
+    at: BlockId<Block>,
+}
+
+impl juniper::Context for Context {}
+
+fn runtime_err(err: impl std::fmt::Debug) -> FieldError {
+    FieldError::new(format!("Runtime trapped: {:?}", err), Value::null())
+}
+
+fn chain_err(reason: Reason) -> FieldError {
+    FieldError::new(format!("{:?}", reason), Value::null())
+}
+
+fn parse_account(account: &str) -> FieldResult<ChainAccount> {
+    ChainAccount::from_str(account).map_err(chain_err)
+}
+
+fn page_size(first: Option<i32>) -> usize {
+    first.unwrap_or(DEFAULT_PAGE_SIZE).max(0).min(MAX_PAGE_SIZE) as usize
+}
+
+#[derive(GraphQLObject)]
+/// The current state of a market.
+pub struct Market {
+    asset: String,
+    total_supply: String,
+    total_borrow: String,
+    total_value_locked: String,
+    utilization: String,
+    supply_rate: String,
+    borrow_rate: String,
+    price: String,
+    supply_cap: String,
+    liquidity_factor: String,
+}
+
+#[derive(GraphQLObject)]
+/// A page of markets, ordered by asset.
+pub struct MarketConnection {
+    nodes: Vec<Market>,
+    end_cursor: Option<String>,
+    has_next_page: bool,
+}
+
+#[derive(GraphQLObject)]
+/// The balance of an asset held by an account, negative if borrowed.
+pub struct Position {
+    asset: String,
+    balance: String,
+}
+
+#[derive(GraphQLObject)]
+/// The balances of CASH and each asset held by an account.
+pub struct Portfolio {
+    cash: String,
+    positions: Vec<Position>,
+}
+
+#[derive(GraphQLObject)]
+/// A notice which is yet to be executed on its underlying chain.
+pub struct Notice {
+    era_id: i32,
+    era_index: i32,
+    encoded_notice: String,
+    signers: Vec<String>,
+}
+
+#[derive(GraphQLObject)]
+/// The latest price of a ticker.
+pub struct Price {
+    ticker: String,
+    price: String,
+}
+
+/// An account holding CASH or an asset, whose fields are only fetched when selected.
+pub struct Account(ChainAccount);
+
+#[graphql_object(context = Context)]
+impl Account {
+    fn account(&self) -> String {
+        String::from(self.0)
+    }
+
+    fn liquidity(&self, context: &Context) -> FieldResult<String> {
+        let liquidity = context
+            .client
+            .runtime_api()
+            .get_liquidity(&context.at, self.0)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(format!("{}", liquidity))
+    }
+
+    fn portfolio(&self, context: &Context) -> FieldResult<Portfolio> {
+        let portfolio = context
+            .client
+            .runtime_api()
+            .get_portfolio(&context.at, self.0)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(Portfolio {
+            cash: format!("{}", portfolio.cash.value),
+            positions: portfolio
+                .positions
+                .into_iter()
+                .map(|(info, balance)| Position {
+                    asset: String::from(info.asset),
+                    balance: format!("{}", balance.value),
+                })
+                .collect(),
+        })
+    }
+
+    fn pending_notices(&self, context: &Context) -> FieldResult<Vec<Notice>> {
+        let notices = context
+            .client
+            .runtime_api()
+            .get_pending_notices(&context.at, self.0)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(notices
+            .into_iter()
+            .map(|(notice_id, encoded_notice, signature_pairs)| Notice {
+                era_id: notice_id.era_id() as i32,
+                era_index: notice_id.era_index() as i32,
+                encoded_notice: format!("0x{}", hex::encode(encoded_notice)),
+                signers: api_signatures(signature_pairs)
+                    .into_iter()
+                    .map(|(signer, _signature)| signer)
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
+#[derive(GraphQLObject)]
+#[graphql(context = Context)]
+/// A page of accounts, ordered by account.
+pub struct AccountConnection {
+    nodes: Vec<Account>,
+    end_cursor: Option<String>,
+    has_next_page: bool,
+}
+
+pub struct Query;
+
+#[graphql_object(context = Context)]
+impl Query {
+    /// The markets, a page at a time after the asset given as cursor.
+    fn markets(
+        &self,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> FieldResult<MarketConnection> {
+        let summaries = context
+            .client
+            .runtime_api()
+            .get_markets_summary(&context.at)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        let mut markets: Vec<Market> = summaries
+            .into_iter()
+            .map(|summary| Market {
+                asset: String::from(summary.asset),
+                total_supply: format!("{}", summary.total_supply),
+                total_borrow: format!("{}", summary.total_borrow),
+                total_value_locked: format!("{}", summary.total_value_locked),
+                utilization: format!("{}", summary.utilization.0),
+                supply_rate: format!("{}", summary.supply_rate.0),
+                borrow_rate: format!("{}", summary.borrow_rate.0),
+                price: format!("{}", summary.price),
+                supply_cap: format!("{}", summary.supply_cap),
+                liquidity_factor: format!("{}", summary.liquidity_factor.0),
+            })
+            .filter(|market| after.as_ref().map_or(true, |after| market.asset > *after))
+            .collect();
+        markets.sort_by(|a, b| a.asset.cmp(&b.asset));
+
+        let size = page_size(first);
+        let has_next_page = markets.len() > size;
+        markets.truncate(size);
+        Ok(MarketConnection {
+            end_cursor: markets.last().map(|market| market.asset.clone()),
+            nodes: markets,
+            has_next_page,
+        })
+    }
+
+    /// The accounts holding CASH or an asset, a page at a time after the account given as cursor.
+    fn accounts(
+        &self,
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> FieldResult<AccountConnection> {
+        let start_key = after.as_deref().map(parse_account).transpose()?;
+        let size = page_size(first);
+        let mut accounts = context
+            .client
+            .runtime_api()
+            .get_accounts_page(&context.at, start_key, size as u32 + 1)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+
+        let has_next_page = accounts.len() > size;
+        accounts.truncate(size);
+        Ok(AccountConnection {
+            end_cursor: accounts.last().map(|account| String::from(*account)),
+            nodes: accounts.into_iter().map(Account).collect(),
+            has_next_page,
+        })
+    }
+
+    /// An account, e.g. `ETH:0x...`.
+    fn account(&self, account: String) -> FieldResult<Account> {
+        Ok(Account(parse_account(&account)?))
+    }
+
+    /// The price of each supported asset, by ticker.
+    fn prices(&self, context: &Context) -> FieldResult<Vec<Price>> {
+        let api = context.client.runtime_api();
+        let assets = api
+            .get_assets(&context.at)
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        let mut prices: Vec<Price> = Vec::with_capacity(assets.len());
+        for info in assets {
+            let ticker = String::from(info.ticker);
+            if prices.iter().any(|price| price.ticker == ticker) {
+                continue;
+            }
+            let price = api
+                .get_price_with_ticker(&context.at, info.ticker)
+                .map_err(runtime_err)?
+                .map_err(chain_err)?;
+            prices.push(Price {
+                ticker,
+                price: format!("{}", price),
+            });
+        }
+        Ok(prices)
+    }
+
+    /// The price of a ticker, e.g. `ETH`.
+    fn price(&self, context: &Context, ticker: String) -> FieldResult<Price> {
+        let price = context
+            .client
+            .runtime_api()
+            .get_price(&context.at, ticker.clone())
+            .map_err(runtime_err)?
+            .map_err(chain_err)?;
+        Ok(Price {
+            ticker,
+            price: format!("{}", price),
+        })
+    }
+
+    /// The pending notices for an account's underlying chain, with the signatures collected so far.
+    fn notices(&self, context: &Context, account: String) -> FieldResult<Vec<Notice>> {
+        Account(parse_account(&account)?).pending_notices(context)
+    }
+}
+
+pub type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+pub fn schema() -> Schema {
+    Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+async fn handle(
+    request: Request<Body>,
+    schema: Arc<Schema>,
+    client: Arc<FullClient>,
+) -> Result<Response<Body>, Infallible> {
+    if (request.method(), request.uri().path()) != (&Method::POST, "/graphql") {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NOT_FOUND;
+        return Ok(response);
+    }
+
+    let body = match hyper::body::to_bytes(request.into_body()).await {
+        Ok(body) => body,
+        Err(err) => {
+            let mut response = Response::new(Body::from(err.to_string()));
+            *response.status_mut() = StatusCode::BAD_REQUEST;
+            return Ok(response);
+        }
+    };
+    let (status, body) = match serde_json::from_slice::<GraphQLRequest>(&body) {
+        Ok(query) => {
+            let context = Context {
+                at: BlockId::hash(client.info().best_hash),
+                client,
+            };
+            let result = query.execute_sync(&schema, &context);
+            let status = if result.is_ok() {
+                StatusCode::OK
+            } else {
+                StatusCode::BAD_REQUEST
+            };
+            (status, serde_json::to_vec(&result).unwrap_or_default())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string().into_bytes()),
+    };
+
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static("application/json"),
+    );
+    Ok(response)
+}
+
+/// Serve GraphQL queries POSTed to `/graphql` at the given address, for as long as the node runs.
+pub async fn serve(addr: SocketAddr, client: Arc<FullClient>) {
+    let schema = Arc::new(schema());
+    let make_service = make_service_fn(move |_| {
+        let schema = schema.clone();
+        let client = client.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                handle(request, schema.clone(), client.clone())
+            }))
+        }
+    });
+
+    info!("GraphQL server listening on http://{}/graphql", addr);
+    if let Err(err) = Server::bind(&addr).serve(make_service).await {
+        error!("GraphQL server failed: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema() {
+        let schema = schema().as_schema_language();
+        for type_name in &[
+            "type Query",
+            "type Market",
+            "type MarketConnection",
+            "type Account",
+            "type AccountConnection",
+            "type Portfolio",
+            "type Notice",
+            "type Price",
+        ] {
+            assert!(schema.contains(type_name), "missing {}", type_name);
+        }
+        assert!(schema.contains("hasNextPage: Boolean!"));
+        assert!(schema.contains("pendingNotices: [Notice!]!"));
+    }
+}
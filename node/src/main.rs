@@ -7,7 +7,15 @@ mod service;
 mod api;
 mod cli;
 mod command;
+#[cfg(feature = "graphql")]
+mod graphql;
+mod indexer;
+mod metrics;
 mod rpc;
+mod snapshot;
+mod spec_config;
+mod start_block;
+mod subscriptions;
 
 fn main() -> sc_cli::Result<()> {
     command::run()
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use futures::StreamExt;
+use sc_client_api::BlockchainEvents;
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use substrate_prometheus_endpoint::{register, GaugeVec, Opts, PrometheusError, Registry, U64};
+
+use our_std::warn;
+use pallet_cash::types::ProtocolMetrics;
+use pallet_cash_runtime_api::CashApi as CashRuntimeApi;
+
+/// The gauges for the backlogs of the protocol, as of the best block.
+pub struct ProtocolGauges {
+    ingression_queue: GaugeVec<U64>,
+    pending_block_support: GaugeVec<U64>,
+    unsigned_notices: GaugeVec<U64>,
+    price_age: GaugeVec<U64>,
+}
+
+fn gauge_vec(
+    registry: &Registry,
+    name: &str,
+    help: &str,
+    labels: &[&str],
+) -> Result<GaugeVec<U64>, PrometheusError> {
+    register(GaugeVec::new(Opts::new(name, help), labels)?, registry)
+}
+
+impl ProtocolGauges {
+    /// Register the gauges, so that they are exposed to Prometheus.
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(ProtocolGauges {
+            ingression_queue: gauge_vec(
+                registry,
+                "gateway_ingression_queue_events",
+                "Number of events from an underlying chain waiting to be ingressed",
+                &["chain"],
+            )?,
+            pending_block_support: gauge_vec(
+                registry,
+                "gateway_pending_chain_block_support",
+                "Number of validators supporting a pending block of an underlying chain",
+                &["chain", "block"],
+            )?,
+            unsigned_notices: gauge_vec(
+                registry,
+                "gateway_unsigned_notices",
+                "Number of pending notices for an underlying chain without enough signatures",
+                &["chain"],
+            )?,
+            price_age: gauge_vec(
+                registry,
+                "gateway_price_age_milliseconds",
+                "Time since the price of a ticker was last posted",
+                &["ticker"],
+            )?,
+        })
+    }
+
+    fn update(&self, metrics: ProtocolMetrics) {
+        // Note: reset so that pending blocks and tickers which are gone are no longer reported
+        self.ingression_queue.reset();
+        self.pending_block_support.reset();
+        self.unsigned_notices.reset();
+        self.price_age.reset();
+
+        for (chain_id, depth) in metrics.ingression_queue_depths {
+            self.ingression_queue
+                .with_label_values(&[&format!("{:?}", chain_id)])
+                .set(depth as u64);
+        }
+        for (chain_id, block_number, support) in metrics.pending_block_support {
+            self.pending_block_support
+                .with_label_values(&[&format!("{:?}", chain_id), &block_number.to_string()])
+                .set(support as u64);
+        }
+        for (chain_id, count) in metrics.unsigned_notices {
+            self.unsigned_notices
+                .with_label_values(&[&format!("{:?}", chain_id)])
+                .set(count as u64);
+        }
+        for (ticker, age) in metrics.price_ages {
+            self.price_age
+                .with_label_values(&[&String::from(ticker)])
+                .set(age);
+        }
+    }
+}
+
+/// Update the gauges each time a new best block is imported, for as long as the node runs.
+pub async fn report_protocol_metrics<B, C>(client: Arc<C>, gauges: ProtocolGauges)
+where
+    B: BlockT,
+    C: ProvideRuntimeApi<B> + BlockchainEvents<B>,
+    C::Api: CashRuntimeApi<B>,
+{
+    let mut imports = client.import_notification_stream();
+    while let Some(notification) = imports.next().await {
+        if !notification.is_new_best {
+            continue;
+        }
+        match client
+            .runtime_api()
+            .get_protocol_metrics(&BlockId::hash(notification.hash))
+        {
+            Ok(Ok(metrics)) => gauges.update(metrics),
+            err => warn!(
+                "Failed to get protocol metrics for block {:?}: {:?}",
+                notification.hash, err
+            ),
+        }
+    }
+}
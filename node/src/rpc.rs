@@ -16,7 +16,8 @@
 use std::sync::Arc;
 
 use gateway_runtime::{opaque::Block, AccountId, Index};
-use sc_client_api::AuxStore;
+use sc_client_api::{AuxStore, BlockchainEvents};
+use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -36,6 +37,8 @@ pub struct FullDeps<C, P, SC> {
     pub chain_spec: Box<dyn sc_chain_spec::ChainSpec>,
     /// Whether to deny unsafe calls
     pub deny_unsafe: DenyUnsafe,
+    /// Executor to drive the subscriptions
+    pub subscription_executor: SubscriptionTaskExecutor,
 }
 
 /// Instantiate all Full RPC extensions.
@@ -46,6 +49,7 @@ where
     C: ProvideRuntimeApi<Block>
         + HeaderBackend<Block>
         + AuxStore
+        + BlockchainEvents<Block>
         + HeaderMetadata<Block, Error = BlockChainError>
         + Sync
         + Send
@@ -63,6 +67,7 @@ where
         client,
         pool,
         deny_unsafe,
+        subscription_executor,
         ..
     } = deps;
 
@@ -73,7 +78,11 @@ where
     )));
 
     io.extend_with(crate::api::GatewayRpcApi::to_delegate(
-        crate::api::GatewayRpcHandler::new(client),
+        crate::api::GatewayRpcHandler::new(client.clone(), deny_unsafe),
+    ));
+
+    io.extend_with(crate::subscriptions::GatewayEventsApi::to_delegate(
+        crate::subscriptions::GatewayEventsHandler::new(client, subscription_executor),
     ));
 
     io
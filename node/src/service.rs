@@ -1,5 +1,6 @@
 //! Service implementation. Specialized wrapper over substrate service.
 
+use crate::cli::GatewayCmd;
 use gateway_runtime::{self as node_runtime, opaque::Block, RuntimeApi};
 use pallet_cash;
 use pallet_oracle;
@@ -12,7 +13,7 @@ use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
 use std::sync::Arc;
 use std::time::Duration;
 
-type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
+pub(crate) type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 
@@ -26,6 +27,7 @@ native_executor_instance!(
         runtime_interfaces::validator_config_interface::HostFunctions,
         runtime_interfaces::keyring_interface::HostFunctions,
         runtime_interfaces::price_feed_interface::HostFunctions,
+        runtime_interfaces::metrics_interface::HostFunctions,
     ),
 );
 
@@ -132,7 +134,10 @@ pub fn new_partial(
     })
 }
 
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+    mut config: Configuration,
+    gateway: GatewayCmd,
+) -> Result<TaskManager, ServiceError> {
     let sc_service::PartialComponents {
         client,
         backend,
@@ -177,18 +182,57 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
     let enable_grandpa = !config.disable_grandpa;
     let prometheus_registry = config.prometheus_registry().cloned();
 
+    if let Some(registry) = prometheus_registry.as_ref() {
+        runtime_interfaces::metrics::register_metrics(registry).map_err(|err| {
+            ServiceError::Other(format!(
+                "Failed to register offchain worker metrics: {}",
+                err
+            ))
+        })?;
+
+        let gauges = crate::metrics::ProtocolGauges::register(registry).map_err(|err| {
+            ServiceError::Other(format!("Failed to register protocol metrics: {}", err))
+        })?;
+        task_manager.spawn_handle().spawn(
+            "protocol-metrics",
+            crate::metrics::report_protocol_metrics(client.clone(), gauges),
+        );
+    }
+
+    if let Some(url) = gateway.indexer_url {
+        let sender = crate::indexer::start_indexer(url).map_err(ServiceError::Other)?;
+        task_manager.spawn_handle().spawn(
+            "gateway-indexer",
+            crate::indexer::index_protocol_events(client.clone(), sender),
+        );
+    }
+
+    if let Some(addr) = gateway.graphql_addr {
+        #[cfg(feature = "graphql")]
+        task_manager
+            .spawn_handle()
+            .spawn("graphql", crate::graphql::serve(addr, client.clone()));
+        #[cfg(not(feature = "graphql"))]
+        return Err(ServiceError::Other(format!(
+            "Serving GraphQL at {} wasn't enabled when building the node. \
+             You can enable it with `--features graphql`.",
+            addr
+        )));
+    }
+
     let rpc_extensions_builder = {
         let client = client.clone();
         let pool = transaction_pool.clone();
         let select_chain = select_chain.clone();
         let chain_spec = config.chain_spec.cloned_box();
-        Box::new(move |deny_unsafe, _| {
+        Box::new(move |deny_unsafe, subscription_executor| {
             let deps = crate::rpc::FullDeps {
                 client: client.clone(),
                 pool: pool.clone(),
                 select_chain: select_chain.clone(),
                 chain_spec: chain_spec.cloned_box(),
                 deny_unsafe,
+                subscription_executor,
             };
 
             crate::rpc::create_full(deps)
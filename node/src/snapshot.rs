@@ -0,0 +1,221 @@
+use codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use sc_client_api::StorageProvider;
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::StorageKey;
+use sp_runtime::generic::BlockId;
+
+use gateway_runtime::opaque::Block;
+use our_std::info;
+
+use crate::chain_spec::ChainSpec;
+use crate::cli::ExportGatewayStateCmd;
+use crate::service::FullClient;
+
+/// The storage of the Cash pallet at a block, as raw SCALE encoded keys and values.
+#[derive(Clone, Debug, Default, PartialEq, Encode, Decode, Serialize, Deserialize)]
+pub struct GatewayStateSnapshot {
+    pub block_number: u32,
+    pub block_hash: [u8; 32],
+    pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+/// The snapshot as written to JSON, with everything hex encoded as in a raw chain spec.
+#[derive(Serialize, Deserialize)]
+struct JsonSnapshot {
+    block_number: u32,
+    block_hash: String,
+    entries: BTreeMap<String, String>,
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn hex_decode(s: &str) -> Result<Vec<u8>, String> {
+    hex::decode(s.trim_start_matches("0x")).map_err(|e| format!("Invalid hex {:?}: {}", s, e))
+}
+
+/// The prefix of every storage key of the Cash pallet.
+pub fn cash_prefix() -> Vec<u8> {
+    sp_core::hashing::twox_128(b"Cash").to_vec()
+}
+
+fn is_scale(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext == "scale")
+}
+
+impl GatewayStateSnapshot {
+    /// Write the snapshot to a file, as SCALE if it has a `.scale` extension and as JSON otherwise.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let bytes = if is_scale(path) {
+            self.encode()
+        } else {
+            let json = JsonSnapshot {
+                block_number: self.block_number,
+                block_hash: hex_encode(&self.block_hash),
+                entries: self
+                    .entries
+                    .iter()
+                    .map(|(key, value)| (hex_encode(key), hex_encode(value)))
+                    .collect(),
+            };
+            serde_json::to_vec_pretty(&json).map_err(|e| e.to_string())?
+        };
+        std::fs::write(path, bytes).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+    }
+
+    /// Read a snapshot from a file written by `write`.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if is_scale(path) {
+            return Self::decode(&mut &bytes[..])
+                .map_err(|e| format!("Invalid snapshot {:?}: {}", path, e));
+        }
+        let json: JsonSnapshot = serde_json::from_slice(&bytes)
+            .map_err(|e| format!("Invalid snapshot {:?}: {}", path, e))?;
+        let mut block_hash = [0u8; 32];
+        let hash = hex_decode(&json.block_hash)?;
+        if hash.len() != 32 {
+            return Err(format!("Invalid snapshot block hash: {}", json.block_hash));
+        }
+        block_hash.copy_from_slice(&hash);
+        Ok(GatewayStateSnapshot {
+            block_number: json.block_number,
+            block_hash,
+            entries: json
+                .entries
+                .iter()
+                .map(|(key, value)| Ok((hex_decode(key)?, hex_decode(value)?)))
+                .collect::<Result<_, String>>()?,
+        })
+    }
+
+    /// Take a snapshot of the Cash pallet's storage at the given block.
+    pub fn export(client: &FullClient, at: BlockId<Block>) -> Result<Self, String> {
+        let block_hash = client
+            .expect_block_hash_from_id(&at)
+            .map_err(|e| e.to_string())?;
+        let block_number = client
+            .expect_block_number_from_id(&at)
+            .map_err(|e| e.to_string())?;
+        let entries = client
+            .storage_pairs(&at, &StorageKey(cash_prefix()))
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|(key, value)| (key.0, value.0))
+            .collect();
+        Ok(GatewayStateSnapshot {
+            block_number,
+            block_hash: block_hash.into(),
+            entries,
+        })
+    }
+
+    /// Replace the Cash pallet's genesis storage in a chain spec with the snapshot, making it raw.
+    pub fn import(&self, spec: ChainSpec) -> Result<ChainSpec, String> {
+        let mut json: serde_json::Value = serde_json::from_str(&spec.as_json(true)?)
+            .map_err(|e| format!("Invalid chain spec: {}", e))?;
+        let top = json
+            .pointer_mut("/genesis/raw/top")
+            .and_then(|top| top.as_object_mut())
+            .ok_or("Invalid raw chain spec")?;
+
+        let prefix = hex_encode(&cash_prefix());
+        top.retain(|key, _| !key.starts_with(&prefix));
+        for (key, value) in &self.entries {
+            if !key.starts_with(&cash_prefix()) {
+                return Err(format!("Not a Cash storage key: {}", hex_encode(key)));
+            }
+            top.insert(hex_encode(key), hex_encode(value).into());
+        }
+
+        let bytes = serde_json::to_vec(&json).map_err(|e| format!("Invalid chain spec: {}", e))?;
+        ChainSpec::from_json_bytes(bytes)
+    }
+}
+
+/// Read a snapshot and use it as the Cash pallet's genesis storage in the chain spec.
+pub fn with_gateway_state(spec: ChainSpec, path: &Path) -> Result<ChainSpec, String> {
+    let snapshot = GatewayStateSnapshot::read(path)?;
+    info!(
+        "Importing {} Cash storage entries from block {} (0x{})",
+        snapshot.entries.len(),
+        snapshot.block_number,
+        hex::encode(snapshot.block_hash)
+    );
+    snapshot.import(spec)
+}
+
+impl ExportGatewayStateCmd {
+    /// Export the Cash pallet's storage at the block given, or the best block.
+    pub fn run(&self, client: Arc<FullClient>) -> sc_cli::Result<()> {
+        let at = match &self.at {
+            Some(at) => at.parse::<Block>()?,
+            None => BlockId::Hash(client.info().best_hash),
+        };
+        let snapshot = GatewayStateSnapshot::export(&client, at)?;
+        snapshot.write(&self.output)?;
+        info!(
+            "Exported {} Cash storage entries from block {} to {:?}",
+            snapshot.entries.len(),
+            snapshot.block_number,
+            self.output
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_spec::development_config;
+    use gateway_runtime::BuildStorage;
+
+    fn snapshot() -> GatewayStateSnapshot {
+        let mut key = cash_prefix();
+        key.extend(sp_core::hashing::twox_128(b"CashYield").iter());
+        GatewayStateSnapshot {
+            block_number: 7,
+            block_hash: [7; 32],
+            entries: vec![(key, 300u128.encode())],
+        }
+    }
+
+    #[test]
+    fn test_write_and_read_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in &["state.json", "state.scale"] {
+            let path = dir.path().join(file);
+            snapshot().write(&path).unwrap();
+            assert_eq!(GatewayStateSnapshot::read(&path), Ok(snapshot()));
+        }
+    }
+
+    #[test]
+    fn test_import_snapshot() {
+        let spec = snapshot().import(development_config()).unwrap();
+        let storage = spec.build_storage().unwrap();
+        let (key, value) = &snapshot().entries[0];
+        assert_eq!(storage.top.get(key), Some(value));
+
+        // Cash storage in the spec which isn't in the snapshot is dropped
+        let prefix = cash_prefix();
+        assert_eq!(
+            storage
+                .top
+                .keys()
+                .filter(|k| k.starts_with(&prefix))
+                .count(),
+            1
+        );
+
+        let mut bad = snapshot();
+        bad.entries[0].0 = b"not cash".to_vec();
+        assert!(bad.import(development_config()).is_err());
+    }
+}
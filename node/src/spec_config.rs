@@ -0,0 +1,457 @@
+use serde::Deserialize;
+use std::path::Path;
+use std::str::FromStr;
+
+use gateway_runtime::AccountId;
+use pallet_cash::{
+    chains::{Chain, ChainAccount, ChainAsset, ChainBlock, ChainId, Ethereum, Polygon},
+    factor::Factor,
+    rates::{InterestRateModel, APR},
+    symbol::Symbol,
+    types::{AssetInfo, Bips, Timestamp},
+};
+use pallet_oracle::ticker::Ticker;
+use sc_network::config::MultiaddrWithPeerId;
+use sc_service::ChainType;
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_core::crypto::Ss58Codec;
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+
+use crate::chain_spec::{testnet_genesis, ChainSpec};
+use crate::cli::BuildSpecGatewayCmd;
+
+/// The most decimals an asset may have, beyond which its amounts would overflow.
+const MAX_DECIMALS: u8 = 36;
+
+/// A description of a chain to build a chain spec for, as read from a TOML or JSON file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpecConfig {
+    pub name: String,
+    pub id: String,
+    #[serde(default)]
+    pub chain_type: ChainType,
+    #[serde(default)]
+    pub boot_nodes: Vec<String>,
+    pub protocol_id: Option<String>,
+    /// The initial CASH yield, in basis points.
+    #[serde(default)]
+    pub cash_yield: u64,
+    /// The time the initial CASH yield started accruing, in milliseconds since the epoch.
+    #[serde(default)]
+    pub last_yield_timestamp: Timestamp,
+    #[serde(default)]
+    pub assets: Vec<AssetConfig>,
+    #[serde(default)]
+    pub starports: Vec<String>,
+    #[serde(default)]
+    pub reporters: Vec<String>,
+    #[serde(default)]
+    pub validators: Vec<ValidatorConfig>,
+    #[serde(default)]
+    pub genesis_blocks: Vec<GenesisBlockConfig>,
+}
+
+/// An asset supported at genesis, e.g. `asset = "ETH:0x..."`, `symbol = "USDC"`, `decimals = 6`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssetConfig {
+    pub asset: String,
+    pub symbol: String,
+    pub decimals: u8,
+    /// The ticker to price the asset with, which is the symbol if not given.
+    pub ticker: Option<String>,
+    /// The liquidity factor, as a fraction e.g. `"0.8"`.
+    pub liquidity_factor: String,
+    #[serde(default)]
+    pub rate_model: InterestRateModel,
+    /// The miner shares, as a fraction e.g. `"0.01"`.
+    pub miner_shares: Option<String>,
+    /// The supply cap, in the smallest units of the asset.
+    pub supply_cap: Option<String>,
+    pub liquidation_incentive: Option<Bips>,
+}
+
+/// A genesis validator, identified by SS58 encoded keys and an Ethereum address.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorConfig {
+    pub substrate_id: String,
+    pub eth_address: String,
+    pub aura: String,
+    pub grandpa: String,
+}
+
+/// The block of an underlying chain from which its events are ingressed.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisBlockConfig {
+    pub chain: String,
+    pub number: u64,
+    pub hash: String,
+    pub parent_hash: String,
+}
+
+/// The genesis described by a spec config, once each part of it has been parsed and checked.
+#[derive(Clone)]
+struct ValidGenesis {
+    authorities: Vec<(AccountId, <Ethereum as Chain>::Address, AuraId, GrandpaId)>,
+    reporters: Vec<String>,
+    assets: Vec<AssetInfo>,
+    cash_yield: APR,
+    last_yield_timestamp: Timestamp,
+    starports: Vec<ChainAccount>,
+    genesis_blocks: Vec<ChainBlock>,
+}
+
+/// Parse a nominal decimal string, e.g. `"0.8"`, as an integer with the given number of decimals.
+fn parse_nominal(s: &str, decimals: u8) -> Result<u128, String> {
+    let (int_part, frac_part) = s.split_once('.').unwrap_or((s, ""));
+    let valid_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if int_part.is_empty() || !valid_digits(int_part) || !valid_digits(frac_part) {
+        return Err(format!("{:?} is not a decimal number", s));
+    }
+    if frac_part.len() > decimals as usize {
+        return Err(format!("{:?} has more than {} decimals", s, decimals));
+    }
+    let digits = format!(
+        "{}{:0<width$}",
+        int_part,
+        frac_part,
+        width = decimals as usize
+    );
+    u128::from_str(&digits).map_err(|_| format!("{:?} is too large", s))
+}
+
+fn parse_fraction(s: &str, what: &str) -> Result<Factor, String> {
+    let factor =
+        Factor(parse_nominal(s, Factor::DECIMALS).map_err(|e| format!("{}: {}", what, e))?);
+    if factor > Factor::ONE {
+        return Err(format!("{}: {:?} is more than 1", what, s));
+    }
+    Ok(factor)
+}
+
+fn parse_ss58<T: Ss58Codec>(s: &str, what: &str) -> Result<T, String> {
+    T::from_ss58check(s).map_err(|e| format!("{}: {:?} is not a valid SS58 key: {:?}", what, s, e))
+}
+
+fn parse_eth_address(s: &str, what: &str) -> Result<<Ethereum as Chain>::Address, String> {
+    <Ethereum as Chain>::str_to_address(s)
+        .map_err(|_| format!("{}: {:?} is not a valid Ethereum address", what, s))
+}
+
+fn parse_reason<T, E: std::fmt::Debug>(
+    result: Result<T, E>,
+    s: &str,
+    what: &str,
+) -> Result<T, String> {
+    result.map_err(|e| format!("{}: {:?} is invalid: {:?}", what, s, e))
+}
+
+/// Fail if any key is repeated, naming the first one which is.
+fn ensure_unique<K, I>(keys: I, what: &str) -> Result<(), String>
+where
+    K: PartialEq + std::fmt::Debug,
+    I: IntoIterator<Item = K>,
+{
+    let mut seen = Vec::new();
+    for key in keys {
+        if seen.contains(&key) {
+            return Err(format!("Duplicate {}: {:?}", what, key));
+        }
+        seen.push(key);
+    }
+    Ok(())
+}
+
+impl AssetConfig {
+    fn parse(&self) -> Result<AssetInfo, String> {
+        let what = format!("asset {}", self.asset);
+        let asset = parse_reason(ChainAsset::from_str(&self.asset), &self.asset, &what)?;
+        if self.decimals > MAX_DECIMALS {
+            return Err(format!(
+                "{}: {} decimals is more than {}",
+                what, self.decimals, MAX_DECIMALS
+            ));
+        }
+        let symbol = parse_reason(Symbol::from_str(&self.symbol), &self.symbol, &what)?;
+        let ticker_str = self.ticker.as_deref().unwrap_or(&self.symbol);
+        let ticker = parse_reason(Ticker::from_str(ticker_str), ticker_str, &what)?;
+        let liquidity_factor = parse_fraction(&self.liquidity_factor, &what)?;
+        if liquidity_factor == Factor::ZERO {
+            return Err(format!("{}: liquidity factor must be more than 0", what));
+        }
+        let miner_shares = match &self.miner_shares {
+            Some(shares) => parse_fraction(shares, &what)?,
+            None => Factor::ZERO,
+        };
+        let supply_cap = match &self.supply_cap {
+            Some(cap) => parse_nominal(cap, 0).map_err(|e| format!("{}: {}", what, e))?,
+            None => 0,
+        };
+        Ok(AssetInfo {
+            asset,
+            decimals: self.decimals,
+            liquidity_factor,
+            rate_model: self.rate_model,
+            miner_shares,
+            supply_cap,
+            symbol,
+            ticker,
+            liquidation_incentive: self.liquidation_incentive,
+        })
+    }
+}
+
+impl GenesisBlockConfig {
+    fn parse(&self) -> Result<ChainBlock, String> {
+        let what = format!("genesis block {}", self.chain);
+        let parse_hash =
+            |hash: &str| parse_reason(<Ethereum as Chain>::str_to_hash(hash), hash, &what);
+        let block = ethereum_client::EthereumBlock {
+            hash: parse_hash(&self.hash)?,
+            parent_hash: parse_hash(&self.parent_hash)?,
+            number: self.number,
+            timestamp: 0,
+            events: vec![],
+        };
+        match parse_reason(ChainId::from_str(&self.chain), &self.chain, &what)? {
+            ChainId::Eth => Ok(<Ethereum as Chain>::chain_block(block)),
+            ChainId::Matic => Ok(<Polygon as Chain>::chain_block(block)),
+            chain_id => Err(format!("{}: {:?} has no blocks", what, chain_id)),
+        }
+    }
+}
+
+impl SpecConfig {
+    /// Read a spec config from a file, as TOML if it has a `.toml` extension and as JSON otherwise.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read spec config {:?}: {}", path, e))?;
+        if path.extension().map_or(false, |ext| ext == "toml") {
+            toml::from_str(&contents).map_err(|e| format!("Invalid spec config {:?}: {}", path, e))
+        } else {
+            serde_json::from_str(&contents)
+                .map_err(|e| format!("Invalid spec config {:?}: {}", path, e))
+        }
+    }
+
+    fn validate(&self) -> Result<ValidGenesis, String> {
+        if self.name.is_empty() || self.id.is_empty() {
+            return Err("Chain name and id must not be empty".into());
+        }
+
+        let mut authorities = Vec::with_capacity(self.validators.len());
+        for v in &self.validators {
+            let what = format!("validator {}", v.substrate_id);
+            authorities.push((
+                parse_ss58::<AccountId>(&v.substrate_id, &what)?,
+                parse_eth_address(&v.eth_address, &what)?,
+                parse_ss58::<AuraId>(&v.aura, &what)?,
+                parse_ss58::<GrandpaId>(&v.grandpa, &what)?,
+            ));
+        }
+        ensure_unique(authorities.iter().map(|a| &a.0), "validator substrate id")?;
+        ensure_unique(authorities.iter().map(|a| a.1), "validator eth address")?;
+        ensure_unique(authorities.iter().map(|a| &a.2), "validator aura key")?;
+        ensure_unique(authorities.iter().map(|a| &a.3), "validator grandpa key")?;
+
+        let reporters = self
+            .reporters
+            .iter()
+            .map(|r| parse_eth_address(r, "reporter"))
+            .collect::<Result<Vec<_>, _>>()?;
+        ensure_unique(reporters, "reporter")?;
+
+        let assets = self
+            .assets
+            .iter()
+            .map(AssetConfig::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        ensure_unique(assets.iter().map(|a| a.asset), "asset")?;
+
+        let starports = self
+            .starports
+            .iter()
+            .map(|s| parse_reason(ChainAccount::from_str(s), s, "starport"))
+            .collect::<Result<Vec<_>, _>>()?;
+        ensure_unique(starports.iter().map(|s| s.chain_id()), "starport for chain")?;
+
+        let genesis_blocks = self
+            .genesis_blocks
+            .iter()
+            .map(GenesisBlockConfig::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        ensure_unique(
+            genesis_blocks.iter().map(|b| b.chain_id()),
+            "genesis block for chain",
+        )?;
+        for block in &genesis_blocks {
+            if !starports.iter().any(|s| s.chain_id() == block.chain_id()) {
+                return Err(format!(
+                    "Genesis block for chain {:?} has no starport",
+                    block.chain_id()
+                ));
+            }
+        }
+
+        Ok(ValidGenesis {
+            authorities,
+            reporters: self.reporters.clone(),
+            assets,
+            cash_yield: APR(self.cash_yield.into()),
+            last_yield_timestamp: self.last_yield_timestamp,
+            starports,
+            genesis_blocks,
+        })
+    }
+
+    /// Check the config and build the chain spec it describes.
+    pub fn build(&self) -> Result<ChainSpec, String> {
+        let genesis = self.validate()?;
+        let boot_nodes = self
+            .boot_nodes
+            .iter()
+            .map(|node| {
+                MultiaddrWithPeerId::from_str(node)
+                    .map_err(|e| format!("Invalid boot node {:?}: {}", node, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ChainSpec::from_genesis(
+            &self.name,
+            &self.id,
+            self.chain_type.clone(),
+            move || {
+                let genesis = genesis.clone();
+                testnet_genesis(
+                    genesis.authorities,
+                    genesis.reporters.iter().map(String::as_str).collect(),
+                    genesis.assets,
+                    genesis.cash_yield,
+                    genesis.last_yield_timestamp,
+                    genesis.starports,
+                    genesis.genesis_blocks,
+                )
+            },
+            boot_nodes,
+            None,
+            self.protocol_id.as_deref(),
+            None,
+            None,
+        ))
+    }
+}
+
+impl BuildSpecGatewayCmd {
+    /// Build the chain spec described by the config file, and write it out.
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let spec = SpecConfig::from_file(&self.config)?.build()?;
+        let json = sc_service::chain_ops::build_spec(&spec, self.raw)?;
+        match &self.output {
+            Some(path) => std::fs::write(path, json)?,
+            None => println!("{}", json),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gateway_runtime::BuildStorage;
+
+    const CONFIG: &str = r#"
+        name = "Test"
+        id = "test"
+        chain_type = "Local"
+        cash_yield = 300
+        starports = ["ETH:0xbbde1662bc3ed16aa8c618c9833c801f3543b587"]
+        reporters = ["0x85615b076615317c80f14cbad6501eec031cd51c"]
+
+        [[assets]]
+        asset = "ETH:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+        symbol = "ETH"
+        decimals = 18
+        liquidity_factor = "0.8"
+        miner_shares = "0.01"
+        rate_model = { Kink = { zero_rate = 0, kink_rate = 500, kink_utilization = 8000, full_rate = 2000 } }
+
+        [[assets]]
+        asset = "ETH:0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+        symbol = "USDC"
+        ticker = "USD"
+        decimals = 6
+        liquidity_factor = "0.85"
+        supply_cap = "1000000000000"
+
+        [[validators]]
+        substrate_id = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        eth_address = "0x55413a2d4908d130c908ccf2f298b235bacd427a"
+        aura = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        grandpa = "5FA9nQDVg267DEd8m1ZypXLBnvN7SFxYwV7ndqSYGiN9TTpu"
+
+        [[genesis_blocks]]
+        chain = "ETH"
+        number = 5073175
+        hash = "0xe241601070eaf03c82fa5565933fcf558d6fdd80789102eb9560e2380ea6081a"
+        parent_hash = "0xfc132abf7bdb0184eabb922d2a74ea98753917c77392d01c60687ba98b98d2ca"
+    "#;
+
+    fn config() -> SpecConfig {
+        toml::from_str(CONFIG).unwrap()
+    }
+
+    #[test]
+    fn test_build_spec_from_config() {
+        let genesis = config().validate().unwrap();
+        assert_eq!(genesis.assets.len(), 2);
+        assert_eq!(
+            genesis.assets[0].liquidity_factor,
+            Factor::from_nominal("0.8")
+        );
+        assert_eq!(genesis.assets[1].ticker, Ticker::new("USD"));
+        assert_eq!(genesis.assets[1].supply_cap, 1000000000000);
+        assert_eq!(genesis.genesis_blocks[0].chain_id(), ChainId::Eth);
+
+        config().build().unwrap().build_storage().unwrap();
+    }
+
+    #[test]
+    fn test_invalid_configs() {
+        let mut bad_address = config();
+        bad_address.reporters.push("0x1234".into());
+        assert!(bad_address.validate().is_err());
+
+        let mut bad_decimals = config();
+        bad_decimals.assets[0].decimals = 77;
+        assert!(bad_decimals.validate().is_err());
+
+        let mut bad_factor = config();
+        bad_factor.assets[0].liquidity_factor = "1.5".into();
+        assert!(bad_factor.validate().is_err());
+
+        let mut duplicate_asset = config();
+        duplicate_asset.assets[1].asset = duplicate_asset.assets[0].asset.clone();
+        assert_eq!(
+            duplicate_asset.validate().err(),
+            Some(format!(
+                "Duplicate asset: {:?}",
+                ChainAsset::from_str(&duplicate_asset.assets[0].asset).unwrap()
+            ))
+        );
+
+        let mut orphan_block = config();
+        orphan_block.starports.clear();
+        assert!(orphan_block.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_nominal() {
+        assert_eq!(parse_nominal("0.8", 4), Ok(8000));
+        assert_eq!(parse_nominal("12", 2), Ok(1200));
+        assert!(parse_nominal("0.123", 2).is_err());
+        assert!(parse_nominal("-1", 2).is_err());
+        assert!(parse_nominal(".5", 2).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use ethereum_client::{
+    encode_block_hash_hex, encode_block_number_hex, parse_u64, parse_word, BlockResponse,
+    EthereumBlock, EthereumBlockId,
+};
+use hyper::{Body, Client, Method, Request};
+use hyper_rustls::HttpsConnector;
+use our_std::info;
+use pallet_cash::chains::{Chain, ChainBlock, ChainId, Ethereum, Polygon};
+use runtime_interfaces::validator_config_interface;
+
+use crate::chain_spec::ChainSpec;
+
+/// How long to wait for a start block to be fetched, if no timeout is configured for the RPC.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A block of an underlying chain to start ingressing from, e.g. `ETH:5073175` or `ETH:0x...`.
+#[derive(Clone, Debug)]
+pub struct StartBlock {
+    pub chain_id: ChainId,
+    pub block_id: EthereumBlockId,
+}
+
+impl FromStr for StartBlock {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chain, block) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Start block must be <chain>:<number-or-hash>: {}", s))?;
+        let chain_id = match ChainId::from_str(chain) {
+            Ok(chain_id @ ChainId::Eth) | Ok(chain_id @ ChainId::Matic) => chain_id,
+            _ => return Err(format!("Unsupported start block chain: {}", chain)),
+        };
+        let block_id = if block.starts_with("0x") {
+            EthereumBlockId::Hash(
+                <Ethereum as Chain>::str_to_hash(block)
+                    .map_err(|_| format!("Invalid start block hash: {}", block))?,
+            )
+        } else {
+            EthereumBlockId::Number(
+                block
+                    .parse()
+                    .map_err(|_| format!("Invalid start block number: {}", block))?,
+            )
+        };
+        Ok(StartBlock { chain_id, block_id })
+    }
+}
+
+fn rpc_url(chain_id: ChainId) -> Result<String, String> {
+    match chain_id {
+        ChainId::Eth => validator_config_interface::get_eth_rpc_url(),
+        ChainId::Matic => validator_config_interface::get_matic_rpc_url(),
+        _ => None,
+    }
+    .ok_or_else(|| format!("No RPC URL configured for {:?}", chain_id))
+}
+
+async fn request_block(url: &str, body: String) -> Result<String, String> {
+    let (timeout_ms, headers) = validator_config_interface::get_http_options(url);
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri(url)
+        .header("Content-Type", "application/json");
+    for (name, value) in headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    let request = request
+        .body(Body::from(body))
+        .map_err(|e| format!("Invalid request to {}: {}", url, e))?;
+
+    let client = Client::builder().build::<_, Body>(HttpsConnector::new());
+    let timeout = timeout_ms.map_or(DEFAULT_TIMEOUT, Duration::from_millis);
+    let response = tokio::time::timeout(timeout, client.request(request))
+        .await
+        .map_err(|_| format!("Timed out requesting {}", url))?
+        .map_err(|e| format!("Failed to request {}: {}", url, e))?;
+    let bytes = hyper::body::to_bytes(response.into_body())
+        .await
+        .map_err(|e| format!("Failed to read response from {}: {}", url, e))?;
+    String::from_utf8(bytes.to_vec()).map_err(|e| format!("Invalid response from {}: {}", url, e))
+}
+
+/// Fetch a start block from its chain's configured RPC URL, which also checks that it exists.
+pub fn fetch_start_block(start: &StartBlock) -> Result<ChainBlock, String> {
+    let url = rpc_url(start.chain_id)?;
+    let (method, param) = match start.block_id {
+        EthereumBlockId::Hash(hash) => ("eth_getBlockByHash", encode_block_hash_hex(hash)),
+        EthereumBlockId::Number(number) => {
+            ("eth_getBlockByNumber", encode_block_number_hex(number))
+        }
+    };
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": [param, false],
+        "id": 1,
+    })
+    .to_string();
+
+    let mut runtime = tokio::runtime::Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to start runtime: {}", e))?;
+    let response = runtime.block_on(request_block(&url, body))?;
+    let response: BlockResponse = serde_json::from_str(&response)
+        .map_err(|_| format!("Invalid block response from {}: {}", url, response))?;
+    if let Some(error) = response.error {
+        return Err(format!(
+            "Failed to get start block {:?}: {:?}",
+            start, error
+        ));
+    }
+    let block = response
+        .result
+        .ok_or_else(|| format!("Start block {:?} does not exist", start))?;
+
+    let block = EthereumBlock {
+        hash: parse_word(block.hash).ok_or("Start block has a bad hash")?,
+        parent_hash: parse_word(block.parentHash).ok_or("Start block has a bad parent hash")?,
+        number: parse_u64(block.number).ok_or("Start block has a bad number")?,
+        timestamp: parse_u64(block.timestamp).unwrap_or_default(),
+        events: vec![],
+    };
+    info!(
+        "Starting {:?} from block {} ({})",
+        start.chain_id,
+        block.number,
+        encode_block_hash_hex(block.hash)
+    );
+    match start.chain_id {
+        ChainId::Matic => Ok(<Polygon as Chain>::chain_block(block)),
+        _ => Ok(<Ethereum as Chain>::chain_block(block)),
+    }
+}
+
+/// Replace the genesis blocks of a chain spec for the chains of the given blocks.
+/// Only a chain spec which is not yet raw can have its genesis blocks replaced.
+pub fn set_genesis_blocks(spec: ChainSpec, blocks: Vec<ChainBlock>) -> Result<ChainSpec, String> {
+    let mut json: serde_json::Value = serde_json::from_str(&spec.as_json(false)?)
+        .map_err(|e| format!("Invalid chain spec: {}", e))?;
+    let genesis_blocks = json
+        .pointer_mut("/genesis/runtime/palletCash/genesisBlocks")
+        .ok_or("Cannot set the start blocks of a raw chain spec")?;
+
+    let mut merged: Vec<ChainBlock> = serde_json::from_value(genesis_blocks.take())
+        .map_err(|e| format!("Invalid genesis blocks: {}", e))?;
+    merged.retain(|old| !blocks.iter().any(|new| new.chain_id() == old.chain_id()));
+    merged.extend(blocks);
+    *genesis_blocks =
+        serde_json::to_value(merged).map_err(|e| format!("Invalid genesis blocks: {}", e))?;
+
+    let bytes = serde_json::to_vec(&json).map_err(|e| format!("Invalid chain spec: {}", e))?;
+    ChainSpec::from_json_bytes(bytes)
+}
+
+/// Fetch the start blocks and make them the genesis blocks of the chain spec.
+pub fn with_start_blocks(
+    spec: ChainSpec,
+    start_blocks: &[StartBlock],
+) -> Result<ChainSpec, String> {
+    let blocks = start_blocks
+        .iter()
+        .map(fetch_start_block)
+        .collect::<Result<Vec<_>, _>>()?;
+    set_genesis_blocks(spec, blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_spec::development_config;
+    use gateway_runtime::BuildStorage;
+
+    #[test]
+    fn test_parse_start_block() {
+        assert!(matches!(
+            StartBlock::from_str("ETH:5073175"),
+            Ok(StartBlock {
+                chain_id: ChainId::Eth,
+                block_id: EthereumBlockId::Number(5073175),
+            })
+        ));
+        assert!(matches!(
+            StartBlock::from_str(
+                "MATIC:0xe241601070eaf03c82fa5565933fcf558d6fdd80789102eb9560e2380ea6081a"
+            ),
+            Ok(StartBlock {
+                chain_id: ChainId::Matic,
+                block_id: EthereumBlockId::Hash(_),
+            })
+        ));
+        assert!(StartBlock::from_str("ETH").is_err());
+        assert!(StartBlock::from_str("DOT:1").is_err());
+        assert!(StartBlock::from_str("ETH:latest").is_err());
+        assert!(StartBlock::from_str("ETH:0x1234").is_err());
+    }
+
+    #[test]
+    fn test_set_genesis_blocks() {
+        let block = EthereumBlock {
+            hash: [1; 32],
+            parent_hash: [2; 32],
+            number: 3,
+            timestamp: 0,
+            events: vec![],
+        };
+        let spec =
+            set_genesis_blocks(development_config(), vec![ChainBlock::Eth(block.clone())]).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+        let genesis_blocks: Vec<ChainBlock> = serde_json::from_value(
+            json.pointer("/genesis/runtime/palletCash/genesisBlocks")
+                .unwrap()
+                .clone(),
+        )
+        .unwrap();
+        assert_eq!(genesis_blocks, vec![ChainBlock::Eth(block)]);
+        spec.build_storage().unwrap();
+
+        let raw = ChainSpec::from_json_bytes(spec.as_json(true).unwrap().into_bytes()).unwrap();
+        assert!(set_genesis_blocks(raw, vec![]).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use futures::{future, StreamExt, TryStreamExt};
+use jsonrpc_core::{
+    futures::{Future, Sink},
+    Error as RpcError, Result as RpcResult,
+};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
+};
+
+use pallet_cash::{
+    chains::ChainAccount,
+    types::{AssetAmount, CashOrChainAsset},
+    Event as CashEvent,
+};
+
+use our_std::warn;
+use pallet_cash_runtime_api::CashApi as CashRuntimeApi;
+
+use types_derive::Types;
+
+use crate::api::{api_asset, chain_err, parse_asset};
+
+#[derive(Default, Deserialize, Serialize, Types)]
+pub struct ApiProtocolEvent {
+    pub block_number: u32,
+    pub block_hash: String,
+    pub kind: String,
+    pub asset: Option<String>,
+    pub collateral_asset: Option<String>,
+    pub sender: Option<ChainAccount>,
+    pub recipient: Option<ChainAccount>,
+    pub amount: Option<String>,
+    pub era_id: Option<u32>,
+    pub era_index: Option<u32>,
+    pub encoded_notice: Option<String>,
+}
+
+#[rpc]
+pub trait GatewayEventsApi {
+    type Metadata;
+
+    #[pubsub(
+        subscription = "gateway_accountEvents",
+        subscribe,
+        name = "gateway_subscribeAccountEvents"
+    )]
+    fn subscribe_account_events(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<Vec<ApiProtocolEvent>>,
+        account: ChainAccount,
+    );
+
+    #[pubsub(
+        subscription = "gateway_accountEvents",
+        unsubscribe,
+        name = "gateway_unsubscribeAccountEvents"
+    )]
+    fn unsubscribe_account_events(
+        &self,
+        metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> RpcResult<bool>;
+
+    #[pubsub(
+        subscription = "gateway_marketEvents",
+        subscribe,
+        name = "gateway_subscribeMarketEvents"
+    )]
+    fn subscribe_market_events(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<Vec<ApiProtocolEvent>>,
+        asset: String,
+    );
+
+    #[pubsub(
+        subscription = "gateway_marketEvents",
+        unsubscribe,
+        name = "gateway_unsubscribeMarketEvents"
+    )]
+    fn unsubscribe_market_events(
+        &self,
+        metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> RpcResult<bool>;
+}
+
+pub struct GatewayEventsHandler<C, B> {
+    client: Arc<C>,
+    manager: SubscriptionManager,
+    _block: PhantomData<B>,
+}
+
+impl<C, B> GatewayEventsHandler<C, B> {
+    pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+        Self {
+            client,
+            manager: SubscriptionManager::new(Arc::new(executor)),
+            _block: Default::default(),
+        }
+    }
+}
+
+impl<C, B> GatewayEventsHandler<C, B>
+where
+    B: BlockT,
+    C: 'static + Send + Sync + ProvideRuntimeApi<B> + BlockchainEvents<B>,
+    C::Api: CashRuntimeApi<B>,
+{
+    /// Pushes the protocol events involving the accounts or assets a subscriber is interested in,
+    ///  as each block is finalized. Blocks without any such events are skipped.
+    fn subscribe(
+        &self,
+        subscriber: Subscriber<Vec<ApiProtocolEvent>>,
+        interested: impl Fn(&[ChainAccount], &[CashOrChainAsset]) -> bool + Send + 'static,
+    ) {
+        let client = self.client.clone();
+        let stream = self
+            .client
+            .finality_notification_stream()
+            .filter_map(move |notification| {
+                let block_number = (*notification.header.number()).unique_saturated_into();
+                let block_hash = format!("{:?}", notification.hash);
+                let events = match client
+                    .runtime_api()
+                    .get_cash_events(&BlockId::hash(notification.hash))
+                {
+                    Ok(Ok(events)) => events,
+                    err => {
+                        warn!("Failed to get events for block {}: {:?}", block_hash, err);
+                        vec![]
+                    }
+                };
+                let matching: Vec<ApiProtocolEvent> =
+                    block_protocol_events(events, block_number, &block_hash)
+                        .into_iter()
+                        .filter(|(_, accounts, assets)| interested(accounts, assets))
+                        .map(|(event, _, _)| event)
+                        .collect();
+                future::ready(if matching.is_empty() {
+                    None
+                } else {
+                    Some(Ok::<_, ()>(Ok::<_, RpcError>(matching)))
+                })
+            })
+            .compat();
+
+        self.manager.add(subscriber, |sink| {
+            sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e))
+                .send_all(stream)
+                .map(|_| ())
+        });
+    }
+}
+
+impl<C, B> GatewayEventsApi for GatewayEventsHandler<C, B>
+where
+    B: BlockT,
+    C: 'static + Send + Sync + ProvideRuntimeApi<B> + BlockchainEvents<B>,
+    C::Api: CashRuntimeApi<B>,
+{
+    type Metadata = sc_rpc_api::Metadata;
+
+    fn subscribe_account_events(
+        &self,
+        _metadata: Self::Metadata,
+        subscriber: Subscriber<Vec<ApiProtocolEvent>>,
+        account: ChainAccount,
+    ) {
+        self.subscribe(subscriber, move |accounts, _| accounts.contains(&account))
+    }
+
+    fn unsubscribe_account_events(
+        &self,
+        _metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> RpcResult<bool> {
+        Ok(self.manager.cancel(id))
+    }
+
+    fn subscribe_market_events(
+        &self,
+        _metadata: Self::Metadata,
+        subscriber: Subscriber<Vec<ApiProtocolEvent>>,
+        asset: String,
+    ) {
+        match parse_asset(&asset) {
+            Ok(asset) => self.subscribe(subscriber, move |_, assets| assets.contains(&asset)),
+            Err(reason) => {
+                let _ = subscriber.reject(chain_err(reason));
+            }
+        }
+    }
+
+    fn unsubscribe_market_events(
+        &self,
+        _metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> RpcResult<bool> {
+        Ok(self.manager.cancel(id))
+    }
+}
+
+/// Converts the events of a block into its protocol events, with the accounts and assets each involves.
+pub fn block_protocol_events(
+    events: Vec<CashEvent>,
+    block_number: u32,
+    block_hash: &str,
+) -> Vec<(ApiProtocolEvent, Vec<ChainAccount>, Vec<CashOrChainAsset>)> {
+    events
+        .into_iter()
+        .filter_map(protocol_event)
+        .map(|(event, accounts, assets)| {
+            let event = ApiProtocolEvent {
+                block_number,
+                block_hash: block_hash.into(),
+                ..event
+            };
+            (event, accounts, assets)
+        })
+        .collect()
+}
+
+/// Converts an event into the protocol event pushed to subscribers, with the accounts and assets it involves.
+/// Returns None for events which are not protocol events, such as those for governance or validators.
+fn protocol_event(
+    event: CashEvent,
+) -> Option<(ApiProtocolEvent, Vec<ChainAccount>, Vec<CashOrChainAsset>)> {
+    let transfer = |kind: &str,
+                    asset: CashOrChainAsset,
+                    sender: ChainAccount,
+                    recipient: ChainAccount,
+                    amount: AssetAmount| {
+        let event = ApiProtocolEvent {
+            kind: kind.into(),
+            asset: Some(api_asset(asset)),
+            sender: Some(sender),
+            recipient: Some(recipient),
+            amount: Some(format!("{}", amount)),
+            ..Default::default()
+        };
+        Some((event, vec![sender, recipient], vec![asset]))
+    };
+    let liquidate = |kind: &str,
+                     asset: CashOrChainAsset,
+                     collateral_asset: CashOrChainAsset,
+                     liquidator: ChainAccount,
+                     borrower: ChainAccount,
+                     amount: AssetAmount| {
+        let event = ApiProtocolEvent {
+            kind: kind.into(),
+            asset: Some(api_asset(asset)),
+            collateral_asset: Some(api_asset(collateral_asset)),
+            sender: Some(liquidator),
+            recipient: Some(borrower),
+            amount: Some(format!("{}", amount)),
+            ..Default::default()
+        };
+        Some((
+            event,
+            vec![liquidator, borrower],
+            vec![asset, collateral_asset],
+        ))
+    };
+
+    use CashOrChainAsset::{Cash, ChainAsset};
+    match event {
+        CashEvent::Locked(asset, sender, recipient, amount, _) => {
+            transfer("Locked", ChainAsset(asset), sender, recipient, amount)
+        }
+        CashEvent::LockedCash(sender, recipient, principal, _) => {
+            transfer("LockedCash", Cash, sender, recipient, principal.0)
+        }
+        CashEvent::Extract(asset, sender, recipient, amount) => {
+            transfer("Extract", ChainAsset(asset), sender, recipient, amount)
+        }
+        CashEvent::ExtractCash(sender, recipient, principal, _) => {
+            transfer("ExtractCash", Cash, sender, recipient, principal.0)
+        }
+        CashEvent::Transfer(asset, sender, recipient, amount) => {
+            transfer("Transfer", ChainAsset(asset), sender, recipient, amount)
+        }
+        CashEvent::TransferCash(sender, recipient, principal, _) => {
+            transfer("TransferCash", Cash, sender, recipient, principal.0)
+        }
+        CashEvent::Liquidate(asset, collateral_asset, liquidator, borrower, amount) => liquidate(
+            "Liquidate",
+            ChainAsset(asset),
+            ChainAsset(collateral_asset),
+            liquidator,
+            borrower,
+            amount,
+        ),
+        CashEvent::LiquidateCash(collateral_asset, liquidator, borrower, principal, _) => {
+            liquidate(
+                "LiquidateCash",
+                Cash,
+                ChainAsset(collateral_asset),
+                liquidator,
+                borrower,
+                principal.0,
+            )
+        }
+        CashEvent::LiquidateCashCollateral(asset, liquidator, borrower, amount) => liquidate(
+            "LiquidateCashCollateral",
+            ChainAsset(asset),
+            Cash,
+            liquidator,
+            borrower,
+            amount,
+        ),
+        CashEvent::Notice(notice_id, notice, encoded_notice) => {
+            let event = ApiProtocolEvent {
+                kind: "Notice".into(),
+                era_id: Some(notice_id.era_id()),
+                era_index: Some(notice_id.era_index()),
+                encoded_notice: Some(format!("0x{}", hex::encode(encoded_notice))),
+                ..Default::default()
+            };
+            Some((event, notice.accounts(), notice.assets()))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pallet_cash::{
+        chains::ChainAsset,
+        notices::{ExtractionNotice, Notice, NoticeId},
+        types::CashPrincipalAmount,
+    };
+
+    #[test]
+    fn test_protocol_event() {
+        let alice = ChainAccount::Eth([1; 20]);
+        let bob = ChainAccount::Eth([2; 20]);
+        let eth = ChainAsset::Eth([0xee; 20]);
+
+        let (event, accounts, assets) =
+            protocol_event(CashEvent::Transfer(eth, alice, bob, 100)).unwrap();
+        assert_eq!(event.kind, "Transfer");
+        assert_eq!(event.amount, Some("100".into()));
+        assert_eq!(accounts, vec![alice, bob]);
+        assert_eq!(assets, vec![CashOrChainAsset::ChainAsset(eth)]);
+
+        let (event, accounts, assets) = protocol_event(CashEvent::LiquidateCash(
+            eth,
+            alice,
+            bob,
+            CashPrincipalAmount(5),
+            Default::default(),
+        ))
+        .unwrap();
+        assert_eq!(event.asset, Some("CASH".into()));
+        assert_eq!(event.collateral_asset, Some(String::from(eth)));
+        assert_eq!(accounts, vec![alice, bob]);
+        assert_eq!(
+            assets,
+            vec![CashOrChainAsset::Cash, CashOrChainAsset::ChainAsset(eth)]
+        );
+
+        let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+            id: NoticeId(1, 2),
+            parent: [0; 32],
+            asset: [0xee; 20],
+            account: [2; 20],
+            amount: 50,
+        });
+        let (event, accounts, assets) =
+            protocol_event(CashEvent::Notice(NoticeId(1, 2), notice, vec![0xab])).unwrap();
+        assert_eq!(event.kind, "Notice");
+        assert_eq!((event.era_id, event.era_index), (Some(1), Some(2)));
+        assert_eq!(event.encoded_notice, Some("0xab".into()));
+        assert_eq!(accounts, vec![bob]);
+        assert_eq!(assets, vec![CashOrChainAsset::ChainAsset(eth)]);
+
+        assert!(protocol_event(CashEvent::NonceCancelled(alice, 1)).is_none());
+    }
+}
@@ -5,9 +5,47 @@ pub use log;
 // This file includes macros to make it easy to log from either an
 // std or no_std environment. Just use log!("My Log: {}", 5); and
 // things should just magically work.
+//
+// Any of them also takes a target, and structured fields after a `;`, e.g.
+//  debug!(target: targets::EVENTS, "Fetched block"; chain_id = chain_id, number = n);
+// which logs `Fetched block chain_id=Eth number=5` to `gateway::events`.
+
+/// The targets of each subsystem, whose verbosity can be raised on their own,
+/// e.g. `-l gateway::events=trace` to diagnose a chain which is stuck ingesting.
+pub mod targets {
+    /// Auditing the invariants of the protocol's aggregates.
+    pub const AUDIT: &str = "gateway::audit";
+    /// Fetching, tallying and ingressing the blocks and events of underlying chains.
+    pub const EVENTS: &str = "gateway::events";
+    /// Signing, publishing and executing notices.
+    pub const NOTICES: &str = "gateway::notices";
+    /// Fetching and posting prices.
+    pub const ORACLE: &str = "gateway::oracle";
+    /// Checking and committing the balance changes of a transaction.
+    pub const PIPELINE: &str = "gateway::pipeline";
+}
+
+/// Logs a message with structured fields appended as `key=value`, each value formatted by `Debug`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_fields {
+    ($level:ident, $target:expr, $msg:literal $(, $arg:expr)* ; $($key:ident = $value:expr),+ $(,)?) => {{
+        $crate::log::log::$level!(
+            target: $target,
+            concat!($msg $(, " ", stringify!($key), "={:?}")+)
+            $(, $arg)* $(, $value)+
+        );
+    }};
+}
 
 #[macro_export]
 macro_rules! log {
+    (target: $target:expr, $msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(info, $target, $msg $(, $arg)* ; $($fields)+)
+    };
+    ($msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(info, module_path!(), $msg $(, $arg)* ; $($fields)+)
+    };
     ($($arg:tt)*) => {{
         $crate::log::log::info!($($arg)*);
     }}
@@ -15,6 +53,12 @@ macro_rules! log {
 
 #[macro_export]
 macro_rules! debug {
+    (target: $target:expr, $msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(debug, $target, $msg $(, $arg)* ; $($fields)+)
+    };
+    ($msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(debug, module_path!(), $msg $(, $arg)* ; $($fields)+)
+    };
     ($($arg:tt)*) => {{
         $crate::log::log::debug!($($arg)*);
     }}
@@ -22,6 +66,12 @@ macro_rules! debug {
 
 #[macro_export]
 macro_rules! trace {
+    (target: $target:expr, $msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(trace, $target, $msg $(, $arg)* ; $($fields)+)
+    };
+    ($msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(trace, module_path!(), $msg $(, $arg)* ; $($fields)+)
+    };
     ($($arg:tt)*) => {{
         $crate::log::log::trace!($($arg)*);
     }}
@@ -29,6 +79,12 @@ macro_rules! trace {
 
 #[macro_export]
 macro_rules! info {
+    (target: $target:expr, $msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(info, $target, $msg $(, $arg)* ; $($fields)+)
+    };
+    ($msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(info, module_path!(), $msg $(, $arg)* ; $($fields)+)
+    };
     ($($arg:tt)*) => {{
         $crate::log::log::info!($($arg)*);
     }}
@@ -36,6 +92,12 @@ macro_rules! info {
 
 #[macro_export]
 macro_rules! warn {
+    (target: $target:expr, $msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(warn, $target, $msg $(, $arg)* ; $($fields)+)
+    };
+    ($msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(warn, module_path!(), $msg $(, $arg)* ; $($fields)+)
+    };
     ($($arg:tt)*) => {{
         $crate::log::log::warn!($($arg)*);
     }}
@@ -43,6 +105,12 @@ macro_rules! warn {
 
 #[macro_export]
 macro_rules! error {
+    (target: $target:expr, $msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(error, $target, $msg $(, $arg)* ; $($fields)+)
+    };
+    ($msg:literal $(, $arg:expr)* ; $($fields:tt)+) => {
+        $crate::__log_fields!(error, module_path!(), $msg $(, $arg)* ; $($fields)+)
+    };
     ($($arg:tt)*) => {{
         $crate::log::log::error!($($arg)*);
     }}
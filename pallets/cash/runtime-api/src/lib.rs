@@ -1,14 +1,22 @@
 use pallet_cash::{
-    chains::{ChainAccount, ChainAsset},
+    chains::{ChainAccount, ChainAsset, ChainId, ChainSignatureList, ChainStarport},
     core::BTreeMap,
+    notices::{EncodedNotice, NoticeId},
     portfolio::Portfolio,
     rates::APR,
     reason::Reason,
     types::{
-        AssetAmount, AssetBalance, AssetInfo, Balance, CashIndex, CashPrincipal, ValidatorKeys,
+        ActivityEntry, AssetAmount, AssetBalance, AssetInfo, Balance, CashIndex, CashOrChainAsset,
+        CashPrincipal, ChainHealth, Factor, GovernanceResult, MarketSummary, ProtocolMetrics,
+        SessionEarnings, SubstrateId, TrxRequestEstimate, ValidatorKeys, ValidatorMetadata,
+        ValidatorParticipation,
     },
+    Event as CashEvent,
+};
+use pallet_oracle::{
+    ticker::Ticker,
+    types::{AssetPrice, Timestamp},
 };
-use pallet_oracle::{ticker::Ticker, types::AssetPrice};
 
 sp_api::decl_runtime_apis! {
     pub trait CashApi {
@@ -18,12 +26,19 @@ sp_api::decl_runtime_apis! {
         fn get_cash_data() -> Result<(CashIndex, CashPrincipal, Balance), Reason>;
         fn get_full_cash_balance(account: ChainAccount) -> Result<AssetBalance, Reason>;
         fn get_liquidity(account: ChainAccount) -> Result<AssetBalance, Reason>;
+        fn get_logical_cash_balance(account: ChainAccount) -> Result<AssetBalance, Reason>;
+        fn get_logical_liquidity(account: ChainAccount) -> Result<AssetBalance, Reason>;
+        fn get_health_factor(account: ChainAccount) -> Result<Option<Factor>, Reason>;
+        fn get_max_extract(account: ChainAccount, asset: CashOrChainAsset) -> Result<AssetAmount, Reason>;
         fn get_market_totals(asset: ChainAsset) -> Result<(AssetAmount, AssetAmount), Reason>;
+        fn get_markets_summary() -> Result<Vec<MarketSummary>, Reason>;
         fn get_price(ticker: String) -> Result<AssetPrice, Reason>;
         fn get_price_with_ticker(ticker: Ticker) -> Result<AssetPrice, Reason>;
+        fn get_price_history(ticker: String, count: u32) -> Result<Vec<(Timestamp, AssetPrice)>, Reason>;
         fn get_rates(asset: ChainAsset) -> Result<(APR, APR), Reason>;
         fn get_assets() -> Result<Vec<AssetInfo>, Reason>;
         fn get_accounts() -> Result<Vec<ChainAccount>, Reason>;
+        fn get_accounts_page(start_key: Option<ChainAccount>, limit: u32) -> Result<Vec<ChainAccount>, Reason>;
         fn get_asset_meta() -> Result<
                 (
                     BTreeMap<String, u32>,
@@ -34,7 +49,21 @@ sp_api::decl_runtime_apis! {
             Reason,
             >;
         fn get_accounts_liquidity() -> Result<Vec<(ChainAccount, String)>, Reason>;
+        fn get_accounts_liquidity_page(start_key: Option<ChainAccount>, limit: u32, below: Option<AssetBalance>) -> Result<Vec<(ChainAccount, String)>, Reason>;
+        fn get_liquidatable_accounts(max_results: u32) -> Result<Vec<(ChainAccount, AssetBalance, Option<CashOrChainAsset>, Option<CashOrChainAsset>)>, Reason>;
         fn get_portfolio(account: ChainAccount) -> Result<Portfolio, Reason>;
+        fn get_logical_portfolio(account: ChainAccount) -> Result<Portfolio, Reason>;
+        fn get_account_history(account: ChainAccount, from: u32, limit: u32) -> Result<Vec<ActivityEntry>, Reason>;
         fn get_validator_info() -> Result<(Vec<ValidatorKeys>, Vec<(ChainAccount, String)>), Reason>;
+        fn get_validator_participation() -> Result<(ValidatorParticipation, Vec<(ValidatorKeys, ValidatorParticipation)>), Reason>;
+        fn get_validator_metadata() -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>)>, Reason>;
+        fn get_validator_earnings(substrate_id: SubstrateId, last_n_sessions: u32) -> Result<Vec<SessionEarnings>, Reason>;
+        fn get_pending_notices(account: ChainAccount) -> Result<Vec<(NoticeId, EncodedNotice, ChainSignatureList)>, Reason>;
+        fn simulate_notice(chain_id: ChainId, notice_id: NoticeId) -> Result<(ChainStarport, Vec<u8>, Option<u64>), Reason>;
+        fn simulate_governance(actions: Vec<Vec<u8>>) -> Result<Vec<GovernanceResult>, Reason>;
+        fn estimate_trx_request(request: Vec<u8>, account: ChainAccount) -> Result<TrxRequestEstimate, Reason>;
+        fn get_cash_events() -> Result<Vec<CashEvent>, Reason>;
+        fn get_chain_health() -> Result<Vec<ChainHealth>, Reason>;
+        fn get_protocol_metrics() -> Result<ProtocolMetrics, Reason>;
     }
 }
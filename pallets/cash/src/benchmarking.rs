@@ -36,7 +36,7 @@ const ALICE_ADDRESS: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
 const BOB_ADDRESS: &str = "0x59a055a3e566F5d9A9Ea1dA81aB375D5361D7c5e";
 const BOB_ADDRESS_BYTES: [u8; 20] = hex!("59a055a3e566F5d9A9Ea1dA81aB375D5361D7c5e");
 
-const MIN_TX_VALUE: u128 = params::MIN_TX_VALUE.value;
+const MIN_TX_VALUE: u128 = pallet_parameters::DEFAULT_MIN_TX_VALUE;
 
 // endow token to user, create market, add some dummy data
 fn endow_tkn<T: Config>(
@@ -76,53 +76,86 @@ fn init_asset_balance(asset: ChainAsset, account: ChainAccount, balance: AssetBa
     AssetsWithNonZeroBalance::insert(account, asset, ());
 }
 
-fn construct_reorg(num_events: u32) -> (ChainReorg, ethereum_client::EthereumBlock) {
-    let mut events = vec![];
-
+fn lock_events(
+    asset: [u8; 20],
+    amount: AssetAmount,
+    num_events: u32,
+) -> Vec<ethereum_client::EthereumEvent> {
     let event = ethereum_client::EthereumEvent::Lock {
-        asset: [238; 20],
+        asset,
         sender: [3; 20],
         chain: String::from("ETH"),
         recipient: [4; 32],
-        amount: Quantity::from_nominal("10", ETH_UNIT).value,
+        amount,
+        reference: None,
     };
 
-    for _i in 0..num_events {
-        events.push(event.clone());
+    (0..num_events).map(|_| event.clone()).collect()
+}
+
+// build a chain of blocks on top of the given parent, all events go in the first block
+fn construct_blocks(
+    parent_hash: [u8; 32],
+    parent_number: u64,
+    first_hash: u8,
+    num_blocks: u32,
+    mut events: Vec<ethereum_client::EthereumEvent>,
+) -> Vec<ethereum_client::EthereumBlock> {
+    let mut blocks = vec![];
+    let mut parent_hash = parent_hash;
+    for i in 0..num_blocks {
+        let hash = [first_hash + i as u8; 32];
+        blocks.push(ethereum_client::EthereumBlock {
+            hash,
+            parent_hash,
+            number: parent_number + 1 + i as u64,
+            timestamp: 0,
+            events: sp_std::mem::take(&mut events),
+        });
+        parent_hash = hash;
     }
+    blocks
+}
 
-    let last_hash = [4; 32];
-    let chain_id = chains::ChainId::Eth;
-    let last_block = ethereum_client::EthereumBlock {
-        hash: last_hash,
-        parent_hash: [1; 32],
-        number: 1,
-        events: vec![],
-    };
-    LastProcessedBlock::insert(chain_id, ChainBlock::Eth(last_block));
+fn construct_reorg(depth: u32, num_events: u32) -> (ChainReorg, ethereum_client::EthereumBlock) {
+    let events = lock_events(
+        [238; 20],
+        Quantity::from_nominal("10", ETH_UNIT).value,
+        num_events,
+    );
 
-    let reorg_block = ethereum_client::EthereumBlock {
-        hash: [1; 32],
-        parent_hash: last_hash,
-        number: 2,
-        events: events.clone(),
-    };
-
-    let real_block = ethereum_client::EthereumBlock {
-        hash: [1; 32],
+    let ancestor_hash = [4; 32];
+    let chain_id = chains::ChainId::Eth;
+    let reorg_blocks = construct_blocks(ancestor_hash, 1, 50, depth, events.clone());
+    let real_blocks = construct_blocks(ancestor_hash, 1, 150, depth, events);
+    let last_block = reorg_blocks.last().unwrap().clone();
+    let last_hash = last_block.hash;
+    LastProcessedBlock::insert(chain_id, ChainBlock::Eth(last_block.clone()));
+    AncestorHashes::insert(
+        chain_id,
+        [ChainHash::Eth(ancestor_hash)]
+            .iter()
+            .cloned()
+            .chain(reorg_blocks.iter().map(|b| ChainHash::Eth(b.hash)))
+            .collect::<Vec<_>>(),
+    );
+
+    let pending_block = ethereum_client::EthereumBlock {
+        hash: [3; 32],
         parent_hash: last_hash,
-        number: 2,
-        events: events,
+        number: last_block.number + 1,
+        timestamp: 0,
+        events: vec![],
     };
 
     let reorg = ChainReorg::Eth {
         from_hash: last_hash,
-        to_hash: [1; 32],
-        reverse_blocks: vec![reorg_block.clone()],
-        forward_blocks: vec![real_block.clone()],
+        to_hash: real_blocks.last().unwrap().hash,
+        reverse_blocks: reorg_blocks.into_iter().rev().collect(),
+        forward_blocks: real_blocks,
     };
 
-    (reorg, reorg_block)
+    (reorg, pending_block)
 }
 
 benchmarks! {
@@ -139,12 +172,13 @@ benchmarks! {
         let z in 0 .. 10;
         let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
         for i in 0..z {
-            let i: u8 = z.try_into().unwrap();
+            let i: u8 = i.try_into().unwrap();
             let addr_bytes: [u8; 20] = [i; 20];
             let extract_amt: i128 = MIN_TX_VALUE.try_into().unwrap();
             endow_tkn::<T>(signer_vec, extract_amt, addr_bytes);
         }
-        <pallet_timestamp::Now<T>>::put(1u64);
+        // far enough along for every asset to be swept
+        <pallet_timestamp::Now<T>>::put(params::ACCRUAL_SWEEP_INTERVAL + 1);
         LastYieldTimestamp::put(1u64);
         LastBlockTimestamp::put(1u64);
 
@@ -185,6 +219,7 @@ benchmarks! {
             ValidatorKeys {
                 substrate_id,
                 eth_address,
+                next_eth_key: None,
             },
         );
 
@@ -208,7 +243,11 @@ benchmarks! {
         assert_eq!(Cash::<T>::set_yield_next(RawOrigin::Root.into(), APR(100).into(), 86400500), Ok(()));
     }
 
+    // test blocks deep enough for their events to mature, and up to 100 events
     receive_chain_blocks {
+        let b in 4 .. 10;
+        let z in 0 .. 100;
+        <pallet_timestamp::Now<T>>::put(1u64);
         let substrate_id = AccountId32::new([12u8; 32]);
         let eth_address = <Ethereum as Chain>::signer_address().unwrap();
         LastProcessedBlock::insert(
@@ -218,6 +257,7 @@ benchmarks! {
                     hash: [22; 32],
                     parent_hash: [1; 32],
                     number: 0,
+                    timestamp: 0,
                     events: vec![],
                 }
             )
@@ -227,16 +267,23 @@ benchmarks! {
             ValidatorKeys {
                 substrate_id,
                 eth_address,
+                next_eth_key: None,
             },
         );
-        let blocks = ChainBlocks::Eth(vec![]);
+        endow_tkn::<T>(BOB_ADDRESS_BYTES, 0, TKN_ADDR_BYTES);
+        let events = lock_events(TKN_ADDR_BYTES, 1_000_000, z);
+        let blocks = ChainBlocks::Eth(construct_blocks([22; 32], 0, 100, b, events));
         let signature = ChainSignature::Eth(<Ethereum as Chain>::sign_message(&blocks.encode()).unwrap());
     }: {
         assert_ok!(Cash::<T>::receive_chain_blocks(RawOrigin::None.into(), blocks, signature));
+    } verify {
+        assert_eq!(LastProcessedBlock::get(ChainId::Eth).map(|block| block.number()), Some(b as u64));
     }
 
     receive_chain_reorg_pending {
+        let d in 1 .. 10;
         let z in 1 .. 10;
+        <pallet_timestamp::Now<T>>::put(1u64);
         // add 2 vals
         let substrate_id = AccountId32::new([12u8; 32]);
         Validators::insert(
@@ -244,6 +291,7 @@ benchmarks! {
             ValidatorKeys {
                 substrate_id,
                 eth_address: BOB_ADDRESS_BYTES,
+                next_eth_key: None,
             },
         );
 
@@ -252,11 +300,12 @@ benchmarks! {
             ValidatorKeys {
                 substrate_id: AccountId32::new([13u8; 32]),
                 eth_address: <Ethereum as Chain>::signer_address().unwrap(),
+                next_eth_key: None,
             },
         );
 
-        let (reorg, reorg_block) = construct_reorg(z);
-        let reorg_blocks = ChainBlocks::Eth(vec![reorg_block]);
+        let (reorg, pending_block) = construct_reorg(d, z);
+        let reorg_blocks = ChainBlocks::Eth(vec![pending_block]);
         let signature = ChainSignature::Eth(<Ethereum as Chain>::sign_message(&reorg_blocks.encode()).unwrap());
         assert_ok!(Cash::<T>::receive_chain_blocks(RawOrigin::None.into(), reorg_blocks, signature));
         let reorg_signature = ChainSignature::Eth(<Ethereum as Chain>::sign_message(&reorg.encode()).unwrap());
@@ -317,6 +366,36 @@ benchmarks! {
         assert_eq!(Cash::<T>::allow_next_code_with_hash(RawOrigin::Root.into(), hash), Ok(()));
     }
 
+    set_miner {
+        let miner = ChainAccount::Eth(BOB_ADDRESS_BYTES);
+    }: {
+        assert_ok!(Cash::<T>::set_miner(RawOrigin::None.into(), miner));
+    } verify {
+        assert_eq!(Miner::get(), Some(miner));
+    }
+
+    set_starport {
+        let starport = ChainStarport::Eth(BOB_ADDRESS_BYTES);
+    }: {
+        assert_ok!(Cash::<T>::set_starport(RawOrigin::Root.into(), starport));
+    } verify {
+        assert_eq!(Starports::get(ChainId::Eth), Some(starport));
+    }
+
+    set_genesis_block {
+        let block = ChainBlock::Eth(ethereum_client::EthereumBlock {
+            hash: [22; 32],
+            parent_hash: [1; 32],
+            number: 10,
+            timestamp: 0,
+            events: vec![],
+        });
+    }: {
+        assert_ok!(Cash::<T>::set_genesis_block(RawOrigin::Root.into(), block.clone()));
+    } verify {
+        assert_eq!(LastProcessedBlock::get(ChainId::Eth), Some(block));
+    }
+
     set_next_code_via_hash {
         let z in 10_000 .. 100_000;
         let new_code = vec![3u8; z.try_into().unwrap()];
@@ -333,6 +412,7 @@ benchmarks! {
         let val_keys = ValidatorKeys {
             substrate_id: substrate_id.clone(),
             eth_address: eth_address.clone(),
+            next_eth_key: None,
         };
         let val_keyses = vec![val_keys];
         let val_account = ChainAccount::Gate(substrate_id.clone().into());
@@ -403,6 +483,138 @@ benchmarks! {
         assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
     }
 
+    exec_trx_request_supply {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        let supply_amt: i128 = MIN_TX_VALUE.try_into().unwrap();
+        endow_tkn::<T>(signer_vec, supply_amt * 5, TKN_ADDR_BYTES);
+
+        // amount, asset, dest_acct
+        let raw_req: String = format!("(Supply {} Eth:{} Eth:{})", supply_amt, TKN_ADDR, ALICE_ADDRESS);
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+
+        let full_request: Vec<u8> =  format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_repay {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        // bob borrows tkn, signer repays all of it
+        let repay_amt: i128 = MIN_TX_VALUE.try_into().unwrap();
+        endow_tkn::<T>(BOB_ADDRESS_BYTES, -repay_amt, TKN_ADDR_BYTES);
+        endow_tkn::<T>(signer_vec, repay_amt * 5, TKN_ADDR_BYTES);
+
+        // max, asset, borrower
+        let raw_req: String = format!("(Repay Max Eth:{} Eth:{})", TKN_ADDR, BOB_ADDRESS);
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+
+        let full_request: Vec<u8> =  format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_approve {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        // amount, asset, spender
+        let raw_req: String = format!("(Approve Max Eth:{} Eth:{})", TKN_ADDR, BOB_ADDRESS);
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+
+        let full_request: Vec<u8> =  format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_transfer_from {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        // bob holds tkn and lets the signer spend some of it
+        let transfer_amt: i128 = MIN_TX_VALUE.try_into().unwrap();
+        endow_tkn::<T>(BOB_ADDRESS_BYTES, transfer_amt * 5, TKN_ADDR_BYTES);
+        Allowances::insert(
+            ChainAccount::Eth(BOB_ADDRESS_BYTES),
+            (ChainAccount::Eth(signer_vec), CashOrChainAsset::ChainAsset(ChainAsset::Eth(TKN_ADDR_BYTES))),
+            MIN_TX_VALUE * 2,
+        );
+
+        // amount, asset, owner, dest_acct
+        let raw_req: String = format!("(TransferFrom {} Eth:{} Eth:{} Eth:{})", transfer_amt, TKN_ADDR, BOB_ADDRESS, ALICE_ADDRESS);
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+
+        let full_request: Vec<u8> =  format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_cancel {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        // cancel a nonce out of order, within the window
+        NonceWindows::insert(ChainAccount::Eth(signer_vec), 4);
+
+        let raw_req: String = String::from("(Cancel 2)");
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+
+        let full_request: Vec<u8> =  format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
+    exec_trx_request_set_nonce_window {
+        let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
+        let nonce: Nonce = 0u32.into();
+
+        let raw_req: String = String::from("(NonceWindow 16)");
+        let request_vec: Vec<u8> = raw_req.as_bytes().into();
+        let prepended_request = format!("{}:{}", nonce, raw_req);
+
+        let full_request: Vec<u8> =  format!("\x19Ethereum Signed Message:\n{}{}", prepended_request.len(), prepended_request).as_bytes().into();
+
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature_raw = runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+        let signature = ChainAccountSignature::Eth(signer_vec, signature_raw);
+
+    }: {
+        assert_eq!(Cash::<T>::exec_trx_request(RawOrigin::None.into(), request_vec, signature, nonce), Ok(()));
+    }
+
     exec_trx_request_liquidate {
         let signer_vec = <Ethereum as Chain>::signer_address().unwrap();
         let holder = ChainAccount::Eth(signer_vec);
@@ -453,8 +665,17 @@ mod tests {
             assert_ok!(test_benchmark_allow_next_code_with_hash::<Test>());
             assert_ok!(test_benchmark_set_next_code_via_hash::<Test>());
             assert_ok!(test_benchmark_change_validators::<Test>());
+            assert_ok!(test_benchmark_set_miner::<Test>());
+            assert_ok!(test_benchmark_set_starport::<Test>());
+            assert_ok!(test_benchmark_set_genesis_block::<Test>());
             assert_ok!(test_benchmark_exec_trx_request_extract::<Test>());
             assert_ok!(test_benchmark_exec_trx_request_transfer::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_supply::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_repay::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_approve::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_transfer_from::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_cancel::<Test>());
+            assert_ok!(test_benchmark_exec_trx_request_set_nonce_window::<Test>());
             assert_ok!(test_benchmark_exec_trx_request_liquidate::<Test>());
         });
     }
@@ -3,15 +3,19 @@ use ethereum_client::{EthereumBlock, EthereumEvent, EthereumHash};
 use gateway_crypto::public_key_bytes_to_eth_address;
 use our_std::vec::Vec;
 use our_std::{
-    collections::btree_set::BTreeSet, str::FromStr, vec, Debuggable, Deserialize, RuntimeDebug,
-    Serialize,
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    str::FromStr,
+    vec, Debuggable, Deserialize, RuntimeDebug, Serialize,
 };
 use types_derive::{type_alias, Types};
 
 use crate::{
     rates::APR,
     reason::Reason,
-    types::{AssetAmount, CashIndex, SignersSet, Timestamp, ValidatorIdentity, ValidatorKeys},
+    types::{
+        AssetAmount, CashIndex, SignerWeights, SignersSet, Timestamp, ValidatorIdentity,
+        ValidatorKeys,
+    },
 };
 
 /// Used to reserve enum variant fields for future use.
@@ -65,6 +69,19 @@ impl ChainId {
         }
     }
 
+    /// Sign a message with the threshold key whose shares are held by the validators, if configured.
+    /// The threshold protocol itself runs off-chain, behind the keyring holding this validator's share.
+    pub fn sign_threshold(self, message: &[u8]) -> Result<ChainSignature, Reason> {
+        let key_id = runtime_interfaces::validator_config_interface::get_threshold_key_id()
+            .ok_or(Reason::KeyNotFound)?;
+        let signature = runtime_interfaces::keyring_interface::sign_one(message.into(), key_id)?;
+        match self {
+            ChainId::Eth => Ok(ChainSignature::Eth(signature)),
+            ChainId::Matic => Ok(ChainSignature::Matic(signature)),
+            _ => Err(Reason::NotImplemented),
+        }
+    }
+
     pub fn hash_bytes(self, data: &[u8]) -> ChainHash {
         match self {
             ChainId::Gate => ChainHash::Gate(<Gateway as Chain>::hash_bytes(data)),
@@ -85,12 +102,19 @@ impl ChainId {
             ChainId::Matic => Ok(ChainSignature::Matic(<Polygon as Chain>::sign_message(
                 message,
             )?)),
-            ChainId::Dot => Ok(ChainSignature::Dot(<Polkadot as Chain>::sign_message(
-                message,
-            )?)),
+            ChainId::Dot => self.sign_ed25519(message),
         }
     }
 
+    /// Sign a message with the Ed25519 key, for chains which verify Ed25519 rather than secp256k1 signatures.
+    fn sign_ed25519(self, message: &[u8]) -> Result<ChainSignature, Reason> {
+        let key_id = runtime_interfaces::validator_config_interface::get_ed25519_key_id()
+            .ok_or(Reason::KeyNotFound)?;
+        let signature =
+            runtime_interfaces::keyring_interface::sign_ed25519_one(message.into(), key_id)?;
+        Ok(ChainSignature::Ed25519(self, signature))
+    }
+
     pub fn zero_hash(self) -> ChainHash {
         match self {
             ChainId::Gate => ChainHash::Gate(<Gateway as Chain>::zero_hash()),
@@ -232,6 +256,7 @@ impl ChainAccountSignature {
 
     pub fn recover_account(self, message: &[u8]) -> Result<ChainAccount, Reason> {
         match self {
+            // Note: Gate signatures are only accepted for linking accounts, see internal::link
             ChainAccountSignature::Gate(_, _) => Err(Reason::Unreachable),
             ChainAccountSignature::Eth(eth_account, eth_sig) => {
                 Self::check_chain_signature::<Ethereum>(message, eth_account, eth_sig)
@@ -302,6 +327,7 @@ pub enum ChainSignature {
     Eth(<Ethereum as Chain>::Signature),
     Dot(<Polkadot as Chain>::Signature),
     Matic(<Polygon as Chain>::Signature),
+    Ed25519(ChainId, [u8; 64]),
 }
 
 impl ChainSignature {
@@ -311,6 +337,28 @@ impl ChainSignature {
             ChainSignature::Eth(_) => ChainId::Eth,
             ChainSignature::Matic(_) => ChainId::Matic,
             ChainSignature::Dot(_) => ChainId::Dot,
+            ChainSignature::Ed25519(chain_id, _) => *chain_id,
+        }
+    }
+
+    /// Check the message was signed by the given account.
+    /// The signer of an Ed25519 signature cannot be recovered, so it must be known to be checked.
+    pub fn verify(&self, message: &[u8], signer: ChainAccount) -> Result<(), Reason> {
+        let verified = match (self, signer) {
+            (ChainSignature::Ed25519(ChainId::Dot, sig), ChainAccount::Dot(public_key)) => {
+                runtime_interfaces::keyring_interface::ed25519_verify(
+                    message.into(),
+                    *sig,
+                    public_key,
+                )
+            }
+            (ChainSignature::Ed25519(..), _) => false,
+            _ => self.recover(message)? == signer,
+        };
+        if verified {
+            Ok(())
+        } else {
+            Err(Reason::SignatureAccountMismatch)
         }
     }
 
@@ -324,6 +372,7 @@ impl ChainSignature {
                 <Polygon as Chain>::recover_address(message, *sig)?,
             )),
             ChainSignature::Dot(_) => Err(Reason::Unreachable),
+            ChainSignature::Ed25519(..) => Err(Reason::Unreachable),
         }
     }
 }
@@ -335,11 +384,15 @@ pub enum ChainSignatureList {
     Eth(Vec<(<Ethereum as Chain>::Address, <Ethereum as Chain>::Signature)>),
     Dot(Vec<(<Polkadot as Chain>::Address, <Polkadot as Chain>::Signature)>),
     Matic(Vec<(<Polygon as Chain>::Address, <Polygon as Chain>::Signature)>),
+    Threshold(ChainAccount, Option<ChainSignature>),
 }
 
 impl ChainSignatureList {
     pub fn has_signer(&self, signer: ChainAccount) -> bool {
         match (self, signer) {
+            (ChainSignatureList::Threshold(threshold_signer, Some(_)), signer) => {
+                *threshold_signer == signer
+            }
             (ChainSignatureList::Eth(eth_signature_pairs), ChainAccount::Eth(eth_account)) => {
                 eth_signature_pairs.iter().any(|(s, _)| *s == eth_account)
             }
@@ -373,6 +426,27 @@ impl ChainSignatureList {
             _ => Err(Reason::SignatureMismatch),
         }
     }
+
+    /// Whether the single signature of the validators' threshold key has been collected.
+    pub fn has_threshold_signature(&self) -> bool {
+        matches!(self, ChainSignatureList::Threshold(_, Some(_)))
+    }
+
+    pub fn add_threshold_signature(
+        &mut self,
+        signature: &ChainSignature,
+        signer: ChainAccount,
+    ) -> Result<(), Reason> {
+        match self {
+            ChainSignatureList::Threshold(threshold_signer, threshold_signature) => {
+                if *threshold_signer != signer || threshold_signature.is_some() {
+                    return Err(Reason::SignatureAccountMismatch);
+                }
+                Ok(*threshold_signature = Some(*signature))
+            }
+            _ => Err(Reason::SignatureMismatch),
+        }
+    }
 }
 
 // Implement deserialization for ChainIds so we can use them in GenesisConfig / ChainSpec JSON.
@@ -427,6 +501,14 @@ impl ChainBlock {
         }
     }
 
+    /// The time at which the block was produced on the underlying chain, in milliseconds.
+    pub fn timestamp(&self) -> Timestamp {
+        match self {
+            ChainBlock::Eth(block) => block.timestamp.saturating_mul(1000),
+            ChainBlock::Matic(block) => block.timestamp.saturating_mul(1000),
+        }
+    }
+
     fn map_events_eth_like<F>(
         block: &EthereumBlock,
         mut chain_block_event_fn: F,
@@ -636,6 +718,30 @@ pub fn has_super_majority<T: Ord>(signers: &BTreeSet<T>, validator_set: &BTreeSe
     valid_signers.len() >= (2 * validator_set.len() + 3 - 1) / 3
 }
 
+/// Calculate whether the signers are a super majority of the given validator set, and also hold a super majority of its total weight.
+/// Requiring both keeps any one heavily weighted validator from deciding alone.
+/// Only signers are counted when the validator set has no weight, i.e. weighting is disabled.
+pub fn has_weighted_super_majority<T: Ord>(
+    signers: &BTreeSet<T>,
+    validator_set: &BTreeSet<T>,
+    weights: &BTreeMap<T, u128>,
+) -> bool {
+    if !has_super_majority(signers, validator_set) {
+        return false;
+    }
+    let weight_of = |validators: &mut dyn Iterator<Item = &T>| {
+        validators.fold(0u128, |total, v| {
+            total.saturating_add(weights.get(v).copied().unwrap_or(0))
+        })
+    };
+    let total_weight = weight_of(&mut validator_set.iter());
+    if total_weight == 0 {
+        return true;
+    }
+    // using ⌈2t/3⌉ = t - ⌊t/3⌋
+    weight_of(&mut validator_set.intersection(&signers)) >= total_weight - total_weight / 3
+}
+
 /// Type for tallying signatures for an underlying chain block.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub struct ChainBlockTally {
@@ -663,12 +769,12 @@ impl ChainBlockTally {
         self.support.remove(&validator.substrate_id);
     }
 
-    pub fn has_enough_support(&self, validator_set: &SignersSet) -> bool {
-        has_super_majority(&self.support, validator_set)
+    pub fn has_enough_support(&self, validator_set: &SignersSet, weights: &SignerWeights) -> bool {
+        has_weighted_super_majority(&self.support, validator_set, weights)
     }
 
-    pub fn has_enough_dissent(&self, validator_set: &SignersSet) -> bool {
-        has_super_majority(&self.dissent, validator_set)
+    pub fn has_enough_dissent(&self, validator_set: &SignersSet, weights: &SignerWeights) -> bool {
+        has_weighted_super_majority(&self.dissent, validator_set, weights)
     }
 
     pub fn has_supporter(&self, validator_id: &ValidatorIdentity) -> bool {
@@ -703,15 +809,16 @@ impl ChainReorgTally {
     pub fn would_have_enough_support(
         &self,
         validator_set: &SignersSet,
+        weights: &SignerWeights,
         validator: &ValidatorKeys,
     ) -> bool {
         let mut hypothetical_signers = self.support.clone();
         hypothetical_signers.insert(validator.substrate_id.clone());
-        has_super_majority(&hypothetical_signers, validator_set)
+        has_weighted_super_majority(&hypothetical_signers, validator_set, weights)
     }
 
-    pub fn has_enough_support(&self, validator_set: &SignersSet) -> bool {
-        has_super_majority(&self.support, validator_set)
+    pub fn has_enough_support(&self, validator_set: &SignersSet, weights: &SignerWeights) -> bool {
+        has_weighted_super_majority(&self.support, validator_set, weights)
     }
 
     pub fn has_signer(&self, validator_id: &ValidatorIdentity) -> bool {
@@ -749,6 +856,14 @@ impl ChainBlockEvent {
     }
 }
 
+/// Type for identifying an event which failed to process, and is awaiting retry.
+#[type_alias]
+pub type FailedEventId = u64;
+
+/// Type for an optional memo attached to a lock, e.g. for an exchange to credit a deposit.
+#[type_alias]
+pub type LockReference = [u8; 32];
+
 /// Type for describing a set of events coming from an underlying chain.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum ChainBlockEvents {
@@ -950,11 +1065,17 @@ impl Chain for Gateway {
         hash
     }
 
+    /// Gateway accounts for ECDSA keys are the hash of the compressed public key, as in substrate.
     fn recover_user_address(
-        _data: &[u8],
-        _signature: Self::Signature,
+        data: &[u8],
+        signature: Self::Signature,
     ) -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        let public = sp_io::crypto::secp256k1_ecdsa_recover_compressed(
+            &signature,
+            &sp_io::hashing::blake2_256(data),
+        )
+        .map_err(|_| Reason::SignatureMismatch)?;
+        Ok(sp_io::hashing::blake2_256(&public))
     }
 
     fn recover_address(_data: &[u8], _signature: Self::Signature) -> Result<Self::Address, Reason> {
@@ -1251,7 +1372,10 @@ impl Chain for Polkadot {
     }
 
     fn signer_address() -> Result<Self::Address, Reason> {
-        panic!("XXX not implemented");
+        // Note: notices for Polkadot are signed with the Ed25519 key, whose public key is the address
+        let key_id = runtime_interfaces::validator_config_interface::get_ed25519_key_id()
+            .ok_or(Reason::KeyNotFound)?;
+        Ok(runtime_interfaces::keyring_interface::get_ed25519_public_key(key_id)?)
     }
 
     fn str_to_address(_addr: &str) -> Result<Self::Address, Reason> {
@@ -1298,6 +1422,48 @@ mod tests {
     use super::*;
     use ethereum_client::{EthereumBlock, EthereumEvent};
 
+    #[test]
+    fn test_recover_gate_account() {
+        use sp_core::Pair;
+        sp_io::TestExternalities::default().execute_with(|| {
+            let pair = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+            let address = sp_io::hashing::blake2_256(pair.public().as_ref());
+            let signature = pair.sign(b"hello").0;
+            assert_eq!(
+                <Gateway as Chain>::recover_user_address(b"hello", signature),
+                Ok(address)
+            );
+            assert_ne!(
+                <Gateway as Chain>::recover_user_address(b"goodbye", signature),
+                Ok(address)
+            );
+            assert_eq!(
+                ChainAccountSignature::Gate(address, signature).recover_account(b"hello"),
+                Err(Reason::Unreachable)
+            );
+        });
+    }
+
+    #[test]
+    fn test_sign_ed25519_for_dot() {
+        let signer = ChainId::Dot.signer_address().unwrap();
+        let signature = ChainId::Dot.sign(b"hello").unwrap();
+        assert!(matches!(
+            signature,
+            ChainSignature::Ed25519(ChainId::Dot, _)
+        ));
+        assert_eq!(signature.chain_id(), ChainId::Dot);
+        assert_eq!(signature.verify(b"hello", signer), Ok(()));
+        assert_eq!(
+            signature.verify(b"goodbye", signer),
+            Err(Reason::SignatureAccountMismatch)
+        );
+        assert_eq!(
+            signature.verify(b"hello", ChainAccount::Dot([0; 32])),
+            Err(Reason::SignatureAccountMismatch)
+        );
+    }
+
     #[test]
     fn test_chain_events_push() {
         let mut a = ChainBlockEvents::Eth(vec![]);
@@ -1305,6 +1471,7 @@ mod tests {
             hash: [2u8; 32],
             parent_hash: [1u8; 32],
             number: 2,
+            timestamp: 0,
             events: vec![],
         }));
         assert_eq!(a, ChainBlockEvents::Eth(vec![]));
@@ -1312,12 +1479,14 @@ mod tests {
             hash: [2u8; 32],
             parent_hash: [1u8; 32],
             number: 2,
+            timestamp: 0,
             events: vec![EthereumEvent::Lock {
                 asset: [4u8; 20],
                 sender: [5u8; 20],
                 chain: String::from("ETH"),
                 recipient: [6u8; 32],
                 amount: 100,
+                reference: None,
             }],
         }));
         assert_eq!(
@@ -1330,6 +1499,7 @@ mod tests {
                     chain: String::from("ETH"),
                     recipient: [6u8; 32],
                     amount: 100,
+                    reference: None,
                 }
             )])
         );
@@ -1343,12 +1513,14 @@ mod tests {
                 hash: [1u8; 32],
                 parent_hash: [0u8; 32],
                 number: 1,
+                timestamp: 0,
                 events: vec![],
             },
             EthereumBlock {
                 hash: [2u8; 32],
                 parent_hash: [1u8; 32],
                 number: 2,
+                timestamp: 0,
                 events: vec![],
             },
         ]);
@@ -1359,6 +1531,7 @@ mod tests {
                 // dont matter:
                 parent_hash: [0u8; 32],
                 number: 0,
+                timestamp: 0,
                 events: vec![],
             }),
             support: [signer.clone()].iter().cloned().collect(),
@@ -1371,6 +1544,7 @@ mod tests {
                 hash: [1u8; 32],
                 parent_hash: [0u8; 32],
                 number: 1,
+                timestamp: 0,
                 events: vec![],
             }])
         )
@@ -1384,12 +1558,14 @@ mod tests {
                 hash: [1u8; 32],
                 parent_hash: [0u8; 32],
                 number: 1,
+                timestamp: 0,
                 events: vec![],
             },
             EthereumBlock {
                 hash: [2u8; 32],
                 parent_hash: [1u8; 32],
                 number: 2,
+                timestamp: 0,
                 events: vec![],
             },
         ]);
@@ -1400,6 +1576,7 @@ mod tests {
                 // dont matter:
                 parent_hash: [0u8; 32],
                 number: 0,
+                timestamp: 0,
                 events: vec![],
             }),
             support: SignersSet::new(),
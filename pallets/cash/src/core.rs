@@ -14,26 +14,32 @@ use crate::{
         self, Chain, ChainAccount, ChainAsset, ChainBlock, ChainBlockEvent, ChainBlockEvents,
         ChainHash, ChainId, ChainSignature, Ethereum, Polygon,
     },
-    internal, log, pipeline,
+    internal::{self, assets::get_price},
+    log, pipeline,
     portfolio::Portfolio,
     rates::APR,
     reason::Reason,
+    symbol::{CASH, USD},
     types::{
-        AssetAmount, AssetBalance, Balance, CashPrincipalAmount, GovernanceResult, NoticeId,
-        SignersSet, Timestamp, ValidatorKeys,
+        AssetAmount, AssetBalance, Balance, CashOrChainAsset, CashPrincipalAmount, Factor,
+        GovernanceResult, MarketSummary, MathError, NoticeId, Quantity, SignerWeights, SignersSet,
+        Timestamp, ValidatorKeys,
     },
     AssetBalances, AssetsWithNonZeroBalance, CashIndex, CashPrincipals, CashYield, Config, Event,
-    FirstBlock, GlobalCashIndex, IngressionQueue, LastProcessedBlock, Pallet, Starports,
-    SupportedAssets, TotalBorrowAssets, TotalCashPrincipal, TotalSupplyAssets, Validators,
+    FirstBlock, GlobalCashIndex, GovernanceQueue, IngressionQueue, LastProcessedBlock, Pallet,
+    StakeWeightedThresholds, Starports, SupportedAssets, TotalBorrowAssets, TotalCashPrincipal,
+    TotalSupplyAssets, Validators,
 };
 
 use codec::Decode;
 use frame_support::{
     storage::{
-        IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+        with_transaction, IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap,
+        StorageMap, StorageValue,
     },
     traits::UnfilteredDispatchable,
 };
+use sp_runtime::TransactionOutcome;
 use timestamp::GetConvertedTimestamp;
 
 use crate::events::EventError;
@@ -71,6 +77,34 @@ pub fn get_market_totals<T: Config>(
     Ok((total_borrow, total_supply))
 }
 
+/// Return a summary of every supported market, including its totals, rates and price.
+pub fn get_markets_summary<T: Config>() -> Result<Vec<MarketSummary>, Reason> {
+    let mut summaries = Vec::new();
+    for (asset, info) in SupportedAssets::iter() {
+        let total_supply = TotalSupplyAssets::get(asset);
+        let total_borrow = TotalBorrowAssets::get(asset);
+        let utilization = crate::rates::get_utilization(total_supply, total_borrow)?;
+        let (borrow_rate, supply_rate) =
+            info.rate_model
+                .get_rates(utilization, APR::ZERO, info.miner_shares)?;
+        let price = internal::assets::get_price_or_zero::<T>(info.units());
+        let total_value_locked = info.as_quantity(total_supply).mul_price(price)?.value;
+        summaries.push(MarketSummary {
+            asset,
+            total_supply,
+            total_borrow,
+            total_value_locked,
+            utilization,
+            supply_rate,
+            borrow_rate,
+            price: price.value,
+            supply_cap: info.supply_cap,
+            liquidity_factor: info.liquidity_factor,
+        });
+    }
+    Ok(summaries)
+}
+
 /// Return the account's balance for the asset.
 pub fn get_account_balance<T: Config>(
     account: ChainAccount,
@@ -108,6 +142,18 @@ pub fn get_accounts<T: Config>() -> Result<Vec<ChainAccount>, Reason> {
     Ok(all_holders)
 }
 
+/// Return at most limit accounts holding CASH or an asset, in account order, after the given account if any.
+pub fn get_accounts_page<T: Config>(
+    start_key: Option<ChainAccount>,
+    limit: u32,
+) -> Result<Vec<ChainAccount>, Reason> {
+    Ok(get_accounts::<T>()?
+        .into_iter()
+        .filter(|account| start_key.map_or(true, |start| *account > start))
+        .take(limit as usize)
+        .collect())
+}
+
 pub fn get_asset_meta<T: Config>(
 ) -> Result<(BTreeMap<String, u32>, BTreeMap<String, u32>, u32, u32), Reason> {
     let mut asset_suppliers: BTreeMap<String, u32> = BTreeMap::new();
@@ -150,6 +196,58 @@ pub fn get_accounts_liquidity<T: Config>() -> Result<Vec<(ChainAccount, AssetBal
     Ok(info)
 }
 
+/// Return at most limit accounts with their liquidity, in account order, after the given account if any.
+/// If a threshold is given, only accounts whose liquidity is below it are returned.
+pub fn get_accounts_liquidity_page<T: Config>(
+    start_key: Option<ChainAccount>,
+    limit: u32,
+    below: Option<AssetBalance>,
+) -> Result<Vec<(ChainAccount, AssetBalance)>, Reason> {
+    let mut page = Vec::new();
+    for account in get_accounts::<T>()? {
+        if page.len() >= limit as usize {
+            break;
+        }
+        if start_key.map_or(false, |start| account <= start) {
+            continue;
+        }
+        let liquidity = get_liquidity::<T>(account)?.value;
+        if below.map_or(true, |threshold| liquidity < threshold) {
+            page.push((account, liquidity));
+        }
+    }
+    Ok(page)
+}
+
+/// Return up to the given number of underwater accounts, most underwater first.
+/// Each account comes with its liquidity and its largest borrow and collateral positions by value.
+pub fn get_liquidatable_accounts<T: Config>(
+    max_results: u32,
+) -> Result<
+    Vec<(
+        ChainAccount,
+        Balance,
+        Option<CashOrChainAsset>,
+        Option<CashOrChainAsset>,
+    )>,
+    Reason,
+> {
+    let mut accounts = Vec::new();
+    for account in get_accounts::<T>()? {
+        let portfolio = pipeline::load_portfolio::<T>(account)?;
+        let liquidity = portfolio.get_liquidity::<T>()?;
+        if liquidity.value < 0 {
+            let (borrow, collateral) = portfolio.get_largest_positions::<T>()?;
+            accounts.push((account, liquidity, borrow, collateral));
+        }
+    }
+    accounts.sort_by(|(_, a_liquidity, _, _), (_, b_liquidity, _, _)| {
+        a_liquidity.value.cmp(&b_liquidity.value)
+    });
+    accounts.truncate(max_results as usize);
+    Ok(accounts)
+}
+
 /// Calculates the current total CASH value of the account, including all interest from non-CASH markets.
 pub fn get_cash_balance_with_asset_interest<T: Config>(
     account: ChainAccount,
@@ -158,21 +256,107 @@ pub fn get_cash_balance_with_asset_interest<T: Config>(
 }
 
 /// Return the portfolio of the chain account.
+/// Note: linked accounts are not combined, as collateral is checked per account.
 pub fn get_portfolio<T: Config>(account: ChainAccount) -> Result<Portfolio, Reason> {
     Ok(pipeline::load_portfolio::<T>(account)?)
 }
 
+/// Return the health factor of an account, or none if it is not borrowing.
+pub fn get_health_factor<T: Config>(account: ChainAccount) -> Result<Option<Factor>, Reason> {
+    Ok(pipeline::load_portfolio::<T>(account)?.get_health_factor::<T>()?)
+}
+
+/// Return the most of an asset an account can extract while remaining collateralized.
+/// Extracting an asset first uses up the account's supply of it, then borrows the rest,
+///  which is further limited by the funds available in the market.
+pub fn get_max_extract<T: Config>(
+    account: ChainAccount,
+    asset: CashOrChainAsset,
+) -> Result<AssetAmount, Reason> {
+    let portfolio = pipeline::load_portfolio::<T>(account)?;
+    let liquidity = portfolio.get_liquidity::<T>()?;
+    if liquidity.value <= 0 {
+        return Ok(0);
+    }
+    let liquidity = Quantity::new(liquidity.value as AssetAmount, USD);
+
+    match asset {
+        CashOrChainAsset::Cash => Ok(liquidity.div_price(get_price::<T>(CASH)?, CASH)?.value),
+
+        CashOrChainAsset::ChainAsset(chain_asset) => {
+            let asset_info = internal::assets::get_asset::<T>(chain_asset)?;
+            let units = asset_info.units();
+            let price = get_price::<T>(units)?;
+            let held = portfolio
+                .positions
+                .iter()
+                .find(|(info, _)| info.asset == chain_asset)
+                .map_or(0, |(_, balance)| balance.value.max(0) as AssetAmount);
+            let held_worth = Quantity::new(held, units)
+                .mul_price(price)?
+                .mul_factor(asset_info.liquidity_factor)?;
+
+            let max_by_liquidity = if held_worth.value >= liquidity.value {
+                liquidity
+                    .div_factor(asset_info.liquidity_factor)?
+                    .div_price(price, units)?
+                    .value
+            } else {
+                let borrowable = liquidity
+                    .sub(held_worth)?
+                    .mul_factor(asset_info.liquidity_factor)?
+                    .div_price(price, units)?;
+                held.checked_add(borrowable.value)
+                    .ok_or(MathError::Overflow)?
+            };
+            let available = TotalSupplyAssets::get(chain_asset)
+                .saturating_sub(TotalBorrowAssets::get(chain_asset));
+            Ok(min(max_by_liquidity, available))
+        }
+    }
+}
+
 /// Calculates the current liquidity value for an account.
 pub fn get_liquidity<T: Config>(account: ChainAccount) -> Result<Balance, Reason> {
     Ok(pipeline::load_portfolio::<T>(account)?.get_liquidity::<T>()?)
 }
 
+/// View only: return the portfolio of the chain account combined with any account linked to it.
+/// Note: collateral is still checked per account, so the combined liquidity can't be used as one.
+pub fn get_logical_portfolio<T: Config>(account: ChainAccount) -> Result<Portfolio, Reason> {
+    Ok(internal::link::load_logical_portfolio::<T>(account)?)
+}
+
+/// View only: calculates the CASH value of the account combined with any account linked to it.
+pub fn get_logical_cash_balance<T: Config>(account: ChainAccount) -> Result<Balance, Reason> {
+    Ok(internal::link::load_logical_portfolio::<T>(account)?.cash)
+}
+
+/// View only: calculates the liquidity of the account combined with any account linked to it.
+pub fn get_logical_liquidity<T: Config>(account: ChainAccount) -> Result<Balance, Reason> {
+    Ok(internal::link::load_logical_portfolio::<T>(account)?.get_liquidity::<T>()?)
+}
+
 /// Return the set of validator identities to compare with others.
 pub fn get_validator_set<T: Config>() -> Result<SignersSet, Reason> {
     // Note: inefficient, probably manage reading validators from storage better
     Ok(Validators::iter().map(|(_, v)| v.substrate_id).collect())
 }
 
+/// Return the weight of each validator's signature, its bonded CASH principal, or none if signatures are weighted equally.
+pub fn get_signer_weights<T: Config>() -> SignerWeights {
+    if StakeWeightedThresholds::get() {
+        Validators::iter_values()
+            .map(|validator| {
+                let stake = internal::bonding::get_validator_bond(&validator);
+                (validator.substrate_id, stake.0)
+            })
+            .collect()
+    } else {
+        SignerWeights::new()
+    }
+}
+
 /// Return the validator associated with the given signer account.
 pub fn get_validator<T: Config>(signer: ChainAccount) -> Result<ValidatorKeys, Reason> {
     // Note: inefficient, we should index
@@ -211,11 +395,12 @@ pub fn recover_validator<T: Config>(
     signature: ChainSignature,
 ) -> Result<ValidatorKeys, Reason> {
     // Note: inefficient, we should index by every key we want to query by
+    let now = get_recent_timestamp::<T>().ok();
     match signature {
         ChainSignature::Eth(eth_sig) => {
             let eth_address = <Ethereum as Chain>::recover_address(data, eth_sig)?;
             for (_, validator) in Validators::iter() {
-                if validator.eth_address == eth_address {
+                if validator.has_eth_address(eth_address, now) {
                     return Ok(validator);
                 }
             }
@@ -223,7 +408,7 @@ pub fn recover_validator<T: Config>(
         ChainSignature::Matic(eth_sig) => {
             let eth_address = <Polygon as Chain>::recover_address(data, eth_sig)?;
             for (_, validator) in Validators::iter() {
-                if validator.eth_address == eth_address {
+                if validator.has_eth_address(eth_address, now) {
                     return Ok(validator);
                 }
             }
@@ -262,11 +447,13 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
                 chain,
                 recipient,
                 amount,
+                reference,
             } => internal::lock::lock_internal::<T>(
                 internal::assets::get_asset::<T>(ChainAsset::Eth(*asset))?,
                 ChainAccount::Eth(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
                 internal::assets::get_quantity::<T>(ChainAsset::Eth(*asset), *amount)?,
+                *reference,
             ),
 
             ethereum_client::EthereumEvent::LockCash {
@@ -284,7 +471,7 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
             ethereum_client::EthereumEvent::ExecuteProposal {
                 title: _title,
                 extrinsics,
-            } => dispatch_extrinsics_internal::<T>(extrinsics.to_vec()),
+            } => T::GovernanceQueue::queue_governance(extrinsics.to_vec()),
 
             ethereum_client::EthereumEvent::ExecTrxRequest {
                 account,
@@ -306,6 +493,15 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
                 ChainHash::Eth(*notice_hash),
                 result.to_vec(),
             ),
+
+            ethereum_client::EthereumEvent::NoticeRelayed {
+                notice_hash,
+                relayer,
+            } => internal::execution_fees::handle_notice_relayed::<T>(
+                ChainId::Eth,
+                ChainHash::Eth(*notice_hash),
+                ChainAccount::Eth(*relayer),
+            ),
         },
         ChainBlockEvent::Matic(_block_num, eth_event) => match eth_event {
             ethereum_client::EthereumEvent::Lock {
@@ -314,11 +510,13 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
                 chain,
                 recipient,
                 amount,
+                reference,
             } => internal::lock::lock_internal::<T>(
                 internal::assets::get_asset::<T>(ChainAsset::Matic(*asset))?,
                 ChainAccount::Matic(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
                 internal::assets::get_quantity::<T>(ChainAsset::Matic(*asset), *amount)?,
+                *reference,
             ),
 
             ethereum_client::EthereumEvent::LockCash {
@@ -357,6 +555,15 @@ pub fn apply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Result<
                 ChainHash::Matic(*notice_hash),
                 result.to_vec(),
             ),
+
+            ethereum_client::EthereumEvent::NoticeRelayed {
+                notice_hash,
+                relayer,
+            } => internal::execution_fees::handle_notice_relayed::<T>(
+                ChainId::Matic,
+                ChainHash::Matic(*notice_hash),
+                ChainAccount::Matic(*relayer),
+            ),
         },
     }
 }
@@ -374,11 +581,13 @@ pub fn unapply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Resul
                 chain,
                 recipient,
                 amount,
+                reference,
             } => internal::lock::undo_lock_internal::<T>(
                 internal::assets::get_asset::<T>(ChainAsset::Eth(*asset))?,
                 ChainAccount::Eth(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
                 internal::assets::get_quantity::<T>(ChainAsset::Eth(*asset), *amount)?,
+                *reference,
             ),
 
             ethereum_client::EthereumEvent::LockCash {
@@ -402,11 +611,13 @@ pub fn unapply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Resul
                 chain,
                 recipient,
                 amount,
+                reference,
             } => internal::lock::undo_lock_internal::<T>(
                 internal::assets::get_asset::<T>(ChainAsset::Matic(*asset))?,
                 ChainAccount::Matic(*sender),
                 chains::get_chain_account(chain.to_string(), *recipient)?,
                 internal::assets::get_quantity::<T>(ChainAsset::Matic(*asset), *amount)?,
+                *reference,
             ),
 
             ethereum_client::EthereumEvent::LockCash {
@@ -426,40 +637,46 @@ pub fn unapply_chain_event_internal<T: Config>(event: &ChainBlockEvent) -> Resul
     }
 }
 
+/// Decode a SCALE-encoded extrinsic and dispatch it as Root.
+fn dispatch_extrinsic<T: Config>(payload: &[u8]) -> GovernanceResult {
+    log!(
+        "dispatch_extrinsics_internal:: dispatching extrinsic {}",
+        hex::encode(payload)
+    );
+    let call_res: Result<<T as Config>::Call, _> = Decode::decode(&mut &payload[..]);
+    match call_res {
+        Ok(call) => {
+            log!("dispatch_extrinsics_internal:: dispatching {:?}", call);
+            let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
+
+            let gov_res = match res {
+                Ok(_) => GovernanceResult::DispatchSuccess,
+                Err(error_with_post_info) => {
+                    GovernanceResult::DispatchFailure(error_with_post_info.error)
+                }
+            };
+
+            log!("dispatch_extrinsics_internal:: res {:?}", res);
+            gov_res
+        }
+        _ => {
+            log!(
+                "dispatch_extrinsics_internal:: failed to decode extrinsic {}",
+                hex::encode(payload)
+            );
+            GovernanceResult::FailedToDecodeCall
+        }
+    }
+}
+
 pub fn dispatch_extrinsics_internal<T: Config>(extrinsics: Vec<Vec<u8>>) -> Result<(), Reason> {
     // Decode a SCALE-encoded set of extrinsics from the event
     // For each extrinsic, dispatch the given extrinsic as Root
     let results: Vec<(Vec<u8>, GovernanceResult)> = extrinsics
         .into_iter()
         .map(|payload| {
-            log!(
-                "dispatch_extrinsics_internal:: dispatching extrinsic {}",
-                hex::encode(&payload)
-            );
-            let call_res: Result<<T as Config>::Call, _> = Decode::decode(&mut &payload[..]);
-            match call_res {
-                Ok(call) => {
-                    log!("dispatch_extrinsics_internal:: dispatching {:?}", call);
-                    let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
-
-                    let gov_res = match res {
-                        Ok(_) => GovernanceResult::DispatchSuccess,
-                        Err(error_with_post_info) => {
-                            GovernanceResult::DispatchFailure(error_with_post_info.error)
-                        }
-                    };
-
-                    log!("dispatch_extrinsics_internal:: res {:?}", res);
-                    (payload, gov_res)
-                }
-                _ => {
-                    log!(
-                        "dispatch_extrinsics_internal:: failed to decode extrinsic {}",
-                        hex::encode(&payload)
-                    );
-                    (payload, GovernanceResult::FailedToDecodeCall)
-                }
-            }
+            let gov_res = dispatch_extrinsic::<T>(&payload);
+            (payload, gov_res)
         })
         .collect();
 
@@ -468,6 +685,18 @@ pub fn dispatch_extrinsics_internal<T: Config>(extrinsics: Vec<Vec<u8>>) -> Resu
     Ok(())
 }
 
+/// Dispatch a set of SCALE-encoded extrinsics as Root, as governance would, then roll back all their changes.
+/// Each extrinsic sees the changes made by the ones before it, the same as when governance is executed.
+pub fn simulate_governance<T: Config>(extrinsics: Vec<Vec<u8>>) -> Vec<GovernanceResult> {
+    with_transaction(|| {
+        let results = extrinsics
+            .iter()
+            .map(|payload| dispatch_extrinsic::<T>(payload))
+            .collect();
+        TransactionOutcome::Rollback(results)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::factor::Factor;
@@ -495,6 +724,209 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_get_markets_summary() -> Result<(), Reason> {
+        use crate::tests::common::*;
+
+        let alan = ChainAccount::Eth([1; 20]);
+        let bert = ChainAccount::Eth([2; 20]);
+
+        new_test_ext().execute_with(|| {
+            assert_eq!(super::get_markets_summary::<Test>()?, vec![]);
+
+            init_eth_asset()?;
+            init_asset_balance(Eth, alan, Balance::from_nominal("10", ETH).value);
+            init_asset_balance(Eth, bert, Balance::from_nominal("-2", ETH).value);
+
+            let (borrow_rate, supply_rate) = internal::assets::get_rates::<Test>(Eth)?;
+            assert_eq!(
+                super::get_markets_summary::<Test>()?,
+                vec![MarketSummary {
+                    asset: Eth,
+                    total_supply: Quantity::from_nominal("10", ETH).value,
+                    total_borrow: Quantity::from_nominal("2", ETH).value,
+                    total_value_locked: Quantity::from_nominal("20000", USD).value,
+                    utilization: Factor::from_nominal("0.2"),
+                    supply_rate,
+                    borrow_rate,
+                    price: Price::from_nominal(ETH.ticker, "2000").value,
+                    supply_cap: eth.supply_cap,
+                    liquidity_factor: eth.liquidity_factor,
+                }]
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_health_factor() -> Result<(), Reason> {
+        use crate::tests::common::*;
+
+        let alan = ChainAccount::Eth([1; 20]);
+
+        new_test_ext().execute_with(|| {
+            init_eth_asset()?;
+            init_wbtc_asset()?;
+
+            assert_eq!(super::get_health_factor::<Test>(alan)?, None);
+
+            // 0.1 * 60000 * 0.6 / (1 * 2000 / 0.8) = 1.44
+            init_asset_balance(Eth, alan, Balance::from_nominal("-1", ETH).value);
+            init_asset_balance(Wbtc, alan, Balance::from_nominal("0.1", WBTC).value);
+            assert_eq!(
+                super::get_health_factor::<Test>(alan)?,
+                Some(Factor::from_nominal("1.44"))
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_max_extract() -> Result<(), Reason> {
+        use crate::tests::common::*;
+
+        let alan = ChainAccount::Eth([1; 20]);
+        let bert = ChainAccount::Eth([2; 20]);
+
+        new_test_ext().execute_with(|| {
+            init_eth_asset()?;
+            init_wbtc_asset()?;
+
+            // liquidity = 1 * 2000 * 0.8 + 1000 = 2600
+            init_asset_balance(Eth, alan, Balance::from_nominal("1", ETH).value);
+            init_cash(alan, CashPrincipal::from_nominal("1000"));
+            init_asset_balance(Eth, bert, Balance::from_nominal("9", ETH).value);
+            init_asset_balance(Wbtc, bert, Balance::from_nominal("1", WBTC).value);
+
+            assert_eq!(
+                super::get_max_extract::<Test>(alan, CashOrChainAsset::Cash)?,
+                Quantity::from_nominal("2600", CASH).value
+            );
+
+            // all the held ETH, then borrow (2600 - 1600) * 0.8 / 2000 = 0.4 ETH more
+            assert_eq!(
+                super::get_max_extract::<Test>(alan, CashOrChainAsset::ChainAsset(Eth))?,
+                Quantity::from_nominal("1.4", ETH).value
+            );
+
+            // borrow 2600 * 0.6 / 60000 = 0.026 WBTC
+            assert_eq!(
+                super::get_max_extract::<Test>(alan, CashOrChainAsset::ChainAsset(Wbtc))?,
+                Quantity::from_nominal("0.026", WBTC).value
+            );
+
+            // limited by the WBTC available in the market
+            TotalBorrowAssets::insert(Wbtc, Quantity::from_nominal("0.99", WBTC).value);
+            assert_eq!(
+                super::get_max_extract::<Test>(alan, CashOrChainAsset::ChainAsset(Wbtc))?,
+                Quantity::from_nominal("0.01", WBTC).value
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_accounts_pages() -> Result<(), Reason> {
+        use crate::tests::common::*;
+
+        let alan = ChainAccount::Eth([1; 20]);
+        let bert = ChainAccount::Eth([2; 20]);
+        let carl = ChainAccount::Eth([3; 20]);
+
+        new_test_ext().execute_with(|| {
+            init_eth_asset()?;
+
+            // -2000 / 0.8 + 1000 = -1500
+            init_asset_balance(Eth, alan, Balance::from_nominal("-1", ETH).value);
+            init_cash(alan, CashPrincipal::from_nominal("1000"));
+            init_cash(bert, CashPrincipal::from_nominal("200"));
+            init_cash(carl, CashPrincipal::from_nominal("100"));
+
+            assert_eq!(super::get_accounts_page::<Test>(None, 2)?, vec![alan, bert]);
+            assert_eq!(super::get_accounts_page::<Test>(Some(bert), 2)?, vec![carl]);
+            assert_eq!(super::get_accounts_page::<Test>(Some(carl), 2)?, vec![]);
+
+            assert_eq!(
+                super::get_accounts_liquidity_page::<Test>(Some(alan), 10, None)?,
+                vec![
+                    (bert, Balance::from_nominal("200", USD).value),
+                    (carl, Balance::from_nominal("100", USD).value)
+                ]
+            );
+            assert_eq!(
+                super::get_accounts_liquidity_page::<Test>(
+                    None,
+                    10,
+                    Some(Balance::from_nominal("150", USD).value)
+                )?,
+                vec![
+                    (alan, Balance::from_nominal("-1500", USD).value),
+                    (carl, Balance::from_nominal("100", USD).value)
+                ]
+            );
+            assert_eq!(
+                super::get_accounts_liquidity_page::<Test>(None, 1, Some(0))?,
+                vec![(alan, Balance::from_nominal("-1500", USD).value)]
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_liquidatable_accounts() -> Result<(), Reason> {
+        use crate::tests::common::*;
+
+        let alan = ChainAccount::Eth([1; 20]);
+        let bert = ChainAccount::Eth([2; 20]);
+        let carl = ChainAccount::Eth([3; 20]);
+
+        new_test_ext().execute_with(|| {
+            init_eth_asset()?;
+            init_wbtc_asset()?;
+
+            // -2000 / 0.8 + 1000 = -1500
+            init_asset_balance(Eth, alan, Balance::from_nominal("-1", ETH).value);
+            init_cash(alan, CashPrincipal::from_nominal("1000"));
+
+            // -2000 / 0.8 + 600 * 0.6 = -2140
+            init_asset_balance(Eth, bert, Balance::from_nominal("-1", ETH).value);
+            init_asset_balance(Wbtc, bert, Balance::from_nominal("0.01", WBTC).value);
+
+            init_cash(carl, CashPrincipal::from_nominal("100"));
+
+            assert_eq!(
+                super::get_liquidatable_accounts::<Test>(10)?,
+                vec![
+                    (
+                        bert,
+                        Balance::from_nominal("-2140", USD),
+                        Some(CashOrChainAsset::ChainAsset(Eth)),
+                        Some(CashOrChainAsset::ChainAsset(Wbtc))
+                    ),
+                    (
+                        alan,
+                        Balance::from_nominal("-1500", USD),
+                        Some(CashOrChainAsset::ChainAsset(Eth)),
+                        Some(CashOrChainAsset::Cash)
+                    ),
+                ]
+            );
+            assert_eq!(
+                super::get_liquidatable_accounts::<Test>(1)?
+                    .into_iter()
+                    .map(|(account, _, _, _)| account)
+                    .collect::<Vec<_>>(),
+                vec![bert]
+            );
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_compute_cash_principal_per() -> Result<(), Reason> {
         // round numbers (unrealistic but very easy to check)
@@ -549,6 +981,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_simulate_governance() {
+        new_test_ext().execute_with(|| {
+            let set_min_tx_value =
+                |value| mock::Call::Cash(crate::Call::set_min_tx_value(value)).encode();
+            let results = super::simulate_governance::<Test>(vec![
+                set_min_tx_value(Quantity::from_nominal("5", USD)),
+                vec![0xff],
+                set_min_tx_value(Quantity::from_nominal("0", USD)),
+            ]);
+
+            assert_eq!(results[0], GovernanceResult::DispatchSuccess);
+            assert_eq!(results[1], GovernanceResult::FailedToDecodeCall);
+            assert!(matches!(results[2], GovernanceResult::DispatchFailure(_)));
+            assert_eq!(
+                internal::parameters::get_min_tx_value(),
+                Quantity::new(pallet_parameters::DEFAULT_MIN_TX_VALUE, USD)
+            );
+            assert_eq!(System::events().len(), 0);
+        });
+    }
+
     #[test]
     fn test_get_recent_timestamp() {
         new_test_ext().execute_with(|| {
@@ -565,6 +1019,7 @@ mod tests {
             let validator = ValidatorKeys {
                 substrate_id: AccountId32::new([0u8; 32]),
                 eth_address: <Ethereum as Chain>::signer_address().unwrap(),
+                next_eth_key: None,
             };
             Validators::insert(validator.substrate_id.clone(), &validator);
             assert_eq!(get_current_validator::<Test>().unwrap(), validator);
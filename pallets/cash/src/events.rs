@@ -1,14 +1,14 @@
 use crate::{
     chains::{
-        Chain, ChainBlock, ChainBlockNumber, ChainBlocks, ChainHash, ChainId, ChainStarport,
-        Ethereum,
+        Chain, ChainAccount, ChainBlock, ChainBlockNumber, ChainBlocks, ChainHash, ChainId,
+        ChainStarport, Ethereum,
     },
     debug,
     reason::Reason,
 };
 use codec::{Decode, Encode};
 use ethereum_client::{EthereumBlock, EthereumBlockId, EthereumClientError};
-use our_std::RuntimeDebug;
+use our_std::{log::targets::EVENTS, RuntimeDebug};
 use types_derive::Types;
 
 /// Type for errors coming from event ingression.
@@ -77,12 +77,93 @@ pub fn fetch_chain_blocks(
     }
 }
 
+/// Fetch the latest block number of the underlying chain.
+pub fn fetch_chain_head(chain_id: ChainId) -> Result<ChainBlockNumber, Reason> {
+    match chain_id {
+        ChainId::Eth => Ok(fetch_eth_head()?),
+        ChainId::Matic => Ok(fetch_matic_head()?),
+        _ => Err(Reason::NotImplemented),
+    }
+}
+
+fn fetch_eth_head() -> Result<ChainBlockNumber, EventError> {
+    let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
+        .ok_or(EventError::NoRpcUrl)?;
+    ethereum_client::get_latest_block_number(&eth_rpc_url).map_err(EventError::EthereumClientError)
+}
+
+fn fetch_matic_head() -> Result<ChainBlockNumber, EventError> {
+    let matic_rpc_url = runtime_interfaces::validator_config_interface::get_matic_rpc_url()
+        .ok_or(EventError::NoRpcUrl)?;
+    ethereum_client::get_latest_block_number(&matic_rpc_url).map_err(EventError::PolygonClientError)
+}
+
+/// Fetch an estimate of the gas used by sending the given calldata to the Starport.
+pub fn fetch_gas_estimate(
+    chain_id: ChainId,
+    starport: ChainStarport,
+    calldata: &[u8],
+) -> Result<u64, Reason> {
+    match (chain_id, starport) {
+        (ChainId::Eth, ChainStarport::Eth(eth_starport_address)) => {
+            Ok(fetch_eth_gas_estimate(&eth_starport_address, calldata)?)
+        }
+        (ChainId::Matic, ChainStarport::Matic(starport_address)) => {
+            Ok(fetch_matic_gas_estimate(&starport_address, calldata)?)
+        }
+        _ => Err(Reason::NotImplemented),
+    }
+}
+
+fn fetch_eth_gas_estimate(
+    eth_starport_address: &[u8; 20],
+    calldata: &[u8],
+) -> Result<u64, EventError> {
+    let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
+        .ok_or(EventError::NoRpcUrl)?;
+    ethereum_client::estimate_gas(&eth_rpc_url, eth_starport_address, calldata)
+        .map_err(EventError::EthereumClientError)
+}
+
+fn fetch_matic_gas_estimate(
+    matic_starport_address: &[u8; 20],
+    calldata: &[u8],
+) -> Result<u64, EventError> {
+    let matic_rpc_url = runtime_interfaces::validator_config_interface::get_matic_rpc_url()
+        .ok_or(EventError::NoRpcUrl)?;
+    ethereum_client::estimate_gas(&matic_rpc_url, matic_starport_address, calldata)
+        .map_err(EventError::PolygonClientError)
+}
+
+/// Fetch the latest block number of the underlying chain which is checkpointed on L1.
+pub fn fetch_chain_checkpoint(
+    chain_id: ChainId,
+    contract: ChainAccount,
+) -> Result<ChainBlockNumber, Reason> {
+    match (chain_id, contract) {
+        (ChainId::Matic, ChainAccount::Eth(root_chain_address)) => {
+            Ok(fetch_matic_checkpoint(&root_chain_address)?)
+        }
+        _ => Err(Reason::NotImplemented),
+    }
+}
+
+/// Fetch the latest Polygon block number included in a checkpoint on the Ethereum RootChain.
+fn fetch_matic_checkpoint(root_chain_address: &[u8; 20]) -> Result<ChainBlockNumber, EventError> {
+    debug!(target: EVENTS, "Fetching Matic Checkpoint from {:?}", root_chain_address);
+    let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
+        .ok_or(EventError::NoRpcUrl)?;
+    let number = ethereum_client::get_last_child_block(&eth_rpc_url, root_chain_address)
+        .map_err(EventError::EthereumClientError)?;
+    Ok(number)
+}
+
 /// Fetch a single block from the Etherum Starport by hash.
 fn fetch_eth_block_by_hash(
     hash: <Ethereum as Chain>::Hash,
     eth_starport_address: &[u8; 20],
 ) -> Result<EthereumBlock, EventError> {
-    debug!("Fetching Eth Block {:?}", hash);
+    debug!(target: EVENTS, "Fetching Eth Block {:?}", hash);
     let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
         .ok_or(EventError::NoRpcUrl)?;
     let eth_block = ethereum_client::get_block(
@@ -99,7 +180,7 @@ fn fetch_eth_block(
     number: ChainBlockNumber,
     eth_starport_address: &[u8; 20],
 ) -> Result<EthereumBlock, EventError> {
-    debug!("Fetching Eth Block {}", number);
+    debug!(target: EVENTS, "Fetching Eth Block {}", number);
     let eth_rpc_url = runtime_interfaces::validator_config_interface::get_eth_rpc_url()
         .ok_or(EventError::NoRpcUrl)?;
     let eth_block = ethereum_client::get_block(
@@ -141,6 +222,7 @@ fn fetch_eth_like_blocks<
     mut chain_blocks_fn: G,
 ) -> Result<ChainBlocks, EventError> {
     debug!(
+        target: EVENTS,
         "Fetching Blocks chain_id={:?}, from_block={}, to_block={}",
         chain_id, from, to
     );
@@ -207,12 +289,14 @@ mod tests {
                 hash: [1u8; 32],
                 parent_hash: [0u8; 32],
                 number: 1,
+                timestamp: 0,
                 events: vec![],
             },
             ethereum_client::EthereumBlock {
                 hash: [2u8; 32],
                 parent_hash: [1u8; 32],
                 number: 2,
+                timestamp: 0,
                 events: vec![],
             },
         ];
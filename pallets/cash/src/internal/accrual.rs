@@ -0,0 +1,208 @@
+use crate::{
+    chains::ChainAsset,
+    factor::Factor,
+    internal,
+    params::ACCRUAL_SWEEP_INTERVAL,
+    reason::Reason,
+    types::{AccrualPoint, CashPrincipalAmount, Quantity, RewardSpeeds, Timestamp, CASH},
+    AssetRewardSpeeds, BorrowIndices, BorrowRewardIndices, Config, Event, GlobalCashIndex,
+    LastAccrualSweep, LastAccrualTimestamps, LastBlockTimestamp, Module, RateHistory,
+    SupplyIndices, SupplyRewardIndices, SupportedAssets, TotalBorrowAssets, TotalReserves,
+    TotalSupplyAssets, UnsettledAccruals,
+};
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
+use our_std::vec::Vec;
+
+/// Bring an asset's indices current as of the current block, before it is touched.
+pub fn accrue_touched_asset<T: Config>(asset: ChainAsset) -> Result<(), Reason> {
+    accrue_asset::<T>(asset, LastBlockTimestamp::get())
+}
+
+/// Bring an asset's indices current as of now, if not already.
+/// The CASH principal generated/paid is left to be settled at the next block initialization.
+pub fn accrue_asset<T: Config>(asset: ChainAsset, now: Timestamp) -> Result<(), Reason> {
+    let last_accrual_timestamp =
+        LastAccrualTimestamps::get(asset).unwrap_or(LastBlockTimestamp::get());
+
+    // Nothing to accrue before the first block, or more than once per block
+    if last_accrual_timestamp == 0 || last_accrual_timestamp == now {
+        return Ok(());
+    }
+
+    let asset_info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
+    let dt_since_last_accrual = now
+        .checked_sub(last_accrual_timestamp)
+        .ok_or(Reason::TimeTravelNotAllowed)?;
+    let cash_index = GlobalCashIndex::get();
+    let price_cash = internal::assets::get_price_or_zero::<T>(CASH);
+
+    let (asset_cost, asset_yield) = internal::assets::get_rates::<T>(asset)?;
+    let asset_units = asset_info.units();
+    let price_asset = internal::assets::get_price_or_zero::<T>(asset_units);
+    let price_ratio = Factor::ratio(price_asset, price_cash)?;
+    let cash_borrow_principal_per_asset = cash_index
+        .cash_principal_per_asset(asset_cost.simple(dt_since_last_accrual)?, price_ratio)?;
+    let cash_hold_principal_per_asset = cash_index
+        .cash_principal_per_asset(asset_yield.simple(dt_since_last_accrual)?, price_ratio)?;
+
+    let supply_index_new = SupplyIndices::get(&asset).increment(cash_hold_principal_per_asset)?;
+    let borrow_index_new = BorrowIndices::get(&asset).increment(cash_borrow_principal_per_asset)?;
+
+    let supply_asset = Quantity::new(TotalSupplyAssets::get(asset), asset_units);
+    let borrow_asset = Quantity::new(TotalBorrowAssets::get(asset), asset_units);
+    let asset_supply_increase =
+        cash_hold_principal_per_asset.cash_principal_amount(supply_asset)?;
+    let asset_borrow_increase =
+        cash_borrow_principal_per_asset.cash_principal_amount(borrow_asset)?;
+    let asset_reserve_increase = internal::assets::get_reserve_increase::<T>(
+        asset,
+        asset_borrow_increase,
+        asset_supply_increase,
+    )?;
+    let reserves_new = TotalReserves::get(asset).add(asset_reserve_increase)?;
+
+    let (unsettled_borrow, unsettled_supply, unsettled_reserve) = UnsettledAccruals::get();
+    let unsettled_new = (
+        unsettled_borrow.add(asset_borrow_increase)?,
+        unsettled_supply.add(asset_supply_increase)?,
+        unsettled_reserve.add(asset_reserve_increase)?,
+    );
+
+    let reward_speeds = AssetRewardSpeeds::get(asset);
+    let reward_indices_new = if reward_speeds != RewardSpeeds::default() {
+        let supply_reward_index_new =
+            SupplyRewardIndices::get(asset).increment(internal::rewards::get_reward_per_asset(
+                reward_speeds.supply,
+                dt_since_last_accrual,
+                supply_asset,
+            )?)?;
+        let borrow_reward_index_new =
+            BorrowRewardIndices::get(asset).increment(internal::rewards::get_reward_per_asset(
+                reward_speeds.borrow,
+                dt_since_last_accrual,
+                borrow_asset,
+            )?)?;
+        Some((supply_reward_index_new, borrow_reward_index_new))
+    } else {
+        None
+    };
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    SupplyIndices::insert(asset, supply_index_new);
+    BorrowIndices::insert(asset, borrow_index_new);
+    RateHistory::mutate(asset, |history| {
+        internal::initialize::remember_accrual(
+            history,
+            AccrualPoint {
+                timestamp: now,
+                supply_index: supply_index_new,
+                borrow_index: borrow_index_new,
+                supply_rate: asset_yield,
+                borrow_rate: asset_cost,
+            },
+        )
+    });
+
+    if asset_reserve_increase != CashPrincipalAmount::ZERO {
+        TotalReserves::insert(asset, reserves_new);
+    }
+
+    if let Some((supply_reward_index_new, borrow_reward_index_new)) = reward_indices_new {
+        SupplyRewardIndices::insert(asset, supply_reward_index_new);
+        BorrowRewardIndices::insert(asset, borrow_reward_index_new);
+    }
+
+    UnsettledAccruals::put(unsettled_new);
+    LastAccrualTimestamps::insert(asset, now);
+
+    <Module<T>>::deposit_event(Event::AssetAccrued(
+        asset,
+        supply_index_new,
+        borrow_index_new,
+        asset_yield,
+        asset_cost,
+    ));
+
+    Ok(())
+}
+
+/// Bring every asset current as of now, if it has been long enough since the last sweep.
+/// Returns the number of assets swept, so idle markets never go too long without accruing.
+pub fn sweep_idle_assets<T: Config>(now: Timestamp) -> Result<u32, Reason> {
+    if now.saturating_sub(LastAccrualSweep::get()) < ACCRUAL_SWEEP_INTERVAL {
+        return Ok(0);
+    }
+
+    let assets: Vec<ChainAsset> = SupportedAssets::iter().map(|(asset, _)| asset).collect();
+    for asset in assets.iter() {
+        accrue_asset::<T>(*asset, now)?;
+    }
+
+    LastAccrualSweep::put(now);
+
+    Ok(assets.len() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{assets::*, common::*, *};
+
+    #[test]
+    fn test_accrue_asset_once_per_block() {
+        new_test_ext().execute_with(|| {
+            let now = 10 + MILLISECONDS_PER_YEAR / 4;
+            assert_ok!(init_eth_asset());
+            LastBlockTimestamp::put(10);
+            GlobalCashIndex::put(CashIndex::from_nominal("1.123"));
+            SupplyIndices::insert(Eth, AssetIndex::from_nominal("1234"));
+            BorrowIndices::insert(Eth, AssetIndex::from_nominal("1345"));
+            TotalSupplyAssets::insert(Eth, ETH.one() * 300);
+            TotalBorrowAssets::insert(Eth, ETH.one() * 150);
+
+            assert_eq!(accrue_asset::<Test>(Eth, 10), Ok(()));
+            assert_eq!(LastAccrualTimestamps::get(Eth), None);
+
+            assert_eq!(accrue_asset::<Test>(Eth, now), Ok(()));
+            let supply_index = SupplyIndices::get(Eth);
+            let borrow_index = BorrowIndices::get(Eth);
+            let unsettled = UnsettledAccruals::get();
+            assert!(borrow_index > AssetIndex::from_nominal("1345"));
+            assert!(unsettled.0 > CashPrincipalAmount::ZERO);
+            assert_eq!(LastAccrualTimestamps::get(Eth), Some(now));
+            assert_eq!(RateHistory::get(Eth).len(), 1);
+
+            assert_eq!(accrue_asset::<Test>(Eth, now), Ok(()));
+            assert_eq!(SupplyIndices::get(Eth), supply_index);
+            assert_eq!(BorrowIndices::get(Eth), borrow_index);
+            assert_eq!(UnsettledAccruals::get(), unsettled);
+            assert_eq!(RateHistory::get(Eth).len(), 1);
+
+            assert_eq!(
+                accrue_asset::<Test>(Eth, now - 1),
+                Err(Reason::TimeTravelNotAllowed)
+            );
+        });
+    }
+
+    #[test]
+    fn test_sweep_idle_assets() {
+        new_test_ext().execute_with(|| {
+            let now = 10 + ACCRUAL_SWEEP_INTERVAL;
+            assert_ok!(init_eth_asset());
+            assert_ok!(init_wbtc_asset());
+            LastBlockTimestamp::put(10);
+            LastAccrualSweep::put(10);
+
+            assert_eq!(sweep_idle_assets::<Test>(now - 1), Ok(0));
+            assert_eq!(LastAccrualTimestamps::get(Eth), None);
+
+            assert_eq!(sweep_idle_assets::<Test>(now), Ok(2));
+            assert_eq!(LastAccrualSweep::get(), now);
+            assert_eq!(LastAccrualTimestamps::get(Eth), Some(now));
+            assert_eq!(LastAccrualTimestamps::get(Wbtc), Some(now));
+            assert_eq!(sweep_idle_assets::<Test>(now + 1), Ok(0));
+        });
+    }
+}
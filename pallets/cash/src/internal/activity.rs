@@ -0,0 +1,123 @@
+use crate::{
+    chains::ChainAccount,
+    params::MAX_ACCOUNT_ACTIVITY,
+    types::{ActivityEntry, ActivityKind, AssetAmount, CashOrChainAsset},
+    AccountActivity, Config,
+};
+use frame_support::storage::StorageMap;
+use our_std::vec::Vec;
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// Record an operation involving an account at the current block, forgetting the oldest if full.
+pub fn record_activity<T: Config>(
+    account: ChainAccount,
+    kind: ActivityKind,
+    asset: CashOrChainAsset,
+    amount: AssetAmount,
+    counterparty: Option<ChainAccount>,
+) {
+    let block_number: u32 = <frame_system::Module<T>>::block_number().unique_saturated_into();
+    AccountActivity::mutate(account, |history| {
+        remember_activity(
+            history,
+            ActivityEntry {
+                block_number,
+                kind,
+                asset,
+                amount,
+                counterparty,
+            },
+        )
+    });
+}
+
+/// Record an operation moving an asset between two accounts, for each of them.
+pub fn record_exchange<T: Config>(
+    from: ChainAccount,
+    from_kind: ActivityKind,
+    to: ChainAccount,
+    to_kind: ActivityKind,
+    asset: CashOrChainAsset,
+    amount: AssetAmount,
+) {
+    record_activity::<T>(from, from_kind, asset, amount, Some(to));
+    record_activity::<T>(to, to_kind, asset, amount, Some(from));
+}
+
+/// Remember an activity entry, keeping at most the latest MAX_ACCOUNT_ACTIVITY entries.
+pub fn remember_activity(history: &mut Vec<ActivityEntry>, entry: ActivityEntry) {
+    history.push(entry);
+    if history.len() > MAX_ACCOUNT_ACTIVITY {
+        history.drain(..history.len() - MAX_ACCOUNT_ACTIVITY);
+    }
+}
+
+/// Return at most limit operations involving an account from the given block on, oldest first.
+pub fn get_account_history<T: Config>(
+    account: ChainAccount,
+    from: u32,
+    limit: u32,
+) -> Vec<ActivityEntry> {
+    AccountActivity::get(account)
+        .into_iter()
+        .filter(|entry| entry.block_number >= from)
+        .take(limit as usize)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{assets::*, common::*, *};
+
+    #[test]
+    fn test_remember_activity() {
+        let entry = |block_number| ActivityEntry {
+            block_number,
+            kind: ActivityKind::Lock,
+            asset: CashOrChainAsset::Cash,
+            amount: 1,
+            counterparty: None,
+        };
+        let mut history = vec![];
+        for block_number in 0..(MAX_ACCOUNT_ACTIVITY as u32 + 2) {
+            remember_activity(&mut history, entry(block_number));
+        }
+        assert_eq!(history.len(), MAX_ACCOUNT_ACTIVITY);
+        assert_eq!(history[0], entry(2));
+        assert_eq!(
+            history[MAX_ACCOUNT_ACTIVITY - 1],
+            entry(MAX_ACCOUNT_ACTIVITY as u32 + 1)
+        );
+    }
+
+    #[test]
+    fn test_get_account_history() {
+        new_test_ext().execute_with(|| {
+            let account = ChainAccount::Eth([1u8; 20]);
+            let other = ChainAccount::Eth([2u8; 20]);
+            for block_number in 1..=3 {
+                System::set_block_number(block_number);
+                record_activity::<Test>(
+                    account,
+                    ActivityKind::Send,
+                    CashOrChainAsset::ChainAsset(Eth),
+                    block_number as AssetAmount,
+                    Some(other),
+                );
+            }
+
+            let history = get_account_history::<Test>(account, 2, 10);
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[0].block_number, 2);
+            assert_eq!(history[0].counterparty, Some(other));
+            assert_eq!(history[1].amount, 3);
+
+            let history = get_account_history::<Test>(account, 0, 1);
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].block_number, 1);
+
+            assert_eq!(get_account_history::<Test>(other, 0, 10), vec![]);
+        });
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    chains::ChainAccount,
+    log,
+    reason::Reason,
+    require,
+    types::{AssetAmount, CashOrChainAsset},
+    Allowances, Config, Event, Module,
+};
+use frame_support::storage::StorageDoubleMap;
+
+/// Set the amount of an asset which a spender may transfer on behalf of an owner.
+/// An amount of `AssetAmount::MAX` is an unlimited approval, zero revokes the approval.
+pub fn approve_internal<T: Config>(
+    owner: ChainAccount,
+    spender: ChainAccount,
+    asset: CashOrChainAsset,
+    amount: AssetAmount,
+) -> Result<(), Reason> {
+    log!(
+        "approve_internal: {:?} {:?} {:?} {}",
+        owner,
+        spender,
+        asset,
+        amount
+    );
+    require!(owner != spender, Reason::SelfTransfer);
+
+    if amount == 0 {
+        Allowances::remove(owner, (spender, asset));
+    } else {
+        Allowances::insert(owner, (spender, asset), amount);
+    }
+
+    <Module<T>>::deposit_event(Event::Approval(owner, spender, asset, amount));
+
+    Ok(())
+}
+
+/// Use up part of the allowance a spender has for an owner, unless the approval is unlimited.
+pub fn spend_allowance<T: Config>(
+    owner: ChainAccount,
+    spender: ChainAccount,
+    asset: CashOrChainAsset,
+    amount: AssetAmount,
+) -> Result<(), Reason> {
+    let allowance = Allowances::get(owner, (spender, asset));
+    require!(amount <= allowance, Reason::InsufficientAllowance);
+
+    if allowance != AssetAmount::MAX {
+        let remaining = allowance - amount;
+        if remaining == 0 {
+            Allowances::remove(owner, (spender, asset));
+        } else {
+            Allowances::insert(owner, (spender, asset), remaining);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    const OWNER: ChainAccount = ChainAccount::Eth([1; 20]);
+    const SPENDER: ChainAccount = ChainAccount::Eth([2; 20]);
+
+    #[test]
+    fn test_approve_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = CashOrChainAsset::ChainAsset(Eth);
+
+            assert_eq!(
+                approve_internal::<Test>(OWNER, OWNER, asset, 100),
+                Err(Reason::SelfTransfer)
+            );
+
+            assert_eq!(approve_internal::<Test>(OWNER, SPENDER, asset, 100), Ok(()));
+            assert_eq!(Allowances::get(OWNER, (SPENDER, asset)), 100);
+            assert_eq!(Allowances::get(SPENDER, (OWNER, asset)), 0);
+            assert_eq!(Allowances::get(OWNER, (SPENDER, CashOrChainAsset::Cash)), 0);
+
+            assert_eq!(approve_internal::<Test>(OWNER, SPENDER, asset, 0), Ok(()));
+            assert!(!Allowances::contains_key(OWNER, (SPENDER, asset)));
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert_eq!(
+                events,
+                vec![
+                    mock::Event::pallet_cash(crate::Event::Approval(OWNER, SPENDER, asset, 100)),
+                    mock::Event::pallet_cash(crate::Event::Approval(OWNER, SPENDER, asset, 0)),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_spend_allowance() {
+        new_test_ext().execute_with(|| {
+            let asset = CashOrChainAsset::Cash;
+
+            assert_eq!(
+                spend_allowance::<Test>(OWNER, SPENDER, asset, 1),
+                Err(Reason::InsufficientAllowance)
+            );
+
+            Allowances::insert(OWNER, (SPENDER, asset), 100);
+            assert_eq!(spend_allowance::<Test>(OWNER, SPENDER, asset, 40), Ok(()));
+            assert_eq!(Allowances::get(OWNER, (SPENDER, asset)), 60);
+            assert_eq!(
+                spend_allowance::<Test>(OWNER, SPENDER, asset, 61),
+                Err(Reason::InsufficientAllowance)
+            );
+            assert_eq!(spend_allowance::<Test>(OWNER, SPENDER, asset, 60), Ok(()));
+            assert!(!Allowances::contains_key(OWNER, (SPENDER, asset)));
+
+            Allowances::insert(OWNER, (SPENDER, asset), AssetAmount::MAX);
+            assert_eq!(
+                spend_allowance::<Test>(OWNER, SPENDER, asset, 1_000),
+                Ok(())
+            );
+            assert_eq!(Allowances::get(OWNER, (SPENDER, asset)), AssetAmount::MAX);
+        });
+    }
+}
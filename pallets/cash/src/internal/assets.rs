@@ -1,15 +1,24 @@
 use crate::{
-    chains::ChainAsset,
+    chains::{ChainAccount, ChainAsset},
+    factor::BigUint,
+    internal,
+    internal::parameters::get_global_liquidation_incentive,
+    params::MAX_LIQUIDATION_INCENTIVE,
     rates::{InterestRateModel, APR},
     reason::Reason,
+    require,
+    symbol::{Symbol, USD},
     types::{
-        AssetAmount, AssetInfo, AssetQuantity, CashPrincipalAmount, Factor, LiquidityFactor,
-        Quantity, USDQuantity, Units,
+        AssetAmount, AssetInfo, AssetPauseFlags, AssetQuantity, Bips, CashPrincipalAmount, Factor,
+        LiquidityFactor, Quantity, USDQuantity, Units,
     },
-    Config, Event, GlobalCashIndex, Module, SupportedAssets, TotalBorrowAssets, TotalSupplyAssets,
+    AssetPauses, ChainCashPrincipals, Config, DustThresholds, Event, GlobalCashIndex,
+    LastAccrualTimestamps, LastBlockTimestamp, Module, Spreads, SupportedAssets, TotalBorrowAssets,
+    TotalReserves, TotalSupplyAssets,
 };
 use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
 use pallet_oracle::types::Price;
+use pallet_parameters::{parameters::set_parameter, types::ParameterValue};
 
 /// Set the liquidity factor for a supported asset.
 pub fn set_liquidity_factor<T: Config>(
@@ -23,6 +32,79 @@ pub fn set_liquidity_factor<T: Config>(
     })
 }
 
+/// Set the liquidation incentive for a supported asset, or clear it to fall back to the global one.
+pub fn set_liquidation_incentive<T: Config>(
+    asset: ChainAsset,
+    incentive: Option<Bips>,
+) -> Result<(), Reason> {
+    let asset_info = get_asset::<T>(asset)?;
+    if let Some(bips) = incentive {
+        require!(
+            bips <= MAX_LIQUIDATION_INCENTIVE,
+            Reason::InvalidLiquidationIncentive
+        );
+    }
+    support_asset::<T>(AssetInfo {
+        liquidation_incentive: incentive,
+        ..asset_info
+    })
+}
+
+/// Set the liquidation incentive for collateral which does not set its own.
+pub fn set_global_liquidation_incentive<T: Config>(incentive: Bips) -> Result<(), Reason> {
+    set_parameter::<T>(ParameterValue::GlobalLiquidationIncentive(incentive))
+        .map_err(|_| Reason::InvalidLiquidationIncentive)?;
+    <Module<T>>::deposit_event(Event::SetLiquidationIncentive(incentive));
+    Ok(())
+}
+
+/// Return the factor applied to the value repaid to get the value of collateral seized.
+pub fn get_liquidation_incentive<T: Config>(incentive: Option<Bips>) -> Result<Factor, Reason> {
+    let bips = incentive.unwrap_or_else(get_global_liquidation_incentive);
+    Ok(Factor::from_fraction(10000 + bips, 10000)?)
+}
+
+/// Set the fraction of borrower interest on a supported asset which is paid to protocol reserves.
+pub fn set_spread<T: Config>(asset: ChainAsset, spread: Bips) -> Result<(), Reason> {
+    get_asset::<T>(asset)?;
+    require!(spread <= 10000, Reason::InvalidSpread);
+    internal::accrual::accrue_touched_asset::<T>(asset)?;
+    Spreads::insert(asset, spread);
+    <Module<T>>::deposit_event(Event::SetSpread(asset, spread));
+    Ok(())
+}
+
+/// Set the balance below which a position left by an operation on a supported asset is swept away as dust.
+/// Note that swept supply no longer counts as collateral, so this should be far below any meaningful value.
+pub fn set_dust_threshold<T: Config>(
+    asset: ChainAsset,
+    threshold: AssetAmount,
+) -> Result<(), Reason> {
+    get_asset::<T>(asset)?;
+    DustThresholds::insert(asset, threshold);
+    <Module<T>>::deposit_event(Event::SetDustThreshold(asset, threshold));
+    Ok(())
+}
+
+/// Set the fraction of borrower interest on a supported asset which is not paid to suppliers.
+pub fn set_miner_share<T: Config>(asset: ChainAsset, share: Bips) -> Result<(), Reason> {
+    let asset_info = get_asset::<T>(asset)?;
+    require!(share <= 10000, Reason::InvalidMinerShare);
+    support_asset::<T>(AssetInfo {
+        miner_shares: Factor::from_fraction(share, 10000)?,
+        ..asset_info
+    })
+}
+
+/// Set the minimum value (USD) required across all protocol interactions.
+pub fn set_min_tx_value<T: Config>(value: USDQuantity) -> Result<(), Reason> {
+    require!(value.units == USD, Reason::InvalidMinTxValue);
+    set_parameter::<T>(ParameterValue::MinTxValue(value.value))
+        .map_err(|_| Reason::InvalidMinTxValue)?;
+    <Module<T>>::deposit_event(Event::SetMinTxValue(value));
+    Ok(())
+}
+
 /// Set the rate model for a supported asset.
 pub fn set_rate_model<T: Config>(
     asset: ChainAsset,
@@ -38,16 +120,48 @@ pub fn set_rate_model<T: Config>(
 
 /// Support an asset by defining its metadata.
 pub fn support_asset<T: Config>(asset_info: AssetInfo) -> Result<(), Reason> {
+    // Bring an already supported asset current first, so new parameters only apply going forwards
+    if SupportedAssets::contains_key(&asset_info.asset) {
+        internal::accrual::accrue_touched_asset::<T>(asset_info.asset)?;
+    } else if LastBlockTimestamp::get() != 0 {
+        // A new asset starts accruing from now, rather than from whichever block first touches it
+        LastAccrualTimestamps::insert(&asset_info.asset, LastBlockTimestamp::get());
+    }
     SupportedAssets::insert(&asset_info.asset, asset_info);
     <Module<T>>::deposit_event(Event::AssetModified(asset_info));
     Ok(())
 }
 
+/// Set the kinds of new activity paused on a supported asset.
+pub fn set_asset_pauses<T: Config>(
+    asset: ChainAsset,
+    pauses: AssetPauseFlags,
+) -> Result<(), Reason> {
+    get_asset::<T>(asset)?;
+    if pauses == AssetPauseFlags::default() {
+        AssetPauses::remove(asset);
+    } else {
+        AssetPauses::insert(asset, pauses);
+    }
+    <Module<T>>::deposit_event(Event::SetAssetPauses(asset, pauses));
+    Ok(())
+}
+
 /// Return the full asset info for an asset.
 pub fn get_asset<T: Config>(asset: ChainAsset) -> Result<AssetInfo, Reason> {
     Ok(SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?)
 }
 
+/// Return the full asset info for the unique supported asset with the given symbol.
+pub fn get_asset_by_symbol<T: Config>(symbol: Symbol) -> Result<AssetInfo, Reason> {
+    let mut matches = SupportedAssets::iter().filter(|(_asset, info)| info.symbol == symbol);
+    match (matches.next(), matches.next()) {
+        (Some((_asset, info)), None) => Ok(info),
+        (Some(_), Some(_)) => Err(Reason::AmbiguousAssetSymbol),
+        (None, _) => Err(Reason::AssetNotSupported),
+    }
+}
+
 /// Return the USD price associated with the given units.
 pub fn get_price<T: pallet_oracle::Config>(units: Units) -> Result<Price, Reason> {
     pallet_oracle::get_price_by_ticker::<T>(units.ticker).ok_or(Reason::NoPrice)
@@ -92,6 +206,52 @@ pub fn get_rates<T: Config>(asset: ChainAsset) -> Result<(APR, APR), Reason> {
         .get_rates(utilization, APR::ZERO, info.miner_shares)?)
 }
 
+/// Return the part of the CASH principal paid by borrowers of an asset which accrues to reserves.
+/// The reserve increase is taken from the miner share, and never from what suppliers are owed.
+pub fn get_reserve_increase<T: Config>(
+    asset: ChainAsset,
+    borrow_increase: CashPrincipalAmount,
+    supply_increase: CashPrincipalAmount,
+) -> Result<CashPrincipalAmount, Reason> {
+    let spread = Spreads::get(asset);
+    let reserve_increase = CashPrincipalAmount(
+        BigUint::from_uint(borrow_increase.0)
+            .mul_decimal(spread, 4)
+            .to_uint()?,
+    );
+    let max_increase = borrow_increase
+        .sub(supply_increase)
+        .unwrap_or(CashPrincipalAmount::ZERO);
+    Ok(reserve_increase.min(max_increase))
+}
+
+/// Extract some of the accrued reserves for an asset to a recipient, as CASH.
+pub fn withdraw_reserves<T: Config>(
+    asset: ChainAsset,
+    principal: CashPrincipalAmount,
+    recipient: ChainAccount,
+) -> Result<(), Reason> {
+    require!(
+        matches!(recipient, ChainAccount::Eth(_) | ChainAccount::Matic(_)),
+        Reason::InvalidChain
+    );
+    let reserves = TotalReserves::get(asset);
+    require!(principal <= reserves, Reason::InsufficientReserves);
+
+    let chain_id = recipient.chain_id();
+    let reserves_post = reserves.sub(principal)?;
+    let chain_cash_principal_post = ChainCashPrincipals::get(chain_id).add(principal)?;
+
+    TotalReserves::insert(asset, reserves_post);
+    ChainCashPrincipals::insert(chain_id, chain_cash_principal_post);
+
+    internal::notices::dispatch_cash_extraction_notice::<T>(recipient, principal);
+
+    <Module<T>>::deposit_event(Event::ReservesWithdrawn(asset, recipient, principal));
+
+    Ok(())
+}
+
 /// Return the current list of assets.
 pub fn get_assets<T: Config>() -> Result<Vec<AssetInfo>, Reason> {
     let info = SupportedAssets::iter()
@@ -103,6 +263,7 @@ pub fn get_assets<T: Config>() -> Result<Vec<AssetInfo>, Reason> {
 #[cfg(test)]
 mod tests {
     use crate::{
+        internal::parameters::get_min_tx_value,
         rates::*,
         tests::{assert_ok, assets::*, common::*, mock::*, *},
         types::*,
@@ -134,6 +295,181 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_asset_by_symbol() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                super::get_asset_by_symbol::<Test>(Symbol(ETH.ticker.0)),
+                Err(Reason::AssetNotSupported)
+            );
+
+            assert_ok!(init_eth_asset());
+            assert_ok!(init_usdc_asset());
+            assert_eq!(
+                super::get_asset_by_symbol::<Test>(Symbol(ETH.ticker.0)),
+                Ok(eth)
+            );
+
+            let matic_eth = AssetInfo {
+                asset: ChainAsset::Matic([238; 20]),
+                ..eth
+            };
+            SupportedAssets::insert(&matic_eth.asset, matic_eth);
+            assert_eq!(
+                super::get_asset_by_symbol::<Test>(Symbol(ETH.ticker.0)),
+                Err(Reason::AmbiguousAssetSymbol)
+            );
+            assert_eq!(
+                super::get_asset_by_symbol::<Test>(Symbol(USD.ticker.0)),
+                Ok(usdc)
+            );
+        });
+    }
+
+    #[test]
+    fn test_set_liquidation_incentive() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                super::set_liquidation_incentive::<Test>(Eth, Some(1000)),
+                Err(Reason::AssetNotSupported)
+            );
+
+            assert_ok!(init_eth_asset());
+            assert_eq!(
+                super::set_liquidation_incentive::<Test>(Eth, Some(10001)),
+                Err(Reason::InvalidLiquidationIncentive)
+            );
+            assert_eq!(
+                super::get_liquidation_incentive::<Test>(
+                    SupportedAssets::get(Eth).unwrap().liquidation_incentive
+                ),
+                Ok(Factor::from_nominal("1.08"))
+            );
+
+            assert_ok!(super::set_liquidation_incentive::<Test>(Eth, Some(1000)));
+            assert_eq!(
+                super::get_liquidation_incentive::<Test>(
+                    SupportedAssets::get(Eth).unwrap().liquidation_incentive
+                ),
+                Ok(Factor::from_nominal("1.1"))
+            );
+
+            assert_ok!(super::set_global_liquidation_incentive::<Test>(500));
+            assert_eq!(
+                super::get_liquidation_incentive::<Test>(None),
+                Ok(Factor::from_nominal("1.05"))
+            );
+
+            assert_ok!(super::set_liquidation_incentive::<Test>(Eth, None));
+            assert_eq!(
+                super::get_liquidation_incentive::<Test>(
+                    SupportedAssets::get(Eth).unwrap().liquidation_incentive
+                ),
+                Ok(Factor::from_nominal("1.05"))
+            );
+            assert_eq!(
+                super::set_global_liquidation_incentive::<Test>(10001),
+                Err(Reason::InvalidLiquidationIncentive)
+            );
+        });
+    }
+
+    #[test]
+    fn test_set_spread() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                super::set_spread::<Test>(Eth, 1000),
+                Err(Reason::AssetNotSupported)
+            );
+
+            assert_ok!(init_eth_asset());
+            assert_eq!(
+                super::set_spread::<Test>(Eth, 10001),
+                Err(Reason::InvalidSpread)
+            );
+            assert_ok!(super::set_spread::<Test>(Eth, 1000));
+            assert_eq!(Spreads::get(Eth), 1000);
+        });
+    }
+
+    #[test]
+    fn test_set_miner_share() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                super::set_miner_share::<Test>(Eth, 1000),
+                Err(Reason::AssetNotSupported)
+            );
+
+            assert_ok!(init_eth_asset());
+            assert_eq!(
+                super::set_miner_share::<Test>(Eth, 10001),
+                Err(Reason::InvalidMinerShare)
+            );
+            assert_ok!(super::set_miner_share::<Test>(Eth, 1000));
+            assert_eq!(
+                SupportedAssets::get(Eth).unwrap().miner_shares,
+                Factor::from_nominal("0.1")
+            );
+        });
+    }
+
+    #[test]
+    fn test_set_min_tx_value() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                get_min_tx_value(),
+                Quantity::new(pallet_parameters::DEFAULT_MIN_TX_VALUE, USD)
+            );
+            assert_eq!(
+                super::set_min_tx_value::<Test>(Quantity::from_nominal("0", USD)),
+                Err(Reason::InvalidMinTxValue)
+            );
+            assert_eq!(
+                super::set_min_tx_value::<Test>(Quantity::from_nominal("1001", USD)),
+                Err(Reason::InvalidMinTxValue)
+            );
+            assert_eq!(
+                super::set_min_tx_value::<Test>(Quantity::from_nominal("5", ETH)),
+                Err(Reason::InvalidMinTxValue)
+            );
+            assert_ok!(super::set_min_tx_value::<Test>(Quantity::from_nominal(
+                "5", USD
+            )));
+            assert_eq!(get_min_tx_value(), Quantity::from_nominal("5", USD));
+        });
+    }
+
+    #[test]
+    fn test_set_asset_pauses() {
+        new_test_ext().execute_with(|| {
+            let pauses = AssetPauseFlags {
+                borrow: true,
+                ..AssetPauseFlags::default()
+            };
+            assert_eq!(
+                super::set_asset_pauses::<Test>(Eth, pauses),
+                Err(Reason::AssetNotSupported)
+            );
+
+            assert_ok!(init_eth_asset());
+            assert_ok!(super::set_asset_pauses::<Test>(Eth, pauses));
+            assert_eq!(AssetPauses::get(Eth), pauses);
+
+            assert_ok!(super::set_asset_pauses::<Test>(
+                Eth,
+                AssetPauseFlags::default()
+            ));
+            assert!(!AssetPauses::contains_key(Eth));
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::SetAssetPauses(
+                    Eth, pauses
+                )))
+            );
+        });
+    }
+
     #[test]
     fn test_set_rate_model_not_supported() {
         new_test_ext().execute_with(|| {
@@ -183,6 +519,19 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_support_asset_seeds_accrual_timestamp() {
+        new_test_ext().execute_with(|| {
+            // Nothing to seed before the first block
+            assert_ok!(super::support_asset::<Test>(eth));
+            assert_eq!(LastAccrualTimestamps::get(Eth), None);
+
+            LastBlockTimestamp::put(1000);
+            assert_ok!(super::support_asset::<Test>(wbtc));
+            assert_eq!(LastAccrualTimestamps::get(Wbtc), Some(1000));
+        })
+    }
+
     #[test]
     fn test_support_asset_again() {
         new_test_ext().execute_with(|| {
@@ -242,6 +591,59 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_withdraw_reserves() {
+        new_test_ext().execute_with(|| {
+            let recipient = ChainAccount::Eth([1; 20]);
+            TotalReserves::insert(Eth, CashPrincipalAmount(1000));
+            ChainCashPrincipals::insert(ChainId::Eth, CashPrincipalAmount(50));
+
+            assert_eq!(
+                super::withdraw_reserves::<Test>(Eth, CashPrincipalAmount(1001), recipient),
+                Err(Reason::InsufficientReserves)
+            );
+            assert_eq!(
+                super::withdraw_reserves::<Test>(
+                    Eth,
+                    CashPrincipalAmount(100),
+                    ChainAccount::Gate([1; 32])
+                ),
+                Err(Reason::InvalidChain)
+            );
+
+            assert_ok!(super::withdraw_reserves::<Test>(
+                Eth,
+                CashPrincipalAmount(400),
+                recipient
+            ));
+            assert_eq!(TotalReserves::get(Eth), CashPrincipalAmount(600));
+            assert_eq!(
+                ChainCashPrincipals::get(ChainId::Eth),
+                CashPrincipalAmount(450)
+            );
+
+            let notice_id = NoticeId(0, 1);
+            assert_eq!(
+                Notices::get(ChainId::Eth, notice_id),
+                Some(Notice::CashExtractionNotice(CashExtractionNotice::Eth {
+                    id: notice_id,
+                    parent: [0u8; 32],
+                    account: [1; 20],
+                    principal: 400,
+                }))
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::ReservesWithdrawn(
+                    Eth,
+                    recipient,
+                    CashPrincipalAmount(400)
+                )))
+            );
+        });
+    }
+
     #[test]
     fn test_get_assets() -> Result<(), Reason> {
         new_test_ext().execute_with(|| {
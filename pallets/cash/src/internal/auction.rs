@@ -0,0 +1,341 @@
+use crate::{
+    chains::{ChainAccount, ChainAccountSignature, ChainAsset},
+    internal::{
+        assets::get_asset,
+        exec_trx_request::prepend_nonce,
+        liquidate::{liquidate_cash_principal_internal, liquidate_internal},
+        nonces::{check_nonce, use_nonce},
+        parameters::get_global_liquidation_incentive,
+        pause::is_emergency_paused,
+    },
+    log,
+    params::LIQUIDATION_AUCTION_RAMP_BLOCKS,
+    pipeline::CashPipeline,
+    reason::Reason,
+    require,
+    symbol::CASH,
+    types::{AssetAmount, AssetInfo, Bips, CashOrChainAsset, Nonce, Quantity},
+    Config, Event, GlobalCashIndex, LiquidationAuctions, Module,
+};
+use codec::alloc::string::String;
+use frame_support::storage::{StorageMap, StorageValue};
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// Return the current block number, which times liquidation auctions.
+fn current_block<T: Config>() -> u32 {
+    <frame_system::Module<T>>::block_number().unique_saturated_into()
+}
+
+/// Return the seize bonus for a collateral asset some number of blocks into an auction, in bips.
+/// The bonus ramps linearly from zero up to the liquidation incentive for the asset.
+pub fn get_auction_bonus<T: Config>(collateral_asset: AssetInfo, elapsed: u32) -> Bips {
+    let max_bonus = collateral_asset
+        .liquidation_incentive
+        .unwrap_or_else(get_global_liquidation_incentive);
+    max_bonus * elapsed.min(LIQUIDATION_AUCTION_RAMP_BLOCKS) as Bips
+        / LIQUIDATION_AUCTION_RAMP_BLOCKS as Bips
+}
+
+/// Check that a liquidation auction may be opened for an account.
+pub fn check_open_liquidation_auction<T: Config>(borrower: ChainAccount) -> Result<(), Reason> {
+    check_no_liquidation_auction(borrower)?;
+    CashPipeline::new().check_underwater::<T>(borrower)?;
+    Ok(())
+}
+
+/// Check that no liquidation auction is open for an account, so it may be liquidated at the fixed incentive.
+/// While an auction is open, the borrower is only liquidated by bids at the auction bonus.
+pub fn check_no_liquidation_auction(borrower: ChainAccount) -> Result<(), Reason> {
+    require!(
+        !LiquidationAuctions::contains_key(borrower),
+        Reason::LiquidationAuctionOpen
+    );
+    Ok(())
+}
+
+/// Open a liquidation auction for an underwater account, starting from no seize bonus.
+pub fn open_liquidation_auction<T: Config>(borrower: ChainAccount) -> Result<(), Reason> {
+    check_open_liquidation_auction::<T>(borrower)?;
+    let start = current_block::<T>();
+    log!("Opened liquidation auction for {:?} at {}", borrower, start);
+    LiquidationAuctions::insert(borrower, start);
+    <Module<T>>::deposit_event(Event::LiquidationAuctionOpened(borrower, start));
+    Ok(())
+}
+
+/// Check that the liquidation auction for an account may be closed, as it is no longer underwater.
+pub fn check_close_liquidation_auction<T: Config>(borrower: ChainAccount) -> Result<(), Reason> {
+    require!(
+        LiquidationAuctions::contains_key(borrower),
+        Reason::NoLiquidationAuction
+    );
+    match CashPipeline::new().check_underwater::<T>(borrower) {
+        Ok(_) => Err(Reason::InsufficientLiquidity),
+        Err(Reason::SufficientLiquidity) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Close the liquidation auction for an account which is no longer underwater.
+pub fn close_liquidation_auction<T: Config>(borrower: ChainAccount) -> Result<(), Reason> {
+    check_close_liquidation_auction::<T>(borrower)?;
+    LiquidationAuctions::remove(borrower);
+    <Module<T>>::deposit_event(Event::LiquidationAuctionClosed(borrower));
+    Ok(())
+}
+
+/// The payload a keeper signs, with a nonce prepended, to bid in a liquidation auction.
+/// This reads like the `Liquidate` trx request, so keepers can see what they sign.
+pub fn liquidation_bid_payload(
+    borrower: ChainAccount,
+    borrowed: CashOrChainAsset,
+    collateral: ChainAsset,
+    amount: AssetAmount,
+) -> Vec<u8> {
+    let borrowed = match borrowed {
+        CashOrChainAsset::Cash => String::from("Cash"),
+        CashOrChainAsset::ChainAsset(asset) => String::from(asset),
+    };
+    format!(
+        "(LiquidationBid {} {} {} {})",
+        amount,
+        borrowed,
+        String::from(collateral),
+        String::from(borrower)
+    )
+    .into_bytes()
+}
+
+/// Recover the bidder from its signature, checking that the nonce may be used.
+pub fn recover_bidder<T: Config>(
+    borrower: ChainAccount,
+    borrowed: CashOrChainAsset,
+    collateral: ChainAsset,
+    amount: AssetAmount,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<ChainAccount, Reason> {
+    let payload = liquidation_bid_payload(borrower, borrowed, collateral, amount);
+    let bidder = signature
+        .recover_account(&prepend_nonce(&payload, nonce)[..])
+        .map_err(|_| Reason::SignatureAccountMismatch)?;
+    check_nonce::<T>(bidder, nonce)?;
+    Ok(bidder)
+}
+
+/// Repay a borrow in an open liquidation auction, seizing collateral at the current auction bonus.
+/// The auction closes once the borrower is no longer underwater.
+pub fn bid_liquidation_auction<T: Config>(
+    borrower: ChainAccount,
+    borrowed: CashOrChainAsset,
+    collateral: ChainAsset,
+    amount: AssetAmount,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(), Reason> {
+    require!(!is_emergency_paused(), Reason::EmergencyPaused);
+    require!(
+        borrowed != CashOrChainAsset::ChainAsset(collateral),
+        Reason::InKindLiquidation
+    );
+    let bidder = recover_bidder::<T>(borrower, borrowed, collateral, amount, signature, nonce)?;
+    let start = LiquidationAuctions::get(borrower).ok_or(Reason::NoLiquidationAuction)?;
+    let collateral_asset = get_asset::<T>(collateral)?;
+    let bonus =
+        get_auction_bonus::<T>(collateral_asset, current_block::<T>().saturating_sub(start));
+
+    // Note: the auction bonus takes the place of the fixed incentive for the collateral
+    let auction_collateral_asset = AssetInfo {
+        liquidation_incentive: Some(bonus),
+        ..collateral_asset
+    };
+    match borrowed {
+        CashOrChainAsset::Cash => {
+            let principal =
+                GlobalCashIndex::get().cash_principal_amount(Quantity::new(amount, CASH))?;
+            liquidate_cash_principal_internal::<T>(
+                auction_collateral_asset,
+                bidder,
+                borrower,
+                principal,
+            )?;
+        }
+        CashOrChainAsset::ChainAsset(asset) => {
+            let asset_info = get_asset::<T>(asset)?;
+            liquidate_internal::<T>(
+                asset_info,
+                auction_collateral_asset,
+                bidder,
+                borrower,
+                asset_info.as_quantity(amount),
+            )?;
+        }
+    }
+
+    use_nonce::<T>(bidder, nonce);
+    <Module<T>>::deposit_event(Event::LiquidationAuctionBid(borrower, bidder, bonus));
+
+    if check_close_liquidation_auction::<T>(borrower).is_ok() {
+        LiquidationAuctions::remove(borrower);
+        <Module<T>>::deposit_event(Event::LiquidationAuctionClosed(borrower));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{assets::*, common::*, *};
+
+    const BORROWER: ChainAccount = ChainAccount::Eth([2; 20]);
+
+    fn sign_bid(
+        borrowed: CashOrChainAsset,
+        collateral: ChainAsset,
+        amount: AssetAmount,
+        nonce: Nonce,
+    ) -> (ChainAccount, ChainAccountSignature) {
+        let bidder_key = <Ethereum as Chain>::signer_address().unwrap();
+        let message = prepend_nonce(
+            &liquidation_bid_payload(BORROWER, borrowed, collateral, amount),
+            nonce,
+        );
+        let full_message: Vec<u8> = [
+            format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes(),
+            &message[..],
+        ]
+        .concat();
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        let signature = ChainAccountSignature::Eth(
+            bidder_key,
+            runtime_interfaces::keyring_interface::sign_one(full_message, eth_key_id).unwrap(),
+        );
+        (ChainAccount::Eth(bidder_key), signature)
+    }
+
+    #[test]
+    fn test_liquidation_bid_payload() {
+        assert_eq!(
+            liquidation_bid_payload(BORROWER, CashOrChainAsset::Cash, Wbtc, 5),
+            format!(
+                "(LiquidationBid 5 Cash ETH:0x{} ETH:0x{})",
+                hex::encode([0xbb; 20]),
+                hex::encode([2; 20])
+            )
+            .into_bytes()
+        );
+    }
+
+    #[test]
+    fn test_get_auction_bonus() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(get_auction_bonus::<Test>(eth, 0), 0);
+            assert_eq!(get_auction_bonus::<Test>(eth, 50), 400);
+            assert_eq!(get_auction_bonus::<Test>(eth, 100), 800);
+            assert_eq!(get_auction_bonus::<Test>(eth, 1000), 800);
+            assert_eq!(
+                get_auction_bonus::<Test>(
+                    AssetInfo {
+                        liquidation_incentive: Some(1000),
+                        ..eth
+                    },
+                    50
+                ),
+                500
+            );
+        });
+    }
+
+    #[test]
+    fn test_open_and_close_liquidation_auction() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+
+            init_asset_balance(Eth, BORROWER, Balance::from_nominal("-1", ETH).value);
+            assert_eq!(
+                close_liquidation_auction::<Test>(BORROWER),
+                Err(Reason::NoLiquidationAuction)
+            );
+            assert_eq!(open_liquidation_auction::<Test>(BORROWER), Ok(()));
+            assert_eq!(LiquidationAuctions::get(BORROWER), Some(1));
+            assert_eq!(
+                open_liquidation_auction::<Test>(BORROWER),
+                Err(Reason::LiquidationAuctionOpen)
+            );
+            assert_eq!(
+                close_liquidation_auction::<Test>(BORROWER),
+                Err(Reason::InsufficientLiquidity)
+            );
+
+            init_cash(BORROWER, CashPrincipal::from_nominal("10000"));
+            assert_eq!(close_liquidation_auction::<Test>(BORROWER), Ok(()));
+            assert_eq!(LiquidationAuctions::get(BORROWER), None);
+            assert_eq!(
+                open_liquidation_auction::<Test>(BORROWER),
+                Err(Reason::SufficientLiquidity)
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(events.contains(&mock::Event::pallet_cash(
+                crate::Event::LiquidationAuctionOpened(BORROWER, 1)
+            )));
+            assert!(events.contains(&mock::Event::pallet_cash(
+                crate::Event::LiquidationAuctionClosed(BORROWER)
+            )));
+        });
+    }
+
+    #[test]
+    fn test_bid_liquidation_auction() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+            assert_ok!(init_wbtc_asset());
+
+            let borrowed = CashOrChainAsset::ChainAsset(Eth);
+            let amount = Quantity::from_nominal("1", ETH).value;
+            let (bidder, signature) = sign_bid(borrowed, Wbtc, amount, 0);
+
+            init_asset_balance(Eth, BORROWER, Balance::from_nominal("-80", ETH).value);
+            init_asset_balance(Wbtc, BORROWER, Balance::from_nominal("2", WBTC).value);
+            init_cash(BORROWER, CashPrincipal::from_nominal("100000"));
+            init_asset_balance(Eth, bidder, Balance::from_nominal("1", ETH).value);
+
+            assert_eq!(
+                bid_liquidation_auction::<Test>(BORROWER, borrowed, Wbtc, amount, signature, 0),
+                Err(Reason::NoLiquidationAuction)
+            );
+
+            assert_eq!(open_liquidation_auction::<Test>(BORROWER), Ok(()));
+            System::set_block_number(26);
+
+            // Seize amount = 1.02 * 1 * 2000 / 60000 = 0.034 WBTC
+            assert_eq!(
+                bid_liquidation_auction::<Test>(BORROWER, borrowed, Wbtc, amount, signature, 0),
+                Ok(())
+            );
+            assert_eq!(
+                AssetBalances::get(Wbtc, BORROWER),
+                Balance::from_nominal("1.966", WBTC).value
+            );
+            assert_eq!(
+                AssetBalances::get(Wbtc, bidder),
+                Balance::from_nominal("0.034", WBTC).value
+            );
+            assert_eq!(
+                AssetBalances::get(Eth, BORROWER),
+                Balance::from_nominal("-79", ETH).value
+            );
+            assert_eq!(LiquidationAuctions::get(BORROWER), Some(1));
+            assert_eq!(
+                bid_liquidation_auction::<Test>(BORROWER, borrowed, Wbtc, amount, signature, 0),
+                Err(Reason::IncorrectNonce(0, 1))
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(events.contains(&mock::Event::pallet_cash(
+                crate::Event::LiquidationAuctionBid(BORROWER, bidder, 200)
+            )));
+        });
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{
+    chains::ChainAccount,
+    internal::{
+        assets::{get_price, get_value},
+        balance_helpers::neg_balance,
+        insurance::get_insurance_cover,
+    },
+    log,
+    pipeline::{load_portfolio, CashPipeline},
+    reason::Reason,
+    require,
+    types::{CashIndex, CashPrincipalAmount, Factor, CASH},
+    BadDebt, Config, Event, GlobalCashIndex, InsuranceFund, LastYieldCashIndex, Module,
+    TotalReserves,
+};
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
+
+/// Write off the debts of an account which has no collateral left for liquidators to seize.
+/// The shortfall is covered from protocol reserves first, then the insurance fund,
+/// and only then socialized across CASH holders.
+pub fn absorb_internal<T: Config>(account: ChainAccount) -> Result<(), Reason> {
+    let portfolio = load_portfolio::<T>(account)?;
+    require!(
+        portfolio.cash.value <= 0 && portfolio.positions.iter().all(|(_, b)| b.value <= 0),
+        Reason::NotInsolvent
+    );
+
+    let index = GlobalCashIndex::get();
+    let price_cash = get_price::<T>(CASH)?;
+    let mut pipeline = CashPipeline::new();
+    let mut shortfall = CashPrincipalAmount::ZERO;
+    for (asset_info, balance) in portfolio.positions {
+        let debt = asset_info.as_quantity(neg_balance(balance.value)?);
+        let debt_cash = get_value::<T>(debt)?.div_price(price_cash, CASH)?;
+        shortfall = shortfall.add(index.cash_principal_amount(debt_cash)?)?;
+        pipeline = pipeline.forgive_asset::<T>(account, asset_info.asset, debt)?;
+    }
+
+    // Note: forgiving asset debts settles their interest into the account's CASH principal
+    let cash_debt = pipeline
+        .state
+        .get_cash_principal::<T>(account)
+        .amount_repayable()?;
+    if cash_debt != CashPrincipalAmount::ZERO {
+        shortfall = shortfall.add(cash_debt)?;
+        pipeline = pipeline.forgive_cash::<T>(account, cash_debt)?;
+    }
+    require!(shortfall != CashPrincipalAmount::ZERO, Reason::NotInsolvent);
+
+    let mut from_reserves = CashPrincipalAmount::ZERO;
+    let mut reserve_updates = Vec::new();
+    for (asset, reserves) in TotalReserves::iter() {
+        let remaining = shortfall.sub(from_reserves)?;
+        if remaining == CashPrincipalAmount::ZERO {
+            break;
+        }
+        let used = if reserves < remaining {
+            reserves
+        } else {
+            remaining
+        };
+        from_reserves = from_reserves.add(used)?;
+        reserve_updates.push((asset, reserves.sub(used)?));
+    }
+
+    let (from_insurance, insurance_fund_new) =
+        get_insurance_cover::<T>(shortfall.sub(from_reserves)?)?;
+    let socialized = shortfall.sub(from_reserves)?.sub(from_insurance)?;
+    let cash_index_new = socialize_loss(
+        GlobalCashIndex::get(),
+        socialized,
+        pipeline.state.get_total_cash_principal::<T>(),
+    )?;
+    let last_yield_cash_index_new = socialize_loss(
+        LastYieldCashIndex::get(),
+        socialized,
+        pipeline.state.get_total_cash_principal::<T>(),
+    )?;
+    let bad_debt_new = pipeline.state.get_bad_debt::<T>().add(shortfall)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    log!(
+        "Absorbed {:?}: shortfall={:?} from_reserves={:?} from_insurance={:?} socialized={:?}",
+        account,
+        shortfall,
+        from_reserves,
+        from_insurance,
+        socialized
+    );
+    pipeline.commit::<T>();
+    for (asset, reserves) in reserve_updates {
+        TotalReserves::insert(asset, reserves);
+    }
+    InsuranceFund::put(insurance_fund_new);
+    GlobalCashIndex::put(cash_index_new);
+    LastYieldCashIndex::put(last_yield_cash_index_new);
+    BadDebt::put(bad_debt_new);
+
+    <Module<T>>::deposit_event(Event::BadDebtAbsorbed(
+        account,
+        shortfall,
+        from_reserves,
+        from_insurance,
+        socialized,
+    ));
+
+    Ok(())
+}
+
+/// Lower a CASH index so that the total CASH principal is worth the given loss less than before.
+fn socialize_loss(
+    index: CashIndex,
+    loss: CashPrincipalAmount,
+    total_cash_principal: CashPrincipalAmount,
+) -> Result<CashIndex, Reason> {
+    if loss == CashPrincipalAmount::ZERO {
+        return Ok(index);
+    }
+    let remaining = total_cash_principal
+        .sub(loss)
+        .map_err(|_| Reason::InsufficientChainCash)?;
+    let factor = Factor::from_fraction(remaining.0, total_cash_principal.0)?;
+    Ok(index.increment(factor.into())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{assets::*, common::*, *};
+
+    const BORROWER: ChainAccount = ChainAccount::Eth([2; 20]);
+
+    #[test]
+    fn test_absorb_with_collateral() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+            assert_ok!(init_wbtc_asset());
+
+            init_asset_balance(Eth, BORROWER, Balance::from_nominal("-1", ETH).value);
+            init_asset_balance(Wbtc, BORROWER, Balance::from_nominal("0.01", WBTC).value);
+
+            assert_eq!(absorb_internal::<Test>(BORROWER), Err(Reason::NotInsolvent));
+        });
+    }
+
+    #[test]
+    fn test_absorb_without_debt() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(absorb_internal::<Test>(BORROWER), Err(Reason::NotInsolvent));
+        });
+    }
+
+    #[test]
+    fn test_absorb_from_reserves_and_socialized() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+
+            // borrower owes 1 ETH ($2000) and 500 CASH, with nothing left to seize
+            init_asset_balance(Eth, BORROWER, Balance::from_nominal("-1", ETH).value);
+            init_cash(BORROWER, CashPrincipal::from_nominal("-500"));
+            TotalReserves::insert(Eth, CashPrincipalAmount::from_nominal("1000"));
+            InsuranceFund::put(CashPrincipalAmount::from_nominal("500"));
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("15000"));
+
+            assert_eq!(absorb_internal::<Test>(BORROWER), Ok(()));
+
+            let shortfall = CashPrincipalAmount::from_nominal("2500");
+            let from_reserves = CashPrincipalAmount::from_nominal("1000");
+            let from_insurance = CashPrincipalAmount::from_nominal("500");
+            let socialized = CashPrincipalAmount::from_nominal("1000");
+            assert_eq!(AssetBalances::get(Eth, BORROWER), 0);
+            assert_eq!(TotalBorrowAssets::get(Eth), 0);
+            assert_eq!(CashPrincipals::get(BORROWER), CashPrincipal::ZERO);
+            assert_eq!(TotalReserves::get(Eth), CashPrincipalAmount::ZERO);
+            assert_eq!(InsuranceFund::get(), CashPrincipalAmount::ZERO);
+            assert_eq!(BadDebt::get(), shortfall);
+
+            // the remaining 14500 principal is now worth 1000 less
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                CashPrincipalAmount::from_nominal("14500")
+            );
+            assert_eq!(
+                GlobalCashIndex::get(),
+                CashIndex::from_nominal("0.931034482758620689")
+            );
+            assert_eq!(LastYieldCashIndex::get(), GlobalCashIndex::get());
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::BadDebtAbsorbed(
+                    BORROWER,
+                    shortfall,
+                    from_reserves,
+                    from_insurance,
+                    socialized
+                )))
+            );
+
+            assert_eq!(absorb_internal::<Test>(BORROWER), Err(Reason::NotInsolvent));
+        });
+    }
+}
@@ -0,0 +1,371 @@
+use crate::{
+    chains::ChainAccount,
+    params::{MIN_BOND, UNBONDING_DELAY, UNBONDING_RELEASES_PER_BLOCK},
+    pipeline::CashPipeline,
+    reason::Reason,
+    require,
+    trx_req::MaxAssetAmount,
+    types::{CashPrincipalAmount, Quantity, Timestamp, Unbonding, ValidatorKeys, CASH},
+    Config, Event, GlobalCashIndex, InsuranceFund, MinValidatorBond, Module, UnbondingQueue,
+    UnbondingQueueBounds, Unbondings, ValidatorBonds, Validators,
+};
+use frame_support::storage::{StorageMap, StorageValue};
+use our_std::debug;
+
+/// Set the minimum CASH principal a validator's Eth account must have bonded to join the validator set.
+pub fn set_min_validator_bond<T: Config>(principal: CashPrincipalAmount) -> Result<(), Reason> {
+    MinValidatorBond::put(principal);
+    <Module<T>>::deposit_event(Event::SetMinValidatorBond(principal));
+    Ok(())
+}
+
+/// Return the CASH principal bonded on behalf of a validator, by its Eth account.
+pub fn get_validator_bond(validator: &ValidatorKeys) -> CashPrincipalAmount {
+    ValidatorBonds::get(ChainAccount::Eth(validator.eth_address))
+}
+
+/// Require the validators joining the set to have bonded at least the minimum.
+/// Validators already in the set keep their seat, even if their bond has since fallen.
+pub fn check_validator_bonds<T: Config>(validators: &[ValidatorKeys]) -> Result<(), Reason> {
+    let min_bond = MinValidatorBond::get();
+    for validator in validators {
+        if !Validators::contains_key(&validator.substrate_id) {
+            require!(
+                get_validator_bond(validator) >= min_bond,
+                Reason::ValidatorNotBonded
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Move CASH principal held by an account into its bond, returning the principal and the bond after.
+pub fn prepare_bond<T: Config>(
+    pipeline: CashPipeline,
+    account: ChainAccount,
+    amount: MaxAssetAmount,
+) -> Result<(CashPipeline, CashPrincipalAmount, CashPrincipalAmount), Reason> {
+    let withdrawable = pipeline
+        .state
+        .get_cash_principal::<T>(account)
+        .amount_withdrawable()?;
+    let principal = match amount {
+        MaxAssetAmount::Max => withdrawable,
+        MaxAssetAmount::Amount(amount) => {
+            GlobalCashIndex::get().cash_principal_amount(Quantity::new(amount, CASH))?
+        }
+    };
+    require!(principal <= withdrawable, Reason::InsufficientTotalFunds);
+    let bond_post = ValidatorBonds::get(account).add(principal)?;
+    require!(bond_post >= MIN_BOND, Reason::BondTooSmall);
+    let pipeline = pipeline
+        .bond_cash::<T>(account, principal)?
+        .check_collateralized::<T>(account)?;
+    Ok((pipeline, principal, bond_post))
+}
+
+/// Record the bond of an account, once the CASH principal moved into it is committed.
+pub fn finish_bond<T: Config>(
+    account: ChainAccount,
+    principal: CashPrincipalAmount,
+    bond_post: CashPrincipalAmount,
+) {
+    ValidatorBonds::insert(account, bond_post);
+    <Module<T>>::deposit_event(Event::Bonded(account, principal));
+}
+
+/// Unbond CASH principal from an account's bond, to be released back to it after the unbonding delay.
+/// Unbonding again adds to the principal already unbonding, and restarts the delay.
+/// Unless unbonding all of it, at least the minimum must be unbonded, and remain bonded.
+pub fn unbond<T: Config>(
+    account: ChainAccount,
+    amount: MaxAssetAmount,
+    now: Timestamp,
+) -> Result<(), Reason> {
+    let bond = ValidatorBonds::get(account);
+    let principal = match amount {
+        MaxAssetAmount::Max => bond,
+        MaxAssetAmount::Amount(amount) => {
+            GlobalCashIndex::get().cash_principal_amount(Quantity::new(amount, CASH))?
+        }
+    };
+    require!(
+        principal > CashPrincipalAmount::ZERO,
+        Reason::InsufficientBond
+    );
+    let bond_post = bond.sub(principal).map_err(|_| Reason::InsufficientBond)?;
+    require!(
+        bond_post == CashPrincipalAmount::ZERO || (principal >= MIN_BOND && bond_post >= MIN_BOND),
+        Reason::BondTooSmall
+    );
+    let unbonding_post = Unbonding {
+        principal: Unbondings::get(account)
+            .map_or(CashPrincipalAmount::ZERO, |unbonding| unbonding.principal)
+            .add(principal)?,
+        release_at: now.saturating_add(UNBONDING_DELAY),
+    };
+
+    if bond_post == CashPrincipalAmount::ZERO {
+        ValidatorBonds::remove(account);
+    } else {
+        ValidatorBonds::insert(account, bond_post);
+    }
+    Unbondings::insert(account, unbonding_post);
+    queue_unbonding(account, unbonding_post.release_at);
+
+    <Module<T>>::deposit_event(Event::Unbonded(
+        account,
+        principal,
+        unbonding_post.release_at,
+    ));
+
+    Ok(())
+}
+
+/// Add an account to the end of the unbonding queue, to be released at the given time.
+/// Since the unbonding delay is fixed, release times are queued in order.
+fn queue_unbonding(account: ChainAccount, release_at: Timestamp) {
+    let (start, end) = UnbondingQueueBounds::get();
+    UnbondingQueue::insert(end, (account, release_at));
+    UnbondingQueueBounds::put((start, end + 1));
+}
+
+/// Release the unbonded CASH principal whose unbonding delay has passed, back to its accounts.
+/// At most UNBONDING_RELEASES_PER_BLOCK entries are taken from the front of the queue per block.
+pub fn release_unbondings<T: Config>(now: Timestamp) {
+    for _ in 0..UNBONDING_RELEASES_PER_BLOCK {
+        let (start, end) = UnbondingQueueBounds::get();
+        if start == end {
+            break;
+        }
+        let queued = UnbondingQueue::get(start);
+        if let Some((_, release_at)) = queued {
+            if release_at > now {
+                break;
+            }
+        }
+        UnbondingQueue::remove(start);
+        UnbondingQueueBounds::put((start + 1, end));
+
+        // Skip entries superseded by unbonding again, or slashed
+        if let Some((account, release_at)) = queued {
+            match Unbondings::get(account) {
+                Some(unbonding) if unbonding.release_at == release_at => {
+                    release_unbonding::<T>(account, unbonding)
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Release an unbonding back to its account, or queue it to try again if it can't be.
+fn release_unbonding<T: Config>(account: ChainAccount, unbonding: Unbonding) {
+    match CashPipeline::new().unbond_cash::<T>(account, unbonding.principal) {
+        Ok(pipeline) => pipeline.commit::<T>(),
+        Err(reason) => {
+            debug!("Unbonding of {:?} not released: {:?}", account, reason);
+            queue_unbonding(account, unbonding.release_at);
+            return;
+        }
+    }
+    Unbondings::remove(account);
+    <Module<T>>::deposit_event(Event::BondReleased(account, unbonding.principal));
+}
+
+/// Slash the bond of an account into the insurance fund, including any CASH principal still unbonding.
+pub fn slash_bond<T: Config>(account: ChainAccount) -> Result<CashPrincipalAmount, Reason> {
+    let unbonding =
+        Unbondings::get(account).map_or(CashPrincipalAmount::ZERO, |unbonding| unbonding.principal);
+    let slashed = ValidatorBonds::get(account).add(unbonding)?;
+    if slashed == CashPrincipalAmount::ZERO {
+        return Ok(slashed);
+    }
+    let insurance_fund_post = InsuranceFund::get().add(slashed)?;
+
+    ValidatorBonds::remove(account);
+    Unbondings::remove(account);
+    InsuranceFund::put(insurance_fund_post);
+
+    <Module<T>>::deposit_event(Event::BondSlashed(account, slashed));
+
+    Ok(slashed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::*, types::CashPrincipal, CashPrincipals};
+    use frame_support::storage::IterableStorageMap;
+
+    const BONDER: ChainAccount = ChainAccount::Eth([1; 20]);
+
+    #[test]
+    fn test_bond_unbond_and_release() {
+        new_test_ext().execute_with(|| {
+            CashPrincipals::insert(BONDER, CashPrincipal(1000_000000));
+
+            assert_eq!(
+                prepare_bond::<Test>(
+                    CashPipeline::new(),
+                    BONDER,
+                    MaxAssetAmount::Amount(1000_000001)
+                )
+                .map(|(_, principal, _)| principal),
+                Err(Reason::InsufficientTotalFunds)
+            );
+            assert_eq!(
+                prepare_bond::<Test>(
+                    CashPipeline::new(),
+                    BONDER,
+                    MaxAssetAmount::Amount(9_999999)
+                )
+                .map(|(_, principal, _)| principal),
+                Err(Reason::BondTooSmall)
+            );
+            let (pipeline, principal, bond_post) = prepare_bond::<Test>(
+                CashPipeline::new(),
+                BONDER,
+                MaxAssetAmount::Amount(600_000000),
+            )
+            .unwrap();
+            pipeline.commit::<Test>();
+            finish_bond::<Test>(BONDER, principal, bond_post);
+            assert_eq!(CashPrincipals::get(BONDER), CashPrincipal(400_000000));
+            assert_eq!(ValidatorBonds::get(BONDER), CashPrincipalAmount(600_000000));
+
+            assert_eq!(
+                unbond::<Test>(BONDER, MaxAssetAmount::Amount(600_000001), 10),
+                Err(Reason::InsufficientBond)
+            );
+            assert_eq!(
+                unbond::<Test>(BONDER, MaxAssetAmount::Amount(9_999999), 10),
+                Err(Reason::BondTooSmall)
+            );
+            assert_eq!(
+                unbond::<Test>(BONDER, MaxAssetAmount::Amount(590_000001), 10),
+                Err(Reason::BondTooSmall)
+            );
+            assert_eq!(
+                unbond::<Test>(BONDER, MaxAssetAmount::Amount(200_000000), 10),
+                Ok(())
+            );
+            assert_eq!(ValidatorBonds::get(BONDER), CashPrincipalAmount(400_000000));
+            assert_eq!(
+                Unbondings::get(BONDER),
+                Some(Unbonding {
+                    principal: CashPrincipalAmount(200_000000),
+                    release_at: 10 + UNBONDING_DELAY,
+                })
+            );
+
+            release_unbondings::<Test>(10 + UNBONDING_DELAY - 1);
+            assert_eq!(CashPrincipals::get(BONDER), CashPrincipal(400_000000));
+
+            release_unbondings::<Test>(10 + UNBONDING_DELAY);
+            assert_eq!(CashPrincipals::get(BONDER), CashPrincipal(600_000000));
+            assert_eq!(Unbondings::get(BONDER), None);
+            assert_eq!(UnbondingQueueBounds::get(), (1, 1));
+        });
+    }
+
+    #[test]
+    fn test_release_unbondings_in_order_and_bounded() {
+        new_test_ext().execute_with(|| {
+            let bonders: Vec<ChainAccount> = (0..UNBONDING_RELEASES_PER_BLOCK + 1)
+                .map(|i| ChainAccount::Eth([i as u8 + 1; 20]))
+                .collect();
+            for (i, bonder) in bonders.iter().enumerate() {
+                ValidatorBonds::insert(bonder, MIN_BOND);
+                assert_eq!(
+                    unbond::<Test>(*bonder, MaxAssetAmount::Max, 10 + i as Timestamp),
+                    Ok(())
+                );
+            }
+
+            // Unbonding again supersedes the queued release
+            ValidatorBonds::insert(bonders[0], MIN_BOND);
+            assert_eq!(unbond::<Test>(bonders[0], MaxAssetAmount::Max, 100), Ok(()));
+
+            let last = 10 + UNBONDING_RELEASES_PER_BLOCK as Timestamp + UNBONDING_DELAY;
+            release_unbondings::<Test>(last);
+            assert_eq!(
+                UnbondingQueueBounds::get(),
+                (
+                    UNBONDING_RELEASES_PER_BLOCK,
+                    UNBONDING_RELEASES_PER_BLOCK + 2
+                )
+            );
+            assert!(Unbondings::get(bonders[0]).is_some());
+            for bonder in &bonders[1..bonders.len() - 1] {
+                assert_eq!(Unbondings::get(bonder), None);
+                assert_eq!(
+                    CashPrincipals::get(bonder),
+                    CashPrincipal(MIN_BOND.0 as i128)
+                );
+            }
+            assert!(Unbondings::get(bonders[bonders.len() - 1]).is_some());
+
+            release_unbondings::<Test>(last);
+            assert_eq!(Unbondings::get(bonders[bonders.len() - 1]), None);
+            assert!(Unbondings::get(bonders[0]).is_some());
+
+            release_unbondings::<Test>(100 + UNBONDING_DELAY);
+            assert_eq!(Unbondings::get(bonders[0]), None);
+            assert_eq!(
+                CashPrincipals::get(bonders[0]),
+                CashPrincipal(2 * MIN_BOND.0 as i128)
+            );
+        });
+    }
+
+    #[test]
+    fn test_slash_bond() {
+        new_test_ext().execute_with(|| {
+            ValidatorBonds::insert(BONDER, CashPrincipalAmount(300));
+            Unbondings::insert(
+                BONDER,
+                Unbonding {
+                    principal: CashPrincipalAmount(200),
+                    release_at: 10,
+                },
+            );
+            InsuranceFund::put(CashPrincipalAmount(1000));
+
+            assert_eq!(slash_bond::<Test>(BONDER), Ok(CashPrincipalAmount(500)));
+            assert_eq!(ValidatorBonds::get(BONDER), CashPrincipalAmount::ZERO);
+            assert_eq!(Unbondings::get(BONDER), None);
+            assert_eq!(InsuranceFund::get(), CashPrincipalAmount(1500));
+            assert_eq!(slash_bond::<Test>(BONDER), Ok(CashPrincipalAmount::ZERO));
+        });
+    }
+
+    #[test]
+    fn test_check_validator_bonds() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let current: Vec<ValidatorKeys> = Validators::iter_values().collect();
+            let candidate = ValidatorKeys {
+                substrate_id: AccountId32::new([9u8; 32]),
+                eth_address: [9u8; 20],
+                next_eth_key: None,
+            };
+            let mut validators = current.clone();
+            validators.push(candidate.clone());
+
+            assert_eq!(check_validator_bonds::<Test>(&validators), Ok(()));
+            assert_eq!(
+                set_min_validator_bond::<Test>(CashPrincipalAmount(100)),
+                Ok(())
+            );
+            assert_eq!(
+                check_validator_bonds::<Test>(&validators),
+                Err(Reason::ValidatorNotBonded)
+            );
+            assert_eq!(check_validator_bonds::<Test>(&current), Ok(()));
+
+            ValidatorBonds::insert(ChainAccount::Eth([9u8; 20]), CashPrincipalAmount(100));
+            assert_eq!(check_validator_bonds::<Test>(&validators), Ok(()));
+        });
+    }
+}
@@ -1,8 +1,16 @@
 use crate::{
-    internal, reason::Reason, require, types::ValidatorKeys, Config, Event, Module, NextValidators,
-    NoticeHolds, SessionInterface,
+    chains::{Chain, ChainAccount, ChainAccountSignature, ChainId, Ethereum},
+    core::{get_recent_timestamp, get_validator},
+    internal,
+    params::KEY_TRANSITION_PERIOD,
+    reason::Reason,
+    require,
+    types::{SubstrateId, Timestamp, ValidatorKeys},
+    Config, Event, LatestNotice, Module, NextValidators, NoticeHolds, SessionInterface, Validators,
 };
+use codec::alloc::string::String;
 use frame_support::storage::{IterableStorageMap, StorageMap};
+use our_std::vec::Vec;
 
 pub fn change_validators<T: Config>(validators: Vec<ValidatorKeys>) -> Result<(), Reason> {
     require!(NoticeHolds::iter().count() == 0, Reason::PendingAuthNotice);
@@ -13,6 +21,7 @@ pub fn change_validators<T: Config>(validators: Vec<ValidatorKeys>) -> Result<()
             Reason::ChangeValidatorsError
         );
     }
+    internal::bonding::check_validator_bonds::<T>(&validators)?;
 
     for (id, _keys) in NextValidators::iter() {
         NextValidators::take(&id);
@@ -31,6 +40,210 @@ pub fn change_validators<T: Config>(validators: Vec<ValidatorKeys>) -> Result<()
     Ok(())
 }
 
+/// Drop a compromised validator from the active set immediately, and change to the remaining validators.
+/// Any change of validators already pending is superseded, without waiting on its notices to be signed.
+pub fn remove_validator_immediately<T: Config>(substrate_id: SubstrateId) -> Result<(), Reason> {
+    require!(
+        Validators::contains_key(&substrate_id),
+        Reason::UnknownValidator
+    );
+    let remaining: Vec<ValidatorKeys> = Validators::iter_values()
+        .filter(|v| v.substrate_id != substrate_id)
+        .collect();
+    require!(!remaining.is_empty(), Reason::ChangeValidatorsError);
+    for validator in remaining.iter() {
+        require!(
+            <T>::SessionInterface::has_next_keys(validator.substrate_id.clone()),
+            Reason::ChangeValidatorsError
+        );
+    }
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    Validators::remove(&substrate_id);
+    for (chain_id, _notice_id) in NoticeHolds::iter() {
+        NoticeHolds::remove(chain_id);
+    }
+
+    <Module<T>>::deposit_event(Event::ValidatorRemoved(substrate_id));
+
+    change_validators::<T>(remaining)
+}
+
+/// Return the message which both the current and new Eth keys of a validator sign to rotate keys.
+/// The current Eth notice era is included, so that the proof cannot be replayed after rotating.
+pub fn rotate_keys_payload(
+    current_eth_address: <Ethereum as Chain>::Address,
+    new_eth_address: <Ethereum as Chain>::Address,
+) -> Vec<u8> {
+    let era_id = LatestNotice::get(ChainId::Eth).map_or(0, |(notice_id, _)| notice_id.era_id());
+    format!(
+        "(RotateKeys {} {} {})",
+        String::from(ChainAccount::Eth(current_eth_address)),
+        String::from(ChainAccount::Eth(new_eth_address)),
+        era_id
+    )
+    .into_bytes()
+}
+
+/// Recover the validator rotating its Eth key, and its keys after rotation, from the proof signatures.
+pub fn recover_key_rotation<T: Config>(
+    new_account: ChainAccount,
+    current_signature: ChainAccountSignature,
+    new_signature: ChainAccountSignature,
+) -> Result<(ValidatorKeys, ValidatorKeys), Reason> {
+    let (current_eth_address, new_eth_address) = match (current_signature, new_account) {
+        (
+            ChainAccountSignature::Eth(current_eth_address, _),
+            ChainAccount::Eth(new_eth_address),
+        ) => (current_eth_address, new_eth_address),
+        _ => return Err(Reason::InvalidKeyRotation),
+    };
+    let validator = get_validator::<T>(ChainAccount::Eth(current_eth_address))?;
+    require!(
+        Validators::iter_values().all(|v| v.eth_address != new_eth_address),
+        Reason::InvalidKeyRotation
+    );
+    require!(
+        NextValidators::iter().count() == 0,
+        Reason::InvalidKeyRotation
+    );
+
+    let payload = rotate_keys_payload(current_eth_address, new_eth_address);
+    require!(
+        current_signature.recover_account(&payload[..])? == ChainAccount::Eth(current_eth_address),
+        Reason::SignatureAccountMismatch
+    );
+    require!(
+        new_signature.recover_account(&payload[..])? == ChainAccount::Eth(new_eth_address),
+        Reason::SignatureAccountMismatch
+    );
+
+    let rotated = ValidatorKeys {
+        substrate_id: validator.substrate_id.clone(),
+        eth_address: new_eth_address,
+        next_eth_key: None,
+    };
+    Ok((validator, rotated))
+}
+
+/// Rotate the Eth key of an active validator, proven by signatures from both its current and new keys.
+/// The validator set is changed just as by governance, with the new key replacing the current one.
+pub fn rotate_keys<T: Config>(
+    new_account: ChainAccount,
+    current_signature: ChainAccountSignature,
+    new_signature: ChainAccountSignature,
+) -> Result<(), Reason> {
+    let (validator, rotated) =
+        recover_key_rotation::<T>(new_account, current_signature, new_signature)?;
+    let validators = Validators::iter_values()
+        .map(|v| {
+            if v.substrate_id == rotated.substrate_id {
+                rotated.clone()
+            } else {
+                v
+            }
+        })
+        .collect();
+
+    change_validators::<T>(validators)?;
+
+    <Module<T>>::deposit_event(Event::ValidatorKeysRotated(
+        rotated.substrate_id,
+        ChainAccount::Eth(validator.eth_address),
+        new_account,
+    ));
+
+    Ok(())
+}
+
+/// Return the message which both the current and next Eth keys of a validator sign to advertise the next key.
+/// The current Eth notice era is included, so that the proof cannot be replayed after rotating.
+pub fn next_eth_key_payload(
+    current_eth_address: <Ethereum as Chain>::Address,
+    next_eth_address: <Ethereum as Chain>::Address,
+) -> Vec<u8> {
+    let era_id = LatestNotice::get(ChainId::Eth).map_or(0, |(notice_id, _)| notice_id.era_id());
+    format!(
+        "(AdvertiseNextEthKey {} {} {})",
+        String::from(ChainAccount::Eth(current_eth_address)),
+        String::from(ChainAccount::Eth(next_eth_address)),
+        era_id
+    )
+    .into_bytes()
+}
+
+/// Recover the validator advertising its next Eth key, and its keys with the next key, from the proof signatures.
+/// The next key is accepted alongside the current one until the transition period expires.
+pub fn recover_next_eth_key<T: Config>(
+    next_account: ChainAccount,
+    current_signature: ChainAccountSignature,
+    next_signature: ChainAccountSignature,
+) -> Result<(ValidatorKeys, ValidatorKeys), Reason> {
+    let (current_eth_address, next_eth_address) = match (current_signature, next_account) {
+        (
+            ChainAccountSignature::Eth(current_eth_address, _),
+            ChainAccount::Eth(next_eth_address),
+        ) => (current_eth_address, next_eth_address),
+        _ => return Err(Reason::InvalidKeyRotation),
+    };
+    let validator = get_validator::<T>(ChainAccount::Eth(current_eth_address))?;
+    let now = get_recent_timestamp::<T>()?;
+    require!(
+        Validators::iter_values().all(|v| !v.has_eth_address(next_eth_address, Some(now))),
+        Reason::InvalidKeyRotation
+    );
+
+    let payload = next_eth_key_payload(current_eth_address, next_eth_address);
+    require!(
+        current_signature.recover_account(&payload[..])? == ChainAccount::Eth(current_eth_address),
+        Reason::SignatureAccountMismatch
+    );
+    require!(
+        next_signature.recover_account(&payload[..])? == ChainAccount::Eth(next_eth_address),
+        Reason::SignatureAccountMismatch
+    );
+
+    let expires_at: Timestamp = now.saturating_add(KEY_TRANSITION_PERIOD);
+    let advertised = ValidatorKeys {
+        next_eth_key: Some((next_eth_address, expires_at)),
+        ..validator.clone()
+    };
+    Ok((validator, advertised))
+}
+
+/// Advertise the next Eth key of an active validator, proven by signatures from both its current and next keys.
+/// Either key is accepted from the validator until the key is rotated, or the transition period expires.
+pub fn advertise_next_eth_key<T: Config>(
+    next_account: ChainAccount,
+    current_signature: ChainAccountSignature,
+    next_signature: ChainAccountSignature,
+) -> Result<(), Reason> {
+    let (_validator, advertised) =
+        recover_next_eth_key::<T>(next_account, current_signature, next_signature)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    Validators::insert(&advertised.substrate_id, &advertised);
+    NextValidators::mutate(&advertised.substrate_id, |keys| {
+        if let Some(keys) = keys {
+            if keys.eth_address == advertised.eth_address {
+                keys.next_eth_key = advertised.next_eth_key;
+            }
+        }
+    });
+
+    if let Some((_, expires_at)) = advertised.next_eth_key {
+        <Module<T>>::deposit_event(Event::ValidatorNextEthKeyAdvertised(
+            advertised.substrate_id,
+            next_account,
+            expires_at,
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,6 +261,7 @@ mod tests {
             let prev_keys = ValidatorKeys {
                 substrate_id: prev_substrate_id.clone(),
                 eth_address: [9; 20],
+                next_eth_key: None,
             };
 
             NextValidators::insert(prev_substrate_id, prev_keys);
@@ -57,6 +271,7 @@ mod tests {
             let val_keys = ValidatorKeys {
                 substrate_id: substrate_id.clone(),
                 eth_address: eth_address.clone(),
+                next_eth_key: None,
             };
             let val_keyses = vec![val_keys];
             let val_account = ChainAccount::Gate(substrate_id.clone().into());
@@ -155,6 +370,7 @@ mod tests {
             let vals = vec![ValidatorKeys {
                 substrate_id: substrate_id.clone(),
                 eth_address: [1; 20],
+                next_eth_key: None,
             }];
             assert_eq!(
                 change_validators::<Test>(vals.clone()),
@@ -162,4 +378,163 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_remove_validator_immediately_checks_remaining() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let validators: Vec<ValidatorKeys> = Validators::iter_values().collect();
+
+            assert_eq!(
+                remove_validator_immediately::<Test>([7; 32].into()),
+                Err(Reason::UnknownValidator)
+            );
+            assert_eq!(
+                remove_validator_immediately::<Test>(validators[0].substrate_id.clone()),
+                Err(Reason::ChangeValidatorsError)
+            );
+            assert_eq!(Validators::iter_values().count(), validators.len());
+
+            Validators::remove(&validators[1].substrate_id);
+            assert_eq!(
+                remove_validator_immediately::<Test>(validators[0].substrate_id.clone()),
+                Err(Reason::ChangeValidatorsError)
+            );
+            assert_eq!(Validators::iter_values().count(), 1);
+        });
+    }
+
+    #[test]
+    fn test_recover_key_rotation_rejects_bad_proofs() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let validators: Vec<ValidatorKeys> = Validators::iter_values().collect();
+            let (current, other) = (&validators[0], &validators[1]);
+            let signature = |eth_address| ChainAccountSignature::Eth(eth_address, [0; 65]);
+
+            assert_eq!(
+                recover_key_rotation::<Test>(
+                    ChainAccount::Eth([7; 20]),
+                    ChainAccountSignature::Gate([0; 32], [0; 65]),
+                    signature([7; 20])
+                ),
+                Err(Reason::InvalidKeyRotation)
+            );
+            assert_eq!(
+                recover_key_rotation::<Test>(
+                    ChainAccount::Eth([7; 20]),
+                    signature([6; 20]),
+                    signature([7; 20])
+                ),
+                Err(Reason::UnknownValidator)
+            );
+            assert_eq!(
+                recover_key_rotation::<Test>(
+                    ChainAccount::Eth(other.eth_address),
+                    signature(current.eth_address),
+                    signature(other.eth_address)
+                ),
+                Err(Reason::InvalidKeyRotation)
+            );
+        });
+    }
+
+    #[test]
+    fn test_recover_next_eth_key_rejects_bad_proofs() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let validators: Vec<ValidatorKeys> = Validators::iter_values().collect();
+            let (current, other) = (&validators[0], &validators[1]);
+            let signature = |eth_address| ChainAccountSignature::Eth(eth_address, [0; 65]);
+
+            assert_eq!(
+                recover_next_eth_key::<Test>(
+                    ChainAccount::Eth([7; 20]),
+                    signature([6; 20]),
+                    signature([7; 20])
+                ),
+                Err(Reason::UnknownValidator)
+            );
+            assert_eq!(
+                recover_next_eth_key::<Test>(
+                    ChainAccount::Eth(other.eth_address),
+                    signature(current.eth_address),
+                    signature(other.eth_address)
+                ),
+                Err(Reason::InvalidKeyRotation)
+            );
+
+            // Another validator's advertised next key cannot be claimed either
+            Validators::insert(
+                &other.substrate_id,
+                ValidatorKeys {
+                    next_eth_key: Some(([7; 20], 1000)),
+                    ..other.clone()
+                },
+            );
+            assert_eq!(
+                recover_next_eth_key::<Test>(
+                    ChainAccount::Eth([7; 20]),
+                    signature(current.eth_address),
+                    signature([7; 20])
+                ),
+                Err(Reason::InvalidKeyRotation)
+            );
+        });
+    }
+
+    #[test]
+    fn test_has_eth_address_during_transition() {
+        let keys = ValidatorKeys {
+            substrate_id: [1; 32].into(),
+            eth_address: [1; 20],
+            next_eth_key: Some(([2; 20], 1000)),
+        };
+        assert!(keys.has_eth_address([1; 20], None));
+        assert!(keys.has_eth_address([1; 20], Some(1000)));
+        assert!(keys.has_eth_address([2; 20], Some(999)));
+        assert!(!keys.has_eth_address([2; 20], Some(1000)));
+        assert!(!keys.has_eth_address([2; 20], None));
+        assert!(!keys.has_eth_address([3; 20], Some(0)));
+    }
+
+    #[test]
+    fn test_should_end_session_waits_for_notice_hold() {
+        use pallet_session::ShouldEndSession;
+
+        new_test_ext().execute_with(|| {
+            let substrate_id: AccountId32 = [2; 32].into();
+            let keys = ValidatorKeys {
+                substrate_id: substrate_id.clone(),
+                eth_address: [1; 20],
+                next_eth_key: None,
+            };
+            let chain_id = ChainId::Eth;
+            let notice_id = NoticeId(1, 0);
+            crate::Validators::insert(substrate_id.clone(), keys.clone());
+            NextValidators::insert(substrate_id, keys);
+            NoticeHolds::insert(chain_id, notice_id);
+
+            // the change authority notice has not been signed yet
+            NoticeStates::insert(
+                chain_id,
+                notice_id,
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Eth(vec![]),
+                },
+            );
+            assert!(!Module::<Test>::should_end_session(1));
+            assert_eq!(NoticeHolds::get(chain_id), Some(notice_id));
+
+            NoticeStates::insert(
+                chain_id,
+                notice_id,
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Eth(vec![([1; 20], [0; 65])]),
+                },
+            );
+            assert!(Module::<Test>::should_end_session(1));
+            assert_eq!(NoticeHolds::get(chain_id), None);
+        });
+    }
 }
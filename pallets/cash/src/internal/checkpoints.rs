@@ -0,0 +1,89 @@
+use crate::{
+    chains::ChainAccount,
+    params::CASH_PRINCIPAL_HISTORY,
+    reason::Reason,
+    require,
+    types::{CashPrincipal, Timestamp},
+    CashPrincipalCheckpoints, CashPrincipals,
+};
+use frame_support::storage::StorageMap;
+
+/// Remember the CASH principal an account holds, before it changes at the given time.
+/// Only the first change at each time is remembered, and those older than the history period are forgotten.
+pub fn checkpoint_cash_principal(account: ChainAccount, now: Timestamp) {
+    let mut checkpoints = CashPrincipalCheckpoints::get(account);
+    if checkpoints.last().map_or(false, |(at, _)| *at >= now) {
+        return;
+    }
+    let horizon = now.saturating_sub(CASH_PRINCIPAL_HISTORY);
+    checkpoints.retain(|(at, _)| *at >= horizon);
+    checkpoints.push((now, CashPrincipals::get(account)));
+    CashPrincipalCheckpoints::insert(account, checkpoints);
+}
+
+/// Return the CASH principal an account held as of the given time, before any changes made at or after it.
+pub fn get_cash_principal_at(
+    account: ChainAccount,
+    at: Timestamp,
+    now: Timestamp,
+) -> Result<CashPrincipal, Reason> {
+    require!(
+        at >= now.saturating_sub(CASH_PRINCIPAL_HISTORY),
+        Reason::CashPrincipalHistoryExpired
+    );
+    Ok(CashPrincipalCheckpoints::get(account)
+        .into_iter()
+        .find(|(changed_at, _)| *changed_at >= at)
+        .map_or_else(|| CashPrincipals::get(account), |(_, principal)| principal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_get_cash_principal_at() {
+        new_test_ext().execute_with(|| {
+            let account = ChainAccount::Eth([1; 20]);
+            CashPrincipals::insert(account, CashPrincipal(100));
+            assert_eq!(
+                get_cash_principal_at(account, 10, 10),
+                Ok(CashPrincipal(100))
+            );
+
+            checkpoint_cash_principal(account, 20);
+            CashPrincipals::insert(account, CashPrincipal(50));
+            // Only the principal before the first change at a time is remembered
+            checkpoint_cash_principal(account, 20);
+            CashPrincipals::insert(account, CashPrincipal(70));
+            checkpoint_cash_principal(account, 30);
+            CashPrincipals::insert(account, CashPrincipal(0));
+
+            assert_eq!(
+                get_cash_principal_at(account, 10, 40),
+                Ok(CashPrincipal(100))
+            );
+            assert_eq!(
+                get_cash_principal_at(account, 20, 40),
+                Ok(CashPrincipal(100))
+            );
+            assert_eq!(
+                get_cash_principal_at(account, 25, 40),
+                Ok(CashPrincipal(70))
+            );
+            assert_eq!(get_cash_principal_at(account, 31, 40), Ok(CashPrincipal(0)));
+
+            let later = 30 + CASH_PRINCIPAL_HISTORY + 1;
+            assert_eq!(
+                get_cash_principal_at(account, 30, later),
+                Err(Reason::CashPrincipalHistoryExpired)
+            );
+            checkpoint_cash_principal(account, later);
+            assert_eq!(
+                CashPrincipalCheckpoints::get(account),
+                vec![(later, CashPrincipal(0))]
+            );
+        });
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    chains::ChainAccount,
+    internal::slashing::get_validator_miner,
+    params::MAX_EARNINGS_SESSIONS,
+    types::{CashPrincipalAmount, SessionEarnings, SessionIndex, SubstrateId},
+    Config, MinerSessionEarnings, ValidatorEarnings, Validators,
+};
+use frame_support::storage::{IterableStorageMap, StorageMap};
+use our_std::vec::Vec;
+
+/// Count the share of interest paid to or withheld from a miner within the current session.
+pub fn track_miner_earnings(
+    miner: ChainAccount,
+    paid: CashPrincipalAmount,
+    withheld: CashPrincipalAmount,
+) {
+    if paid == CashPrincipalAmount::ZERO && withheld == CashPrincipalAmount::ZERO {
+        return;
+    }
+    MinerSessionEarnings::mutate(miner, |earnings| {
+        earnings.paid = CashPrincipalAmount(earnings.paid.0.saturating_add(paid.0));
+        earnings.withheld = CashPrincipalAmount(earnings.withheld.0.saturating_add(withheld.0));
+    });
+}
+
+/// Remember session earnings, keeping at most the latest MAX_EARNINGS_SESSIONS sessions.
+pub fn remember_earnings(history: &mut Vec<SessionEarnings>, earnings: SessionEarnings) {
+    history.push(earnings);
+    if history.len() > MAX_EARNINGS_SESSIONS {
+        history.drain(..history.len() - MAX_EARNINGS_SESSIONS);
+    }
+}
+
+/// Record the earnings of each current validator within the session ending, and start counting afresh.
+pub fn end_session<T: Config>(index: SessionIndex) {
+    for validator in Validators::iter_values() {
+        let earnings = SessionEarnings {
+            session_index: index,
+            ..MinerSessionEarnings::take(get_validator_miner(&validator))
+        };
+        ValidatorEarnings::mutate(&validator.substrate_id, |history| {
+            remember_earnings(history, earnings)
+        });
+    }
+
+    let miners: Vec<ChainAccount> = MinerSessionEarnings::iter()
+        .map(|(miner, _)| miner)
+        .collect();
+    for miner in miners {
+        MinerSessionEarnings::remove(miner);
+    }
+}
+
+/// Return the earnings of a validator in at most its last n sessions, most recent last.
+pub fn get_validator_earnings<T: Config>(
+    substrate_id: SubstrateId,
+    last_n_sessions: u32,
+) -> Vec<SessionEarnings> {
+    let mut history = ValidatorEarnings::get(substrate_id);
+    let skip = history.len().saturating_sub(last_n_sessions as usize);
+    history.drain(..skip);
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::*, types::ValidatorKeys};
+
+    #[test]
+    fn test_end_session_records_validator_earnings() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let validators: Vec<ValidatorKeys> = Validators::iter_values().collect();
+            let (miner, idle) = (&validators[0], &validators[1]);
+            let outsider = ChainAccount::Eth([9u8; 20]);
+
+            track_miner_earnings(
+                get_validator_miner(miner),
+                CashPrincipalAmount(100),
+                CashPrincipalAmount::ZERO,
+            );
+            track_miner_earnings(
+                get_validator_miner(miner),
+                CashPrincipalAmount::ZERO,
+                CashPrincipalAmount(20),
+            );
+            track_miner_earnings(outsider, CashPrincipalAmount(5), CashPrincipalAmount::ZERO);
+            end_session::<Test>(1);
+
+            track_miner_earnings(
+                get_validator_miner(miner),
+                CashPrincipalAmount(50),
+                CashPrincipalAmount::ZERO,
+            );
+            end_session::<Test>(2);
+
+            let earnings = |session_index, paid, withheld| SessionEarnings {
+                session_index,
+                paid: CashPrincipalAmount(paid),
+                withheld: CashPrincipalAmount(withheld),
+            };
+            assert_eq!(
+                get_validator_earnings::<Test>(miner.substrate_id.clone(), 10),
+                vec![earnings(1, 100, 20), earnings(2, 50, 0)]
+            );
+            assert_eq!(
+                get_validator_earnings::<Test>(miner.substrate_id.clone(), 1),
+                vec![earnings(2, 50, 0)]
+            );
+            assert_eq!(
+                get_validator_earnings::<Test>(idle.substrate_id.clone(), 10),
+                vec![earnings(1, 0, 0), earnings(2, 0, 0)]
+            );
+            assert_eq!(
+                MinerSessionEarnings::get(outsider),
+                SessionEarnings::default()
+            );
+        });
+    }
+}
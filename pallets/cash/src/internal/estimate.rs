@@ -0,0 +1,183 @@
+use crate::{
+    chains::ChainAccount,
+    internal::{exec_trx_request::exec_trx_request, pause::is_emergency_paused},
+    notices::EncodeNotice,
+    pipeline::load_portfolio,
+    portfolio::Portfolio,
+    reason::{MathError, Reason},
+    require,
+    types::{AssetBalance, CashOrChainAsset, TrxRequestEstimate},
+    AccountNotices, Config, Notices,
+};
+use frame_support::storage::{with_transaction, StorageDoubleMap, StorageMap};
+use our_std::{str, vec::Vec};
+use sp_runtime::TransactionOutcome;
+
+/// Estimate the effects of a trx request from an account, by executing it and rolling back all the changes.
+/// Signatures and nonces are not checked, but anything else which would fail the request fails the estimate.
+pub fn estimate_trx_request<T: Config>(
+    request: Vec<u8>,
+    sender: ChainAccount,
+) -> Result<TrxRequestEstimate, Reason> {
+    require!(!is_emergency_paused(), Reason::EmergencyPaused);
+    let request_str: &str = str::from_utf8(&request[..]).map_err(|_| Reason::InvalidUTF8)?;
+    let (trx_request, _clauses) = trx_request::parse_request_with_clauses(request_str)?;
+    let mut accounts = request_accounts(trx_request);
+    accounts.push(sender);
+    accounts.sort();
+    accounts.dedup();
+
+    with_transaction(|| {
+        TransactionOutcome::Rollback(estimate_trx_request_internal::<T>(
+            request_str,
+            sender,
+            &accounts,
+        ))
+    })
+}
+
+fn estimate_trx_request_internal<T: Config>(
+    request_str: &str,
+    sender: ChainAccount,
+    accounts: &[ChainAccount],
+) -> Result<TrxRequestEstimate, Reason> {
+    let mut before = Vec::with_capacity(accounts.len());
+    for &account in accounts {
+        let notice_count = AccountNotices::decode_len(account).unwrap_or(0);
+        before.push((account, load_portfolio::<T>(account)?, notice_count));
+    }
+
+    exec_trx_request::<T>(request_str, sender, None)?;
+
+    let mut estimate = TrxRequestEstimate {
+        balance_deltas: Vec::new(),
+        liquidity: load_portfolio::<T>(sender)?.get_liquidity::<T>()?.value,
+        notices: Vec::new(),
+    };
+    for (account, portfolio_before, notice_count) in before {
+        let portfolio_after = load_portfolio::<T>(account)?;
+        for (asset, delta) in balance_deltas(&portfolio_before, &portfolio_after)? {
+            estimate.balance_deltas.push((account, asset, delta));
+        }
+        for notice_id in AccountNotices::get(account).into_iter().skip(notice_count) {
+            if let Some(notice) = Notices::get(account.chain_id(), notice_id) {
+                if !estimate.notices.iter().any(|(id, _)| *id == notice_id) {
+                    estimate.notices.push((notice_id, notice.encode_notice()));
+                }
+            }
+        }
+    }
+    Ok(estimate)
+}
+
+/// The accounts named by a trx request, whose balances it may change.
+fn request_accounts(request: trx_request::TrxRequest) -> Vec<ChainAccount> {
+    use trx_request::TrxRequest::*;
+    match request {
+        Extract(_, _, account)
+        | Transfer(_, _, account)
+        | Supply(_, _, account)
+        | Repay(_, _, account)
+        | Approve(_, _, account)
+        | Liquidate(_, _, _, account)
+        | LiquidateMany(_, _, _, account) => vec![account.into()],
+        TransferMany(_, transfers) => transfers
+            .into_iter()
+            .map(|(_, account)| account.into())
+            .collect(),
+        TransferFrom(_, _, owner, recipient) => vec![owner.into(), recipient.into()],
+        Batch(operations) => operations.into_iter().flat_map(request_accounts).collect(),
+        Cancel(_) | SetNonceWindow(_) | ClaimRewards | Bond(_) | Unbond(_) => vec![],
+    }
+}
+
+/// The change in the balance of CASH and of each asset between two portfolios of an account.
+fn balance_deltas(
+    before: &Portfolio,
+    after: &Portfolio,
+) -> Result<Vec<(CashOrChainAsset, AssetBalance)>, Reason> {
+    let balance_of = |portfolio: &Portfolio, asset: CashOrChainAsset| match asset {
+        CashOrChainAsset::Cash => portfolio.cash.value,
+        CashOrChainAsset::ChainAsset(chain_asset) => portfolio
+            .positions
+            .iter()
+            .find(|(info, _)| info.asset == chain_asset)
+            .map_or(0, |(_, balance)| balance.value),
+    };
+
+    let mut assets = vec![CashOrChainAsset::Cash];
+    for (info, _) in before.positions.iter().chain(after.positions.iter()) {
+        let asset = CashOrChainAsset::ChainAsset(info.asset);
+        if !assets.contains(&asset) {
+            assets.push(asset);
+        }
+    }
+
+    let mut deltas = Vec::new();
+    for asset in assets {
+        let delta = balance_of(after, asset)
+            .checked_sub(balance_of(before, asset))
+            .ok_or(MathError::Overflow)?;
+        if delta != 0 {
+            deltas.push((asset, delta));
+        }
+    }
+    Ok(deltas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::{common::*, *},
+        types::*,
+        Miner,
+    };
+
+    #[test]
+    fn test_estimate_trx_request() {
+        new_test_ext().execute_with(|| {
+            let req_str = "(Transfer 10000000 CASH Eth:0x0101010101010101010101010101010101010101)";
+            let account = ChainAccount::Eth([20; 20]);
+            let to_account = ChainAccount::Eth([1; 20]);
+            init_cash(account, CashPrincipal::from_nominal("100"));
+            Miner::put(ChainAccount::Eth([3; 20]));
+
+            let estimate = estimate_trx_request::<Test>(req_str.into(), account).unwrap();
+            assert_eq!(
+                estimate.balance_deltas,
+                vec![
+                    (
+                        to_account,
+                        CashOrChainAsset::Cash,
+                        Balance::from_nominal("10", CASH).value
+                    ),
+                    (
+                        account,
+                        CashOrChainAsset::Cash,
+                        Balance::from_nominal("-10.01", CASH).value
+                    ),
+                ]
+            );
+            assert_eq!(
+                estimate.liquidity,
+                Balance::from_nominal("89.99", USD).value
+            );
+            assert_eq!(estimate.notices, vec![]);
+
+            // nothing is committed
+            assert_eq!(CashPrincipals::get(to_account), CashPrincipal::ZERO);
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("100")
+            );
+
+            let req_str =
+                "(Transfer 200000000 CASH Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(
+                estimate_trx_request::<Test>(req_str.into(), account),
+                Err(Reason::InsufficientLiquidity)
+            );
+        });
+    }
+}
@@ -6,24 +6,42 @@ use crate::{
     },
     core::{
         self, get_current_validator, get_event_queue, get_first_block, get_last_block,
-        get_starport, get_validator_set, recover_validator, validator_sign,
+        get_recent_timestamp, get_signer_weights, get_starport, get_validator_set,
+        recover_validator, validator_sign,
     },
     debug, error,
-    events::{fetch_chain_block, fetch_chain_block_by_hash, fetch_chain_blocks},
-    internal::assets::{get_cash_quantity, get_quantity, get_value},
+    events::{
+        fetch_chain_block, fetch_chain_block_by_hash, fetch_chain_blocks, fetch_chain_checkpoint,
+    },
+    internal::{
+        assets::{get_cash_quantity, get_quantity, get_value},
+        failed_events,
+        ingestion::is_ingestion_paused,
+        liveness::track_chain_blocks,
+        parameters::get_ingress_slack,
+        slashing::report_equivocation,
+    },
     log,
-    params::{INGRESS_LARGE, INGRESS_QUOTA, INGRESS_SLACK, MAX_EVENT_BLOCKS, MIN_EVENT_BLOCKS},
+    params::{
+        ANCESTOR_HASH_WINDOW, INGRESS_LARGE, INGRESS_QUOTA, MAX_CHAIN_BLOCK_AHEAD,
+        MAX_EVENT_BLOCKS, MIN_EVENT_BLOCKS, PENDING_TALLY_EXPIRY,
+    },
     reason::{MathError, Reason},
     require,
-    types::{CashPrincipalAmount, Quantity, USDQuantity, USD},
-    Call, Config, Event as EventT, IngressionQueue, LastProcessedBlock, Module, PendingChainBlocks,
-    PendingChainReorgs,
+    types::{CashPrincipalAmount, Quantity, Timestamp, USDQuantity, USD},
+    AncestorHashes, Call, CheckpointContracts, Config, Event as EventT, FailedEvents, FirstBlock,
+    IngressionQueue, LastBlockTimestamp, LastProcessedBlock, Module, PendingChainBlocks,
+    PendingChainReorgs, PendingChainTalliesSince,
 };
 use codec::Encode;
 use ethereum_client::EthereumEvent;
-use frame_support::storage::StorageMap;
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
 use frame_system::offchain::SubmitTransaction;
-use our_std::{cmp::max, convert::TryInto};
+use our_std::{
+    cmp::{max, min},
+    convert::TryInto,
+    log::targets::EVENTS,
+};
 use sp_core::offchain::Duration;
 use sp_runtime::offchain::{
     storage::StorageValueRef,
@@ -46,7 +64,7 @@ impl<I: Iterator> CollectRev for I {}
 /// Determine the number of blocks which can still fit on an ingression queue.
 pub fn queue_slack(event_queue: &ChainBlockEvents) -> u32 {
     let queue_len: u32 = event_queue.len().try_into().unwrap_or(u32::MAX);
-    max(INGRESS_SLACK.saturating_sub(queue_len), 1)
+    max(get_ingress_slack().saturating_sub(queue_len), 1)
 }
 
 /// Determine the risk-adjusted value of a particular event, given the current block number.
@@ -83,7 +101,7 @@ pub fn risk_adjusted_value<T: Config>(
             EthereumEvent::Lock { asset, amount, .. } => {
                 let quantity = get_quantity::<T>(ChainAsset::Matic(*asset), *amount)?;
                 let usd_quantity = get_value::<T>(quantity)?;
-                debug!("matic lock detected usd_quantity={:?}", usd_quantity);
+                debug!(target: EVENTS, "matic lock detected usd_quantity={:?}", usd_quantity);
                 Ok(usd_quantity.decay(elapsed_blocks)?)
             }
 
@@ -134,6 +152,10 @@ pub fn track_chain_events<T: Config>() -> Result<(), Reason> {
 
 /// Perform the next step of tracking events from an underlying chain.
 pub fn track_chain_events_on<T: Config>(chain_id: ChainId) -> Result<(), Reason> {
+    if is_ingestion_paused(chain_id) {
+        debug!(target: EVENTS, "Worker skipping paused chain"; chain_id = chain_id);
+        return Ok(());
+    }
     let starport = get_starport::<T>(chain_id)?;
     let me = get_current_validator::<T>()?;
     let last_block = get_last_block::<T>(chain_id)?;
@@ -141,36 +163,68 @@ pub fn track_chain_events_on<T: Config>(chain_id: ChainId) -> Result<(), Reason>
         .number()
         .checked_add(1)
         .ok_or(MathError::Overflow)?;
+    let checkpoint = match CheckpointContracts::get(chain_id) {
+        Some(contract) => Some(fetch_chain_checkpoint(chain_id, contract)?),
+        None => None,
+    };
+    if let Some(checkpoint_number) = checkpoint {
+        if next_block_number > checkpoint_number {
+            debug!(
+                target: EVENTS,
+                "Worker waiting for checkpoint";
+                chain_id = chain_id,
+                next = next_block_number,
+                checkpoint = checkpoint_number
+            );
+            return Ok(());
+        }
+    }
     let next_block = fetch_chain_block(chain_id, next_block_number, starport)?;
     if last_block.hash() == next_block.parent_hash() {
         debug!(
-            "Worker sees the same fork: next={:?} last={:?}",
-            next_block, last_block
+            target: EVENTS,
+            "Worker sees the same fork";
+            chain_id = chain_id,
+            next = next_block,
+            last = last_block
         );
         let pending_blocks = PendingChainBlocks::get(chain_id);
         let event_queue = get_event_queue::<T>(chain_id)?;
         let slack = queue_slack(&event_queue) as u64;
+        let fetch_from = next_block_number
+            .checked_add(1)
+            .ok_or(MathError::Overflow)?;
+        let fetch_to = fetch_from.checked_add(slack).ok_or(MathError::Overflow)?;
+        // Note: only blocks which are already checkpointed are considered final
+        let fetch_to = match checkpoint {
+            Some(checkpoint_number) => min(fetch_to, checkpoint_number.saturating_add(1)),
+            None => fetch_to,
+        };
         let blocks = next_block
             .concat(fetch_chain_blocks(
-                chain_id,
-                next_block_number
-                    .checked_add(1)
-                    .ok_or(MathError::Overflow)?,
-                next_block_number
-                    .checked_add(1)
-                    .ok_or(MathError::Overflow)?
-                    .checked_add(slack)
-                    .ok_or(MathError::Overflow)?,
-                starport,
+                chain_id, fetch_from, fetch_to, starport,
             )?)?
             .filter_already_supported(&me.substrate_id, pending_blocks);
         memorize_chain_blocks::<T>(&blocks)?;
         submit_chain_blocks::<T>(&blocks)
     } else {
         debug!(
-            "Worker sees a different fork: next={:?} last={:?}",
-            next_block, last_block
+            target: EVENTS,
+            "Worker sees a different fork";
+            chain_id = chain_id,
+            next = next_block,
+            last = last_block
         );
+        if checkpoint.is_some() {
+            error!(
+                target: EVENTS,
+                "Worker sees a different fork for checkpointed blocks";
+                chain_id = chain_id,
+                next = next_block,
+                last = last_block
+            );
+            return Err(Reason::ReorgBeyondCheckpoint);
+        }
         let true_block = fetch_chain_block(chain_id, last_block.number(), starport)?;
         let pending_reorgs = PendingChainReorgs::get(chain_id);
         let reorg = formulate_reorg::<T>(chain_id, &last_block, &true_block)?;
@@ -178,7 +232,7 @@ pub fn track_chain_events_on<T: Config>(chain_id: ChainId) -> Result<(), Reason>
             memorize_chain_blocks::<T>(&reorg.forward_blocks())?;
             submit_chain_reorg::<T>(&reorg)
         } else {
-            debug!("Worker already submitted... waiting");
+            debug!(target: EVENTS, "Worker already submitted... waiting");
             // just wait for the reorg to succeed or fail,
             //  or we change our minds in another pass (noop)
             Ok(())
@@ -208,8 +262,12 @@ pub fn ingress_queue<T: Config>(
             match risk_result {
                 Ok(value) => {
                     debug!(
-                        "Computed risk adjusted value ({:?} / {:?} @ {}) of {:?}",
-                        value, available, block_num, event
+                        target: EVENTS,
+                        "Computed risk adjusted value";
+                        value = value,
+                        available = available,
+                        block_number = block_num,
+                        event = event
                     );
                     if value <= available {
                         available = available.sub(value).unwrap();
@@ -224,6 +282,7 @@ pub fn ingress_queue<T: Config>(
                                 <Module<T>>::deposit_event(
                                     EventT::FailedProcessingChainBlockEvent(event.clone(), reason),
                                 );
+                                failed_events::queue_failed_event::<T>(event.clone());
                             }
                         }
                         return false; // remove from queue
@@ -234,6 +293,7 @@ pub fn ingress_queue<T: Config>(
 
                 Err(err) => {
                     error!(
+                        target: EVENTS,
                         "Could not compute risk adjusted value ({}) of {:?}",
                         err, event
                     );
@@ -248,8 +308,10 @@ pub fn ingress_queue<T: Config>(
             }
         } else {
             debug!(
-                "Event not old enough to process (@ {:?}) {:?}",
-                block_num, event
+                target: EVENTS,
+                "Event not old enough to process";
+                block_number = block_num,
+                event = event
             );
             return true; // retain on queue
         }
@@ -260,11 +322,11 @@ pub fn ingress_queue<T: Config>(
 /// Submit the underlying chain blocks the worker calculates are needed by the chain next.
 pub fn submit_chain_blocks<T: Config>(blocks: &ChainBlocks) -> Result<(), Reason> {
     if blocks.len() > 0 {
-        log!("Submitting chain blocks extrinsic: {:?}", blocks);
+        log!(target: EVENTS, "Submitting chain blocks extrinsic"; blocks = blocks);
         let signature = validator_sign::<T>(&blocks.encode()[..])?;
         let call = Call::receive_chain_blocks(blocks.clone(), signature);
         if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
-            log!("Error while submitting chain blocks: {:?}", e);
+            log!(target: EVENTS, "Error while submitting chain blocks: {:?}", e);
             return Err(Reason::FailedToSubmitExtrinsic);
         }
     }
@@ -369,11 +431,11 @@ pub fn formulate_reorg<T: Config>(
 
 /// Submit a reorg message from a worker to the chain.
 pub fn submit_chain_reorg<T: Config>(reorg: &ChainReorg) -> Result<(), Reason> {
-    log!("Submitting chain reorg extrinsic: {:?}", reorg);
+    log!(target: EVENTS, "Submitting chain reorg extrinsic"; reorg = reorg);
     let signature = validator_sign::<T>(&reorg.encode()[..])?;
     let call = Call::receive_chain_reorg(reorg.clone(), signature);
     if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
-        log!("Error while submitting chain blocks: {:?}", e);
+        log!(target: EVENTS, "Error while submitting chain blocks: {:?}", e);
         return Err(Reason::FailedToSubmitExtrinsic);
     }
     Ok(())
@@ -385,14 +447,22 @@ pub fn receive_chain_blocks<T: Config>(
     signature: ChainSignature,
 ) -> Result<(), Reason> {
     let validator_set = get_validator_set::<T>()?;
+    let signer_weights = get_signer_weights::<T>();
     let validator = recover_validator::<T>(&blocks.encode(), signature)?;
     let chain_id = blocks.chain_id();
+    require!(!is_ingestion_paused(chain_id), Reason::ChainIngestionPaused);
     let mut event_queue = get_event_queue::<T>(chain_id)?;
     let mut last_block = get_last_block::<T>(chain_id)?;
     let mut pending_blocks = PendingChainBlocks::get(chain_id);
+    let mut ancestors = get_ancestor_hashes(chain_id, &last_block);
+    let mut progressed = false;
+    let mut attested: u32 = 0;
+    let mut accepted: u32 = 0;
+    let mut equivocations = vec![];
+    let now = get_recent_timestamp::<T>()?;
 
-    debug!("Pending blocks: {:?}", pending_blocks);
-    debug!("Event queue: {:?}", event_queue);
+    debug!(target: EVENTS, "Pending blocks: {:?}", pending_blocks);
+    debug!(target: EVENTS, "Event queue: {:?}", event_queue);
 
     for block in blocks.blocks() {
         if block.number() >= last_block.number() + 1 {
@@ -400,17 +470,28 @@ pub fn receive_chain_blocks<T: Config>(
             if let Some(prior) = pending_blocks.get_mut(offset) {
                 if block != prior.block {
                     debug!(
+                        target: EVENTS,
                         "Received conflicting block, dissenting: {:?} ({:?})",
                         block, prior
                     );
+                    if prior.has_supporter(&validator.substrate_id) {
+                        // validator already signed the block it now dissents from
+                        equivocations.push(block.number());
+                    }
                     prior.add_dissent(&validator);
                 } else {
-                    debug!("Received support for existing block: {:?}", block);
+                    debug!(target: EVENTS, "Received support for existing block: {:?}", block);
+                    if prior.dissent.contains(&validator.substrate_id) {
+                        // validator already signed a block conflicting with the one it now supports
+                        equivocations.push(block.number());
+                    }
                     prior.add_support(&validator);
+                    attested += 1;
                 }
             } else if offset == 0 {
                 if block.parent_hash() != last_block.hash() {
                     debug!(
+                        target: EVENTS,
                         "Received block which would require fork: {:?} ({:?})",
                         block, last_block
                     );
@@ -419,15 +500,24 @@ pub fn receive_chain_blocks<T: Config>(
                     // this *would* be a dissenting vote if prior existed
                     //  but that's ok bc worker will try to reorg instead
                     continue;
+                } else if !is_plausible_block_time(block.timestamp(), last_block.timestamp(), now) {
+                    debug!(
+                        target: EVENTS,
+                        "Received block with implausible timestamp: {:?} ({:?} @ {})",
+                        block, last_block, now
+                    );
+                    continue;
                 } else {
-                    debug!("Received valid first next pending block: {:?}", block);
+                    debug!(target: EVENTS, "Received valid first next pending block: {:?}", block);
                     // write to pending_blocks[offset]
                     //  we already checked offset doesn't exist, this is the first element
                     pending_blocks.push(ChainBlockTally::new(block, &validator));
+                    attested += 1;
                 }
             } else if let Some(parent) = pending_blocks.get(offset - 1) {
                 if block.parent_hash() != parent.block.hash() {
                     debug!(
+                        target: EVENTS,
                         "Received invalid derivative block: {:?} ({:?})",
                         block, parent
                     );
@@ -437,14 +527,23 @@ pub fn receive_chain_blocks<T: Config>(
                     // this *would* be a dissenting vote if prior existed
                     //  but that's ok bc worker should submit parent first
                     continue;
+                } else if !is_plausible_block_time(block.timestamp(), parent.block.timestamp(), now)
+                {
+                    debug!(
+                        target: EVENTS,
+                        "Received derivative block with implausible timestamp: {:?} ({:?} @ {})",
+                        block, parent, now
+                    );
+                    continue;
                 } else {
-                    debug!("Received valid pending block: {:?}", block);
+                    debug!(target: EVENTS, "Received valid pending block: {:?}", block);
                     // write to pending_blocks[offset]
                     //  we already checked offset doesn't exist, but offset - 1 does
                     pending_blocks.push(ChainBlockTally::new(block, &validator));
+                    attested += 1;
                 }
             } else {
-                debug!("Received disconnected block: {:?} ({:?})", block, offset);
+                debug!(target: EVENTS, "Received disconnected block: {:?} ({:?})", block, offset);
                 // we don't have the block, nor a parent for it
                 //  the worker shouldn't submit stuff like this
                 // blocks should be in order in which case this wouldn't happen
@@ -453,23 +552,29 @@ pub fn receive_chain_blocks<T: Config>(
             }
         } else {
             debug!(
+                target: EVENTS,
                 "Received irrelevant past block: {:?} ({:?})",
                 block, last_block
             );
+            // still counts as participation, the validator may just be behind its peers
+            attested += 1;
             continue;
         }
     }
 
     for tally in pending_blocks.clone().iter() {
-        if tally.has_enough_support(&validator_set) {
+        if tally.has_enough_support(&validator_set, &signer_weights) {
             // remove tally from block queue
             //  add events to event queue, advance the block, and process a round of events
             pending_blocks.remove(0); // note: tally is first on queue
             event_queue.push(&tally.block);
             last_block = tally.block.clone();
+            remember_ancestor(&mut ancestors, &last_block);
             ingress_queue::<T>(&last_block, &mut event_queue)?;
+            progressed = true;
+            accepted += 1;
             continue;
-        } else if tally.has_enough_dissent(&validator_set) {
+        } else if tally.has_enough_dissent(&validator_set, &signer_weights) {
             // remove tally and everything after from queue
             pending_blocks = vec![];
             break;
@@ -481,10 +586,181 @@ pub fn receive_chain_blocks<T: Config>(
     LastProcessedBlock::insert(chain_id, last_block);
     PendingChainBlocks::insert(chain_id, pending_blocks);
     IngressionQueue::insert(chain_id, event_queue);
+    AncestorHashes::insert(chain_id, ancestors);
+    track_pending_tallies(chain_id, progressed);
+    track_chain_blocks(&validator.substrate_id, attested, accepted);
+
+    for block_number in equivocations {
+        report_equivocation::<T>(&validator, chain_id, block_number, now);
+    }
 
     Ok(())
 }
 
+/// Determine whether a block timestamp is plausible, given its parent's and the current Gateway time.
+/// There is no bound on how far behind Gateway time a block may be, so ingestion can catch up after a stall.
+pub fn is_plausible_block_time(
+    block_time: Timestamp,
+    parent_time: Timestamp,
+    now: Timestamp,
+) -> bool {
+    block_time >= parent_time && block_time <= now.saturating_add(MAX_CHAIN_BLOCK_AHEAD)
+}
+
+/// Track since when the pending tallies for a chain have been waiting, restarting the clock on progress.
+fn track_pending_tallies(chain_id: ChainId, progressed: bool) {
+    let has_pending = !PendingChainBlocks::get(chain_id).is_empty()
+        || !PendingChainReorgs::get(chain_id).is_empty();
+    if !has_pending {
+        PendingChainTalliesSince::remove(chain_id);
+    } else if progressed || !PendingChainTalliesSince::contains_key(chain_id) {
+        PendingChainTalliesSince::insert(chain_id, LastBlockTimestamp::get());
+    }
+}
+
+/// Drop pending tallies which are superseded by a processed block, or have waited too long for quorum.
+/// A chain whose tallies can't be checked is logged and skipped, so it never holds up block initialization.
+pub fn expire_chain_tallies<T: Config>(now: Timestamp) {
+    let waiting: Vec<(ChainId, Timestamp)> = PendingChainTalliesSince::iter().collect();
+    for (chain_id, since) in waiting {
+        let last_block = match get_last_block::<T>(chain_id) {
+            Ok(last_block) => last_block,
+            Err(err) => {
+                error!(
+                    target: EVENTS,
+                    "Could not expire chain tallies";
+                    chain_id = chain_id,
+                    error = err
+                );
+                continue;
+            }
+        };
+        let mut pending_blocks = PendingChainBlocks::get(chain_id);
+        let mut pending_reorgs = PendingChainReorgs::get(chain_id);
+        let (blocks_before, reorgs_before) = (pending_blocks.len(), pending_reorgs.len());
+
+        // reorgs can only apply on top of the last processed block
+        pending_reorgs.retain(|tally| tally.reorg.from_hash() == last_block.hash());
+
+        if now.saturating_sub(since) > PENDING_TALLY_EXPIRY {
+            pending_blocks.clear();
+            pending_reorgs.clear();
+        }
+
+        let expired_blocks = blocks_before - pending_blocks.len();
+        let expired_reorgs = reorgs_before - pending_reorgs.len();
+        if expired_blocks > 0 || expired_reorgs > 0 {
+            log!(
+                target: EVENTS,
+                "Expiring chain tallies for {:?}: blocks={} reorgs={}",
+                chain_id,
+                expired_blocks,
+                expired_reorgs
+            );
+            PendingChainBlocks::insert(chain_id, pending_blocks);
+            PendingChainReorgs::insert(chain_id, pending_reorgs);
+            track_pending_tallies(chain_id, false);
+            <Module<T>>::deposit_event(EventT::ExpiredChainTally(
+                chain_id,
+                expired_blocks.try_into().unwrap_or(u32::MAX),
+                expired_reorgs.try_into().unwrap_or(u32::MAX),
+            ));
+        }
+    }
+}
+
+/// Get the window of known ancestor hashes for a chain, which always ends with the last processed block.
+pub fn get_ancestor_hashes(chain_id: ChainId, last_block: &ChainBlock) -> Vec<ChainHash> {
+    let ancestors = AncestorHashes::get(chain_id);
+    if ancestors.last() == Some(&last_block.hash()) {
+        ancestors
+    } else {
+        // Note: history is not known before the last block, e.g. after genesis or an upgrade
+        vec![last_block.hash()]
+    }
+}
+
+/// Remember a newly processed block as the latest ancestor, forgetting the oldest as needed.
+pub fn remember_ancestor(ancestors: &mut Vec<ChainHash>, block: &ChainBlock) {
+    ancestors.push(block.hash());
+    if ancestors.len() > ANCESTOR_HASH_WINDOW {
+        ancestors.drain(..ancestors.len() - ANCESTOR_HASH_WINDOW);
+    }
+}
+
+/// Whether the window of ancestor hashes holds all the history a reorg may fork from.
+/// After an upgrade the window starts at the block processed last, and takes ANCESTOR_HASH_WINDOW blocks to fill.
+pub fn is_ancestry_complete(chain_id: ChainId, ancestors: &Vec<ChainHash>) -> bool {
+    ancestors.len() >= ANCESTOR_HASH_WINDOW
+        || FirstBlock::get(chain_id).map(|block| block.hash()).as_ref() == ancestors.first()
+}
+
+/// Check that a reorg connects to known history, returning the position of the common ancestor.
+/// Exactly the history after the common ancestor must be reverted, newest first.
+/// While the history is incomplete, a reorg may fork from before it, in which case all of it must be reverted,
+///  followed by the older blocks back to the common ancestor, and no position is returned.
+pub fn validate_reorg_ancestry(
+    reorg: &ChainReorg,
+    ancestors: &Vec<ChainHash>,
+    ancestry_complete: bool,
+) -> Result<Option<usize>, Reason> {
+    let forward_blocks = reorg.forward_blocks().blocks();
+    let reverse_blocks = reorg.reverse_blocks().blocks();
+    let first_forward = forward_blocks.first().ok_or(Reason::InvalidChainReorg)?;
+    let fork_position = ancestors
+        .iter()
+        .position(|hash| *hash == first_forward.parent_hash());
+    require!(
+        fork_position.is_some() || !ancestry_complete,
+        Reason::InvalidChainReorg
+    );
+
+    // every block being reverted must be known history after the common ancestor, and none may be left out
+    let reverse_hashes: Vec<ChainHash> = reverse_blocks.iter().map(|block| block.hash()).collect();
+    let reverted = &ancestors[fork_position.map_or(0, |position| position + 1)..];
+    match fork_position {
+        Some(_) => require!(
+            reverse_hashes.iter().eq(reverted.iter().rev()),
+            Reason::InvalidChainReorg
+        ),
+        None => {
+            require!(
+                reverse_hashes.len() >= reverted.len()
+                    && reverse_hashes
+                        .iter()
+                        .zip(reverted.iter().rev())
+                        .all(|(a, b)| a == b),
+                Reason::InvalidChainReorg
+            );
+            for (block, parent) in reverse_blocks.iter().zip(reverse_blocks.iter().skip(1)) {
+                require!(
+                    block.parent_hash() == parent.hash(),
+                    Reason::InvalidChainReorg
+                );
+            }
+            require!(
+                reverse_blocks.last().map(|block| block.parent_hash())
+                    == Some(first_forward.parent_hash()),
+                Reason::InvalidChainReorg
+            );
+        }
+    }
+
+    // every block being applied must build on the one before it, ending at the new head
+    for (parent, block) in forward_blocks.iter().zip(forward_blocks.iter().skip(1)) {
+        require!(
+            block.parent_hash() == parent.hash(),
+            Reason::InvalidChainReorg
+        );
+    }
+    require!(
+        forward_blocks.last().map(|block| block.hash()) == Some(reorg.to_hash()),
+        Reason::InvalidChainReorg
+    );
+
+    Ok(fork_position)
+}
+
 /// Receive a reorg message from a worker, tallying it and applying as necessary.
 pub fn receive_chain_reorg<T: Config>(
     reorg: ChainReorg,
@@ -493,12 +769,31 @@ pub fn receive_chain_reorg<T: Config>(
     let validator_set = get_validator_set::<T>()?;
     let validator = recover_validator::<T>(&reorg.encode(), signature)?;
     let chain_id = reorg.chain_id();
+    require!(!is_ingestion_paused(chain_id), Reason::ChainIngestionPaused);
     let mut event_queue = get_event_queue::<T>(chain_id)?;
     let mut last_block = get_last_block::<T>(chain_id)?;
     let mut pending_reorgs = PendingChainReorgs::get(chain_id);
+    let mut ancestors = get_ancestor_hashes(chain_id, &last_block);
 
     // Note: can reject / stop propagating once this check fails
     require!(reorg.from_hash() == last_block.hash(), Reason::HashMismatch);
+    require!(
+        !CheckpointContracts::contains_key(chain_id),
+        Reason::ReorgBeyondCheckpoint
+    );
+    let ancestry_complete = is_ancestry_complete(chain_id, &ancestors);
+    let fork_position = validate_reorg_ancestry(&reorg, &ancestors, ancestry_complete)?;
+
+    // Note: the common ancestor is only known by hash, so its time cannot be checked
+    let now = get_recent_timestamp::<T>()?;
+    let mut parent_time = 0;
+    for block in reorg.forward_blocks().blocks() {
+        require!(
+            is_plausible_block_time(block.timestamp(), parent_time, now),
+            Reason::ImplausibleBlockTime
+        );
+        parent_time = block.timestamp();
+    }
 
     let tally = if let Some(prior) = pending_reorgs.iter_mut().find(|r| r.reorg == reorg) {
         prior.add_support(&validator);
@@ -510,27 +805,36 @@ pub fn receive_chain_reorg<T: Config>(
 
     // Note: whenever there's a race to be the last signer, this will be suboptimal
     //  we don't currently keep a tombstone marking that the reorg was recently processed
-    if tally.has_enough_support(&validator_set) {
+    if tally.has_enough_support(&validator_set, &get_signer_weights::<T>()) {
         // if we have enough support, perform actual reorg
         // for each block going backwards
-        //  remove events from queue, or unapply them if already applied
+        //  remove events from queue or failed events, or unapply them if already applied
+        let mut failed_events = FailedEvents::get(chain_id);
+        let mut reverted_failed_events = vec![];
         for block in tally.reorg.reverse_blocks().blocks() {
             for event in block.events() {
                 // Note: this could be made significantly more efficient
                 //  at the cost of significant complexity
                 if let Some(pos) = event_queue.position(&event) {
                     event_queue.remove(pos);
+                } else if let Some(pos) = failed_events.iter().position(|(_, e)| *e == event) {
+                    // never applied, so there is nothing to unapply, only to stop retrying
+                    reverted_failed_events.push(failed_events.remove(pos));
                 } else {
                     core::unapply_chain_event_internal::<T>(&event)?
                 }
             }
         }
 
+        // forget the history being reverted, back to the common ancestor, or all of it if that's unknown
+        ancestors.truncate(fork_position.map_or(0, |position| position + 1));
+
         // for each block going forwards
         //  add events to event queue, advance the block, and process a round of events
         for block in tally.reorg.forward_blocks().blocks() {
             event_queue.push(&block);
             last_block = block.clone();
+            remember_ancestor(&mut ancestors, &last_block);
             ingress_queue::<T>(&last_block, &mut event_queue)?;
         }
 
@@ -539,9 +843,20 @@ pub fn receive_chain_reorg<T: Config>(
         PendingChainBlocks::insert(chain_id, Vec::<ChainBlockTally>::new());
         PendingChainReorgs::insert(chain_id, Vec::<ChainReorgTally>::new());
         IngressionQueue::insert(chain_id, event_queue);
+        AncestorHashes::insert(chain_id, ancestors);
+        if failed_events.is_empty() {
+            FailedEvents::remove(chain_id);
+        } else {
+            FailedEvents::insert(chain_id, failed_events);
+        }
+        for (event_id, event) in reverted_failed_events {
+            <Module<T>>::deposit_event(EventT::DroppedFailedEvent(chain_id, event_id, event));
+        }
+        track_pending_tallies(chain_id, true);
     } else {
         // otherwise just update the stored reorg tallies
         PendingChainReorgs::insert(chain_id, pending_reorgs);
+        track_pending_tallies(chain_id, false);
     }
 
     Ok(())
@@ -565,6 +880,7 @@ mod tests {
                 hash,
                 parent_hash,
                 number: i,
+                timestamp: 0,
                 events: vec![],
             });
         }
@@ -583,6 +899,7 @@ mod tests {
             hash: [10u8; 32],
             parent_hash: true_block.hash,
             number: 10,
+            timestamp: 0,
             events: vec![],
         }];
         fetched_blocks.extend(new_chain[0..9].iter().rev().cloned().collect::<Vec<_>>());
@@ -590,6 +907,7 @@ mod tests {
             hash: [10u8; 32],
             parent_hash: last_block.hash,
             number: 10,
+            timestamp: 0,
             events: vec![],
         });
         fetched_blocks.extend(old_chain[1..10].iter().rev().cloned());
@@ -769,6 +1087,7 @@ mod tests {
                 chain: String::from("ETH"),
                 recipient: [4; 32],
                 amount: qty!("10", ETH).value,
+                reference: None,
             };
 
             let real_event = EthereumEvent::Lock {
@@ -776,6 +1095,7 @@ mod tests {
                 chain: String::from("ETH"),
                 recipient: [5; 32],
                 amount: qty!("9", ETH).value,
+                reference: None,
                 asset: [238; 20],
             };
 
@@ -783,6 +1103,7 @@ mod tests {
                 hash: reorg_block_hash,
                 parent_hash: premined_block().hash,
                 number: 2,
+                timestamp: 0,
                 events: vec![reorg_event.clone()],
             };
 
@@ -790,37 +1111,47 @@ mod tests {
                 hash: real_block_hash,
                 parent_hash: premined_block().hash,
                 number: 2,
+                timestamp: 0,
                 events: vec![real_event.clone()],
             };
 
             let latest_hash = [10; 32];
 
             // mine dummy blocks to get past limit
-            let blocks_3 = ChainBlocks::Eth(vec![
+            let dummy_blocks = vec![
                 ethereum_client::EthereumBlock {
-                    hash: [3; 32],
+                    hash: [6; 32],
                     parent_hash: reorg_block_hash,
                     number: 3,
+                    timestamp: 0,
                     events: vec![],
                 },
                 ethereum_client::EthereumBlock {
                     hash: [4; 32],
-                    parent_hash: [3; 32],
+                    parent_hash: [6; 32],
                     number: 4,
+                    timestamp: 0,
                     events: vec![],
                 },
                 ethereum_client::EthereumBlock {
                     hash: latest_hash,
                     parent_hash: [4; 32],
                     number: 5,
+                    timestamp: 0,
                     events: vec![],
                 },
-            ]);
+            ];
+            let blocks_3 = ChainBlocks::Eth(dummy_blocks.clone());
 
             let reorg = ChainReorg::Eth {
                 from_hash: latest_hash,
                 to_hash: real_block_hash,
-                reverse_blocks: vec![reorg_block.clone()],
+                reverse_blocks: vec![
+                    dummy_blocks[2].clone(),
+                    dummy_blocks[1].clone(),
+                    dummy_blocks[0].clone(),
+                    reorg_block.clone(),
+                ],
                 forward_blocks: vec![real_block.clone()],
             };
 
@@ -869,18 +1200,21 @@ mod tests {
                     hash: [3; 32],
                     parent_hash: real_block_hash,
                     number: 3,
+                    timestamp: 0,
                     events: vec![],
                 },
                 ethereum_client::EthereumBlock {
                     hash: [4; 32],
                     parent_hash: [3; 32],
                     number: 4,
+                    timestamp: 0,
                     events: vec![],
                 },
                 ethereum_client::EthereumBlock {
                     hash: [5; 32],
                     parent_hash: [4; 32],
                     number: 5,
+                    timestamp: 0,
                     events: vec![],
                 },
             ]);
@@ -900,6 +1234,521 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_receive_chain_reorg_drops_failed_events() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            pallet_oracle::Prices::insert(
+                ETH.ticker,
+                Price::from_nominal(ETH.ticker, "2000.00").value,
+            );
+
+            // locks to an unknown chain, so it fails to apply and cannot be unapplied either
+            let failing_event = EthereumEvent::Lock {
+                asset: [238; 20],
+                sender: [3; 20],
+                chain: String::from("XXX"),
+                recipient: [4; 32],
+                amount: qty!("10", ETH).value,
+                reference: None,
+            };
+            let block =
+                |number: u64, hash: u8, parent_hash: [u8; 32], events: Vec<EthereumEvent>| {
+                    ethereum_client::EthereumBlock {
+                        hash: [hash; 32],
+                        parent_hash,
+                        number,
+                        timestamp: 0,
+                        events,
+                    }
+                };
+            let failing_block = block(2, 2, premined_block().hash, vec![failing_event.clone()]);
+            let blocks = vec![
+                block(3, 3, [2; 32], vec![]),
+                block(4, 4, [3; 32], vec![]),
+                block(5, 5, [4; 32], vec![]),
+            ];
+            let real_block = block(2, 9, premined_block().hash, vec![]);
+
+            assert_ok!(all_receive_chain_blocks(&ChainBlocks::Eth(vec![
+                failing_block.clone()
+            ])));
+            assert_ok!(all_receive_chain_blocks(&ChainBlocks::Eth(blocks.clone())));
+            let failed_event = ChainBlockEvent::Eth(2, failing_event);
+            assert_eq!(
+                FailedEvents::get(ChainId::Eth),
+                vec![(0, failed_event.clone())]
+            );
+
+            let reorg = ChainReorg::Eth {
+                from_hash: [5; 32],
+                to_hash: real_block.hash,
+                reverse_blocks: vec![
+                    blocks[2].clone(),
+                    blocks[1].clone(),
+                    blocks[0].clone(),
+                    failing_block,
+                ],
+                forward_blocks: vec![real_block.clone()],
+            };
+            assert_ok!(a_receive_chain_reorg(&reorg));
+            assert_ok!(b_receive_chain_reorg(&reorg));
+            assert_eq!(
+                LastProcessedBlock::get(ChainId::Eth),
+                Some(ChainBlock::Eth(real_block))
+            );
+            assert_eq!(FailedEvents::get(ChainId::Eth), vec![]);
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::DroppedFailedEvent(
+                    ChainId::Eth,
+                    0,
+                    failed_event
+                )))
+            );
+        });
+    }
+
+    #[test]
+    fn test_receive_chain_reorg_fails_for_checkpointed_chain() {
+        new_test_ext().execute_with(|| {
+            initialize_storage_with_blocks(vec![ChainBlock::Matic(premined_block())]);
+            CheckpointContracts::insert(ChainId::Matic, ChainAccount::Eth([8; 20]));
+
+            let reorg = ChainReorg::Matic {
+                from_hash: premined_block().hash,
+                to_hash: [5; 32],
+                reverse_blocks: vec![],
+                forward_blocks: vec![],
+            };
+
+            assert_err!(a_receive_chain_reorg(&reorg), Reason::ReorgBeyondCheckpoint);
+            assert_eq!(PendingChainReorgs::get(ChainId::Matic), vec![]);
+        });
+    }
+
+    #[test]
+    fn test_receive_chain_reorg_fails_for_unknown_ancestry() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+
+            let known_block = ethereum_client::EthereumBlock {
+                hash: [2; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+            assert_ok!(all_receive_chain_blocks(&ChainBlocks::Eth(vec![
+                known_block.clone()
+            ])));
+            assert_eq!(
+                AncestorHashes::get(ChainId::Eth),
+                vec![
+                    ChainHash::Eth(premined_block().hash),
+                    ChainHash::Eth(known_block.hash)
+                ]
+            );
+
+            let unknown_block = ethereum_client::EthereumBlock {
+                hash: [7; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+            let real_block = ethereum_client::EthereumBlock {
+                hash: [5; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+            let orphan_block = ethereum_client::EthereumBlock {
+                hash: [6; 32],
+                parent_hash: [9; 32],
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+
+            // reverts a block we never processed
+            assert_err!(
+                a_receive_chain_reorg(&ChainReorg::Eth {
+                    from_hash: known_block.hash,
+                    to_hash: real_block.hash,
+                    reverse_blocks: vec![unknown_block],
+                    forward_blocks: vec![real_block.clone()],
+                }),
+                Reason::InvalidChainReorg
+            );
+
+            // forks from a block we never processed
+            assert_err!(
+                a_receive_chain_reorg(&ChainReorg::Eth {
+                    from_hash: known_block.hash,
+                    to_hash: orphan_block.hash,
+                    reverse_blocks: vec![known_block.clone()],
+                    forward_blocks: vec![orphan_block],
+                }),
+                Reason::InvalidChainReorg
+            );
+
+            // does not end at the claimed new head
+            assert_err!(
+                a_receive_chain_reorg(&ChainReorg::Eth {
+                    from_hash: known_block.hash,
+                    to_hash: [8; 32],
+                    reverse_blocks: vec![known_block.clone()],
+                    forward_blocks: vec![real_block.clone()],
+                }),
+                Reason::InvalidChainReorg
+            );
+
+            assert_eq!(PendingChainReorgs::get(ChainId::Eth), vec![]);
+
+            assert_ok!(a_receive_chain_reorg(&ChainReorg::Eth {
+                from_hash: known_block.hash,
+                to_hash: real_block.hash,
+                reverse_blocks: vec![known_block],
+                forward_blocks: vec![real_block],
+            }));
+            assert_eq!(PendingChainReorgs::get(ChainId::Eth).len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_receive_chain_reorg_fails_for_partial_reversal() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+
+            let block_a = ethereum_client::EthereumBlock {
+                hash: [2; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+            let block_b = ethereum_client::EthereumBlock {
+                hash: [3; 32],
+                parent_hash: block_a.hash,
+                number: 3,
+                timestamp: 0,
+                events: vec![],
+            };
+            let real_block = ethereum_client::EthereumBlock {
+                hash: [5; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+            assert_ok!(all_receive_chain_blocks(&ChainBlocks::Eth(vec![
+                block_a.clone(),
+                block_b.clone()
+            ])));
+            let reorg = |reverse_blocks: Vec<ethereum_client::EthereumBlock>| ChainReorg::Eth {
+                from_hash: block_b.hash,
+                to_hash: real_block.hash,
+                reverse_blocks,
+                forward_blocks: vec![real_block.clone()],
+            };
+
+            // leaves out history after the common ancestor
+            assert_err!(
+                a_receive_chain_reorg(&reorg(vec![block_b.clone()])),
+                Reason::InvalidChainReorg
+            );
+
+            // reverts the history out of order
+            assert_err!(
+                a_receive_chain_reorg(&reorg(vec![block_a.clone(), block_b.clone()])),
+                Reason::InvalidChainReorg
+            );
+
+            // reverts the common ancestor too
+            assert_err!(
+                a_receive_chain_reorg(&reorg(vec![
+                    block_b.clone(),
+                    block_a.clone(),
+                    premined_block()
+                ])),
+                Reason::InvalidChainReorg
+            );
+
+            // As after an upgrade, history before the last processed block is unknown,
+            //  so the blocks reverted must lead back to the common ancestor
+            AncestorHashes::remove(ChainId::Eth);
+            assert_err!(
+                a_receive_chain_reorg(&reorg(vec![block_b.clone()])),
+                Reason::InvalidChainReorg
+            );
+            assert_err!(
+                a_receive_chain_reorg(&reorg(vec![block_b.clone(), real_block.clone()])),
+                Reason::InvalidChainReorg
+            );
+            assert_eq!(PendingChainReorgs::get(ChainId::Eth), vec![]);
+
+            assert_ok!(a_receive_chain_reorg(&reorg(vec![
+                block_b.clone(),
+                block_a
+            ])));
+            assert_eq!(PendingChainReorgs::get(ChainId::Eth).len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_receive_chain_reorg_with_incomplete_ancestry() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+
+            let known_block = ethereum_client::EthereumBlock {
+                hash: [2; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+            let real_block = ethereum_client::EthereumBlock {
+                hash: [5; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+            assert_ok!(all_receive_chain_blocks(&ChainBlocks::Eth(vec![
+                known_block.clone()
+            ])));
+
+            // As after an upgrade, history before the last processed block is unknown
+            AncestorHashes::remove(ChainId::Eth);
+            let reorg = ChainReorg::Eth {
+                from_hash: known_block.hash,
+                to_hash: real_block.hash,
+                reverse_blocks: vec![known_block],
+                forward_blocks: vec![real_block.clone()],
+            };
+            assert_ok!(a_receive_chain_reorg(&reorg));
+            assert_ok!(b_receive_chain_reorg(&reorg));
+            assert_eq!(
+                LastProcessedBlock::get(ChainId::Eth),
+                Some(ChainBlock::Eth(real_block.clone()))
+            );
+            assert_eq!(
+                AncestorHashes::get(ChainId::Eth),
+                vec![ChainHash::Eth(real_block.hash)]
+            );
+        });
+    }
+
+    #[test]
+    fn test_expire_chain_tallies() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            LastBlockTimestamp::put(1000);
+
+            let block_2 = ethereum_client::EthereumBlock {
+                hash: [2; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+            let block_3 = ethereum_client::EthereumBlock {
+                hash: [3; 32],
+                parent_hash: [2; 32],
+                number: 3,
+                timestamp: 0,
+                events: vec![],
+            };
+            let superseded_reorg = ChainReorg::Eth {
+                from_hash: [9; 32],
+                to_hash: [8; 32],
+                reverse_blocks: vec![],
+                forward_blocks: vec![],
+            };
+
+            assert_ok!(a_receive_chain_blocks(&ChainBlocks::Eth(vec![
+                block_2.clone()
+            ])));
+            assert_eq!(PendingChainTalliesSince::get(ChainId::Eth), Some(1000));
+            PendingChainReorgs::insert(
+                ChainId::Eth,
+                vec![ChainReorgTally::new(
+                    ChainId::Eth,
+                    superseded_reorg,
+                    &val_a(),
+                )],
+            );
+
+            // superseded tallies expire right away
+            expire_chain_tallies::<Test>(1001);
+            assert_eq!(PendingChainReorgs::get(ChainId::Eth), vec![]);
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth).len(), 1);
+
+            // progress restarts the clock for what becomes pending next
+            LastBlockTimestamp::put(2000);
+            assert_ok!(all_receive_chain_blocks(&ChainBlocks::Eth(vec![block_2])));
+            assert_eq!(PendingChainTalliesSince::get(ChainId::Eth), None);
+            assert_ok!(a_receive_chain_blocks(&ChainBlocks::Eth(vec![block_3])));
+            assert_eq!(PendingChainTalliesSince::get(ChainId::Eth), Some(2000));
+
+            expire_chain_tallies::<Test>(2000 + PENDING_TALLY_EXPIRY);
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth).len(), 1);
+
+            // tallies which wait too long expire
+            expire_chain_tallies::<Test>(2001 + PENDING_TALLY_EXPIRY);
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth), vec![]);
+            assert_eq!(PendingChainTalliesSince::get(ChainId::Eth), None);
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::ExpiredChainTally(
+                    ChainId::Eth,
+                    0,
+                    1
+                )))
+            );
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::ExpiredChainTally(
+                    ChainId::Eth,
+                    1,
+                    0
+                )))
+            );
+        });
+    }
+
+    #[test]
+    fn test_remember_ancestor_keeps_window() {
+        let mut ancestors = vec![];
+        for block in gen_blocks(0, ANCESTOR_HASH_WINDOW as u64 + 2, 0) {
+            remember_ancestor(&mut ancestors, &ChainBlock::Eth(block));
+        }
+        let expected = gen_blocks(0, ANCESTOR_HASH_WINDOW as u64 + 2, 0)
+            .into_iter()
+            .skip(2)
+            .map(|block| ChainHash::Eth(block.hash))
+            .collect::<Vec<_>>();
+        assert_eq!(ancestors, expected);
+    }
+
+    #[test]
+    fn test_receive_chain_blocks_fails_for_paused_chain() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            assert_ok!(CashModule::pause_chain_ingestion(
+                Origin::root(),
+                ChainId::Eth
+            ));
+
+            let blocks = ChainBlocks::Eth(vec![ethereum_client::EthereumBlock {
+                hash: [2; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            }]);
+
+            assert_err!(
+                a_receive_chain_blocks(&blocks),
+                Reason::ChainIngestionPaused
+            );
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth), vec![]);
+
+            assert_ok!(CashModule::resume_chain_ingestion(
+                Origin::root(),
+                ChainId::Eth
+            ));
+            assert_ok!(a_receive_chain_blocks(&blocks));
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth).len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_receive_chain_blocks_skips_implausible_block_time() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+
+            // Gateway time is 500ms, so a block an hour ahead is too far in the future
+            let future_block = ethereum_client::EthereumBlock {
+                hash: [2; 32],
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 60 * 60,
+                events: vec![],
+            };
+            assert_ok!(a_receive_chain_blocks(&ChainBlocks::Eth(vec![
+                future_block.clone()
+            ])));
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth), vec![]);
+
+            assert_err!(
+                a_receive_chain_reorg(&ChainReorg::Eth {
+                    from_hash: premined_block().hash,
+                    to_hash: future_block.hash,
+                    reverse_blocks: vec![],
+                    forward_blocks: vec![future_block],
+                }),
+                Reason::ImplausibleBlockTime
+            );
+
+            assert!(is_plausible_block_time(1000, 1000, 500));
+            assert!(!is_plausible_block_time(1000, 2000, 500));
+            assert!(is_plausible_block_time(0, 0, 30 * 24 * 60 * 60 * 1000));
+        });
+    }
+
+    #[test]
+    fn test_receive_chain_blocks_reports_equivocation() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+
+            let block = |hash| ethereum_client::EthereumBlock {
+                hash,
+                parent_hash: premined_block().hash,
+                number: 2,
+                timestamp: 0,
+                events: vec![],
+            };
+            assert_ok!(a_receive_chain_blocks(&ChainBlocks::Eth(vec![block(
+                [2; 32]
+            )])));
+            assert_eq!(ValidatorOffenses::iter().count(), 0);
+
+            // Validator a signs a conflicting block at the same height
+            assert_ok!(a_receive_chain_blocks(&ChainBlocks::Eth(vec![block(
+                [9; 32]
+            )])));
+            let offenses: Vec<(SubstrateId, Vec<ValidatorOffense>)> =
+                ValidatorOffenses::iter().collect();
+            assert_eq!(offenses.len(), 1);
+            assert_eq!(offenses[0].1.len(), 1);
+            assert_eq!(offenses[0].1[0].chain_id, ChainId::Eth);
+            assert_eq!(offenses[0].1[0].block_number, 2);
+            let validator = Validators::get(&offenses[0].0).unwrap();
+            assert!(WithheldValidators::get(&validator.substrate_id));
+
+            // Validator b dissenting without having signed the block is no offense
+            assert_ok!(b_receive_chain_blocks(&ChainBlocks::Eth(vec![block(
+                [9; 32]
+            )])));
+            assert_eq!(ValidatorOffenses::iter().count(), 1);
+        });
+    }
+
+    #[test]
+    fn test_track_chain_events_on_skips_paused_chain() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            IngestionPaused::insert(ChainId::Eth, true);
+
+            // Note: no http calls are mocked, so any fetch would fail
+            assert_eq!(track_chain_events_on::<Test>(ChainId::Eth), Ok(()));
+        });
+    }
+
     #[test]
     fn test_collect_rev() {
         let x = vec![1, 2, 3];
@@ -965,11 +1814,13 @@ mod tests {
                 chain: String::from("ETH"),
                 recipient: [2; 32],
                 amount: qty!("75", ETH).value,
+                reference: None,
             };
             let blocks_2 = ChainBlocks::Eth(vec![ethereum_client::EthereumBlock {
                 hash: [2; 32],
                 parent_hash: premined_block().hash,
                 number: 2,
+                timestamp: 0,
                 events: vec![event.clone()],
             }]);
             let blocks_3 = ChainBlocks::Eth(vec![
@@ -977,18 +1828,21 @@ mod tests {
                     hash: [3; 32],
                     parent_hash: [2; 32],
                     number: 3,
+                    timestamp: 0,
                     events: vec![],
                 },
                 ethereum_client::EthereumBlock {
                     hash: [4; 32],
                     parent_hash: [3; 32],
                     number: 4,
+                    timestamp: 0,
                     events: vec![],
                 },
                 ethereum_client::EthereumBlock {
                     hash: [5; 32],
                     parent_hash: [4; 32],
                     number: 5,
+                    timestamp: 0,
                     events: vec![],
                 },
             ]);
@@ -996,6 +1850,7 @@ mod tests {
                 hash: [6; 32],
                 parent_hash: [5; 32],
                 number: 6,
+                timestamp: 0,
                 events: vec![],
             }]);
 
@@ -1,21 +1,42 @@
 use crate::{
     chains::{ChainAccount, ChainAccountSignature},
+    core::get_recent_timestamp,
     internal::{
+        allowance::{approve_internal, spend_allowance},
         assets::get_asset,
-        extract::{extract_cash_principal_internal, extract_internal},
+        auction::check_no_liquidation_auction,
+        bonding::{finish_bond, prepare_bond, unbond},
+        execution_fees::reserve_execution_fee,
+        extract::{
+            check_extract_rate_limit, finish_extract_cash_principal_internal,
+            finish_extract_internal, prepare_extract_cash_principal_internal,
+            prepare_extract_internal,
+        },
         liquidate::{
             liquidate_cash_collateral_internal, liquidate_cash_principal_internal,
-            liquidate_internal,
+            liquidate_internal, liquidate_many_internal,
+        },
+        miner::get_some_miner,
+        nonces::{cancel_nonce, check_nonce, set_nonce_window, use_nonce},
+        pause::is_emergency_paused,
+        rewards::{finish_claim_rewards, prepare_claim_rewards},
+        transfer::{
+            finish_transfer_cash_principal_internal, finish_transfer_internal,
+            prepare_transfer_cash_principal_internal, prepare_transfer_internal,
         },
-        transfer::{transfer_cash_principal_internal, transfer_internal},
     },
     log,
-    params::TRANSFER_FEE,
+    params::{EXECUTION_POOL, TRANSFER_FEE},
+    pipeline::CashPipeline,
     reason::Reason,
     require,
     symbol::CASH,
-    types::{CashIndex, CashOrChainAsset, CashPrincipalAmount, Nonce, Quantity},
-    CashPrincipals, Config, GlobalCashIndex, Nonces,
+    trx_req::{resolve_asset, scale_max_amount, MaxAssetAmount},
+    types::{
+        AssetAmount, AssetBalance, AssetInfo, AssetQuantity, CashIndex, CashOrChainAsset,
+        CashPrincipalAmount, MathError, Nonce, Quantity,
+    },
+    Config, Event, GlobalCashIndex, Module, Nonces,
 };
 use frame_support::storage::{StorageMap, StorageValue};
 use our_std::{convert::TryInto, str};
@@ -34,6 +55,7 @@ pub fn exec<T: Config>(
     nonce: Nonce,
 ) -> Result<(), Reason> {
     log!("exec: {}", nonce);
+    require!(!is_emergency_paused(), Reason::EmergencyPaused);
     let request_str: &str = str::from_utf8(&request[..]).map_err(|_| Reason::InvalidUTF8)?;
     let sender = signature.recover_account(&prepend_nonce(&request, nonce)[..])?;
     exec_trx_request::<T>(request_str, sender, Some(nonce))
@@ -68,176 +90,671 @@ pub fn exec_trx_request<T: Config>(
 ) -> Result<(), Reason> {
     log!("exec_trx_request: {}", request_str);
     // Match TrxReq against known Transaction Requests
-    let trx_request = trx_request::parse_request(request_str)?;
+    let (trx_request, clauses) = trx_request::parse_request_with_clauses(request_str)?;
+
+    if let Some(deadline) = clauses.deadline {
+        let now = get_recent_timestamp::<T>()?;
+        require!(now <= deadline, Reason::TrxRequestExpired);
+    }
+
+    // Note: liquidations commit their own effects, so can't pay a fee atomically
+    let relayer_fee = get_relayer_fee::<T>(clauses.fee);
+    require!(
+        relayer_fee.is_none()
+            || !matches!(
+                trx_request,
+                trx_request::TrxRequest::Liquidate(..) | trx_request::TrxRequest::LiquidateMany(..)
+            ),
+        Reason::RelayerFeeNotSupported
+    );
+
+    // Note: the execution fee is reimbursed to whoever relays the extraction notice
+    let execution_fee = clauses.execution_fee.map(CashPrincipalAmount);
+    require!(
+        execution_fee.is_none() || matches!(trx_request, trx_request::TrxRequest::Extract(..)),
+        Reason::ExecutionFeeNotSupported
+    );
 
     if let Some(nonce) = nonce_opt {
-        // Read Require Nonce=Nonce_Account+1
-        let current_nonce = Nonces::get(sender);
-        require!(
-            nonce == current_nonce,
-            Reason::IncorrectNonce(nonce, current_nonce)
-        );
+        check_nonce::<T>(sender, nonce)?;
     }
 
     match trx_request {
-        trx_request::TrxRequest::Extract(max_amount, asset, account) => {
-            match CashOrChainAsset::from(asset) {
-                CashOrChainAsset::Cash => match max_amount {
-                    trx_request::MaxAmount::Max => {
-                        let principal_amount = CashPrincipals::get(sender).amount_withdrawable()?;
-                        extract_cash_principal_internal::<T>(
+        trx_request::TrxRequest::Batch(operations) => {
+            let mut pipeline = CashPipeline::new();
+            let mut prepared = Vec::with_capacity(operations.len());
+            for operation in operations {
+                let (next_pipeline, prepared_operation) =
+                    prepare_operation::<T>(pipeline, operation, sender)?;
+                pipeline = next_pipeline;
+                prepared.push(prepared_operation);
+            }
+            check_batch_extract_rate_limits::<T>(sender, &prepared)?;
+            let pipeline = prepare_relayer_fee::<T>(pipeline, sender, relayer_fee)?;
+            pipeline.commit::<T>();
+            for prepared_operation in prepared {
+                finish_operation::<T>(prepared_operation)?;
+            }
+        }
+
+        trx_request::TrxRequest::TransferMany(asset, transfers) => {
+            let asset = resolve_asset::<T>(asset)?;
+            let mut pipeline = CashPipeline::new();
+            let mut prepared = Vec::with_capacity(transfers.len());
+            for (max_amount, account) in transfers {
+                let (next_pipeline, prepared_operation) = match asset {
+                    CashOrChainAsset::Cash => {
+                        let principal_amount = match scale_max_amount(max_amount, CASH.decimals)? {
+                            MaxAssetAmount::Max => return Err(Reason::Unreachable),
+                            MaxAssetAmount::Amount(amount) => GlobalCashIndex::get()
+                                .cash_principal_amount(Quantity::new(amount, CASH))?,
+                        };
+                        prepare_cash_transfer::<T>(
+                            pipeline,
                             sender,
                             account.into(),
                             principal_amount,
-                        )?;
+                        )?
                     }
 
-                    trx_request::MaxAmount::Amount(amount) => {
-                        let index = GlobalCashIndex::get();
-                        let principal_amount =
-                            index.cash_principal_amount(Quantity::new(amount, CASH))?;
-                        extract_cash_principal_internal::<T>(
+                    CashOrChainAsset::ChainAsset(chain_asset) => {
+                        let asset_info = get_asset::<T>(chain_asset)?;
+                        let asset_amount = match scale_max_amount(max_amount, asset_info.decimals)?
+                        {
+                            MaxAssetAmount::Max => return Err(Reason::Unreachable),
+                            MaxAssetAmount::Amount(amount) => asset_info.as_quantity(amount.into()),
+                        };
+                        prepare_asset_transfer::<T>(
+                            pipeline,
+                            asset_info,
                             sender,
                             account.into(),
-                            principal_amount,
-                        )?;
+                            asset_amount,
+                        )?
                     }
-                },
-
-                CashOrChainAsset::ChainAsset(chain_asset) => match max_amount {
-                    trx_request::MaxAmount::Max => {
-                        return Err(Reason::MaxForNonCashAsset);
-                    }
-                    trx_request::MaxAmount::Amount(amount) => {
-                        let asset = get_asset::<T>(chain_asset)?;
-                        let asset_amount = asset.as_quantity(amount.into());
-                        extract_internal::<T>(asset, sender, account.into(), asset_amount)?;
-                    }
-                },
+                };
+                pipeline = next_pipeline;
+                prepared.push(prepared_operation);
+            }
+            let pipeline = prepare_relayer_fee::<T>(pipeline, sender, relayer_fee)?;
+            pipeline.commit::<T>();
+            for prepared_operation in prepared {
+                finish_operation::<T>(prepared_operation)?;
             }
         }
 
-        trx_request::TrxRequest::Transfer(max_amount, asset, account) => {
-            match CashOrChainAsset::from(asset) {
-                CashOrChainAsset::Cash => match max_amount {
-                    trx_request::MaxAmount::Max => {
-                        let index: CashIndex = GlobalCashIndex::get();
-                        let user_principal = CashPrincipals::get(sender);
-                        let fee_principal = index.cash_principal_amount(TRANSFER_FEE)?;
-                        let transfer_principal: CashPrincipalAmount = user_principal
-                            .sub_amount(fee_principal)?
-                            .try_into()
-                            .map_err(|_| Reason::InsufficientCashForMaxTransfer)?;
-                        transfer_cash_principal_internal::<T>(
-                            sender,
-                            account.into(),
-                            transfer_principal,
-                        )?;
-                    }
+        trx_request::TrxRequest::Liquidate(
+            max_amount,
+            trx_borrowed_asset,
+            trx_collateral_asset,
+            borrower,
+        ) => {
+            // Note: while an auction is open, the borrower may only be liquidated by bidding in it
+            let borrower: ChainAccount = borrower.into();
+            check_no_liquidation_auction(borrower)?;
+            match (
+                resolve_asset::<T>(trx_borrowed_asset)?,
+                resolve_asset::<T>(trx_collateral_asset)?,
+            ) {
+                (x, y) if x == y => return Err(Reason::InKindLiquidation),
+
+                (CashOrChainAsset::Cash, CashOrChainAsset::ChainAsset(collateral)) => {
+                    let collateral_asset = get_asset::<T>(collateral)?;
+                    let cash_principal_amount = match scale_max_amount(max_amount, CASH.decimals)? {
+                        MaxAssetAmount::Max => panic!("Not supported"), // TODO
+                        MaxAssetAmount::Amount(amount) => {
+                            let index = GlobalCashIndex::get();
+                            index.cash_principal_amount(Quantity::new(amount, CASH))?
+                        }
+                    };
+
+                    liquidate_cash_principal_internal::<T>(
+                        collateral_asset,
+                        sender,
+                        borrower,
+                        cash_principal_amount,
+                    )?;
+                }
 
-                    trx_request::MaxAmount::Amount(amount) => {
-                        let index = GlobalCashIndex::get();
-                        let principal_amount =
-                            index.cash_principal_amount(Quantity::new(amount, CASH))?;
-                        transfer_cash_principal_internal::<T>(
-                            sender,
-                            account.into(),
-                            principal_amount,
-                        )?;
-                    }
-                },
+                (CashOrChainAsset::ChainAsset(borrowed), CashOrChainAsset::Cash) => {
+                    let borrowed_asset = get_asset::<T>(borrowed)?;
+                    let borrowed_asset_amount =
+                        match scale_max_amount(max_amount, borrowed_asset.decimals)? {
+                            MaxAssetAmount::Max => panic!("Not supported"), // TODO
+                            MaxAssetAmount::Amount(amount) => {
+                                borrowed_asset.as_quantity(amount.into())
+                            }
+                        };
+
+                    liquidate_cash_collateral_internal::<T>(
+                        borrowed_asset,
+                        sender,
+                        borrower,
+                        borrowed_asset_amount,
+                    )?;
+                }
 
-                CashOrChainAsset::ChainAsset(chain_asset) => match max_amount {
-                    trx_request::MaxAmount::Max => {
-                        return Err(Reason::MaxForNonCashAsset);
-                    }
+                (
+                    CashOrChainAsset::ChainAsset(borrowed),
+                    CashOrChainAsset::ChainAsset(collateral),
+                ) => {
+                    let borrowed_asset = get_asset::<T>(borrowed)?;
+                    let collateral_asset = get_asset::<T>(collateral)?;
+                    let borrowed_asset_amount =
+                        match scale_max_amount(max_amount, borrowed_asset.decimals)? {
+                            MaxAssetAmount::Max => panic!("Not supported"), // TODO
+                            MaxAssetAmount::Amount(amount) => {
+                                borrowed_asset.as_quantity(amount.into())
+                            }
+                        };
+
+                    liquidate_internal::<T>(
+                        borrowed_asset,
+                        collateral_asset,
+                        sender,
+                        borrower,
+                        borrowed_asset_amount,
+                    )?;
+                }
 
-                    trx_request::MaxAmount::Amount(amount) => {
-                        let asset = get_asset::<T>(chain_asset)?;
-                        let asset_amount = asset.as_quantity(amount.into());
-                        transfer_internal::<T>(asset, sender, account.into(), asset_amount)?;
-                    }
-                },
+                _ => return Err(Reason::InvalidLiquidation), // Probably isn't possible
             }
         }
 
-        trx_request::TrxRequest::Liquidate(
+        trx_request::TrxRequest::LiquidateMany(
             max_amount,
             trx_borrowed_asset,
-            trx_collateral_asset,
+            trx_collateral_assets,
             borrower,
-        ) => match (
-            CashOrChainAsset::from(trx_borrowed_asset),
-            CashOrChainAsset::from(trx_collateral_asset),
-        ) {
-            (x, y) if x == y => return Err(Reason::InKindLiquidation),
-
-            (CashOrChainAsset::Cash, CashOrChainAsset::ChainAsset(collateral)) => {
-                let collateral_asset = get_asset::<T>(collateral)?;
-                let cash_principal_amount = match max_amount {
-                    trx_request::MaxAmount::Max => panic!("Not supported"), // TODO
-                    trx_request::MaxAmount::Amount(amount) => {
-                        let index = GlobalCashIndex::get();
-                        index.cash_principal_amount(Quantity::new(amount, CASH))?
+        ) => {
+            let borrowed = resolve_asset::<T>(trx_borrowed_asset)?;
+            let borrowed_units = match borrowed {
+                CashOrChainAsset::Cash => CASH,
+                CashOrChainAsset::ChainAsset(chain_asset) => get_asset::<T>(chain_asset)?.units(),
+            };
+            let quantity = match scale_max_amount(max_amount, borrowed_units.decimals)? {
+                MaxAssetAmount::Max => return Err(Reason::InvalidLiquidation),
+                MaxAssetAmount::Amount(amount) => Quantity::new(amount, borrowed_units),
+            };
+            let mut collateral_assets = Vec::with_capacity(trx_collateral_assets.len());
+            for trx_collateral_asset in trx_collateral_assets {
+                match resolve_asset::<T>(trx_collateral_asset)? {
+                    collateral if collateral == borrowed => return Err(Reason::InKindLiquidation),
+                    CashOrChainAsset::Cash => return Err(Reason::InvalidLiquidation),
+                    CashOrChainAsset::ChainAsset(collateral) => {
+                        collateral_assets.push(get_asset::<T>(collateral)?)
                     }
-                };
+                }
+            }
+
+            let borrower: ChainAccount = borrower.into();
+            check_no_liquidation_auction(borrower)?;
+            liquidate_many_internal::<T>(borrowed, collateral_assets, sender, borrower, quantity)?;
+        }
+
+        trx_request::TrxRequest::Approve(max_amount, asset, spender) => {
+            let asset = resolve_asset::<T>(asset)?;
+            let decimals = match asset {
+                CashOrChainAsset::Cash => CASH.decimals,
+                CashOrChainAsset::ChainAsset(chain_asset) => get_asset::<T>(chain_asset)?.decimals,
+            };
+            let amount = match scale_max_amount(max_amount, decimals)? {
+                MaxAssetAmount::Max => AssetAmount::MAX,
+                MaxAssetAmount::Amount(amount) => amount,
+            };
+            let pipeline = prepare_relayer_fee::<T>(CashPipeline::new(), sender, relayer_fee)?;
+            approve_internal::<T>(sender, spender.into(), asset, amount)?;
+            pipeline.commit::<T>();
+        }
+
+        trx_request::TrxRequest::Cancel(nonce) => {
+            let pipeline = prepare_relayer_fee::<T>(CashPipeline::new(), sender, relayer_fee)?;
+            cancel_nonce::<T>(sender, nonce, nonce_opt)?;
+            pipeline.commit::<T>();
+        }
+
+        trx_request::TrxRequest::SetNonceWindow(window) => {
+            let pipeline = prepare_relayer_fee::<T>(CashPipeline::new(), sender, relayer_fee)?;
+            set_nonce_window::<T>(sender, window)?;
+            pipeline.commit::<T>();
+        }
+
+        trx_request::TrxRequest::ClaimRewards => {
+            let (pipeline, principal) = prepare_claim_rewards::<T>(CashPipeline::new(), sender)?;
+            let pipeline = prepare_relayer_fee::<T>(pipeline, sender, relayer_fee)?;
+            pipeline.commit::<T>();
+            finish_claim_rewards::<T>(sender, principal);
+        }
+
+        trx_request::TrxRequest::Bond(max_amount) => {
+            let (pipeline, principal, bond_post) = prepare_bond::<T>(
+                CashPipeline::new(),
+                sender,
+                scale_max_amount(max_amount, CASH.decimals)?,
+            )?;
+            let pipeline = prepare_relayer_fee::<T>(pipeline, sender, relayer_fee)?;
+            pipeline.commit::<T>();
+            finish_bond::<T>(sender, principal, bond_post);
+        }
+
+        trx_request::TrxRequest::Unbond(max_amount) => {
+            let pipeline = prepare_relayer_fee::<T>(CashPipeline::new(), sender, relayer_fee)?;
+            unbond::<T>(
+                sender,
+                scale_max_amount(max_amount, CASH.decimals)?,
+                get_recent_timestamp::<T>()?,
+            )?;
+            pipeline.commit::<T>();
+        }
+
+        trx_request::TrxRequest::TransferFrom(amount, asset, owner, recipient) => {
+            let owner: ChainAccount = owner.into();
+            let asset = resolve_asset::<T>(asset)?;
+            let (pipeline, prepared_operation) = match asset {
+                CashOrChainAsset::Cash => {
+                    let index: CashIndex = GlobalCashIndex::get();
+                    let principal_amount =
+                        index.cash_principal_amount(Quantity::new(amount, CASH))?;
+                    prepare_cash_transfer::<T>(
+                        CashPipeline::new(),
+                        owner,
+                        recipient.into(),
+                        principal_amount,
+                    )?
+                }
+
+                CashOrChainAsset::ChainAsset(chain_asset) => {
+                    let asset_info = get_asset::<T>(chain_asset)?;
+                    prepare_asset_transfer::<T>(
+                        CashPipeline::new(),
+                        asset_info,
+                        owner,
+                        recipient.into(),
+                        asset_info.as_quantity(amount.into()),
+                    )?
+                }
+            };
+            let pipeline = prepare_relayer_fee::<T>(pipeline, sender, relayer_fee)?;
+            spend_allowance::<T>(owner, sender, asset, amount)?;
+            pipeline.commit::<T>();
+            finish_operation::<T>(prepared_operation)?;
+        }
+
+        operation => {
+            let (pipeline, prepared_operation) =
+                prepare_operation::<T>(CashPipeline::new(), operation, sender)?;
+            let pipeline = prepare_relayer_fee::<T>(pipeline, sender, relayer_fee)?;
+            let pipeline = prepare_execution_fee::<T>(pipeline, sender, execution_fee)?;
+            pipeline.commit::<T>();
+            finish_execution_fee::<T>(&prepared_operation, sender, execution_fee)?;
+            finish_operation::<T>(prepared_operation)?;
+        }
+    }
 
-                liquidate_cash_principal_internal::<T>(
-                    collateral_asset,
-                    sender,
-                    borrower.into(),
-                    cash_principal_amount,
-                )?;
+    finish_relayer_fee::<T>(sender, relayer_fee);
+
+    if let Some(nonce) = nonce_opt {
+        use_nonce::<T>(sender, nonce);
+    }
+
+    Ok(())
+}
+
+/// Determine the account to pay and the CASH principal, for a relayer fee authorized by the signer.
+fn get_relayer_fee<T: Config>(
+    fee: Option<trx_request::Fee>,
+) -> Option<(ChainAccount, CashPrincipalAmount)> {
+    fee.map(|fee| {
+        let relayer = match fee.recipient {
+            Some(account) => account.into(),
+            None => get_some_miner::<T>(),
+        };
+        (relayer, CashPrincipalAmount(fee.principal))
+    })
+}
+
+/// Add the payment of the relayer fee, if any, to the pipeline.
+fn prepare_relayer_fee<T: Config>(
+    pipeline: CashPipeline,
+    sender: ChainAccount,
+    relayer_fee: Option<(ChainAccount, CashPrincipalAmount)>,
+) -> Result<CashPipeline, Reason> {
+    match relayer_fee {
+        Some((relayer, principal)) => pipeline
+            .transfer_cash::<T>(sender, relayer, principal)?
+            .check_collateralized::<T>(sender),
+        None => Ok(pipeline),
+    }
+}
+
+/// Emit the events for the relayer fee, if any, once its payment is committed.
+fn finish_relayer_fee<T: Config>(
+    sender: ChainAccount,
+    relayer_fee: Option<(ChainAccount, CashPrincipalAmount)>,
+) {
+    if let Some((relayer, principal)) = relayer_fee {
+        let index = GlobalCashIndex::get();
+        <Module<T>>::deposit_event(Event::TransferCash(sender, relayer, principal, index));
+        <Module<T>>::deposit_event(Event::RelayerPaid(relayer, principal));
+    }
+}
+
+/// Add the payment of the execution fee, if any, into the execution pool to the pipeline.
+fn prepare_execution_fee<T: Config>(
+    pipeline: CashPipeline,
+    sender: ChainAccount,
+    execution_fee: Option<CashPrincipalAmount>,
+) -> Result<CashPipeline, Reason> {
+    match execution_fee {
+        Some(principal) => pipeline
+            .transfer_cash::<T>(sender, EXECUTION_POOL, principal)?
+            .check_collateralized::<T>(sender),
+        None => Ok(pipeline),
+    }
+}
+
+/// Hold the execution fee, if any, for the extraction notice about to be dispatched.
+fn finish_execution_fee<T: Config>(
+    prepared: &PreparedOperation,
+    sender: ChainAccount,
+    execution_fee: Option<CashPrincipalAmount>,
+) -> Result<(), Reason> {
+    if let Some(principal) = execution_fee {
+        let chain_id = match prepared {
+            PreparedOperation::Extract(_, _, recipient, _) => recipient.chain_id(),
+            PreparedOperation::ExtractCash(_, recipient, _) => recipient.chain_id(),
+            _ => return Err(Reason::ExecutionFeeNotSupported),
+        };
+        let index = GlobalCashIndex::get();
+        <Module<T>>::deposit_event(Event::TransferCash(
+            sender,
+            EXECUTION_POOL,
+            principal,
+            index,
+        ));
+        reserve_execution_fee::<T>(chain_id, sender, principal)?;
+    }
+    Ok(())
+}
+
+/// An operation whose effects have been added to a pipeline, awaiting the commit.
+enum PreparedOperation {
+    Extract(AssetInfo, ChainAccount, ChainAccount, AssetQuantity),
+    ExtractCash(ChainAccount, ChainAccount, CashPrincipalAmount),
+    Transfer(AssetInfo, ChainAccount, ChainAccount, AssetQuantity),
+    TransferCash(ChainAccount, ChainAccount, CashPrincipalAmount),
+}
+
+/// Add the effects of a single operation to the pipeline, reading balances through it.
+fn prepare_operation<T: Config>(
+    pipeline: CashPipeline,
+    operation: trx_request::TrxRequest,
+    sender: ChainAccount,
+) -> Result<(CashPipeline, PreparedOperation), Reason> {
+    match operation {
+        trx_request::TrxRequest::Extract(max_amount, asset, account) => {
+            let recipient: ChainAccount = account.into();
+            match resolve_asset::<T>(asset)? {
+                CashOrChainAsset::Cash => {
+                    let principal_amount = match scale_max_amount(max_amount, CASH.decimals)? {
+                        MaxAssetAmount::Max => pipeline
+                            .state
+                            .get_cash_principal::<T>(sender)
+                            .amount_withdrawable()?,
+
+                        MaxAssetAmount::Amount(amount) => {
+                            let index = GlobalCashIndex::get();
+                            index.cash_principal_amount(Quantity::new(amount, CASH))?
+                        }
+                    };
+                    let pipeline = prepare_extract_cash_principal_internal::<T>(
+                        pipeline,
+                        sender,
+                        principal_amount,
+                    )?;
+                    Ok((
+                        pipeline,
+                        PreparedOperation::ExtractCash(sender, recipient, principal_amount),
+                    ))
+                }
+
+                CashOrChainAsset::ChainAsset(chain_asset) => {
+                    let asset = get_asset::<T>(chain_asset)?;
+                    match scale_max_amount(max_amount, asset.decimals)? {
+                        MaxAssetAmount::Max => Err(Reason::MaxForNonCashAsset),
+
+                        MaxAssetAmount::Amount(amount) => {
+                            let asset_amount = asset.as_quantity(amount.into());
+                            let pipeline = prepare_extract_internal::<T>(
+                                pipeline,
+                                asset,
+                                sender,
+                                asset_amount,
+                            )?;
+                            Ok((
+                                pipeline,
+                                PreparedOperation::Extract(asset, sender, recipient, asset_amount),
+                            ))
+                        }
+                    }
+                }
             }
+        }
+
+        trx_request::TrxRequest::Transfer(max_amount, asset, account) => {
+            let recipient: ChainAccount = account.into();
+            match resolve_asset::<T>(asset)? {
+                CashOrChainAsset::Cash => {
+                    let index: CashIndex = GlobalCashIndex::get();
+                    let principal_amount = match scale_max_amount(max_amount, CASH.decimals)? {
+                        MaxAssetAmount::Max => {
+                            let user_principal = pipeline.state.get_cash_principal::<T>(sender);
+                            let fee_principal = index.cash_principal_amount(TRANSFER_FEE)?;
+                            user_principal
+                                .sub_amount(fee_principal)?
+                                .try_into()
+                                .map_err(|_| Reason::InsufficientCashForMaxTransfer)?
+                        }
+
+                        MaxAssetAmount::Amount(amount) => {
+                            index.cash_principal_amount(Quantity::new(amount, CASH))?
+                        }
+                    };
+                    prepare_cash_transfer::<T>(pipeline, sender, recipient, principal_amount)
+                }
 
-            (CashOrChainAsset::ChainAsset(borrowed), CashOrChainAsset::Cash) => {
-                let borrowed_asset = get_asset::<T>(borrowed)?;
-                let borrowed_asset_amount = match max_amount {
-                    trx_request::MaxAmount::Max => panic!("Not supported"), // TODO
-                    trx_request::MaxAmount::Amount(amount) => {
-                        borrowed_asset.as_quantity(amount.into())
+                CashOrChainAsset::ChainAsset(chain_asset) => {
+                    let asset = get_asset::<T>(chain_asset)?;
+                    match scale_max_amount(max_amount, asset.decimals)? {
+                        MaxAssetAmount::Max => Err(Reason::MaxForNonCashAsset),
+
+                        MaxAssetAmount::Amount(amount) => {
+                            let asset_amount = asset.as_quantity(amount.into());
+                            prepare_asset_transfer::<T>(
+                                pipeline,
+                                asset,
+                                sender,
+                                recipient,
+                                asset_amount,
+                            )
+                        }
                     }
-                };
+                }
+            }
+        }
 
-                liquidate_cash_collateral_internal::<T>(
-                    borrowed_asset,
-                    sender,
-                    borrower.into(),
-                    borrowed_asset_amount,
-                )?;
+        trx_request::TrxRequest::Supply(max_amount, asset, account) => {
+            let recipient: ChainAccount = account.into();
+            match resolve_asset::<T>(asset)? {
+                CashOrChainAsset::Cash => {
+                    let index: CashIndex = GlobalCashIndex::get();
+                    let fee_principal = index.cash_principal_amount(TRANSFER_FEE)?;
+                    let available_principal = pipeline
+                        .state
+                        .get_cash_principal::<T>(sender)
+                        .sub_amount(fee_principal)?
+                        .amount_withdrawable()?;
+                    let principal_amount = match scale_max_amount(max_amount, CASH.decimals)? {
+                        MaxAssetAmount::Max => available_principal,
+                        MaxAssetAmount::Amount(amount) => {
+                            index.cash_principal_amount(Quantity::new(amount, CASH))?
+                        }
+                    };
+                    require!(
+                        principal_amount <= available_principal,
+                        Reason::SupplyExceedsBalance
+                    );
+                    prepare_cash_transfer::<T>(pipeline, sender, recipient, principal_amount)
+                }
+
+                CashOrChainAsset::ChainAsset(chain_asset) => {
+                    let asset = get_asset::<T>(chain_asset)?;
+                    let available =
+                        positive_part(pipeline.state.get_asset_balance::<T>(asset, sender).value);
+                    let asset_amount = match scale_max_amount(max_amount, asset.decimals)? {
+                        MaxAssetAmount::Max => asset.as_quantity(available),
+                        MaxAssetAmount::Amount(amount) => asset.as_quantity(amount.into()),
+                    };
+                    require!(
+                        asset_amount.value <= available,
+                        Reason::SupplyExceedsBalance
+                    );
+                    prepare_asset_transfer::<T>(pipeline, asset, sender, recipient, asset_amount)
+                }
             }
+        }
 
-            (CashOrChainAsset::ChainAsset(borrowed), CashOrChainAsset::ChainAsset(collateral)) => {
-                let borrowed_asset = get_asset::<T>(borrowed)?;
-                let collateral_asset = get_asset::<T>(collateral)?;
-                let borrowed_asset_amount = match max_amount {
-                    trx_request::MaxAmount::Max => panic!("Not supported"), // TODO
-                    trx_request::MaxAmount::Amount(amount) => {
-                        borrowed_asset.as_quantity(amount.into())
-                    }
-                };
+        trx_request::TrxRequest::Repay(max_amount, asset, account) => {
+            let borrower: ChainAccount = account.into();
+            match resolve_asset::<T>(asset)? {
+                CashOrChainAsset::Cash => {
+                    let index: CashIndex = GlobalCashIndex::get();
+                    let owed_principal = pipeline
+                        .state
+                        .get_cash_principal::<T>(borrower)
+                        .amount_repayable()?;
+                    let principal_amount = match scale_max_amount(max_amount, CASH.decimals)? {
+                        MaxAssetAmount::Max => owed_principal,
+                        MaxAssetAmount::Amount(amount) => {
+                            index.cash_principal_amount(Quantity::new(amount, CASH))?
+                        }
+                    };
+                    require!(
+                        principal_amount <= owed_principal,
+                        Reason::RepayExceedsBorrow
+                    );
+                    prepare_cash_transfer::<T>(pipeline, sender, borrower, principal_amount)
+                }
 
-                liquidate_internal::<T>(
-                    borrowed_asset,
-                    collateral_asset,
-                    sender,
-                    borrower.into(),
-                    borrowed_asset_amount,
-                )?;
+                CashOrChainAsset::ChainAsset(chain_asset) => {
+                    let asset = get_asset::<T>(chain_asset)?;
+                    let owed = positive_part(
+                        pipeline
+                            .state
+                            .get_asset_balance::<T>(asset, borrower)
+                            .value
+                            .saturating_neg(),
+                    );
+                    let asset_amount = match scale_max_amount(max_amount, asset.decimals)? {
+                        MaxAssetAmount::Max => asset.as_quantity(owed),
+                        MaxAssetAmount::Amount(amount) => asset.as_quantity(amount.into()),
+                    };
+                    require!(asset_amount.value <= owed, Reason::RepayExceedsBorrow);
+                    prepare_asset_transfer::<T>(pipeline, asset, sender, borrower, asset_amount)
+                }
             }
+        }
 
-            _ => return Err(Reason::InvalidLiquidation), // Probably isn't possible
-        },
+        // Note: liquidations, approvals and nested batches are never parsed as batch operations
+        _ => Err(Reason::Unreachable),
     }
+}
 
-    if let Some(nonce) = nonce_opt {
-        // Update user nonce
-        Nonces::insert(sender, nonce + 1);
-    }
+fn prepare_cash_transfer<T: Config>(
+    pipeline: CashPipeline,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    principal: CashPrincipalAmount,
+) -> Result<(CashPipeline, PreparedOperation), Reason> {
+    let pipeline =
+        prepare_transfer_cash_principal_internal::<T>(pipeline, sender, recipient, principal)?;
+    Ok((
+        pipeline,
+        PreparedOperation::TransferCash(sender, recipient, principal),
+    ))
+}
+
+fn prepare_asset_transfer<T: Config>(
+    pipeline: CashPipeline,
+    asset: AssetInfo,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    quantity: AssetQuantity,
+) -> Result<(CashPipeline, PreparedOperation), Reason> {
+    let pipeline = prepare_transfer_internal::<T>(pipeline, asset, sender, recipient, quantity)?;
+    Ok((
+        pipeline,
+        PreparedOperation::Transfer(asset, sender, recipient, quantity),
+    ))
+}
 
+/// Check the combined extractions of each asset in a batch against any extract rate limits.
+/// Each extraction is checked alone as it is prepared, but not against the rest of the batch.
+fn check_batch_extract_rate_limits<T: Config>(
+    sender: ChainAccount,
+    prepared: &Vec<PreparedOperation>,
+) -> Result<(), Reason> {
+    let mut totals: Vec<(CashOrChainAsset, AssetAmount)> = Vec::new();
+    for prepared_operation in prepared {
+        let (asset, amount) = match prepared_operation {
+            PreparedOperation::Extract(asset, _, _, quantity) => {
+                (CashOrChainAsset::ChainAsset(asset.asset), quantity.value)
+            }
+            PreparedOperation::ExtractCash(_, _, principal) => (
+                CashOrChainAsset::Cash,
+                GlobalCashIndex::get().cash_quantity(*principal)?.value,
+            ),
+            _ => continue,
+        };
+        match totals.iter_mut().find(|(a, _)| *a == asset) {
+            Some((_, total)) => *total = total.checked_add(amount).ok_or(MathError::Overflow)?,
+            None => totals.push((asset, amount)),
+        }
+    }
+    for (asset, total) in totals {
+        check_extract_rate_limit::<T>(sender, asset, total)?;
+    }
     Ok(())
 }
 
+/// Dispatch the notices and events for an operation, once the pipeline is committed.
+fn finish_operation<T: Config>(prepared: PreparedOperation) -> Result<(), Reason> {
+    match prepared {
+        PreparedOperation::Extract(asset, sender, recipient, quantity) => {
+            finish_extract_internal::<T>(asset, sender, recipient, quantity);
+            Ok(())
+        }
+        PreparedOperation::ExtractCash(sender, recipient, principal) => {
+            finish_extract_cash_principal_internal::<T>(sender, recipient, principal);
+            Ok(())
+        }
+        PreparedOperation::Transfer(asset, sender, recipient, quantity) => {
+            finish_transfer_internal::<T>(asset, sender, recipient, quantity)
+        }
+        PreparedOperation::TransferCash(sender, recipient, principal) => {
+            finish_transfer_cash_principal_internal::<T>(sender, recipient, principal)
+        }
+    }
+}
+
+fn positive_part(balance: AssetBalance) -> AssetAmount {
+    if balance > 0 {
+        balance as AssetAmount
+    } else {
+        0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,6 +778,21 @@ mod tests {
         assert_eq!(result, Err(Reason::TrxRequestTooLong));
     }
 
+    #[test]
+    fn test_exec_during_emergency_pause() {
+        new_test_ext().execute_with(|| {
+            EmergencyPauseActive::put(true);
+            assert_eq!(
+                exec::<Test>(
+                    "(NonceWindow 2)".as_bytes().to_vec(),
+                    get_empty_signature(),
+                    0
+                ),
+                Err(Reason::EmergencyPaused)
+            );
+        });
+    }
+
     #[test]
     fn exec_trx_request_extract_cash_principal_internal() {
         new_test_ext().execute_with(|| {
@@ -756,6 +1288,517 @@ mod tests {
 
     // TODO: Liquidation Unit Tests
 
+    #[test]
+    fn exec_trx_supply_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            let to_account = ChainAccount::Eth([1; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+            let nonce = 0;
+
+            let req_str =
+                "(Supply 4000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(nonce)),
+                Err(Reason::SupplyExceedsBalance)
+            );
+
+            let req_str = "(Supply Max Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(nonce)),
+                Ok(())
+            );
+
+            assert_eq!(AssetBalances::get(asset, account), 0);
+            assert_eq!(
+                AssetBalances::get(asset, to_account),
+                Balance::from_nominal("3", ETH).value
+            );
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("0.99")
+            );
+            assert_eq!(Nonces::get(account), nonce + 1);
+        });
+    }
+
+    #[test]
+    fn exec_trx_supply_principal_cash_internal_insufficient() {
+        new_test_ext().execute_with(|| {
+            let account = ChainAccount::Eth([20; 20]);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+
+            // the transfer fee would have to be borrowed
+            let req_str = "(Supply 1000000 CASH Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(0)),
+                Err(Reason::SupplyExceedsBalance)
+            );
+        });
+    }
+
+    #[test]
+    fn exec_trx_repay_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            let borrower = ChainAccount::Eth([1; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_asset_balance(asset, borrower, Balance::from_nominal("-2", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+            let nonce = 0;
+
+            let req_str =
+                "(Repay 3000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(nonce)),
+                Err(Reason::RepayExceedsBorrow)
+            );
+
+            let req_str = "(Repay Max Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(nonce)),
+                Ok(())
+            );
+
+            assert_eq!(
+                AssetBalances::get(asset, account),
+                Balance::from_nominal("1", ETH).value
+            );
+            assert_eq!(AssetBalances::get(asset, borrower), 0);
+            assert_eq!(Nonces::get(account), nonce + 1);
+        });
+    }
+
+    #[test]
+    fn exec_trx_batch_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            let to_account = ChainAccount::Eth([1; 20]);
+            let other_account = ChainAccount::Eth([2; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+            let nonce = 0;
+
+            let req_str = "(Batch \
+              (Transfer 1000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+                Eth:0x0101010101010101010101010101010101010101) \
+              (Supply Max Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+                Eth:0x0202020202020202020202020202020202020202))";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(nonce)),
+                Ok(())
+            );
+
+            assert_eq!(AssetBalances::get(asset, account), 0);
+            assert_eq!(
+                AssetBalances::get(asset, to_account),
+                Balance::from_nominal("1", ETH).value
+            );
+            assert_eq!(
+                AssetBalances::get(asset, other_account),
+                Balance::from_nominal("2", ETH).value
+            );
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("0.98")
+            );
+            assert_eq!(Nonces::get(account), nonce + 1);
+        });
+    }
+
+    #[test]
+    fn exec_trx_batch_internal_is_atomic() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            let to_account = ChainAccount::Eth([1; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+            let nonce = 0;
+
+            // the supply sees the balance left after the transfer, so it fails, and so does the transfer
+            let req_str = "(Batch \
+              (Transfer 2000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+                Eth:0x0101010101010101010101010101010101010101) \
+              (Supply 2000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+                Eth:0x0101010101010101010101010101010101010101))";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(nonce)),
+                Err(Reason::SupplyExceedsBalance)
+            );
+
+            assert_eq!(
+                AssetBalances::get(asset, account),
+                Balance::from_nominal("3", ETH).value
+            );
+            assert_eq!(AssetBalances::get(asset, to_account), 0);
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("1")
+            );
+            assert_eq!(Nonces::get(account), nonce);
+            assert_eq!(System::events().len(), 0);
+        });
+    }
+
+    #[test]
+    fn exec_trx_transfer_symbol_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            let to_account = ChainAccount::Eth([1; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+
+            let req_str =
+                "(Transfer 1000000000000000000 UNI Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(0)),
+                Err(Reason::AssetNotSupported)
+            );
+
+            let req_str =
+                "(Transfer 1000000000000000000 ETH Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(exec_trx_request::<Test>(req_str, account, Some(0)), Ok(()));
+            assert_eq!(
+                AssetBalances::get(asset, to_account),
+                Balance::from_nominal("1", ETH).value
+            );
+            assert_eq!(Nonces::get(account), 1);
+        });
+    }
+
+    #[test]
+    fn exec_trx_transfer_decimal_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            let to_account = ChainAccount::Eth([1; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+
+            let req_str = "(Transfer 1.5 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(exec_trx_request::<Test>(req_str, account, Some(0)), Ok(()));
+            assert_eq!(
+                AssetBalances::get(asset, to_account),
+                Balance::from_nominal("1.5", ETH).value
+            );
+
+            let req_str =
+                "(Transfer 0.1234567 CASH Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(1)),
+                Err(Reason::TooManyDecimals)
+            );
+            assert_eq!(Nonces::get(account), 1);
+        });
+    }
+
+    #[test]
+    fn exec_trx_relayer_fee_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            let relayer = ChainAccount::Eth([3; 20]);
+            let miner = ChainAccount::Eth([4; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+            Miner::put(miner);
+
+            let req_str = "(Transfer 1000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101) :fee 10000 Eth:0x0303030303030303030303030303030303030303";
+            assert_eq!(exec_trx_request::<Test>(req_str, account, Some(0)), Ok(()));
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("0.98")
+            );
+            assert_eq!(
+                CashPrincipals::get(relayer),
+                CashPrincipal::from_nominal("0.01")
+            );
+            assert_eq!(
+                CashPrincipals::get(miner),
+                CashPrincipal::from_nominal("0.01")
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(events.contains(&mock::Event::pallet_cash(crate::Event::RelayerPaid(
+                relayer,
+                CashPrincipalAmount(10000)
+            ))));
+
+            let req_str = "(NonceWindow 2) :fee 10000";
+            assert_eq!(exec_trx_request::<Test>(req_str, account, Some(1)), Ok(()));
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("0.97")
+            );
+            assert_eq!(
+                CashPrincipals::get(miner),
+                CashPrincipal::from_nominal("0.02")
+            );
+            assert_eq!(Nonces::get(account), 2);
+        });
+    }
+
+    #[test]
+    fn exec_trx_relayer_fee_liquidate_unsupported() {
+        new_test_ext().execute_with(|| {
+            let req_str = "(Liquidate 55 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+                CASH Eth:0x0101010101010101010101010101010101010101) :fee 10000";
+            let account = ChainAccount::Eth([20; 20]);
+
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(0)),
+                Err(Reason::RelayerFeeNotSupported)
+            );
+        });
+    }
+
+    #[test]
+    fn exec_trx_execution_fee_internal() {
+        new_test_ext().execute_with(|| {
+            let account = ChainAccount::Eth([20; 20]);
+            let recipient = ChainAccount::Eth([1; 20]);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+
+            let req_str = "(Extract 500000 CASH Eth:0x0101010101010101010101010101010101010101) :execfee 10000";
+            assert_eq!(exec_trx_request::<Test>(req_str, account, Some(0)), Ok(()));
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("0.49")
+            );
+            assert_eq!(
+                CashPrincipals::get(EXECUTION_POOL),
+                CashPrincipal::from_nominal("0.01")
+            );
+            assert_eq!(
+                ExecutionPools::get(recipient.chain_id()),
+                CashPrincipalAmount(10000)
+            );
+            assert_eq!(AccountNotices::get(recipient), vec![NoticeId(0, 1)]);
+            assert_eq!(
+                NoticeExecutionFees::get(recipient.chain_id(), NoticeId(0, 1)),
+                CashPrincipalAmount(10000)
+            );
+        });
+    }
+
+    #[test]
+    fn exec_trx_execution_fee_transfer_unsupported() {
+        new_test_ext().execute_with(|| {
+            let req_str =
+                "(Transfer 1 CASH Eth:0x0101010101010101010101010101010101010101) :execfee 10000";
+            let account = ChainAccount::Eth([20; 20]);
+
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(0)),
+                Err(Reason::ExecutionFeeNotSupported)
+            );
+        });
+    }
+
+    #[test]
+    fn exec_trx_cancel_nonce_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+            let req_str =
+                "(Transfer 1000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101)";
+
+            assert_eq!(
+                exec_trx_request::<Test>("(NonceWindow 2)", account, Some(0)),
+                Ok(())
+            );
+            assert_eq!(Nonces::get(account), 1);
+
+            // cancel nonce 2 out of order, then a request signed with it can no longer execute
+            assert_eq!(
+                exec_trx_request::<Test>("(Cancel 2)", account, Some(1)),
+                Ok(())
+            );
+            assert_eq!(Nonces::get(account), 3);
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(2)),
+                Err(Reason::IncorrectNonce(2, 3))
+            );
+
+            // a request may cancel its own nonce
+            assert_eq!(
+                exec_trx_request::<Test>("(Cancel 3)", account, Some(3)),
+                Ok(())
+            );
+            assert_eq!(Nonces::get(account), 4);
+            assert_eq!(
+                AssetBalances::get(asset, account),
+                Balance::from_nominal("3", ETH).value
+            );
+        });
+    }
+
+    #[test]
+    fn exec_trx_deadline_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            let to_account = ChainAccount::Eth([1; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(500);
+
+            let req_str =
+                "(Transfer 1000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101) :deadline 499";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, account, Some(0)),
+                Err(Reason::TrxRequestExpired)
+            );
+            assert_eq!(Nonces::get(account), 0);
+
+            let req_str =
+                "(Transfer 1000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101) :deadline 500";
+            assert_eq!(exec_trx_request::<Test>(req_str, account, Some(0)), Ok(()));
+            assert_eq!(
+                AssetBalances::get(asset, to_account),
+                Balance::from_nominal("1", ETH).value
+            );
+            assert_eq!(Nonces::get(account), 1);
+        });
+    }
+
+    #[test]
+    fn exec_trx_transfer_many_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let account = ChainAccount::Eth([20; 20]);
+            let recipient_a = ChainAccount::Eth([1; 20]);
+            let recipient_b = ChainAccount::Eth([2; 20]);
+            init_asset_balance(asset, account, Balance::from_nominal("3", ETH).value);
+            init_cash(account, CashPrincipal::from_nominal("1"));
+
+            // more than the account can afford, so neither transfer happens
+            let req_str = "(TransferMany Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              (2.0 Eth:0x0101010101010101010101010101010101010101) \
+              (2.0 Eth:0x0202020202020202020202020202020202020202))";
+            assert!(exec_trx_request::<Test>(req_str, account, Some(0)).is_err());
+            assert_eq!(AssetBalances::get(asset, recipient_a), 0);
+            assert_eq!(
+                AssetBalances::get(asset, account),
+                Balance::from_nominal("3", ETH).value
+            );
+
+            let req_str = "(TransferMany Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              (1.0 Eth:0x0101010101010101010101010101010101010101) \
+              (1.5 Eth:0x0202020202020202020202020202020202020202))";
+            assert_eq!(exec_trx_request::<Test>(req_str, account, Some(0)), Ok(()));
+            assert_eq!(
+                AssetBalances::get(asset, account),
+                Balance::from_nominal("0.5", ETH).value
+            );
+            assert_eq!(
+                AssetBalances::get(asset, recipient_a),
+                Balance::from_nominal("1", ETH).value
+            );
+            assert_eq!(
+                AssetBalances::get(asset, recipient_b),
+                Balance::from_nominal("1.5", ETH).value
+            );
+            assert_eq!(
+                CashPrincipals::get(account),
+                CashPrincipal::from_nominal("0.98")
+            );
+            assert_eq!(Nonces::get(account), 1);
+        });
+    }
+
+    #[test]
+    fn exec_trx_approve_and_transfer_from_internal() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let owner = ChainAccount::Eth([1; 20]);
+            let spender = ChainAccount::Eth([20; 20]);
+            let recipient = ChainAccount::Eth([2; 20]);
+            init_asset_balance(asset, owner, Balance::from_nominal("3", ETH).value);
+            init_cash(owner, CashPrincipal::from_nominal("1"));
+
+            let req_str = "(Approve 2000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x1414141414141414141414141414141414141414)";
+            assert_eq!(exec_trx_request::<Test>(req_str, owner, Some(0)), Ok(()));
+            assert_eq!(
+                Allowances::get(owner, (spender, CashOrChainAsset::ChainAsset(Eth))),
+                Balance::from_nominal("2", ETH).value as AssetAmount
+            );
+            assert_eq!(Nonces::get(owner), 1);
+
+            let req_str = "(TransferFrom 1500000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101 Eth:0x0202020202020202020202020202020202020202)";
+            assert_eq!(exec_trx_request::<Test>(req_str, spender, Some(0)), Ok(()));
+            assert_eq!(
+                AssetBalances::get(asset, owner),
+                Balance::from_nominal("1.5", ETH).value
+            );
+            assert_eq!(
+                AssetBalances::get(asset, recipient),
+                Balance::from_nominal("1.5", ETH).value
+            );
+            assert_eq!(
+                CashPrincipals::get(owner),
+                CashPrincipal::from_nominal("0.99")
+            );
+            assert_eq!(
+                Allowances::get(owner, (spender, CashOrChainAsset::ChainAsset(Eth))),
+                Balance::from_nominal("0.5", ETH).value as AssetAmount
+            );
+            assert_eq!(Nonces::get(spender), 1);
+            assert_eq!(Nonces::get(owner), 1);
+        });
+    }
+
+    #[test]
+    fn exec_trx_transfer_from_insufficient_allowance() {
+        new_test_ext().execute_with(|| {
+            let asset = init_eth_asset().unwrap();
+            let owner = ChainAccount::Eth([1; 20]);
+            let spender = ChainAccount::Eth([20; 20]);
+            init_asset_balance(asset, owner, Balance::from_nominal("3", ETH).value);
+            init_cash(owner, CashPrincipal::from_nominal("1"));
+            Allowances::insert(
+                owner,
+                (spender, CashOrChainAsset::ChainAsset(Eth)),
+                Balance::from_nominal("1", ETH).value as AssetAmount,
+            );
+
+            let req_str = "(TransferFrom 2000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+              Eth:0x0101010101010101010101010101010101010101 Eth:0x0202020202020202020202020202020202020202)";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, spender, Some(0)),
+                Err(Reason::InsufficientAllowance)
+            );
+            assert_eq!(
+                AssetBalances::get(asset, owner),
+                Balance::from_nominal("3", ETH).value
+            );
+            assert_eq!(
+                Allowances::get(owner, (spender, CashOrChainAsset::ChainAsset(Eth))),
+                Balance::from_nominal("1", ETH).value as AssetAmount
+            );
+            assert_eq!(Nonces::get(spender), 0);
+        });
+    }
+
     #[test]
     fn exec_trx_liquidate_in_kind() {
         new_test_ext().execute_with(|| {
@@ -1093,6 +2136,44 @@ mod tests {
         });
     }
 
+    #[test]
+    fn exec_trx_liquidate_during_auction() {
+        new_test_ext().execute_with(|| {
+            let wbtc_asset = init_wbtc_asset().unwrap();
+            let eth_asset = init_eth_asset().unwrap();
+            let borrower_account = ChainAccount::Eth([1; 20]);
+            let liquidator_account = ChainAccount::Eth([2; 20]);
+            init_asset_balance(
+                eth_asset,
+                borrower_account,
+                Balance::from_nominal("32.4", ETH).value,
+            );
+            init_asset_balance(
+                wbtc_asset,
+                borrower_account,
+                Balance::from_nominal("-5", WBTC).value,
+            );
+            init_asset_balance(
+                wbtc_asset,
+                liquidator_account,
+                Balance::from_nominal("3", WBTC).value,
+            );
+            crate::LiquidationAuctions::insert(borrower_account, 1);
+
+            let req_str = "(Liquidate 100000000 Eth:0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb \
+                Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee \
+                Eth:0x0101010101010101010101010101010101010101)";
+            assert_eq!(
+                exec_trx_request::<Test>(req_str, liquidator_account, Some(0)),
+                Err(Reason::LiquidationAuctionOpen)
+            );
+            assert_eq!(
+                AssetBalances::get(wbtc_asset, borrower_account),
+                Balance::from_nominal("-5", WBTC).value
+            );
+        });
+    }
+
     // TODO: Implement max
     #[test]
     #[should_panic(expected = "Not supported")]
@@ -0,0 +1,175 @@
+use crate::{
+    chains::{ChainAccount, ChainHash, ChainId},
+    log,
+    notices::NoticeId,
+    params::EXECUTION_POOL,
+    pipeline::CashPipeline,
+    reason::Reason,
+    require,
+    types::CashPrincipalAmount,
+    Config, Event, ExecutionPools, ExecutionReimbursements, GlobalCashIndex, Module,
+    NoticeExecutionFees, NoticeHashes, PendingExecutionFees,
+};
+use frame_support::storage::{StorageDoubleMap, StorageMap, StorageValue};
+
+/// Hold an execution fee already paid into the pool, until the next extraction notice on the chain.
+pub fn reserve_execution_fee<T: Config>(
+    chain_id: ChainId,
+    payer: ChainAccount,
+    principal: CashPrincipalAmount,
+) -> Result<(), Reason> {
+    let pool = ExecutionPools::get(chain_id).add(principal)?;
+    let pending = PendingExecutionFees::get(chain_id).add(principal)?;
+    ExecutionPools::insert(chain_id, pool);
+    PendingExecutionFees::insert(chain_id, pending);
+    <Module<T>>::deposit_event(Event::ExecutionFeePaid(chain_id, payer, principal));
+    Ok(())
+}
+
+/// Attach the execution fees held for a chain to the extraction notice being dispatched.
+pub fn assign_execution_fee(chain_id: ChainId, notice_id: NoticeId) {
+    let principal = PendingExecutionFees::take(chain_id);
+    if principal != CashPrincipalAmount::ZERO {
+        NoticeExecutionFees::insert(chain_id, notice_id, principal);
+    }
+}
+
+/// Credit the execution fee for a notice, if any, to the account which relayed it to the Starport.
+pub fn handle_notice_relayed<T: Config>(
+    chain_id: ChainId,
+    notice_hash: ChainHash,
+    relayer: ChainAccount,
+) -> Result<(), Reason> {
+    let notice_id = match NoticeHashes::get(notice_hash) {
+        Some(notice_id) => notice_id,
+        None => return Ok(()),
+    };
+    let principal = NoticeExecutionFees::take(chain_id, notice_id);
+    if principal == CashPrincipalAmount::ZERO {
+        return Ok(());
+    }
+
+    log!(
+        "Relayer {:?} earned {:?} for notice {:?}",
+        relayer,
+        principal,
+        notice_id
+    );
+    let pool = ExecutionPools::get(chain_id).sub(principal)?;
+    let owed = ExecutionReimbursements::get(relayer).add(principal)?;
+    ExecutionPools::insert(chain_id, pool);
+    ExecutionReimbursements::insert(relayer, owed);
+    <Module<T>>::deposit_event(Event::ExecutionFeeEarned(
+        relayer, chain_id, notice_id, principal,
+    ));
+    Ok(())
+}
+
+/// Pay out all the execution fees a relayer has earned from the pool.
+pub fn claim_execution_reimbursement<T: Config>(relayer: ChainAccount) -> Result<(), Reason> {
+    let principal = ExecutionReimbursements::get(relayer);
+    require!(
+        principal != CashPrincipalAmount::ZERO,
+        Reason::NoExecutionReimbursement
+    );
+
+    CashPipeline::new()
+        .transfer_cash::<T>(EXECUTION_POOL, relayer, principal)?
+        .commit::<T>();
+    ExecutionReimbursements::remove(relayer);
+
+    let index = GlobalCashIndex::get();
+    <Module<T>>::deposit_event(Event::TransferCash(
+        EXECUTION_POOL,
+        relayer,
+        principal,
+        index,
+    ));
+    <Module<T>>::deposit_event(Event::ExecutionReimbursed(relayer, principal));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    const EXTRACTOR: ChainAccount = ChainAccount::Eth([1; 20]);
+    const RELAYER: ChainAccount = ChainAccount::Eth([2; 20]);
+
+    #[test]
+    fn test_execution_fee_reimbursed_to_relayer() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let notice_id = NoticeId(0, 1);
+            let notice_hash = ChainHash::Eth([3; 32]);
+            let fee = CashPrincipalAmount(1000);
+
+            CashPrincipals::insert(EXECUTION_POOL, CashPrincipal(1000));
+            assert_eq!(
+                reserve_execution_fee::<Test>(chain_id, EXTRACTOR, fee),
+                Ok(())
+            );
+            assert_eq!(ExecutionPools::get(chain_id), fee);
+
+            assign_execution_fee(chain_id, notice_id);
+            assert_eq!(
+                PendingExecutionFees::get(chain_id),
+                CashPrincipalAmount::ZERO
+            );
+            assert_eq!(NoticeExecutionFees::get(chain_id, notice_id), fee);
+
+            NoticeHashes::insert(notice_hash, notice_id);
+            assert_eq!(
+                handle_notice_relayed::<Test>(chain_id, notice_hash, RELAYER),
+                Ok(())
+            );
+            // a replayed receipt earns nothing more
+            assert_eq!(
+                handle_notice_relayed::<Test>(chain_id, notice_hash, RELAYER),
+                Ok(())
+            );
+            assert_eq!(ExecutionPools::get(chain_id), CashPrincipalAmount::ZERO);
+            assert_eq!(ExecutionReimbursements::get(RELAYER), fee);
+
+            assert_eq!(claim_execution_reimbursement::<Test>(RELAYER), Ok(()));
+            assert_eq!(CashPrincipals::get(RELAYER), CashPrincipal(1000));
+            assert_eq!(CashPrincipals::get(EXECUTION_POOL), CashPrincipal(0));
+            assert_eq!(
+                claim_execution_reimbursement::<Test>(RELAYER),
+                Err(Reason::NoExecutionReimbursement)
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::ExecutionFeeEarned(
+                    RELAYER, chain_id, notice_id, fee
+                )))
+            );
+            assert!(events.contains(&mock::Event::pallet_cash(
+                crate::Event::ExecutionReimbursed(RELAYER, fee)
+            )));
+        });
+    }
+
+    #[test]
+    fn test_handle_notice_relayed_without_fee() {
+        new_test_ext().execute_with(|| {
+            let notice_hash = ChainHash::Eth([3; 32]);
+            assert_eq!(
+                handle_notice_relayed::<Test>(ChainId::Eth, notice_hash, RELAYER),
+                Ok(())
+            );
+
+            NoticeHashes::insert(notice_hash, NoticeId(0, 1));
+            assert_eq!(
+                handle_notice_relayed::<Test>(ChainId::Eth, notice_hash, RELAYER),
+                Ok(())
+            );
+            assert_eq!(
+                ExecutionReimbursements::get(RELAYER),
+                CashPrincipalAmount::ZERO
+            );
+        });
+    }
+}
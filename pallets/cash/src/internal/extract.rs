@@ -1,15 +1,213 @@
 use crate::{
-    chains::ChainAccount,
-    internal,
-    params::MIN_TX_VALUE,
+    chains::{ChainAccount, ChainAccountSignature, ChainAsset},
+    core::get_recent_timestamp,
+    internal::{
+        self,
+        activity::record_activity,
+        exec_trx_request::prepend_nonce,
+        nonces::{check_nonce, use_nonce},
+    },
+    params::EXTRACT_DELAY,
     pipeline::CashPipeline,
     reason::Reason,
     require, require_min_tx_value,
-    types::{AssetInfo, AssetQuantity, CashIndex, CashPrincipalAmount},
-    Config, Event, GlobalCashIndex, Module,
+    types::{
+        ActivityKind, AssetAmount, AssetInfo, AssetQuantity, CashIndex, CashOrChainAsset,
+        CashPrincipalAmount, ExtractRateLimit, ExtractRateUsage, Nonce, PendingExtract,
+        PendingExtractId, Timestamp,
+    },
+    AssetPauses, Config, Event, ExtractDelayThresholds, ExtractRateLimits, ExtractRateUsages,
+    GlobalCashIndex, Module, NextPendingExtractId, PendingExtracts,
 };
-use frame_support::storage::StorageValue;
-use our_std::log;
+use frame_support::storage::{IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue};
+use our_std::{debug, log};
+
+/// Set or remove the limit on how much of an asset each account may extract over a window.
+pub fn set_extract_rate_limit<T: Config>(
+    asset: CashOrChainAsset,
+    limit: Option<ExtractRateLimit>,
+) -> Result<(), Reason> {
+    match limit {
+        Some(limit) => {
+            require!(limit.window > 0, Reason::InvalidExtractRateLimit);
+            ExtractRateLimits::insert(asset, limit);
+        }
+        None => ExtractRateLimits::remove(asset),
+    }
+    <Module<T>>::deposit_event(Event::SetExtractRateLimit(asset, limit));
+    Ok(())
+}
+
+/// Return how much of a rate limit remains used at the given time, after release over the window.
+fn get_used_amount(
+    limit: ExtractRateLimit,
+    usage: Option<ExtractRateUsage>,
+    now: Timestamp,
+) -> AssetAmount {
+    match usage {
+        Some(usage) => {
+            let elapsed = now.saturating_sub(usage.timestamp) as AssetAmount;
+            let released = limit.amount.saturating_mul(elapsed) / limit.window as AssetAmount;
+            usage.amount.saturating_sub(released)
+        }
+        None => 0,
+    }
+}
+
+/// Check that extracting an amount of an asset would keep the account within any rate limit.
+pub fn check_extract_rate_limit<T: Config>(
+    account: ChainAccount,
+    asset: CashOrChainAsset,
+    amount: AssetAmount,
+) -> Result<(), Reason> {
+    if let Some(limit) = ExtractRateLimits::get(asset) {
+        let now = get_recent_timestamp::<T>()?;
+        let used = get_used_amount(limit, ExtractRateUsages::get(account, asset), now);
+        require!(
+            used.saturating_add(amount) <= limit.amount,
+            Reason::ExtractRateLimited
+        );
+    }
+    Ok(())
+}
+
+/// Record an extraction against any rate limit on the asset.
+fn use_extract_rate_limit<T: Config>(
+    account: ChainAccount,
+    asset: CashOrChainAsset,
+    amount: AssetAmount,
+) {
+    if let Some(limit) = ExtractRateLimits::get(asset) {
+        // Note: the timestamp was already read successfully when checking the limit
+        let now = get_recent_timestamp::<T>().unwrap_or_default();
+        let used = get_used_amount(limit, ExtractRateUsages::get(account, asset), now);
+        ExtractRateUsages::insert(
+            account,
+            asset,
+            ExtractRateUsage {
+                amount: used.saturating_add(amount),
+                timestamp: now,
+            },
+        );
+    }
+}
+
+/// Set or remove the amount above which a single extraction of an asset is held by the timelock.
+pub fn set_extract_delay_threshold<T: Config>(
+    asset: CashOrChainAsset,
+    threshold: Option<AssetAmount>,
+) -> Result<(), Reason> {
+    match threshold {
+        Some(threshold) => ExtractDelayThresholds::insert(asset, threshold),
+        None => ExtractDelayThresholds::remove(asset),
+    }
+    <Module<T>>::deposit_event(Event::SetExtractDelayThreshold(asset, threshold));
+    Ok(())
+}
+
+/// Return whether an extraction of an amount of an asset must be held by the timelock.
+fn is_delayed(asset: CashOrChainAsset, amount: AssetAmount) -> bool {
+    matches!(ExtractDelayThresholds::get(asset), Some(threshold) if amount > threshold)
+}
+
+/// Hold an extraction until the timelock delay has passed, instead of dispatching its notice.
+fn delay_extract<T: Config>(
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    asset: CashOrChainAsset,
+    amount: AssetAmount,
+) {
+    // Note: the timestamp is always set once blocks are being produced
+    let now = get_recent_timestamp::<T>().unwrap_or_default();
+    let id = NextPendingExtractId::get();
+    let pending = PendingExtract {
+        sender,
+        recipient,
+        asset,
+        amount,
+        release_at: now.saturating_add(EXTRACT_DELAY),
+    };
+    NextPendingExtractId::put(id.saturating_add(1));
+    PendingExtracts::insert(id, pending);
+    <Module<T>>::deposit_event(Event::DelayedExtract(id, pending));
+}
+
+/// Dispatch the notices for any timelocked extractions whose release time has passed.
+pub fn release_pending_extracts<T: Config>(now: Timestamp) {
+    let pending_extracts: Vec<(PendingExtractId, PendingExtract)> = PendingExtracts::iter()
+        .filter(|(_, pending)| pending.release_at <= now)
+        .collect();
+    for (id, pending) in pending_extracts {
+        match pending.asset {
+            CashOrChainAsset::Cash => {
+                dispatch_extract_cash::<T>(
+                    pending.sender,
+                    pending.recipient,
+                    CashPrincipalAmount(pending.amount),
+                );
+            }
+            CashOrChainAsset::ChainAsset(asset) => {
+                match internal::assets::get_quantity::<T>(asset, pending.amount) {
+                    Ok(quantity) => {
+                        dispatch_extract::<T>(asset, pending.sender, pending.recipient, quantity)
+                    }
+                    Err(reason) => {
+                        debug!("Pending extract {} not released: {:?}", id, reason);
+                        continue;
+                    }
+                }
+            }
+        }
+        PendingExtracts::remove(id);
+    }
+}
+
+/// The payload a sender signs, with a nonce prepended, to cancel a timelocked extraction.
+pub fn cancel_extract_payload(id: PendingExtractId) -> Vec<u8> {
+    format!("(CancelExtract {})", id).into_bytes()
+}
+
+/// Recover the signer of a cancellation, checking that it sent the extraction and the nonce may be used.
+pub fn recover_extract_canceler<T: Config>(
+    id: PendingExtractId,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(ChainAccount, PendingExtract), Reason> {
+    let pending = PendingExtracts::get(id).ok_or(Reason::PendingExtractMissing)?;
+    let signer = signature
+        .recover_account(&prepend_nonce(&cancel_extract_payload(id), nonce)[..])
+        .map_err(|_| Reason::SignatureAccountMismatch)?;
+    require!(signer == pending.sender, Reason::NotExtractSender);
+    check_nonce::<T>(signer, nonce)?;
+    Ok((signer, pending))
+}
+
+/// Cancel a timelocked extraction before its release, returning the funds to the sender.
+pub fn cancel_pending_extract<T: Config>(
+    id: PendingExtractId,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(), Reason> {
+    let (sender, pending) = recover_extract_canceler::<T>(id, signature, nonce)?;
+    let pipeline = match pending.asset {
+        CashOrChainAsset::Cash => {
+            CashPipeline::new().lock_cash::<T>(sender, CashPrincipalAmount(pending.amount))?
+        }
+        CashOrChainAsset::ChainAsset(asset) => CashPipeline::new().lock_asset::<T>(
+            sender,
+            asset,
+            internal::assets::get_quantity::<T>(asset, pending.amount)?,
+        )?,
+    };
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    pipeline.commit::<T>();
+    PendingExtracts::remove(id);
+    use_nonce::<T>(sender, nonce);
+    <Module<T>>::deposit_event(Event::CanceledExtract(id));
+    Ok(())
+}
 
 pub fn extract_internal<T: Config>(
     asset: AssetInfo,
@@ -18,24 +216,65 @@ pub fn extract_internal<T: Config>(
     quantity: AssetQuantity,
 ) -> Result<(), Reason> {
     log!("extract_cash_principal_internal");
+    prepare_extract_internal::<T>(CashPipeline::new(), asset, sender, quantity)?.commit::<T>();
+    finish_extract_internal::<T>(asset, sender, recipient, quantity);
+    Ok(())
+}
+
+/// Add the effects of an extraction to a pipeline, without committing them.
+pub fn prepare_extract_internal<T: Config>(
+    pipeline: CashPipeline,
+    asset: AssetInfo,
+    sender: ChainAccount,
+    quantity: AssetQuantity,
+) -> Result<CashPipeline, Reason> {
     require_min_tx_value!(internal::assets::get_value::<T>(quantity)?);
+    require!(!AssetPauses::get(asset.asset).extract, Reason::AssetPaused);
+    check_extract_rate_limit::<T>(
+        sender,
+        CashOrChainAsset::ChainAsset(asset.asset),
+        quantity.value,
+    )?;
 
-    CashPipeline::new()
+    pipeline
         .extract_asset::<T>(sender, asset.asset, quantity)?
+        .check_asset_unpaused::<T>(asset)?
         .check_collateralized::<T>(sender)?
-        .check_sufficient_total_funds::<T>(asset)?
-        .commit::<T>();
+        .check_sufficient_total_funds::<T>(asset)
+}
 
-    internal::notices::dispatch_extraction_notice::<T>(asset.asset, recipient, quantity);
+/// Dispatch the notice and event for an extraction, once its effects are committed.
+pub fn finish_extract_internal<T: Config>(
+    asset: AssetInfo,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    quantity: AssetQuantity,
+) {
+    let extract_asset = CashOrChainAsset::ChainAsset(asset.asset);
+    use_extract_rate_limit::<T>(sender, extract_asset, quantity.value);
+    if is_delayed(extract_asset, quantity.value) {
+        delay_extract::<T>(sender, recipient, extract_asset, quantity.value);
+    } else {
+        dispatch_extract::<T>(asset.asset, sender, recipient, quantity);
+    }
+}
 
-    <Module<T>>::deposit_event(Event::Extract(
-        asset.asset,
+fn dispatch_extract<T: Config>(
+    asset: ChainAsset,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    quantity: AssetQuantity,
+) {
+    internal::notices::dispatch_extraction_notice::<T>(asset, recipient, quantity);
+    record_activity::<T>(
         sender,
-        recipient,
+        ActivityKind::Extract,
+        CashOrChainAsset::ChainAsset(asset),
         quantity.value,
-    ));
+        Some(recipient),
+    );
 
-    Ok(())
+    <Module<T>>::deposit_event(Event::Extract(asset, sender, recipient, quantity.value));
 }
 
 pub fn extract_cash_principal_internal<T: Config>(
@@ -43,20 +282,60 @@ pub fn extract_cash_principal_internal<T: Config>(
     recipient: ChainAccount,
     principal: CashPrincipalAmount,
 ) -> Result<(), Reason> {
+    prepare_extract_cash_principal_internal::<T>(CashPipeline::new(), sender, principal)?
+        .commit::<T>();
+    finish_extract_cash_principal_internal::<T>(sender, recipient, principal);
+    Ok(())
+}
+
+/// Add the effects of a CASH extraction to a pipeline, without committing them.
+pub fn prepare_extract_cash_principal_internal<T: Config>(
+    pipeline: CashPipeline,
+    sender: ChainAccount,
+    principal: CashPrincipalAmount,
+) -> Result<CashPipeline, Reason> {
     let index: CashIndex = GlobalCashIndex::get();
     let amount = index.cash_quantity(principal)?;
     require_min_tx_value!(internal::assets::get_value::<T>(amount)?);
+    check_extract_rate_limit::<T>(sender, CashOrChainAsset::Cash, amount.value)?;
 
-    CashPipeline::new()
+    pipeline
         .extract_cash::<T>(sender, principal)?
-        .check_collateralized::<T>(sender)?
-        .commit::<T>();
+        .check_collateralized::<T>(sender)
+}
 
+/// Dispatch the notice and event for a CASH extraction, once its effects are committed.
+pub fn finish_extract_cash_principal_internal<T: Config>(
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    principal: CashPrincipalAmount,
+) {
+    let index: CashIndex = GlobalCashIndex::get();
+    let amount = index.cash_quantity(principal).map_or(0, |q| q.value);
+    use_extract_rate_limit::<T>(sender, CashOrChainAsset::Cash, amount);
+    if is_delayed(CashOrChainAsset::Cash, amount) {
+        delay_extract::<T>(sender, recipient, CashOrChainAsset::Cash, principal.0);
+    } else {
+        dispatch_extract_cash::<T>(sender, recipient, principal);
+    }
+}
+
+fn dispatch_extract_cash<T: Config>(
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    principal: CashPrincipalAmount,
+) {
+    let index: CashIndex = GlobalCashIndex::get(); // Grab cash index just for event
     internal::notices::dispatch_cash_extraction_notice::<T>(recipient, principal);
+    record_activity::<T>(
+        sender,
+        ActivityKind::Extract,
+        CashOrChainAsset::Cash,
+        principal.0,
+        Some(recipient),
+    );
 
     <Module<T>>::deposit_event(Event::ExtractCash(sender, recipient, principal, index));
-
-    Ok(())
 }
 
 #[cfg(test)]
@@ -84,6 +363,271 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_extract_asset_paused() -> Result<(), Reason> {
+        let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
+        let max = ChainAccount::from_str("Eth:0x7f89077b122afaaf6ab50aa12e9cb46bb9a058c4")?;
+
+        new_test_ext().execute_with(|| {
+            Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000.19").value);
+            SupportedAssets::insert(&Eth, eth);
+            AssetBalances::insert(&Eth, &jared, Balance::from_nominal("1", ETH).value);
+            TotalSupplyAssets::insert(&Eth, Quantity::from_nominal("1", ETH).value);
+            AssetPauses::insert(
+                &Eth,
+                AssetPauseFlags {
+                    extract: true,
+                    ..AssetPauseFlags::default()
+                },
+            );
+
+            assert_err!(
+                super::extract_internal::<Test>(eth, jared, max, qty!("1", ETH)),
+                Reason::AssetPaused
+            );
+
+            AssetPauses::remove(&Eth);
+            assert_ok!(super::extract_internal::<Test>(
+                eth,
+                jared,
+                max,
+                qty!("1", ETH)
+            ));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_set_extract_rate_limit() {
+        new_test_ext().execute_with(|| {
+            let limit = ExtractRateLimit {
+                amount: Quantity::from_nominal("1", ETH).value,
+                window: 1000,
+            };
+            assert_err!(
+                super::set_extract_rate_limit::<Test>(
+                    CashOrChainAsset::ChainAsset(Eth),
+                    Some(ExtractRateLimit { window: 0, ..limit })
+                ),
+                Reason::InvalidExtractRateLimit
+            );
+            assert_ok!(super::set_extract_rate_limit::<Test>(
+                CashOrChainAsset::ChainAsset(Eth),
+                Some(limit)
+            ));
+            assert_eq!(
+                ExtractRateLimits::get(CashOrChainAsset::ChainAsset(Eth)),
+                Some(limit)
+            );
+            assert_ok!(super::set_extract_rate_limit::<Test>(
+                CashOrChainAsset::ChainAsset(Eth),
+                None
+            ));
+            assert_eq!(
+                ExtractRateLimits::get(CashOrChainAsset::ChainAsset(Eth)),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn test_extract_asset_rate_limited() -> Result<(), Reason> {
+        let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
+        let max = ChainAccount::from_str("Eth:0x7f89077b122afaaf6ab50aa12e9cb46bb9a058c4")?;
+
+        new_test_ext().execute_with(|| {
+            Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000.19").value);
+            SupportedAssets::insert(&Eth, eth);
+            AssetBalances::insert(&Eth, &jared, Balance::from_nominal("5", ETH).value);
+            TotalSupplyAssets::insert(&Eth, Quantity::from_nominal("5", ETH).value);
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1000);
+            assert_ok!(super::set_extract_rate_limit::<Test>(
+                CashOrChainAsset::ChainAsset(Eth),
+                Some(ExtractRateLimit {
+                    amount: Quantity::from_nominal("2", ETH).value,
+                    window: 1000,
+                })
+            ));
+
+            assert_err!(
+                super::extract_internal::<Test>(eth, jared, max, qty!("3", ETH)),
+                Reason::ExtractRateLimited
+            );
+            assert_ok!(super::extract_internal::<Test>(
+                eth,
+                jared,
+                max,
+                qty!("2", ETH)
+            ));
+            assert_eq!(
+                ExtractRateUsages::get(jared, CashOrChainAsset::ChainAsset(Eth)),
+                Some(ExtractRateUsage {
+                    amount: Quantity::from_nominal("2", ETH).value,
+                    timestamp: 1000,
+                })
+            );
+            assert_err!(
+                super::extract_internal::<Test>(eth, jared, max, qty!("1", ETH)),
+                Reason::ExtractRateLimited
+            );
+
+            // Half the window releases half the limit
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1500);
+            assert_err!(
+                super::extract_internal::<Test>(eth, jared, max, qty!("1.5", ETH)),
+                Reason::ExtractRateLimited
+            );
+            assert_ok!(super::extract_internal::<Test>(
+                eth,
+                jared,
+                max,
+                qty!("1", ETH)
+            ));
+            assert_eq!(
+                ExtractRateUsages::get(jared, CashOrChainAsset::ChainAsset(Eth)),
+                Some(ExtractRateUsage {
+                    amount: Quantity::from_nominal("2", ETH).value,
+                    timestamp: 1500,
+                })
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_extract_asset_delayed() -> Result<(), Reason> {
+        let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
+        let max = ChainAccount::from_str("Eth:0x7f89077b122afaaf6ab50aa12e9cb46bb9a058c4")?;
+
+        new_test_ext().execute_with(|| {
+            Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000.19").value);
+            SupportedAssets::insert(&Eth, eth);
+            AssetBalances::insert(&Eth, &jared, Balance::from_nominal("5", ETH).value);
+            TotalSupplyAssets::insert(&Eth, Quantity::from_nominal("5", ETH).value);
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1000);
+            assert_ok!(super::set_extract_delay_threshold::<Test>(
+                CashOrChainAsset::ChainAsset(Eth),
+                Some(Quantity::from_nominal("1", ETH).value)
+            ));
+
+            assert_ok!(super::extract_internal::<Test>(
+                eth,
+                jared,
+                max,
+                qty!("1", ETH)
+            ));
+            assert_eq!(
+                LatestNotice::get(ChainId::Eth).map(|n| n.0),
+                Some(NoticeId(0, 1))
+            );
+
+            assert_ok!(super::extract_internal::<Test>(
+                eth,
+                jared,
+                max,
+                qty!("2", ETH)
+            ));
+            let pending = PendingExtract {
+                sender: jared,
+                recipient: max,
+                asset: CashOrChainAsset::ChainAsset(Eth),
+                amount: Quantity::from_nominal("2", ETH).value,
+                release_at: 1000 + EXTRACT_DELAY,
+            };
+            assert_eq!(PendingExtracts::get(0), Some(pending));
+            assert_eq!(
+                AssetBalances::get(&Eth, &jared),
+                Balance::from_nominal("2", ETH).value
+            );
+            assert_eq!(
+                LatestNotice::get(ChainId::Eth).map(|n| n.0),
+                Some(NoticeId(0, 1))
+            );
+
+            super::release_pending_extracts::<Test>(999 + EXTRACT_DELAY);
+            assert_eq!(PendingExtracts::get(0), Some(pending));
+
+            super::release_pending_extracts::<Test>(1000 + EXTRACT_DELAY);
+            assert_eq!(PendingExtracts::get(0), None);
+            assert_eq!(
+                LatestNotice::get(ChainId::Eth).map(|n| n.0),
+                Some(NoticeId(0, 2))
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::DelayedExtract(
+                    0, pending
+                )))
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_cancel_pending_extract() {
+        new_test_ext().execute_with(|| {
+            let sender_key = <Ethereum as Chain>::signer_address().unwrap();
+            let sender = ChainAccount::Eth(sender_key);
+            let message = super::prepend_nonce(&super::cancel_extract_payload(0), 0);
+            let full_message: Vec<u8> = [
+                format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes(),
+                &message[..],
+            ]
+            .concat();
+            let eth_key_id =
+                runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+            let signature = ChainAccountSignature::Eth(
+                sender_key,
+                runtime_interfaces::keyring_interface::sign_one(full_message, eth_key_id).unwrap(),
+            );
+
+            Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "2000.19").value);
+            SupportedAssets::insert(&Eth, eth);
+            AssetBalances::insert(&Eth, &sender, Balance::from_nominal("5", ETH).value);
+            TotalSupplyAssets::insert(&Eth, Quantity::from_nominal("5", ETH).value);
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1000);
+            assert_ok!(super::set_extract_delay_threshold::<Test>(
+                CashOrChainAsset::ChainAsset(Eth),
+                Some(0)
+            ));
+
+            assert_err!(
+                super::cancel_pending_extract::<Test>(0, signature, 0),
+                Reason::PendingExtractMissing
+            );
+            assert_ok!(super::extract_internal::<Test>(
+                eth,
+                sender,
+                sender,
+                qty!("2", ETH)
+            ));
+            assert_eq!(
+                AssetBalances::get(&Eth, &sender),
+                Balance::from_nominal("3", ETH).value
+            );
+
+            assert_ok!(super::cancel_pending_extract::<Test>(0, signature, 0));
+            assert_eq!(PendingExtracts::get(0), None);
+            assert_eq!(
+                AssetBalances::get(&Eth, &sender),
+                Balance::from_nominal("5", ETH).value
+            );
+            assert_eq!(
+                TotalSupplyAssets::get(&Eth),
+                Quantity::from_nominal("5", ETH).value
+            );
+            assert_eq!(LatestNotice::get(ChainId::Eth), None);
+            assert_err!(
+                super::cancel_pending_extract::<Test>(0, signature, 0),
+                Reason::PendingExtractMissing
+            );
+        })
+    }
+
     #[test]
     fn test_extract_internal_min_value() -> Result<(), Reason> {
         let asset = ChainAsset::Eth([238; 20]);
@@ -0,0 +1,215 @@
+use crate::{
+    chains::{ChainBlockEvent, ChainId, FailedEventId},
+    core, debug, log,
+    params::{FAILED_EVENT_RETRIES_PER_BLOCK, MAX_FAILED_EVENTS},
+    reason::Reason,
+    Config, Event, FailedEvents, Module, NextFailedEventId,
+};
+use frame_support::storage::{IterableStorageMap, StorageMap};
+use our_std::cmp::min;
+
+/// Queue an event which failed to process, so that it may be retried later.
+/// If the queue is full, the event at the front, retried longest ago, is dropped to make room.
+pub fn queue_failed_event<T: Config>(event: ChainBlockEvent) -> FailedEventId {
+    let chain_id = event.chain_id();
+    let event_id = NextFailedEventId::get(chain_id);
+    let mut queue = FailedEvents::get(chain_id);
+    let dropped: Vec<(FailedEventId, ChainBlockEvent)> = if queue.len() >= MAX_FAILED_EVENTS {
+        queue.drain(..queue.len() + 1 - MAX_FAILED_EVENTS).collect()
+    } else {
+        vec![]
+    };
+    queue.push((event_id, event));
+
+    NextFailedEventId::insert(chain_id, event_id.saturating_add(1));
+    FailedEvents::insert(chain_id, queue);
+    for (dropped_id, dropped_event) in dropped {
+        log!(
+            "Dropping failed event {:?} {}: {:?}",
+            chain_id,
+            dropped_id,
+            dropped_event
+        );
+        <Module<T>>::deposit_event(Event::DroppedFailedEvent(
+            chain_id,
+            dropped_id,
+            dropped_event,
+        ));
+    }
+    <Module<T>>::deposit_event(Event::QueuedFailedEvent(chain_id, event_id));
+    event_id
+}
+
+/// Retry a particular failed event, removing it from the queue only if it succeeds.
+pub fn retry_failed_event<T: Config>(
+    chain_id: ChainId,
+    event_id: FailedEventId,
+) -> Result<(), Reason> {
+    let mut queue = FailedEvents::get(chain_id);
+    let position = queue
+        .iter()
+        .position(|(id, _)| *id == event_id)
+        .ok_or(Reason::FailedEventMissing)?;
+    let (_, event) = queue[position].clone();
+    log!(
+        "Retrying failed event {:?} {}: {:?}",
+        chain_id,
+        event_id,
+        event
+    );
+    core::apply_chain_event_internal::<T>(&event)?;
+    queue.remove(position);
+    FailedEvents::insert(chain_id, queue);
+    processed_failed_event::<T>(chain_id, event_id, event);
+    Ok(())
+}
+
+/// Retry a bounded number of failed events, rotating any which fail again to the back of the queue.
+pub fn retry_failed_events<T: Config>() -> Result<(), Reason> {
+    let mut budget = FAILED_EVENT_RETRIES_PER_BLOCK;
+    let queues: Vec<(ChainId, Vec<(FailedEventId, ChainBlockEvent)>)> =
+        FailedEvents::iter().collect();
+    for (chain_id, mut queue) in queues {
+        let attempts = min(budget, queue.len());
+        for _ in 0..attempts {
+            let (event_id, event) = queue.remove(0);
+            match core::apply_chain_event_internal::<T>(&event) {
+                Ok(()) => processed_failed_event::<T>(chain_id, event_id, event),
+                Err(reason) => {
+                    debug!(
+                        "Failed event {:?} {} still failing: {:?}",
+                        chain_id, event_id, reason
+                    );
+                    queue.push((event_id, event));
+                }
+            }
+        }
+        budget -= attempts;
+        if queue.is_empty() {
+            FailedEvents::remove(chain_id);
+        } else {
+            FailedEvents::insert(chain_id, queue);
+        }
+    }
+    Ok(())
+}
+
+fn processed_failed_event<T: Config>(
+    chain_id: ChainId,
+    event_id: FailedEventId,
+    event: ChainBlockEvent,
+) {
+    <Module<T>>::deposit_event(Event::ProcessedChainBlockEvent(event));
+    <Module<T>>::deposit_event(Event::RetriedFailedEvent(chain_id, event_id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn lock_event(asset: [u8; 20]) -> ChainBlockEvent {
+        ChainBlockEvent::Eth(
+            2,
+            ethereum_client::EthereumEvent::Lock {
+                asset,
+                sender: [3; 20],
+                chain: String::from("ETH"),
+                recipient: [4; 32],
+                amount: qty!("1", ETH).value,
+                reference: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_retry_failed_event() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            pallet_oracle::Prices::insert(
+                ETH.ticker,
+                Price::from_nominal(ETH.ticker, "2000.00").value,
+            );
+            let unsupported = lock_event([100; 20]);
+
+            assert_eq!(queue_failed_event::<Test>(unsupported.clone()), 0);
+            assert_eq!(queue_failed_event::<Test>(unsupported.clone()), 1);
+            assert_eq!(
+                retry_failed_event::<Test>(ChainId::Eth, 2),
+                Err(Reason::FailedEventMissing)
+            );
+
+            // still unsupported, so the event stays queued
+            assert!(retry_failed_event::<Test>(ChainId::Eth, 1).is_err());
+            assert_eq!(
+                FailedEvents::get(ChainId::Eth),
+                vec![(0, unsupported.clone()), (1, unsupported.clone())]
+            );
+
+            let eth_info = SupportedAssets::get(Eth).unwrap();
+            SupportedAssets::insert(
+                ChainAsset::Eth([100; 20]),
+                AssetInfo {
+                    asset: ChainAsset::Eth([100; 20]),
+                    ..eth_info
+                },
+            );
+            assert_eq!(retry_failed_event::<Test>(ChainId::Eth, 1), Ok(()));
+            assert_eq!(FailedEvents::get(ChainId::Eth), vec![(0, unsupported)]);
+        });
+    }
+
+    #[test]
+    fn test_retry_failed_events_rotates_queue() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            pallet_oracle::Prices::insert(
+                ETH.ticker,
+                Price::from_nominal(ETH.ticker, "2000.00").value,
+            );
+            let supported = lock_event([238; 20]);
+            let unsupported = lock_event([100; 20]);
+
+            queue_failed_event::<Test>(unsupported.clone());
+            queue_failed_event::<Test>(supported);
+
+            assert_eq!(retry_failed_events::<Test>(), Ok(()));
+            assert_eq!(FailedEvents::get(ChainId::Eth), vec![(0, unsupported)]);
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::RetriedFailedEvent(
+                    ChainId::Eth,
+                    1
+                )))
+            );
+        });
+    }
+
+    #[test]
+    fn test_queue_failed_event_drops_when_full() {
+        new_test_ext().execute_with(|| {
+            let event = lock_event([100; 20]);
+            for _ in 0..MAX_FAILED_EVENTS {
+                queue_failed_event::<Test>(event.clone());
+            }
+            assert_eq!(FailedEvents::get(ChainId::Eth).len(), MAX_FAILED_EVENTS);
+
+            let event_id = queue_failed_event::<Test>(event.clone());
+            assert_eq!(event_id, MAX_FAILED_EVENTS as FailedEventId);
+            let queue = FailedEvents::get(ChainId::Eth);
+            assert_eq!(queue.len(), MAX_FAILED_EVENTS);
+            assert_eq!(queue[0].0, 1);
+            assert_eq!(queue[MAX_FAILED_EVENTS - 1].0, event_id);
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::DroppedFailedEvent(
+                    ChainId::Eth,
+                    0,
+                    event
+                )))
+            );
+        });
+    }
+}
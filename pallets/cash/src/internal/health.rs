@@ -0,0 +1,177 @@
+use crate::{
+    chains::{ChainBlockNumber, ChainId},
+    core::{get_event_queue, get_recent_timestamp},
+    debug,
+    events::fetch_chain_head,
+    has_requisite_signatures,
+    notices::NoticeState,
+    reason::Reason,
+    types::{ChainHealth, ProtocolMetrics},
+    Config, LastProcessedBlock, NoticeStates, PendingChainBlocks, Starports, Validators,
+};
+use frame_support::storage::{IterableStorageDoubleMap, IterableStorageMap, StorageMap};
+use our_std::vec::Vec;
+use sp_runtime::offchain::storage::StorageValueRef;
+
+fn chain_head_key(chain_id: ChainId) -> String {
+    format!("cash::chain_head::{:?}", chain_id)
+}
+
+/// The underlying chains which have a Starport, in order.
+fn starport_chains() -> Vec<ChainId> {
+    let mut chain_ids: Vec<ChainId> = Starports::iter().map(|(chain_id, _)| chain_id).collect();
+    chain_ids.sort();
+    chain_ids
+}
+
+/// Remember the latest block number of each underlying chain, so the health of ingestion may be queried.
+pub fn track_chain_heads<T: Config>() -> Result<(), Reason> {
+    for chain_id in starport_chains() {
+        let chain_head = fetch_chain_head(chain_id)?;
+        debug!("Chain head for {:?}: {}", chain_id, chain_head);
+        StorageValueRef::persistent(chain_head_key(chain_id).as_bytes()).set(&chain_head);
+    }
+    Ok(())
+}
+
+/// Return the health of each underlying chain, as last seen by this node.
+pub fn get_chain_health<T: Config>() -> Result<Vec<ChainHealth>, Reason> {
+    let mut pending_notices: Vec<(ChainId, u32)> = Vec::new();
+    for (chain_id, _notice_id, notice_state) in NoticeStates::iter() {
+        if let NoticeState::Pending { .. } = notice_state {
+            match pending_notices.iter_mut().find(|(id, _)| *id == chain_id) {
+                Some((_, count)) => *count += 1,
+                None => pending_notices.push((chain_id, 1)),
+            }
+        }
+    }
+
+    let mut health = Vec::new();
+    for chain_id in starport_chains() {
+        let chain_head = match StorageValueRef::persistent(chain_head_key(chain_id).as_bytes())
+            .get::<ChainBlockNumber>()
+        {
+            Some(Some(chain_head)) => Some(chain_head),
+            _ => None,
+        };
+        health.push(ChainHealth {
+            chain_id,
+            last_processed_block: LastProcessedBlock::get(chain_id).map(|block| block.number()),
+            chain_head,
+            ingression_queue_depth: get_event_queue::<T>(chain_id)?.len() as u32,
+            pending_notices: pending_notices
+                .iter()
+                .find(|(id, _)| *id == chain_id)
+                .map_or(0, |(_, count)| *count),
+        });
+    }
+    Ok(health)
+}
+
+/// Return the current backlogs of ingression, notices and prices, to be exported as metrics.
+pub fn get_protocol_metrics<T: Config>() -> Result<ProtocolMetrics, Reason> {
+    let validators: Vec<_> = Validators::iter().map(|v| v.1).collect();
+    let mut unsigned_notices: Vec<(ChainId, u32)> = Vec::new();
+    for (chain_id, _notice_id, notice_state) in NoticeStates::iter() {
+        if let NoticeState::Pending { .. } = notice_state {
+            if !has_requisite_signatures(notice_state, &validators) {
+                match unsigned_notices.iter_mut().find(|(id, _)| *id == chain_id) {
+                    Some((_, count)) => *count += 1,
+                    None => unsigned_notices.push((chain_id, 1)),
+                }
+            }
+        }
+    }
+
+    let mut ingression_queue_depths = Vec::new();
+    let mut pending_block_support = Vec::new();
+    for chain_id in starport_chains() {
+        ingression_queue_depths.push((chain_id, get_event_queue::<T>(chain_id)?.len() as u32));
+        for tally in PendingChainBlocks::get(chain_id) {
+            pending_block_support.push((
+                chain_id,
+                tally.block.number(),
+                tally.support.len() as u32,
+            ));
+        }
+    }
+
+    let now = get_recent_timestamp::<T>()?;
+    let mut price_ages: Vec<_> = pallet_oracle::PriceTimes::iter()
+        .map(|(ticker, time)| (ticker, now.saturating_sub(time)))
+        .collect();
+    price_ages.sort();
+
+    Ok(ProtocolMetrics {
+        ingression_queue_depths,
+        pending_block_support,
+        unsigned_notices,
+        price_ages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{chains::ChainSignatureList, notices::NoticeId, tests::*};
+
+    #[test]
+    fn test_get_chain_health() {
+        let calls = vec![gen_mock_call_block_number(9)];
+        let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+
+        t.execute_with(|| {
+            initialize_storage();
+            let pending = NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Eth(vec![]),
+            };
+            NoticeStates::insert(ChainId::Eth, NoticeId(0, 1), pending.clone());
+            NoticeStates::insert(ChainId::Eth, NoticeId(0, 2), pending);
+            NoticeStates::insert(ChainId::Eth, NoticeId(0, 3), NoticeState::Executed);
+
+            let mut health = ChainHealth {
+                chain_id: ChainId::Eth,
+                last_processed_block: Some(1),
+                chain_head: None,
+                ingression_queue_depth: 0,
+                pending_notices: 2,
+            };
+            assert_eq!(get_chain_health::<Test>(), Ok(vec![health]));
+
+            assert_eq!(track_chain_heads::<Test>(), Ok(()));
+            health.chain_head = Some(9);
+            assert_eq!(get_chain_health::<Test>(), Ok(vec![health]));
+        });
+    }
+
+    #[test]
+    fn test_get_protocol_metrics() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            NoticeStates::insert(
+                ChainId::Eth,
+                NoticeId(0, 1),
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Eth(vec![]),
+                },
+            );
+            let mut block = premined_block();
+            block.number = 2;
+            PendingChainBlocks::insert(
+                ChainId::Eth,
+                vec![ChainBlockTally::new(ChainBlock::Eth(block), &val_a())],
+            );
+            pallet_oracle::PriceTimes::insert(ETH.ticker, 200);
+
+            assert_eq!(
+                get_protocol_metrics::<Test>(),
+                Ok(ProtocolMetrics {
+                    ingression_queue_depths: vec![(ChainId::Eth, 0)],
+                    pending_block_support: vec![(ChainId::Eth, 2, 1)],
+                    unsigned_notices: vec![(ChainId::Eth, 1)],
+                    price_ages: vec![(ETH.ticker, 300)],
+                })
+            );
+        });
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{chains::ChainId, log, reason::Reason, Config, Event, IngestionPaused, Module};
+use frame_support::storage::StorageMap;
+use our_std::log::targets::EVENTS;
+
+/// Determine whether ingestion of events from the given chain is currently paused.
+pub fn is_ingestion_paused(chain_id: ChainId) -> bool {
+    IngestionPaused::get(chain_id)
+}
+
+/// Stop accepting blocks from the given chain until ingestion is resumed.
+pub fn pause_chain_ingestion<T: Config>(chain_id: ChainId) -> Result<(), Reason> {
+    log!(target: EVENTS, "Pausing ingestion for chain {:?}", chain_id);
+    IngestionPaused::insert(chain_id, true);
+    <Module<T>>::deposit_event(Event::ChainIngestionPaused(chain_id));
+    Ok(())
+}
+
+/// Start accepting blocks from the given chain again.
+pub fn resume_chain_ingestion<T: Config>(chain_id: ChainId) -> Result<(), Reason> {
+    log!(target: EVENTS, "Resuming ingestion for chain {:?}", chain_id);
+    IngestionPaused::remove(chain_id);
+    <Module<T>>::deposit_event(Event::ChainIngestionResumed(chain_id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_pause_and_resume_chain_ingestion() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(is_ingestion_paused(ChainId::Eth), false);
+
+            assert_eq!(pause_chain_ingestion::<Test>(ChainId::Eth), Ok(()));
+            assert_eq!(is_ingestion_paused(ChainId::Eth), true);
+            assert_eq!(is_ingestion_paused(ChainId::Matic), false);
+
+            assert_eq!(resume_chain_ingestion::<Test>(ChainId::Eth), Ok(()));
+            assert_eq!(is_ingestion_paused(ChainId::Eth), false);
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert_eq!(
+                events,
+                vec![
+                    mock::Event::pallet_cash(crate::Event::ChainIngestionPaused(ChainId::Eth)),
+                    mock::Event::pallet_cash(crate::Event::ChainIngestionResumed(ChainId::Eth)),
+                ]
+            );
+        });
+    }
+}
@@ -1,74 +1,56 @@
 use crate::{
-    chains::ChainAsset,
     core::get_recent_timestamp,
-    factor::Factor,
     internal,
-    params::GATEWAY_VOID,
+    params::{GATEWAY_VOID, MAX_RATE_HISTORY},
     reason::Reason,
-    types::{AssetIndex, CashPrincipalAmount, Quantity, Timestamp, CASH},
-    BorrowIndices, CashPrincipals, CashYield, CashYieldNext, Config, Event, GlobalCashIndex,
-    LastBlockTimestamp, LastMinerSharePrincipal, LastYieldCashIndex, LastYieldTimestamp,
-    MinerCumulative, Module, SupplyIndices, SupportedAssets, TotalBorrowAssets, TotalCashPrincipal,
-    TotalSupplyAssets,
+    require,
+    types::{AccrualPoint, CashPrincipalAmount, Timestamp},
+    Author, CashPrincipals, CashYield, CashYieldNext, Config, Event, GlobalCashIndex,
+    InsuranceFund, LastBlockTimestamp, LastMinerSharePrincipal, LastYieldCashIndex,
+    LastYieldTimestamp, MinerCumulative, Module, SessionInterface, TotalCashPrincipal,
+    UnsettledAccruals, WithheldValidators,
 };
-use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
-
-/// Block initialization hook
-pub fn on_initialize<T: Config>() -> Result<(), Reason> {
-    initialize_block::<T>(get_recent_timestamp::<T>()?)
+use frame_support::storage::{StorageMap, StorageValue};
+
+/// Block initialization hook, returning the number of assets swept.
+pub fn on_initialize<T: Config>() -> Result<u32, Reason> {
+    let now = get_recent_timestamp::<T>()?;
+    let swept = initialize_block::<T>(now)?;
+    Author::set(<T>::SessionInterface::block_author());
+    internal::events::expire_chain_tallies::<T>(now);
+    internal::failed_events::retry_failed_events::<T>()?;
+    internal::extract::release_pending_extracts::<T>(now);
+    internal::bonding::release_unbondings::<T>(now);
+    Ok(swept)
 }
 
-/// Initialize block, given now
-pub fn initialize_block<T: Config>(now: Timestamp) -> Result<(), Reason> {
+/// Initialize block, given now, returning the number of assets swept.
+/// Assets are otherwise accrued lazily as they are touched, here we just settle what they generated/paid.
+pub fn initialize_block<T: Config>(now: Timestamp) -> Result<u32, Reason> {
     let last_yield_timestamp = LastYieldTimestamp::get();
     let last_block_timestamp = LastBlockTimestamp::get();
 
     // If this is the first block, no interest is accrued, just record the timestamp
     if last_block_timestamp == 0 {
         LastBlockTimestamp::put(now);
-        return Ok(());
+        return Ok(0);
     }
 
-    // Iterate through listed assets, summing the CASH principal they generated/paid last block
-    let dt_since_last_block = now
-        .checked_sub(last_block_timestamp)
-        .ok_or(Reason::TimeTravelNotAllowed)?;
+    require!(now >= last_block_timestamp, Reason::TimeTravelNotAllowed);
     let dt_since_last_yield = now
         .checked_sub(last_yield_timestamp)
         .ok_or(Reason::TimeTravelNotAllowed)?;
-    let mut cash_principal_supply_increase = CashPrincipalAmount::ZERO;
-    let mut cash_principal_borrow_increase = CashPrincipalAmount::ZERO;
 
-    let last_block_cash_index = GlobalCashIndex::get();
+    // Periodically bring idle assets current, before settling the CASH principal all assets generated/paid
+    let swept = internal::accrual::sweep_idle_assets::<T>(now)?;
+    let (
+        cash_principal_borrow_increase,
+        cash_principal_supply_increase,
+        cash_principal_reserve_increase,
+    ) = UnsettledAccruals::get();
+
     let last_yield_cash_index = LastYieldCashIndex::get();
     let cash_yield = CashYield::get();
-    let price_cash = internal::assets::get_price_or_zero::<T>(CASH);
-
-    let mut asset_updates: Vec<(ChainAsset, AssetIndex, AssetIndex)> = Vec::new();
-    for (asset, asset_info) in SupportedAssets::iter() {
-        let (asset_cost, asset_yield) = internal::assets::get_rates::<T>(asset)?;
-        let asset_units = asset_info.units();
-        let price_asset = internal::assets::get_price_or_zero::<T>(asset_units);
-        let price_ratio = Factor::ratio(price_asset, price_cash)?;
-        let cash_borrow_principal_per_asset = last_block_cash_index
-            .cash_principal_per_asset(asset_cost.simple(dt_since_last_block)?, price_ratio)?;
-        let cash_hold_principal_per_asset = last_block_cash_index
-            .cash_principal_per_asset(asset_yield.simple(dt_since_last_block)?, price_ratio)?;
-
-        let supply_index = SupplyIndices::get(&asset);
-        let borrow_index = BorrowIndices::get(&asset);
-        let supply_index_new = supply_index.increment(cash_hold_principal_per_asset)?;
-        let borrow_index_new = borrow_index.increment(cash_borrow_principal_per_asset)?;
-
-        let supply_asset = Quantity::new(TotalSupplyAssets::get(asset), asset_units);
-        let borrow_asset = Quantity::new(TotalBorrowAssets::get(asset), asset_units);
-        cash_principal_supply_increase = cash_principal_supply_increase
-            .add(cash_hold_principal_per_asset.cash_principal_amount(supply_asset)?)?;
-        cash_principal_borrow_increase = cash_principal_borrow_increase
-            .add(cash_borrow_principal_per_asset.cash_principal_amount(borrow_asset)?)?;
-
-        asset_updates.push((asset.clone(), supply_index_new, borrow_index_new));
-    }
 
     // Pay miners and update the CASH interest index on CASH itself
     let total_cash_principal = TotalCashPrincipal::get();
@@ -76,27 +58,43 @@ pub fn initialize_block<T: Config>(now: Timestamp) -> Result<(), Reason> {
     let increment = cash_yield.compound(dt_since_last_yield)?;
     let cash_index_new = last_yield_cash_index.increment(increment.into())?;
     let total_cash_principal_new = total_cash_principal.add(cash_principal_borrow_increase)?;
-    let miner_share_principal =
-        cash_principal_borrow_increase.sub(cash_principal_supply_increase)?;
+    let miner_spread_principal = cash_principal_borrow_increase
+        .sub(cash_principal_supply_increase)?
+        .sub(cash_principal_reserve_increase)?;
+    let insurance_increase =
+        internal::insurance::get_insurance_increase::<T>(miner_spread_principal)?;
+    let miner_share_principal = miner_spread_principal.sub(insurance_increase)?;
+    let insurance_fund = InsuranceFund::get().add(insurance_increase)?;
 
     let last_miner = internal::miner::get_some_miner::<T>(); // Miner not yet set for this block, so this is "last miner"
     let last_miner_share_principal = LastMinerSharePrincipal::get();
+
+    // Withhold the earnings of a block authored by a penalized validator, whatever it mined to, into the insurance fund
+    // Author not yet set for this block either, so this is the "last author"
+    let last_miner_withheld = Author::get().map_or(false, |author| WithheldValidators::get(author));
+    let (last_miner_paid_principal, insurance_fund_new) = if last_miner_withheld {
+        (
+            CashPrincipalAmount::ZERO,
+            insurance_fund.add(last_miner_share_principal)?,
+        )
+    } else {
+        (last_miner_share_principal, insurance_fund)
+    };
     let miner_cash_principal_old = CashPrincipals::get(&last_miner);
     let miner_cash_principal_new =
-        miner_cash_principal_old.add_amount(last_miner_share_principal)?;
+        miner_cash_principal_old.add_amount(last_miner_paid_principal)?;
 
     // Auxiliary cumulative values
-    let miner_cumulative = MinerCumulative::get(&last_miner).add(last_miner_share_principal)?;
+    let miner_cumulative = MinerCumulative::get(&last_miner).add(last_miner_paid_principal)?;
+    let last_miner_withheld_principal =
+        last_miner_share_principal.sub(last_miner_paid_principal)?;
 
     // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
 
     CashPrincipals::insert(last_miner, miner_cash_principal_new);
 
-    for (asset, new_supply_index, new_borrow_index) in asset_updates.drain(..) {
-        SupplyIndices::insert(asset.clone(), new_supply_index);
-        BorrowIndices::insert(asset, new_borrow_index);
-    }
-
+    UnsettledAccruals::kill();
+    InsuranceFund::put(insurance_fund_new);
     GlobalCashIndex::put(cash_index_new);
     TotalCashPrincipal::put(total_cash_principal_new);
     LastMinerSharePrincipal::put(miner_share_principal);
@@ -104,6 +102,11 @@ pub fn initialize_block<T: Config>(now: Timestamp) -> Result<(), Reason> {
 
     // Auxiliary cumulative values
     MinerCumulative::insert(last_miner, miner_cumulative);
+    internal::earnings::track_miner_earnings(
+        last_miner,
+        last_miner_paid_principal,
+        last_miner_withheld_principal,
+    );
 
     // Possibly rotate in any scheduled next CASH rate
     if let Some((next_apr, next_start)) = CashYieldNext::get() {
@@ -115,19 +118,34 @@ pub fn initialize_block<T: Config>(now: Timestamp) -> Result<(), Reason> {
         }
     }
 
-    if last_miner_share_principal != CashPrincipalAmount::ZERO {
+    if last_miner_paid_principal != CashPrincipalAmount::ZERO {
         // No need to emit events when nothing happens
         <Module<T>>::deposit_event(Event::TransferCash(
             GATEWAY_VOID,
             last_miner,
-            last_miner_share_principal,
+            last_miner_paid_principal,
             cash_index_new,
         ));
     }
 
-    <Module<T>>::deposit_event(Event::MinerPaid(last_miner, last_miner_share_principal));
+    if last_miner_withheld && last_miner_share_principal != CashPrincipalAmount::ZERO {
+        <Module<T>>::deposit_event(Event::MinerEarningsWithheld(
+            last_miner,
+            last_miner_share_principal,
+        ));
+    }
 
-    Ok(())
+    <Module<T>>::deposit_event(Event::MinerPaid(last_miner, last_miner_paid_principal));
+
+    Ok(swept)
+}
+
+/// Remember the latest accrual point for an asset, forgetting the oldest as needed.
+pub fn remember_accrual(history: &mut Vec<AccrualPoint>, point: AccrualPoint) {
+    history.push(point);
+    if history.len() > MAX_RATE_HISTORY {
+        history.drain(..history.len() - MAX_RATE_HISTORY);
+    }
 }
 
 #[cfg(test)]
@@ -169,7 +187,7 @@ mod tests {
             let result = initialize_block::<Test>(now);
             let shares = CashPrincipalAmount(242097062);
             let cash_index = CashIndex::from_nominal("1.192441828000000000");
-            assert_eq!(result, Ok(()));
+            assert_eq!(result, Ok(1));
 
             assert_eq!(
                 SupplyIndices::get(&asset),
@@ -192,8 +210,8 @@ mod tests {
             assert_eq!(LastMinerSharePrincipal::get(), shares);
             assert_eq!(MinerCumulative::get(&miner), CashPrincipalAmount(0));
 
-            // Run again to give last block principal to miner
-            assert_eq!(initialize_block::<Test>(now), Ok(()));
+            // Run again to give last block principal to miner, the asset is already current
+            assert_eq!(initialize_block::<Test>(now), Ok(0));
             assert_eq!(
                 CashPrincipals::get(&miner),
                 CashPrincipal::from_nominal("243.097062")
@@ -201,10 +219,30 @@ mod tests {
             assert_eq!(LastMinerSharePrincipal::get(), CashPrincipalAmount(0));
             assert_eq!(MinerCumulative::get(&miner), shares);
 
+            let supply_index = AssetIndex::from_nominal("1273.542520035618878005");
+            let borrow_index = AssetIndex::from_nominal("1425.699020480854853072");
+            let accrual = AccrualPoint {
+                timestamp: now,
+                supply_index,
+                borrow_index,
+                supply_rate: APR(1225),
+                borrow_rate: APR(2500),
+            };
+            assert_eq!(RateHistory::get(&asset), vec![accrual]);
+
+            let accrued_event = mock::Event::pallet_cash(crate::Event::AssetAccrued(
+                asset,
+                supply_index,
+                borrow_index,
+                APR(1225),
+                APR(2500),
+            ));
             let mut events_iter = System::events().into_iter();
+            let accrued_event_1 = events_iter.next().unwrap();
             let miner_paid_event_1 = events_iter.next().unwrap();
             let transfer_cash_event_1 = events_iter.next().unwrap();
             let miner_paid_event_2 = events_iter.next().unwrap();
+            assert_eq!(accrued_event, accrued_event_1.event);
             assert_eq!(
                 mock::Event::pallet_cash(crate::Event::MinerPaid(miner, CashPrincipalAmount(0))),
                 miner_paid_event_1.event
@@ -222,11 +260,176 @@ mod tests {
                 mock::Event::pallet_cash(crate::Event::MinerPaid(miner, shares)),
                 miner_paid_event_2.event
             );
-            // should be exactly 3 events
+            // should be exactly 4 events
             assert!(events_iter.next().is_none());
         });
     }
 
+    #[test]
+    fn test_remember_accrual() {
+        let point = |timestamp| AccrualPoint {
+            timestamp,
+            supply_index: AssetIndex::ONE,
+            borrow_index: AssetIndex::ONE,
+            supply_rate: APR::ZERO,
+            borrow_rate: APR::ZERO,
+        };
+        let mut history = vec![];
+        for timestamp in 0..(MAX_RATE_HISTORY as u64 + 2) {
+            remember_accrual(&mut history, point(timestamp));
+        }
+        assert_eq!(history.len(), MAX_RATE_HISTORY);
+        assert_eq!(history.first(), Some(&point(2)));
+        assert_eq!(history.last(), Some(&point(MAX_RATE_HISTORY as u64 + 1)));
+    }
+
+    #[test]
+    fn test_on_initialize_accrues_reserves() {
+        new_test_ext().execute_with(|| {
+            let miner = ChainAccount::Eth([0; 20]);
+            let asset = Eth;
+            let asset_info = AssetInfo {
+                rate_model: InterestRateModel::new_kink(0, 2500, Factor::from_nominal("0.5"), 5000),
+                miner_shares: MinerShares::from_nominal("0.02"),
+                ..AssetInfo::minimal(asset, ETH)
+            };
+            let last_yield_timestamp = 10;
+            let now = last_yield_timestamp + MILLISECONDS_PER_YEAR / 4;
+
+            Miner::put(miner);
+            LastBlockTimestamp::put(last_yield_timestamp);
+            LastYieldTimestamp::put(last_yield_timestamp);
+            SupportedAssets::insert(&asset, asset_info);
+            Spreads::insert(&asset, 100); // 1% of borrower interest
+            TotalReserves::insert(&asset, CashPrincipalAmount(5));
+            GlobalCashIndex::put(CashIndex::from_nominal("1.123"));
+            LastYieldCashIndex::put(CashIndex::from_nominal("1.123"));
+            SupplyIndices::insert(&asset, AssetIndex::from_nominal("1234"));
+            BorrowIndices::insert(&asset, AssetIndex::from_nominal("1345"));
+            TotalSupplyAssets::insert(asset.clone(), asset_info.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(asset.clone(), asset_info.as_quantity_nominal("150").value);
+            CashYield::put(APR::from_nominal("0.24"));
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("450000"));
+            pallet_oracle::Prices::insert(
+                asset_info.ticker,
+                1450_000000 as pallet_oracle::types::AssetPrice,
+            );
+
+            assert_eq!(initialize_block::<Test>(now), Ok(1));
+
+            // borrowers paid 12104.853072 CASH principal, 1% of which goes to reserves
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                CashPrincipalAmount::from_nominal("462104.853072")
+            );
+            assert_eq!(
+                TotalReserves::get(&asset),
+                CashPrincipalAmount(5 + 121048530)
+            );
+            assert_eq!(
+                LastMinerSharePrincipal::get(),
+                CashPrincipalAmount(242097062 - 121048530)
+            );
+        });
+    }
+
+    #[test]
+    fn test_on_initialize_accrues_insurance() {
+        new_test_ext().execute_with(|| {
+            let asset = Eth;
+            let asset_info = AssetInfo {
+                rate_model: InterestRateModel::new_kink(0, 2500, Factor::from_nominal("0.5"), 5000),
+                miner_shares: MinerShares::from_nominal("0.02"),
+                ..AssetInfo::minimal(asset, ETH)
+            };
+            let last_yield_timestamp = 10;
+            let now = last_yield_timestamp + MILLISECONDS_PER_YEAR / 4;
+
+            LastBlockTimestamp::put(last_yield_timestamp);
+            LastYieldTimestamp::put(last_yield_timestamp);
+            SupportedAssets::insert(&asset, asset_info);
+            InsuranceShare::put(2500); // a quarter of the miner spread
+            InsuranceFund::put(CashPrincipalAmount(7));
+            GlobalCashIndex::put(CashIndex::from_nominal("1.123"));
+            LastYieldCashIndex::put(CashIndex::from_nominal("1.123"));
+            SupplyIndices::insert(&asset, AssetIndex::from_nominal("1234"));
+            BorrowIndices::insert(&asset, AssetIndex::from_nominal("1345"));
+            TotalSupplyAssets::insert(asset.clone(), asset_info.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(asset.clone(), asset_info.as_quantity_nominal("150").value);
+            CashYield::put(APR::from_nominal("0.24"));
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("450000"));
+            pallet_oracle::Prices::insert(
+                asset_info.ticker,
+                1450_000000 as pallet_oracle::types::AssetPrice,
+            );
+
+            assert_eq!(initialize_block::<Test>(now), Ok(1));
+
+            // the miner spread of 242097062 is split with the insurance fund
+            assert_eq!(InsuranceFund::get(), CashPrincipalAmount(7 + 60524265));
+            assert_eq!(
+                LastMinerSharePrincipal::get(),
+                CashPrincipalAmount(242097062 - 60524265)
+            );
+        });
+    }
+
+    #[test]
+    fn test_on_initialize_reserves_capped_by_miner_share() {
+        new_test_ext().execute_with(|| {
+            let asset = Eth;
+            let asset_info = AssetInfo {
+                rate_model: InterestRateModel::new_kink(0, 2500, Factor::from_nominal("0.5"), 5000),
+                miner_shares: MinerShares::from_nominal("0.02"),
+                ..AssetInfo::minimal(asset, ETH)
+            };
+            let last_yield_timestamp = 10;
+            let now = last_yield_timestamp + MILLISECONDS_PER_YEAR / 4;
+
+            LastBlockTimestamp::put(last_yield_timestamp);
+            LastYieldTimestamp::put(last_yield_timestamp);
+            SupportedAssets::insert(&asset, asset_info);
+            Spreads::insert(&asset, 1000); // more than the miner shares
+            GlobalCashIndex::put(CashIndex::from_nominal("1.123"));
+            LastYieldCashIndex::put(CashIndex::from_nominal("1.123"));
+            SupplyIndices::insert(&asset, AssetIndex::from_nominal("1234"));
+            BorrowIndices::insert(&asset, AssetIndex::from_nominal("1345"));
+            TotalSupplyAssets::insert(asset.clone(), asset_info.as_quantity_nominal("300").value);
+            TotalBorrowAssets::insert(asset.clone(), asset_info.as_quantity_nominal("150").value);
+            CashYield::put(APR::from_nominal("0.24"));
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("450000"));
+            pallet_oracle::Prices::insert(
+                asset_info.ticker,
+                1450_000000 as pallet_oracle::types::AssetPrice,
+            );
+
+            assert_eq!(initialize_block::<Test>(now), Ok(1));
+            assert_eq!(TotalReserves::get(&asset), CashPrincipalAmount(242097062));
+            assert_eq!(LastMinerSharePrincipal::get(), CashPrincipalAmount::ZERO);
+        });
+    }
+
+    #[test]
+    fn test_on_initialize_withholds_offending_miner() {
+        new_test_ext().execute_with(|| {
+            let miner = ChainAccount::Eth([1; 20]);
+            let now = 10 + MILLISECONDS_PER_YEAR / 4;
+            LastBlockTimestamp::put(10);
+            LastYieldTimestamp::put(10);
+            InsuranceFund::put(CashPrincipalAmount(7));
+            LastMinerSharePrincipal::put(CashPrincipalAmount(100));
+            let author = AccountId32::new([2; 32]);
+            WithheldValidators::insert(&author, true);
+            Author::put(author);
+            internal::miner::set_miner::<Test>(miner);
+
+            assert_eq!(initialize_block::<Test>(now), Ok(0));
+            assert_eq!(InsuranceFund::get(), CashPrincipalAmount(107));
+            assert_eq!(CashPrincipals::get(&miner), CashPrincipal::ZERO);
+            assert_eq!(MinerCumulative::get(&miner), CashPrincipalAmount::ZERO);
+        });
+    }
+
     #[test]
     fn test_on_initialize_next_yield_progression() {
         new_test_ext().execute_with(|| {
@@ -248,7 +451,7 @@ mod tests {
             LastYieldTimestamp::put(last_yield_timestamp);
 
             let result = initialize_block::<Test>(now);
-            assert_eq!(result, Ok(()));
+            assert_eq!(result, Ok(0));
 
             let increment_expected = cash_yield_initial
                 .compound(now - last_yield_timestamp)
@@ -288,7 +491,7 @@ mod tests {
             LastBlockTimestamp::put(last_block_timestamp);
 
             let result = initialize_block::<Test>(now);
-            assert_eq!(result, Ok(()));
+            assert_eq!(result, Ok(0));
 
             let increment_expected = cash_yield_initial
                 .compound(now - last_yield_timestamp)
@@ -326,7 +529,7 @@ mod tests {
             LastYieldTimestamp::put(next_yield_timestamp);
 
             let result = initialize_block::<Test>(now);
-            assert_eq!(result, Ok(()));
+            assert_eq!(result, Ok(0));
 
             let increment_expected = cash_yield_next
                 .compound(now - next_yield_timestamp)
@@ -0,0 +1,135 @@
+use crate::{
+    chains::ChainAccount,
+    internal,
+    reason::Reason,
+    require,
+    types::{BigUint, Bips, CashPrincipalAmount},
+    ChainCashPrincipals, Config, Event, InsuranceFund, InsuranceShare, Module,
+};
+use frame_support::storage::{StorageMap, StorageValue};
+
+/// Set the share of the miner spread which accrues to the insurance fund, in bips.
+pub fn set_insurance_share<T: Config>(share: Bips) -> Result<(), Reason> {
+    require!(share <= 10000, Reason::InvalidInsuranceShare);
+    InsuranceShare::put(share);
+    <Module<T>>::deposit_event(Event::SetInsuranceShare(share));
+    Ok(())
+}
+
+/// Return the part of the miner share of borrower interest which accrues to the insurance fund.
+pub fn get_insurance_increase<T: Config>(
+    miner_share: CashPrincipalAmount,
+) -> Result<CashPrincipalAmount, Reason> {
+    Ok(CashPrincipalAmount(
+        BigUint::from_uint(miner_share.0)
+            .mul_decimal(InsuranceShare::get(), 4)
+            .to_uint()?,
+    ))
+}
+
+/// Return how much of a loss the insurance fund covers, and what is left in the fund after.
+pub fn get_insurance_cover<T: Config>(
+    loss: CashPrincipalAmount,
+) -> Result<(CashPrincipalAmount, CashPrincipalAmount), Reason> {
+    let fund = InsuranceFund::get();
+    let cover = fund.min(loss);
+    Ok((cover, fund.sub(cover)?))
+}
+
+/// Pay out some of the insurance fund to a recipient, as CASH.
+pub fn pay_insurance<T: Config>(
+    principal: CashPrincipalAmount,
+    recipient: ChainAccount,
+) -> Result<(), Reason> {
+    require!(
+        matches!(recipient, ChainAccount::Eth(_) | ChainAccount::Matic(_)),
+        Reason::InvalidChain
+    );
+    let fund = InsuranceFund::get();
+    require!(principal <= fund, Reason::InsufficientInsuranceFund);
+
+    let chain_id = recipient.chain_id();
+    let fund_post = fund.sub(principal)?;
+    let chain_cash_principal_post = ChainCashPrincipals::get(chain_id).add(principal)?;
+
+    InsuranceFund::put(fund_post);
+    ChainCashPrincipals::insert(chain_id, chain_cash_principal_post);
+
+    internal::notices::dispatch_cash_extraction_notice::<T>(recipient, principal);
+
+    <Module<T>>::deposit_event(Event::InsurancePaid(recipient, principal));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_set_insurance_share() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_insurance_share::<Test>(10001),
+                Err(Reason::InvalidInsuranceShare)
+            );
+            assert_eq!(set_insurance_share::<Test>(2500), Ok(()));
+            assert_eq!(InsuranceShare::get(), 2500);
+            assert_eq!(
+                get_insurance_increase::<Test>(CashPrincipalAmount(1000)),
+                Ok(CashPrincipalAmount(250))
+            );
+        });
+    }
+
+    #[test]
+    fn test_get_insurance_cover() {
+        new_test_ext().execute_with(|| {
+            InsuranceFund::put(CashPrincipalAmount(1000));
+            assert_eq!(
+                get_insurance_cover::<Test>(CashPrincipalAmount(400)),
+                Ok((CashPrincipalAmount(400), CashPrincipalAmount(600)))
+            );
+            assert_eq!(
+                get_insurance_cover::<Test>(CashPrincipalAmount(1400)),
+                Ok((CashPrincipalAmount(1000), CashPrincipalAmount::ZERO))
+            );
+        });
+    }
+
+    #[test]
+    fn test_pay_insurance() {
+        new_test_ext().execute_with(|| {
+            let recipient = ChainAccount::Eth([1; 20]);
+            InsuranceFund::put(CashPrincipalAmount(1000));
+
+            assert_eq!(
+                pay_insurance::<Test>(CashPrincipalAmount(1001), recipient),
+                Err(Reason::InsufficientInsuranceFund)
+            );
+            assert_eq!(
+                pay_insurance::<Test>(CashPrincipalAmount(100), ChainAccount::Gate([1; 32])),
+                Err(Reason::InvalidChain)
+            );
+
+            assert_eq!(
+                pay_insurance::<Test>(CashPrincipalAmount(400), recipient),
+                Ok(())
+            );
+            assert_eq!(InsuranceFund::get(), CashPrincipalAmount(600));
+            assert_eq!(
+                ChainCashPrincipals::get(ChainId::Eth),
+                CashPrincipalAmount(400)
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::InsurancePaid(
+                    recipient,
+                    CashPrincipalAmount(400)
+                )))
+            );
+        });
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    chains::ChainAsset,
+    internal::balance_helpers::{neg_balance, pos_balance},
+    reason::{MathError, Reason},
+    types::{AssetAmount, BrokenInvariant, CashPrincipalAmount},
+    AssetBalances, CashPrincipals, ChainCashPrincipals, Config, Event, Module, SupportedAssets,
+    TotalBorrowAssets, TotalCashPrincipal, TotalSupplyAssets, UnsettledAccruals,
+};
+use frame_support::storage::{
+    IterableStorageDoubleMap, IterableStorageMap, StorageMap, StorageValue,
+};
+use our_std::{error, log::targets::AUDIT, vec::Vec};
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// Recompute the total supply and borrow of an asset from its balances.
+fn sum_asset_balances(asset: ChainAsset) -> Result<(AssetAmount, AssetAmount), Reason> {
+    let mut supply: AssetAmount = 0;
+    let mut borrow: AssetAmount = 0;
+    for (_account, balance) in AssetBalances::iter_prefix(asset) {
+        supply = supply
+            .checked_add(pos_balance(balance))
+            .ok_or(MathError::Overflow)?;
+        borrow = borrow
+            .checked_add(neg_balance(balance)?)
+            .ok_or(MathError::Overflow)?;
+    }
+    Ok((supply, borrow))
+}
+
+/// Recompute the aggregates kept in storage from what they aggregate, returning those which diverge.
+/// The asset totals must equal the sums of the balances exactly.
+/// CASH interest is only settled into accounts as they are touched, so for CASH just a bound holds:
+///  accounts and chains cannot hold more CASH principal than is in existence, counting unsettled interest.
+pub fn check_invariants<T: Config>() -> Result<Vec<BrokenInvariant>, Reason> {
+    let mut broken = Vec::new();
+    for (asset, _asset_info) in SupportedAssets::iter() {
+        let (supply, borrow) = sum_asset_balances(asset)?;
+        let total_supply = TotalSupplyAssets::get(asset);
+        let total_borrow = TotalBorrowAssets::get(asset);
+        if total_supply != supply {
+            broken.push(BrokenInvariant::TotalSupply(asset, total_supply, supply));
+        }
+        if total_borrow != borrow {
+            broken.push(BrokenInvariant::TotalBorrow(asset, total_borrow, borrow));
+        }
+    }
+
+    let mut held = CashPrincipalAmount::ZERO;
+    for (_account, principal) in CashPrincipals::iter() {
+        held = held.add(CashPrincipalAmount(pos_balance(principal.0)))?;
+    }
+    for (_chain_id, principal) in ChainCashPrincipals::iter() {
+        held = held.add(principal)?;
+    }
+    let (unsettled_borrow, _unsettled_supply, _unsettled_reserve) = UnsettledAccruals::get();
+    let total_cash = TotalCashPrincipal::get().add(unsettled_borrow)?;
+    if held > total_cash {
+        broken.push(BrokenInvariant::TotalCash(total_cash, held));
+    }
+
+    Ok(broken)
+}
+
+/// Check the invariants, emitting an event for each one which is broken.
+pub fn assert_invariants<T: Config>() -> Result<(), Reason> {
+    for invariant in check_invariants::<T>()? {
+        error!(target: AUDIT, "Broken invariant"; invariant = invariant);
+        <Module<T>>::deposit_event(Event::BrokenInvariant(invariant));
+    }
+    Ok(())
+}
+
+/// Check the invariants from the offchain worker, if this node audits them and it's time to.
+/// Events can't be emitted offchain, so anything broken is just logged.
+pub fn audit_invariants<T: Config>(block_number: T::BlockNumber) -> Result<(), Reason> {
+    let interval = match runtime_interfaces::validator_config_interface::get_audit_interval() {
+        Some(interval) if interval > 0 => interval,
+        _ => return Ok(()),
+    };
+    let block_number: u32 = block_number.unique_saturated_into();
+    if block_number % interval != 0 {
+        return Ok(());
+    }
+    for invariant in check_invariants::<T>()? {
+        error!(target: AUDIT, "Broken invariant"; invariant = invariant, block_number = block_number);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{common::*, *};
+
+    #[test]
+    fn test_check_invariants() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+            let account = ChainAccount::Eth([1; 20]);
+            init_asset_balance(Eth, account, eth.as_balance_nominal("1").value);
+            init_asset_balance(Eth, ChainAccount::Eth([2; 20]), -1);
+            ChainCashPrincipals::insert(ChainId::Eth, CashPrincipalAmount(400));
+            CashPrincipals::insert(account, CashPrincipal(100));
+            CashPrincipals::insert(ChainAccount::Eth([2; 20]), CashPrincipal(-100));
+            TotalCashPrincipal::put(CashPrincipalAmount(500));
+            assert_eq!(check_invariants::<Test>(), Ok(vec![]));
+
+            // Unsettled interest counts towards the CASH in existence
+            CashPrincipals::insert(account, CashPrincipal(150));
+            assert_eq!(
+                check_invariants::<Test>(),
+                Ok(vec![BrokenInvariant::TotalCash(
+                    CashPrincipalAmount(500),
+                    CashPrincipalAmount(550)
+                )])
+            );
+            UnsettledAccruals::put((
+                CashPrincipalAmount(50),
+                CashPrincipalAmount::ZERO,
+                CashPrincipalAmount::ZERO,
+            ));
+            assert_eq!(check_invariants::<Test>(), Ok(vec![]));
+
+            let supply = eth.as_balance_nominal("1").value as AssetAmount;
+            TotalSupplyAssets::insert(Eth, supply + 1);
+            TotalBorrowAssets::insert(Eth, 0);
+            assert_ok!(assert_invariants::<Test>());
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::BrokenInvariant(
+                    BrokenInvariant::TotalSupply(Eth, supply + 1, supply)
+                )))
+            );
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::BrokenInvariant(
+                    BrokenInvariant::TotalBorrow(Eth, 0, 1)
+                )))
+            );
+        });
+    }
+}
@@ -0,0 +1,265 @@
+use crate::{
+    chains::{Chain, ChainAccount, ChainAccountSignature, Gateway},
+    internal::{
+        exec_trx_request::prepend_nonce,
+        nonces::{check_nonce, use_nonce},
+    },
+    pipeline,
+    portfolio::Portfolio,
+    reason::Reason,
+    require,
+    types::Nonce,
+    Config, Event, LinkedAccounts, Module,
+};
+use codec::alloc::string::String;
+use frame_support::storage::StorageMap;
+use our_std::vec::Vec;
+
+/// Prefix of the message a Gate key signs to link accounts, so its signature can't be taken for any other purpose.
+/// Gate signatures are not accepted anywhere else.
+pub const GATE_LINK_PREFIX: &[u8] = b"\x19Gateway Signed Link:\n";
+
+/// Return the message which both sides sign to link an Eth account with a Gate account.
+pub fn link_accounts_payload(eth_account: ChainAccount, gate_account: ChainAccount) -> Vec<u8> {
+    format!(
+        "(LinkAccounts {} {})",
+        String::from(eth_account),
+        String::from(gate_account)
+    )
+    .into_bytes()
+}
+
+/// Return the message which either side signs, with a nonce prepended, to unlink its account.
+pub fn unlink_accounts_payload(account: ChainAccount) -> Vec<u8> {
+    format!("(UnlinkAccounts {})", String::from(account)).into_bytes()
+}
+
+/// Recover the Eth and Gate accounts signing a link, checking that neither is already linked.
+pub fn recover_linked_accounts<T: Config>(
+    eth_signature: ChainAccountSignature,
+    gate_signature: ChainAccountSignature,
+) -> Result<(ChainAccount, ChainAccount), Reason> {
+    let (eth_account, gate_account) = match (eth_signature, gate_signature) {
+        (
+            ChainAccountSignature::Eth(eth_address, _),
+            ChainAccountSignature::Gate(gate_address, _),
+        ) => (
+            ChainAccount::Eth(eth_address),
+            ChainAccount::Gate(gate_address),
+        ),
+        _ => return Err(Reason::InvalidAccountLink),
+    };
+    let payload = link_accounts_payload(eth_account, gate_account);
+    require!(
+        eth_signature.recover_account(&payload[..])? == eth_account,
+        Reason::SignatureAccountMismatch
+    );
+    require!(
+        recover_gate_link_account(&payload[..], gate_signature)? == gate_account,
+        Reason::SignatureAccountMismatch
+    );
+    require!(
+        !LinkedAccounts::contains_key(eth_account) && !LinkedAccounts::contains_key(gate_account),
+        Reason::AccountAlreadyLinked
+    );
+    Ok((eth_account, gate_account))
+}
+
+/// Recover the Gate account signing a link, over the link message with its domain prefix.
+fn recover_gate_link_account(
+    payload: &[u8],
+    gate_signature: ChainAccountSignature,
+) -> Result<ChainAccount, Reason> {
+    match gate_signature {
+        ChainAccountSignature::Gate(_, signature) => {
+            let message = [GATE_LINK_PREFIX, payload].concat();
+            let recovered = <Gateway as Chain>::recover_user_address(&message, signature)?;
+            Ok(ChainAccount::Gate(recovered))
+        }
+        _ => Err(Reason::InvalidAccountLink),
+    }
+}
+
+/// Link an Eth account with a Gate account, signed by both, so they may be viewed as one.
+pub fn link_accounts<T: Config>(
+    eth_signature: ChainAccountSignature,
+    gate_signature: ChainAccountSignature,
+) -> Result<(), Reason> {
+    let (eth_account, gate_account) = recover_linked_accounts::<T>(eth_signature, gate_signature)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    LinkedAccounts::insert(eth_account, gate_account);
+    LinkedAccounts::insert(gate_account, eth_account);
+    <Module<T>>::deposit_event(Event::AccountsLinked(eth_account, gate_account));
+    Ok(())
+}
+
+/// Recover the account signing an unlink and the account it is linked with, checking the nonce may be used.
+/// Gate keys sign under the link domain prefix, as for linking.
+pub fn recover_unlinker<T: Config>(
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(ChainAccount, ChainAccount), Reason> {
+    let account = match signature {
+        ChainAccountSignature::Eth(eth_address, _) => ChainAccount::Eth(eth_address),
+        ChainAccountSignature::Gate(gate_address, _) => ChainAccount::Gate(gate_address),
+        _ => return Err(Reason::InvalidAccountLink),
+    };
+    let message = prepend_nonce(&unlink_accounts_payload(account), nonce);
+    let signer = match signature {
+        ChainAccountSignature::Gate(_, _) => recover_gate_link_account(&message[..], signature)?,
+        _ => signature.recover_account(&message[..])?,
+    };
+    require!(signer == account, Reason::SignatureAccountMismatch);
+    check_nonce::<T>(signer, nonce)?;
+    let linked = LinkedAccounts::get(signer).ok_or(Reason::AccountNotLinked)?;
+    Ok((signer, linked))
+}
+
+/// Unlink an account from the account it is linked with, signed by either one.
+pub fn unlink_accounts<T: Config>(
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(), Reason> {
+    let (account, linked) = recover_unlinker::<T>(signature, nonce)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    use_nonce::<T>(account, nonce);
+    LinkedAccounts::remove(account);
+    LinkedAccounts::remove(linked);
+    <Module<T>>::deposit_event(Event::AccountsUnlinked(account, linked));
+    Ok(())
+}
+
+/// Return the accounts which make up the logical account of the given one, starting with itself.
+pub fn get_logical_accounts<T: Config>(account: ChainAccount) -> Vec<ChainAccount> {
+    match LinkedAccounts::get(account) {
+        Some(linked) => vec![account, linked],
+        None => vec![account],
+    }
+}
+
+/// Return the combined portfolio of all the accounts which make up a logical account.
+pub fn load_logical_portfolio<T: Config>(account: ChainAccount) -> Result<Portfolio, Reason> {
+    let portfolio = pipeline::load_portfolio::<T>(account)?;
+    match LinkedAccounts::get(account) {
+        Some(linked) => portfolio.merge(pipeline::load_portfolio::<T>(linked)?),
+        None => Ok(portfolio),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{assets::*, common::*, *};
+    use sp_core::Pair;
+
+    fn eth_signature(payload: &[u8]) -> ChainAccountSignature {
+        let eth_address = <Ethereum as Chain>::signer_address().unwrap();
+        let full_message: Vec<u8> = [
+            format!("\x19Ethereum Signed Message:\n{}", payload.len()).as_bytes(),
+            payload,
+        ]
+        .concat();
+        let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+        ChainAccountSignature::Eth(
+            eth_address,
+            runtime_interfaces::keyring_interface::sign_one(full_message, eth_key_id).unwrap(),
+        )
+    }
+
+    fn gate_signature(pair: &sp_core::ecdsa::Pair, payload: &[u8]) -> ChainAccountSignature {
+        let gate_address = sp_io::hashing::blake2_256(pair.public().as_ref());
+        let message = [GATE_LINK_PREFIX, payload].concat();
+        ChainAccountSignature::Gate(gate_address, pair.sign(&message).0)
+    }
+
+    #[test]
+    fn test_link_accounts() {
+        new_test_ext().execute_with(|| {
+            let pair = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+            let eth_account = ChainAccount::Eth(<Ethereum as Chain>::signer_address().unwrap());
+            let gate_account =
+                ChainAccount::Gate(sp_io::hashing::blake2_256(pair.public().as_ref()));
+            let payload = link_accounts_payload(eth_account, gate_account);
+            let eth_sig = eth_signature(&payload);
+            let gate_sig = gate_signature(&pair, &payload);
+
+            assert_eq!(
+                link_accounts::<Test>(gate_sig, eth_sig),
+                Err(Reason::InvalidAccountLink)
+            );
+            assert_eq!(
+                link_accounts::<Test>(eth_sig, gate_signature(&pair, b"(LinkAccounts)")),
+                Err(Reason::SignatureAccountMismatch)
+            );
+            let undomained_gate_sig = ChainAccountSignature::Gate(
+                sp_io::hashing::blake2_256(pair.public().as_ref()),
+                pair.sign(&payload).0,
+            );
+            assert_eq!(
+                link_accounts::<Test>(eth_sig, undomained_gate_sig),
+                Err(Reason::SignatureAccountMismatch)
+            );
+
+            assert_eq!(link_accounts::<Test>(eth_sig, gate_sig), Ok(()));
+            assert_eq!(LinkedAccounts::get(eth_account), Some(gate_account));
+            assert_eq!(LinkedAccounts::get(gate_account), Some(eth_account));
+            assert_eq!(
+                get_logical_accounts::<Test>(gate_account),
+                vec![gate_account, eth_account]
+            );
+
+            assert_eq!(
+                link_accounts::<Test>(eth_sig, gate_sig),
+                Err(Reason::AccountAlreadyLinked)
+            );
+
+            let unlink_payload = prepend_nonce(&unlink_accounts_payload(gate_account), 0);
+            let unlink_sig = gate_signature(&pair, &unlink_payload);
+            assert_eq!(
+                unlink_accounts::<Test>(unlink_sig, 1),
+                Err(Reason::SignatureAccountMismatch)
+            );
+            assert_eq!(unlink_accounts::<Test>(unlink_sig, 0), Ok(()));
+            assert_eq!(LinkedAccounts::get(eth_account), None);
+            assert_eq!(LinkedAccounts::get(gate_account), None);
+            assert_eq!(
+                unlink_accounts::<Test>(
+                    gate_signature(
+                        &pair,
+                        &prepend_nonce(&unlink_accounts_payload(gate_account), 1)
+                    ),
+                    1
+                ),
+                Err(Reason::AccountNotLinked)
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_logical_portfolio() {
+        new_test_ext().execute_with(|| {
+            let eth_account = ChainAccount::Eth([1u8; 20]);
+            let gate_account = ChainAccount::Gate([2u8; 32]);
+            assert_ok!(init_eth_asset());
+            init_asset_balance(Eth, eth_account, Balance::from_nominal("2", ETH).value);
+            init_asset_balance(Eth, gate_account, Balance::from_nominal("-1", ETH).value);
+            CashPrincipals::insert(gate_account, CashPrincipal::from_nominal("10"));
+
+            let portfolio = load_logical_portfolio::<Test>(eth_account).unwrap();
+            assert_eq!(portfolio.cash, Balance::from_nominal("0", CASH));
+            assert_eq!(portfolio.positions.len(), 1);
+            assert_eq!(portfolio.positions[0].1, Balance::from_nominal("2", ETH));
+
+            LinkedAccounts::insert(eth_account, gate_account);
+            LinkedAccounts::insert(gate_account, eth_account);
+            let portfolio = load_logical_portfolio::<Test>(eth_account).unwrap();
+            assert_eq!(portfolio.cash, Balance::from_nominal("10", CASH));
+            assert_eq!(portfolio.positions.len(), 1);
+            assert_eq!(portfolio.positions[0].1, Balance::from_nominal("1", ETH));
+        });
+    }
+}
@@ -1,25 +1,32 @@
 use crate::{
     chains::ChainAccount,
     core,
-    factor::Factor,
-    internal::assets::{get_price, get_value},
+    internal::{
+        activity::record_exchange,
+        assets::{get_asset, get_liquidation_incentive, get_price, get_value},
+        balance_helpers::pos_balance,
+    },
     must,
-    params::MIN_TX_VALUE,
+    params::MAX_LIQUIDATION_COLLATERALS,
     pipeline::CashPipeline,
     reason::Reason,
     require, require_min_tx_value,
     symbol::Units,
-    types::{AssetInfo, AssetQuantity, CashPrincipalAmount, Quantity, CASH},
-    Config, Event, GlobalCashIndex, Module,
+    types::{
+        ActivityKind, AssetAmount, AssetBalance, AssetInfo, AssetQuantity, BigUint, Bips,
+        CashOrChainAsset, CashPrincipalAmount, Factor, MathError, Quantity, CASH,
+    },
+    AssetBalances, CloseFactor, Config, Event, GlobalCashIndex, Module,
 };
-use frame_support::storage::StorageValue;
+use frame_support::storage::{StorageDoubleMap, StorageValue};
 use our_std::result::Result;
 
 fn calculate_seize_quantity<T: Config>(
     quantity: AssetQuantity,
     collateral_units: Units,
+    collateral_incentive: Option<Bips>,
 ) -> Result<Quantity, Reason> {
-    let liquidation_incentive = Factor::from_nominal("1.08"); // XXX spec first
+    let liquidation_incentive = get_liquidation_incentive::<T>(collateral_incentive)?;
     let asset_price = get_price::<T>(quantity.units)?;
     let collateral_price = get_price::<T>(collateral_units)?;
 
@@ -33,6 +40,34 @@ fn calculate_seize_quantity<T: Config>(
         .div_price(collateral_price, collateral_units)?)
 }
 
+/// Check that a liquidation repays no more of the borrow than the close factor allows.
+/// The borrow before liquidation is the amount repaid plus what remains borrowed after it.
+fn check_close_factor<T: Config>(
+    repay: AssetAmount,
+    balance_post: AssetBalance,
+) -> Result<(), Reason> {
+    let remaining = balance_post.checked_neg().ok_or(MathError::Overflow)?;
+    let borrow_pre = repay
+        .checked_add(remaining as AssetAmount)
+        .ok_or(MathError::Overflow)?;
+    let max_repay = BigUint::from_uint(borrow_pre)
+        .mul_decimal(CloseFactor::get().0, Factor::DECIMALS)
+        .to_uint()?;
+    require!(repay <= max_repay, Reason::CloseFactorExceeded);
+    Ok(())
+}
+
+/// Set the maximum fraction of a borrow which may be repaid in a single liquidation.
+pub fn set_close_factor<T: Config>(close_factor: Factor) -> Result<(), Reason> {
+    require!(
+        close_factor > Factor::ZERO && close_factor <= Factor::ONE,
+        Reason::BadFactor
+    );
+    CloseFactor::put(close_factor);
+    <Module<T>>::deposit_event(Event::SetCloseFactor(close_factor));
+    Ok(())
+}
+
 pub fn liquidate_internal<T: Config>(
     asset: AssetInfo,
     collateral_asset: AssetInfo,
@@ -42,7 +77,11 @@ pub fn liquidate_internal<T: Config>(
 ) -> Result<(), Reason> {
     require!(asset != collateral_asset, Reason::InKindLiquidation);
     require_min_tx_value!(get_value::<T>(quantity)?);
-    let seize_quantity = calculate_seize_quantity::<T>(quantity, collateral_asset.units())?;
+    let seize_quantity = calculate_seize_quantity::<T>(
+        quantity,
+        collateral_asset.units(),
+        collateral_asset.liquidation_incentive,
+    )?;
 
     CashPipeline::new()
         .check_underwater::<T>(borrower)?
@@ -51,12 +90,23 @@ pub fn liquidate_internal<T: Config>(
         .check_asset_balance::<T, _>(borrower, asset, |asset_balance| {
             must!(asset_balance.lte(0), Reason::RepayTooMuch)
         })?
+        .check_asset_balance::<T, _>(borrower, asset, |asset_balance| {
+            check_close_factor::<T>(quantity.value, asset_balance.value)
+        })?
         .check_asset_balance::<T, _>(borrower, collateral_asset, |collateral_balance| {
             must!(collateral_balance.gte(0), Reason::InsufficientCollateral)
         })?
         .check_collateralized::<T>(liquidator)?
         .commit::<T>();
 
+    record_exchange::<T>(
+        liquidator,
+        ActivityKind::Liquidate,
+        borrower,
+        ActivityKind::Liquidated,
+        CashOrChainAsset::ChainAsset(asset.asset),
+        quantity.value,
+    );
     <Module<T>>::deposit_event(Event::Liquidate(
         asset.asset,
         collateral_asset.asset,
@@ -78,7 +128,11 @@ pub fn liquidate_cash_principal_internal<T: Config>(
     let quantity = index.cash_quantity(principal)?;
 
     require_min_tx_value!(get_value::<T>(quantity)?);
-    let seize_quantity = calculate_seize_quantity::<T>(quantity, collateral_asset.units())?;
+    let seize_quantity = calculate_seize_quantity::<T>(
+        quantity,
+        collateral_asset.units(),
+        collateral_asset.liquidation_incentive,
+    )?;
 
     CashPipeline::new()
         .check_underwater::<T>(borrower)?
@@ -87,12 +141,23 @@ pub fn liquidate_cash_principal_internal<T: Config>(
         .check_cash_principal::<T, _>(borrower, |cash_principal| {
             must!(cash_principal.lte(0), Reason::RepayTooMuch)
         })?
+        .check_cash_principal::<T, _>(borrower, |cash_principal| {
+            check_close_factor::<T>(principal.0, cash_principal.0)
+        })?
         .check_asset_balance::<T, _>(borrower, collateral_asset, |collateral_balance| {
             must!(collateral_balance.gte(0), Reason::InsufficientCollateral)
         })?
         .check_collateralized::<T>(liquidator)?
         .commit::<T>();
 
+    record_exchange::<T>(
+        liquidator,
+        ActivityKind::Liquidate,
+        borrower,
+        ActivityKind::Liquidated,
+        CashOrChainAsset::Cash,
+        principal.0,
+    );
     <Module<T>>::deposit_event(Event::LiquidateCash(
         collateral_asset.asset,
         liquidator,
@@ -113,7 +178,7 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
     let index = GlobalCashIndex::get();
 
     require_min_tx_value!(get_value::<T>(quantity)?);
-    let seize_quantity = calculate_seize_quantity::<T>(quantity, CASH)?;
+    let seize_quantity = calculate_seize_quantity::<T>(quantity, CASH, None)?;
     let seize_principal = index.cash_principal_amount(seize_quantity)?;
 
     CashPipeline::new()
@@ -123,12 +188,23 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
         .check_asset_balance::<T, _>(borrower, asset, |asset_balance| {
             must!(asset_balance.lte(0), Reason::RepayTooMuch)
         })?
+        .check_asset_balance::<T, _>(borrower, asset, |asset_balance| {
+            check_close_factor::<T>(quantity.value, asset_balance.value)
+        })?
         .check_cash_principal::<T, _>(borrower, |cash_principal| {
             must!(cash_principal.gte(0), Reason::InsufficientCollateral)
         })?
         .check_collateralized::<T>(liquidator)?
         .commit::<T>();
 
+    record_exchange::<T>(
+        liquidator,
+        ActivityKind::Liquidate,
+        borrower,
+        ActivityKind::Liquidated,
+        CashOrChainAsset::ChainAsset(asset.asset),
+        quantity.value,
+    );
     <Module<T>>::deposit_event(Event::LiquidateCashCollateral(
         asset.asset,
         liquidator,
@@ -139,6 +215,146 @@ pub fn liquidate_cash_collateral_internal<T: Config>(
     Ok(())
 }
 
+/// Split a repayment across collateral assets, in proportion to the value of the borrower's
+/// holdings of each. The last collateral asset receives any remainder lost to rounding.
+fn split_repay_by_collateral<T: Config>(
+    quantity: Quantity,
+    collateral_assets: &Vec<AssetInfo>,
+    borrower: ChainAccount,
+) -> Result<Vec<(AssetInfo, Quantity)>, Reason> {
+    let mut values = Vec::with_capacity(collateral_assets.len());
+    let mut total_value: AssetAmount = 0;
+    for collateral_asset in collateral_assets {
+        let balance = AssetBalances::get(collateral_asset.asset, borrower);
+        let held = collateral_asset.as_quantity(pos_balance(balance));
+        let value = get_value::<T>(held)?.value;
+        total_value = total_value.checked_add(value).ok_or(MathError::Overflow)?;
+        values.push(value);
+    }
+    require!(total_value > 0, Reason::InsufficientCollateral);
+
+    let mut remaining = quantity;
+    let mut shares = Vec::with_capacity(collateral_assets.len());
+    for (i, (collateral_asset, value)) in collateral_assets.iter().zip(values).enumerate() {
+        let share = if i + 1 == collateral_assets.len() {
+            remaining
+        } else {
+            quantity.mul_factor(Factor::from_fraction(value, total_value)?)?
+        };
+        remaining = remaining.sub(share)?;
+        shares.push((*collateral_asset, share));
+    }
+    Ok(shares)
+}
+
+/// Repay one borrow and seize from several collateral assets in a single pipeline.
+/// The seized value is split in proportion to the value of the borrower's holdings of each.
+pub fn liquidate_many_internal<T: Config>(
+    borrowed: CashOrChainAsset,
+    collateral_assets: Vec<AssetInfo>,
+    liquidator: ChainAccount,
+    borrower: ChainAccount,
+    quantity: Quantity,
+) -> Result<(), Reason> {
+    require!(
+        !collateral_assets.is_empty() && collateral_assets.len() <= MAX_LIQUIDATION_COLLATERALS,
+        Reason::InvalidLiquidation
+    );
+    for (i, collateral_asset) in collateral_assets.iter().enumerate() {
+        require!(
+            borrowed != CashOrChainAsset::ChainAsset(collateral_asset.asset),
+            Reason::InKindLiquidation
+        );
+        require!(
+            !collateral_assets[..i].contains(collateral_asset),
+            Reason::InvalidLiquidation
+        );
+    }
+    require_min_tx_value!(get_value::<T>(quantity)?);
+
+    let index = GlobalCashIndex::get();
+    let shares = split_repay_by_collateral::<T>(quantity, &collateral_assets, borrower)?;
+    let mut pipeline = CashPipeline::new().check_underwater::<T>(borrower)?;
+    pipeline = match borrowed {
+        CashOrChainAsset::Cash => {
+            let principal = index.cash_principal_amount(quantity)?;
+            pipeline
+                .transfer_cash::<T>(liquidator, borrower, principal)?
+                .check_cash_principal::<T, _>(borrower, |cash_principal| {
+                    must!(cash_principal.lte(0), Reason::RepayTooMuch)
+                })?
+                .check_cash_principal::<T, _>(borrower, |cash_principal| {
+                    check_close_factor::<T>(principal.0, cash_principal.0)
+                })?
+        }
+        CashOrChainAsset::ChainAsset(asset) => {
+            let asset_info = get_asset::<T>(asset)?;
+            pipeline
+                .transfer_asset::<T>(liquidator, borrower, asset, quantity)?
+                .check_asset_balance::<T, _>(borrower, asset_info, |asset_balance| {
+                    must!(asset_balance.lte(0), Reason::RepayTooMuch)
+                })?
+                .check_asset_balance::<T, _>(borrower, asset_info, |asset_balance| {
+                    check_close_factor::<T>(quantity.value, asset_balance.value)
+                })?
+        }
+    };
+    for (collateral_asset, share) in &shares {
+        if share.value == 0 {
+            continue;
+        }
+        let seize_quantity = calculate_seize_quantity::<T>(
+            *share,
+            collateral_asset.units(),
+            collateral_asset.liquidation_incentive,
+        )?;
+        pipeline = pipeline
+            .transfer_asset::<T>(borrower, liquidator, collateral_asset.asset, seize_quantity)?
+            .check_asset_balance::<T, _>(borrower, *collateral_asset, |collateral_balance| {
+                must!(collateral_balance.gte(0), Reason::InsufficientCollateral)
+            })?;
+    }
+    pipeline
+        .check_collateralized::<T>(liquidator)?
+        .commit::<T>();
+
+    let repaid = match borrowed {
+        CashOrChainAsset::Cash => index.cash_principal_amount(quantity)?.0,
+        CashOrChainAsset::ChainAsset(_) => quantity.value,
+    };
+    record_exchange::<T>(
+        liquidator,
+        ActivityKind::Liquidate,
+        borrower,
+        ActivityKind::Liquidated,
+        borrowed,
+        repaid,
+    );
+    for (collateral_asset, share) in shares {
+        if share.value == 0 {
+            continue;
+        }
+        match borrowed {
+            CashOrChainAsset::Cash => <Module<T>>::deposit_event(Event::LiquidateCash(
+                collateral_asset.asset,
+                liquidator,
+                borrower,
+                index.cash_principal_amount(share)?,
+                index,
+            )),
+            CashOrChainAsset::ChainAsset(asset) => <Module<T>>::deposit_event(Event::Liquidate(
+                asset,
+                collateral_asset.asset,
+                liquidator,
+                borrower,
+                share.value,
+            )),
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,7 +382,7 @@ mod tests {
             init_wbtc_asset().unwrap();
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, None),
                 Err(Reason::NoPrice)
             );
         })
@@ -180,7 +396,7 @@ mod tests {
             init_eth_asset().unwrap();
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, None),
                 Err(Reason::NoPrice)
             );
         })
@@ -198,7 +414,7 @@ mod tests {
             init_wbtc_asset().unwrap();
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, None),
                 Err(Reason::MathError(MathError::Overflow))
             );
         })
@@ -216,7 +432,7 @@ mod tests {
             init_wbtc_asset().unwrap();
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, None),
                 Err(Reason::MathError(MathError::Overflow))
             );
         })
@@ -232,7 +448,7 @@ mod tests {
             pallet_oracle::Prices::insert(ETH.ticker, Price::from_nominal(ETH.ticker, "0").value);
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, None),
                 Err(Reason::NoPrice)
             );
         })
@@ -248,7 +464,7 @@ mod tests {
             pallet_oracle::Prices::insert(WBTC.ticker, Price::from_nominal(WBTC.ticker, "0").value);
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, None),
                 Err(Reason::NoPrice)
             );
         })
@@ -271,7 +487,7 @@ mod tests {
             );
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, None),
                 Ok(Quantity {
                     value: 3600000, // 1.08 * 1 * 2000 / 60000 = 0.036e8
                     units: WBTC
@@ -284,7 +500,7 @@ mod tests {
             );
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, WBTC),
+                calculate_seize_quantity::<Test>(quantity, WBTC, None),
                 Ok(Quantity {
                     value: 4320000, // 1.08 * 1 * 2000 / 50000 = 0.0432e8
                     units: WBTC
@@ -292,7 +508,7 @@ mod tests {
             );
 
             assert_eq!(
-                calculate_seize_quantity::<Test>(quantity, CASH),
+                calculate_seize_quantity::<Test>(quantity, CASH, None),
                 Ok(Quantity {
                     value: 2160000000, // 1.08 * 1 * 2000 / 1 = 2160e6
                     units: CASH
@@ -305,13 +521,22 @@ mod tests {
                         value: 1000000000,
                         units: CASH
                     },
-                    ETH
+                    ETH,
+                    None
                 ),
                 Ok(Quantity {
                     value: 540000000000000000, // 1.08 * 1 * 1000 / 2000 = 0.54e18
                     units: ETH
                 })
             );
+
+            assert_eq!(
+                calculate_seize_quantity::<Test>(quantity, WBTC, Some(1000)),
+                Ok(Quantity {
+                    value: 4400000, // 1.10 * 1 * 2000 / 50000 = 0.044e8
+                    units: WBTC
+                })
+            );
         })
     }
 
@@ -521,6 +746,28 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_liquidate_internal_close_factor_exceeded() {
+        new_test_ext().execute_with(|| {
+            let amount: AssetQuantity = eth.as_quantity_nominal("41");
+
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+
+            init_asset_balance(Eth, borrower, Balance::from_nominal("-80", ETH).value); // -80 * 2000 / 0.8 = -200000
+            init_asset_balance(Wbtc, borrower, Balance::from_nominal("2", WBTC).value); // 2 * 60000 * 0.6 = 72000
+            init_cash(borrower, CashPrincipal::from_nominal("100000")); // 100000 + 72000 - 200000 = -28000
+
+            init_cash(liquidator, CashPrincipal::from_nominal("1000000"));
+
+            // Close factor 0.5 * 80 = 40 ETH max repay
+            assert_eq!(
+                liquidate_internal::<Test>(asset, collateral_asset, liquidator, borrower, amount),
+                Err(Reason::CloseFactorExceeded)
+            );
+        })
+    }
+
     #[test]
     fn test_liquidate_internal_repay_too_much() {
         new_test_ext().execute_with(|| {
@@ -1004,6 +1251,53 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_liquidate_cash_principal_internal_close_factor_exceeded() {
+        new_test_ext().execute_with(|| {
+            let principal: CashPrincipalAmount = CashPrincipalAmount::from_nominal("150001");
+
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+
+            init_asset_balance(Eth, borrower, Balance::from_nominal("80", ETH).value); // 80 * 2000 / 0.8 = 200000
+            init_asset_balance(Wbtc, borrower, Balance::from_nominal("2", WBTC).value); // 2 * 60000 * 0.6 = 72000
+            init_cash(borrower, CashPrincipal::from_nominal("-300000")); // -300000 + 72000 + 200000 = -28000
+
+            init_cash(liquidator, CashPrincipal::from_nominal("1000000"));
+
+            // Close factor 0.5 * 300000 = 150000 CASH principal max repay
+            assert_eq!(
+                liquidate_cash_principal_internal::<Test>(
+                    collateral_asset,
+                    liquidator,
+                    borrower,
+                    principal
+                ),
+                Err(Reason::CloseFactorExceeded)
+            );
+        })
+    }
+
+    #[test]
+    fn test_set_close_factor() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(CloseFactor::get(), Factor::from_nominal("0.5"));
+            assert_eq!(
+                set_close_factor::<Test>(Factor::ZERO),
+                Err(Reason::BadFactor)
+            );
+            assert_eq!(
+                set_close_factor::<Test>(Factor::from_nominal("1.01")),
+                Err(Reason::BadFactor)
+            );
+            assert_eq!(set_close_factor::<Test>(Factor::ONE), Ok(()));
+            assert_eq!(CloseFactor::get(), Factor::ONE);
+
+            // Repaying the full borrow is now permitted
+            assert_eq!(check_close_factor::<Test>(100, 0), Ok(()));
+        })
+    }
+
     #[test]
     fn test_liquidate_cash_principal_internal_ok() {
         new_test_ext().execute_with(|| {
@@ -1390,4 +1684,139 @@ mod tests {
             );
         })
     }
+
+    #[test]
+    fn test_liquidate_many_internal_invalid_collaterals() {
+        new_test_ext().execute_with(|| {
+            let amount: AssetQuantity = eth.as_quantity_nominal("1");
+
+            assert_eq!(
+                liquidate_many_internal::<Test>(
+                    CashOrChainAsset::ChainAsset(Eth),
+                    vec![],
+                    liquidator,
+                    borrower,
+                    amount
+                ),
+                Err(Reason::InvalidLiquidation)
+            );
+            assert_eq!(
+                liquidate_many_internal::<Test>(
+                    CashOrChainAsset::ChainAsset(Eth),
+                    vec![wbtc, wbtc],
+                    liquidator,
+                    borrower,
+                    amount
+                ),
+                Err(Reason::InvalidLiquidation)
+            );
+            assert_eq!(
+                liquidate_many_internal::<Test>(
+                    CashOrChainAsset::ChainAsset(Eth),
+                    vec![wbtc, eth],
+                    liquidator,
+                    borrower,
+                    amount
+                ),
+                Err(Reason::InKindLiquidation)
+            );
+        })
+    }
+
+    #[test]
+    fn test_liquidate_many_internal_not_underwater() {
+        new_test_ext().execute_with(|| {
+            let amount: AssetQuantity = Quantity::from_nominal("1000", CASH);
+
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+
+            init_asset_balance(Eth, borrower, Balance::from_nominal("1", ETH).value);
+            init_cash(borrower, CashPrincipal::from_nominal("-100"));
+            init_cash(liquidator, CashPrincipal::from_nominal("100000"));
+
+            assert_eq!(
+                liquidate_many_internal::<Test>(
+                    CashOrChainAsset::Cash,
+                    vec![eth, wbtc],
+                    liquidator,
+                    borrower,
+                    amount
+                ),
+                Err(Reason::SufficientLiquidity)
+            );
+        })
+    }
+
+    #[test]
+    fn test_liquidate_many_internal_ok() {
+        new_test_ext().execute_with(|| {
+            let amount: AssetQuantity = Quantity::from_nominal("1000", CASH);
+
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+
+            init_asset_balance(Eth, borrower, Balance::from_nominal("1", ETH).value); // 1 * 2000 = 2000
+            init_asset_balance(Wbtc, borrower, Balance::from_nominal("0.1", WBTC).value); // 0.1 * 60000 = 6000
+            init_cash(borrower, CashPrincipal::from_nominal("-20000"));
+            init_cash(liquidator, CashPrincipal::from_nominal("100000"));
+
+            // Repay is split 1/4 against ETH and 3/4 against WBTC
+            // Seize amounts = 1.08 * 250 / 2000 = 0.135 ETH, 1.08 * 750 / 60000 = 0.0135 WBTC
+
+            assert_ok!(liquidate_many_internal::<Test>(
+                CashOrChainAsset::Cash,
+                vec![eth, wbtc],
+                liquidator,
+                borrower,
+                amount
+            ));
+
+            assert_eq!(
+                AssetBalances::get(Eth, borrower),
+                Balance::from_nominal("0.865", ETH).value
+            );
+            assert_eq!(
+                AssetBalances::get(Eth, liquidator),
+                Balance::from_nominal("0.135", ETH).value
+            );
+            assert_eq!(
+                AssetBalances::get(Wbtc, borrower),
+                Balance::from_nominal("0.0865", WBTC).value
+            );
+            assert_eq!(
+                AssetBalances::get(Wbtc, liquidator),
+                Balance::from_nominal("0.0135", WBTC).value
+            );
+            assert_eq!(
+                CashPrincipals::get(borrower),
+                CashPrincipal::from_nominal("-19000")
+            );
+            assert_eq!(
+                CashPrincipals::get(liquidator),
+                CashPrincipal::from_nominal("99000")
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert_eq!(
+                events,
+                vec![
+                    mock::Event::pallet_cash(crate::Event::LiquidateCash(
+                        Eth,
+                        liquidator,
+                        borrower,
+                        CashPrincipalAmount::from_nominal("250"),
+                        CashIndex::from_nominal("1")
+                    )),
+                    mock::Event::pallet_cash(crate::Event::LiquidateCash(
+                        Wbtc,
+                        liquidator,
+                        borrower,
+                        CashPrincipalAmount::from_nominal("750"),
+                        CashIndex::from_nominal("1")
+                    )),
+                ]
+            );
+        })
+    }
 }
@@ -0,0 +1,185 @@
+use crate::{
+    factor::Factor,
+    reason::Reason,
+    require,
+    types::{SessionIndex, ValidatorIdentity, ValidatorKeys, ValidatorParticipation},
+    Config, Event, MaxMissedFraction, Module, SessionParticipation, ValidatorParticipations,
+    Validators,
+};
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
+use our_std::{cmp::min, log, vec::Vec};
+
+/// Set the maximum fraction of chain blocks and notices a validator may miss within a session.
+pub fn set_max_missed_fraction<T: Config>(fraction: Factor) -> Result<(), Reason> {
+    require!(fraction <= Factor::ONE, Reason::BadFactor);
+    MaxMissedFraction::put(fraction);
+    <Module<T>>::deposit_event(Event::SetMaxMissedFraction(fraction));
+    Ok(())
+}
+
+/// Count chain blocks attested by a validator, and those accepted by the validator set.
+pub fn track_chain_blocks(validator: &ValidatorIdentity, attested: u32, accepted: u32) {
+    if attested > 0 {
+        ValidatorParticipations::mutate(validator, |participation| {
+            participation.blocks_attested = participation.blocks_attested.saturating_add(attested)
+        });
+    }
+    if accepted > 0 {
+        SessionParticipation::mutate(|expected| {
+            expected.blocks_attested = expected.blocks_attested.saturating_add(accepted)
+        });
+    }
+}
+
+/// Count a notice dispatched for the validator set to sign.
+pub fn track_notice_dispatched() {
+    SessionParticipation::mutate(|expected| {
+        expected.notices_signed = expected.notices_signed.saturating_add(1)
+    });
+}
+
+/// Count a notice signed by a validator.
+pub fn track_notice_signed(validator: &ValidatorIdentity) {
+    ValidatorParticipations::mutate(validator, |participation| {
+        participation.notices_signed = participation.notices_signed.saturating_add(1)
+    });
+}
+
+/// Return the fraction of the expected chain blocks and notices a validator missed.
+pub fn get_missed_fraction(
+    participation: ValidatorParticipation,
+    expected: ValidatorParticipation,
+) -> Result<Factor, Reason> {
+    let total = expected.blocks_attested as u64 + expected.notices_signed as u64;
+    if total == 0 {
+        return Ok(Factor::ZERO);
+    }
+    let done = min(participation.blocks_attested, expected.blocks_attested) as u64
+        + min(participation.notices_signed, expected.notices_signed) as u64;
+    Ok(Factor::from_fraction(total - done, total)?)
+}
+
+/// Return the participation expected this session, and that of each current validator.
+pub fn get_validator_participation<T: Config>() -> (
+    ValidatorParticipation,
+    Vec<(ValidatorKeys, ValidatorParticipation)>,
+) {
+    let participations = Validators::iter_values()
+        .map(|validator| {
+            let participation = ValidatorParticipations::get(&validator.substrate_id);
+            (validator, participation)
+        })
+        .collect();
+    (SessionParticipation::get(), participations)
+}
+
+/// Report the validators which missed too much of the session ending, and start counting afresh.
+pub fn end_session<T: Config>(index: SessionIndex) {
+    let max_missed = MaxMissedFraction::get();
+    let (expected, participations) = get_validator_participation::<T>();
+    for (validator, participation) in participations {
+        match get_missed_fraction(participation, expected) {
+            Ok(missed) if missed > max_missed => {
+                log!(
+                    "Validator under-performed in session {}: {:?} {:?} / {:?}",
+                    index,
+                    validator,
+                    participation,
+                    expected
+                );
+                <Module<T>>::deposit_event(Event::ValidatorUnderperformed(
+                    validator.substrate_id,
+                    participation,
+                    expected,
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    let tracked: Vec<ValidatorIdentity> =
+        ValidatorParticipations::iter().map(|(id, _)| id).collect();
+    for id in tracked {
+        ValidatorParticipations::remove(&id);
+    }
+    SessionParticipation::kill();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_get_missed_fraction() {
+        let participation = |blocks_attested, notices_signed| ValidatorParticipation {
+            blocks_attested,
+            notices_signed,
+        };
+        assert_eq!(
+            get_missed_fraction(participation(0, 0), participation(0, 0)),
+            Ok(Factor::ZERO)
+        );
+        assert_eq!(
+            get_missed_fraction(participation(3, 1), participation(3, 1)),
+            Ok(Factor::ZERO)
+        );
+        assert_eq!(
+            get_missed_fraction(participation(1, 0), participation(3, 1)),
+            Ok(Factor::from_nominal("0.75"))
+        );
+        assert_eq!(
+            get_missed_fraction(participation(9, 1), participation(3, 1)),
+            Ok(Factor::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_end_session_reports_underperforming_validators() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let validators: Vec<ValidatorKeys> = Validators::iter_values().collect();
+            let (active, idle) = (&validators[0], &validators[1]);
+            track_chain_blocks(&active.substrate_id, 4, 4);
+            track_notice_dispatched();
+            track_notice_signed(&active.substrate_id);
+            track_chain_blocks(&idle.substrate_id, 1, 0);
+
+            end_session::<Test>(1);
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            let underperformed = |validator: &ValidatorKeys, participation| {
+                mock::Event::pallet_cash(crate::Event::ValidatorUnderperformed(
+                    validator.substrate_id.clone(),
+                    participation,
+                    ValidatorParticipation {
+                        blocks_attested: 4,
+                        notices_signed: 1,
+                    },
+                ))
+            };
+            assert!(events.contains(&underperformed(
+                idle,
+                ValidatorParticipation {
+                    blocks_attested: 1,
+                    notices_signed: 0,
+                }
+            )));
+            assert!(!events.contains(&underperformed(
+                active,
+                ValidatorParticipation {
+                    blocks_attested: 4,
+                    notices_signed: 1,
+                }
+            )));
+            assert_eq!(
+                ValidatorParticipations::get(&active.substrate_id),
+                ValidatorParticipation::default()
+            );
+            assert_eq!(
+                SessionParticipation::get(),
+                ValidatorParticipation::default()
+            );
+        });
+    }
+}
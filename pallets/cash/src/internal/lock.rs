@@ -1,8 +1,11 @@
 use crate::{
-    chains::ChainAccount,
+    chains::{ChainAccount, LockReference},
+    internal::activity::record_activity,
     pipeline::CashPipeline,
     reason::Reason,
-    types::{AssetInfo, AssetQuantity, CashIndex, CashPrincipalAmount},
+    types::{
+        ActivityKind, AssetInfo, AssetQuantity, CashIndex, CashOrChainAsset, CashPrincipalAmount,
+    },
     Config, Event, GlobalCashIndex, Module,
 };
 use frame_support::storage::StorageValue;
@@ -12,16 +15,27 @@ pub fn lock_internal<T: Config>(
     sender: ChainAccount,
     recipient: ChainAccount,
     quantity: AssetQuantity,
+    reference: Option<LockReference>,
 ) -> Result<(), Reason> {
     CashPipeline::new()
         .lock_asset::<T>(recipient, asset.asset, quantity)?
+        .check_asset_unpaused::<T>(asset)?
+        .check_chain_asset_cap::<T>(asset.asset.chain_id())?
         .commit::<T>();
 
+    record_activity::<T>(
+        recipient,
+        ActivityKind::Lock,
+        CashOrChainAsset::ChainAsset(asset.asset),
+        quantity.value,
+        Some(sender),
+    );
     <Module<T>>::deposit_event(Event::Locked(
         asset.asset,
         sender,
         recipient,
         quantity.value,
+        reference,
     ));
 
     Ok(())
@@ -36,6 +50,13 @@ pub fn lock_cash_principal_internal<T: Config>(
         .lock_cash::<T>(recipient, principal)?
         .commit::<T>();
 
+    record_activity::<T>(
+        recipient,
+        ActivityKind::Lock,
+        CashOrChainAsset::Cash,
+        principal.0,
+        Some(sender),
+    );
     let index: CashIndex = GlobalCashIndex::get(); // Grab cash index just for event
     <Module<T>>::deposit_event(Event::LockedCash(sender, recipient, principal, index));
 
@@ -48,6 +69,7 @@ pub fn undo_lock_internal<T: Config>(
     sender: ChainAccount,
     recipient: ChainAccount,
     quantity: AssetQuantity,
+    reference: Option<LockReference>,
 ) -> Result<(), Reason> {
     // Note: we don't check liquidity here since who knows
     CashPipeline::new()
@@ -59,6 +81,7 @@ pub fn undo_lock_internal<T: Config>(
         sender,
         recipient,
         quantity.value,
+        reference,
     ));
 
     Ok(())
@@ -210,6 +233,61 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_lock_asset_with_reference() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            pallet_oracle::Prices::insert(
+                ETH.ticker,
+                Price::from_nominal(ETH.ticker, "2000.00").value,
+            );
+            let eth_info = SupportedAssets::get(Eth).unwrap();
+            let quantity = qty!("1", ETH);
+
+            assert_eq!(
+                lock_internal::<Test>(eth_info, GEOFF, JARED, quantity, Some([7; 32])),
+                Ok(())
+            );
+            assert_eq!(AssetBalances::get(Eth, JARED), bal!("1", ETH).value);
+
+            // Check emitted `Locked` event
+            let locked_event = System::events().into_iter().last().unwrap();
+            assert_eq!(
+                mock::Event::pallet_cash(crate::Event::Locked(
+                    Eth,
+                    GEOFF,
+                    JARED,
+                    quantity.value,
+                    Some([7; 32])
+                )),
+                locked_event.event
+            );
+        });
+    }
+
+    #[test]
+    fn test_lock_asset_chain_asset_cap() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            pallet_oracle::Prices::insert(
+                ETH.ticker,
+                Price::from_nominal(ETH.ticker, "2000.00").value,
+            );
+            let eth_info = SupportedAssets::get(Eth).unwrap();
+            ChainAssetCaps::insert(ChainId::Eth, Quantity::from_nominal("3000", USD));
+
+            assert_eq!(
+                lock_internal::<Test>(eth_info, GEOFF, JARED, qty!("1", ETH), None),
+                Ok(())
+            );
+            assert_eq!(
+                lock_internal::<Test>(eth_info, GEOFF, JARED, qty!("1", ETH), None),
+                Err(Reason::ChainAssetCapExceeded)
+            );
+            assert_eq!(AssetBalances::get(Eth, JARED), bal!("1", ETH).value);
+        });
+    }
+
     #[test]
     fn lock_cash_without_chain_cash_or_total_cash_fails() -> Result<(), Reason> {
         let jared = ChainAccount::from_str("Eth:0x18c8F1222083997405F2E482338A4650ac02e1d6")?;
@@ -0,0 +1,116 @@
+use crate::{
+    chains::{ChainAccount, ChainAccountSignature},
+    core::get_validator,
+    internal::{
+        exec_trx_request::prepend_nonce,
+        nonces::{check_nonce, use_nonce},
+    },
+    params::MAX_VALIDATOR_METADATA_LEN,
+    reason::Reason,
+    require,
+    types::{Nonce, ValidatorKeys, ValidatorMetadata},
+    Config, Event, Module, ValidatorMetadatas, Validators,
+};
+use codec::Encode;
+use frame_support::storage::{IterableStorageMap, StorageMap};
+use our_std::vec::Vec;
+
+/// The payload a validator signs with its Eth key, with a nonce prepended, to publish its metadata.
+pub fn set_validator_metadata_payload(metadata: &ValidatorMetadata) -> Vec<u8> {
+    format!(
+        "(SetValidatorMetadata 0x{})",
+        hex::encode(metadata.encode())
+    )
+    .into_bytes()
+}
+
+/// Recover the validator publishing metadata, checking the metadata is well-formed and the nonce may be used.
+pub fn recover_validator_metadata_signer<T: Config>(
+    metadata: &ValidatorMetadata,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(ChainAccount, ValidatorKeys), Reason> {
+    require!(
+        [
+            &metadata.name,
+            &metadata.website,
+            &metadata.contact,
+            &metadata.security_policy_url
+        ]
+        .iter()
+        .all(|field| field.len() <= MAX_VALIDATOR_METADATA_LEN),
+        Reason::InvalidValidatorMetadata
+    );
+    let signer = signature
+        .recover_account(&prepend_nonce(&set_validator_metadata_payload(metadata), nonce)[..])
+        .map_err(|_| Reason::SignatureAccountMismatch)?;
+    let validator = get_validator::<T>(signer)?;
+    check_nonce::<T>(signer, nonce)?;
+    Ok((signer, validator))
+}
+
+/// Publish the metadata identifying who runs a validator, signed by its Eth key.
+pub fn set_validator_metadata<T: Config>(
+    metadata: ValidatorMetadata,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(), Reason> {
+    let (signer, validator) = recover_validator_metadata_signer::<T>(&metadata, signature, nonce)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    ValidatorMetadatas::insert(&validator.substrate_id, metadata);
+    use_nonce::<T>(signer, nonce);
+    <Module<T>>::deposit_event(Event::SetValidatorMetadata(validator.substrate_id));
+    Ok(())
+}
+
+/// Return each current validator, with the metadata it has published, if any.
+pub fn get_validator_metadata<T: Config>() -> Vec<(ValidatorKeys, Option<ValidatorMetadata>)> {
+    Validators::iter_values()
+        .map(|validator| {
+            let metadata = ValidatorMetadatas::get(&validator.substrate_id);
+            (validator, metadata)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn metadata(name: &str) -> ValidatorMetadata {
+        ValidatorMetadata {
+            name: name.as_bytes().to_vec(),
+            website: b"https://example.com".to_vec(),
+            contact: b"ops@example.com".to_vec(),
+            security_policy_url: b"https://example.com/security".to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_set_validator_metadata_payload() {
+        let payload = set_validator_metadata_payload(&metadata("Validator"));
+        assert!(payload.starts_with(b"(SetValidatorMetadata 0x24"));
+        assert_ne!(payload, set_validator_metadata_payload(&metadata("Other")));
+    }
+
+    #[test]
+    fn test_recover_validator_metadata_signer_rejects_bad_metadata() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let signature = ChainAccountSignature::Eth([1; 20], [0; 65]);
+            let long_name = "x".repeat(MAX_VALIDATOR_METADATA_LEN + 1);
+
+            assert_eq!(
+                recover_validator_metadata_signer::<Test>(&metadata(&long_name), signature, 0),
+                Err(Reason::InvalidValidatorMetadata)
+            );
+            assert_eq!(
+                recover_validator_metadata_signer::<Test>(&metadata("Validator"), signature, 0),
+                Err(Reason::SignatureAccountMismatch)
+            );
+        });
+    }
+}
@@ -0,0 +1,937 @@
+use crate::{
+    chains::{
+        Chain, ChainAsset, ChainBlock, ChainBlockEvent, ChainBlockEvents, ChainBlockNumber,
+        ChainBlockTally, ChainReorg, ChainReorgTally, Ethereum, FailedEventId,
+    },
+    core::simulate_governance,
+    log,
+    params::STORAGE_VERSION,
+    rates::InterestRateModel,
+    symbol::Symbol,
+    types::{
+        AssetAmount, AssetInfo, Bips, Decimals, GovernanceResult, LiquidityFactor, MinerShares,
+        SignersSet, SubstrateId, Ticker, Timestamp, USDQuantity, ValidatorKeys,
+    },
+    Config, FailedEvents, FirstBlock, IngressionQueue, LastProcessedBlock, NextValidators,
+    PendingChainBlocks, PendingChainReorgs, StorageVersion, SupportedAssets, Validators,
+};
+use codec::{alloc::string::String, Decode, DecodeAll, Encode, Input};
+use ethereum_client::{EthereumBlock, EthereumBlockNumber, EthereumEvent, EthereumHash};
+use frame_support::{
+    storage::{migration::take_storage_value, IterableStorageMap, StorageValue},
+    traits::Get,
+    weights::Weight,
+};
+use our_std::vec::Vec;
+use runtime_interfaces::validator_config_interface;
+
+/// Run each migration from the version storage is at up to the current version, returning the weight used.
+/// Migrations must be idempotent, so that running one against storage already migrated changes nothing.
+pub fn on_runtime_upgrade<T: Config>() -> Weight {
+    let db_weight = <T as frame_system::Config>::DbWeight::get();
+    let mut weight = db_weight.reads(1);
+    let mut version = StorageVersion::get();
+    while version < STORAGE_VERSION {
+        version += 1;
+        weight = weight.saturating_add(migrate_to::<T>(version));
+        StorageVersion::put(version);
+        weight = weight.saturating_add(db_weight.writes(1));
+        log!("Migrated pallet cash storage to version {}", version);
+    }
+    weight
+}
+
+/// Migrate storage from the version before the given one, returning the weight used.
+fn migrate_to<T: Config>(version: u32) -> Weight {
+    match version {
+        1 => migrate_asset_info_liquidation_incentive::<T>(),
+        2 => migrate_parameters::<T>(),
+        3 => migrate_validator_keys::<T>(),
+        4 => migrate_block_timestamps::<T>(),
+        _ => 0,
+    }
+}
+
+/// The layout of asset info before version 1, without a liquidation incentive.
+#[derive(Encode, Decode)]
+struct AssetInfoV0 {
+    asset: ChainAsset,
+    decimals: Decimals,
+    liquidity_factor: LiquidityFactor,
+    rate_model: InterestRateModel,
+    miner_shares: MinerShares,
+    supply_cap: AssetAmount,
+    symbol: Symbol,
+    ticker: Ticker,
+}
+
+/// Asset info decoded from either the layout before version 1, or the current layout.
+struct AnyAssetInfo(AssetInfo);
+
+impl Decode for AnyAssetInfo {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let v0 = AssetInfoV0::decode(input)?;
+        let liquidation_incentive = match input.remaining_len()? {
+            Some(0) => None,
+            _ => Option::<Bips>::decode(input)?,
+        };
+        Ok(AnyAssetInfo(AssetInfo {
+            asset: v0.asset,
+            decimals: v0.decimals,
+            liquidity_factor: v0.liquidity_factor,
+            rate_model: v0.rate_model,
+            miner_shares: v0.miner_shares,
+            supply_cap: v0.supply_cap,
+            symbol: v0.symbol,
+            ticker: v0.ticker,
+            liquidation_incentive,
+        }))
+    }
+}
+
+/// Version 1: add the per-asset liquidation incentive to asset info, falling back to the global one.
+fn migrate_asset_info_liquidation_incentive<T: Config>() -> Weight {
+    let mut count: Weight = 0;
+    SupportedAssets::translate::<AnyAssetInfo, _>(|_asset, info| {
+        count += 1;
+        Some(info.0)
+    });
+    <T as frame_system::Config>::DbWeight::get().reads_writes(count, count)
+}
+
+/// Version 2: move the global liquidation incentive and min tx value to the parameters pallet.
+fn migrate_parameters<T: Config>() -> Weight {
+    if let Some(incentive) = take_storage_value::<Bips>(b"Cash", b"GlobalLiquidationIncentive", &[])
+    {
+        pallet_parameters::GlobalLiquidationIncentive::put(incentive);
+    }
+    if let Some(value) = take_storage_value::<USDQuantity>(b"Cash", b"MinTxValue", &[]) {
+        pallet_parameters::MinTxValue::put(value.value);
+    }
+    <T as frame_system::Config>::DbWeight::get().reads_writes(2, 4)
+}
+
+/// The layout of validator keys before version 3, without a next Eth key.
+#[derive(Encode, Decode)]
+struct ValidatorKeysV2 {
+    substrate_id: SubstrateId,
+    eth_address: <Ethereum as Chain>::Address,
+}
+
+/// Validator keys decoded from either the layout before version 3, or the current layout.
+struct AnyValidatorKeys(ValidatorKeys);
+
+impl Decode for AnyValidatorKeys {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let v2 = ValidatorKeysV2::decode(input)?;
+        let next_eth_key = match input.remaining_len()? {
+            Some(0) => None,
+            _ => Option::<(<Ethereum as Chain>::Address, Timestamp)>::decode(input)?,
+        };
+        Ok(AnyValidatorKeys(ValidatorKeys {
+            substrate_id: v2.substrate_id,
+            eth_address: v2.eth_address,
+            next_eth_key,
+        }))
+    }
+}
+
+/// Version 3: add the advertised next Eth key to the keys of current and next validators.
+fn migrate_validator_keys<T: Config>() -> Weight {
+    let mut count: Weight = 0;
+    Validators::translate::<AnyValidatorKeys, _>(|_id, keys| {
+        count += 1;
+        Some(keys.0)
+    });
+    NextValidators::translate::<AnyValidatorKeys, _>(|_id, keys| {
+        count += 1;
+        Some(keys.0)
+    });
+    <T as frame_system::Config>::DbWeight::get().reads_writes(count, count)
+}
+
+/// The layout of Ethereum events before version 4, without a reference on locks.
+#[derive(Encode, Decode)]
+enum EthereumEventV3 {
+    Lock {
+        asset: [u8; 20],
+        sender: [u8; 20],
+        chain: String,
+        recipient: [u8; 32],
+        amount: u128,
+    },
+    LockCash {
+        sender: [u8; 20],
+        chain: String,
+        recipient: [u8; 32],
+        amount: u128,
+        principal: u128,
+    },
+    ExecTrxRequest {
+        account: [u8; 20],
+        trx_request: String,
+    },
+    ExecuteProposal {
+        title: String,
+        extrinsics: Vec<Vec<u8>>,
+    },
+    NoticeInvoked {
+        era_id: u32,
+        era_index: u32,
+        notice_hash: [u8; 32],
+        result: Vec<u8>,
+    },
+    NoticeRelayed {
+        notice_hash: [u8; 32],
+        relayer: [u8; 20],
+    },
+}
+
+/// The layout of Ethereum blocks before version 4, without a timestamp.
+#[derive(Encode, Decode)]
+struct EthereumBlockV3 {
+    hash: EthereumHash,
+    parent_hash: EthereumHash,
+    number: EthereumBlockNumber,
+    events: Vec<EthereumEventV3>,
+}
+
+/// The layout of underlying chain blocks before version 4.
+#[derive(Encode, Decode)]
+enum ChainBlockV3 {
+    Eth(EthereumBlockV3),
+    Matic(EthereumBlockV3),
+}
+
+/// The layout of block tallies before version 4.
+#[derive(Encode, Decode)]
+struct ChainBlockTallyV3 {
+    block: ChainBlockV3,
+    support: SignersSet,
+    dissent: SignersSet,
+}
+
+/// The layout of underlying chain reorgs before version 4.
+#[derive(Encode, Decode)]
+enum ChainReorgV3 {
+    Eth {
+        from_hash: EthereumHash,
+        to_hash: EthereumHash,
+        reverse_blocks: Vec<EthereumBlockV3>,
+        forward_blocks: Vec<EthereumBlockV3>,
+    },
+    Matic {
+        from_hash: EthereumHash,
+        to_hash: EthereumHash,
+        reverse_blocks: Vec<EthereumBlockV3>,
+        forward_blocks: Vec<EthereumBlockV3>,
+    },
+}
+
+/// The layout of reorg tallies before version 4.
+#[derive(Encode, Decode)]
+struct ChainReorgTallyV3 {
+    reorg: ChainReorgV3,
+    support: SignersSet,
+}
+
+/// The layout of an underlying chain event before version 4.
+#[derive(Encode, Decode)]
+enum ChainBlockEventV3 {
+    Reserved,
+    Eth(ChainBlockNumber, EthereumEventV3),
+    Matic(ChainBlockNumber, EthereumEventV3),
+}
+
+/// The layout of queued underlying chain events before version 4.
+#[derive(Encode, Decode)]
+enum ChainBlockEventsV3 {
+    Reserved,
+    Eth(Vec<(ChainBlockNumber, EthereumEventV3)>),
+    Matic(Vec<(ChainBlockNumber, EthereumEventV3)>),
+}
+
+/// A type containing underlying chain events, whose layout before version 4 is known.
+trait WithEventsV3: Decode + Sized {
+    type V3: Decode;
+
+    /// Upgrade from the layout before version 4, giving blocks no timestamp and locks no reference.
+    fn from_v3(old: Self::V3) -> Self;
+}
+
+impl WithEventsV3 for EthereumEvent {
+    type V3 = EthereumEventV3;
+
+    fn from_v3(old: EthereumEventV3) -> Self {
+        match old {
+            EthereumEventV3::Lock {
+                asset,
+                sender,
+                chain,
+                recipient,
+                amount,
+            } => EthereumEvent::Lock {
+                asset,
+                sender,
+                chain,
+                recipient,
+                amount,
+                reference: None,
+            },
+            EthereumEventV3::LockCash {
+                sender,
+                chain,
+                recipient,
+                amount,
+                principal,
+            } => EthereumEvent::LockCash {
+                sender,
+                chain,
+                recipient,
+                amount,
+                principal,
+            },
+            EthereumEventV3::ExecTrxRequest {
+                account,
+                trx_request,
+            } => EthereumEvent::ExecTrxRequest {
+                account,
+                trx_request,
+            },
+            EthereumEventV3::ExecuteProposal { title, extrinsics } => {
+                EthereumEvent::ExecuteProposal { title, extrinsics }
+            }
+            EthereumEventV3::NoticeInvoked {
+                era_id,
+                era_index,
+                notice_hash,
+                result,
+            } => EthereumEvent::NoticeInvoked {
+                era_id,
+                era_index,
+                notice_hash,
+                result,
+            },
+            EthereumEventV3::NoticeRelayed {
+                notice_hash,
+                relayer,
+            } => EthereumEvent::NoticeRelayed {
+                notice_hash,
+                relayer,
+            },
+        }
+    }
+}
+
+impl WithEventsV3 for EthereumBlock {
+    type V3 = EthereumBlockV3;
+
+    fn from_v3(old: EthereumBlockV3) -> Self {
+        EthereumBlock {
+            hash: old.hash,
+            parent_hash: old.parent_hash,
+            number: old.number,
+            timestamp: 0,
+            events: Vec::from_v3(old.events),
+        }
+    }
+}
+
+impl<B: WithEventsV3> WithEventsV3 for Vec<B> {
+    type V3 = Vec<B::V3>;
+
+    fn from_v3(old: Vec<B::V3>) -> Self {
+        old.into_iter().map(B::from_v3).collect()
+    }
+}
+
+impl<A: Decode, B: WithEventsV3> WithEventsV3 for (A, B) {
+    type V3 = (A, B::V3);
+
+    fn from_v3((a, b): (A, B::V3)) -> Self {
+        (a, B::from_v3(b))
+    }
+}
+
+impl WithEventsV3 for ChainBlockEvent {
+    type V3 = ChainBlockEventV3;
+
+    fn from_v3(old: ChainBlockEventV3) -> Self {
+        match old {
+            ChainBlockEventV3::Reserved => ChainBlockEvent::Reserved,
+            ChainBlockEventV3::Eth(number, event) => {
+                ChainBlockEvent::Eth(number, EthereumEvent::from_v3(event))
+            }
+            ChainBlockEventV3::Matic(number, event) => {
+                ChainBlockEvent::Matic(number, EthereumEvent::from_v3(event))
+            }
+        }
+    }
+}
+
+impl WithEventsV3 for ChainBlockEvents {
+    type V3 = ChainBlockEventsV3;
+
+    fn from_v3(old: ChainBlockEventsV3) -> Self {
+        match old {
+            ChainBlockEventsV3::Reserved => ChainBlockEvents::Reserved,
+            ChainBlockEventsV3::Eth(events) => ChainBlockEvents::Eth(Vec::from_v3(events)),
+            ChainBlockEventsV3::Matic(events) => ChainBlockEvents::Matic(Vec::from_v3(events)),
+        }
+    }
+}
+
+impl WithEventsV3 for ChainBlock {
+    type V3 = ChainBlockV3;
+
+    fn from_v3(old: ChainBlockV3) -> Self {
+        match old {
+            ChainBlockV3::Eth(block) => ChainBlock::Eth(EthereumBlock::from_v3(block)),
+            ChainBlockV3::Matic(block) => ChainBlock::Matic(EthereumBlock::from_v3(block)),
+        }
+    }
+}
+
+impl WithEventsV3 for ChainBlockTally {
+    type V3 = ChainBlockTallyV3;
+
+    fn from_v3(old: ChainBlockTallyV3) -> Self {
+        ChainBlockTally {
+            block: ChainBlock::from_v3(old.block),
+            support: old.support,
+            dissent: old.dissent,
+        }
+    }
+}
+
+impl WithEventsV3 for ChainReorgTally {
+    type V3 = ChainReorgTallyV3;
+
+    fn from_v3(old: ChainReorgTallyV3) -> Self {
+        let reorg = match old.reorg {
+            ChainReorgV3::Eth {
+                from_hash,
+                to_hash,
+                reverse_blocks,
+                forward_blocks,
+            } => ChainReorg::Eth {
+                from_hash,
+                to_hash,
+                reverse_blocks: Vec::from_v3(reverse_blocks),
+                forward_blocks: Vec::from_v3(forward_blocks),
+            },
+            ChainReorgV3::Matic {
+                from_hash,
+                to_hash,
+                reverse_blocks,
+                forward_blocks,
+            } => ChainReorg::Matic {
+                from_hash,
+                to_hash,
+                reverse_blocks: Vec::from_v3(reverse_blocks),
+                forward_blocks: Vec::from_v3(forward_blocks),
+            },
+        };
+        ChainReorgTally {
+            reorg,
+            support: old.support,
+        }
+    }
+}
+
+/// A value decoded from either its current layout, or its layout before version 4.
+/// The timestamp and reference were added in the middle of a value, so the whole value is tried as each in turn.
+struct AnyEvents<V>(V);
+
+impl<V: WithEventsV3> Decode for AnyEvents<V> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let len = input
+            .remaining_len()?
+            .ok_or("Length of stored value is unknown")?;
+        let mut raw = vec![0; len];
+        input.read(&mut raw)?;
+        match V::decode_all(&raw) {
+            Ok(value) => Ok(AnyEvents(value)),
+            Err(_) => Ok(AnyEvents(V::from_v3(V::V3::decode_all(&raw)?))),
+        }
+    }
+}
+
+/// Version 4: add the timestamp to the underlying chain blocks kept in storage, and the reference to lock events.
+/// Blocks already stored are given none, which the next block received always follows plausibly.
+/// Locks already stored are given no reference, as they could not have carried one.
+/// Events are kept in blocks, and also in the ingression queue and failed events, which are migrated alike.
+fn migrate_block_timestamps<T: Config>() -> Weight {
+    let mut count: Weight = 0;
+    FirstBlock::translate::<AnyEvents<ChainBlock>, _>(|_chain_id, block| {
+        count += 1;
+        Some(block.0)
+    });
+    LastProcessedBlock::translate::<AnyEvents<ChainBlock>, _>(|_chain_id, block| {
+        count += 1;
+        Some(block.0)
+    });
+    PendingChainBlocks::translate::<AnyEvents<Vec<ChainBlockTally>>, _>(|_chain_id, tallies| {
+        count += 1;
+        Some(tallies.0)
+    });
+    PendingChainReorgs::translate::<AnyEvents<Vec<ChainReorgTally>>, _>(|_chain_id, tallies| {
+        count += 1;
+        Some(tallies.0)
+    });
+    IngressionQueue::translate::<AnyEvents<ChainBlockEvents>, _>(|_chain_id, events| {
+        count += 1;
+        Some(events.0)
+    });
+    FailedEvents::translate::<AnyEvents<Vec<(FailedEventId, ChainBlockEvent)>>, _>(
+        |_chain_id, events| {
+            count += 1;
+            Some(events.0)
+        },
+    );
+    <T as frame_system::Config>::DbWeight::get().reads_writes(count, count)
+}
+
+/// The validator config key for governance payloads to dispatch after trying an upgrade against live state,
+/// given as hex encoded calls separated by commas, e.g. `--env TRY_RUNTIME_GOVERNANCE=0x...,0x...`.
+pub const TRY_RUNTIME_GOVERNANCE: &str = "TRY_RUNTIME_GOVERNANCE";
+
+/// Check that every value of a storage map decodes as its current layout, e.g. after being migrated.
+fn ensure_decodable<V: Decode>(item: &[u8], err: &'static str) -> Result<(), &'static str> {
+    let mut prefix = sp_io::hashing::twox_128(b"Cash").to_vec();
+    prefix.extend(&sp_io::hashing::twox_128(item));
+    let mut key = prefix.clone();
+    while let Some(next) = sp_io::storage::next_key(&key) {
+        if !next.starts_with(&prefix) {
+            break;
+        }
+        match sp_io::storage::get(&next) {
+            Some(raw) if V::decode_all(&raw).is_ok() => (),
+            _ => {
+                log!("{}: {}", err, hex::encode(&next));
+                return Err(err);
+            }
+        }
+        key = next;
+    }
+    Ok(())
+}
+
+/// Parse the governance payloads to dispatch after trying an upgrade, if any are configured.
+fn governance_payloads() -> Result<Vec<Vec<u8>>, &'static str> {
+    match validator_config_interface::get(TRY_RUNTIME_GOVERNANCE) {
+        Some(payloads) => payloads
+            .split(',')
+            .map(|payload| hex::decode(payload.trim().trim_start_matches("0x")))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "Governance payloads must be hex encoded calls separated by commas"),
+        None => Ok(vec![]),
+    }
+}
+
+/// Check storage can be migrated by this runtime, before trying an upgrade against live state.
+pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+    if StorageVersion::get() > STORAGE_VERSION {
+        return Err("Storage is at a newer version than this runtime");
+    }
+    Ok(())
+}
+
+/// Check storage was migrated to the current version and layout, after trying an upgrade against live state.
+/// Any governance payloads configured are then dispatched against the migrated storage, and must succeed.
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+    if StorageVersion::get() != STORAGE_VERSION {
+        return Err("Storage was not migrated to the current version");
+    }
+    ensure_decodable::<AssetInfo>(b"SupportedAssets", "Asset info failed to decode")?;
+    ensure_decodable::<ValidatorKeys>(b"Validators", "Validator keys failed to decode")?;
+    ensure_decodable::<ValidatorKeys>(b"NextValidators", "Next validator keys failed to decode")?;
+    ensure_decodable::<ChainBlock>(b"FirstBlock", "First blocks failed to decode")?;
+    ensure_decodable::<ChainBlock>(b"LastProcessedBlock", "Last blocks failed to decode")?;
+    ensure_decodable::<Vec<ChainBlockTally>>(
+        b"PendingChainBlocks",
+        "Pending chain blocks failed to decode",
+    )?;
+    ensure_decodable::<Vec<ChainReorgTally>>(
+        b"PendingChainReorgs",
+        "Pending chain reorgs failed to decode",
+    )?;
+    ensure_decodable::<ChainBlockEvents>(b"IngressionQueue", "Queued events failed to decode")?;
+    ensure_decodable::<Vec<(FailedEventId, ChainBlockEvent)>>(
+        b"FailedEvents",
+        "Failed events failed to decode",
+    )?;
+
+    let payloads = governance_payloads()?;
+    for (payload, result) in payloads
+        .iter()
+        .zip(simulate_governance::<T>(payloads.clone()))
+    {
+        log!(
+            "Governance payload {} result: {:?}",
+            hex::encode(payload),
+            result
+        );
+        if result != GovernanceResult::DispatchSuccess {
+            return Err("Governance payload failed to dispatch");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        symbol::USD,
+        tests::{assets::*, *},
+        types::Quantity,
+    };
+    use frame_support::storage::{migration::put_storage_value, unhashed, StorageMap};
+
+    fn put_v0_asset_info(info: AssetInfo) {
+        let v0 = AssetInfoV0 {
+            asset: info.asset,
+            decimals: info.decimals,
+            liquidity_factor: info.liquidity_factor,
+            rate_model: info.rate_model,
+            miner_shares: info.miner_shares,
+            supply_cap: info.supply_cap,
+            symbol: info.symbol,
+            ticker: info.ticker,
+        };
+        unhashed::put_raw(&SupportedAssets::hashed_key_for(info.asset), &v0.encode());
+    }
+
+    #[test]
+    fn test_on_runtime_upgrade() {
+        new_test_ext().execute_with(|| {
+            let eth_info = AssetInfo {
+                liquidation_incentive: None,
+                ..eth
+            };
+            let wbtc_info = AssetInfo {
+                liquidation_incentive: Some(1000),
+                ..wbtc
+            };
+            put_v0_asset_info(eth_info);
+            SupportedAssets::insert(Wbtc, wbtc_info);
+            unhashed::put_raw(
+                &FirstBlock::hashed_key_for(ChainId::Eth),
+                &ChainBlockV3::Eth(EthereumBlockV3 {
+                    hash: [1; 32],
+                    parent_hash: [0; 32],
+                    number: 1,
+                    events: vec![],
+                })
+                .encode(),
+            );
+            let first_block = ChainBlock::Eth(EthereumBlock {
+                hash: [1; 32],
+                parent_hash: [0; 32],
+                number: 1,
+                timestamp: 0,
+                events: vec![],
+            });
+            assert_eq!(StorageVersion::get(), 0);
+
+            on_runtime_upgrade::<Test>();
+            assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+            assert_eq!(SupportedAssets::get(Eth), Some(eth_info));
+            assert_eq!(SupportedAssets::get(Wbtc), Some(wbtc_info));
+            assert_eq!(FirstBlock::get(ChainId::Eth), Some(first_block.clone()));
+
+            // Already up to date, and idempotent if run again
+            on_runtime_upgrade::<Test>();
+            StorageVersion::put(0);
+            on_runtime_upgrade::<Test>();
+            assert_eq!(SupportedAssets::get(Eth), Some(eth_info));
+            assert_eq!(SupportedAssets::get(Wbtc), Some(wbtc_info));
+            assert_eq!(FirstBlock::get(ChainId::Eth), Some(first_block));
+        });
+    }
+
+    #[test]
+    fn test_migrate_parameters() {
+        new_test_ext().execute_with(|| {
+            put_storage_value(b"Cash", b"GlobalLiquidationIncentive", &[], 1000 as Bips);
+            put_storage_value(
+                b"Cash",
+                b"MinTxValue",
+                &[],
+                Quantity::from_nominal("5", USD),
+            );
+            StorageVersion::put(1);
+
+            on_runtime_upgrade::<Test>();
+            assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+            assert_eq!(pallet_parameters::GlobalLiquidationIncentive::get(), 1000);
+            assert_eq!(pallet_parameters::MinTxValue::get(), 5_000000);
+            assert_eq!(
+                take_storage_value::<Bips>(b"Cash", b"GlobalLiquidationIncentive", &[]),
+                None
+            );
+
+            // Already moved, and idempotent if run again
+            StorageVersion::put(1);
+            on_runtime_upgrade::<Test>();
+            assert_eq!(pallet_parameters::GlobalLiquidationIncentive::get(), 1000);
+            assert_eq!(pallet_parameters::MinTxValue::get(), 5_000000);
+        });
+    }
+
+    #[test]
+    fn test_migrate_validator_keys() {
+        new_test_ext().execute_with(|| {
+            let current = ValidatorKeysV2 {
+                substrate_id: [1; 32].into(),
+                eth_address: [1; 20],
+            };
+            let next = ValidatorKeysV2 {
+                substrate_id: [2; 32].into(),
+                eth_address: [2; 20],
+            };
+            unhashed::put_raw(
+                &Validators::hashed_key_for(&current.substrate_id),
+                &current.encode(),
+            );
+            unhashed::put_raw(
+                &NextValidators::hashed_key_for(&next.substrate_id),
+                &next.encode(),
+            );
+            let advertised = ValidatorKeys {
+                substrate_id: [3; 32].into(),
+                eth_address: [3; 20],
+                next_eth_key: Some(([4; 20], 1000)),
+            };
+            Validators::insert(&advertised.substrate_id, &advertised);
+            StorageVersion::put(2);
+
+            on_runtime_upgrade::<Test>();
+            assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+            let expected = ValidatorKeys {
+                substrate_id: [1; 32].into(),
+                eth_address: [1; 20],
+                next_eth_key: None,
+            };
+            assert_eq!(
+                Validators::get(&expected.substrate_id),
+                Some(expected.clone())
+            );
+            assert_eq!(
+                NextValidators::get(&next.substrate_id),
+                Some(ValidatorKeys {
+                    substrate_id: [2; 32].into(),
+                    eth_address: [2; 20],
+                    next_eth_key: None,
+                })
+            );
+            assert_eq!(
+                Validators::get(&advertised.substrate_id),
+                Some(advertised.clone())
+            );
+
+            // Already migrated, and idempotent if run again
+            StorageVersion::put(2);
+            on_runtime_upgrade::<Test>();
+            assert_eq!(Validators::get(&expected.substrate_id), Some(expected));
+            assert_eq!(Validators::get(&advertised.substrate_id), Some(advertised));
+        });
+    }
+
+    #[test]
+    fn test_migrate_block_timestamps() {
+        new_test_ext().execute_with(|| {
+            let old_lock = || EthereumEventV3::Lock {
+                asset: [2; 20],
+                sender: [3; 20],
+                chain: String::from("ETH"),
+                recipient: [4; 32],
+                amount: 500,
+            };
+            let new_lock = || EthereumEvent::Lock {
+                asset: [2; 20],
+                sender: [3; 20],
+                chain: String::from("ETH"),
+                recipient: [4; 32],
+                amount: 500,
+                reference: None,
+            };
+            let old_block = |number: u64| EthereumBlockV3 {
+                hash: [number as u8; 32],
+                parent_hash: [number as u8 - 1; 32],
+                number,
+                events: vec![
+                    old_lock(),
+                    EthereumEventV3::NoticeRelayed {
+                        notice_hash: [number as u8; 32],
+                        relayer: [5; 20],
+                    },
+                ],
+            };
+            let new_block = |number: u64| EthereumBlock {
+                hash: [number as u8; 32],
+                parent_hash: [number as u8 - 1; 32],
+                number,
+                timestamp: 0,
+                events: vec![
+                    new_lock(),
+                    EthereumEvent::NoticeRelayed {
+                        notice_hash: [number as u8; 32],
+                        relayer: [5; 20],
+                    },
+                ],
+            };
+            let support: SignersSet = vec![[1; 32].into()].into_iter().collect();
+            unhashed::put_raw(
+                &LastProcessedBlock::hashed_key_for(ChainId::Eth),
+                &ChainBlockV3::Eth(old_block(1)).encode(),
+            );
+            unhashed::put_raw(
+                &PendingChainBlocks::hashed_key_for(ChainId::Eth),
+                &vec![ChainBlockTallyV3 {
+                    block: ChainBlockV3::Eth(old_block(2)),
+                    support: support.clone(),
+                    dissent: SignersSet::new(),
+                }]
+                .encode(),
+            );
+            unhashed::put_raw(
+                &PendingChainReorgs::hashed_key_for(ChainId::Eth),
+                &vec![ChainReorgTallyV3 {
+                    reorg: ChainReorgV3::Eth {
+                        from_hash: [2; 32],
+                        to_hash: [4; 32],
+                        reverse_blocks: vec![old_block(2)],
+                        forward_blocks: vec![old_block(3), old_block(4)],
+                    },
+                    support: support.clone(),
+                }]
+                .encode(),
+            );
+            unhashed::put_raw(
+                &IngressionQueue::hashed_key_for(ChainId::Eth),
+                &ChainBlockEventsV3::Eth(vec![(1, old_lock())]).encode(),
+            );
+            unhashed::put_raw(
+                &FailedEvents::hashed_key_for(ChainId::Eth),
+                &vec![(0 as FailedEventId, ChainBlockEventV3::Eth(1, old_lock()))].encode(),
+            );
+            let referenced_lock = EthereumEvent::Lock {
+                asset: [2; 20],
+                sender: [3; 20],
+                chain: String::from("ETH"),
+                recipient: [4; 32],
+                amount: 500,
+                reference: Some([6; 32]),
+            };
+            let current = ChainBlock::Matic(EthereumBlock {
+                timestamp: 1_600_000_000,
+                events: vec![referenced_lock.clone()],
+                ..new_block(5)
+            });
+            LastProcessedBlock::insert(ChainId::Matic, current.clone());
+            IngressionQueue::insert(
+                ChainId::Matic,
+                ChainBlockEvents::Matic(vec![(5, referenced_lock.clone())]),
+            );
+            StorageVersion::put(3);
+
+            on_runtime_upgrade::<Test>();
+            assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+            let expected_blocks = vec![ChainBlockTally {
+                block: ChainBlock::Eth(new_block(2)),
+                support: support.clone(),
+                dissent: SignersSet::new(),
+            }];
+            let expected_reorgs = vec![ChainReorgTally {
+                reorg: ChainReorg::Eth {
+                    from_hash: [2; 32],
+                    to_hash: [4; 32],
+                    reverse_blocks: vec![new_block(2)],
+                    forward_blocks: vec![new_block(3), new_block(4)],
+                },
+                support,
+            }];
+            assert_eq!(
+                LastProcessedBlock::get(ChainId::Eth),
+                Some(ChainBlock::Eth(new_block(1)))
+            );
+            assert_eq!(
+                LastProcessedBlock::get(ChainId::Matic),
+                Some(current.clone())
+            );
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth), expected_blocks);
+            assert_eq!(PendingChainReorgs::get(ChainId::Eth), expected_reorgs);
+            let expected_queue = ChainBlockEvents::Eth(vec![(1, new_lock())]);
+            let expected_failed = vec![(0, ChainBlockEvent::Eth(1, new_lock()))];
+            let current_queue = ChainBlockEvents::Matic(vec![(5, referenced_lock)]);
+            assert_eq!(
+                IngressionQueue::get(ChainId::Eth),
+                Some(expected_queue.clone())
+            );
+            assert_eq!(
+                IngressionQueue::get(ChainId::Matic),
+                Some(current_queue.clone())
+            );
+            assert_eq!(FailedEvents::get(ChainId::Eth), expected_failed);
+
+            // Already migrated, and idempotent if run again
+            StorageVersion::put(3);
+            on_runtime_upgrade::<Test>();
+            assert_eq!(
+                LastProcessedBlock::get(ChainId::Eth),
+                Some(ChainBlock::Eth(new_block(1)))
+            );
+            assert_eq!(LastProcessedBlock::get(ChainId::Matic), Some(current));
+            assert_eq!(PendingChainBlocks::get(ChainId::Eth), expected_blocks);
+            assert_eq!(PendingChainReorgs::get(ChainId::Eth), expected_reorgs);
+            assert_eq!(IngressionQueue::get(ChainId::Eth), Some(expected_queue));
+            assert_eq!(IngressionQueue::get(ChainId::Matic), Some(current_queue));
+            assert_eq!(FailedEvents::get(ChainId::Eth), expected_failed);
+        });
+    }
+
+    #[test]
+    fn test_upgrade_checks() {
+        new_test_ext().execute_with(|| {
+            put_v0_asset_info(eth);
+            SupportedAssets::insert(Wbtc, wbtc);
+            assert_eq!(pre_upgrade::<Test>(), Ok(()));
+            assert_eq!(
+                post_upgrade::<Test>(),
+                Err("Storage was not migrated to the current version")
+            );
+
+            // A migration which forgot to translate some values is caught
+            StorageVersion::put(STORAGE_VERSION);
+            assert_eq!(post_upgrade::<Test>(), Err("Asset info failed to decode"));
+
+            StorageVersion::put(0);
+            on_runtime_upgrade::<Test>();
+            assert_eq!(post_upgrade::<Test>(), Ok(()));
+
+            // Blocks stored before the layout with timestamps are caught, until migrated
+            unhashed::put_raw(
+                &LastProcessedBlock::hashed_key_for(ChainId::Eth),
+                &ChainBlockV3::Eth(EthereumBlockV3 {
+                    hash: [1; 32],
+                    parent_hash: [0; 32],
+                    number: 1,
+                    events: vec![],
+                })
+                .encode(),
+            );
+            assert_eq!(post_upgrade::<Test>(), Err("Last blocks failed to decode"));
+            StorageVersion::put(3);
+            on_runtime_upgrade::<Test>();
+            assert_eq!(post_upgrade::<Test>(), Ok(()));
+
+            StorageVersion::put(STORAGE_VERSION + 1);
+            assert_eq!(
+                pre_upgrade::<Test>(),
+                Err("Storage is at a newer version than this runtime")
+            );
+        });
+    }
+}
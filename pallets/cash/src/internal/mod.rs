@@ -1,16 +1,41 @@
+pub mod accrual;
+pub mod activity;
+pub mod allowance;
 pub mod assets;
+pub mod auction;
+pub mod bad_debt;
 pub mod balance_helpers;
+pub mod bonding;
 pub mod change_validators;
+pub mod checkpoints;
+pub mod earnings;
+pub mod estimate;
 pub mod events;
 pub mod exec_trx_request;
+pub mod execution_fees;
 pub mod extract;
+pub mod failed_events;
+pub mod health;
+pub mod ingestion;
 pub mod initialize;
+pub mod insurance;
+pub mod invariants;
+pub mod link;
 pub mod liquidate;
+pub mod liveness;
 pub mod lock;
+pub mod metadata;
+pub mod migrations;
 pub mod miner;
 pub mod next_code;
+pub mod nonces;
 pub mod notices;
+pub mod parameters;
+pub mod pause;
+pub mod rewards;
 pub mod set_yield_next;
+pub mod slashing;
+pub mod stake;
 pub mod supply_cap;
 pub mod transfer;
 pub mod validate_trx;
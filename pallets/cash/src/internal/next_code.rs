@@ -1,18 +1,49 @@
 use crate::{
     chains::{Chain, Gateway},
+    params::NEXT_CODE_HASH_EXPIRY_BLOCKS,
     reason::Reason,
     require,
     types::CodeHash,
-    AllowedNextCodeHash, Config, Event, Module,
+    AllowedNextCodeHash, AllowedNextCodeHashExpiry, Config, Event, Module,
 };
 use frame_support::{dispatch::DispatchResultWithPostInfo, storage::StorageValue};
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// Return the current block number, which times the expiry of the allowed next code hash.
+fn current_block<T: Config>() -> u32 {
+    <frame_system::Module<T>>::block_number().unique_saturated_into()
+}
 
 pub fn allow_next_code_with_hash<T: Config>(hash: CodeHash) -> Result<(), Reason> {
     AllowedNextCodeHash::put(hash);
+    AllowedNextCodeHashExpiry::put(
+        current_block::<T>().saturating_add(NEXT_CODE_HASH_EXPIRY_BLOCKS),
+    );
     <Module<T>>::deposit_event(Event::AllowedNextCodeHash(hash));
     Ok(())
 }
 
+/// Revoke the allowed next code hash, before it is used or expires.
+pub fn revoke_next_code_hash<T: Config>() -> Result<(), Reason> {
+    let hash = AllowedNextCodeHash::get().ok_or(Reason::InvalidCodeHash)?;
+    AllowedNextCodeHash::kill();
+    AllowedNextCodeHashExpiry::kill();
+    <Module<T>>::deposit_event(Event::RevokedNextCodeHash(hash));
+    Ok(())
+}
+
+/// Check the hash of some code is the allowed next code hash, and has not expired.
+pub fn check_next_code_hash<T: Config>(hash: CodeHash) -> Result<(), Reason> {
+    require!(
+        Some(hash) == AllowedNextCodeHash::get(),
+        Reason::InvalidCodeHash
+    );
+    if let Some(expiry) = AllowedNextCodeHashExpiry::get() {
+        require!(current_block::<T>() <= expiry, Reason::NextCodeHashExpired);
+    }
+    Ok(())
+}
+
 #[cfg(not(test))]
 fn dispatch_call<T: Config>(code: Vec<u8>) -> DispatchResultWithPostInfo {
     use frame_support::traits::UnfilteredDispatchable;
@@ -22,11 +53,9 @@ fn dispatch_call<T: Config>(code: Vec<u8>) -> DispatchResultWithPostInfo {
 
 pub fn set_next_code_via_hash<T: Config>(code: Vec<u8>) -> Result<(), Reason> {
     let hash = <Gateway as Chain>::hash_bytes(&code);
-    require!(
-        Some(hash) == AllowedNextCodeHash::get(),
-        Reason::InvalidCodeHash
-    );
+    check_next_code_hash::<T>(hash)?;
     AllowedNextCodeHash::kill();
+    AllowedNextCodeHashExpiry::kill();
     let result = dispatch_call::<T>(code);
     <Module<T>>::deposit_event(Event::AttemptedSetCodeByHash(
         hash,
@@ -76,6 +105,49 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_next_code_hash_expires() {
+        new_test_ext().execute_with(|| {
+            let new_code = vec![1, 2, 3];
+            let hash = <Gateway as Chain>::hash_bytes(&new_code);
+            assert_eq!(allow_next_code_with_hash::<Test>(hash), Ok(()));
+            assert_eq!(
+                AllowedNextCodeHashExpiry::get(),
+                Some(1 + NEXT_CODE_HASH_EXPIRY_BLOCKS)
+            );
+
+            System::set_block_number(1 + NEXT_CODE_HASH_EXPIRY_BLOCKS as u64);
+            assert_eq!(check_next_code_hash::<Test>(hash), Ok(()));
+
+            System::set_block_number(2 + NEXT_CODE_HASH_EXPIRY_BLOCKS as u64);
+            assert_eq!(
+                set_next_code_via_hash::<Test>(new_code),
+                Err(Reason::NextCodeHashExpired)
+            );
+            assert_eq!(AllowedNextCodeHash::get(), Some(hash));
+        });
+    }
+
+    #[test]
+    fn test_revoke_next_code_hash() {
+        new_test_ext().execute_with(|| {
+            let new_code = vec![1, 2, 3];
+            let hash = <Gateway as Chain>::hash_bytes(&new_code);
+            assert_eq!(
+                revoke_next_code_hash::<Test>(),
+                Err(Reason::InvalidCodeHash)
+            );
+            assert_eq!(allow_next_code_with_hash::<Test>(hash), Ok(()));
+            assert_eq!(revoke_next_code_hash::<Test>(), Ok(()));
+            assert_eq!(AllowedNextCodeHash::get(), None);
+            assert_eq!(AllowedNextCodeHashExpiry::get(), None);
+            assert_eq!(
+                set_next_code_via_hash::<Test>(new_code),
+                Err(Reason::InvalidCodeHash)
+            );
+        });
+    }
+
     #[test]
     fn test_set_next_code_via_hash_mismatch() {
         new_test_ext().execute_with(|| {
@@ -0,0 +1,145 @@
+use crate::{
+    chains::ChainAccount, log, params::MAX_NONCE_WINDOW, reason::Reason, require, types::Nonce,
+    Config, Event, Module, NonceWindows, Nonces, UsedNonces,
+};
+use frame_support::storage::{StorageDoubleMap, StorageMap};
+
+/// Check that the account may use the given nonce, either as its next nonce or within its window.
+pub fn check_nonce<T: Config>(account: ChainAccount, nonce: Nonce) -> Result<(), Reason> {
+    let current_nonce = Nonces::get(account);
+    let window = NonceWindows::get(account);
+    require!(
+        nonce >= current_nonce
+            && nonce <= current_nonce.saturating_add(window)
+            && !UsedNonces::get(account, nonce),
+        Reason::IncorrectNonce(nonce, current_nonce)
+    );
+    Ok(())
+}
+
+/// Mark a nonce as used, advancing the next nonce past any which were already used out of order.
+pub fn use_nonce<T: Config>(account: ChainAccount, nonce: Nonce) {
+    let current_nonce = Nonces::get(account);
+    if nonce == current_nonce {
+        let mut next_nonce = current_nonce + 1;
+        while UsedNonces::take(account, next_nonce) {
+            next_nonce += 1;
+        }
+        Nonces::insert(account, next_nonce);
+    } else {
+        UsedNonces::insert(account, nonce, true);
+    }
+}
+
+/// Cancel an unused nonce, so that no request signed with it can be executed.
+/// A request may also cancel its own nonce, which is then used up by the request itself.
+pub fn cancel_nonce<T: Config>(
+    account: ChainAccount,
+    nonce: Nonce,
+    request_nonce: Option<Nonce>,
+) -> Result<(), Reason> {
+    log!("cancel_nonce: {:?} {}", account, nonce);
+    if request_nonce != Some(nonce) {
+        check_nonce::<T>(account, nonce)?;
+        use_nonce::<T>(account, nonce);
+    }
+    <Module<T>>::deposit_event(Event::NonceCancelled(account, nonce));
+    Ok(())
+}
+
+/// Set how many nonces beyond the next one an account accepts out of order.
+pub fn set_nonce_window<T: Config>(account: ChainAccount, window: Nonce) -> Result<(), Reason> {
+    require!(window <= MAX_NONCE_WINDOW, Reason::NonceWindowTooLarge);
+    if window == 0 {
+        NonceWindows::remove(account);
+    } else {
+        NonceWindows::insert(account, window);
+    }
+    <Module<T>>::deposit_event(Event::NonceWindowSet(account, window));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    const ACCOUNT: ChainAccount = ChainAccount::Eth([1; 20]);
+
+    #[test]
+    fn test_sequential_nonces() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(check_nonce::<Test>(ACCOUNT, 0), Ok(()));
+            assert_eq!(
+                check_nonce::<Test>(ACCOUNT, 1),
+                Err(Reason::IncorrectNonce(1, 0))
+            );
+            use_nonce::<Test>(ACCOUNT, 0);
+            assert_eq!(Nonces::get(ACCOUNT), 1);
+            assert_eq!(
+                check_nonce::<Test>(ACCOUNT, 0),
+                Err(Reason::IncorrectNonce(0, 1))
+            );
+        });
+    }
+
+    #[test]
+    fn test_nonce_window() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_nonce_window::<Test>(ACCOUNT, MAX_NONCE_WINDOW + 1),
+                Err(Reason::NonceWindowTooLarge)
+            );
+            assert_eq!(set_nonce_window::<Test>(ACCOUNT, 2), Ok(()));
+
+            assert_eq!(check_nonce::<Test>(ACCOUNT, 2), Ok(()));
+            assert_eq!(
+                check_nonce::<Test>(ACCOUNT, 3),
+                Err(Reason::IncorrectNonce(3, 0))
+            );
+
+            use_nonce::<Test>(ACCOUNT, 2);
+            use_nonce::<Test>(ACCOUNT, 1);
+            assert_eq!(Nonces::get(ACCOUNT), 0);
+            assert_eq!(
+                check_nonce::<Test>(ACCOUNT, 1),
+                Err(Reason::IncorrectNonce(1, 0))
+            );
+
+            use_nonce::<Test>(ACCOUNT, 0);
+            assert_eq!(Nonces::get(ACCOUNT), 3);
+            assert!(!UsedNonces::contains_key(ACCOUNT, 1));
+            assert!(!UsedNonces::contains_key(ACCOUNT, 2));
+        });
+    }
+
+    #[test]
+    fn test_cancel_nonce() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(set_nonce_window::<Test>(ACCOUNT, 4), Ok(()));
+            assert_eq!(cancel_nonce::<Test>(ACCOUNT, 3, Some(0)), Ok(()));
+            assert_eq!(
+                check_nonce::<Test>(ACCOUNT, 3),
+                Err(Reason::IncorrectNonce(3, 0))
+            );
+            assert_eq!(
+                cancel_nonce::<Test>(ACCOUNT, 3, Some(0)),
+                Err(Reason::IncorrectNonce(3, 0))
+            );
+
+            // cancelling its own nonce leaves it to be used by the request
+            assert_eq!(cancel_nonce::<Test>(ACCOUNT, 0, Some(0)), Ok(()));
+            assert_eq!(Nonces::get(ACCOUNT), 0);
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert_eq!(
+                events,
+                vec![
+                    mock::Event::pallet_cash(crate::Event::NonceWindowSet(ACCOUNT, 4)),
+                    mock::Event::pallet_cash(crate::Event::NonceCancelled(ACCOUNT, 3)),
+                    mock::Event::pallet_cash(crate::Event::NonceCancelled(ACCOUNT, 0)),
+                ]
+            );
+        });
+    }
+}
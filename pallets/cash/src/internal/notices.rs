@@ -1,30 +1,182 @@
 use crate::{
-    chains::{ChainAccount, ChainAsset, ChainHash, ChainId, ChainSignature},
-    core::recover_validator,
+    chains::{
+        Chain, ChainAccount, ChainAsset, ChainHash, ChainId, ChainSignature, ChainSignatureList,
+        ChainStarport, Ethereum,
+    },
+    core::{get_starport, recover_validator},
+    debug,
+    events::fetch_gas_estimate,
+    has_requisite_signatures,
+    internal::{self, execution_fees::assign_execution_fee},
     log,
     notices::{
-        CashExtractionNotice, ChangeAuthorityNotice, EncodeNotice, ExtractionNotice,
-        FutureYieldNotice, Notice, NoticeId, NoticeState, SetSupplyCapNotice,
+        encode_invoke_calldata, CashExtractionNotice, ChangeAuthorityNotice, EncodeNotice,
+        EncodedNotice, ExtractionBatchNotice, ExtractionNotice, FutureYieldNotice, Notice,
+        NoticeId, NoticeState, SetSupplyCapNotice,
     },
+    params::MAX_EXTRACTION_BATCH_SIZE,
     require,
     types::{
         AssetAmount, AssetQuantity, CashIndex, CashPrincipalAmount, Reason, Timestamp,
         ValidatorKeys, APR,
     },
-    AccountNotices, Call, Config, Event, LatestNotice, Module, NoticeHashes, NoticeHolds,
-    NoticeStates, Notices,
+    AccountNotices, Call, Config, Event, ExtractionBatchSize, LatestNotice, Module, NoticeHashes,
+    NoticeHolds, NoticeStates, Notices, PendingExtractions, ThresholdSigners, Validators,
+};
+use frame_support::storage::{
+    IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
 };
-use frame_support::storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap};
 use frame_system::offchain::SubmitTransaction;
+use our_std::log::targets::NOTICES;
+use sp_runtime::offchain::storage::StorageValueRef;
 
 pub fn dispatch_extraction_notice<T: Config>(
     asset: ChainAsset,
     recipient: ChainAccount,
     amount: AssetQuantity,
+) {
+    let batch_size = ExtractionBatchSize::get();
+    if batch_size < 2 {
+        return dispatch_single_extraction_notice::<T>(asset, recipient, amount.value);
+    }
+
+    // Queue the extraction, dispatching the batch as soon as it is full
+    let chain_id = recipient.chain_id();
+    let mut pending = PendingExtractions::get(chain_id);
+    pending.push((asset, recipient, amount.value));
+    if pending.len() >= batch_size as usize {
+        PendingExtractions::remove(chain_id);
+        dispatch_extraction_batch_notice::<T>(chain_id, pending);
+    } else {
+        PendingExtractions::insert(chain_id, pending);
+    }
+}
+
+/// Dispatch the extractions still waiting to be batched, at the end of each block.
+pub fn flush_extraction_batches<T: Config>() {
+    let pending: Vec<(ChainId, Vec<(ChainAsset, ChainAccount, AssetAmount)>)> =
+        PendingExtractions::iter().collect();
+    for (chain_id, extractions) in pending {
+        PendingExtractions::remove(chain_id);
+        dispatch_extraction_batch_notice::<T>(chain_id, extractions);
+    }
+}
+
+/// Set the maximum number of extractions per chain which are combined into a single notice.
+pub fn set_extraction_batch_size<T: Config>(size: u32) -> Result<(), Reason> {
+    require!(
+        size <= MAX_EXTRACTION_BATCH_SIZE,
+        Reason::ExtractionBatchTooLarge
+    );
+    ExtractionBatchSize::put(size);
+    <Module<T>>::deposit_event(Event::SetExtractionBatchSize(size));
+    Ok(())
+}
+
+/// Set the threshold key which signs notices for a chain on behalf of the validators, or unset it.
+/// The Starport is told to change its authority to the threshold key, or back to the validators,
+///  by a notice which is itself signed as before the change. Only notices dispatched afterwards are affected.
+pub fn set_threshold_signer<T: Config>(
+    chain_id: ChainId,
+    signer: Option<ChainAccount>,
+) -> Result<(), Reason> {
+    let new_authorities = match signer {
+        Some(signer) => {
+            require!(signer.chain_id() == chain_id, Reason::ChainMismatch);
+            match signer {
+                ChainAccount::Eth(address) | ChainAccount::Matic(address) => vec![address],
+                _ => return Err(Reason::NotImplemented),
+            }
+        }
+        None => Validators::iter_values()
+            .map(|validator| validator.eth_address)
+            .collect(),
+    };
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    if signer.is_some() || ThresholdSigners::contains_key(chain_id) {
+        dispatch_chain_change_authority_notice::<T>(chain_id, new_authorities);
+    }
+    match signer {
+        Some(signer) => ThresholdSigners::insert(chain_id, signer),
+        None => ThresholdSigners::remove(chain_id),
+    }
+    <Module<T>>::deposit_event(Event::SetThresholdSigner(chain_id, signer));
+    Ok(())
+}
+
+fn dispatch_extraction_batch_notice<T: Config>(
+    chain_id: ChainId,
+    extractions: Vec<(ChainAsset, ChainAccount, AssetAmount)>,
+) {
+    if let [(asset, recipient, amount)] = extractions[..] {
+        return dispatch_single_extraction_notice::<T>(asset, recipient, amount);
+    }
+    if extractions.is_empty() {
+        return;
+    }
+
+    let mut recipients: Vec<ChainAccount> = extractions.iter().map(|(_, r, _)| *r).collect();
+    recipients.sort();
+    recipients.dedup();
+    dispatch_notice::<T>(chain_id, &recipients, false, &|notice_id, parent_hash| {
+        let (mut assets, mut amounts, mut accounts) = (vec![], vec![], vec![]);
+        Notice::ExtractionBatchNotice(match parent_hash {
+            ChainHash::Eth(eth_parent_hash) => {
+                for (asset, recipient, amount) in extractions.iter() {
+                    match (asset, recipient) {
+                        (ChainAsset::Eth(eth_asset), ChainAccount::Eth(eth_account)) => {
+                            assets.push(*eth_asset);
+                            amounts.push(*amount);
+                            accounts.push(*eth_account);
+                        }
+
+                        _ => panic!("XXX not implemented"), // generate these w/ macros?
+                    }
+                }
+                ExtractionBatchNotice::Eth {
+                    id: notice_id,
+                    parent: eth_parent_hash,
+                    assets,
+                    amounts,
+                    accounts,
+                }
+            }
+            ChainHash::Matic(eth_parent_hash) => {
+                for (asset, recipient, amount) in extractions.iter() {
+                    match (asset, recipient) {
+                        (ChainAsset::Matic(eth_asset), ChainAccount::Matic(eth_account)) => {
+                            assets.push(*eth_asset);
+                            amounts.push(*amount);
+                            accounts.push(*eth_account);
+                        }
+
+                        _ => panic!("XXX not implemented"), // generate these w/ macros?
+                    }
+                }
+                ExtractionBatchNotice::Matic {
+                    id: notice_id,
+                    parent: eth_parent_hash,
+                    assets,
+                    amounts,
+                    accounts,
+                }
+            }
+
+            _ => panic!("XXX not implemented"), // generate these w/ macros?
+        })
+    })
+}
+
+fn dispatch_single_extraction_notice<T: Config>(
+    asset: ChainAsset,
+    recipient: ChainAccount,
+    amount: AssetAmount,
 ) {
     dispatch_notice::<T>(
         recipient.chain_id(),
-        Some(recipient),
+        &[recipient],
         false,
         &|notice_id, parent_hash| {
             Notice::ExtractionNotice(match (asset, recipient, parent_hash) {
@@ -37,7 +189,7 @@ pub fn dispatch_extraction_notice<T: Config>(
                     parent: eth_parent_hash,
                     asset: eth_asset,
                     account: eth_account,
-                    amount: amount.value,
+                    amount,
                 },
                 (
                     ChainAsset::Matic(eth_asset),
@@ -48,7 +200,7 @@ pub fn dispatch_extraction_notice<T: Config>(
                     parent: eth_parent_hash,
                     asset: eth_asset,
                     account: eth_account,
-                    amount: amount.value,
+                    amount,
                 },
 
                 _ => panic!("XXX not implemented"), // generate these w/ macros?
@@ -63,7 +215,7 @@ pub fn dispatch_cash_extraction_notice<T: Config>(
 ) {
     dispatch_notice::<T>(
         recipient.chain_id(),
-        Some(recipient),
+        &[recipient],
         false,
         &|notice_id, parent_hash| {
             Notice::CashExtractionNotice(match (recipient, parent_hash) {
@@ -93,7 +245,7 @@ pub fn dispatch_cash_extraction_notice<T: Config>(
 pub fn dispatch_supply_cap_notice<T: Config>(chain_asset: ChainAsset, cap: AssetAmount) {
     dispatch_notice::<T>(
         chain_asset.chain_id(),
-        None,
+        &[],
         true,
         &|notice_id, parent_hash| {
             Notice::SetSupplyCapNotice(match (chain_asset, parent_hash) {
@@ -127,7 +279,7 @@ pub fn dispatch_future_yield_notice<T: Config>(
 ) {
     // XXX for each chain id
     let chain_id = ChainId::Eth;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
+    dispatch_notice::<T>(chain_id, &[], true, &|notice_id, parent_hash| {
         Notice::FutureYieldNotice(match parent_hash {
             ChainHash::Eth(eth_parent_hash) => FutureYieldNotice::Eth {
                 id: notice_id,
@@ -142,7 +294,7 @@ pub fn dispatch_future_yield_notice<T: Config>(
     });
 
     let chain_id = ChainId::Matic;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
+    dispatch_notice::<T>(chain_id, &[], true, &|notice_id, parent_hash| {
         Notice::FutureYieldNotice(match parent_hash {
             ChainHash::Matic(eth_parent_hash) => FutureYieldNotice::Matic {
                 id: notice_id,
@@ -158,27 +310,30 @@ pub fn dispatch_future_yield_notice<T: Config>(
 }
 
 pub fn dispatch_change_authority_notice<T: Config>(validators: Vec<ValidatorKeys>) {
-    // XXX for each chain id
-    let chain_id = ChainId::Eth;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
+    let new_authorities: Vec<_> = validators.iter().map(|x| x.eth_address).collect();
+    for chain_id in [ChainId::Eth, ChainId::Matic].iter() {
+        // A chain whose notices are signed by a threshold key keeps the key as its authority
+        if !ThresholdSigners::contains_key(chain_id) {
+            dispatch_chain_change_authority_notice::<T>(*chain_id, new_authorities.clone());
+        }
+    }
+}
+
+fn dispatch_chain_change_authority_notice<T: Config>(
+    chain_id: ChainId,
+    new_authorities: Vec<<Ethereum as Chain>::Address>,
+) {
+    dispatch_notice::<T>(chain_id, &[], true, &|notice_id, parent_hash| {
         Notice::ChangeAuthorityNotice(match parent_hash {
             ChainHash::Eth(eth_parent_hash) => ChangeAuthorityNotice::Eth {
                 id: notice_id,
                 parent: eth_parent_hash,
-                new_authorities: validators.iter().map(|x| x.eth_address).collect::<Vec<_>>(),
+                new_authorities: new_authorities.clone(),
             },
-
-            _ => panic!("XXX not implemented"), // generate these w/ macros?
-        })
-    });
-
-    let chain_id = ChainId::Matic;
-    dispatch_notice::<T>(chain_id, None, true, &|notice_id, parent_hash| {
-        Notice::ChangeAuthorityNotice(match parent_hash {
             ChainHash::Matic(eth_parent_hash) => ChangeAuthorityNotice::Matic {
                 id: notice_id,
                 parent: eth_parent_hash,
-                new_authorities: validators.iter().map(|x| x.eth_address).collect::<Vec<_>>(),
+                new_authorities: new_authorities.clone(),
             },
 
             _ => panic!("XXX not implemented"), // generate these w/ macros?
@@ -190,7 +345,7 @@ pub fn dispatch_change_authority_notice<T: Config>(validators: Vec<ValidatorKeys
 /// Called from effects phase and thus may not fail.
 fn dispatch_notice<T: Config>(
     chain_id: ChainId,
-    maybe_recipient: Option<ChainAccount>,
+    recipients: &[ChainAccount],
     should_increment_era: bool,
     notice_fn: &dyn Fn(NoticeId, ChainHash) -> Notice,
 ) {
@@ -207,27 +362,42 @@ fn dispatch_notice<T: Config>(
     let notice = notice_fn(notice_id, parent_hash);
     let notice_hash = notice.hash();
     Notices::insert(chain_id, notice_id, &notice);
-    NoticeStates::insert(chain_id, notice_id, NoticeState::pending(&notice));
+    let notice_state = match ThresholdSigners::get(chain_id) {
+        Some(signer) => NoticeState::pending_threshold(signer),
+        None => NoticeState::pending(&notice),
+    };
+    NoticeStates::insert(chain_id, notice_id, notice_state);
     LatestNotice::insert(chain_id, (notice_id, notice_hash));
     NoticeHashes::insert(notice_hash, notice_id);
-    if let Some(recipient) = maybe_recipient {
+    for recipient in recipients {
         AccountNotices::append(recipient, notice_id);
     }
 
     if let Notice::ChangeAuthorityNotice(_) = &notice {
         NoticeHolds::insert(chain_id, notice_id);
     }
+    internal::liveness::track_notice_dispatched();
+
+    if matches!(
+        notice,
+        Notice::ExtractionNotice(_)
+            | Notice::CashExtractionNotice(_)
+            | Notice::ExtractionBatchNotice(_)
+    ) {
+        assign_execution_fee(chain_id, notice_id);
+    }
 
     // Deposit Notice Event
     let encoded_notice = notice.encode_notice();
     Module::<T>::deposit_event(Event::Notice(notice_id, notice, encoded_notice));
 }
 
+/// Mark a notice as executed, upon seeing the Starport's receipt for it on the underlying chain.
 pub fn handle_notice_invoked<T: Config>(
     chain_id: ChainId,
     notice_id: NoticeId,
     notice_hash: ChainHash,
-    _result: Vec<u8>,
+    result: Vec<u8>,
 ) -> Result<(), Reason> {
     require!(
         NoticeHashes::get(notice_hash) == Some(notice_id),
@@ -236,34 +406,90 @@ pub fn handle_notice_invoked<T: Config>(
     Notices::take(chain_id, notice_id);
     if let Some(notice_hold_id) = NoticeHolds::get(chain_id) {
         if notice_hold_id == notice_id {
-            log!("Removing notice hold as executed");
+            log!(target: NOTICES, "Removing notice hold as executed");
             NoticeHolds::take(chain_id);
         }
     }
-    NoticeStates::insert(chain_id, notice_id, NoticeState::Executed);
+    if NoticeStates::get(chain_id, notice_id) != NoticeState::Executed {
+        NoticeStates::insert(chain_id, notice_id, NoticeState::Executed);
+        <Module<T>>::deposit_event(Event::NoticeExecuted(chain_id, notice_id, result));
+    }
     Ok(())
 }
 
+/// Get the notices for an account, or any account linked to it, which are still awaiting execution,
+///  with the signatures collected so far.
+pub fn get_pending_notices<T: Config>(
+    account: ChainAccount,
+) -> Result<Vec<(NoticeId, EncodedNotice, ChainSignatureList)>, Reason> {
+    let mut pending = vec![];
+    for account in internal::link::get_logical_accounts::<T>(account) {
+        let chain_id = account.chain_id();
+        for notice_id in AccountNotices::get(account) {
+            if let NoticeState::Pending { signature_pairs } = NoticeStates::get(chain_id, notice_id)
+            {
+                let notice = Notices::get(chain_id, notice_id)
+                    .ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
+                pending.push((notice_id, notice.encode_notice(), signature_pairs));
+            }
+        }
+    }
+    Ok(pending)
+}
+
 fn process_notice_state<T: Config>(
     chain_id: ChainId,
     notice_id: NoticeId,
     notice_state: NoticeState,
 ) -> Result<bool, Reason> {
     match notice_state {
+        NoticeState::Pending {
+            signature_pairs: ChainSignatureList::Threshold(_, None),
+        } => {
+            let notice = Notices::get(chain_id, notice_id)
+                .ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
+            let signature = match chain_id.sign_threshold(&notice.encode_notice()) {
+                Ok(signature) => signature,
+                // Note: not every validator need hold a share of the threshold key
+                Err(Reason::KeyNotFound) => return Ok(false),
+                Err(err) => return Err(err),
+            };
+            log!(
+                target: NOTICES,
+                "Posting Threshold Signature for [{},{}]",
+                notice_id.0,
+                notice_id.1
+            );
+
+            let call = <Call<T>>::publish_signature(chain_id, notice_id, signature);
+            SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+                .map_err(|()| Reason::FailedToSubmitExtrinsic)?;
+            runtime_interfaces::metrics_interface::inc_counter("notices_signed", 1);
+
+            Ok(true)
+        }
         NoticeState::Pending { signature_pairs } => {
             let signer = chain_id.signer_address()?;
-            if !signature_pairs.has_signer(signer) {
+            if !signature_pairs.has_signer(signer) && !signature_pairs.has_threshold_signature() {
                 let notice = Notices::get(chain_id, notice_id)
                     .ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
                 let signature: ChainSignature = notice.sign_notice()?; // NO_COV_FAIL: key already checked
-                log!("Posting Signature for [{},{}]", notice_id.0, notice_id.1);
+                log!(target: NOTICES, "Posting Signature for [{},{}]", notice_id.0, notice_id.1);
 
                 let call = <Call<T>>::publish_signature(chain_id, notice_id, signature);
                 SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
                     .map_err(|()| Reason::FailedToSubmitExtrinsic)?; // NO_COV_FAIL: extrinsic is valid
+                runtime_interfaces::metrics_interface::inc_counter("notices_signed", 1);
 
                 Ok(true)
             } else {
+                if let Err(err) = estimate_notice_gas::<T>(chain_id, notice_id, signature_pairs) {
+                    debug!(
+                        target: NOTICES,
+                        "Failed to estimate gas for [{},{}]: {:?}",
+                        notice_id.0, notice_id.1, err
+                    );
+                }
                 Ok(false)
             }
         }
@@ -271,6 +497,77 @@ fn process_notice_state<T: Config>(
     }
 }
 
+fn notice_gas_estimate_key(chain_id: ChainId, notice_id: NoticeId) -> String {
+    format!(
+        "cash::notice_gas_estimate::{:?}::{}::{}",
+        chain_id, notice_id.0, notice_id.1
+    )
+}
+
+/// Remember how much gas relaying a fully signed notice would use, so it may be simulated later.
+fn estimate_notice_gas<T: Config>(
+    chain_id: ChainId,
+    notice_id: NoticeId,
+    signature_pairs: ChainSignatureList,
+) -> Result<(), Reason> {
+    let key = notice_gas_estimate_key(chain_id, notice_id);
+    let krf = StorageValueRef::persistent(key.as_bytes());
+    if let Some(Some(_)) = krf.get::<u64>() {
+        return Ok(());
+    }
+
+    // Note: the Starport rejects the notice without a quorum, so there is nothing to estimate yet
+    let validators: Vec<_> = Validators::iter().map(|v| v.1).collect();
+    let notice_state = NoticeState::Pending { signature_pairs };
+    if !has_requisite_signatures(notice_state.clone(), &validators) {
+        return Ok(());
+    }
+
+    let (starport, calldata) = get_invoke_calldata::<T>(chain_id, notice_id, notice_state)?;
+    let gas_estimate = fetch_gas_estimate(chain_id, starport, &calldata)?;
+    log!(
+        target: NOTICES,
+        "Estimated gas for [{},{}]: {}",
+        notice_id.0,
+        notice_id.1,
+        gas_estimate
+    );
+    krf.set(&gas_estimate);
+    Ok(())
+}
+
+fn get_invoke_calldata<T: Config>(
+    chain_id: ChainId,
+    notice_id: NoticeId,
+    notice_state: NoticeState,
+) -> Result<(ChainStarport, Vec<u8>), Reason> {
+    let notice =
+        Notices::get(chain_id, notice_id).ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
+    let signature_pairs = match notice_state {
+        NoticeState::Pending { signature_pairs } => signature_pairs,
+        _ => return Err(Reason::NoticeMissing(chain_id, notice_id)),
+    };
+    let starport = get_starport::<T>(chain_id)?;
+    let calldata = encode_invoke_calldata(&notice.encode_notice(), &signature_pairs)?;
+    Ok((starport, calldata))
+}
+
+/// Get the Starport and the exact calldata which would relay a notice with the signatures collected so far,
+/// along with the gas it was last estimated to use by the offchain worker, if any.
+pub fn simulate_notice<T: Config>(
+    chain_id: ChainId,
+    notice_id: NoticeId,
+) -> Result<(ChainStarport, Vec<u8>, Option<u64>), Reason> {
+    let notice_state = NoticeStates::get(chain_id, notice_id);
+    let (starport, calldata) = get_invoke_calldata::<T>(chain_id, notice_id, notice_state)?;
+    let key = notice_gas_estimate_key(chain_id, notice_id);
+    let gas_estimate = match StorageValueRef::persistent(key.as_bytes()).get::<u64>() {
+        Some(Some(gas_estimate)) => Some(gas_estimate),
+        _ => None,
+    };
+    Ok((starport, calldata, gas_estimate))
+}
+
 pub fn process_notices<T: Config>(_block_number: T::BlockNumber) -> (usize, usize, Vec<Reason>) {
     NoticeStates::iter().fold((0, 0, vec![]), |(succ, skip, mut fail), (chain_id, notice_id, notice_state)| {
         match process_notice_state::<T>(chain_id, notice_id, notice_state) {
@@ -284,22 +581,62 @@ pub fn process_notices<T: Config>(_block_number: T::BlockNumber) -> (usize, usiz
     })
 }
 
+/// Recover the validator signing a notice, which must be signed by its current Eth key.
+/// A validator's next key is not yet an authority on the Starports, so cannot sign notices.
+pub fn recover_notice_signer<T: Config>(
+    notice: &Notice,
+    signature: ChainSignature,
+) -> Result<ValidatorKeys, Reason> {
+    let message = notice.encode_notice();
+    let validator = recover_validator::<T>(&message, signature)?;
+    match signature.recover(&message)? {
+        ChainAccount::Eth(address) | ChainAccount::Matic(address)
+            if address == validator.eth_address =>
+        {
+            Ok(validator)
+        }
+        _ => Err(Reason::SignatureAccountMismatch),
+    }
+}
+
 pub fn publish_signature<T: Config>(
     chain_id: ChainId,
     notice_id: NoticeId,
     signature: ChainSignature,
 ) -> Result<(), Reason> {
-    log!("Publishing Signature: [{},{}]", notice_id.0, notice_id.1);
+    log!(target: NOTICES, "Publishing Signature: [{},{}]", notice_id.0, notice_id.1);
 
     match NoticeStates::get(chain_id, notice_id) {
         NoticeState::Missing => Ok(()),
 
+        NoticeState::Pending {
+            signature_pairs: ChainSignatureList::Threshold(signer, threshold_signature),
+        } => {
+            if threshold_signature.is_some() {
+                return Ok(());
+            }
+
+            let notice = Notices::get(chain_id, notice_id)
+                .ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
+            let mut signature_pairs = ChainSignatureList::Threshold(signer, None);
+            signature_pairs
+                .add_threshold_signature(&signature, signature.recover(&notice.encode_notice())?)?;
+
+            NoticeStates::insert(
+                chain_id,
+                notice_id,
+                NoticeState::Pending { signature_pairs },
+            );
+
+            Ok(())
+        }
+
         NoticeState::Pending {
             mut signature_pairs,
         } => {
             let notice = Notices::get(chain_id, notice_id)
                 .ok_or(Reason::NoticeMissing(chain_id, notice_id))?;
-            let validator = recover_validator::<T>(&notice.encode_notice(), signature)?;
+            let validator = recover_notice_signer::<T>(&notice, signature)?;
 
             if signature_pairs.has_validator_signature(signature.chain_id(), &validator) {
                 return Ok(());
@@ -312,6 +649,7 @@ pub fn publish_signature<T: Config>(
                 notice_id,
                 NoticeState::Pending { signature_pairs },
             );
+            internal::liveness::track_notice_signed(&validator.substrate_id);
 
             Ok(())
         }
@@ -406,6 +744,68 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_dispatch_extraction_notice_batches() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let asset = ChainAsset::Eth([1; 20]);
+            let alice = ChainAccount::Eth([2; 20]);
+            let bob = ChainAccount::Eth([3; 20]);
+            let amount = qty!("1", ETH);
+
+            assert_eq!(
+                set_extraction_batch_size::<Test>(MAX_EXTRACTION_BATCH_SIZE + 1),
+                Err(Reason::ExtractionBatchTooLarge)
+            );
+            assert_eq!(set_extraction_batch_size::<Test>(3), Ok(()));
+
+            dispatch_extraction_notice::<Test>(asset, alice, amount);
+            dispatch_extraction_notice::<Test>(asset, bob, amount);
+            assert_eq!(LatestNotice::get(chain_id), None);
+            assert_eq!(PendingExtractions::get(chain_id).len(), 2);
+
+            // the batch is dispatched as soon as it is full
+            dispatch_extraction_notice::<Test>(asset, alice, amount);
+            assert_eq!(PendingExtractions::get(chain_id), vec![]);
+            assert_eq!(
+                Notices::get(chain_id, NoticeId(0, 1)),
+                Some(Notice::ExtractionBatchNotice(ExtractionBatchNotice::Eth {
+                    id: NoticeId(0, 1),
+                    parent: [0u8; 32],
+                    assets: vec![[1; 20], [1; 20], [1; 20]],
+                    amounts: vec![amount.value, amount.value, amount.value],
+                    accounts: vec![[2; 20], [3; 20], [2; 20]],
+                }))
+            );
+            assert_eq!(AccountNotices::get(alice), vec![NoticeId(0, 1)]);
+            assert_eq!(AccountNotices::get(bob), vec![NoticeId(0, 1)]);
+
+            // a lone extraction left at the end of the block gets a regular notice
+            let (_, batch_hash) = LatestNotice::get(chain_id).unwrap();
+            let parent = match batch_hash {
+                ChainHash::Eth(hash) => hash,
+                _ => panic!("expected eth hash"),
+            };
+            dispatch_extraction_notice::<Test>(asset, bob, amount);
+            flush_extraction_batches::<Test>();
+            assert_eq!(PendingExtractions::get(chain_id), vec![]);
+            assert_eq!(
+                Notices::get(chain_id, NoticeId(0, 2)),
+                Some(Notice::ExtractionNotice(ExtractionNotice::Eth {
+                    id: NoticeId(0, 2),
+                    parent,
+                    asset: [1; 20],
+                    amount: amount.value,
+                    account: [3; 20],
+                }))
+            );
+            assert_eq!(
+                AccountNotices::get(bob),
+                vec![NoticeId(0, 1), NoticeId(0, 2)]
+            );
+        });
+    }
+
     #[test]
     fn test_handle_notice_invoked_when_notice_missing() {
         new_test_ext().execute_with(|| {
@@ -428,6 +828,47 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_handle_notice_invoked_deposits_event_once() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let notice_id = NoticeId(5, 6);
+            let notice_hash = ChainHash::Eth([1; 32]);
+
+            NoticeHashes::insert(notice_hash, notice_id);
+            NoticeStates::insert(
+                chain_id,
+                notice_id,
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Eth(vec![]),
+                },
+            );
+
+            assert_eq!(
+                handle_notice_invoked::<Test>(chain_id, notice_id, notice_hash, vec![1, 2]),
+                Ok(())
+            );
+            assert_eq!(
+                handle_notice_invoked::<Test>(chain_id, notice_id, notice_hash, vec![1, 2]),
+                Ok(())
+            );
+            assert_eq!(
+                NoticeStates::get(chain_id, notice_id),
+                NoticeState::Executed
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert_eq!(
+                events,
+                vec![mock::Event::pallet_cash(crate::Event::NoticeExecuted(
+                    chain_id,
+                    notice_id,
+                    vec![1, 2]
+                ))]
+            );
+        });
+    }
+
     #[test]
     fn test_handle_notice_invoked_mismatched_notice() {
         new_test_ext().execute_with(|| {
@@ -468,6 +909,45 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_pending_notices() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let asset = ChainAsset::Eth([1; 20]);
+            let account = ChainAccount::Eth([2; 20]);
+            let amount = qty!("1", ETH);
+
+            assert_eq!(get_pending_notices::<Test>(account), Ok(vec![]));
+
+            dispatch_extraction_notice::<Test>(asset, account, amount);
+            dispatch_extraction_notice::<Test>(asset, account, amount);
+            let first = Notices::get(chain_id, NoticeId(0, 1)).unwrap();
+            let second = Notices::get(chain_id, NoticeId(0, 2)).unwrap();
+            let signature_pairs = ChainSignatureList::Eth(vec![([9; 20], [8; 65])]);
+            NoticeStates::insert(
+                chain_id,
+                NoticeId(0, 2),
+                NoticeState::Pending {
+                    signature_pairs: signature_pairs.clone(),
+                },
+            );
+            assert_eq!(
+                get_pending_notices::<Test>(account),
+                Ok(vec![
+                    (
+                        NoticeId(0, 1),
+                        first.encode_notice(),
+                        ChainSignatureList::Eth(vec![])
+                    ),
+                    (NoticeId(0, 2), second.encode_notice(), signature_pairs),
+                ])
+            );
+
+            NoticeStates::insert(chain_id, NoticeId(0, 1), NoticeState::Executed);
+            assert_eq!(get_pending_notices::<Test>(account).unwrap().len(), 1);
+        });
+    }
+
     /** `process_notice_state` tests **/
 
     // Currently, the env vars set in other tests make this very difficult to test
@@ -608,6 +1088,55 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_simulate_notice() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: notice_id,
+                parent: [3u8; 32],
+                asset: [1; 20],
+                amount: 100,
+                account: [2; 20],
+            });
+            let signature_pairs = ChainSignatureList::Eth(vec![([1; 20], [4; 65])]);
+
+            assert_eq!(
+                simulate_notice::<Test>(chain_id, notice_id),
+                Err(Reason::NoticeMissing(chain_id, notice_id))
+            );
+
+            Notices::insert(chain_id, notice_id, &notice);
+            NoticeStates::insert(
+                chain_id,
+                notice_id,
+                NoticeState::Pending {
+                    signature_pairs: signature_pairs.clone(),
+                },
+            );
+            assert_eq!(
+                simulate_notice::<Test>(chain_id, notice_id),
+                Err(Reason::StarportMissing)
+            );
+
+            let starport = ChainAccount::Eth(ETH_STARPORT_ADDR);
+            crate::Starports::insert(chain_id, starport);
+            let calldata = encode_invoke_calldata(&notice.encode_notice(), &signature_pairs);
+            assert_eq!(
+                simulate_notice::<Test>(chain_id, notice_id),
+                Ok((starport, calldata.clone().unwrap(), None))
+            );
+
+            StorageValueRef::persistent(notice_gas_estimate_key(chain_id, notice_id).as_bytes())
+                .set(&54321u64);
+            assert_eq!(
+                simulate_notice::<Test>(chain_id, notice_id),
+                Ok((starport, calldata.unwrap(), Some(54321)))
+            );
+        });
+    }
+
     /** `publish_signature` tests **/
 
     #[test]
@@ -671,6 +1200,7 @@ mod tests {
                 ValidatorKeys {
                     substrate_id,
                     eth_address: signer,
+                    next_eth_key: None,
                 },
             );
 
@@ -743,6 +1273,7 @@ mod tests {
                 ValidatorKeys {
                     substrate_id,
                     eth_address: signer,
+                    next_eth_key: None,
                 },
             );
 
@@ -784,6 +1315,7 @@ mod tests {
                 ValidatorKeys {
                     substrate_id,
                     eth_address: signer,
+                    next_eth_key: None,
                 },
             );
 
@@ -855,4 +1387,124 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_threshold_signer_pends_notices_for_threshold_signature() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let signer = ChainAccount::Eth([7; 20]);
+
+            // The change of authority is itself signed by the authority it replaces
+            let latest_change_authority = || {
+                let (notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
+                match Notices::get(ChainId::Eth, notice_id) {
+                    Some(Notice::ChangeAuthorityNotice(ChangeAuthorityNotice::Eth {
+                        new_authorities,
+                        ..
+                    })) => (new_authorities, NoticeStates::get(ChainId::Eth, notice_id)),
+                    notice => panic!("Not a change of authority: {:?}", notice),
+                }
+            };
+            assert_eq!(
+                set_threshold_signer::<Test>(ChainId::Matic, Some(signer)),
+                Err(Reason::ChainMismatch)
+            );
+            assert_ok!(set_threshold_signer::<Test>(ChainId::Eth, Some(signer)));
+            assert_eq!(
+                latest_change_authority(),
+                (
+                    vec![[7; 20]],
+                    NoticeState::Pending {
+                        signature_pairs: ChainSignatureList::Eth(vec![])
+                    }
+                )
+            );
+
+            // Changing validators leaves the threshold key as the authority
+            let latest_notice = LatestNotice::get(ChainId::Eth);
+            dispatch_change_authority_notice::<Test>(Validators::iter_values().collect());
+            assert_eq!(LatestNotice::get(ChainId::Eth), latest_notice);
+
+            dispatch_cash_extraction_notice::<Test>(
+                ChainAccount::Eth([2; 20]),
+                CashPrincipalAmount(10),
+            );
+            let (notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
+            assert_eq!(
+                NoticeStates::get(ChainId::Eth, notice_id),
+                NoticeState::pending_threshold(signer)
+            );
+
+            assert_ok!(set_threshold_signer::<Test>(ChainId::Eth, None));
+            assert_eq!(
+                latest_change_authority(),
+                (
+                    Validators::iter_values()
+                        .map(|validator| validator.eth_address)
+                        .collect::<Vec<_>>(),
+                    NoticeState::pending_threshold(signer)
+                )
+            );
+
+            dispatch_cash_extraction_notice::<Test>(
+                ChainAccount::Eth([2; 20]),
+                CashPrincipalAmount(10),
+            );
+            let (notice_id, _) = LatestNotice::get(ChainId::Eth).unwrap();
+            assert_eq!(
+                NoticeStates::get(ChainId::Eth, notice_id),
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Eth(vec![])
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_publish_signature_threshold() {
+        new_test_ext().execute_with(|| {
+            let chain_id = ChainId::Eth;
+            let notice_id = NoticeId(5, 6);
+            let notice = Notice::ExtractionNotice(ExtractionNotice::Eth {
+                id: NoticeId(80, 1),
+                parent: [3u8; 32],
+                asset: [1; 20],
+                amount: 100,
+                account: [2; 20],
+            });
+            let signature = notice.sign_notice().unwrap();
+            let signer = ChainAccount::Eth(<Ethereum as Chain>::signer_address().unwrap());
+            Notices::insert(chain_id, notice_id, notice);
+
+            // Note: the threshold key need not belong to any validator
+            NoticeStates::insert(
+                chain_id,
+                notice_id,
+                NoticeState::pending_threshold(ChainAccount::Eth([7; 20])),
+            );
+            assert_eq!(
+                publish_signature::<Test>(chain_id, notice_id, signature),
+                Err(Reason::SignatureAccountMismatch)
+            );
+
+            NoticeStates::insert(chain_id, notice_id, NoticeState::pending_threshold(signer));
+            assert_eq!(
+                publish_signature::<Test>(chain_id, notice_id, signature),
+                Ok(())
+            );
+            let notice_state = NoticeStates::get(chain_id, notice_id);
+            assert_eq!(
+                notice_state,
+                NoticeState::Pending {
+                    signature_pairs: ChainSignatureList::Threshold(signer, Some(signature))
+                }
+            );
+            assert!(crate::has_requisite_signatures(notice_state, &vec![]));
+
+            assert_eq!(
+                publish_signature::<Test>(chain_id, notice_id, ChainSignature::Eth([1; 65])),
+                Ok(())
+            );
+        });
+    }
 }
@@ -0,0 +1,26 @@
+use crate::{
+    symbol::USD,
+    types::{Bips, Quantity, USDQuantity},
+};
+use frame_support::storage::StorageValue;
+use pallet_parameters::{GlobalLiquidationIncentive, IngressSlack, MinTxValue, SessionPeriod};
+
+/// Return the number of blocks in between periodic sessions.
+pub fn get_session_period() -> u32 {
+    SessionPeriod::get()
+}
+
+/// Return the maximum size of the block queue before we back-off sending new blocks.
+pub fn get_ingress_slack() -> u32 {
+    IngressSlack::get()
+}
+
+/// Return the minimum value (USD) required across all protocol interactions.
+pub fn get_min_tx_value() -> USDQuantity {
+    Quantity::new(MinTxValue::get(), USD)
+}
+
+/// Return the liquidation incentive on seized collateral, for collateral which does not set its own.
+pub fn get_global_liquidation_incentive() -> Bips {
+    GlobalLiquidationIncentive::get()
+}
@@ -0,0 +1,123 @@
+use crate::{
+    chains::{ChainAccount, ChainAccountSignature},
+    internal::{
+        exec_trx_request::prepend_nonce,
+        nonces::{check_nonce, use_nonce},
+    },
+    log,
+    reason::Reason,
+    require,
+    types::Nonce,
+    Config, EmergencyPauseActive, Event, Module, PauseGuardian,
+};
+use frame_support::storage::StorageValue;
+
+/// The payload the pause guardian signs, with a nonce prepended, to pause the chain.
+pub const EMERGENCY_PAUSE_PAYLOAD: &[u8] = b"emergency_pause";
+
+/// Determine whether transaction requests and notice signing are currently halted.
+pub fn is_emergency_paused() -> bool {
+    EmergencyPauseActive::get()
+}
+
+/// Set the account allowed to trigger an emergency pause, or remove it.
+pub fn set_pause_guardian<T: Config>(guardian: Option<ChainAccount>) -> Result<(), Reason> {
+    match guardian {
+        Some(account) => PauseGuardian::put(account),
+        None => PauseGuardian::kill(),
+    }
+    <Module<T>>::deposit_event(Event::SetPauseGuardian(guardian));
+    Ok(())
+}
+
+/// Recover the pause guardian from its signature, checking that the nonce may be used.
+pub fn recover_pause_guardian<T: Config>(
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<ChainAccount, Reason> {
+    let signer = signature
+        .recover_account(&prepend_nonce(&EMERGENCY_PAUSE_PAYLOAD.to_vec(), nonce)[..])
+        .map_err(|_| Reason::SignatureAccountMismatch)?;
+    require!(
+        PauseGuardian::get() == Some(signer),
+        Reason::NotPauseGuardian
+    );
+    check_nonce::<T>(signer, nonce)?;
+    Ok(signer)
+}
+
+/// Halt transaction requests and notice signing, on behalf of the pause guardian.
+pub fn emergency_pause<T: Config>(
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(), Reason> {
+    let guardian = recover_pause_guardian::<T>(signature, nonce)?;
+    log!("Emergency pause by {:?}", guardian);
+    use_nonce::<T>(guardian, nonce);
+    EmergencyPauseActive::put(true);
+    <Module<T>>::deposit_event(Event::EmergencyPaused(guardian));
+    Ok(())
+}
+
+/// Resume transaction requests and notice signing.
+pub fn emergency_unpause<T: Config>() -> Result<(), Reason> {
+    log!("Emergency unpause");
+    EmergencyPauseActive::kill();
+    <Module<T>>::deposit_event(Event::EmergencyUnpaused);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_emergency_pause_and_unpause() {
+        new_test_ext().execute_with(|| {
+            let guardian_key = <Ethereum as Chain>::signer_address().unwrap();
+            let guardian = ChainAccount::Eth(guardian_key);
+            let message = prepend_nonce(&EMERGENCY_PAUSE_PAYLOAD.to_vec(), 0);
+            let full_message: Vec<u8> = [
+                format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes(),
+                &message[..],
+            ]
+            .concat();
+            let eth_key_id =
+                runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+            let signature = ChainAccountSignature::Eth(
+                guardian_key,
+                runtime_interfaces::keyring_interface::sign_one(full_message, eth_key_id).unwrap(),
+            );
+
+            assert_eq!(
+                emergency_pause::<Test>(signature, 0),
+                Err(Reason::NotPauseGuardian)
+            );
+
+            assert_eq!(set_pause_guardian::<Test>(Some(guardian)), Ok(()));
+            assert_eq!(
+                emergency_pause::<Test>(signature, 1),
+                Err(Reason::SignatureAccountMismatch)
+            );
+            assert_eq!(emergency_pause::<Test>(signature, 0), Ok(()));
+            assert!(is_emergency_paused());
+
+            // the signature cannot be replayed once used
+            assert_eq!(emergency_unpause::<Test>(), Ok(()));
+            assert!(!is_emergency_paused());
+            assert_eq!(
+                emergency_pause::<Test>(signature, 0),
+                Err(Reason::IncorrectNonce(0, 1))
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::EmergencyPaused(
+                    guardian
+                )))
+            );
+            assert!(events.contains(&mock::Event::pallet_cash(crate::Event::EmergencyUnpaused)));
+        });
+    }
+}
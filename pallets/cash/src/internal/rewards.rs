@@ -0,0 +1,192 @@
+use crate::{
+    chains::{ChainAccount, ChainAsset},
+    internal::{self, assets::get_asset},
+    params::MILLISECONDS_PER_DAY,
+    pipeline::CashPipeline,
+    reason::Reason,
+    require,
+    types::{
+        AssetIndex, AssetQuantity, BigUint, CashPrincipalAmount, CashPrincipalPerAsset,
+        RewardSpeeds, Timestamp,
+    },
+    AssetRewardSpeeds, Config, Event, Module,
+};
+use frame_support::storage::StorageMap;
+
+/// Set the CASH principal emitted each day as rewards to the suppliers and borrowers of an asset.
+pub fn set_reward_speeds<T: Config>(asset: ChainAsset, speeds: RewardSpeeds) -> Result<(), Reason> {
+    get_asset::<T>(asset)?;
+    internal::accrual::accrue_touched_asset::<T>(asset)?;
+    AssetRewardSpeeds::insert(asset, speeds);
+    <Module<T>>::deposit_event(Event::SetRewardSpeeds(asset, speeds));
+    Ok(())
+}
+
+/// Return the rewards accrued per unit of an asset, for a daily emission over some time shared by a total.
+/// Nothing is emitted while there is no total to share it.
+pub fn get_reward_per_asset(
+    speed: CashPrincipalAmount,
+    dt: Timestamp,
+    total: AssetQuantity,
+) -> Result<CashPrincipalPerAsset, Reason> {
+    if speed == CashPrincipalAmount::ZERO || total.value == 0 {
+        return Ok(AssetIndex::default());
+    }
+    Ok(AssetIndex(
+        BigUint::from_uint(speed.0)
+            .convert(CashPrincipalAmount::DECIMALS, AssetIndex::DECIMALS)
+            .mul_uint(dt as u128)
+            .div_uint(MILLISECONDS_PER_DAY as u128)?
+            .div_decimal(total.value, total.units.decimals)?
+            .to_uint()?,
+    ))
+}
+
+/// Accrue and pay out all the rewards earned by an account, as CASH.
+pub fn prepare_claim_rewards<T: Config>(
+    pipeline: CashPipeline,
+    account: ChainAccount,
+) -> Result<(CashPipeline, CashPrincipalAmount), Reason> {
+    let pipeline = pipeline.accrue_rewards::<T>(account)?;
+    let principal = pipeline.state.get_rewards_accrued::<T>(account);
+    require!(
+        principal > CashPrincipalAmount::ZERO,
+        Reason::NoRewardsAccrued
+    );
+    Ok((pipeline.claim_rewards::<T>(account, principal)?, principal))
+}
+
+/// Emit the event for a claim of rewards, once its effects are committed.
+pub fn finish_claim_rewards<T: Config>(account: ChainAccount, principal: CashPrincipalAmount) {
+    <Module<T>>::deposit_event(Event::ClaimRewards(account, principal));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{assets::*, common::*, *};
+
+    const SUPPLIER: ChainAccount = ChainAccount::Eth([1; 20]);
+    const BORROWER: ChainAccount = ChainAccount::Eth([2; 20]);
+
+    #[test]
+    fn test_get_reward_per_asset() {
+        assert_eq!(
+            get_reward_per_asset(
+                CashPrincipalAmount::from_nominal("100"),
+                MILLISECONDS_PER_DAY / 2,
+                Quantity::from_nominal("10", ETH)
+            ),
+            Ok(AssetIndex::from_nominal("5"))
+        );
+        assert_eq!(
+            get_reward_per_asset(
+                CashPrincipalAmount::from_nominal("100"),
+                MILLISECONDS_PER_DAY,
+                Quantity::from_nominal("0", ETH)
+            ),
+            Ok(AssetIndex::default())
+        );
+    }
+
+    #[test]
+    fn test_set_reward_speeds() {
+        new_test_ext().execute_with(|| {
+            let speeds = RewardSpeeds {
+                supply: CashPrincipalAmount::from_nominal("100"),
+                borrow: CashPrincipalAmount::from_nominal("50"),
+            };
+            assert_eq!(
+                set_reward_speeds::<Test>(Eth, speeds),
+                Err(Reason::AssetNotSupported)
+            );
+            assert_ok!(init_eth_asset());
+            assert_eq!(set_reward_speeds::<Test>(Eth, speeds), Ok(()));
+            assert_eq!(AssetRewardSpeeds::get(Eth), speeds);
+        });
+    }
+
+    #[test]
+    fn test_claim_rewards() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+            init_asset_balance(Eth, SUPPLIER, Balance::from_nominal("10", ETH).value);
+            init_asset_balance(Eth, BORROWER, Balance::from_nominal("-2", ETH).value);
+
+            assert_eq!(
+                prepare_claim_rewards::<Test>(CashPipeline::new(), SUPPLIER).map(|(_, p)| p),
+                Err(Reason::NoRewardsAccrued)
+            );
+
+            SupplyRewardIndices::insert(Eth, AssetIndex::from_nominal("1.5"));
+            BorrowRewardIndices::insert(Eth, AssetIndex::from_nominal("2"));
+            let total_cash_pre = TotalCashPrincipal::get();
+
+            let (pipeline, principal) =
+                prepare_claim_rewards::<Test>(CashPipeline::new(), SUPPLIER).unwrap();
+            pipeline.commit::<Test>();
+            finish_claim_rewards::<Test>(SUPPLIER, principal);
+            assert_eq!(principal, CashPrincipalAmount::from_nominal("15"));
+            assert_eq!(
+                CashPrincipals::get(SUPPLIER),
+                CashPrincipal::from_nominal("15")
+            );
+            assert_eq!(RewardsAccrued::get(SUPPLIER), CashPrincipalAmount::ZERO);
+            assert_eq!(
+                LastRewardIndices::get(Eth, SUPPLIER),
+                AssetIndex::from_nominal("1.5")
+            );
+
+            let (pipeline, principal) =
+                prepare_claim_rewards::<Test>(CashPipeline::new(), BORROWER).unwrap();
+            pipeline.commit::<Test>();
+            assert_eq!(principal, CashPrincipalAmount::from_nominal("4"));
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                total_cash_pre
+                    .add(CashPrincipalAmount::from_nominal("19"))
+                    .unwrap()
+            );
+
+            assert_eq!(
+                prepare_claim_rewards::<Test>(CashPipeline::new(), SUPPLIER).map(|(_, p)| p),
+                Err(Reason::NoRewardsAccrued)
+            );
+
+            let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+            assert!(
+                events.contains(&mock::Event::pallet_cash(crate::Event::ClaimRewards(
+                    SUPPLIER,
+                    CashPrincipalAmount::from_nominal("15")
+                )))
+            );
+        });
+    }
+
+    #[test]
+    fn test_rewards_accrue_on_balance_change() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+            init_asset_balance(Eth, SUPPLIER, Balance::from_nominal("10", ETH).value);
+            SupplyRewardIndices::insert(Eth, AssetIndex::from_nominal("1"));
+
+            CashPipeline::new()
+                .lock_asset::<Test>(SUPPLIER, Eth, Quantity::from_nominal("5", ETH))
+                .unwrap()
+                .commit::<Test>();
+            assert_eq!(
+                RewardsAccrued::get(SUPPLIER),
+                CashPrincipalAmount::from_nominal("10")
+            );
+            assert_eq!(
+                LastRewardIndices::get(Eth, SUPPLIER),
+                AssetIndex::from_nominal("1")
+            );
+
+            SupplyRewardIndices::insert(Eth, AssetIndex::from_nominal("2"));
+            let (_, principal) =
+                prepare_claim_rewards::<Test>(CashPipeline::new(), SUPPLIER).unwrap();
+            assert_eq!(principal, CashPrincipalAmount::from_nominal("25"));
+        });
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{
+    chains::{ChainAccount, ChainBlockNumber, ChainId},
+    internal,
+    params::MAX_VALIDATOR_OFFENSES,
+    reason::Reason,
+    types::{Timestamp, ValidatorKeys, ValidatorOffense},
+    Config, Event, Module, ValidatorOffenses, WithheldValidators,
+};
+use frame_support::storage::StorageMap;
+use our_std::log;
+
+/// Return the account a validator is expected to mine to, which holds its bond and tracks its earnings.
+pub fn get_validator_miner(validator: &ValidatorKeys) -> ChainAccount {
+    ChainAccount::Eth(validator.eth_address)
+}
+
+/// Record that a validator signed conflicting blocks of a chain at the same height.
+/// The validator's bond is slashed, and the earnings of blocks it authors withheld until governance pardons or removes it.
+pub fn report_equivocation<T: Config>(
+    validator: &ValidatorKeys,
+    chain_id: ChainId,
+    block_number: ChainBlockNumber,
+    now: Timestamp,
+) {
+    let mut offenses = ValidatorOffenses::get(&validator.substrate_id);
+    if offenses
+        .iter()
+        .any(|offense| offense.chain_id == chain_id && offense.block_number == block_number)
+    {
+        return;
+    }
+
+    log!(
+        "Validator equivocated: {:?} {:?} @ {}",
+        validator,
+        chain_id,
+        block_number
+    );
+    offenses.push(ValidatorOffense {
+        chain_id,
+        block_number,
+        timestamp: now,
+    });
+    if offenses.len() > MAX_VALIDATOR_OFFENSES {
+        offenses.drain(..offenses.len() - MAX_VALIDATOR_OFFENSES);
+    }
+
+    ValidatorOffenses::insert(&validator.substrate_id, offenses);
+    WithheldValidators::insert(&validator.substrate_id, true);
+    if let Err(reason) = internal::bonding::slash_bond::<T>(get_validator_miner(validator)) {
+        log!("Validator bond not slashed: {:?} {:?}", validator, reason);
+    }
+    <Module<T>>::deposit_event(Event::ValidatorEquivocated(
+        validator.substrate_id.clone(),
+        chain_id,
+        block_number,
+    ));
+}
+
+/// Clear the offenses of a validator, and stop withholding its mining earnings.
+pub fn pardon_validator<T: Config>(validator: ValidatorKeys) -> Result<(), Reason> {
+    ValidatorOffenses::remove(&validator.substrate_id);
+    WithheldValidators::remove(&validator.substrate_id);
+    <Module<T>>::deposit_event(Event::ValidatorPardoned(validator.substrate_id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_report_equivocation_and_pardon() {
+        new_test_ext().execute_with(|| {
+            let validator = ValidatorKeys {
+                substrate_id: AccountId32::new([1u8; 32]),
+                eth_address: [2u8; 20],
+                next_eth_key: None,
+            };
+
+            report_equivocation::<Test>(&validator, ChainId::Eth, 5, 100);
+            report_equivocation::<Test>(&validator, ChainId::Eth, 5, 200);
+            assert_eq!(
+                ValidatorOffenses::get(&validator.substrate_id),
+                vec![ValidatorOffense {
+                    chain_id: ChainId::Eth,
+                    block_number: 5,
+                    timestamp: 100,
+                }]
+            );
+            assert_eq!(WithheldValidators::get(&validator.substrate_id), true);
+
+            for block_number in 0..(MAX_VALIDATOR_OFFENSES as u64 + 1) {
+                report_equivocation::<Test>(&validator, ChainId::Eth, 10 + block_number, 300);
+            }
+            let offenses = ValidatorOffenses::get(&validator.substrate_id);
+            assert_eq!(offenses.len(), MAX_VALIDATOR_OFFENSES);
+            assert_eq!(offenses[0].block_number, 11);
+
+            assert_eq!(pardon_validator::<Test>(validator.clone()), Ok(()));
+            assert_eq!(ValidatorOffenses::get(&validator.substrate_id), vec![]);
+            assert_eq!(WithheldValidators::get(&validator.substrate_id), false);
+        });
+    }
+}
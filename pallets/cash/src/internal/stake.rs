@@ -0,0 +1,110 @@
+use crate::{reason::Reason, Config, Event, Module, StakeWeightedThresholds};
+use frame_support::storage::StorageValue;
+
+/// Switch signature thresholds to or from being weighted by validator stake, i.e. bonded CASH principal.
+pub fn set_stake_weighted_thresholds<T: Config>(enabled: bool) -> Result<(), Reason> {
+    StakeWeightedThresholds::put(enabled);
+    <Module<T>>::deposit_event(Event::SetStakeWeightedThresholds(enabled));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chains::has_weighted_super_majority,
+        core::get_signer_weights,
+        tests::*,
+        types::{CashPrincipalAmount, ValidatorKeys},
+        ValidatorBonds, Validators,
+    };
+    use our_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
+
+    #[test]
+    fn test_has_weighted_super_majority() {
+        let set = |ids: &[u8]| ids.iter().cloned().collect::<BTreeSet<u8>>();
+        let validators = set(&[1, 2, 3]);
+        let no_weights = BTreeMap::new();
+        assert!(has_weighted_super_majority(
+            &set(&[1, 2]),
+            &validators,
+            &no_weights
+        ));
+        assert!(!has_weighted_super_majority(
+            &set(&[1]),
+            &validators,
+            &no_weights
+        ));
+
+        let weights: BTreeMap<u8, u128> = vec![(1, 70), (2, 20), (3, 10)].into_iter().collect();
+        assert!(has_weighted_super_majority(
+            &set(&[1, 2]),
+            &validators,
+            &weights
+        ));
+        assert!(has_weighted_super_majority(
+            &set(&[1, 3]),
+            &validators,
+            &weights
+        ));
+        // A single validator holding most of the weight is still not enough signers
+        assert!(!has_weighted_super_majority(
+            &set(&[1]),
+            &validators,
+            &weights
+        ));
+        // Enough signers are not enough without enough of the weight
+        assert!(!has_weighted_super_majority(
+            &set(&[2, 3]),
+            &validators,
+            &weights
+        ));
+        assert!(has_weighted_super_majority(
+            &set(&[1, 2, 4]),
+            &set(&[1, 2]),
+            &weights
+        ));
+
+        // Validators with no bond weigh nothing, but must still be counted
+        let bonded_one: BTreeMap<u8, u128> = vec![(1, 1)].into_iter().collect();
+        assert!(!has_weighted_super_majority(
+            &set(&[1]),
+            &validators,
+            &bonded_one
+        ));
+        assert!(has_weighted_super_majority(
+            &set(&[1, 2]),
+            &validators,
+            &bonded_one
+        ));
+    }
+
+    #[test]
+    fn test_get_signer_weights() {
+        new_test_ext().execute_with(|| {
+            initialize_storage();
+            let validators: Vec<ValidatorKeys> = Validators::iter_values().collect();
+            let (bonded, unbonded) = (&validators[0], &validators[1]);
+            ValidatorBonds::insert(
+                ChainAccount::Eth(bonded.eth_address),
+                CashPrincipalAmount(100),
+            );
+            ValidatorBonds::insert(ChainAccount::Eth([9u8; 20]), CashPrincipalAmount(500));
+            assert_eq!(get_signer_weights::<Test>(), BTreeMap::new());
+
+            assert_ok!(set_stake_weighted_thresholds::<Test>(true));
+            assert_eq!(
+                get_signer_weights::<Test>(),
+                vec![
+                    (bonded.substrate_id.clone(), 100),
+                    (unbonded.substrate_id.clone(), 0)
+                ]
+                .into_iter()
+                .collect()
+            );
+
+            assert_ok!(set_stake_weighted_thresholds::<Test>(false));
+            assert_eq!(get_signer_weights::<Test>(), BTreeMap::new());
+        });
+    }
+}
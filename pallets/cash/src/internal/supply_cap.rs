@@ -1,10 +1,13 @@
 use crate::{
-    chains::ChainAsset,
+    chains::{ChainAsset, ChainId},
     internal,
     reason::Reason,
-    types::{AssetAmount, AssetInfo},
-    Config,
+    require,
+    symbol::USD,
+    types::{AssetAmount, AssetInfo, CashPrincipalAmount, MathError, USDQuantity},
+    ChainAssetCaps, ChainCashCaps, Config, Event, Module,
 };
+use frame_support::storage::StorageMap;
 
 pub fn set_supply_cap<T: Config>(asset: ChainAsset, cap: AssetAmount) -> Result<(), Reason> {
     let asset_info = internal::assets::get_asset::<T>(asset)?;
@@ -18,12 +21,75 @@ pub fn set_supply_cap<T: Config>(asset: ChainAsset, cap: AssetAmount) -> Result<
     Ok(())
 }
 
+/// Set or remove the cap on the CASH principal which may be held on a downstream chain.
+pub fn set_chain_cash_cap<T: Config>(
+    chain_id: ChainId,
+    cap: Option<CashPrincipalAmount>,
+) -> Result<(), Reason> {
+    require!(chain_id != ChainId::Gate, Reason::InvalidChain);
+    match cap {
+        Some(cap) => ChainCashCaps::insert(chain_id, cap),
+        None => ChainCashCaps::remove(chain_id),
+    }
+    <Module<T>>::deposit_event(Event::SetChainCashCap(chain_id, cap));
+    Ok(())
+}
+
+/// Set or remove the cap on the value of the assets which may be supplied from a downstream chain.
+pub fn set_chain_asset_cap<T: Config>(
+    chain_id: ChainId,
+    cap: Option<USDQuantity>,
+) -> Result<(), Reason> {
+    require!(chain_id != ChainId::Gate, Reason::InvalidChain);
+    match cap {
+        Some(cap) => {
+            if cap.units != USD {
+                Err(MathError::UnitsMismatch)?
+            }
+            ChainAssetCaps::insert(chain_id, cap)
+        }
+        None => ChainAssetCaps::remove(chain_id),
+    }
+    <Module<T>>::deposit_event(Event::SetChainAssetCap(chain_id, cap));
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{chains::*, notices::*, tests::*, types::*, LatestNotice, NoticeStates, Notices};
     use frame_support::storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap};
 
+    #[test]
+    fn test_set_chain_caps() {
+        new_test_ext().execute_with(|| {
+            let cash_cap = CashPrincipalAmount::from_nominal("1000");
+            let asset_cap = Quantity::from_nominal("1000", USD);
+            assert_eq!(
+                set_chain_cash_cap::<Test>(ChainId::Gate, Some(cash_cap)),
+                Err(Reason::InvalidChain)
+            );
+            assert_eq!(
+                set_chain_asset_cap::<Test>(ChainId::Eth, Some(Quantity::from_nominal("1", ETH))),
+                Err(Reason::MathError(MathError::UnitsMismatch))
+            );
+
+            assert_eq!(
+                set_chain_cash_cap::<Test>(ChainId::Eth, Some(cash_cap)),
+                Ok(())
+            );
+            assert_eq!(
+                set_chain_asset_cap::<Test>(ChainId::Eth, Some(asset_cap)),
+                Ok(())
+            );
+            assert_eq!(ChainCashCaps::get(ChainId::Eth), Some(cash_cap));
+            assert_eq!(ChainAssetCaps::get(ChainId::Eth), Some(asset_cap));
+
+            assert_eq!(set_chain_cash_cap::<Test>(ChainId::Eth, None), Ok(()));
+            assert_eq!(ChainCashCaps::get(ChainId::Eth), None);
+        });
+    }
+
     #[test]
     fn test_set_supply_cap_not_supported() {
         new_test_ext().execute_with(|| {
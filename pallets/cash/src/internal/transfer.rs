@@ -1,33 +1,80 @@
 use crate::{
     chains::ChainAccount,
-    internal::{assets::get_value, miner::get_some_miner},
-    params::{MIN_TX_VALUE, TRANSFER_FEE},
+    internal::{activity::record_exchange, assets::get_value, miner::get_some_miner},
+    params::{MAX_MIN_PRINCIPAL_GATE, TRANSFER_FEE},
     pipeline::CashPipeline,
     reason::Reason,
     require, require_min_tx_value,
-    types::{AssetInfo, AssetQuantity, CashPrincipalAmount},
-    Config, Event, GlobalCashIndex, Module,
+    types::{
+        ActivityKind, AssetInfo, AssetQuantity, CashOrChainAsset, CashPrincipal,
+        CashPrincipalAmount,
+    },
+    Config, Event, GlobalCashIndex, MinPrincipalGate, Module,
 };
 use frame_support::storage::StorageValue;
 
+/// Set the minimum CASH principal required in order to use a Gateway account.
+pub fn set_min_principal_gate<T: Config>(principal: CashPrincipal) -> Result<(), Reason> {
+    require!(
+        principal > CashPrincipal::ZERO && principal <= MAX_MIN_PRINCIPAL_GATE,
+        Reason::InvalidMinPrincipalGate
+    );
+    MinPrincipalGate::put(principal);
+    <Module<T>>::deposit_event(Event::SetMinPrincipalGate(principal));
+    Ok(())
+}
+
 pub fn transfer_internal<T: Config>(
     asset: AssetInfo,
     sender: ChainAccount,
     recipient: ChainAccount,
     amount: AssetQuantity,
 ) -> Result<(), Reason> {
+    prepare_transfer_internal::<T>(CashPipeline::new(), asset, sender, recipient, amount)?
+        .commit::<T>();
+    finish_transfer_internal::<T>(asset, sender, recipient, amount)
+}
+
+/// Add the effects of a transfer, including its fee, to a pipeline, without committing them.
+pub fn prepare_transfer_internal<T: Config>(
+    pipeline: CashPipeline,
+    asset: AssetInfo,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    amount: AssetQuantity,
+) -> Result<CashPipeline, Reason> {
     let miner = get_some_miner::<T>();
     let index = GlobalCashIndex::get();
     let fee_principal = index.cash_principal_amount(TRANSFER_FEE)?;
 
     require_min_tx_value!(get_value::<T>(amount)?);
 
-    CashPipeline::new()
+    pipeline
         .transfer_asset::<T>(sender, recipient, asset.asset, amount)?
+        .check_asset_unpaused::<T>(asset)?
         .transfer_cash::<T>(sender, miner, fee_principal)?
-        .check_collateralized::<T>(sender)?
-        .commit::<T>();
+        .check_collateralized::<T>(sender)
+}
 
+/// Emit the events for a transfer, once its effects are committed.
+pub fn finish_transfer_internal<T: Config>(
+    asset: AssetInfo,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    amount: AssetQuantity,
+) -> Result<(), Reason> {
+    let miner = get_some_miner::<T>();
+    let index = GlobalCashIndex::get();
+    let fee_principal = index.cash_principal_amount(TRANSFER_FEE)?;
+
+    record_exchange::<T>(
+        sender,
+        ActivityKind::Send,
+        recipient,
+        ActivityKind::Receive,
+        CashOrChainAsset::ChainAsset(asset.asset),
+        amount.value,
+    );
     <Module<T>>::deposit_event(Event::Transfer(
         asset.asset,
         sender,
@@ -45,6 +92,23 @@ pub fn transfer_cash_principal_internal<T: Config>(
     recipient: ChainAccount,
     principal: CashPrincipalAmount,
 ) -> Result<(), Reason> {
+    prepare_transfer_cash_principal_internal::<T>(
+        CashPipeline::new(),
+        sender,
+        recipient,
+        principal,
+    )?
+    .commit::<T>();
+    finish_transfer_cash_principal_internal::<T>(sender, recipient, principal)
+}
+
+/// Add the effects of a CASH transfer, including its fee, to a pipeline, without committing them.
+pub fn prepare_transfer_cash_principal_internal<T: Config>(
+    pipeline: CashPipeline,
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    principal: CashPrincipalAmount,
+) -> Result<CashPipeline, Reason> {
     let miner = get_some_miner::<T>();
     let index = GlobalCashIndex::get();
     let fee_principal = index.cash_principal_amount(TRANSFER_FEE)?;
@@ -52,12 +116,30 @@ pub fn transfer_cash_principal_internal<T: Config>(
 
     require_min_tx_value!(get_value::<T>(amount)?);
 
-    CashPipeline::new()
+    pipeline
         .transfer_cash::<T>(sender, recipient, principal)?
         .transfer_cash::<T>(sender, miner, fee_principal)?
-        .check_collateralized::<T>(sender)?
-        .commit::<T>();
+        .check_collateralized::<T>(sender)
+}
 
+/// Emit the events for a CASH transfer, once its effects are committed.
+pub fn finish_transfer_cash_principal_internal<T: Config>(
+    sender: ChainAccount,
+    recipient: ChainAccount,
+    principal: CashPrincipalAmount,
+) -> Result<(), Reason> {
+    let miner = get_some_miner::<T>();
+    let index = GlobalCashIndex::get();
+    let fee_principal = index.cash_principal_amount(TRANSFER_FEE)?;
+
+    record_exchange::<T>(
+        sender,
+        ActivityKind::Send,
+        recipient,
+        ActivityKind::Receive,
+        CashOrChainAsset::Cash,
+        principal.0,
+    );
     <Module<T>>::deposit_event(Event::TransferCash(sender, recipient, principal, index));
     <Module<T>>::deposit_event(Event::TransferCash(sender, miner, fee_principal, index));
     <Module<T>>::deposit_event(Event::MinerPaid(miner, fee_principal));
@@ -69,6 +151,7 @@ pub fn transfer_cash_principal_internal<T: Config>(
 mod tests {
     use super::*;
     use crate::{
+        symbol::USD,
         tests::{assets::*, common::*, mock::*},
         types::*,
         *,
@@ -81,6 +164,26 @@ mod tests {
     #[allow(non_upper_case_globals)]
     const account_b: ChainAccount = ChainAccount::Eth([2u8; 20]);
 
+    #[test]
+    fn test_set_min_principal_gate() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(MinPrincipalGate::get(), params::MIN_PRINCIPAL_GATE);
+            assert_eq!(
+                set_min_principal_gate::<Test>(CashPrincipal::ZERO),
+                Err(Reason::InvalidMinPrincipalGate)
+            );
+            assert_eq!(
+                set_min_principal_gate::<Test>(CashPrincipal::from_nominal("1001")),
+                Err(Reason::InvalidMinPrincipalGate)
+            );
+            assert_eq!(
+                set_min_principal_gate::<Test>(CashPrincipal::from_nominal("10")),
+                Ok(())
+            );
+            assert_eq!(MinPrincipalGate::get(), CashPrincipal::from_nominal("10"));
+        });
+    }
+
     #[test]
     fn test_transfer_internal_unsupported() {
         new_test_ext().execute_with(|| {
@@ -103,6 +206,17 @@ mod tests {
                 transfer_internal::<Test>(usdc, account_a, account_b, amount),
                 Err(Reason::MinTxValueNotMet),
             );
+
+            pallet_parameters::MinTxValue::put(5_000000);
+            assert_eq!(
+                transfer_internal::<Test>(
+                    usdc,
+                    account_a,
+                    account_b,
+                    usdc.as_quantity_nominal("2")
+                ),
+                Err(Reason::MinTxValueNotMet),
+            );
         });
     }
 
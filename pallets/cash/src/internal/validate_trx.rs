@@ -1,14 +1,15 @@
 use crate::{
-    chains::{Chain, Ethereum},
+    chains::{Chain, ChainSignatureList, Ethereum},
     core::recover_validator,
     internal,
-    notices::EncodeNotice,
+    notices::{EncodeNotice, NoticeState},
     params::{UNSIGNED_TXS_LONGEVITY, UNSIGNED_TXS_PRIORITY},
     reason::Reason,
-    AllowedNextCodeHash, Call, Config, Notices, Validators,
+    types::CashPrincipalAmount,
+    Call, Config, ExecutionReimbursements, NonceWindows, NoticeStates, Notices, Validators,
 };
 use codec::Encode;
-use frame_support::storage::{IterableStorageMap, StorageDoubleMap, StorageValue};
+use frame_support::storage::{IterableStorageMap, StorageDoubleMap};
 use our_std::{log, RuntimeDebug};
 use sp_runtime::transaction_validity::{TransactionSource, TransactionValidity, ValidTransaction};
 
@@ -22,6 +23,15 @@ pub enum ValidationError {
     InvalidPrice(Reason),
     UnknownNotice,
     InvalidTrxRequest(Reason),
+    NoExecutionReimbursement,
+    InvalidPauseGuardian(Reason),
+    EmergencyPaused,
+    InvalidLiquidationAuction(Reason),
+    InvalidPendingExtract(Reason),
+    InvalidAccountLink(Reason),
+    InvalidKeyRotation(Reason),
+    InvalidValidatorMetadata(Reason),
+    InvalidThresholdSignature,
 }
 
 pub fn check_validation_failure<T: Config>(
@@ -51,7 +61,7 @@ pub fn validate_unsigned<T: Config>(
         Call::set_next_code_via_hash(next_code) => {
             let hash = <Ethereum as Chain>::hash_bytes(&next_code);
 
-            if AllowedNextCodeHash::get() == Some(hash) {
+            if internal::next_code::check_next_code_hash::<T>(hash).is_ok() {
                 Ok(
                     ValidTransaction::with_tag_prefix("Gateway::set_next_code_via_hash")
                         .priority(UNSIGNED_TXS_PRIORITY)
@@ -116,6 +126,10 @@ pub fn validate_unsigned<T: Config>(
         }
 
         Call::exec_trx_request(request, signature, nonce) => {
+            if internal::pause::is_emergency_paused() {
+                return Err(ValidationError::EmergencyPaused);
+            }
+
             let signer_res = internal::exec_trx_request::is_minimally_valid_trx_request::<T>(
                 request.to_vec(),
                 *signature,
@@ -125,8 +139,12 @@ pub fn validate_unsigned<T: Config>(
             match (signer_res, nonce) {
                 (Err(e), _) => Err(ValidationError::InvalidTrxRequest(e)),
                 (Ok((sender, current_nonce)), nonce) => {
-                    // Nonce check
-                    if current_nonce == 0 || *nonce == current_nonce {
+                    // Nonce check, accepting nonces within the account's window out of order
+                    let window = NonceWindows::get(sender);
+                    if current_nonce == 0
+                        || (*nonce >= current_nonce
+                            && *nonce <= current_nonce.saturating_add(window))
+                    {
                         Ok(
                             ValidTransaction::with_tag_prefix("Gateway::exec_trx_request")
                                 .priority(UNSIGNED_TXS_PRIORITY)
@@ -152,9 +170,204 @@ pub fn validate_unsigned<T: Config>(
             }
         }
 
+        Call::claim_execution_reimbursement(relayer) => {
+            if ExecutionReimbursements::get(relayer) != CashPrincipalAmount::ZERO {
+                Ok(
+                    ValidTransaction::with_tag_prefix("Gateway::claim_execution_reimbursement")
+                        .priority(UNSIGNED_TXS_PRIORITY)
+                        .longevity(UNSIGNED_TXS_LONGEVITY)
+                        .and_provides(relayer)
+                        .propagate(true)
+                        .build(),
+                )
+            } else {
+                Err(ValidationError::NoExecutionReimbursement)
+            }
+        }
+
+        Call::emergency_pause(signature, nonce) => {
+            match internal::pause::recover_pause_guardian::<T>(*signature, *nonce) {
+                Ok(guardian) => Ok(
+                    ValidTransaction::with_tag_prefix("Gateway::emergency_pause")
+                        .priority(UNSIGNED_TXS_PRIORITY)
+                        .longevity(UNSIGNED_TXS_LONGEVITY)
+                        .and_provides((guardian, nonce))
+                        .propagate(true)
+                        .build(),
+                ),
+                Err(e) => Err(ValidationError::InvalidPauseGuardian(e)),
+            }
+        }
+
+        Call::open_liquidation_auction(borrower) => {
+            match internal::auction::check_open_liquidation_auction::<T>(*borrower) {
+                Ok(()) => Ok(ValidTransaction::with_tag_prefix(
+                    "Gateway::open_liquidation_auction",
+                )
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides(borrower)
+                .propagate(true)
+                .build()),
+                Err(e) => Err(ValidationError::InvalidLiquidationAuction(e)),
+            }
+        }
+
+        Call::close_liquidation_auction(borrower) => {
+            match internal::auction::check_close_liquidation_auction::<T>(*borrower) {
+                Ok(()) => Ok(ValidTransaction::with_tag_prefix(
+                    "Gateway::close_liquidation_auction",
+                )
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides(borrower)
+                .propagate(true)
+                .build()),
+                Err(e) => Err(ValidationError::InvalidLiquidationAuction(e)),
+            }
+        }
+
+        Call::bid_liquidation_auction(borrower, borrowed, collateral, amount, signature, nonce) => {
+            if internal::pause::is_emergency_paused() {
+                return Err(ValidationError::EmergencyPaused);
+            }
+
+            match internal::auction::recover_bidder::<T>(
+                *borrower,
+                *borrowed,
+                *collateral,
+                *amount,
+                *signature,
+                *nonce,
+            ) {
+                Ok(bidder) => Ok(ValidTransaction::with_tag_prefix(
+                    "Gateway::bid_liquidation_auction",
+                )
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides((bidder, nonce))
+                .propagate(true)
+                .build()),
+                Err(e) => Err(ValidationError::InvalidLiquidationAuction(e)),
+            }
+        }
+
+        Call::cancel_pending_extract(id, signature, nonce) => {
+            match internal::extract::recover_extract_canceler::<T>(*id, *signature, *nonce) {
+                Ok((sender, _)) => Ok(ValidTransaction::with_tag_prefix(
+                    "Gateway::cancel_pending_extract",
+                )
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides((sender, nonce))
+                .propagate(true)
+                .build()),
+                Err(e) => Err(ValidationError::InvalidPendingExtract(e)),
+            }
+        }
+
+        Call::set_validator_metadata(metadata, signature, nonce) => {
+            match internal::metadata::recover_validator_metadata_signer::<T>(
+                metadata, *signature, *nonce,
+            ) {
+                Ok((signer, _)) => Ok(ValidTransaction::with_tag_prefix(
+                    "Gateway::set_validator_metadata",
+                )
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides((signer, nonce))
+                .propagate(true)
+                .build()),
+                Err(e) => Err(ValidationError::InvalidValidatorMetadata(e)),
+            }
+        }
+
+        Call::link_accounts(eth_signature, gate_signature) => {
+            match internal::link::recover_linked_accounts::<T>(*eth_signature, *gate_signature) {
+                Ok((eth_account, gate_account)) => {
+                    Ok(ValidTransaction::with_tag_prefix("Gateway::link_accounts")
+                        .priority(UNSIGNED_TXS_PRIORITY)
+                        .longevity(UNSIGNED_TXS_LONGEVITY)
+                        .and_provides((eth_account, gate_account))
+                        .propagate(true)
+                        .build())
+                }
+                Err(e) => Err(ValidationError::InvalidAccountLink(e)),
+            }
+        }
+
+        Call::unlink_accounts(signature, nonce) => {
+            match internal::link::recover_unlinker::<T>(*signature, *nonce) {
+                Ok((account, _linked)) => Ok(ValidTransaction::with_tag_prefix(
+                    "Gateway::unlink_accounts",
+                )
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides((account, nonce))
+                .propagate(true)
+                .build()),
+                Err(e) => Err(ValidationError::InvalidAccountLink(e)),
+            }
+        }
+
+        Call::rotate_keys(new_account, current_signature, new_signature) => {
+            match internal::change_validators::recover_key_rotation::<T>(
+                *new_account,
+                *current_signature,
+                *new_signature,
+            ) {
+                Ok((validator, rotated)) => {
+                    Ok(ValidTransaction::with_tag_prefix("Gateway::rotate_keys")
+                        .priority(UNSIGNED_TXS_PRIORITY)
+                        .longevity(UNSIGNED_TXS_LONGEVITY)
+                        .and_provides((validator.eth_address, rotated.eth_address))
+                        .propagate(true)
+                        .build())
+                }
+                Err(e) => Err(ValidationError::InvalidKeyRotation(e)),
+            }
+        }
+
+        Call::advertise_next_eth_key(next_account, current_signature, next_signature) => {
+            match internal::change_validators::recover_next_eth_key::<T>(
+                *next_account,
+                *current_signature,
+                *next_signature,
+            ) {
+                Ok((validator, _advertised)) => Ok(ValidTransaction::with_tag_prefix(
+                    "Gateway::advertise_next_eth_key",
+                )
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides((validator.eth_address, next_account))
+                .propagate(true)
+                .build()),
+                Err(e) => Err(ValidationError::InvalidKeyRotation(e)),
+            }
+        }
+
         Call::publish_signature(chain_id, notice_id, signature) => {
             let notice = Notices::get(chain_id, notice_id).ok_or(ValidationError::UnknownNotice)?;
-            let validator = recover_validator::<T>(&notice.encode_notice(), *signature)
+
+            // Note: the threshold key is not any one validator's, it is checked against the notice instead
+            if let NoticeState::Pending {
+                signature_pairs: ChainSignatureList::Threshold(signer, None),
+            } = NoticeStates::get(chain_id, notice_id)
+            {
+                return match signature.recover(&notice.encode_notice()) {
+                    Ok(account) if account == signer => Ok(ValidTransaction::with_tag_prefix(
+                        "Gateway::publish_signature",
+                    )
+                    .priority(UNSIGNED_TXS_PRIORITY)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides((chain_id, notice_id, signature))
+                    .propagate(true)
+                    .build()),
+                    _ => Err(ValidationError::InvalidThresholdSignature),
+                };
+            }
+
+            let validator = internal::notices::recover_notice_signer::<T>(&notice, *signature)
                 .map_err(|_| ValidationError::InvalidValidator)?;
 
             // XXX what happens if not eth here? seems broken
@@ -180,6 +393,7 @@ mod tests {
     use super::*;
     use crate::{core::validator_sign, tests::*, Call};
     use ethereum_client::EthereumBlock;
+    use frame_support::storage::StorageValue;
 
     #[test]
     fn test_set_miner_external() {
@@ -308,6 +522,7 @@ mod tests {
                 ValidatorKeys {
                     substrate_id: substrate_id.clone(),
                     eth_address,
+                    next_eth_key: None,
                 },
             );
 
@@ -316,18 +531,21 @@ mod tests {
                     hash: [1; 32],
                     parent_hash: [0; 32],
                     number: 1,
+                    timestamp: 0,
                     events: vec![],
                 },
                 EthereumBlock {
                     hash: [2; 32],
                     parent_hash: [1; 32],
                     number: 2,
+                    timestamp: 0,
                     events: vec![],
                 },
                 EthereumBlock {
                     hash: [3; 32],
                     parent_hash: [2; 32],
                     number: 3,
+                    timestamp: 0,
                     events: vec![],
                 },
             ]);
@@ -425,6 +643,47 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_exec_trx_request_nonce_within_window() {
+        new_test_ext().execute_with(|| {
+            let request: Vec<u8> = String::from(
+                "(Extract 50000000 Cash Eth:0xfc04833Ca66b7D6B4F540d4C2544228f64a25ac2)",
+            )
+            .as_bytes()
+            .into();
+            let nonce = 7;
+            let full_request: Vec<u8> = format!("\x19Ethereum Signed Message:\n727:(Extract 50000000 Cash Eth:0xfc04833Ca66b7D6B4F540d4C2544228f64a25ac2)")
+                .as_bytes()
+                .into();
+            let eth_address = <Ethereum as Chain>::signer_address().unwrap();
+            let eth_key_id =
+                runtime_interfaces::validator_config_interface::get_eth_key_id().unwrap();
+            let signature_raw =
+                runtime_interfaces::keyring_interface::sign_one(full_request, eth_key_id).unwrap();
+
+            let signature = ChainAccountSignature::Eth(eth_address, signature_raw);
+
+            Nonces::insert(ChainAccount::Eth(eth_address), 5);
+            NonceWindows::insert(ChainAccount::Eth(eth_address), 2);
+
+            let exp = ValidTransaction::with_tag_prefix("Gateway::exec_trx_request")
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides((ChainAccount::Eth(eth_address), 7))
+                .and_provides(request.clone())
+                .propagate(true)
+                .build();
+
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::InBlock {},
+                    &Call::exec_trx_request::<Test>(request, signature, nonce),
+                ),
+                Ok(exp)
+            );
+        });
+    }
+
     #[test]
     fn test_exec_trx_request_valid_request_wrong_nonce() {
         new_test_ext().execute_with(|| {
@@ -624,6 +883,7 @@ mod tests {
                 ValidatorKeys {
                     substrate_id,
                     eth_address: signer,
+                    next_eth_key: None,
                 },
             );
 
@@ -644,6 +904,64 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_claim_execution_reimbursement() {
+        new_test_ext().execute_with(|| {
+            let relayer = ChainAccount::Eth([2; 20]);
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::claim_execution_reimbursement::<Test>(relayer),
+                ),
+                Err(ValidationError::NoExecutionReimbursement)
+            );
+
+            ExecutionReimbursements::insert(relayer, CashPrincipalAmount(1000));
+            let exp = ValidTransaction::with_tag_prefix("Gateway::claim_execution_reimbursement")
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .and_provides(relayer)
+                .propagate(true)
+                .build();
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::claim_execution_reimbursement::<Test>(relayer),
+                ),
+                Ok(exp)
+            );
+        });
+    }
+
+    #[test]
+    fn test_emergency_pause() {
+        new_test_ext().execute_with(|| {
+            let signature = ChainAccountSignature::Eth([0u8; 20], [0u8; 65]);
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::emergency_pause::<Test>(signature, 0),
+                ),
+                Err(ValidationError::InvalidPauseGuardian(
+                    Reason::SignatureAccountMismatch
+                ))
+            );
+
+            EmergencyPauseActive::put(true);
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::exec_trx_request::<Test>(
+                        "(NonceWindow 2)".as_bytes().to_vec(),
+                        signature,
+                        0
+                    ),
+                ),
+                Err(ValidationError::EmergencyPaused)
+            );
+        });
+    }
+
     #[test]
     fn test_other() {
         new_test_ext().execute_with(|| {
@@ -13,22 +13,27 @@ extern crate trx_request;
 use crate::{
     chains::{
         ChainAccount, ChainAccountSignature, ChainAsset, ChainBlock, ChainBlockEvent,
-        ChainBlockEvents, ChainBlockTally, ChainBlocks, ChainHash, ChainId, ChainReorg,
-        ChainReorgTally, ChainSignature, ChainSignatureList, ChainStarport,
+        ChainBlockEvents, ChainBlockNumber, ChainBlockTally, ChainBlocks, ChainHash, ChainId,
+        ChainReorg, ChainReorgTally, ChainSignature, ChainSignatureList, ChainStarport,
+        FailedEventId, LockReference,
     },
     notices::{Notice, NoticeId, NoticeState},
     portfolio::Portfolio,
     symbol::CASH,
     types::{
-        AssetAmount, AssetBalance, AssetIndex, AssetInfo, Balance, Bips, CashIndex, CashPrincipal,
-        CashPrincipalAmount, CodeHash, EncodedNotice, GovernanceResult, InterestRateModel,
-        LiquidityFactor, Nonce, Reason, SessionIndex, Timestamp, ValidatorKeys, APR,
+        AccrualPoint, ActivityEntry, AssetAmount, AssetBalance, AssetIndex, AssetInfo,
+        AssetPauseFlags, Balance, Bips, BrokenInvariant, CashIndex, CashOrChainAsset,
+        CashPrincipal, CashPrincipalAmount, ChainHealth, CodeHash, EncodedNotice, ExtractRateLimit,
+        ExtractRateUsage, Factor, GovernanceResult, InterestRateModel, LiquidityFactor,
+        MarketSummary, Nonce, PendingExtract, PendingExtractId, ProtocolMetrics, Reason,
+        RewardSpeeds, SessionEarnings, SessionIndex, Timestamp, TrxRequestEstimate, USDQuantity,
+        Unbonding, ValidatorKeys, ValidatorMetadata, ValidatorOffense, ValidatorParticipation, APR,
     },
 };
-use codec::{alloc::string::String, Encode};
+use codec::{alloc::string::String, Decode, Encode};
 use frame_support::{
     decl_event, decl_module, decl_storage, dispatch,
-    traits::{StoredMap, UnfilteredDispatchable},
+    traits::{Get, StoredMap, UnfilteredDispatchable},
     weights::{DispatchClass, GetDispatchInfo, Pays, Weight},
     Parameter,
 };
@@ -36,13 +41,19 @@ use frame_system;
 use frame_system::{ensure_none, ensure_root, offchain::CreateSignedTransaction};
 use num_traits::Zero;
 use our_std::{
-    collections::btree_map::BTreeMap, collections::btree_set::BTreeSet, convert::TryInto, debug,
-    error, log, str, vec::Vec, warn, Debuggable,
+    collections::btree_map::BTreeMap,
+    collections::btree_set::BTreeSet,
+    convert::TryInto,
+    debug, error, log,
+    log::targets::{AUDIT, EVENTS, NOTICES},
+    str,
+    vec::Vec,
+    warn, Debuggable,
 };
 use sp_core::crypto::AccountId32;
 use sp_runtime::{
     transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
-    Percent,
+    ConsensusEngineId, Percent,
 };
 
 use pallet_oracle;
@@ -90,6 +101,7 @@ pub trait Config:
     + CreateSignedTransaction<Call<Self>>
     + pallet_timestamp::Config
     + pallet_oracle::Config
+    + pallet_parameters::Config
 {
     /// Because this pallet emits events, it depends on the runtime's definition of an event.
     type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
@@ -111,6 +123,9 @@ pub trait Config:
     /// Associated type which allows us to interact with substrate Sessions.
     type SessionInterface: self::SessionInterface<SubstrateId>;
 
+    /// Where governance actions from Starports are queued before being dispatched as root, i.e. the timelock.
+    type GovernanceQueue: self::GovernanceQueue<Self>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -123,6 +138,9 @@ decl_storage! {
         /// A possible next code hash which is used to accept code provided to SetNextCodeViaHash.
         AllowedNextCodeHash get(fn allowed_next_code_hash): Option<CodeHash>;
 
+        /// The last block number at which the allowed next code hash may be used.
+        AllowedNextCodeHashExpiry get(fn allowed_next_code_hash_expiry): Option<u32>;
+
         /// The upcoming session at which to tell the sessions pallet to rotate the validators.
         NextSessionIndex get(fn next_session_index): SessionIndex;
 
@@ -132,6 +150,49 @@ decl_storage! {
         /// The current set of allowed validators, and their associated keys.
         Validators get(fn validators): map hasher(blake2_128_concat) SubstrateId => Option<ValidatorKeys>;
 
+        /// The metadata published by each validator, identifying who runs it.
+        ValidatorMetadatas get(fn validator_metadata): map hasher(blake2_128_concat) SubstrateId => Option<ValidatorMetadata>;
+
+        /// Whether chain block and reorg tallies are weighted by validator stake (bonded CASH principal), as well as counted.
+        StakeWeightedThresholds get(fn stake_weighted_thresholds): bool;
+
+        /// The CASH principal bonded by each account, as a prospective validator's Eth account.
+        ValidatorBonds get(fn validator_bond): map hasher(blake2_128_concat) ChainAccount => CashPrincipalAmount;
+
+        /// The CASH principal unbonded by each account, held until its release time.
+        Unbondings get(fn unbonding): map hasher(blake2_128_concat) ChainAccount => Option<Unbonding>;
+
+        /// The accounts unbonding in order of release time, by position in the queue, with the release time queued.
+        /// An entry whose account has since unbonded again, or been slashed, no longer matches and is skipped.
+        UnbondingQueue get(fn unbonding_queue): map hasher(twox_64_concat) u64 => Option<(ChainAccount, Timestamp)>;
+
+        /// The position of the first unreleased entry in the unbonding queue, and the position after the last.
+        UnbondingQueueBounds get(fn unbonding_queue_bounds): (u64, u64);
+
+        /// The minimum CASH principal a validator's Eth account must have bonded to join the validator set.
+        MinValidatorBond get(fn min_validator_bond): CashPrincipalAmount;
+
+        /// The offenses committed by each validator, most recent last, bounded by MAX_VALIDATOR_OFFENSES.
+        ValidatorOffenses get(fn validator_offenses): map hasher(blake2_128_concat) SubstrateId => Vec<ValidatorOffense>;
+
+        /// The chain blocks attested and notices signed by each validator within the current session.
+        ValidatorParticipations get(fn validator_participation): map hasher(blake2_128_concat) SubstrateId => ValidatorParticipation;
+
+        /// The chain blocks accepted and notices dispatched within the current session, for validators to participate in.
+        SessionParticipation get(fn session_participation): ValidatorParticipation;
+
+        /// The maximum fraction of chain blocks and notices a validator may miss within a session before being reported.
+        MaxMissedFraction get(fn max_missed_fraction): Factor = Factor::from_nominal("0.5");
+
+        /// The earnings of each miner within the current session, recorded against its validator at the end of the session.
+        MinerSessionEarnings get(fn miner_session_earnings): map hasher(blake2_128_concat) ChainAccount => SessionEarnings;
+
+        /// The earnings of each validator in recent sessions, most recent last, bounded by MAX_EARNINGS_SESSIONS.
+        ValidatorEarnings get(fn validator_earnings): map hasher(blake2_128_concat) SubstrateId => Vec<SessionEarnings>;
+
+        /// The validators whose blocks' share of interest is withheld into the insurance fund, due to an offense.
+        WithheldValidators get(fn withheld_validator): map hasher(blake2_128_concat) SubstrateId => bool;
+
         /// An index to track interest earned by CASH holders and owed by CASH borrowers.
         /// Note - the implementation of Default for CashIndex returns ONE. This also provides
         /// the initial value as it is currently implemented.
@@ -143,21 +204,54 @@ decl_storage! {
         /// The current APR on CASH held, and the base rate paid by borrowers.
         CashYield get(fn cash_yield) config(): APR;
 
-        /// The liquidation incentive on seized collateral (e.g. 8% = 800 bips).
-        GlobalLiquidationIncentive get(fn liquidation_incentive): Bips;
+        /// The maximum fraction of a borrow which may be repaid in a single liquidation.
+        CloseFactor get(fn close_factor): Factor = Factor::from_nominal("0.5");
+
+        /// The minimum CASH principal required in order to use a Gateway account.
+        MinPrincipalGate get(fn min_principal_gate): CashPrincipal = params::MIN_PRINCIPAL_GATE;
 
         /// The fraction of borrower interest that is paid to the protocol (e.g. 1/10th = 1000 bips).
         Spreads get(fn spread): map hasher(blake2_128_concat) ChainAsset => Bips;
 
+        /// The CASH principal accrued to the protocol from the spread on borrower interest, by asset.
+        TotalReserves get(fn total_reserves): map hasher(blake2_128_concat) ChainAsset => CashPrincipalAmount;
+
+        /// The balance below which a position left by an operation is swept away as dust, by asset.
+        DustThresholds get(fn dust_threshold): map hasher(blake2_128_concat) ChainAsset => AssetAmount;
+
+        /// The kinds of new activity currently paused, by asset.
+        AssetPauses get(fn asset_pauses): map hasher(blake2_128_concat) ChainAsset => AssetPauseFlags;
+
         /// The mapping of indices to track interest owed by asset borrowers, by asset.
         BorrowIndices get(fn borrow_index): map hasher(blake2_128_concat) ChainAsset => AssetIndex;
 
         /// The mapping of indices to track interest earned by asset suppliers, by asset.
         SupplyIndices get(fn supply_index): map hasher(blake2_128_concat) ChainAsset => AssetIndex;
 
+        /// The most recent accrual points for each asset, oldest first, bounded by MAX_RATE_HISTORY.
+        RateHistory get(fn rate_history): map hasher(blake2_128_concat) ChainAsset => Vec<AccrualPoint>;
+
+        /// The most recent operations involving each account, oldest first, bounded by MAX_ACCOUNT_ACTIVITY.
+        AccountActivity get(fn account_activity): map hasher(blake2_128_concat) ChainAccount => Vec<ActivityEntry>;
+
+        /// The timestamp each asset's indices were last brought current, or the last block timestamp if never.
+        LastAccrualTimestamps get(fn last_accrual_timestamp): map hasher(blake2_128_concat) ChainAsset => Option<Timestamp>;
+
+        /// The timestamp of the last sweep bringing all assets current.
+        LastAccrualSweep get(fn last_accrual_sweep): Timestamp;
+
+        /// The CASH principal (borrowed, supplied, reserved) by asset accruals, not yet settled at block initialization.
+        UnsettledAccruals get(fn unsettled_accruals): (CashPrincipalAmount, CashPrincipalAmount, CashPrincipalAmount);
+
         /// The total CASH principal held per chain.
         ChainCashPrincipals get(fn chain_cash_principal): map hasher(blake2_128_concat) ChainId => CashPrincipalAmount;
 
+        /// The maximum CASH principal which may be held on each chain, if any.
+        ChainCashCaps get(fn chain_cash_cap): map hasher(blake2_128_concat) ChainId => Option<CashPrincipalAmount>;
+
+        /// The maximum value (USD) of the assets of each chain which may be supplied, if any.
+        ChainAssetCaps get(fn chain_asset_cap): map hasher(blake2_128_concat) ChainId => Option<USDQuantity>;
+
         /// The total CASH principal in existence.
         TotalCashPrincipal get(fn total_cash_principal): CashPrincipalAmount;
 
@@ -170,6 +264,9 @@ decl_storage! {
         /// The mapping of CASH principal, by account.
         CashPrincipals get(fn cash_principal): map hasher(blake2_128_concat) ChainAccount => CashPrincipal;
 
+        /// The CASH principal each account held before it changed, by the time it changed, kept for the history period.
+        CashPrincipalCheckpoints get(fn cash_principal_checkpoints): map hasher(blake2_128_concat) ChainAccount => Vec<(Timestamp, CashPrincipal)>;
+
         /// The mapping of asset balances, by asset and account.
         AssetBalances get(fn asset_balance): double_map hasher(blake2_128_concat) ChainAsset, hasher(blake2_128_concat) ChainAccount => AssetBalance;
 
@@ -194,18 +291,52 @@ decl_storage! {
         /// The change authority notices which must be fully signed before we allow notice signing to continue
         NoticeHolds get(fn notice_hold): map hasher(blake2_128_concat) ChainId => Option<NoticeId>;
 
+        /// The maximum number of extractions per chain combined into a single notice, batching is off below two.
+        ExtractionBatchSize get(fn extraction_batch_size): u32;
+
+        /// The account of the threshold key whose shares are held by the validators, by chain.
+        /// When set, notices for the chain await a single signature of this key, rather than one from each validator.
+        ThresholdSigners get(fn threshold_signer): map hasher(blake2_128_concat) ChainId => Option<ChainAccount>;
+
+        /// Mapping of chain to extractions waiting to be combined into a single notice. [asset, recipient, amount]
+        PendingExtractions get(fn pending_extractions): map hasher(blake2_128_concat) ChainId => Vec<(ChainAsset, ChainAccount, AssetAmount)>;
+
+        /// The execution fees per chain which are held in the pool and not yet earned by a relayer.
+        ExecutionPools get(fn execution_pool): map hasher(blake2_128_concat) ChainId => CashPrincipalAmount;
+
+        /// The execution fees per chain awaiting the next extraction notice to be dispatched.
+        PendingExecutionFees get(fn pending_execution_fee): map hasher(blake2_128_concat) ChainId => CashPrincipalAmount;
+
+        /// The execution fee reimbursed to whoever relays a given notice to its Starport.
+        NoticeExecutionFees get(fn notice_execution_fee): double_map hasher(blake2_128_concat) ChainId, hasher(blake2_128_concat) NoticeId => CashPrincipalAmount;
+
+        /// The execution fees earned by each relayer, which may be claimed from the pool.
+        ExecutionReimbursements get(fn execution_reimbursement): map hasher(blake2_128_concat) ChainAccount => CashPrincipalAmount;
+
         /// Index of notices by chain account
         AccountNotices get(fn account_notices): map hasher(blake2_128_concat) ChainAccount => Vec<NoticeId>;
 
         /// The last used nonce for each account, initialized at zero.
         Nonces get(fn nonce): map hasher(blake2_128_concat) ChainAccount => Nonce;
 
+        /// The number of nonces beyond the next one which each account accepts out of order.
+        NonceWindows get(fn nonce_window): map hasher(blake2_128_concat) ChainAccount => Nonce;
+
+        /// Nonces beyond the next one which each account has already used or cancelled.
+        UsedNonces get(fn used_nonce): double_map hasher(blake2_128_concat) ChainAccount, hasher(blake2_128_concat) Nonce => bool;
+
+        /// The amount of an asset or CASH which a spender may transfer on behalf of an owner, by owner and spender.
+        Allowances get(fn allowance): double_map hasher(blake2_128_concat) ChainAccount, hasher(blake2_128_concat) (ChainAccount, CashOrChainAsset) => AssetAmount;
+
         /// The asset metadata for each supported asset, which will also be synced with the starports.
         SupportedAssets get(fn asset): map hasher(blake2_128_concat) ChainAsset => Option<AssetInfo>;
 
         /// Miner of the current block.
         Miner get(fn miner): Option<ChainAccount>;
 
+        /// Validator which authored this block, whose miner's share is withheld if it has been penalized.
+        Author get(fn author): Option<SubstrateId>;
+
         /// Mapping of total principal paid to each miner.
         MinerCumulative get(fn miner_cumulative): map hasher(blake2_128_concat) ChainAccount => CashPrincipalAmount;
 
@@ -235,6 +366,78 @@ decl_storage! {
 
         /// Mapping of chain to the relevant Starport address.
         Starports get(fn starports): map hasher(blake2_128_concat) ChainId => Option<ChainStarport>;
+
+        /// Mapping of chain to the L1 contract which checkpoints its blocks, for chains which are only final once checkpointed.
+        CheckpointContracts get(fn checkpoint_contract): map hasher(blake2_128_concat) ChainId => Option<ChainAccount>;
+
+        /// Mapping of chain to events which failed to process, and are awaiting retry, bounded by MAX_FAILED_EVENTS.
+        FailedEvents get(fn failed_events): map hasher(blake2_128_concat) ChainId => Vec<(FailedEventId, ChainBlockEvent)>;
+
+        /// Mapping of chain to the identifier to assign to its next failed event.
+        NextFailedEventId get(fn next_failed_event_id): map hasher(blake2_128_concat) ChainId => FailedEventId;
+
+        /// Mapping of chain to the time since which its pending tallies have been waiting without progress.
+        PendingChainTalliesSince get(fn pending_chain_tallies_since): map hasher(blake2_128_concat) ChainId => Option<Timestamp>;
+
+        /// Mapping of chain to the hashes of its most recently processed blocks, oldest first.
+        AncestorHashes get(fn ancestor_hashes): map hasher(blake2_128_concat) ChainId => Vec<ChainHash>;
+
+        /// Mapping of chain to whether ingestion of its blocks is paused.
+        IngestionPaused get(fn ingestion_paused): map hasher(blake2_128_concat) ChainId => bool;
+
+        /// The account allowed to trigger an emergency pause, if any.
+        PauseGuardian get(fn pause_guardian): Option<ChainAccount>;
+
+        /// Whether transaction requests and notice signing are halted by an emergency pause.
+        EmergencyPauseActive get(fn emergency_pause_active): bool;
+
+        /// The total CASH principal of bad debt written off by absorbing insolvent accounts, or sweeping borrow dust reserves do not cover.
+        BadDebt get(fn bad_debt): CashPrincipalAmount;
+
+        /// The share of the miner spread which accrues to the insurance fund, in bips.
+        InsuranceShare get(fn insurance_share): Bips;
+
+        /// The CASH principal held by the protocol insurance fund, a first-loss buffer for bad debt.
+        InsuranceFund get(fn insurance_fund): CashPrincipalAmount;
+
+        /// Mapping of underwater account to the block at which its liquidation auction opened.
+        LiquidationAuctions get(fn liquidation_auctions): map hasher(blake2_128_concat) ChainAccount => Option<u32>;
+
+        /// Mapping of asset to the maximum amount each account may extract over a rolling window.
+        ExtractRateLimits get(fn extract_rate_limits): map hasher(blake2_128_concat) CashOrChainAsset => Option<ExtractRateLimit>;
+
+        /// Mapping of account and asset to its recent usage of the extract rate limit.
+        ExtractRateUsages get(fn extract_rate_usages): double_map hasher(blake2_128_concat) ChainAccount, hasher(blake2_128_concat) CashOrChainAsset => Option<ExtractRateUsage>;
+
+        /// Mapping of asset to the amount above which a single extraction is held by the timelock.
+        ExtractDelayThresholds get(fn extract_delay_thresholds): map hasher(blake2_128_concat) CashOrChainAsset => Option<AssetAmount>;
+
+        /// Mapping of id to the extractions held by the timelock, waiting to be released.
+        PendingExtracts get(fn pending_extracts): map hasher(blake2_128_concat) PendingExtractId => Option<PendingExtract>;
+
+        /// The id to assign the next extraction held by the timelock.
+        NextPendingExtractId get(fn next_pending_extract_id): PendingExtractId;
+
+        /// Mapping of asset to the CASH principal emitted each day as rewards to its suppliers and borrowers.
+        AssetRewardSpeeds get(fn asset_reward_speeds): map hasher(blake2_128_concat) ChainAsset => RewardSpeeds;
+
+        /// Mapping of asset to the latest supply reward index, the rewards accrued per unit supplied.
+        SupplyRewardIndices get(fn supply_reward_index): map hasher(blake2_128_concat) ChainAsset => AssetIndex;
+
+        /// Mapping of asset to the latest borrow reward index, the rewards accrued per unit borrowed.
+        BorrowRewardIndices get(fn borrow_reward_index): map hasher(blake2_128_concat) ChainAsset => AssetIndex;
+
+        /// Mapping of (asset, account) to the reward index at which rewards were last accrued for the balance.
+        LastRewardIndices get(fn last_reward_index): double_map hasher(blake2_128_concat) ChainAsset, hasher(blake2_128_concat) ChainAccount => AssetIndex;
+
+        /// Mapping of account to the CASH principal accrued as rewards and not yet claimed.
+        RewardsAccrued get(fn rewards_accrued): map hasher(blake2_128_concat) ChainAccount => CashPrincipalAmount;
+
+        /// Mapping of account to the account on another chain it is linked with, in both directions.
+        LinkedAccounts get(fn linked_account): map hasher(blake2_128_concat) ChainAccount => Option<ChainAccount>;
+
+        /// The version of the layout of storage, as of the last migration run.
+        StorageVersion get(fn storage_version): u32;
     }
 
     add_extra_genesis {
@@ -247,6 +450,7 @@ decl_storage! {
             Pallet::<T>::initialize_validators(config.validators.clone());
             Pallet::<T>::initialize_starports(config.starports.clone());
             Pallet::<T>::initialize_genesis_blocks(config.genesis_blocks.clone());
+            StorageVersion::put(params::STORAGE_VERSION);
         })
     }
 }
@@ -255,11 +459,23 @@ decl_storage! {
 
 decl_event!(
     pub enum Event {
-        /// An account has locked an asset. [asset, sender, recipient, amount]
-        Locked(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
+        /// An account has locked an asset. [asset, sender, recipient, amount, reference]
+        Locked(
+            ChainAsset,
+            ChainAccount,
+            ChainAccount,
+            AssetAmount,
+            Option<LockReference>,
+        ),
 
-        /// Revert a lock event while handling a chain re-organization. [asset, sender, recipient, amount]
-        ReorgRevertLocked(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
+        /// Revert a lock event while handling a chain re-organization. [asset, sender, recipient, amount, reference]
+        ReorgRevertLocked(
+            ChainAsset,
+            ChainAccount,
+            ChainAccount,
+            AssetAmount,
+            Option<LockReference>,
+        ),
 
         /// An account has locked CASH. [sender, recipient, principal, index]
         LockedCash(ChainAccount, ChainAccount, CashPrincipalAmount, CashIndex),
@@ -279,6 +495,15 @@ decl_event!(
         /// An account has transferred CASH. [sender, recipient, principal, index]
         TransferCash(ChainAccount, ChainAccount, CashPrincipalAmount, CashIndex),
 
+        /// An account has approved a spender to transfer an amount on its behalf. [owner, spender, asset, amount]
+        Approval(ChainAccount, ChainAccount, CashOrChainAsset, AssetAmount),
+
+        /// An account has cancelled one of its nonces. [account, nonce]
+        NonceCancelled(ChainAccount, Nonce),
+
+        /// An account has changed how many nonces it accepts out of order. [account, window]
+        NonceWindowSet(ChainAccount, Nonce),
+
         /// An account has been liquidated. [asset, collateral_asset, liquidator, borrower, amount]
         Liquidate(
             ChainAsset,
@@ -300,12 +525,21 @@ decl_event!(
         /// An account using CASH as collateral has been liquidated. [asset, liquidator, borrower, amount]
         LiquidateCashCollateral(ChainAsset, ChainAccount, ChainAccount, AssetAmount),
 
+        /// Relayer paid a fee for submitting a trx request. [relayer, principal]
+        RelayerPaid(ChainAccount, CashPrincipalAmount),
+
         /// Miner paid. [miner, principal]
         MinerPaid(ChainAccount, CashPrincipalAmount),
 
+        /// Interest accrued on an asset. [asset, supply_index, borrow_index, supply_rate, borrow_rate]
+        AssetAccrued(ChainAsset, AssetIndex, AssetIndex, APR, APR),
+
         /// The next code hash has been allowed. [hash]
         AllowedNextCodeHash(CodeHash),
 
+        /// The allowed next code hash was revoked before being used. [hash]
+        RevokedNextCodeHash(CodeHash),
+
         /// An attempt to set code via hash was made. [hash, result]
         AttemptedSetCodeByHash(CodeHash, dispatch::DispatchResult),
 
@@ -315,9 +549,21 @@ decl_event!(
         /// An Ethereum event failed during processing. [event_id, reason]
         FailedProcessingChainBlockEvent(ChainBlockEvent, Reason),
 
+        /// A failed event has been queued for retry. [chain_id, failed_event_id]
+        QueuedFailedEvent(ChainId, FailedEventId),
+
+        /// A failed event has been retried successfully. [chain_id, failed_event_id]
+        RetriedFailedEvent(ChainId, FailedEventId),
+
+        /// A failed event has been dropped, from a full queue or with its block in a reorg, and will not be retried. [chain_id, failed_event_id, event]
+        DroppedFailedEvent(ChainId, FailedEventId, ChainBlockEvent),
+
         /// A new notice is generated by the chain. [notice_id, notice, encoded_notice]
         Notice(NoticeId, Notice, EncodedNotice),
 
+        /// A notice has been executed by the Starport on its chain. [chain_id, notice_id, result]
+        NoticeExecuted(ChainId, NoticeId, Vec<u8>),
+
         /// A sequence of governance actions has been executed. [actions]
         ExecutedGovernance(Vec<(Vec<u8>, GovernanceResult)>),
 
@@ -327,9 +573,171 @@ decl_event!(
         /// A new validator set has been chosen. [validators]
         ChangeValidators(Vec<ValidatorKeys>),
 
+        /// A validator has signed conflicting blocks of a chain at the same height. [validator, chain_id, block_number]
+        ValidatorEquivocated(SubstrateId, ChainId, ChainBlockNumber),
+
+        /// A compromised validator has been dropped from the active set. [validator]
+        ValidatorRemoved(SubstrateId),
+
+        /// A validator has published its metadata. [validator]
+        SetValidatorMetadata(SubstrateId),
+
+        /// A validator has rotated its Eth key. [validator, current_account, new_account]
+        ValidatorKeysRotated(SubstrateId, ChainAccount, ChainAccount),
+
+        /// A validator has advertised its next Eth key, accepted alongside the current one until expiry. [validator, next_account, expires_at]
+        ValidatorNextEthKeyAdvertised(SubstrateId, ChainAccount, Timestamp),
+
+        /// The offenses of a validator have been pardoned. [validator]
+        ValidatorPardoned(SubstrateId),
+
+        /// Signature thresholds have been switched to or from being stake-weighted. [enabled]
+        SetStakeWeightedThresholds(bool),
+
+        /// An account has bonded CASH principal. [account, principal]
+        Bonded(ChainAccount, CashPrincipalAmount),
+
+        /// An account has unbonded CASH principal, held until its release time. [account, principal, release_at]
+        Unbonded(ChainAccount, CashPrincipalAmount, Timestamp),
+
+        /// Unbonded CASH principal has been released back to an account. [account, principal]
+        BondReleased(ChainAccount, CashPrincipalAmount),
+
+        /// The bond of an account has been slashed into the insurance fund. [account, principal]
+        BondSlashed(ChainAccount, CashPrincipalAmount),
+
+        /// The minimum bond required to join the validator set has been set. [principal]
+        SetMinValidatorBond(CashPrincipalAmount),
+
+        /// A validator missed too many chain blocks and notices within a session. [validator, participation, expected]
+        ValidatorUnderperformed(SubstrateId, ValidatorParticipation, ValidatorParticipation),
+
+        /// The maximum fraction of a session a validator may miss has been set. [fraction]
+        SetMaxMissedFraction(Factor),
+
+        /// A miner's share of interest has been withheld into the insurance fund. [miner, principal]
+        MinerEarningsWithheld(ChainAccount, CashPrincipalAmount),
+
         /// A new yield rate has been chosen. [next_rate, next_start_at]
         SetYieldNext(APR, Timestamp),
 
+        /// Pending tallies for a chain have expired without reaching quorum. [chain_id, blocks, reorgs]
+        ExpiredChainTally(ChainId, u32, u32),
+
+        /// Ingestion of blocks from a chain has been paused. [chain_id]
+        ChainIngestionPaused(ChainId),
+
+        /// Ingestion of blocks from a chain has been resumed. [chain_id]
+        ChainIngestionResumed(ChainId),
+
+        /// The pause guardian has been set or removed. [guardian]
+        SetPauseGuardian(Option<ChainAccount>),
+
+        /// The pause guardian has halted transaction requests and notice signing. [guardian]
+        EmergencyPaused(ChainAccount),
+
+        /// Transaction requests and notice signing have resumed.
+        EmergencyUnpaused,
+
+        /// An insolvent account's debts were written off. [account, shortfall, from_reserves, from_insurance, socialized]
+        BadDebtAbsorbed(
+            ChainAccount,
+            CashPrincipalAmount,
+            CashPrincipalAmount,
+            CashPrincipalAmount,
+            CashPrincipalAmount,
+        ),
+
+        /// The share of the miner spread accruing to the insurance fund has been set. [share]
+        SetInsuranceShare(Bips),
+
+        /// Some of the insurance fund has been paid out to a recipient. [recipient, principal]
+        InsurancePaid(ChainAccount, CashPrincipalAmount),
+
+        /// A liquidation auction has opened for an underwater account. [borrower, block]
+        LiquidationAuctionOpened(ChainAccount, u32),
+
+        /// A keeper has liquidated an account in its auction. [borrower, bidder, bonus]
+        LiquidationAuctionBid(ChainAccount, ChainAccount, Bips),
+
+        /// The liquidation auction for an account has closed. [borrower]
+        LiquidationAuctionClosed(ChainAccount),
+
+        /// The extract rate limit for an asset has been set or removed. [asset, limit]
+        SetExtractRateLimit(CashOrChainAsset, Option<ExtractRateLimit>),
+
+        /// The cap on the CASH principal held on a chain has been set or removed. [chain_id, cap]
+        SetChainCashCap(ChainId, Option<CashPrincipalAmount>),
+
+        /// The cap on the value of the assets supplied from a chain has been set or removed. [chain_id, cap]
+        SetChainAssetCap(ChainId, Option<USDQuantity>),
+
+        /// The threshold above which extractions of an asset are timelocked has been set or removed. [asset, threshold]
+        SetExtractDelayThreshold(CashOrChainAsset, Option<AssetAmount>),
+
+        /// A large extraction has been held by the timelock until its release time. [id, extraction]
+        DelayedExtract(PendingExtractId, PendingExtract),
+
+        /// A timelocked extraction has been canceled by its sender, returning the funds. [id]
+        CanceledExtract(PendingExtractId),
+
+        /// The rewards emitted each day for an asset have been set. [asset, speeds]
+        SetRewardSpeeds(ChainAsset, RewardSpeeds),
+
+        /// An account has claimed its accrued rewards, as CASH. [account, principal]
+        ClaimRewards(ChainAccount, CashPrincipalAmount),
+
+        /// An Eth account has been linked with a Gate account. [eth_account, gate_account]
+        AccountsLinked(ChainAccount, ChainAccount),
+
+        /// An account has been unlinked from the account it was linked with. [account, linked_account]
+        AccountsUnlinked(ChainAccount, ChainAccount),
+
+        /// The maximum number of extractions combined into a single notice has been set. [size]
+        SetExtractionBatchSize(u32),
+
+        /// The threshold key signing notices for a chain has been set, or unset to go back to validator signatures. [chain_id, signer]
+        SetThresholdSigner(ChainId, Option<ChainAccount>),
+
+        /// An execution fee has been paid into the pool for an extraction. [chain_id, account, principal]
+        ExecutionFeePaid(ChainId, ChainAccount, CashPrincipalAmount),
+
+        /// A relayer has earned the execution fee for a notice. [relayer, chain_id, notice_id, principal]
+        ExecutionFeeEarned(ChainAccount, ChainId, NoticeId, CashPrincipalAmount),
+
+        /// A relayer has claimed its execution reimbursements from the pool. [relayer, principal]
+        ExecutionReimbursed(ChainAccount, CashPrincipalAmount),
+
+        /// Protocol reserves for an asset have been extracted to a recipient. [asset, recipient, principal]
+        ReservesWithdrawn(ChainAsset, ChainAccount, CashPrincipalAmount),
+
+        /// The global liquidation incentive has been set. [incentive]
+        SetLiquidationIncentive(Bips),
+
+        /// The spread paid to reserves on borrower interest for an asset has been set. [asset, spread]
+        SetSpread(ChainAsset, Bips),
+
+        /// The close factor for liquidations has been set. [close_factor]
+        SetCloseFactor(Factor),
+
+        /// The minimum value required across all protocol interactions has been set. [value]
+        SetMinTxValue(USDQuantity),
+
+        /// The minimum CASH principal required to use a Gateway account has been set. [principal]
+        SetMinPrincipalGate(CashPrincipal),
+
+        /// The kinds of new activity paused on an asset have been set. [asset, pauses]
+        SetAssetPauses(ChainAsset, AssetPauseFlags),
+
+        /// The balance below which positions are swept away as dust has been set for an asset. [asset, threshold]
+        SetDustThreshold(ChainAsset, AssetAmount),
+
+        /// A dust balance left by an operation has been swept into protocol reserves. [asset, account, balance]
+        DustSwept(ChainAsset, ChainAccount, AssetBalance),
+
+        /// An aggregate kept in storage has been found to diverge from what it aggregates. [invariant]
+        BrokenInvariant(BrokenInvariant),
+
         /// Failed to process a given extrinsic. [reason]
         Failure(Reason),
     }
@@ -345,9 +753,25 @@ fn check_failure<T: Config>(res: Result<(), Reason>) -> Result<(), Reason> {
     res
 }
 
+/// Receives governance actions from Starports, to be dispatched as root once they are allowed to be.
+pub trait GovernanceQueue<T: Config> {
+    fn queue_governance(extrinsics: Vec<Vec<u8>>) -> Result<(), Reason>;
+}
+
+/// Dispatch governance actions from Starports immediately, for runtimes without a timelock.
+impl<T: Config> GovernanceQueue<T> for () {
+    fn queue_governance(extrinsics: Vec<Vec<u8>>) -> Result<(), Reason> {
+        core::dispatch_extrinsics_internal::<T>(extrinsics)
+    }
+}
+
+/// The engine id under which Aura records the slot of a block in its pre-runtime digest.
+const AURA_ENGINE_ID: ConsensusEngineId = *b"aura";
+
 pub trait SessionInterface<AccountId>: frame_system::Config {
     fn has_next_keys(x: AccountId) -> bool;
     fn rotate_session();
+    fn block_author() -> Option<AccountId>;
 }
 
 impl<T: Config> SessionInterface<SubstrateId> for T
@@ -364,6 +788,31 @@ where
     fn rotate_session() {
         <pallet_session::Module<T>>::rotate_session();
     }
+
+    // Aura assigns each slot to the session validator at the slot index, round-robin
+    fn block_author() -> Option<SubstrateId> {
+        let digest = <frame_system::Module<T>>::digest();
+        let slot = digest
+            .logs
+            .iter()
+            .find_map(|log| match log.as_pre_runtime() {
+                Some((AURA_ENGINE_ID, mut data)) => u64::decode(&mut data).ok(),
+                _ => None,
+            })?;
+        let validators = <pallet_session::Module<T>>::validators();
+        if validators.is_empty() {
+            return None;
+        }
+        validators
+            .get((slot % validators.len() as u64) as usize)
+            .cloned()
+    }
+}
+
+impl<T: Config> pallet_oracle::ValidatorSet for Module<T> {
+    fn eth_addresses() -> Vec<pallet_oracle::types::ValidatorAddress> {
+        Validators::iter().map(|(_, v)| v.eth_address).collect()
+    }
 }
 
 impl<T: Config> pallet_session::SessionManager<SubstrateId> for Module<T> {
@@ -394,8 +843,9 @@ impl<T: Config> pallet_session::SessionManager<SubstrateId> for Module<T> {
             ()
         }
     }
-    fn end_session(_: SessionIndex) {
-        ()
+    fn end_session(index: SessionIndex) {
+        internal::liveness::end_session::<T>(index);
+        internal::earnings::end_session::<T>(index);
     }
 }
 
@@ -428,8 +878,10 @@ fn has_requisite_signatures(notice_state: NoticeState, validators: &Vec<Validato
                     vec_to_set::<EthAddrType>(signature_pairs.iter().map(|p| p.0).collect());
                 let validator_set =
                     vec_to_set::<EthAddrType>(validators.iter().map(|v| v.eth_address).collect());
+                // Note: the Starport counts signers rather than weighing them, so notices must too
                 chains::has_super_majority::<EthAddrType>(&signature_set, &validator_set)
             }
+            ChainSignatureList::Threshold(..) => signature_pairs.has_threshold_signature(),
             _ => false,
         },
         _ => false,
@@ -458,7 +910,8 @@ impl<T: Config> pallet_session::ShouldEndSession<T::BlockNumber> for Module<T> {
             }
         } else {
             // no era changes pending, periodic
-            let period: T::BlockNumber = <T>::BlockNumber::from(params::SESSION_PERIOD as u32);
+            let period: T::BlockNumber =
+                <T>::BlockNumber::from(internal::parameters::get_session_period());
             let is_new_period = (now % period) == <T>::BlockNumber::from(0 as u32);
 
             if is_new_period {
@@ -480,7 +933,8 @@ impl<T: Config> frame_support::traits::EstimateNextSessionRotation<T::BlockNumbe
     }
 
     fn estimate_current_session_progress(now: T::BlockNumber) -> (Option<Percent>, Weight) {
-        let period: T::BlockNumber = <T>::BlockNumber::from(params::SESSION_PERIOD as u32);
+        let period: T::BlockNumber =
+            <T>::BlockNumber::from(internal::parameters::get_session_period());
         (
             Some(Percent::from_rational(now % period, period)),
             Weight::zero(),
@@ -488,7 +942,8 @@ impl<T: Config> frame_support::traits::EstimateNextSessionRotation<T::BlockNumbe
     }
 
     fn estimate_next_session_rotation(now: T::BlockNumber) -> (Option<T::BlockNumber>, Weight) {
-        let period: T::BlockNumber = <T>::BlockNumber::from(params::SESSION_PERIOD as u32);
+        let period: T::BlockNumber =
+            <T>::BlockNumber::from(internal::parameters::get_session_period());
         (Some(now + period - now % period), Weight::zero())
     }
 }
@@ -498,20 +953,103 @@ fn get_exec_req_weights<T: Config>(request: Vec<u8>) -> frame_support::weights::
         Err(_) => return params::ERROR_WEIGHT,
         Ok(f) => f,
     };
-    match trx_request::parse_request(request_str) {
-        Ok(trx_request::TrxRequest::Extract(_max_amount, _asset, _account)) => {
+    // Only versions of the grammar the runtime knows how to weigh are accepted
+    match trx_request::parse_versioned_request(request_str) {
+        Ok((trx_request::Version::V1, trx_request)) => get_trx_request_weights::<T>(&trx_request),
+        _ => params::ERROR_WEIGHT,
+    }
+}
+
+fn get_trx_request_weights<T: Config>(
+    trx_request: &trx_request::TrxRequest,
+) -> frame_support::weights::Weight {
+    match trx_request {
+        trx_request::TrxRequest::Extract(_max_amount, _asset, _account) => {
             <T as Config>::WeightInfo::exec_trx_request_extract()
         }
 
-        Ok(trx_request::TrxRequest::Transfer(_max_amount, _asset, _account)) => {
+        trx_request::TrxRequest::Transfer(_max_amount, _asset, _account) => {
             <T as Config>::WeightInfo::exec_trx_request_transfer()
         }
 
-        Ok(trx_request::TrxRequest::Liquidate(_max_amount, _borrowed, _collat, _account)) => {
+        trx_request::TrxRequest::TransferMany(_asset, transfers) => {
+            <T as Config>::WeightInfo::exec_trx_request_transfer()
+                .saturating_mul(transfers.len() as frame_support::weights::Weight)
+        }
+
+        // Supply and Repay run the same pipeline as a transfer, Repay also reads the borrower's balance
+        trx_request::TrxRequest::Supply(_max_amount, _asset, _account) => {
+            <T as Config>::WeightInfo::exec_trx_request_transfer()
+        }
+
+        trx_request::TrxRequest::Repay(_max_amount, _asset, _account) => {
+            <T as Config>::WeightInfo::exec_trx_request_transfer()
+                .saturating_add(T::DbWeight::get().reads(1))
+        }
+
+        trx_request::TrxRequest::Approve(_max_amount, _asset, _spender) => {
+            <T as Config>::WeightInfo::exec_trx_request_approve()
+        }
+
+        trx_request::TrxRequest::TransferFrom(_amount, _asset, _owner, _recipient) => {
+            <T as Config>::WeightInfo::exec_trx_request_transfer_from()
+        }
+
+        trx_request::TrxRequest::Liquidate(_max_amount, _borrowed, _collat, _account) => {
             <T as Config>::WeightInfo::exec_trx_request_liquidate()
         }
 
-        _ => params::ERROR_WEIGHT,
+        trx_request::TrxRequest::LiquidateMany(_max_amount, _borrowed, collats, _account) => {
+            <T as Config>::WeightInfo::exec_trx_request_liquidate()
+                .saturating_mul(collats.len() as frame_support::weights::Weight)
+        }
+
+        trx_request::TrxRequest::Cancel(_nonce) => {
+            <T as Config>::WeightInfo::exec_trx_request_cancel()
+        }
+
+        trx_request::TrxRequest::SetNonceWindow(_window) => {
+            <T as Config>::WeightInfo::exec_trx_request_set_nonce_window()
+        }
+
+        trx_request::TrxRequest::ClaimRewards => {
+            <T as Config>::WeightInfo::exec_trx_request_transfer().saturating_mul(
+                SupportedAssets::iter().count().max(1) as frame_support::weights::Weight,
+            )
+        }
+
+        trx_request::TrxRequest::Bond(_max_amount) => {
+            <T as Config>::WeightInfo::exec_trx_request_transfer()
+        }
+
+        trx_request::TrxRequest::Unbond(_max_amount) => {
+            <T as Config>::WeightInfo::exec_trx_request_cancel()
+        }
+
+        trx_request::TrxRequest::Batch(operations) => {
+            operations.iter().fold(0, |weight, operation| {
+                weight.saturating_add(get_trx_request_weights::<T>(operation))
+            })
+        }
+    }
+}
+
+fn get_chain_blocks_weights_eth_like<T: Config>(
+    blocks: &Vec<EthereumBlock>,
+) -> frame_support::weights::Weight {
+    let block_count = blocks.len().try_into().unwrap_or(u32::MAX);
+    let event_count = blocks
+        .iter()
+        .fold(0usize, |acc, x| acc.saturating_add(x.events.len()))
+        .try_into()
+        .unwrap_or(u32::MAX);
+    <T as Config>::WeightInfo::receive_chain_blocks(block_count, event_count)
+}
+
+fn get_chain_blocks_weights<T: Config>(blocks: &ChainBlocks) -> frame_support::weights::Weight {
+    match blocks {
+        ChainBlocks::Eth(blocks) => get_chain_blocks_weights_eth_like::<T>(blocks),
+        ChainBlocks::Matic(blocks) => get_chain_blocks_weights_eth_like::<T>(blocks),
     }
 }
 
@@ -533,6 +1071,11 @@ fn get_chain_reorg_weights_eth_like<T: Config>(
         .try_into()
         .unwrap();
     let event_count = forward_event_count + reverse_event_count;
+    let depth = forward_blocks
+        .len()
+        .max(reverse_blocks.len())
+        .try_into()
+        .unwrap_or(u32::MAX);
 
     if let Some(prior) = PendingChainReorgs::get(chain_id)
         .iter_mut()
@@ -540,17 +1083,21 @@ fn get_chain_reorg_weights_eth_like<T: Config>(
     {
         let validator = core::recover_validator::<T>(&reorg.encode(), *signature)?;
         // if reorg would get applied, just estimate gas by counting the number of reorged events
-        if prior.would_have_enough_support(&prior.support, &validator) {
+        let validator_set = core::get_validator_set::<T>()?;
+        let signer_weights = core::get_signer_weights::<T>();
+        if prior.would_have_enough_support(&validator_set, &signer_weights, &validator) {
             let avg_weight = <T as Config>::WeightInfo::exec_trx_request_extract();
             // TODO: only count forward weight if we have passed than min_event_blocks
             Ok(event_count * avg_weight)
         } else {
             Ok(<T as Config>::WeightInfo::receive_chain_reorg_pending(
+                depth,
                 event_count.try_into().unwrap(),
             ))
         }
     } else {
         Ok(<T as Config>::WeightInfo::receive_chain_reorg_pending(
+            depth,
             event_count.try_into().unwrap(),
         ))
     }
@@ -601,15 +1148,16 @@ decl_module! {
         // Events must be initialized if they are used by the pallet.
         fn deposit_event() = default;
 
+        /// Called by substrate when the runtime is upgraded, to migrate storage to the current version.
         fn on_runtime_upgrade() -> Weight {
-            0 // XXX
+            internal::migrations::on_runtime_upgrade::<T>()
         }
 
         /// Called by substrate on block initialization.
         /// Our initialization function is fallible, but that's not allowed.
         fn on_initialize(block: T::BlockNumber) -> frame_support::weights::Weight {
             match internal::initialize::on_initialize::<T>() {
-                Ok(()) => <T as Config>::WeightInfo::on_initialize(SupportedAssets::iter().count().try_into().unwrap()),
+                Ok(swept) => <T as Config>::WeightInfo::on_initialize(swept),
                 Err(err) => {
                     // This should never happen...
                     error!("Could not initialize block!!! {:#?} {:#?}", block, err);
@@ -618,33 +1166,51 @@ decl_module! {
             }
         }
 
+        /// Called by substrate on block finalization.
+        fn on_finalize() {
+            internal::notices::flush_extraction_batches::<T>();
+        }
+
         /// Offchain Worker entry point.
         fn offchain_worker(block_number: T::BlockNumber) {
             match internal::events::track_chain_events::<T>() {
                 Ok(()) => (),
                 Err(Reason::WorkerBusy) => {
-                    debug!("offchain_worker is still busy in track_chain_events");
+                    debug!(target: EVENTS, "offchain_worker is still busy in track_chain_events");
                 }
                 Err(err) => {
-                    error!("offchain_worker error during track_chain_events: {:?}", err);
+                    error!(target: EVENTS, "offchain_worker error during track_chain_events"; error = err);
                 }
             }
 
+            if let Err(err) = internal::health::track_chain_heads::<T>() {
+                error!(target: EVENTS, "offchain_worker error during track_chain_heads"; error = err);
+            }
+
+            if let Err(err) = internal::invariants::audit_invariants::<T>(block_number) {
+                error!(target: AUDIT, "offchain_worker error during audit_invariants"; error = err);
+            }
+
             // XXX we need to 'lock' notices too right?
+            if internal::pause::is_emergency_paused() {
+                debug!(target: NOTICES, "offchain_worker not processing notices during emergency pause");
+                return;
+            }
+
             match internal::notices::process_notices::<T>(block_number) {
                 (succ, skip, failures) => {
                     if succ > 0 || skip > 0 {
-                        log!("offchain_worker process_notices: {} successful, {} skipped", succ, skip);
+                        log!(target: NOTICES, "offchain_worker process_notices"; successful = succ, skipped = skip);
                     }
                     if failures.len() > 0 {
-                        error!("offchain_worker error(s) during process notices: {:?}", failures);
+                        error!(target: NOTICES, "offchain_worker error(s) during process notices"; failures = failures);
                     }
                 }
             }
         }
 
         /// Sets the miner of the this block via inherent
-        #[weight = (0, DispatchClass::Operational)]
+        #[weight = (<T as Config>::WeightInfo::set_miner(), DispatchClass::Operational)]
         fn set_miner(origin, miner: ChainAccount) {
             ensure_none(origin)?;
             internal::miner::set_miner::<T>(miner);
@@ -657,13 +1223,69 @@ decl_module! {
             Ok(check_failure::<T>(internal::change_validators::change_validators::<T>(validators))?)
         }
 
-        /// Sets the allowed next code hash to the given hash. [Root]
+        /// Drops a compromised validator from the active set immediately, forcing a change to the remaining validators. [Root]
+        #[weight = (<T as Config>::WeightInfo::change_validators(), DispatchClass::Operational, Pays::No)]
+        pub fn remove_validator_immediately(origin, substrate_id: SubstrateId) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::change_validators::remove_validator_immediately::<T>(substrate_id))?)
+        }
+
+        /// Rotates the Eth key of an active validator, signed by both its current and new keys. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::change_validators(), DispatchClass::Operational, Pays::No)]
+        pub fn rotate_keys(origin, new_account: ChainAccount, current_signature: ChainAccountSignature, new_signature: ChainAccountSignature) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::change_validators::rotate_keys::<T>(new_account, current_signature, new_signature))?)
+        }
+
+        /// Advertises the next Eth key of an active validator, signed by both its current and next keys. [User] [Free]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn advertise_next_eth_key(origin, next_account: ChainAccount, current_signature: ChainAccountSignature, next_signature: ChainAccountSignature) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::change_validators::advertise_next_eth_key::<T>(next_account, current_signature, next_signature))?)
+        }
+
+        /// Clears the offenses of a validator, no longer withholding its mining earnings. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn pardon_validator(origin, validator: ValidatorKeys) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::slashing::pardon_validator::<T>(validator))?)
+        }
+
+        /// Switch chain block and reorg tallies to or from being weighted by validator stake, as well as counted. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_stake_weighted_thresholds(origin, enabled: bool) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::stake::set_stake_weighted_thresholds::<T>(enabled))?)
+        }
+
+        /// Set the minimum CASH principal a validator's Eth account must have bonded to join the validator set. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_min_validator_bond(origin, principal: CashPrincipalAmount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::bonding::set_min_validator_bond::<T>(principal))?)
+        }
+
+        /// Set the maximum fraction of chain blocks and notices a validator may miss within a session. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_max_missed_fraction(origin, fraction: Factor) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::liveness::set_max_missed_fraction::<T>(fraction))?)
+        }
+
+        /// Sets the allowed next code hash to the given hash, until it expires. [Root]
         #[weight = (<T as Config>::WeightInfo::allow_next_code_with_hash(), DispatchClass::Operational, Pays::No)]
         pub fn allow_next_code_with_hash(origin, hash: CodeHash) -> dispatch::DispatchResult {
             ensure_root(origin)?;
             Ok(check_failure::<T>(internal::next_code::allow_next_code_with_hash::<T>(hash))?)
         }
 
+        /// Revokes the allowed next code hash, so it can no longer be used. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn revoke_next_code_hash(origin) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::next_code::revoke_next_code_hash::<T>())?)
+        }
+
         /// Sets the allowed next code hash to the given hash. [User] [Free]
         #[weight = (
             <T as Config>::WeightInfo::set_next_code_via_hash(code.len().try_into().unwrap_or(u32::MAX)),
@@ -677,7 +1299,7 @@ decl_module! {
             Ok(res?)
         }
 
-        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        #[weight = (<T as Config>::WeightInfo::set_starport(), DispatchClass::Operational, Pays::No)]
         pub fn set_starport(origin, starport: ChainStarport) -> dispatch::DispatchResult {
             ensure_root(origin)?;
             log!("Setting Starport to {:?}", starport);
@@ -685,7 +1307,72 @@ decl_module! {
             Ok(())
         }
 
+        /// Sets the L1 contract used to determine which blocks of a chain are final [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_checkpoint_contract(origin, chain_id: ChainId, contract: ChainAccount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            log!("Setting checkpoint contract for {:?} to {:?}", chain_id, contract);
+            CheckpointContracts::insert(chain_id, contract);
+            Ok(())
+        }
+
+        /// Retries processing an event which previously failed. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn retry_failed_event(origin, chain_id: ChainId, event_id: FailedEventId) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::failed_events::retry_failed_event::<T>(chain_id, event_id))?)
+        }
+
+        /// Stops ingesting blocks from the given chain, until resumed. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn pause_chain_ingestion(origin, chain_id: ChainId) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::ingestion::pause_chain_ingestion::<T>(chain_id))?)
+        }
+
+        /// Resumes ingesting blocks from the given chain. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn resume_chain_ingestion(origin, chain_id: ChainId) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::ingestion::resume_chain_ingestion::<T>(chain_id))?)
+        }
+
+        /// Sets or removes the account allowed to trigger an emergency pause. [Root]
         #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_pause_guardian(origin, guardian: Option<ChainAccount>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::pause::set_pause_guardian::<T>(guardian))?)
+        }
+
+        /// Halts transaction requests and notice signing, signed by the pause guardian. [User] [Free]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn emergency_pause(origin, signature: ChainAccountSignature, nonce: Nonce) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::pause::emergency_pause::<T>(signature, nonce))?)
+        }
+
+        /// Resumes transaction requests and notice signing after an emergency pause. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn emergency_unpause(origin) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::pause::emergency_unpause::<T>())?)
+        }
+
+        /// Sets the maximum number of extractions per chain combined into a single notice. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_extraction_batch_size(origin, size: u32) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::notices::set_extraction_batch_size::<T>(size))?)
+        }
+
+        /// Sets the threshold key which signs notices for a chain on behalf of the validators, or unsets it. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_threshold_signer(origin, chain_id: ChainId, signer: Option<ChainAccount>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::notices::set_threshold_signer::<T>(chain_id, signer))?)
+        }
+
+        #[weight = (<T as Config>::WeightInfo::set_genesis_block(), DispatchClass::Operational, Pays::No)]
         pub fn set_genesis_block(origin, chain_block: ChainBlock) -> dispatch::DispatchResult {
             ensure_root(origin)?;
             log!("Setting last processed block to {:?}", chain_block);
@@ -708,6 +1395,69 @@ decl_module! {
             Ok(check_failure::<T>(internal::assets::set_liquidity_factor::<T>(asset, factor))?)
         }
 
+        /// Set the liquidation incentive for seizing an asset as collateral, or clear it to use the global incentive. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_liquidation_incentive(origin, asset: ChainAsset, incentive: Option<Bips>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_liquidation_incentive::<T>(asset, incentive))?)
+        }
+
+        /// Set the liquidation incentive for any collateral which does not set its own. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_global_liquidation_incentive(origin, incentive: Bips) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_global_liquidation_incentive::<T>(incentive))?)
+        }
+
+        /// Set the maximum fraction of a borrow which may be repaid in a single liquidation. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_close_factor(origin, close_factor: Factor) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::liquidate::set_close_factor::<T>(close_factor))?)
+        }
+
+        /// Set the minimum value (USD) required across all protocol interactions. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_min_tx_value(origin, value: USDQuantity) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_min_tx_value::<T>(value))?)
+        }
+
+        /// Set the minimum CASH principal required in order to use a Gateway account. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_min_principal_gate(origin, principal: CashPrincipal) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::transfer::set_min_principal_gate::<T>(principal))?)
+        }
+
+        /// Pause or unpause new supply, borrowing and extraction of an asset. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_asset_pauses(origin, asset: ChainAsset, pauses: AssetPauseFlags) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_asset_pauses::<T>(asset, pauses))?)
+        }
+
+        /// Set the fraction of borrower interest on an asset which is paid to protocol reserves, in bips. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_spread(origin, asset: ChainAsset, spread: Bips) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_spread::<T>(asset, spread))?)
+        }
+
+        /// Set the balance below which an asset position left by an operation is swept away as dust. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_dust_threshold(origin, asset: ChainAsset, threshold: AssetAmount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_dust_threshold::<T>(asset, threshold))?)
+        }
+
+        /// Set the fraction of borrower interest on an asset which is not paid to suppliers, in bips. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_miner_share(origin, asset: ChainAsset, share: Bips) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::set_miner_share::<T>(asset, share))?)
+        }
+
         /// Update the interest rate model for a given asset. [Root]
         #[weight = (<T as Config>::WeightInfo::set_rate_model(), DispatchClass::Operational, Pays::No)]
         pub fn set_rate_model(origin, asset: ChainAsset, model: InterestRateModel) -> dispatch::DispatchResult {
@@ -715,6 +1465,76 @@ decl_module! {
             Ok(check_failure::<T>(internal::assets::set_rate_model::<T>(asset, model))?)
         }
 
+        /// Extract accrued protocol reserves for an asset to a recipient. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn withdraw_reserves(origin, asset: ChainAsset, amount: CashPrincipalAmount, recipient: ChainAccount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::assets::withdraw_reserves::<T>(asset, amount, recipient))?)
+        }
+
+        /// Write off the debts of an account with no collateral left to liquidate. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn absorb(origin, account: ChainAccount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::bad_debt::absorb_internal::<T>(account))?)
+        }
+
+        /// Sets the share of the miner spread which accrues to the insurance fund, in bips. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_insurance_share(origin, share: Bips) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::insurance::set_insurance_share::<T>(share))?)
+        }
+
+        /// Pay out some of the insurance fund to a recipient, as CASH. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn pay_insurance(origin, amount: CashPrincipalAmount, recipient: ChainAccount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::insurance::pay_insurance::<T>(amount, recipient))?)
+        }
+
+        /// Sets or removes the cap on the CASH principal held on a chain. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_chain_cash_cap(origin, chain_id: ChainId, cap: Option<CashPrincipalAmount>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::supply_cap::set_chain_cash_cap::<T>(chain_id, cap))?)
+        }
+
+        /// Sets or removes the cap on the value (USD) of the assets supplied from a chain. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_chain_asset_cap(origin, chain_id: ChainId, cap: Option<USDQuantity>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::supply_cap::set_chain_asset_cap::<T>(chain_id, cap))?)
+        }
+
+        /// Sets or removes the per-account limit on extracting an asset over a rolling window. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_extract_rate_limit(origin, asset: CashOrChainAsset, limit: Option<ExtractRateLimit>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::extract::set_extract_rate_limit::<T>(asset, limit))?)
+        }
+
+        /// Sets or removes the amount above which a single extraction of an asset is timelocked. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_extract_delay_threshold(origin, asset: CashOrChainAsset, threshold: Option<AssetAmount>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::extract::set_extract_delay_threshold::<T>(asset, threshold))?)
+        }
+
+        /// Sets the CASH principal emitted each day as rewards to the suppliers and borrowers of an asset. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_reward_speeds(origin, asset: ChainAsset, speeds: RewardSpeeds) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::rewards::set_reward_speeds::<T>(asset, speeds))?)
+        }
+
+        /// Recompute the protocol's aggregates, emitting an event for each which has diverged. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn assert_invariants(origin) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(internal::invariants::assert_invariants::<T>())?)
+        }
+
         /// Set the cash yield rate at some point in the future. [Root]
         #[weight = (<T as Config>::WeightInfo::set_yield_next(), DispatchClass::Operational, Pays::No)]
         pub fn set_yield_next(origin, next_apr: APR, next_apr_start: Timestamp) -> dispatch::DispatchResult {
@@ -730,7 +1550,7 @@ decl_module! {
         }
 
         /// Receive the chain blocks message from the worker to make progress on event ingression. [Root]
-        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        #[weight = (get_chain_blocks_weights::<T>(blocks), DispatchClass::Operational, Pays::No)]
         pub fn receive_chain_blocks(origin, blocks: ChainBlocks, signature: ChainSignature) -> dispatch::DispatchResult {
             log!("receive_chain_blocks(origin, blocks, signature): {:?} {:?}", blocks, signature);
             ensure_none(origin)?;
@@ -751,6 +1571,62 @@ decl_module! {
             Ok(check_failure::<T>(internal::notices::publish_signature::<T>(chain_id, notice_id, signature))?)
         }
 
+        /// Claim the execution fees earned by a relayer for relaying notices. [User] [Free]
+        #[weight = (0, DispatchClass::Normal, Pays::No)]
+        pub fn claim_execution_reimbursement(origin, relayer: ChainAccount) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::execution_fees::claim_execution_reimbursement::<T>(relayer))?)
+        }
+
+        /// Open a liquidation auction for an underwater account. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::exec_trx_request_liquidate(), DispatchClass::Normal, Pays::No)]
+        pub fn open_liquidation_auction(origin, borrower: ChainAccount) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::auction::open_liquidation_auction::<T>(borrower))?)
+        }
+
+        /// Close the liquidation auction for an account which is no longer underwater. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::exec_trx_request_liquidate(), DispatchClass::Normal, Pays::No)]
+        pub fn close_liquidation_auction(origin, borrower: ChainAccount) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::auction::close_liquidation_auction::<T>(borrower))?)
+        }
+
+        /// Liquidate an account at the current bonus of its auction, signed by the bidder. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::exec_trx_request_liquidate(), DispatchClass::Normal, Pays::No)]
+        pub fn bid_liquidation_auction(origin, borrower: ChainAccount, borrowed: CashOrChainAsset, collateral: ChainAsset, amount: AssetAmount, signature: ChainAccountSignature, nonce: Nonce) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::auction::bid_liquidation_auction::<T>(borrower, borrowed, collateral, amount, signature, nonce))?)
+        }
+
+        /// Cancel a timelocked extraction before its release, signed by its sender. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::exec_trx_request_extract(), DispatchClass::Normal, Pays::No)]
+        pub fn cancel_pending_extract(origin, id: PendingExtractId, signature: ChainAccountSignature, nonce: Nonce) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::extract::cancel_pending_extract::<T>(id, signature, nonce))?)
+        }
+
+        /// Publish the metadata identifying who runs a validator, signed by its Eth key. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::exec_trx_request_cancel(), DispatchClass::Normal, Pays::No)]
+        pub fn set_validator_metadata(origin, metadata: ValidatorMetadata, signature: ChainAccountSignature, nonce: Nonce) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::metadata::set_validator_metadata::<T>(metadata, signature, nonce))?)
+        }
+
+        /// Link an Eth account with a Gate account, signed by both sides. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::publish_signature(), DispatchClass::Normal, Pays::No)]
+        pub fn link_accounts(origin, eth_signature: ChainAccountSignature, gate_signature: ChainAccountSignature) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::link::link_accounts::<T>(eth_signature, gate_signature))?)
+        }
+
+        /// Unlink an account from the account it is linked with, signed by either side. [User] [Free]
+        #[weight = (<T as Config>::WeightInfo::publish_signature(), DispatchClass::Normal, Pays::No)]
+        pub fn unlink_accounts(origin, signature: ChainAccountSignature, nonce: Nonce) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(internal::link::unlink_accounts::<T>(signature, nonce))?)
+        }
+
         /// Execute a transaction request on behalf of a user
         #[weight = (get_exec_req_weights::<T>(request.to_vec()), DispatchClass::Normal, Pays::No)]
         pub fn exec_trx_request(origin, request: Vec<u8>, signature: ChainAccountSignature, nonce: Nonce) -> dispatch::DispatchResult {
@@ -857,6 +1733,34 @@ impl<T: Config> Module<T> {
         Ok(core::get_liquidity::<T>(account)?.value)
     }
 
+    /// Get the full cash balance for the given account combined with any account linked to it, for viewing only.
+    pub fn get_logical_cash_balance(account: ChainAccount) -> Result<AssetBalance, Reason> {
+        Ok(core::get_logical_cash_balance::<T>(account)?.value)
+    }
+
+    /// Get the liquidity for the given account combined with any account linked to it, for viewing only.
+    pub fn get_logical_liquidity(account: ChainAccount) -> Result<AssetBalance, Reason> {
+        Ok(core::get_logical_liquidity::<T>(account)?.value)
+    }
+
+    /// Get the health factor for the given account, if it is borrowing.
+    pub fn get_health_factor(account: ChainAccount) -> Result<Option<Factor>, Reason> {
+        Ok(core::get_health_factor::<T>(account)?)
+    }
+
+    /// Get the most of the given asset the account can extract.
+    pub fn get_max_extract(
+        account: ChainAccount,
+        asset: CashOrChainAsset,
+    ) -> Result<AssetAmount, Reason> {
+        Ok(core::get_max_extract::<T>(account, asset)?)
+    }
+
+    /// Get a summary of every supported market.
+    pub fn get_markets_summary() -> Result<Vec<MarketSummary>, Reason> {
+        Ok(core::get_markets_summary::<T>()?)
+    }
+
     /// Get the total supply for the given asset.
     pub fn get_market_totals(asset: ChainAsset) -> Result<(AssetAmount, AssetAmount), Reason> {
         Ok(core::get_market_totals::<T>(asset)?)
@@ -877,6 +1781,14 @@ impl<T: Config> Module<T> {
         Ok(core::get_accounts::<T>()?)
     }
 
+    /// Get a page of the accounts holding CASH or an asset, after the given account.
+    pub fn get_accounts_page(
+        start_key: Option<ChainAccount>,
+        limit: u32,
+    ) -> Result<Vec<ChainAccount>, Reason> {
+        Ok(core::get_accounts_page::<T>(start_key, limit)?)
+    }
+
     /// Get the user counts for the given asset.
     pub fn get_asset_meta(
     ) -> Result<(BTreeMap<String, u32>, BTreeMap<String, u32>, u32, u32), Reason> {
@@ -892,11 +1804,142 @@ impl<T: Config> Module<T> {
         Ok(accounts)
     }
 
+    /// Get a page of the accounts with their liquidity, after the given account and below the threshold if any.
+    pub fn get_accounts_liquidity_page(
+        start_key: Option<ChainAccount>,
+        limit: u32,
+        below: Option<AssetBalance>,
+    ) -> Result<Vec<(ChainAccount, String)>, Reason> {
+        Ok(
+            core::get_accounts_liquidity_page::<T>(start_key, limit, below)?
+                .into_iter()
+                .map(|(chain_account, bal)| (chain_account, format!("{}", bal)))
+                .collect(),
+        )
+    }
+
+    /// Get the most underwater accounts, with their largest borrow and collateral positions.
+    pub fn get_liquidatable_accounts(
+        max_results: u32,
+    ) -> Result<
+        Vec<(
+            ChainAccount,
+            AssetBalance,
+            Option<CashOrChainAsset>,
+            Option<CashOrChainAsset>,
+        )>,
+        Reason,
+    > {
+        Ok(core::get_liquidatable_accounts::<T>(max_results)?
+            .into_iter()
+            .map(|(account, liquidity, borrow, collateral)| {
+                (account, liquidity.value, borrow, collateral)
+            })
+            .collect())
+    }
+
     /// Get the portfolio for the given chain account.
     pub fn get_portfolio(account: ChainAccount) -> Result<Portfolio, Reason> {
         Ok(core::get_portfolio::<T>(account)?)
     }
 
+    /// Get the portfolio for the given chain account combined with any account linked to it, for viewing only.
+    pub fn get_logical_portfolio(account: ChainAccount) -> Result<Portfolio, Reason> {
+        Ok(core::get_logical_portfolio::<T>(account)?)
+    }
+
+    /// Get the operations involving the given account since the given block, oldest first.
+    pub fn get_account_history(
+        account: ChainAccount,
+        from: u32,
+        limit: u32,
+    ) -> Result<Vec<ActivityEntry>, Reason> {
+        Ok(internal::activity::get_account_history::<T>(
+            account, from, limit,
+        ))
+    }
+
+    /// Get the notices for the given account awaiting execution, with their signatures.
+    pub fn get_pending_notices(
+        account: ChainAccount,
+    ) -> Result<Vec<(NoticeId, EncodedNotice, ChainSignatureList)>, Reason> {
+        Ok(internal::notices::get_pending_notices::<T>(account)?)
+    }
+
+    /// Get the Starport, calldata and last gas estimate for relaying the given notice.
+    pub fn simulate_notice(
+        chain_id: ChainId,
+        notice_id: NoticeId,
+    ) -> Result<(ChainStarport, Vec<u8>, Option<u64>), Reason> {
+        Ok(internal::notices::simulate_notice::<T>(
+            chain_id, notice_id,
+        )?)
+    }
+
+    /// Dry-run a trx request from an account against current state, without committing any changes.
+    pub fn estimate_trx_request(
+        request: Vec<u8>,
+        account: ChainAccount,
+    ) -> Result<TrxRequestEstimate, Reason> {
+        Ok(internal::estimate::estimate_trx_request::<T>(
+            request, account,
+        )?)
+    }
+
+    /// Get the health of ingestion and notices for each underlying chain.
+    pub fn get_chain_health() -> Result<Vec<ChainHealth>, Reason> {
+        Ok(internal::health::get_chain_health::<T>()?)
+    }
+
+    /// Get the backlogs of ingression, notices and prices, to export as metrics.
+    pub fn get_protocol_metrics() -> Result<ProtocolMetrics, Reason> {
+        Ok(internal::health::get_protocol_metrics::<T>()?)
+    }
+
+    /// Dry-run governance actions as Root against current state, without committing any changes.
+    pub fn simulate_governance(actions: Vec<Vec<u8>>) -> Result<Vec<GovernanceResult>, Reason> {
+        Ok(core::simulate_governance::<T>(actions))
+    }
+
+    /// Check storage can be migrated, before trying a runtime upgrade against live state.
+    pub fn pre_upgrade_checks() -> Result<(), &'static str> {
+        internal::migrations::pre_upgrade::<T>()
+    }
+
+    /// Check storage was migrated, and dispatch any governance payloads configured to try,
+    /// after trying a runtime upgrade against live state.
+    pub fn post_upgrade_checks() -> Result<(), &'static str> {
+        internal::migrations::post_upgrade::<T>()
+    }
+
+    /// Get the participation expected of validators this session, and that of each current validator.
+    pub fn get_validator_participation() -> Result<
+        (
+            ValidatorParticipation,
+            Vec<(ValidatorKeys, ValidatorParticipation)>,
+        ),
+        Reason,
+    > {
+        Ok(internal::liveness::get_validator_participation::<T>())
+    }
+
+    /// Get the current validators, with the metadata each has published, if any.
+    pub fn get_validator_metadata(
+    ) -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>)>, Reason> {
+        Ok(internal::metadata::get_validator_metadata::<T>())
+    }
+
+    /// Get the earnings of a validator in its most recent sessions, most recent last.
+    pub fn get_validator_earnings(
+        substrate_id: SubstrateId,
+        last_n_sessions: u32,
+    ) -> Result<Vec<SessionEarnings>, Reason> {
+        Ok(internal::earnings::get_validator_earnings::<T>(
+            substrate_id,
+            last_n_sessions,
+        ))
+    }
+
     /// Get the active validators, and  sets
     pub fn get_validator_info() -> Result<(Vec<ValidatorKeys>, Vec<(ChainAccount, String)>), Reason>
     {
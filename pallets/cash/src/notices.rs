@@ -1,6 +1,10 @@
 use crate::{
-    chains::{Chain, ChainHash, ChainId, ChainSignature, ChainSignatureList, Ethereum, Polygon},
+    chains::{
+        Chain, ChainAccount, ChainAsset, ChainHash, ChainId, ChainSignature, ChainSignatureList,
+        Ethereum, Polygon,
+    },
     reason::Reason,
+    types::CashOrChainAsset,
 };
 use codec::{Decode, Encode};
 use ethabi::Token;
@@ -44,6 +48,8 @@ impl NoticeId {
 lazy_static! {
     static ref UNLOCK_SIG: <Ethereum as Chain>::Hash =
         <Ethereum as Chain>::hash_bytes(b"unlock(address,uint256,address)");
+    static ref UNLOCK_BATCH_SIG: <Ethereum as Chain>::Hash =
+        <Ethereum as Chain>::hash_bytes(b"unlockBatch(address[],uint256[],address[])");
     static ref UNLOCK_CASH_SIG: <Ethereum as Chain>::Hash =
         <Ethereum as Chain>::hash_bytes(b"unlockCash(address,uint128)");
     static ref SET_FUTURE_YIELD_SIG: <Ethereum as Chain>::Hash =
@@ -52,6 +58,8 @@ lazy_static! {
         <Ethereum as Chain>::hash_bytes(b"setSupplyCap(address,uint256)");
     static ref CHANGE_AUTHORITIES_SIG: <Ethereum as Chain>::Hash =
         <Ethereum as Chain>::hash_bytes(b"changeAuthorities(address[])");
+    static ref INVOKE_SIG: <Ethereum as Chain>::Hash =
+        <Ethereum as Chain>::hash_bytes(b"invoke(bytes,bytes[])");
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
@@ -72,6 +80,24 @@ pub enum ExtractionNotice {
     },
 }
 
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum ExtractionBatchNotice {
+    Eth {
+        id: NoticeId,
+        parent: <Ethereum as Chain>::Hash,
+        assets: Vec<<Ethereum as Chain>::Address>,
+        amounts: Vec<<Ethereum as Chain>::Amount>,
+        accounts: Vec<<Ethereum as Chain>::Address>,
+    },
+    Matic {
+        id: NoticeId,
+        parent: <Polygon as Chain>::Hash,
+        assets: Vec<<Polygon as Chain>::Address>,
+        amounts: Vec<<Polygon as Chain>::Amount>,
+        accounts: Vec<<Polygon as Chain>::Address>,
+    },
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub enum CashExtractionNotice {
     Eth {
@@ -143,6 +169,7 @@ pub enum Notice {
     FutureYieldNotice(FutureYieldNotice),
     SetSupplyCapNotice(SetSupplyCapNotice),
     ChangeAuthorityNotice(ChangeAuthorityNotice),
+    ExtractionBatchNotice(ExtractionBatchNotice),
 }
 
 impl Notice {
@@ -172,6 +199,55 @@ impl Notice {
                 ChangeAuthorityNotice::Eth { .. } => ChainId::Eth,
                 ChangeAuthorityNotice::Matic { .. } => ChainId::Matic,
             },
+            Notice::ExtractionBatchNotice(n) => match n {
+                ExtractionBatchNotice::Eth { .. } => ChainId::Eth,
+                ExtractionBatchNotice::Matic { .. } => ChainId::Matic,
+            },
+        }
+    }
+
+    /// The accounts a notice pays out to, if any.
+    pub fn accounts(&self) -> Vec<ChainAccount> {
+        match self {
+            Notice::ExtractionNotice(ExtractionNotice::Eth { account, .. })
+            | Notice::CashExtractionNotice(CashExtractionNotice::Eth { account, .. }) => {
+                vec![ChainAccount::Eth(*account)]
+            }
+            Notice::ExtractionNotice(ExtractionNotice::Matic { account, .. })
+            | Notice::CashExtractionNotice(CashExtractionNotice::Matic { account, .. }) => {
+                vec![ChainAccount::Matic(*account)]
+            }
+            Notice::ExtractionBatchNotice(ExtractionBatchNotice::Eth { accounts, .. }) => {
+                accounts.iter().map(|a| ChainAccount::Eth(*a)).collect()
+            }
+            Notice::ExtractionBatchNotice(ExtractionBatchNotice::Matic { accounts, .. }) => {
+                accounts.iter().map(|a| ChainAccount::Matic(*a)).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// The assets a notice pays out or changes the market for, if any.
+    pub fn assets(&self) -> Vec<CashOrChainAsset> {
+        match self {
+            Notice::ExtractionNotice(ExtractionNotice::Eth { asset, .. })
+            | Notice::SetSupplyCapNotice(SetSupplyCapNotice::Eth { asset, .. }) => {
+                vec![CashOrChainAsset::ChainAsset(ChainAsset::Eth(*asset))]
+            }
+            Notice::ExtractionNotice(ExtractionNotice::Matic { asset, .. })
+            | Notice::SetSupplyCapNotice(SetSupplyCapNotice::Matic { asset, .. }) => {
+                vec![CashOrChainAsset::ChainAsset(ChainAsset::Matic(*asset))]
+            }
+            Notice::ExtractionBatchNotice(ExtractionBatchNotice::Eth { assets, .. }) => assets
+                .iter()
+                .map(|a| CashOrChainAsset::ChainAsset(ChainAsset::Eth(*a)))
+                .collect(),
+            Notice::ExtractionBatchNotice(ExtractionBatchNotice::Matic { assets, .. }) => assets
+                .iter()
+                .map(|a| CashOrChainAsset::ChainAsset(ChainAsset::Matic(*a)))
+                .collect(),
+            Notice::CashExtractionNotice(_) => vec![CashOrChainAsset::Cash],
+            _ => vec![],
         }
     }
 
@@ -184,6 +260,33 @@ pub trait EncodeNotice {
     fn encode_notice(&self) -> EncodedNotice;
 }
 
+/// Encode the Starport call which relays a notice, along with the signatures collected for it.
+pub fn encode_invoke_calldata(
+    encoded_notice: &EncodedNotice,
+    signature_pairs: &ChainSignatureList,
+) -> Result<Vec<u8>, Reason> {
+    let signatures = match signature_pairs {
+        ChainSignatureList::Eth(pairs) | ChainSignatureList::Matic(pairs) => pairs
+            .iter()
+            .map(|(_, signature)| Token::Bytes(signature.to_vec()))
+            .collect(),
+        ChainSignatureList::Threshold(_, signature) => match signature {
+            Some(ChainSignature::Eth(signature)) | Some(ChainSignature::Matic(signature)) => {
+                vec![Token::Bytes(signature.to_vec())]
+            }
+            None => vec![],
+            _ => return Err(Reason::NotImplemented),
+        },
+        _ => return Err(Reason::NotImplemented),
+    };
+    let mut result = INVOKE_SIG[0..4].to_vec();
+    result.extend_from_slice(&ethabi::encode(&[
+        Token::Bytes(encoded_notice.clone()),
+        Token::Array(signatures),
+    ]));
+    Ok(result)
+}
+
 const ETH_CHAIN_IDENT: &'static [u8] = b"ETH:";
 const MATIC_CHAIN_IDENT: &'static [u8] = b"MAT:";
 
@@ -249,6 +352,77 @@ impl EncodeNotice for ExtractionNotice {
     }
 }
 
+impl EncodeNotice for ExtractionBatchNotice {
+    fn encode_notice(&self) -> EncodedNotice {
+        match self {
+            ExtractionBatchNotice::Eth {
+                id,
+                parent,
+                assets,
+                amounts,
+                accounts,
+            } => encode_notice_params_eth_like(
+                id,
+                parent,
+                *UNLOCK_BATCH_SIG,
+                &[
+                    Token::Array(
+                        assets
+                            .iter()
+                            .map(|asset| Token::Address(asset.into()))
+                            .collect(),
+                    ),
+                    Token::Array(
+                        amounts
+                            .iter()
+                            .map(|amount| Token::Uint((*amount).into()))
+                            .collect(),
+                    ),
+                    Token::Array(
+                        accounts
+                            .iter()
+                            .map(|account| Token::Address(account.into()))
+                            .collect(),
+                    ),
+                ],
+                ETH_CHAIN_IDENT.to_vec(),
+            ),
+            ExtractionBatchNotice::Matic {
+                id,
+                parent,
+                assets,
+                amounts,
+                accounts,
+            } => encode_notice_params_eth_like(
+                id,
+                parent,
+                *UNLOCK_BATCH_SIG,
+                &[
+                    Token::Array(
+                        assets
+                            .iter()
+                            .map(|asset| Token::Address(asset.into()))
+                            .collect(),
+                    ),
+                    Token::Array(
+                        amounts
+                            .iter()
+                            .map(|amount| Token::Uint((*amount).into()))
+                            .collect(),
+                    ),
+                    Token::Array(
+                        accounts
+                            .iter()
+                            .map(|account| Token::Address(account.into()))
+                            .collect(),
+                    ),
+                ],
+                MATIC_CHAIN_IDENT.to_vec(),
+            ),
+        }
+    }
+}
+
 impl EncodeNotice for CashExtractionNotice {
     fn encode_notice(&self) -> EncodedNotice {
         match self {
@@ -405,6 +579,7 @@ impl EncodeNotice for Notice {
             Notice::FutureYieldNotice(n) => n.encode_notice(),
             Notice::SetSupplyCapNotice(n) => n.encode_notice(),
             Notice::ChangeAuthorityNotice(n) => n.encode_notice(),
+            Notice::ExtractionBatchNotice(n) => n.encode_notice(),
         }
     }
 }
@@ -431,6 +606,10 @@ pub fn default_notice_signatures(notice: &Notice) -> ChainSignatureList {
             ChangeAuthorityNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
             ChangeAuthorityNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
         },
+        Notice::ExtractionBatchNotice(n) => match n {
+            ExtractionBatchNotice::Eth { .. } => ChainSignatureList::Eth(vec![]),
+            ExtractionBatchNotice::Matic { .. } => ChainSignatureList::Matic(vec![]),
+        },
     }
 }
 
@@ -448,6 +627,13 @@ impl NoticeState {
             signature_pairs: default_notice_signatures(&notice),
         }
     }
+
+    /// A notice awaiting the single signature of the validators' threshold key.
+    pub fn pending_threshold(signer: ChainAccount) -> Self {
+        NoticeState::Pending {
+            signature_pairs: ChainSignatureList::Threshold(signer, None),
+        }
+    }
 }
 
 impl Default for NoticeState {
@@ -525,6 +711,64 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_encodes_extraction_batch_notice() -> Result<(), ethabi::Error> {
+        let assets = vec![[2u8; 20], [4u8; 20]];
+        let amounts = vec![50, 60];
+        let accounts = vec![[1u8; 20], [5u8; 20]];
+
+        let notice = Notice::ExtractionBatchNotice(ExtractionBatchNotice::Eth {
+            id: NoticeId(80, 1),
+            parent: [3u8; 32],
+            assets: assets.clone(),
+            amounts: amounts.clone(),
+            accounts: accounts.clone(),
+        });
+        let encoded = notice.encode_notice();
+
+        let expected_header = [
+            69, 84, 72, 58, // ETH:
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 80, // eraId
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 1, // eraIndex
+            3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3,
+            3, 3, 3, // parent
+            0x01, 0x63, 0xee, 0x85, // Function Signature (0x0163ee85)
+        ];
+        assert_eq!(&encoded[..104], &expected_header[..]);
+
+        // Test against auto-encoding
+        let unlock_batch_fn = Function {
+            name: String::from("unlockBatch"),
+            inputs: vec![
+                Param {
+                    name: String::from("assets"),
+                    kind: ParamType::Array(Box::new(ParamType::Address)),
+                },
+                Param {
+                    name: String::from("amounts"),
+                    kind: ParamType::Array(Box::new(ParamType::Uint(256))),
+                },
+                Param {
+                    name: String::from("accounts"),
+                    kind: ParamType::Array(Box::new(ParamType::Address)),
+                },
+            ],
+            outputs: vec![],
+            constant: false,
+        };
+        assert_eq!(
+            &unlock_batch_fn.encode_input(&[
+                Token::Array(assets.iter().map(|a| Token::Address(a.into())).collect()),
+                Token::Array(amounts.iter().map(|a| Token::Uint((*a).into())).collect()),
+                Token::Array(accounts.iter().map(|a| Token::Address(a.into())).collect()),
+            ])?[..],
+            &encoded[100..]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_encodes_cash_extraction_notice() -> Result<(), ethabi::Error> {
         let account = [1u8; 20];
@@ -759,4 +1003,92 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_encode_invoke_calldata() -> Result<(), ethabi::Error> {
+        let encoded_notice = vec![1, 2, 3];
+        let signature_pairs = ChainSignatureList::Eth(vec![([1; 20], [4; 65]), ([2; 20], [5; 65])]);
+
+        let invoke_fn = Function {
+            name: String::from("invoke"),
+            inputs: vec![
+                Param {
+                    name: String::from("notice"),
+                    kind: ParamType::Bytes,
+                },
+                Param {
+                    name: String::from("signatures"),
+                    kind: ParamType::Array(Box::new(ParamType::Bytes)),
+                },
+            ],
+            outputs: vec![],
+            constant: false,
+        };
+        let expected = invoke_fn.encode_input(&[
+            Token::Bytes(encoded_notice.clone()),
+            Token::Array(vec![Token::Bytes(vec![4; 65]), Token::Bytes(vec![5; 65])]),
+        ])?;
+        assert_eq!(&expected[0..4], &[0xad, 0xcf, 0x8c, 0x85]);
+        assert_eq!(
+            encode_invoke_calldata(&encoded_notice, &signature_pairs),
+            Ok(expected)
+        );
+        assert_eq!(
+            encode_invoke_calldata(&encoded_notice, &ChainSignatureList::Dot(vec![])),
+            Err(Reason::NotImplemented)
+        );
+
+        let threshold = ChainSignatureList::Threshold(
+            ChainAccount::Eth([1; 20]),
+            Some(ChainSignature::Eth([4; 65])),
+        );
+        let expected = invoke_fn.encode_input(&[
+            Token::Bytes(encoded_notice.clone()),
+            Token::Array(vec![Token::Bytes(vec![4; 65])]),
+        ])?;
+        assert_eq!(
+            encode_invoke_calldata(&encoded_notice, &threshold),
+            Ok(expected)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_notice_accounts_and_assets() {
+        let batch = Notice::ExtractionBatchNotice(ExtractionBatchNotice::Matic {
+            id: NoticeId(0, 1),
+            parent: [0; 32],
+            assets: vec![[3; 20], [4; 20]],
+            amounts: vec![10, 20],
+            accounts: vec![[1; 20], [2; 20]],
+        });
+        assert_eq!(
+            batch.accounts(),
+            vec![ChainAccount::Matic([1; 20]), ChainAccount::Matic([2; 20])]
+        );
+        assert_eq!(
+            batch.assets(),
+            vec![
+                CashOrChainAsset::ChainAsset(ChainAsset::Matic([3; 20])),
+                CashOrChainAsset::ChainAsset(ChainAsset::Matic([4; 20]))
+            ]
+        );
+
+        let cash = Notice::CashExtractionNotice(CashExtractionNotice::Eth {
+            id: NoticeId(0, 2),
+            parent: [0; 32],
+            account: [1; 20],
+            principal: 100,
+        });
+        assert_eq!(cash.accounts(), vec![ChainAccount::Eth([1; 20])]);
+        assert_eq!(cash.assets(), vec![CashOrChainAsset::Cash]);
+
+        let change = Notice::ChangeAuthorityNotice(ChangeAuthorityNotice::Eth {
+            id: NoticeId(0, 3),
+            parent: [0; 32],
+            new_authorities: vec![[5; 20]],
+        });
+        assert_eq!(change.accounts(), vec![]);
+        assert_eq!(change.assets(), vec![]);
+    }
 }
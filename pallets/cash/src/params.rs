@@ -1,9 +1,13 @@
 use crate::{
     chains::{ChainAccount, ChainBlockNumber},
     symbol::{CASH, USD},
-    types::{CashPrincipal, Quantity, Timestamp},
+    types::{Bips, CashPrincipal, CashPrincipalAmount, Nonce, Quantity, Timestamp},
 };
 
+/// Number of most recently processed block hashes remembered per underlying chain.
+/// Reorgs must fork from within this window of known history.
+pub const ANCESTOR_HASH_WINDOW: usize = 256;
+
 /// The large value (USD) used for ingesting gov events.
 pub const INGRESS_LARGE: Quantity = Quantity::from_nominal("1000000000000", USD);
 
@@ -11,12 +15,23 @@ pub const INGRESS_LARGE: Quantity = Quantity::from_nominal("1000000000000", USD)
 /// Could become a per-chain quota in the future.
 pub const INGRESS_QUOTA: Quantity = Quantity::from_nominal("10000", USD);
 
-/// Maximum size of the block queue before we back-off sending new blocks.
-pub const INGRESS_SLACK: u32 = 50;
+/// Maximum liquidation incentive on seized collateral (bips), i.e. seizing double the value repaid.
+/// Mirrors the bound on the global liquidation incentive parameter.
+pub const MAX_LIQUIDATION_INCENTIVE: Bips = pallet_parameters::MAX_LIQUIDATION_INCENTIVE;
+
+/// Number of blocks over which the seize bonus in a liquidation auction ramps up to the full incentive.
+pub const LIQUIDATION_AUCTION_RAMP_BLOCKS: u32 = 100;
+
+/// Maximum number of collateral assets which may be seized from in a single liquidation.
+/// Mirrors the limit on collateral assets in a LiquidateMany request.
+pub const MAX_LIQUIDATION_COLLATERALS: usize = trx_request::MAX_LIQUIDATION_COLLATERALS;
 
 /// Number of milliseconds in a year.
 pub const MILLISECONDS_PER_YEAR: Timestamp = 365 * 24 * 60 * 60 * 1000;
 
+/// Number of milliseconds in a day.
+pub const MILLISECONDS_PER_DAY: Timestamp = 24 * 60 * 60 * 1000;
+
 /// Minimum number of underlying chain blocks to wait before ingesting any event, due to reorg risk.
 pub const MIN_EVENT_BLOCKS: ChainBlockNumber = 3;
 
@@ -27,18 +42,74 @@ pub const MAX_EVENT_BLOCKS: ChainBlockNumber = 60;
 /// Must be sufficient time to propagate changes to L1s before they occur.
 pub const MIN_NEXT_SYNC_TIME: Timestamp = 24 * 60 * 60 * 1000; // XXX confirm
 
-/// Minimum CASH principal required in order to use a Gateway account.
+/// Default minimum CASH principal required in order to use a Gateway account.
 /// Note that validators must meet this minimum in order to submit the set session keys extrinsic.
 pub const MIN_PRINCIPAL_GATE: CashPrincipal = CashPrincipal::from_nominal("1");
 
-/// Minimum value (USD) required across all protocol interactions.
-pub const MIN_TX_VALUE: Quantity = Quantity::from_nominal("1", USD);
+/// Maximum minimum CASH principal which may be required in order to use a Gateway account.
+pub const MAX_MIN_PRINCIPAL_GATE: CashPrincipal = CashPrincipal::from_nominal("1000");
+
+/// Maximum number of nonces beyond the next one which an account may accept out of order.
+pub const MAX_NONCE_WINDOW: Nonce = 64;
+
+/// Number of most recent accrual points remembered per asset.
+pub const MAX_RATE_HISTORY: usize = 256;
+
+/// Number of most recent operations remembered per account.
+pub const MAX_ACCOUNT_ACTIVITY: usize = 256;
+
+/// Number of most recent offenses remembered per validator.
+pub const MAX_VALIDATOR_OFFENSES: usize = 16;
+
+/// Maximum length of each field of the metadata a validator publishes.
+pub const MAX_VALIDATOR_METADATA_LEN: usize = 256;
+
+/// Number of most recent sessions whose earnings are remembered per validator.
+pub const MAX_EARNINGS_SESSIONS: usize = 64;
+
+/// Amount of time (milliseconds) unbonded CASH is held before release, so late offenses can still be slashed.
+pub const UNBONDING_DELAY: Timestamp = 14 * 24 * 60 * 60 * 1000;
+
+/// Minimum CASH principal an account may keep bonded, or unbond at once, so dust can't crowd the unbonding queue.
+pub const MIN_BOND: CashPrincipalAmount = CashPrincipalAmount::from_nominal("10");
+
+/// Maximum number of unbondings released per block, any others due are released in the blocks after.
+pub const UNBONDING_RELEASES_PER_BLOCK: u64 = 16;
+
+/// Amount of time (milliseconds) past CASH principal can be looked up for, e.g. to weigh votes as of a proposal's start.
+pub const CASH_PRINCIPAL_HISTORY: Timestamp = 30 * 24 * 60 * 60 * 1000;
+
+/// Amount of time (milliseconds) a validator's advertised next Eth key is accepted alongside its current one.
+pub const KEY_TRANSITION_PERIOD: Timestamp = 7 * 24 * 60 * 60 * 1000;
+
+/// Amount of time (milliseconds) between sweeps bringing all markets current, regardless of activity.
+/// Markets are otherwise only accrued as they are touched.
+pub const ACCRUAL_SWEEP_INTERVAL: Timestamp = 60 * 60 * 1000;
+
+/// Maximum number of extractions which may be combined into a single notice.
+/// Bounded by the gas needed to execute the notice on the underlying chain.
+pub const MAX_EXTRACTION_BATCH_SIZE: u32 = 50;
 
 /// Flat transfer fee (CASH).
 pub const TRANSFER_FEE: Quantity = Quantity::from_nominal("0.01", CASH);
 
-/// The number of blocks in between periodic sessions.
-pub const SESSION_PERIOD: u32 = 14400; // Assuming 6s blocks, ~1 period per day
+/// Maximum amount of time (milliseconds) an underlying chain block may be ahead of Gateway time.
+pub const MAX_CHAIN_BLOCK_AHEAD: Timestamp = 5 * 60 * 1000;
+
+/// Maximum number of failed events automatically retried per block.
+pub const FAILED_EVENT_RETRIES_PER_BLOCK: usize = 5;
+
+/// Maximum number of failed events queued per chain, beyond which the event at the front is dropped.
+pub const MAX_FAILED_EVENTS: usize = 256;
+
+/// Amount of time (milliseconds) pending chain tallies may wait without progress before expiring.
+pub const PENDING_TALLY_EXPIRY: Timestamp = 60 * 60 * 1000;
+
+/// Amount of time (milliseconds) extractions above the delay threshold are held before release.
+pub const EXTRACT_DELAY: Timestamp = 30 * 60 * 1000;
+
+/// Number of blocks an allowed next code hash may be used for, before it expires.
+pub const NEXT_CODE_HASH_EXPIRY_BLOCKS: u32 = 201600; // Assuming 6s blocks, ~14 days
 
 /// Standard priority for all unsigned transactions.
 pub const UNSIGNED_TXS_PRIORITY: u64 = 100;
@@ -52,5 +123,11 @@ pub const ERROR_WEIGHT: u64 = 100_000_000;
 /// The void account from whence miner CASH is transferred out of.
 pub const GATEWAY_VOID: ChainAccount = ChainAccount::Gate([0u8; 32]);
 
+/// The account holding the execution fees paid by extractors, until reimbursed to relayers.
+pub const EXECUTION_POOL: ChainAccount = ChainAccount::Gate([1u8; 32]);
+
 /// The maximum length of a trx request
 pub const MAX_TRX_REQUEST_LEN: usize = 2048;
+
+/// Version of the layout of the pallet's storage, run up to by the migrations on runtime upgrade.
+pub const STORAGE_VERSION: u32 = 4;
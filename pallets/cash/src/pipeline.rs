@@ -1,23 +1,34 @@
 use frame_support::{
-    storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap, StorageValue},
+    storage::{
+        IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+    },
     traits::StoredMap,
 };
 use our_std::collections::btree_map::BTreeMap;
-use our_std::RuntimeDebug;
+use our_std::{debug, log::targets::PIPELINE, trace, RuntimeDebug};
 use sp_core::crypto::AccountId32;
 
 use crate::{
     chains::{ChainAccount, ChainId},
-    internal::balance_helpers::*,
-    params::MIN_PRINCIPAL_GATE,
+    core::get_recent_timestamp,
+    internal::{
+        accrual::accrue_touched_asset,
+        assets::{get_price, get_price_or_zero, get_value},
+        balance_helpers::*,
+        checkpoints::checkpoint_cash_principal,
+    },
     portfolio::Portfolio,
     reason::Reason,
+    symbol::{CASH, USD},
     types::{
         AssetBalance, AssetIndex, AssetInfo, Balance, CashPrincipal, CashPrincipalAmount, Quantity,
     },
-    AssetAmount, AssetBalances, AssetsWithNonZeroBalance, BorrowIndices, CashPrincipals,
-    ChainAsset, ChainCashPrincipals, Config, GlobalCashIndex, LastIndices, SupplyIndices,
-    SupportedAssets, TotalBorrowAssets, TotalCashPrincipal, TotalSupplyAssets,
+    AssetAmount, AssetBalances, AssetPauses, AssetsWithNonZeroBalance, BadDebt, BorrowIndices,
+    BorrowRewardIndices, CashPrincipals, ChainAsset, ChainAssetCaps, ChainCashCaps,
+    ChainCashPrincipals, Config, DustThresholds, Event, GlobalCashIndex, LastIndices,
+    LastRewardIndices, MinPrincipalGate, Module, RewardsAccrued, SupplyIndices,
+    SupplyRewardIndices, SupportedAssets, TotalBorrowAssets, TotalCashPrincipal, TotalReserves,
+    TotalSupplyAssets,
 };
 
 trait Apply {
@@ -31,9 +42,14 @@ pub struct State {
     asset_balances: BTreeMap<(ChainAsset, ChainAccount), AssetBalance>,
     assets_with_non_zero_balance: BTreeMap<(ChainAsset, ChainAccount), bool>,
     last_indices: BTreeMap<(ChainAsset, ChainAccount), AssetIndex>,
+    last_reward_indices: BTreeMap<(ChainAsset, ChainAccount), AssetIndex>,
+    rewards_accrued: BTreeMap<ChainAccount, CashPrincipalAmount>,
     cash_principals: BTreeMap<ChainAccount, CashPrincipal>,
     total_cash_principal: Option<CashPrincipalAmount>,
     chain_cash_principals: BTreeMap<ChainId, CashPrincipalAmount>,
+    total_reserves: BTreeMap<ChainAsset, CashPrincipalAmount>,
+    bad_debt: Option<CashPrincipalAmount>,
+    dust_swept: Vec<(ChainAsset, ChainAccount, AssetBalance)>,
 }
 
 impl State {
@@ -44,9 +60,14 @@ impl State {
             asset_balances: BTreeMap::new(),
             assets_with_non_zero_balance: BTreeMap::new(),
             last_indices: BTreeMap::new(),
+            last_reward_indices: BTreeMap::new(),
+            rewards_accrued: BTreeMap::new(),
             cash_principals: BTreeMap::new(),
             total_cash_principal: None,
             chain_cash_principals: BTreeMap::new(),
+            total_reserves: BTreeMap::new(),
+            bad_debt: None,
+            dust_swept: Vec::new(),
         }
     }
 
@@ -187,6 +208,45 @@ impl State {
             .insert((asset_info.asset, account), last_index);
     }
 
+    pub fn get_last_reward_index<T: Config>(
+        self: &Self,
+        asset_info: AssetInfo,
+        account: ChainAccount,
+    ) -> AssetIndex {
+        self.last_reward_indices
+            .get(&(asset_info.asset, account))
+            .map(|x| *x)
+            .unwrap_or_else(|| LastRewardIndices::get(asset_info.asset, account))
+    }
+
+    pub fn set_last_reward_index<T: Config>(
+        self: &mut Self,
+        asset_info: AssetInfo,
+        account: ChainAccount,
+        last_reward_index: AssetIndex,
+    ) {
+        self.last_reward_indices
+            .insert((asset_info.asset, account), last_reward_index);
+    }
+
+    pub fn get_rewards_accrued<T: Config>(
+        self: &Self,
+        account: ChainAccount,
+    ) -> CashPrincipalAmount {
+        self.rewards_accrued
+            .get(&account)
+            .map(|x| *x)
+            .unwrap_or_else(|| RewardsAccrued::get(account))
+    }
+
+    pub fn set_rewards_accrued<T: Config>(
+        self: &mut Self,
+        account: ChainAccount,
+        rewards_accrued: CashPrincipalAmount,
+    ) {
+        self.rewards_accrued.insert(account, rewards_accrued);
+    }
+
     pub fn get_cash_principal<T: Config>(self: &Self, account: ChainAccount) -> CashPrincipal {
         self.cash_principals
             .get(&account)
@@ -233,39 +293,159 @@ impl State {
             .insert(chain_id, chain_cash_principal);
     }
 
+    pub fn get_total_reserves<T: Config>(self: &Self, asset: ChainAsset) -> CashPrincipalAmount {
+        self.total_reserves
+            .get(&asset)
+            .map(|x| *x)
+            .unwrap_or_else(|| TotalReserves::get(asset))
+    }
+
+    pub fn set_total_reserves<T: Config>(
+        self: &mut Self,
+        asset: ChainAsset,
+        total_reserves: CashPrincipalAmount,
+    ) {
+        self.total_reserves.insert(asset, total_reserves);
+    }
+
+    pub fn get_bad_debt<T: Config>(self: &Self) -> CashPrincipalAmount {
+        self.bad_debt.unwrap_or_else(|| BadDebt::get())
+    }
+
+    pub fn set_bad_debt<T: Config>(self: &mut Self, bad_debt: CashPrincipalAmount) {
+        self.bad_debt = Some(bad_debt);
+    }
+
+    /// Zero out an asset balance left as dust, crediting its value to reserves for supply,
+    ///  or debiting it from reserves for borrows (as far as reserves go, the rest is bad debt).
+    fn sweep_dust_balance<T: Config>(
+        self: &mut Self,
+        asset_info: AssetInfo,
+        account: ChainAccount,
+        balance: Balance,
+    ) -> Result<(), Reason> {
+        let supply_dust = asset_info.as_quantity(pos_balance(balance.value));
+        let borrow_dust = asset_info.as_quantity(neg_balance(balance.value)?);
+        let dust_cash = get_value::<T>(supply_dust.add(borrow_dust)?)?
+            .div_price(get_price::<T>(CASH)?, CASH)?;
+        let dust_principal = GlobalCashIndex::get().cash_principal_amount(dust_cash)?;
+
+        let total_supply_new = self
+            .get_total_supply_asset::<T>(asset_info)
+            .sub(supply_dust)?;
+        let total_borrow_new = self
+            .get_total_borrow_asset::<T>(asset_info)
+            .sub(borrow_dust)?;
+        let reserves = self.get_total_reserves::<T>(asset_info.asset);
+        let total_cash_principal = self.get_total_cash_principal::<T>();
+        let bad_debt = self.get_bad_debt::<T>();
+        let (reserves_new, total_cash_principal_new, bad_debt_new) = if balance.value > 0 {
+            (
+                reserves.add(dust_principal)?,
+                total_cash_principal.add(dust_principal)?,
+                bad_debt,
+            )
+        } else {
+            let debited = dust_principal.min(reserves);
+            (
+                reserves.sub(debited)?,
+                total_cash_principal.sub(debited)?,
+                bad_debt.add(dust_principal.sub(debited)?)?,
+            )
+        };
+
+        self.set_total_supply_asset::<T>(asset_info, total_supply_new);
+        self.set_total_borrow_asset::<T>(asset_info, total_borrow_new);
+        self.set_total_reserves::<T>(asset_info.asset, reserves_new);
+        self.set_total_cash_principal::<T>(total_cash_principal_new);
+        if bad_debt_new != bad_debt {
+            self.set_bad_debt::<T>(bad_debt_new);
+        }
+        self.set_asset_balance::<T>(asset_info, account, asset_info.as_balance(0));
+        self.dust_swept
+            .push((asset_info.asset, account, balance.value));
+
+        Ok(())
+    }
+
+    /// Sweep away any asset balances left below their asset's dust threshold.
+    /// Dust which cannot be swept (e.g. for lack of a price) is just left alone.
+    fn sweep_dust<T: Config>(self: &mut Self) {
+        let balances: Vec<((ChainAsset, ChainAccount), AssetBalance)> = self
+            .asset_balances
+            .iter()
+            .map(|(key, balance)| (*key, *balance))
+            .collect();
+        for ((asset, account), balance) in balances {
+            let threshold = DustThresholds::get(asset);
+            let amount = pos_balance(balance).max(neg_balance(balance).unwrap_or(AssetAmount::MAX));
+            if amount == 0 || amount >= threshold {
+                continue;
+            }
+            if let Some(asset_info) = SupportedAssets::get(asset) {
+                let mut swept = self.clone();
+                if swept
+                    .sweep_dust_balance::<T>(asset_info, account, asset_info.as_balance(balance))
+                    .is_ok()
+                {
+                    *self = swept;
+                }
+            }
+        }
+    }
+
     pub fn commit<T: Config>(self: &Self) {
-        self.total_supply_asset
+        let st = self;
+        let now = get_recent_timestamp::<T>().ok();
+        st.total_supply_asset
             .iter()
             .for_each(|(chain_asset, asset_amount)| {
                 TotalSupplyAssets::insert(chain_asset, asset_amount);
             });
-        self.total_borrow_asset
+        st.total_borrow_asset
             .iter()
             .for_each(|(chain_asset, asset_amount)| {
                 TotalBorrowAssets::insert(chain_asset, asset_amount);
             });
-        self.asset_balances
+        st.asset_balances
             .iter()
             .for_each(|((chain_asset, account), balance)| {
                 AssetBalances::insert(chain_asset, account, balance);
             });
-        self.assets_with_non_zero_balance.iter().for_each(
-            |((chain_asset, account), is_non_zero)| {
+        st.assets_with_non_zero_balance
+            .iter()
+            .for_each(|((chain_asset, account), is_non_zero)| {
                 if *is_non_zero {
                     AssetsWithNonZeroBalance::insert(account, chain_asset, ());
                 } else {
                     AssetsWithNonZeroBalance::remove(account, chain_asset);
                 }
-            },
-        );
-        self.last_indices
+            });
+        st.last_indices
             .iter()
             .for_each(|((chain_asset, account), last_index)| {
                 LastIndices::insert(chain_asset, account, last_index);
             });
-        self.cash_principals
+        st.last_reward_indices
+            .iter()
+            .for_each(|((chain_asset, account), last_reward_index)| {
+                LastRewardIndices::insert(chain_asset, account, last_reward_index);
+            });
+        st.rewards_accrued
+            .iter()
+            .for_each(|(account, rewards_accrued)| {
+                if *rewards_accrued == CashPrincipalAmount::ZERO {
+                    RewardsAccrued::remove(account);
+                } else {
+                    RewardsAccrued::insert(account, rewards_accrued);
+                }
+            });
+        st.cash_principals
             .iter()
             .for_each(|(account, cash_principal)| {
+                if let Some(now) = now {
+                    checkpoint_cash_principal(*account, now);
+                }
                 CashPrincipals::insert(account, cash_principal);
 
                 // Existential balance for Gateway accounts...
@@ -276,7 +456,7 @@ impl State {
                         //  For now we just inefficiently write to the StoredMap each time principal changes.
                         // Also note: Technically these StoredMap calls can fail (though probably provably safe),
                         //  which would presumably trigger the underlying panic this is meant to avoid.
-                        if cash_principal >= &MIN_PRINCIPAL_GATE {
+                        if *cash_principal >= MinPrincipalGate::get() {
                             _ = T::AccountStore::insert(&AccountId32::new(*gate_address), ());
                         } else {
                             _ = T::AccountStore::remove(&AccountId32::new(*gate_address));
@@ -286,14 +466,25 @@ impl State {
                     _ => {}
                 }
             });
-        if let Some(total_cash_principal_new) = self.total_cash_principal {
+        if let Some(total_cash_principal_new) = st.total_cash_principal {
             TotalCashPrincipal::put(total_cash_principal_new);
         }
-        self.chain_cash_principals
+        st.chain_cash_principals
             .iter()
             .for_each(|(chain_id, chain_cash_principal)| {
                 ChainCashPrincipals::insert(chain_id, chain_cash_principal);
             });
+        st.total_reserves
+            .iter()
+            .for_each(|(chain_asset, total_reserves)| {
+                TotalReserves::insert(chain_asset, total_reserves);
+            });
+        if let Some(bad_debt_new) = st.bad_debt {
+            BadDebt::put(bad_debt_new);
+        }
+        st.dust_swept.iter().for_each(|(asset, account, balance)| {
+            <Module<T>>::deposit_event(Event::DustSwept(*asset, *account, *balance));
+        });
     }
 }
 
@@ -311,6 +502,8 @@ fn prepare_augment_asset<T: Config>(
     let recipient_balance_pre = st.get_asset_balance::<T>(asset_info, recipient);
     let recipient_last_index_pre = st.get_last_index::<T>(asset_info, recipient);
     let recipient_cash_principal_pre = st.get_cash_principal::<T>(recipient);
+    let recipient_last_reward_index_pre = st.get_last_reward_index::<T>(asset_info, recipient);
+    let recipient_rewards_accrued_pre = st.get_rewards_accrued::<T>(recipient);
 
     let (recipient_repay_amount, recipient_supply_amount) =
         repay_and_supply_amount(recipient_balance_pre.value, quantity)?;
@@ -333,11 +526,23 @@ fn prepare_augment_asset<T: Config>(
             borrow_index,
         )?;
 
+    let (recipient_rewards_accrued_post, recipient_last_reward_index_post) =
+        effect_of_asset_rewards_internal(
+            recipient_balance_pre,
+            recipient_balance_post,
+            recipient_rewards_accrued_pre,
+            recipient_last_reward_index_pre,
+            SupplyRewardIndices::get(asset),
+            BorrowRewardIndices::get(asset),
+        )?;
+
     st.set_total_supply_asset::<T>(asset_info, total_supply_new);
     st.set_total_borrow_asset::<T>(asset_info, total_borrow_new);
     st.set_asset_balance::<T>(asset_info, recipient, recipient_balance_post);
     st.set_last_index::<T>(asset_info, recipient, recipient_last_index_post);
     st.set_cash_principal::<T>(recipient, recipient_cash_principal_post);
+    st.set_last_reward_index::<T>(asset_info, recipient, recipient_last_reward_index_post);
+    st.set_rewards_accrued::<T>(recipient, recipient_rewards_accrued_post);
 
     Ok(st)
 }
@@ -356,6 +561,8 @@ fn prepare_reduce_asset<T: Config>(
     let sender_balance_pre = st.get_asset_balance::<T>(asset_info, sender);
     let sender_last_index_pre = st.get_last_index::<T>(asset_info, sender);
     let sender_cash_principal_pre = st.get_cash_principal::<T>(sender);
+    let sender_last_reward_index_pre = st.get_last_reward_index::<T>(asset_info, sender);
+    let sender_rewards_accrued_pre = st.get_rewards_accrued::<T>(sender);
 
     let (sender_withdraw_amount, sender_borrow_amount) =
         withdraw_and_borrow_amount(sender_balance_pre.value, quantity)?;
@@ -377,11 +584,23 @@ fn prepare_reduce_asset<T: Config>(
         borrow_index,
     )?;
 
+    let (sender_rewards_accrued_post, sender_last_reward_index_post) =
+        effect_of_asset_rewards_internal(
+            sender_balance_pre,
+            sender_balance_post,
+            sender_rewards_accrued_pre,
+            sender_last_reward_index_pre,
+            SupplyRewardIndices::get(asset),
+            BorrowRewardIndices::get(asset),
+        )?;
+
     st.set_total_supply_asset::<T>(asset_info, total_supply_new);
     st.set_total_borrow_asset::<T>(asset_info, total_borrow_new);
     st.set_asset_balance::<T>(asset_info, sender, sender_balance_post);
     st.set_last_index::<T>(asset_info, sender, sender_last_index_post);
     st.set_cash_principal::<T>(sender, sender_cash_principal_post);
+    st.set_last_reward_index::<T>(asset_info, sender, sender_last_reward_index_post);
+    st.set_rewards_accrued::<T>(sender, sender_rewards_accrued_post);
 
     Ok(st)
 }
@@ -441,12 +660,59 @@ fn prepare_reduce_cash<T: Config>(
         let chain_id = sender.chain_id();
         let chain_cash_principal_post =
             st.get_chain_cash_principal::<T>(chain_id).add(principal)?;
+        if let Some(cap) = ChainCashCaps::get(chain_id) {
+            if chain_cash_principal_post > cap {
+                Err(Reason::ChainCashCapExceeded)?
+            }
+        }
         st.set_chain_cash_principal::<T>(chain_id, chain_cash_principal_post);
     }
 
     Ok(st)
 }
 
+fn prepare_accrue_rewards<T: Config>(
+    mut st: State,
+    account: ChainAccount,
+) -> Result<State, Reason> {
+    let mut rewards_accrued = st.get_rewards_accrued::<T>(account);
+    for asset in st.get_assets_with_non_zero_balance::<T>(account) {
+        let asset_info = SupportedAssets::get(asset).ok_or(Reason::AssetNotSupported)?;
+        let balance = st.get_asset_balance::<T>(asset_info, account);
+        let (rewards_accrued_post, last_reward_index_post) = effect_of_asset_rewards_internal(
+            balance,
+            balance,
+            rewards_accrued,
+            st.get_last_reward_index::<T>(asset_info, account),
+            SupplyRewardIndices::get(asset),
+            BorrowRewardIndices::get(asset),
+        )?;
+        rewards_accrued = rewards_accrued_post;
+        st.set_last_reward_index::<T>(asset_info, account, last_reward_index_post);
+    }
+    st.set_rewards_accrued::<T>(account, rewards_accrued);
+    Ok(st)
+}
+
+fn prepare_claim_rewards<T: Config>(
+    mut st: State,
+    account: ChainAccount,
+    principal: CashPrincipalAmount,
+) -> Result<State, Reason> {
+    let rewards_accrued_post = st
+        .get_rewards_accrued::<T>(account)
+        .sub(principal)
+        .map_err(|_| Reason::NoRewardsAccrued)?;
+    let cash_principal_post = st.get_cash_principal::<T>(account).add_amount(principal)?;
+    let total_cash_post = st.get_total_cash_principal::<T>().add(principal)?;
+
+    st.set_rewards_accrued::<T>(account, rewards_accrued_post);
+    st.set_cash_principal::<T>(account, cash_principal_post);
+    st.set_total_cash_principal::<T>(total_cash_post);
+
+    Ok(st)
+}
+
 #[derive(Clone, Eq, PartialEq, RuntimeDebug)]
 pub enum Effect {
     AugmentAsset {
@@ -469,6 +735,13 @@ pub enum Effect {
         principal: CashPrincipalAmount,
         to_external: bool,
     },
+    AccrueRewards {
+        account: ChainAccount,
+    },
+    ClaimRewards {
+        account: ChainAccount,
+        principal: CashPrincipalAmount,
+    },
 }
 
 impl Apply for Effect {
@@ -494,6 +767,10 @@ impl Apply for Effect {
                 principal,
                 to_external,
             } => prepare_reduce_cash::<T>(state, sender, principal, to_external),
+            Effect::AccrueRewards { account } => prepare_accrue_rewards::<T>(state, account),
+            Effect::ClaimRewards { account, principal } => {
+                prepare_claim_rewards::<T>(state, account, principal)
+            }
         }
     }
 }
@@ -513,8 +790,12 @@ impl CashPipeline {
         }
     }
 
+    /// Apply an effect to the state, sweeping away any dust it leaves.
+    /// Dust is swept as the effects are applied, so that checks see the balances which will be committed.
     fn apply_effect<T: Config>(mut self: Self, effect: Effect) -> Result<Self, Reason> {
+        trace!(target: PIPELINE, "Applying effect"; effect = effect);
         self.state = effect.clone().apply::<T>(self.state)?;
+        self.state.sweep_dust::<T>();
         self.effects.push(effect);
         Ok(self)
     }
@@ -529,6 +810,7 @@ impl CashPipeline {
         if sender == recipient {
             Err(Reason::SelfTransfer)?
         }
+        accrue_touched_asset::<T>(asset)?;
         self.apply_effect::<T>(Effect::AugmentAsset {
             recipient,
             asset,
@@ -547,6 +829,7 @@ impl CashPipeline {
         asset: ChainAsset,
         quantity: Quantity,
     ) -> Result<Self, Reason> {
+        accrue_touched_asset::<T>(asset)?;
         self.apply_effect::<T>(Effect::AugmentAsset {
             recipient,
             asset,
@@ -560,6 +843,7 @@ impl CashPipeline {
         asset: ChainAsset,
         quantity: Quantity,
     ) -> Result<Self, Reason> {
+        accrue_touched_asset::<T>(asset)?;
         self.apply_effect::<T>(Effect::ReduceAsset {
             sender,
             asset,
@@ -612,15 +896,98 @@ impl CashPipeline {
         })
     }
 
+    /// Move CASH principal out of an account's balance, to be held as its bond.
+    pub fn bond_cash<T: Config>(
+        self: Self,
+        account: ChainAccount,
+        principal: CashPrincipalAmount,
+    ) -> Result<Self, Reason> {
+        self.apply_effect::<T>(Effect::ReduceCash {
+            sender: account,
+            principal,
+            to_external: false,
+        })
+    }
+
+    /// Move CASH principal held as an account's bond back into its balance.
+    pub fn unbond_cash<T: Config>(
+        self: Self,
+        account: ChainAccount,
+        principal: CashPrincipalAmount,
+    ) -> Result<Self, Reason> {
+        self.apply_effect::<T>(Effect::AugmentCash {
+            recipient: account,
+            principal,
+            from_external: false,
+        })
+    }
+
+    /// Repay an account's asset debt out of nothing, to write it off as bad debt.
+    pub fn forgive_asset<T: Config>(
+        self: Self,
+        account: ChainAccount,
+        asset: ChainAsset,
+        quantity: Quantity,
+    ) -> Result<Self, Reason> {
+        accrue_touched_asset::<T>(asset)?;
+        self.apply_effect::<T>(Effect::AugmentAsset {
+            recipient: account,
+            asset,
+            quantity,
+        })
+    }
+
+    /// Repay an account's CASH debt out of nothing, to write it off as bad debt.
+    pub fn forgive_cash<T: Config>(
+        self: Self,
+        account: ChainAccount,
+        principal: CashPrincipalAmount,
+    ) -> Result<Self, Reason> {
+        self.apply_effect::<T>(Effect::AugmentCash {
+            recipient: account,
+            principal,
+            from_external: false,
+        })
+    }
+
+    /// Accrue the rewards earned by all of an account's balances since they last changed.
+    pub fn accrue_rewards<T: Config>(self: Self, account: ChainAccount) -> Result<Self, Reason> {
+        self.apply_effect::<T>(Effect::AccrueRewards { account })
+    }
+
+    /// Pay out accrued rewards to an account, as CASH.
+    pub fn claim_rewards<T: Config>(
+        self: Self,
+        account: ChainAccount,
+        principal: CashPrincipalAmount,
+    ) -> Result<Self, Reason> {
+        self.apply_effect::<T>(Effect::ClaimRewards { account, principal })
+    }
+
+    /// Bring every asset in an account's portfolio current, so its liquidity is checked as of now.
+    fn accrue_portfolio<T: Config>(self: &Self, account: ChainAccount) -> Result<(), Reason> {
+        for asset in self.state.get_assets_with_non_zero_balance::<T>(account) {
+            accrue_touched_asset::<T>(asset)?;
+        }
+        Ok(())
+    }
+
     pub fn check_collateralized<T: Config>(
         self: Self,
         account: ChainAccount,
     ) -> Result<Self, Reason> {
+        self.accrue_portfolio::<T>(account)?;
         let liquidity = self
             .state
             .build_portfolio::<T>(account)?
             .get_liquidity::<T>()?;
         if liquidity.value < 0 {
+            debug!(
+                target: PIPELINE,
+                "Account would be undercollateralized";
+                account = account,
+                liquidity = liquidity.value
+            );
             Err(Reason::InsufficientLiquidity)?
         } else {
             Ok(self)
@@ -628,6 +995,7 @@ impl CashPipeline {
     }
 
     pub fn check_underwater<T: Config>(self: Self, account: ChainAccount) -> Result<Self, Reason> {
+        self.accrue_portfolio::<T>(account)?;
         let liquidity = self
             .state
             .build_portfolio::<T>(account)?
@@ -666,6 +1034,46 @@ impl CashPipeline {
         Ok(self)
     }
 
+    /// Fail if the pipeline adds to the total supply or total borrow of an asset, while paused.
+    /// Moving existing positions around (e.g. repaying or liquidating) is still allowed.
+    pub fn check_asset_unpaused<T: Config>(
+        self: Self,
+        asset_info: AssetInfo,
+    ) -> Result<Self, Reason> {
+        let pauses = AssetPauses::get(asset_info.asset);
+        let total_supply_asset = self.state.get_total_supply_asset::<T>(asset_info);
+        let total_borrow_asset = self.state.get_total_borrow_asset::<T>(asset_info);
+        if pauses.supply && total_supply_asset.value > TotalSupplyAssets::get(asset_info.asset) {
+            Err(Reason::AssetPaused)?
+        }
+        if pauses.borrow && total_borrow_asset.value > TotalBorrowAssets::get(asset_info.asset) {
+            Err(Reason::AssetPaused)?
+        }
+        Ok(self)
+    }
+
+    /// Fail if the pipeline adds to the value of the assets supplied from a chain, beyond its cap.
+    pub fn check_chain_asset_cap<T: Config>(self: Self, chain_id: ChainId) -> Result<Self, Reason> {
+        if let Some(cap) = ChainAssetCaps::get(chain_id) {
+            let mut value_pre = Quantity::new(0, USD);
+            let mut value_post = Quantity::new(0, USD);
+            for (asset, asset_info) in SupportedAssets::iter() {
+                if asset.chain_id() != chain_id {
+                    continue;
+                }
+                let price = get_price_or_zero::<T>(asset_info.units());
+                let total_supply_pre = asset_info.as_quantity(TotalSupplyAssets::get(asset));
+                let total_supply_post = self.state.get_total_supply_asset::<T>(asset_info);
+                value_pre = value_pre.add(total_supply_pre.mul_price(price)?)?;
+                value_post = value_post.add(total_supply_post.mul_price(price)?)?;
+            }
+            if value_post > value_pre && value_post > cap {
+                Err(Reason::ChainAssetCapExceeded)?
+            }
+        }
+        Ok(self)
+    }
+
     // TODO: Do we need this check on other functions?
     pub fn check_sufficient_total_funds<T: Config>(
         self: Self,
@@ -680,12 +1088,36 @@ impl CashPipeline {
     }
 
     pub fn commit<T: Config>(self: Self) {
+        trace!(target: PIPELINE, "Committing effects"; effects = self.effects.len());
         self.state.commit::<T>();
     }
 }
 
 /// Return CASH Principal including asset interest, and a new asset index,
 ///  given a balance change, the previous position, and current market global indices.
+fn effect_of_asset_rewards_internal(
+    balance_old: Balance,
+    balance_new: Balance,
+    rewards_accrued_pre: CashPrincipalAmount,
+    last_reward_index: AssetIndex,
+    supply_reward_index: AssetIndex,
+    borrow_reward_index: AssetIndex,
+) -> Result<(CashPrincipalAmount, AssetIndex), Reason> {
+    let reward_index = if balance_old.value >= 0 {
+        supply_reward_index
+    } else {
+        borrow_reward_index
+    };
+    let rewards_delta = reward_index.cash_principal_amount_since(last_reward_index, balance_old)?;
+    let rewards_accrued_post = rewards_accrued_pre.add(rewards_delta)?;
+    let last_reward_index_post = if balance_new.value >= 0 {
+        supply_reward_index
+    } else {
+        borrow_reward_index
+    };
+    Ok((rewards_accrued_post, last_reward_index_post))
+}
+
 fn effect_of_asset_interest_internal(
     balance_old: Balance,
     balance_new: Balance,
@@ -720,6 +1152,7 @@ mod tests {
         chains::*,
         tests::{assert_ok, assets::*, common::*, mock::*},
         types::*,
+        LastAccrualTimestamps, LastBlockTimestamp,
     };
     use our_std::convert::TryInto;
 
@@ -761,6 +1194,18 @@ mod tests {
                     ]
                     .into_iter()
                     .collect(),
+                    last_reward_indices: vec![
+                        ((Eth, account_a), AssetIndex::from_nominal("0")),
+                        ((Eth, account_b), AssetIndex::from_nominal("0"))
+                    ]
+                    .into_iter()
+                    .collect(),
+                    rewards_accrued: vec![
+                        (account_a, CashPrincipalAmount::ZERO),
+                        (account_b, CashPrincipalAmount::ZERO)
+                    ]
+                    .into_iter()
+                    .collect(),
                     cash_principals: vec![
                         (account_a, CashPrincipal::from_nominal("0")),
                         (account_b, CashPrincipal::from_nominal("0")),
@@ -769,6 +1214,9 @@ mod tests {
                     .collect(),
                     total_cash_principal: None,
                     chain_cash_principals: vec![].into_iter().collect(),
+                    total_reserves: vec![].into_iter().collect(),
+                    bad_debt: None,
+                    dust_swept: vec![],
                 }
             );
         })
@@ -799,11 +1247,20 @@ mod tests {
                     last_indices: vec![((Eth, account_a), AssetIndex::from_nominal("0"))]
                         .into_iter()
                         .collect(),
+                    last_reward_indices: vec![((Eth, account_a), AssetIndex::from_nominal("0"))]
+                        .into_iter()
+                        .collect(),
+                    rewards_accrued: vec![(account_a, CashPrincipalAmount::ZERO)]
+                        .into_iter()
+                        .collect(),
                     cash_principals: vec![(account_a, CashPrincipal::from_nominal("0")),]
                         .into_iter()
                         .collect(),
                     total_cash_principal: None,
                     chain_cash_principals: vec![].into_iter().collect(),
+                    total_reserves: vec![].into_iter().collect(),
+                    bad_debt: None,
+                    dust_swept: vec![],
                 }
             );
         })
@@ -834,11 +1291,20 @@ mod tests {
                     last_indices: vec![((Eth, account_a), AssetIndex::from_nominal("0"))]
                         .into_iter()
                         .collect(),
+                    last_reward_indices: vec![((Eth, account_a), AssetIndex::from_nominal("0"))]
+                        .into_iter()
+                        .collect(),
+                    rewards_accrued: vec![(account_a, CashPrincipalAmount::ZERO)]
+                        .into_iter()
+                        .collect(),
                     cash_principals: vec![(account_a, CashPrincipal::from_nominal("0")),]
                         .into_iter()
                         .collect(),
                     total_cash_principal: None,
                     chain_cash_principals: vec![].into_iter().collect(),
+                    total_reserves: vec![].into_iter().collect(),
+                    bad_debt: None,
+                    dust_swept: vec![],
                 }
             );
         })
@@ -862,6 +1328,8 @@ mod tests {
                     asset_balances: vec![].into_iter().collect(),
                     assets_with_non_zero_balance: vec![].into_iter().collect(),
                     last_indices: vec![].into_iter().collect(),
+                    last_reward_indices: vec![].into_iter().collect(),
+                    rewards_accrued: vec![].into_iter().collect(),
                     cash_principals: vec![
                         (account_a, CashPrincipal::from_nominal("-1")),
                         (account_b, CashPrincipal::from_nominal("1")),
@@ -870,6 +1338,9 @@ mod tests {
                     .collect(),
                     total_cash_principal: Some(quantity),
                     chain_cash_principals: vec![].into_iter().collect(),
+                    total_reserves: vec![].into_iter().collect(),
+                    bad_debt: None,
+                    dust_swept: vec![],
                 }
             );
         })
@@ -894,6 +1365,8 @@ mod tests {
                     asset_balances: vec![].into_iter().collect(),
                     assets_with_non_zero_balance: vec![].into_iter().collect(),
                     last_indices: vec![].into_iter().collect(),
+                    last_reward_indices: vec![].into_iter().collect(),
+                    rewards_accrued: vec![].into_iter().collect(),
                     cash_principals: vec![(account_a, CashPrincipal::from_nominal("1")),]
                         .into_iter()
                         .collect(),
@@ -904,6 +1377,9 @@ mod tests {
                     )]
                     .into_iter()
                     .collect(),
+                    total_reserves: vec![].into_iter().collect(),
+                    bad_debt: None,
+                    dust_swept: vec![],
                 }
             );
         })
@@ -928,6 +1404,8 @@ mod tests {
                     asset_balances: vec![].into_iter().collect(),
                     assets_with_non_zero_balance: vec![].into_iter().collect(),
                     last_indices: vec![].into_iter().collect(),
+                    last_reward_indices: vec![].into_iter().collect(),
+                    rewards_accrued: vec![].into_iter().collect(),
                     cash_principals: vec![(account_a, CashPrincipal::from_nominal("-1")),]
                         .into_iter()
                         .collect(),
@@ -938,11 +1416,30 @@ mod tests {
                     )]
                     .into_iter()
                     .collect(),
+                    total_reserves: vec![].into_iter().collect(),
+                    bad_debt: None,
+                    dust_swept: vec![],
                 }
             );
         })
     }
 
+    #[test]
+    fn test_extract_cash_chain_cash_cap() {
+        new_test_ext().execute_with(|| {
+            ChainCashPrincipals::insert(ChainId::Eth, CashPrincipalAmount::from_nominal("3"));
+            ChainCashCaps::insert(ChainId::Eth, CashPrincipalAmount::from_nominal("4"));
+
+            assert_ok!(CashPipeline::new()
+                .extract_cash::<Test>(account_a, CashPrincipalAmount::from_nominal("1")));
+            assert_eq!(
+                CashPipeline::new()
+                    .extract_cash::<Test>(account_a, CashPrincipalAmount::from_nominal("1.1")),
+                Err(Reason::ChainCashCapExceeded)
+            );
+        })
+    }
+
     #[test]
     fn test_build_portfolio() {
         new_test_ext().execute_with(|| {
@@ -1048,6 +1545,44 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_check_asset_unpaused() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+
+            AssetBalances::insert(Eth, account_a, Balance::from_nominal("-2", ETH).value);
+            TotalBorrowAssets::insert(Eth, Quantity::from_nominal("2", ETH).value);
+            AssetPauses::insert(
+                Eth,
+                AssetPauseFlags {
+                    supply: true,
+                    borrow: true,
+                    extract: false,
+                },
+            );
+
+            // repaying reduces the borrow, so is allowed
+            assert_ok!(CashPipeline::new()
+                .lock_asset::<Test>(account_a, Eth, eth.as_quantity_nominal("1"))
+                .expect("lock_asset(eth) failed")
+                .check_asset_unpaused::<Test>(eth));
+
+            // supplying beyond the borrow is not
+            let res = CashPipeline::new()
+                .lock_asset::<Test>(account_a, Eth, eth.as_quantity_nominal("3"))
+                .expect("lock_asset(eth) failed")
+                .check_asset_unpaused::<Test>(eth);
+            assert_eq!(res, Err(Reason::AssetPaused));
+
+            // nor is borrowing more
+            let res = CashPipeline::new()
+                .extract_asset::<Test>(account_b, Eth, eth.as_quantity_nominal("1"))
+                .expect("extract_asset(eth) failed")
+                .check_asset_unpaused::<Test>(eth);
+            assert_eq!(res, Err(Reason::AssetPaused));
+        })
+    }
+
     #[test]
     fn test_check_underwater() {
         new_test_ext().execute_with(|| {
@@ -1213,6 +1748,20 @@ mod tests {
                     ]
                     .into_iter()
                     .collect(),
+                    last_reward_indices: vec![
+                        ((Eth, account_a), AssetIndex::from_nominal("0")),
+                        ((Eth, account_b), AssetIndex::from_nominal("0")),
+                        ((Wbtc, account_a), AssetIndex::from_nominal("0")),
+                        ((Wbtc, account_b), AssetIndex::from_nominal("0"))
+                    ]
+                    .into_iter()
+                    .collect(),
+                    rewards_accrued: vec![
+                        (account_a, CashPrincipalAmount::ZERO),
+                        (account_b, CashPrincipalAmount::ZERO)
+                    ]
+                    .into_iter()
+                    .collect(),
                     cash_principals: vec![
                         (account_a, CashPrincipal::from_nominal("0")),
                         (account_b, CashPrincipal::from_nominal("0")),
@@ -1221,6 +1770,9 @@ mod tests {
                     .collect(),
                     total_cash_principal: None,
                     chain_cash_principals: vec![].into_iter().collect(),
+                    total_reserves: vec![].into_iter().collect(),
+                    bad_debt: None,
+                    dust_swept: vec![],
                 }
             );
         })
@@ -1381,6 +1933,8 @@ mod tests {
                 ]
                 .into_iter()
                 .collect(),
+                last_reward_indices: vec![].into_iter().collect(),
+                rewards_accrued: vec![].into_iter().collect(),
                 cash_principals: vec![
                     (account_a, CashPrincipal::from_nominal("13000")),
                     (account_b, CashPrincipal::from_nominal("14000")),
@@ -1394,6 +1948,9 @@ mod tests {
                 ]
                 .into_iter()
                 .collect(),
+                total_reserves: vec![].into_iter().collect(),
+                bad_debt: None,
+                dust_swept: vec![],
             };
 
             state.commit::<Test>();
@@ -1453,6 +2010,98 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_checks_accrue_portfolio() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+            assert_ok!(init_wbtc_asset());
+            init_asset_balance(Eth, account_a, eth.as_balance_nominal("1").value);
+            init_asset_balance(Wbtc, account_b, wbtc.as_balance_nominal("-1").value);
+            LastAccrualTimestamps::insert(Eth, 5);
+            LastAccrualTimestamps::insert(Wbtc, 5);
+            LastBlockTimestamp::put(10);
+
+            assert_ok!(CashPipeline::new().check_collateralized::<Test>(account_a));
+            assert_eq!(LastAccrualTimestamps::get(Eth), Some(10));
+            assert_eq!(LastAccrualTimestamps::get(Wbtc), Some(5));
+
+            assert_ok!(CashPipeline::new().check_underwater::<Test>(account_b));
+            assert_eq!(LastAccrualTimestamps::get(Wbtc), Some(10));
+        })
+    }
+
+    #[test]
+    fn test_pipeline_sweeps_dust() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(init_eth_asset());
+            GlobalCashIndex::put(CashIndex::ONE);
+            TotalCashPrincipal::put(CashPrincipalAmount::from_nominal("100"));
+            DustThresholds::insert(Eth, eth.as_quantity_nominal("0.001").value);
+            init_asset_balance(Eth, account_a, eth.as_balance_nominal("1").value);
+            init_asset_balance(Eth, account_b, eth.as_balance_nominal("-1").value);
+
+            // leaves 0.0005 ETH supplied, worth 1 CASH, credited to reserves
+            let pipeline = CashPipeline::new()
+                .extract_asset::<Test>(account_a, Eth, eth.as_quantity_nominal("0.9995"))
+                .unwrap();
+            // the dust is already gone when collateral is checked
+            assert_eq!(
+                pipeline
+                    .state
+                    .get_asset_balance::<Test>(eth, account_a)
+                    .value,
+                0
+            );
+            pipeline.commit::<Test>();
+            assert_eq!(AssetBalances::get(Eth, account_a), 0);
+            assert!(!AssetsWithNonZeroBalance::contains_key(account_a, Eth));
+            assert_eq!(TotalSupplyAssets::get(Eth), 0);
+            assert_eq!(
+                TotalReserves::get(Eth),
+                CashPrincipalAmount::from_nominal("1")
+            );
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                CashPrincipalAmount::from_nominal("101")
+            );
+
+            // leaves 0.0005 ETH borrowed, worth 1 CASH, debited from reserves
+            CashPipeline::new()
+                .lock_asset::<Test>(account_b, Eth, eth.as_quantity_nominal("0.9995"))
+                .unwrap()
+                .commit::<Test>();
+            assert_eq!(AssetBalances::get(Eth, account_b), 0);
+            assert!(!AssetsWithNonZeroBalance::contains_key(account_b, Eth));
+            assert_eq!(TotalBorrowAssets::get(Eth), 0);
+            assert_eq!(TotalReserves::get(Eth), CashPrincipalAmount::ZERO);
+            assert_eq!(
+                TotalCashPrincipal::get(),
+                CashPrincipalAmount::from_nominal("100")
+            );
+            assert_eq!(BadDebt::get(), CashPrincipalAmount::ZERO);
+
+            // borrow dust not covered by reserves is recorded as bad debt
+            init_asset_balance(Eth, account_b, eth.as_balance_nominal("-1").value);
+            CashPipeline::new()
+                .lock_asset::<Test>(account_b, Eth, eth.as_quantity_nominal("0.9995"))
+                .unwrap()
+                .commit::<Test>();
+            assert_eq!(AssetBalances::get(Eth, account_b), 0);
+            assert_eq!(BadDebt::get(), CashPrincipalAmount::from_nominal("1"));
+
+            // balances at or above the threshold are left alone
+            init_asset_balance(Eth, account_a, eth.as_balance_nominal("1").value);
+            CashPipeline::new()
+                .extract_asset::<Test>(account_a, Eth, eth.as_quantity_nominal("0.999"))
+                .unwrap()
+                .commit::<Test>();
+            assert_eq!(
+                AssetBalances::get(Eth, account_a),
+                eth.as_balance_nominal("0.001").value
+            );
+        })
+    }
+
     // #[test]
     // fn test_liquidate_internal_asset_repay_and_supply_amount_overflow() {
     //     new_test_ext().execute_with(|| {
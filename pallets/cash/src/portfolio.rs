@@ -2,7 +2,7 @@ use crate::{
     internal::assets::get_price,
     reason::Reason,
     symbol::CASH,
-    types::{AssetInfo, Balance},
+    types::{AssetAmount, AssetInfo, Balance, CashOrChainAsset, Factor, MathError},
     Config,
 };
 use codec::{Decode, Encode};
@@ -31,6 +31,85 @@ impl Portfolio {
         }
         Ok(liquidity)
     }
+
+    /// Get the ratio of risk-adjusted collateral value to risk-adjusted borrow value, if borrowing.
+    /// An account whose health factor falls below one may be liquidated.
+    pub fn get_health_factor<T: Config>(&self) -> Result<Option<Factor>, Reason> {
+        let mut collateral: AssetAmount = 0;
+        let mut borrows: AssetAmount = 0;
+        let mut adjusted_worths = vec![self.cash.mul_price(get_price::<T>(CASH)?)?];
+        for (info, balance) in &self.positions {
+            let price = get_price::<T>(balance.units)?;
+            let worth = (*balance).mul_price(price)?;
+            if worth.value >= 0 {
+                adjusted_worths.push(worth.mul_factor(info.liquidity_factor)?);
+            } else {
+                adjusted_worths.push(worth.div_factor(info.liquidity_factor)?);
+            }
+        }
+        for worth in adjusted_worths {
+            let value = worth.value.unsigned_abs();
+            if worth.value >= 0 {
+                collateral = collateral.checked_add(value).ok_or(MathError::Overflow)?;
+            } else {
+                borrows = borrows.checked_add(value).ok_or(MathError::Overflow)?;
+            }
+        }
+        if borrows == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(Factor::from_fraction(collateral, borrows)?))
+        }
+    }
+
+    /// Get the largest borrow and the largest collateral position by value, if any.
+    pub fn get_largest_positions<T: Config>(
+        &self,
+    ) -> Result<(Option<CashOrChainAsset>, Option<CashOrChainAsset>), Reason> {
+        let mut largest_borrow: Option<(CashOrChainAsset, Balance)> = None;
+        let mut largest_collateral: Option<(CashOrChainAsset, Balance)> = None;
+        let cash_worth = self.cash.mul_price(get_price::<T>(CASH)?)?;
+        let mut worths = vec![(CashOrChainAsset::Cash, cash_worth)];
+        for (info, balance) in &self.positions {
+            let price = get_price::<T>(balance.units)?;
+            worths.push((
+                CashOrChainAsset::ChainAsset(info.asset),
+                (*balance).mul_price(price)?,
+            ));
+        }
+        for (asset, worth) in worths {
+            if worth.value < 0 {
+                match largest_borrow {
+                    Some((_, largest)) if largest.value <= worth.value => (),
+                    _ => largest_borrow = Some((asset, worth)),
+                }
+            } else if worth.value > 0 {
+                match largest_collateral {
+                    Some((_, largest)) if largest.value >= worth.value => (),
+                    _ => largest_collateral = Some((asset, worth)),
+                }
+            }
+        }
+        Ok((
+            largest_borrow.map(|(asset, _)| asset),
+            largest_collateral.map(|(asset, _)| asset),
+        ))
+    }
+
+    /// Combine the CASH and positions of another portfolio with this one.
+    pub fn merge(self, other: Portfolio) -> Result<Portfolio, Reason> {
+        let mut positions = self.positions;
+        for (info, balance) in other.positions {
+            match positions.iter_mut().find(|(i, _)| i.asset == info.asset) {
+                Some((_, existing)) => *existing = existing.add(balance)?,
+                None => positions.push((info, balance)),
+            }
+        }
+        Ok(Portfolio {
+            cash: self.cash.add(other.cash)?,
+            positions,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -78,6 +157,7 @@ pub mod tests {
                     supply_cap,
                     symbol,
                     ticker,
+                    liquidation_incentive: None,
                 };
                 SupportedAssets::insert(asset, asset_info);
 
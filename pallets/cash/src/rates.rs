@@ -6,7 +6,7 @@ use crate::{
     factor::{BigUint, Factor},
     params::MILLISECONDS_PER_YEAR,
     reason::{MathError, Reason},
-    types::{AssetAmount, MinerShares, Timestamp, Uint},
+    types::{AssetAmount, Bips, MinerShares, Timestamp, Uint},
 };
 
 use types_derive::Types;
@@ -19,6 +19,7 @@ pub enum RatesError {
     KinkAboveFull,
     KinkUtilizationTooHigh,
     Overflowed,
+    NotKinkModel,
 }
 
 /// Annualized interest rate
@@ -88,10 +89,22 @@ impl From<APR> for String {
 
 /// Get the utilization ratio given the amount supplied and borrowed.
 pub fn get_utilization(supplied: AssetAmount, borrowed: AssetAmount) -> Result<Factor, MathError> {
+    get_utilization_excluding_reserves(supplied, borrowed, 0)
+}
+
+/// Get the utilization ratio given the amount supplied and borrowed, not counting reserves as supply.
+///
+/// As in Compound v2, utilization is borrowed / (supplied - reserves).
+pub fn get_utilization_excluding_reserves(
+    supplied: AssetAmount,
+    borrowed: AssetAmount,
+    reserves: AssetAmount,
+) -> Result<Factor, MathError> {
+    let available = supplied.checked_sub(reserves).ok_or(MathError::Underflow)?;
     if borrowed == 0 {
         Ok(Factor::ZERO)
     } else {
-        Ok(Factor::from_fraction(borrowed, supplied)?)
+        Ok(Factor::from_fraction(borrowed, available)?)
     }
 }
 
@@ -108,6 +121,12 @@ pub enum InterestRateModel {
     Fixed {
         rate: APR,
     },
+    JumpRate {
+        base_rate: Bips,
+        multiplier: Bips,
+        jump_multiplier: Bips,
+        kink: Bips,
+    },
 }
 
 /// This is for convenience, we shouldn't rely on a sane default model.
@@ -139,9 +158,74 @@ impl InterestRateModel {
         }
     }
 
+    /// Create a new jump rate model, with all parameters in bips.
+    pub fn new_jump_rate(
+        base_rate: Bips,
+        multiplier: Bips,
+        jump_multiplier: Bips,
+        kink: Bips,
+    ) -> InterestRateModel {
+        InterestRateModel::JumpRate {
+            base_rate,
+            multiplier,
+            jump_multiplier,
+            kink,
+        }
+    }
+
+    /// Convert a kink model into the jump rate model with the same rates, rounding down to bips.
+    pub fn to_jump_rate(self: &Self) -> Result<InterestRateModel, RatesError> {
+        match self {
+            Self::Kink {
+                zero_rate,
+                kink_rate,
+                kink_utilization,
+                full_rate,
+            } => {
+                // APR has 4 decimals, so rates are already in bips
+                let kink = kink_utilization
+                    .0
+                    .checked_div(Factor::ONE.0 / 10000)
+                    .ok_or(RatesError::Overflowed)?;
+                // multiplier = (kink_rate - zero_rate) / kink_utilization
+                let multiplier = kink_rate
+                    .0
+                    .checked_sub(zero_rate.0)
+                    .ok_or(RatesError::ZeroAboveKink)?
+                    .checked_mul(Factor::ONE.0)
+                    .ok_or(RatesError::Overflowed)?
+                    .checked_div(kink_utilization.0)
+                    .ok_or(RatesError::Overflowed)?;
+                // jump_multiplier = (full_rate - kink_rate) / (1 - kink_utilization)
+                let jump_multiplier = full_rate
+                    .0
+                    .checked_sub(kink_rate.0)
+                    .ok_or(RatesError::KinkAboveFull)?
+                    .checked_mul(Factor::ONE.0)
+                    .ok_or(RatesError::Overflowed)?
+                    .checked_div(
+                        Factor::ONE
+                            .0
+                            .checked_sub(kink_utilization.0)
+                            .ok_or(RatesError::KinkUtilizationTooHigh)?,
+                    )
+                    .ok_or(RatesError::KinkUtilizationTooHigh)?;
+                Ok(Self::new_jump_rate(
+                    zero_rate.0,
+                    multiplier,
+                    jump_multiplier,
+                    kink,
+                ))
+            }
+            Self::JumpRate { .. } => Ok(*self),
+            Self::Fixed { .. } => Err(RatesError::NotKinkModel),
+        }
+    }
+
     /// Check the model parameters for sanity
     ///
     /// Kink - monotonically increasing rate with a kink somewhere between 0% and 100% utilization
+    /// JumpRate - kink strictly below 100% utilization and rate at full utilization within bounds
     pub fn check_parameters(self: &Self) -> Result<(), RatesError> {
         match self {
             Self::Kink {
@@ -171,6 +255,15 @@ impl InterestRateModel {
                     return Err(RatesError::ModelRateOutOfBounds);
                 }
             }
+            Self::JumpRate { kink, .. } => {
+                if *kink >= 10000 {
+                    return Err(RatesError::KinkUtilizationTooHigh);
+                }
+
+                if self.get_borrow_rate(Factor::ONE, 0)? > APR::MAX {
+                    return Err(RatesError::ModelRateOutOfBounds);
+                }
+            }
         };
 
         Ok(())
@@ -207,6 +300,29 @@ impl InterestRateModel {
             .checked_add(kink_rate)
     }
 
+    /// The jump rate model, as in Compound v2's JumpRateModel.
+    fn jump_line(
+        utilization: Uint,
+        base_rate: Uint,
+        multiplier: Uint,
+        jump_multiplier: Uint,
+        kink: Uint,
+    ) -> Option<Uint> {
+        // base_rate + min(utilization, kink) * multiplier + max(utilization - kink, 0) * jump_multiplier
+        let kink_utilization = kink.checked_mul(Factor::ONE.0 / 10000)?;
+        let normal = utilization.min(kink_utilization);
+        let excess = utilization.saturating_sub(kink_utilization);
+        normal
+            .checked_mul(multiplier)?
+            .checked_div(Factor::ONE.0)?
+            .checked_add(
+                excess
+                    .checked_mul(jump_multiplier)?
+                    .checked_div(Factor::ONE.0)?,
+            )?
+            .checked_add(base_rate)
+    }
+
     /// Get the borrow rate
     /// Current rate is not used at the moment
     pub fn get_borrow_rate<T: Into<APR>>(
@@ -246,6 +362,23 @@ impl InterestRateModel {
                 }
             }
             Self::Fixed { rate } => Ok(*rate),
+            Self::JumpRate {
+                base_rate,
+                multiplier,
+                jump_multiplier,
+                kink,
+            } => {
+                let result = Self::jump_line(
+                    utilization.0,
+                    *base_rate,
+                    *multiplier,
+                    *jump_multiplier,
+                    *kink,
+                )
+                .ok_or(RatesError::Overflowed)?;
+
+                Ok(result.into())
+            }
         }
     }
 
@@ -483,6 +616,21 @@ mod test {
                 expected: Err(RatesError::ModelRateOutOfBounds),
                 message: "rate must be less than max rate",
             },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_jump_rate(200, 1000, 10000, 8000),
+                expected: Ok(()),
+                message: "jump rate model with sane parameters",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_jump_rate(200, 1000, 10000, 10000),
+                expected: Err(RatesError::KinkUtilizationTooHigh),
+                message: "jump rate kink must be below full utilization",
+            },
+            InterestRateModelCheckParametersTestCase {
+                model: InterestRateModel::new_jump_rate(200, 1000, 20000, 8000),
+                expected: Err(RatesError::ModelRateOutOfBounds),
+                message: "jump rate at full utilization must be less than max rate",
+            },
         ]
     }
 
@@ -560,6 +708,36 @@ mod test {
                 expected: Ok(380.into()),
                 message: "rate at point between kink and full",
             },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_jump_rate(200, 1000, 10000, 8000),
+                utilization: Factor::ZERO,
+                expected: Ok(200.into()),
+                message: "jump rate at zero utilization should be base rate",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_jump_rate(200, 1000, 10000, 8000),
+                utilization: Factor::from_nominal("0.5"),
+                expected: Ok(700.into()),
+                message: "jump rate below kink follows multiplier",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_jump_rate(200, 1000, 10000, 8000),
+                utilization: Factor::from_nominal("0.8"),
+                expected: Ok(1000.into()),
+                message: "jump rate at kink",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_jump_rate(200, 1000, 10000, 8000),
+                utilization: Factor::from_nominal("0.9"),
+                expected: Ok(2000.into()),
+                message: "jump rate above kink follows jump multiplier",
+            },
+            InterestRateModelGetBorrowRateTestCase {
+                model: InterestRateModel::new_jump_rate(200, 1000, 10000, 8000),
+                utilization: Factor::ONE,
+                expected: Ok(3000.into()),
+                message: "jump rate at full utilization",
+            },
         ]
     }
 
@@ -579,6 +757,52 @@ mod test {
             .for_each(test_get_borrow_rate_case)
     }
 
+    #[test]
+    fn test_get_utilization_excluding_reserves() {
+        assert_eq!(
+            get_utilization_excluding_reserves(100, 50, 0),
+            Ok(Factor::from_nominal("0.5"))
+        );
+        assert_eq!(
+            get_utilization_excluding_reserves(110, 50, 10),
+            Ok(Factor::from_nominal("0.5"))
+        );
+        assert_eq!(
+            get_utilization_excluding_reserves(100, 0, 100),
+            Ok(Factor::ZERO)
+        );
+        assert_eq!(
+            get_utilization_excluding_reserves(10, 0, 11),
+            Err(MathError::Underflow)
+        );
+    }
+
+    #[test]
+    fn test_to_jump_rate() {
+        let kink = InterestRateModel::Kink {
+            zero_rate: 100.into(),
+            kink_rate: 200.into(),
+            kink_utilization: Factor::from_nominal("0.5"),
+            full_rate: 500.into(),
+        };
+        let jump = kink.to_jump_rate().unwrap();
+        assert_eq!(jump, InterestRateModel::new_jump_rate(100, 200, 600, 5000));
+        assert_eq!(jump.check_parameters(), Ok(()));
+        for utilization in &["0", "0.1", "0.5", "0.8", "1"] {
+            let utilization = Factor::from_nominal(utilization);
+            assert_eq!(
+                jump.get_borrow_rate(utilization, 0),
+                kink.get_borrow_rate(utilization, 0)
+            );
+        }
+
+        assert_eq!(jump.to_jump_rate(), Ok(jump));
+        assert_eq!(
+            InterestRateModel::Fixed { rate: 100.into() }.to_jump_rate(),
+            Err(RatesError::NotKinkModel)
+        );
+    }
+
     #[test]
     fn test_compound() {
         let mut rates = vec!["0", "0.0001", "0.03", "0.1", "0.2"];
@@ -75,6 +75,54 @@ pub enum Reason {
     StarportMissing,
     InvalidChainBlock,
     TrxRequestTooLong,
+    ReorgBeyondCheckpoint,
+    ChainIngestionPaused,
+    InvalidChainReorg,
+    FailedEventMissing,
+    ImplausibleBlockTime,
+    SupplyExceedsBalance,
+    RepayExceedsBorrow,
+    InsufficientAllowance,
+    AmbiguousAssetSymbol,
+    TooManyDecimals,
+    TrxRequestExpired,
+    NonceWindowTooLarge,
+    RelayerFeeNotSupported,
+    ExtractionBatchTooLarge,
+    ExecutionFeeNotSupported,
+    NoExecutionReimbursement,
+    InsufficientReserves,
+    InvalidLiquidationIncentive,
+    CloseFactorExceeded,
+    AssetPaused,
+    EmergencyPaused,
+    NotPauseGuardian,
+    NotInsolvent,
+    InvalidInsuranceShare,
+    InsufficientInsuranceFund,
+    LiquidationAuctionOpen,
+    NoLiquidationAuction,
+    InvalidExtractRateLimit,
+    ExtractRateLimited,
+    PendingExtractMissing,
+    NotExtractSender,
+    NoRewardsAccrued,
+    InvalidSpread,
+    InvalidMinerShare,
+    InvalidMinTxValue,
+    InvalidMinPrincipalGate,
+    InvalidAccountLink,
+    AccountAlreadyLinked,
+    ChainCashCapExceeded,
+    ChainAssetCapExceeded,
+    InvalidKeyRotation,
+    InsufficientBond,
+    BondTooSmall,
+    ValidatorNotBonded,
+    InvalidValidatorMetadata,
+    NextCodeHashExpired,
+    CashPrincipalHistoryExpired,
+    AccountNotLinked,
 }
 
 impl From<Reason> for frame_support::dispatch::DispatchError {
@@ -146,6 +194,54 @@ impl From<Reason> for frame_support::dispatch::DispatchError {
             Reason::StarportMissing => (40, 0, "starport address not set"),
             Reason::InvalidChainBlock => (41, 0, "invalid chain block"),
             Reason::TrxRequestTooLong => (42, 0, "the trx request was too long"),
+            Reason::ReorgBeyondCheckpoint => (43, 0, "cannot reorg checkpointed blocks"),
+            Reason::ChainIngestionPaused => (44, 0, "chain ingestion is paused"),
+            Reason::InvalidChainReorg => (45, 0, "reorg does not connect to known history"),
+            Reason::FailedEventMissing => (46, 0, "failed event not found"),
+            Reason::ImplausibleBlockTime => (47, 0, "block timestamp is implausible"),
+            Reason::SupplyExceedsBalance => (48, 0, "supply would require borrowing"),
+            Reason::RepayExceedsBorrow => (49, 0, "repay exceeds amount borrowed"),
+            Reason::InsufficientAllowance => (50, 0, "spender allowance is insufficient"),
+            Reason::AmbiguousAssetSymbol => (51, 0, "asset symbol matches several assets"),
+            Reason::TooManyDecimals => (52, 0, "amount has more decimals than the asset"),
+            Reason::TrxRequestExpired => (53, 0, "trx request deadline has passed"),
+            Reason::NonceWindowTooLarge => (54, 0, "nonce window too large"),
+            Reason::RelayerFeeNotSupported => (55, 0, "relayer fee not supported for request"),
+            Reason::ExtractionBatchTooLarge => (56, 0, "extraction batch size too large"),
+            Reason::ExecutionFeeNotSupported => (57, 0, "execution fee only supported for extract"),
+            Reason::NoExecutionReimbursement => (58, 0, "no execution reimbursement to claim"),
+            Reason::InsufficientReserves => (59, 0, "insufficient reserves"),
+            Reason::InvalidLiquidationIncentive => (60, 0, "invalid liquidation incentive"),
+            Reason::CloseFactorExceeded => (61, 0, "repay exceeds close factor"),
+            Reason::AssetPaused => (62, 0, "asset is paused"),
+            Reason::EmergencyPaused => (63, 0, "emergency paused"),
+            Reason::NotPauseGuardian => (64, 0, "not the pause guardian"),
+            Reason::NotInsolvent => (65, 0, "account has collateral or no debt"),
+            Reason::InvalidInsuranceShare => (66, 0, "invalid insurance share"),
+            Reason::InsufficientInsuranceFund => (67, 0, "insufficient insurance fund"),
+            Reason::LiquidationAuctionOpen => (68, 0, "liquidation auction already open"),
+            Reason::NoLiquidationAuction => (69, 0, "no liquidation auction"),
+            Reason::InvalidExtractRateLimit => (70, 0, "invalid extract rate limit"),
+            Reason::ExtractRateLimited => (71, 0, "extract rate limit exceeded"),
+            Reason::PendingExtractMissing => (72, 0, "pending extract missing"),
+            Reason::NotExtractSender => (73, 0, "not the sender of the extract"),
+            Reason::NoRewardsAccrued => (74, 0, "no rewards accrued"),
+            Reason::InvalidSpread => (75, 0, "invalid spread"),
+            Reason::InvalidMinerShare => (76, 0, "invalid miner share"),
+            Reason::InvalidMinTxValue => (77, 0, "invalid min tx value"),
+            Reason::InvalidMinPrincipalGate => (78, 0, "invalid min principal gate"),
+            Reason::InvalidAccountLink => (79, 0, "invalid account link"),
+            Reason::AccountAlreadyLinked => (80, 0, "account already linked"),
+            Reason::ChainCashCapExceeded => (81, 0, "chain cash cap exceeded"),
+            Reason::ChainAssetCapExceeded => (82, 0, "chain asset cap exceeded"),
+            Reason::InvalidKeyRotation => (83, 0, "invalid key rotation"),
+            Reason::InsufficientBond => (84, 0, "insufficient bond"),
+            Reason::BondTooSmall => (84, 1, "bond too small"),
+            Reason::ValidatorNotBonded => (85, 0, "validator not bonded"),
+            Reason::InvalidValidatorMetadata => (86, 0, "invalid validator metadata"),
+            Reason::NextCodeHashExpired => (87, 0, "next code hash expired"),
+            Reason::CashPrincipalHistoryExpired => (88, 0, "cash principal history expired"),
+            Reason::AccountNotLinked => (89, 0, "account not linked"),
         };
         frame_support::dispatch::DispatchError::Module {
             index,
@@ -242,6 +338,15 @@ pub enum TrxReqParseError {
     InvalidExpression,
     InvalidChain,
     InvalidChainAccount,
+    InvalidBatchOperation,
+    InvalidDeadline,
+    InvalidNonce,
+    InvalidFee,
+    InvalidClause,
+    TooManyRecipients,
+    TooManyCollaterals,
+    UnknownVersion,
+    UnsupportedFunction,
 }
 
 impl From<trx_request::ParseError<'_>> for TrxReqParseError {
@@ -259,6 +364,19 @@ impl From<trx_request::ParseError<'_>> for TrxReqParseError {
             trx_request::ParseError::InvalidChainAccount(_) => {
                 TrxReqParseError::InvalidChainAccount
             }
+            trx_request::ParseError::InvalidBatchOperation(_) => {
+                TrxReqParseError::InvalidBatchOperation
+            }
+            trx_request::ParseError::InvalidDeadline => TrxReqParseError::InvalidDeadline,
+            trx_request::ParseError::InvalidNonce => TrxReqParseError::InvalidNonce,
+            trx_request::ParseError::InvalidFee => TrxReqParseError::InvalidFee,
+            trx_request::ParseError::InvalidClause => TrxReqParseError::InvalidClause,
+            trx_request::ParseError::TooManyRecipients => TrxReqParseError::TooManyRecipients,
+            trx_request::ParseError::TooManyCollaterals => TrxReqParseError::TooManyCollaterals,
+            trx_request::ParseError::UnknownVersion(_) => TrxReqParseError::UnknownVersion,
+            trx_request::ParseError::UnsupportedFunction(_, _) => {
+                TrxReqParseError::UnsupportedFunction
+            }
         }
     }
 }
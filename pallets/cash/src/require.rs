@@ -23,6 +23,9 @@ macro_rules! must {
 #[macro_export]
 macro_rules! require_min_tx_value {
     ($value:expr) => {
-        require!($value >= MIN_TX_VALUE, Reason::MinTxValueNotMet);
+        require!(
+            $value >= $crate::internal::parameters::get_min_tx_value(),
+            Reason::MinTxValueNotMet
+        );
     };
 }
@@ -211,6 +211,7 @@ impl<'de> de::Deserialize<'de> for ValidatorKeys {
                 Ok(ValidatorKeys {
                     substrate_id,
                     eth_address,
+                    next_eth_key: None,
                 })
             }
         }
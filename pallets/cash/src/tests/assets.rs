@@ -16,6 +16,7 @@ pub const eth: AssetInfo = AssetInfo {
     supply_cap: Quantity::from_nominal("1000", ETH).value,
     symbol: Symbol(ETH.ticker.0),
     ticker: Ticker(ETH.ticker.0),
+    liquidation_incentive: None,
 };
 
 pub const UNI: Units = Units::from_ticker_str("UNI", 18);
@@ -34,6 +35,7 @@ pub const uni: AssetInfo = AssetInfo {
     supply_cap: Quantity::from_nominal("1000", UNI).value,
     symbol: Symbol(UNI.ticker.0),
     ticker: Ticker(UNI.ticker.0),
+    liquidation_incentive: None,
 };
 
 pub const WBTC: Units = Units::from_ticker_str("WBTC", 8);
@@ -52,6 +54,7 @@ pub const wbtc: AssetInfo = AssetInfo {
     supply_cap: Quantity::from_nominal("1000", WBTC).value,
     symbol: Symbol(WBTC.ticker.0),
     ticker: Ticker(WBTC.ticker.0),
+    liquidation_incentive: None,
 };
 
 pub const Usdc: ChainAsset = ChainAsset::Eth(hex!("cccccccccccccccccccccccccccccccccccccccc"));
@@ -69,4 +72,5 @@ pub const usdc: AssetInfo = AssetInfo {
     supply_cap: Quantity::from_nominal("1000", USD).value,
     symbol: Symbol(USD.ticker.0),
     ticker: Ticker(USD.ticker.0),
+    liquidation_incentive: None,
 };
@@ -86,6 +86,7 @@ frame_support::construct_runtime!(
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
         Oracle: pallet_oracle::{Pallet, Call, Config, Storage, Event, Inherent},
+        Parameters: pallet_parameters::{Pallet, Call, Storage, Event},
         Cash: pallet_cash::{Pallet, Call, Config, Storage, Event, Inherent},
         Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
     }
@@ -148,6 +149,7 @@ impl pallet_oracle::Config for Test {
     type Call = Call;
     type Event = Event;
     type GetConvertedTimestamp = timestamp::TimeConverter<Self>;
+    type Validators = Cash;
 }
 
 impl frame_system::offchain::SigningTypes for Test {
@@ -155,12 +157,17 @@ impl frame_system::offchain::SigningTypes for Test {
     type Signature = Signature;
 }
 
+impl pallet_parameters::Config for Test {
+    type Event = Event;
+}
+
 impl Config for Test {
     type Event = Event;
     type Call = Call;
     type GetConvertedTimestamp = timestamp::TimeConverter<Self>;
     type AccountStore = System;
     type SessionInterface = Self;
+    type GovernanceQueue = ();
     type WeightInfo = ();
 }
 
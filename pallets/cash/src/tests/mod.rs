@@ -12,6 +12,7 @@ use sp_core::offchain::testing;
 pub mod assets;
 pub mod common;
 pub mod mock;
+pub mod pipeline;
 pub mod protocol;
 pub mod testdata;
 pub mod worker;
@@ -52,6 +53,7 @@ pub fn val_a() -> ValidatorKeys {
             "0x8ad1b2918c34ee5d3e881a57c68574ea9dbecb81",
         )
         .unwrap(), // pk: 6bc5ea78f041146e38233f5bc29c703c1cec8eaaa2214353ee8adf7fc598f23d
+        next_eth_key: None,
     }
 }
 
@@ -63,6 +65,7 @@ pub fn val_b() -> ValidatorKeys {
             "0x6a72a2f14577D9Cd0167801EFDd54a07B40d2b61",
         )
         .unwrap(), // pk: 50f05592dc31bfc65a77c4cc80f2764ba8f9a7cce29c94a51fe2d70cb5599374
+        next_eth_key: None,
     }
 }
 
@@ -77,6 +80,7 @@ pub fn premined_block() -> ethereum_client::EthereumBlock {
             16, 36, 102, 87, 72, 211, 140, 211, 214, 121, 89, 97, 234,
         ],
         number: 1,
+        timestamp: 0,
         events: vec![
             ethereum_client::EthereumEvent::Lock {
                 asset: [
@@ -93,6 +97,7 @@ pub fn premined_block() -> ethereum_client::EthereumBlock {
                     135, 38, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 ],
                 amount: 1000000000000000000,
+                reference: None,
             },
             ethereum_client::EthereumEvent::Lock {
                 asset: [
@@ -109,6 +114,7 @@ pub fn premined_block() -> ethereum_client::EthereumBlock {
                     95, 244, 112, 49, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 ],
                 amount: 1000000000000000000,
+                reference: None,
             },
             ethereum_client::EthereumEvent::Lock {
                 asset: [
@@ -125,6 +131,7 @@ pub fn premined_block() -> ethereum_client::EthereumBlock {
                     95, 244, 112, 49, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 ],
                 amount: 1000000000000000000,
+                reference: None,
             },
         ],
     }
@@ -239,6 +246,30 @@ pub fn gen_mock_call_block_by_hash_fail(
     }
 }
 
+pub fn gen_mock_call_block_number(number: u64) -> testing::PendingRequest {
+    let block_number_data = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "eth_blockNumber",
+        "params": [],
+        "id": 1
+    });
+    let block_number_result = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": encode_block_number_hex(number)
+    });
+
+    testing::PendingRequest {
+        method: "POST".into(),
+        uri: "https://ropsten-eth.compound.finance".into(),
+        headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+        body: serde_json::to_vec(&block_number_data).unwrap(),
+        response: Some(serde_json::to_vec(&block_number_result).unwrap()),
+        sent: true,
+        ..Default::default()
+    }
+}
+
 pub fn gen_mock_calls(
     blocks: &[ethereum_client::EthereumBlock],
     starport_address: <Ethereum as Chain>::Address,
@@ -263,6 +294,7 @@ pub fn gen_mock_calls(
                 "hash": format!("0x{}", hex::encode(&block.hash[..])),
                 "number": block_num_str,
                 "parentHash": format!("0x{}", hex::encode(&block.parent_hash[..])),
+                "timestamp": encode_block_number_hex(block.timestamp),
             }
         });
 
@@ -0,0 +1,126 @@
+use super::test;
+use super::*;
+use crate::{pipeline::CashPipeline, tests::common::*};
+use frame_support::storage::{
+    IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+};
+use proptest::{collection::vec, prelude::*};
+
+// Fuzzes sequences of pipelines built from random lock/extract/transfer ops,
+//  checking the accounting invariants of storage after each one is committed.
+
+const ACCOUNTS: usize = 4;
+const ASSETS: [AssetInfo; 2] = [eth, wbtc];
+const MAX_ASSET_AMOUNT: AssetAmount = 10_000_000_000_000_000_000; // 10 ETH, 1e11 WBTC
+const MAX_PRINCIPAL: AssetAmount = 10_000_000_000; // 10,000 CASH
+const CHAIN_CASH_PRINCIPAL: AssetAmount = 1_000_000_000_000; // 1,000,000 CASH
+
+fn account(index: usize) -> ChainAccount {
+    ChainAccount::Eth([index as u8 + 1; 20])
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    LockAsset(usize, usize, AssetAmount),
+    ExtractAsset(usize, usize, AssetAmount),
+    TransferAsset(usize, usize, usize, AssetAmount),
+    LockCash(usize, AssetAmount),
+    ExtractCash(usize, AssetAmount),
+    TransferCash(usize, usize, AssetAmount),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let accounts = 0..ACCOUNTS;
+    let assets = 0..ASSETS.len();
+    let amount = 0..=MAX_ASSET_AMOUNT;
+    let principal = 0..=MAX_PRINCIPAL;
+    prop_oneof![
+        (accounts.clone(), assets.clone(), amount.clone())
+            .prop_map(|(a, i, x)| Op::LockAsset(a, i, x)),
+        (accounts.clone(), assets.clone(), amount.clone())
+            .prop_map(|(a, i, x)| Op::ExtractAsset(a, i, x)),
+        (accounts.clone(), accounts.clone(), assets, amount)
+            .prop_map(|(a, b, i, x)| Op::TransferAsset(a, b, i, x)),
+        (accounts.clone(), principal.clone()).prop_map(|(a, p)| Op::LockCash(a, p)),
+        (accounts.clone(), principal.clone()).prop_map(|(a, p)| Op::ExtractCash(a, p)),
+        (accounts.clone(), accounts, principal).prop_map(|(a, b, p)| Op::TransferCash(a, b, p)),
+    ]
+}
+
+/// Add an op to a pipeline, with the checks the real flows use to keep the totals sound.
+fn apply(pipeline: CashPipeline, op: &Op) -> Result<CashPipeline, Reason> {
+    match *op {
+        Op::LockAsset(a, i, x) => {
+            pipeline.lock_asset::<Test>(account(a), ASSETS[i].asset, ASSETS[i].as_quantity(x))
+        }
+        Op::ExtractAsset(a, i, x) => pipeline
+            .extract_asset::<Test>(account(a), ASSETS[i].asset, ASSETS[i].as_quantity(x))?
+            .check_sufficient_total_funds::<Test>(ASSETS[i]),
+        Op::TransferAsset(a, b, i, x) => pipeline.transfer_asset::<Test>(
+            account(a),
+            account(b),
+            ASSETS[i].asset,
+            ASSETS[i].as_quantity(x),
+        ),
+        Op::LockCash(a, p) => pipeline.lock_cash::<Test>(account(a), CashPrincipalAmount(p)),
+        Op::ExtractCash(a, p) => pipeline.extract_cash::<Test>(account(a), CashPrincipalAmount(p)),
+        Op::TransferCash(a, b, p) => {
+            pipeline.transfer_cash::<Test>(account(a), account(b), CashPrincipalAmount(p))
+        }
+    }
+}
+
+fn check_invariants() {
+    for asset_info in ASSETS.iter() {
+        let asset = asset_info.asset;
+        let (mut supply, mut borrow) = (0, 0);
+        for (holder, balance) in AssetBalances::iter_prefix(asset) {
+            if balance >= 0 {
+                supply += balance as AssetAmount;
+            } else {
+                borrow += (-balance) as AssetAmount;
+            }
+            assert_eq!(
+                AssetsWithNonZeroBalance::contains_key(holder, asset),
+                balance != 0,
+                "non-zero balance of {:?} for {:?}",
+                asset,
+                holder
+            );
+        }
+        assert_eq!(TotalSupplyAssets::get(asset), supply, "total supply");
+        assert_eq!(TotalBorrowAssets::get(asset), borrow, "total borrow");
+        assert!(supply >= borrow, "supply {} < borrow {}", supply, borrow);
+    }
+
+    // All the CASH in existence is either held on a chain, or by an account on Gateway
+    let held: AssetAmount = CashPrincipals::iter()
+        .map(|(_, principal)| principal.0.max(0) as AssetAmount)
+        .sum();
+    let chains: AssetAmount = ChainCashPrincipals::iter()
+        .map(|(_, principal)| principal.0)
+        .sum();
+    assert_eq!(TotalCashPrincipal::get().0, held + chains, "total cash");
+}
+
+proptest! {
+    #[test]
+    fn test_pipeline_invariants(steps in vec(vec(op(), 1..4), 1..32)) {
+        new_test_ext().execute_with(|| {
+            init_eth_asset().unwrap();
+            init_wbtc_asset().unwrap();
+            ChainCashPrincipals::insert(ChainId::Eth, CashPrincipalAmount(CHAIN_CASH_PRINCIPAL));
+            TotalCashPrincipal::put(CashPrincipalAmount(CHAIN_CASH_PRINCIPAL));
+
+            for ops in steps.iter() {
+                let result = ops
+                    .iter()
+                    .try_fold(CashPipeline::new(), |pipeline, op| apply(pipeline, op));
+                if let Ok(pipeline) = result {
+                    pipeline.commit::<Test>();
+                }
+                check_invariants();
+            }
+        });
+    }
+}
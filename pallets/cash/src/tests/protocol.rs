@@ -18,7 +18,8 @@ fn upload_transfer_download() -> Result<(), Reason> {
             uni,
             jared,
             jared,
-            lock_amount
+            lock_amount,
+            None
         ));
         assert_eq!(CashPrincipals::get(&jared), CashPrincipal(0));
         assert_eq!(CashPrincipals::get(&geoff), CashPrincipal(0));
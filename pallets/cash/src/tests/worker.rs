@@ -51,6 +51,7 @@ fn test_offchain_worker() {
             sent: true,
             ..Default::default()
         },
+        gen_mock_call_block_number(4),
     ];
 
     let (mut t, pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
@@ -63,6 +64,10 @@ fn test_offchain_worker() {
         CashModule::offchain_worker(block_num);
 
         assert_eq!(pool_state.read().transactions.len(), 1);
+        assert_eq!(
+            crate::internal::health::get_chain_health::<Test>().unwrap()[0].chain_head,
+            Some(4)
+        );
 
         let tx1 = pool_state.write().transactions.pop().unwrap();
         let ex1: Extrinsic = Decode::decode(&mut &*tx1).unwrap();
@@ -95,6 +100,7 @@ fn test_offchain_worker() {
                                 56, 125, 95, 244, 112, 49, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
                             ],
                             amount: 1000000000000000000,
+                            reference: None,
                         }
                     )
                 }
@@ -2,8 +2,20 @@ use trx_request;
 
 use crate::{
     chains::{ChainAccount, ChainAsset},
-    types::CashOrChainAsset,
+    internal::assets::get_asset_by_symbol,
+    reason::{MathError, Reason},
+    symbol::Symbol,
+    types::{AssetAmount, CashOrChainAsset, Decimals},
+    Config,
 };
+use our_std::{str::FromStr, RuntimeDebug};
+
+/// An amount from a trx request, in the base units of its asset.
+#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum MaxAssetAmount {
+    Amount(AssetAmount),
+    Max,
+}
 
 impl From<trx_request::Account> for ChainAccount {
     fn from(account: trx_request::Account) -> Self {
@@ -15,16 +27,42 @@ impl From<trx_request::Account> for ChainAccount {
     }
 }
 
-impl From<trx_request::Asset> for CashOrChainAsset {
-    fn from(account: trx_request::Asset) -> Self {
-        match account {
-            trx_request::Asset::Cash => CashOrChainAsset::Cash,
-            trx_request::Asset::Eth(eth_address) => {
-                CashOrChainAsset::ChainAsset(ChainAsset::Eth(eth_address))
-            }
-            trx_request::Asset::Matic(eth_address) => {
-                CashOrChainAsset::ChainAsset(ChainAsset::Matic(eth_address))
-            }
+/// Resolve an asset from a trx request, looking up symbolic references among the supported assets.
+pub fn resolve_asset<T: Config>(asset: trx_request::Asset) -> Result<CashOrChainAsset, Reason> {
+    match asset {
+        trx_request::Asset::Cash => Ok(CashOrChainAsset::Cash),
+        trx_request::Asset::Eth(eth_address) => {
+            Ok(CashOrChainAsset::ChainAsset(ChainAsset::Eth(eth_address)))
+        }
+        trx_request::Asset::Matic(eth_address) => {
+            Ok(CashOrChainAsset::ChainAsset(ChainAsset::Matic(eth_address)))
+        }
+        trx_request::Asset::Symbol(symbol_str) => {
+            let symbol = Symbol::from_str(&symbol_str)?;
+            Ok(CashOrChainAsset::ChainAsset(
+                get_asset_by_symbol::<T>(symbol)?.asset,
+            ))
+        }
+    }
+}
+
+/// Scale an amount from a trx request to the base units of an asset with the given decimals.
+pub fn scale_max_amount(
+    max_amount: trx_request::MaxAmount,
+    decimals: Decimals,
+) -> Result<MaxAssetAmount, Reason> {
+    match max_amount {
+        trx_request::MaxAmount::Max => Ok(MaxAssetAmount::Max),
+        trx_request::MaxAmount::Amount(amount) => Ok(MaxAssetAmount::Amount(amount)),
+        trx_request::MaxAmount::Decimal(decimal) => {
+            let shift = decimals
+                .checked_sub(decimal.decimals)
+                .ok_or(Reason::TooManyDecimals)?;
+            let amount = 10u128
+                .checked_pow(shift.into())
+                .and_then(|scale| decimal.digits.checked_mul(scale))
+                .ok_or(MathError::Overflow)?;
+            Ok(MaxAssetAmount::Amount(amount))
         }
     }
 }
@@ -32,6 +70,7 @@ impl From<trx_request::Asset> for CashOrChainAsset {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::{common::*, *};
     use trx_request;
 
     const ALAN: [u8; 20] = [1; 20];
@@ -46,15 +85,60 @@ mod tests {
     }
 
     #[test]
-    fn test_asset_to_chain_asset() {
+    fn test_scale_max_amount() {
+        let decimal = |digits, decimals| {
+            trx_request::MaxAmount::Decimal(trx_request::Decimal { digits, decimals })
+        };
+
         assert_eq!(
-            CashOrChainAsset::from(trx_request::Asset::Eth(ETH)),
-            CashOrChainAsset::ChainAsset(ChainAsset::Eth(ETH))
+            scale_max_amount(trx_request::MaxAmount::Max, 18),
+            Ok(MaxAssetAmount::Max)
+        );
+        assert_eq!(
+            scale_max_amount(trx_request::MaxAmount::Amount(15), 18),
+            Ok(MaxAssetAmount::Amount(15))
         );
-
         assert_eq!(
-            CashOrChainAsset::from(trx_request::Asset::Cash),
-            CashOrChainAsset::Cash
+            scale_max_amount(decimal(15, 1), 18),
+            Ok(MaxAssetAmount::Amount(1_500_000_000_000_000_000))
         );
+        assert_eq!(
+            scale_max_amount(decimal(15, 6), 6),
+            Ok(MaxAssetAmount::Amount(15))
+        );
+        assert_eq!(
+            scale_max_amount(decimal(15, 7), 6),
+            Err(Reason::TooManyDecimals)
+        );
+        assert_eq!(
+            scale_max_amount(decimal(u128::MAX, 0), 1),
+            Err(Reason::MathError(MathError::Overflow))
+        );
+    }
+
+    #[test]
+    fn test_resolve_asset() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Eth(ETH)),
+                Ok(CashOrChainAsset::ChainAsset(ChainAsset::Eth(ETH)))
+            );
+
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Cash),
+                Ok(CashOrChainAsset::Cash)
+            );
+
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Symbol(String::from("ETH"))),
+                Err(Reason::AssetNotSupported)
+            );
+
+            init_eth_asset().unwrap();
+            assert_eq!(
+                resolve_asset::<Test>(trx_request::Asset::Symbol(String::from("ETH"))),
+                Ok(CashOrChainAsset::ChainAsset(ChainAsset::Eth(ETH)))
+            );
+        });
     }
 }
@@ -2,17 +2,20 @@ use codec::{Decode, Encode};
 use frame_support::sp_runtime::DispatchError;
 
 use our_std::{
-    collections::btree_set::BTreeSet,
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
     consts::{int_from_string_with_decimals, static_pow10, uint_from_string_with_decimals},
     convert::{TryFrom, TryInto},
     Deserialize, RuntimeDebug, Serialize,
 };
 use types_derive::{type_alias, Types};
 
-pub use pallet_oracle::{ticker::Ticker, types::Price};
+pub use pallet_oracle::{
+    ticker::Ticker,
+    types::{AssetPrice, Price},
+};
 
 pub use crate::{
-    chains::{Chain, ChainAsset, ChainBlockNumber, ChainId, Ethereum},
+    chains::{Chain, ChainAccount, ChainAsset, ChainBlockNumber, ChainId, Ethereum},
     factor::{BigInt, BigUint, Factor},
     notices::{Notice, NoticeId},
     rates::{InterestRateModel, APR},
@@ -35,6 +38,10 @@ pub type Decimals = u8;
 #[type_alias]
 pub type Nonce = u32;
 
+/// Type for identifying an extraction held back by the withdrawal timelock.
+#[type_alias]
+pub type PendingExtractId = u64;
+
 /// Type for representing time since current Unix epoch in milliseconds.
 #[type_alias]
 pub type Timestamp = timestamp::Timestamp; //u64;
@@ -107,11 +114,76 @@ pub type ValidatorIdentity = SubstrateId;
 #[type_alias]
 pub type SignersSet = BTreeSet<ValidatorIdentity>;
 
+/// Type for the weight of each validator's signature, empty when all are weighted equally.
+#[type_alias]
+pub type SignerWeights = BTreeMap<ValidatorIdentity, u128>;
+
 /// Type for representing the keys to sign notices.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
 pub struct ValidatorKeys {
     pub substrate_id: SubstrateId,
     pub eth_address: <Ethereum as Chain>::Address,
+    /// The Eth key being rotated to, accepted alongside the current one until it expires. [eth_address, expires_at]
+    pub next_eth_key: Option<(<Ethereum as Chain>::Address, Timestamp)>,
+}
+
+impl ValidatorKeys {
+    /// Whether the validator signs with the given Eth address, either its current key,
+    /// or its next key before the transition to it expires.
+    pub fn has_eth_address(
+        &self,
+        eth_address: <Ethereum as Chain>::Address,
+        now: Option<Timestamp>,
+    ) -> bool {
+        if self.eth_address == eth_address {
+            return true;
+        }
+        match (self.next_eth_key, now) {
+            (Some((next_eth_address, expires_at)), Some(now)) => {
+                next_eth_address == eth_address && now < expires_at
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Type for counting a validator's participation within a session, or that expected of it.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
+pub struct ValidatorParticipation {
+    pub blocks_attested: u32,
+    pub notices_signed: u32,
+}
+
+/// Type for the mining earnings of a validator within a session, paid out or withheld for an offense.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
+pub struct SessionEarnings {
+    pub session_index: SessionIndex,
+    pub paid: CashPrincipalAmount,
+    pub withheld: CashPrincipalAmount,
+}
+
+/// Type for the metadata a validator publishes, identifying who runs it.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
+pub struct ValidatorMetadata {
+    pub name: Vec<u8>,
+    pub website: Vec<u8>,
+    pub contact: Vec<u8>,
+    pub security_policy_url: Vec<u8>,
+}
+
+/// Type for an offense by a validator, signing conflicting blocks of a chain at the same height.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ValidatorOffense {
+    pub chain_id: ChainId,
+    pub block_number: ChainBlockNumber,
+    pub timestamp: Timestamp,
+}
+
+/// Type for CASH principal unbonded by an account, held until its release time.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct Unbonding {
+    pub principal: CashPrincipalAmount,
+    pub release_at: Timestamp,
 }
 
 /// Type for referring to either an asset or CASH.
@@ -133,6 +205,7 @@ pub struct AssetInfo {
     pub supply_cap: AssetAmount,
     pub symbol: Symbol,
     pub ticker: Ticker,
+    pub liquidation_incentive: Option<Bips>,
 }
 
 impl AssetInfo {
@@ -146,6 +219,7 @@ impl AssetInfo {
             supply_cap: AssetAmount::default(),
             symbol: Symbol(units.ticker.0),
             ticker: units.ticker,
+            liquidation_incentive: None,
         }
     }
 
@@ -170,6 +244,135 @@ impl AssetInfo {
     }
 }
 
+/// Type for a record of the indices and rates resulting from an asset's interest accrual.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct AccrualPoint {
+    pub timestamp: Timestamp,
+    pub supply_index: AssetIndex,
+    pub borrow_index: AssetIndex,
+    pub supply_rate: APR,
+    pub borrow_rate: APR,
+}
+
+/// Type for the effects a trx request would have if executed now, for previewing it before it's signed.
+/// The balance deltas are per account and asset, and the liquidity is the sender's afterwards.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct TrxRequestEstimate {
+    pub balance_deltas: Vec<(ChainAccount, CashOrChainAsset, AssetBalance)>,
+    pub liquidity: AssetBalance,
+    pub notices: Vec<(NoticeId, EncodedNotice)>,
+}
+
+/// Type for the health of ingesting from and signing notices for an underlying chain.
+/// The chain head is the latest block number last seen by this node's offchain worker, if any.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ChainHealth {
+    pub chain_id: ChainId,
+    pub last_processed_block: Option<ChainBlockNumber>,
+    pub chain_head: Option<ChainBlockNumber>,
+    pub ingression_queue_depth: u32,
+    pub pending_notices: u32,
+}
+
+/// Type for the backlogs of the protocol, which the node exports as Prometheus gauges.
+/// The price ages are the time in milliseconds since the price of each ticker was last posted.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ProtocolMetrics {
+    pub ingression_queue_depths: Vec<(ChainId, u32)>,
+    pub pending_block_support: Vec<(ChainId, ChainBlockNumber, u32)>,
+    pub unsigned_notices: Vec<(ChainId, u32)>,
+    pub price_ages: Vec<(Ticker, Timestamp)>,
+}
+
+/// Type for a summary of the current state of a market, so all markets may be queried at once.
+/// The total value locked is the USD value of the total supply.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct MarketSummary {
+    pub asset: ChainAsset,
+    pub total_supply: AssetAmount,
+    pub total_borrow: AssetAmount,
+    pub total_value_locked: AssetAmount,
+    pub utilization: Factor,
+    pub supply_rate: APR,
+    pub borrow_rate: APR,
+    pub price: AssetPrice,
+    pub supply_cap: AssetAmount,
+    pub liquidity_factor: LiquidityFactor,
+}
+
+/// Type for the kinds of operation recorded in an account's activity history.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum ActivityKind {
+    Lock,
+    Extract,
+    Send,
+    Receive,
+    Liquidate,
+    Liquidated,
+}
+
+/// Type for an operation recorded in an account's activity history, at the block it occurred.
+/// The amount is the CASH principal for CASH operations.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ActivityEntry {
+    pub block_number: u32,
+    pub kind: ActivityKind,
+    pub asset: CashOrChainAsset,
+    pub amount: AssetAmount,
+    pub counterparty: Option<ChainAccount>,
+}
+
+/// Type for an aggregate kept in storage which has diverged from what it aggregates.
+/// [asset or none, the aggregate stored, the aggregate recomputed]
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum BrokenInvariant {
+    TotalSupply(ChainAsset, AssetAmount, AssetAmount),
+    TotalBorrow(ChainAsset, AssetAmount, AssetAmount),
+    TotalCash(CashPrincipalAmount, CashPrincipalAmount),
+}
+
+/// Type for the kinds of new activity which governance may pause on an asset.
+/// Repays and liquidations are always allowed.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
+pub struct AssetPauseFlags {
+    pub supply: bool,
+    pub borrow: bool,
+    pub extract: bool,
+}
+
+/// Type for a limit on how much of an asset an account may extract over a rolling window.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ExtractRateLimit {
+    pub amount: AssetAmount,
+    pub window: Timestamp,
+}
+
+/// Type for how much of an extract rate limit an account had used as of some time.
+/// The usage is released gradually, at the rate of the limit amount per window.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ExtractRateUsage {
+    pub amount: AssetAmount,
+    pub timestamp: Timestamp,
+}
+
+/// Type for an extraction held back by the withdrawal timelock until its release time.
+/// The amount is the CASH principal when extracting CASH.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct PendingExtract {
+    pub sender: ChainAccount,
+    pub recipient: ChainAccount,
+    pub asset: CashOrChainAsset,
+    pub amount: AssetAmount,
+    pub release_at: Timestamp,
+}
+
+/// Type for the CASH principal emitted each day as rewards to the suppliers and borrowers of an asset.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
+pub struct RewardSpeeds {
+    pub supply: CashPrincipalAmount,
+    pub borrow: CashPrincipalAmount,
+}
+
 // Note: ideally we would impl Ord ourselves for all these Ord types,
 //  and assert ticker/units are the same when comparing.
 // We would have to panic, though not for PartialOrd...
@@ -689,6 +892,21 @@ impl AssetIndex {
         ))
     }
 
+    /// Get the CASH principal accrued by the size of a balance, whether supplied or borrowed, since a previous index.
+    pub fn cash_principal_amount_since(
+        self,
+        since: AssetIndex,
+        balance: Balance,
+    ) -> Result<CashPrincipalAmount, MathError> {
+        let delta_index = self.0.checked_sub(since.0).ok_or(MathError::Underflow)?;
+        Ok(CashPrincipalAmount(
+            BigUint::from_uint(delta_index)
+                .mul_decimal(balance.value.unsigned_abs(), balance.units.decimals)
+                .convert(AssetIndex::DECIMALS, CashPrincipalAmount::DECIMALS)
+                .to_uint()?,
+        ))
+    }
+
     // AssetQuantity<U> * CashPrincipalPerAsset(+) -> CashPrincipal(+)
     pub fn cash_principal_amount(
         self,
@@ -54,8 +54,8 @@ pub trait WeightInfo {
     fn on_initialize(z: u32) -> Weight;
     fn publish_signature() -> Weight;
     fn set_yield_next() -> Weight;
-    fn receive_chain_blocks() -> Weight;
-    fn receive_chain_reorg_pending(z: u32) -> Weight;
+    fn receive_chain_blocks(b: u32, z: u32) -> Weight;
+    fn receive_chain_reorg_pending(d: u32, z: u32) -> Weight;
     fn support_asset() -> Weight;
     fn set_rate_model() -> Weight;
     fn set_liquidity_factor() -> Weight;
@@ -63,8 +63,15 @@ pub trait WeightInfo {
     fn allow_next_code_with_hash() -> Weight;
     fn set_next_code_via_hash(z: u32) -> Weight;
     fn change_validators() -> Weight;
+    fn set_miner() -> Weight;
+    fn set_starport() -> Weight;
+    fn set_genesis_block() -> Weight;
     fn exec_trx_request_extract() -> Weight;
     fn exec_trx_request_transfer() -> Weight;
+    fn exec_trx_request_approve() -> Weight;
+    fn exec_trx_request_transfer_from() -> Weight;
+    fn exec_trx_request_cancel() -> Weight;
+    fn exec_trx_request_set_nonce_window() -> Weight;
     fn exec_trx_request_liquidate() -> Weight;
 }
 
@@ -88,13 +95,21 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
-    fn receive_chain_blocks() -> Weight {
-        (189_000_000 as Weight)
+    fn receive_chain_blocks(b: u32, z: u32) -> Weight {
+        (172_418_000 as Weight)
+            // Standard Error: 1_902_000
+            .saturating_add((24_306_000 as Weight).saturating_mul(b as Weight))
+            // Standard Error: 213_000
+            .saturating_add((118_540_000 as Weight).saturating_mul(z as Weight))
             .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(z as Weight)))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(z as Weight)))
     }
-    fn receive_chain_reorg_pending(z: u32) -> Weight {
-        (194_733_000 as Weight)
+    fn receive_chain_reorg_pending(d: u32, z: u32) -> Weight {
+        (181_205_000 as Weight)
+            // Standard Error: 412_000
+            .saturating_add((6_418_000 as Weight).saturating_mul(d as Weight))
             // Standard Error: 56_000
             .saturating_add((1_067_000 as Weight).saturating_mul(z as Weight))
             .saturating_add(T::DbWeight::get().reads(7 as Weight))
@@ -133,6 +148,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(11 as Weight))
             .saturating_add(T::DbWeight::get().writes(12 as Weight))
     }
+    fn set_miner() -> Weight {
+        (3_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_starport() -> Weight {
+        (11_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_genesis_block() -> Weight {
+        (17_000_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
     fn exec_trx_request_extract() -> Weight {
         (295_000_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(15 as Weight))
@@ -143,6 +167,26 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(19 as Weight))
             .saturating_add(T::DbWeight::get().writes(13 as Weight))
     }
+    fn exec_trx_request_approve() -> Weight {
+        (98_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_transfer_from() -> Weight {
+        (322_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(20 as Weight))
+            .saturating_add(T::DbWeight::get().writes(14 as Weight))
+    }
+    fn exec_trx_request_cancel() -> Weight {
+        (84_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_set_nonce_window() -> Weight {
+        (80_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn exec_trx_request_liquidate() -> Weight {
         (475_000_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(33 as Weight))
@@ -169,13 +213,21 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
-    fn receive_chain_blocks() -> Weight {
-        (189_000_000 as Weight)
+    fn receive_chain_blocks(b: u32, z: u32) -> Weight {
+        (172_418_000 as Weight)
+            // Standard Error: 1_902_000
+            .saturating_add((24_306_000 as Weight).saturating_mul(b as Weight))
+            // Standard Error: 213_000
+            .saturating_add((118_540_000 as Weight).saturating_mul(z as Weight))
             .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(z as Weight)))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(z as Weight)))
     }
-    fn receive_chain_reorg_pending(z: u32) -> Weight {
-        (194_733_000 as Weight)
+    fn receive_chain_reorg_pending(d: u32, z: u32) -> Weight {
+        (181_205_000 as Weight)
+            // Standard Error: 412_000
+            .saturating_add((6_418_000 as Weight).saturating_mul(d as Weight))
             // Standard Error: 56_000
             .saturating_add((1_067_000 as Weight).saturating_mul(z as Weight))
             .saturating_add(RocksDbWeight::get().reads(7 as Weight))
@@ -214,6 +266,15 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(11 as Weight))
             .saturating_add(RocksDbWeight::get().writes(12 as Weight))
     }
+    fn set_miner() -> Weight {
+        (3_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_starport() -> Weight {
+        (11_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_genesis_block() -> Weight {
+        (17_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
     fn exec_trx_request_extract() -> Weight {
         (295_000_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(15 as Weight))
@@ -224,6 +285,26 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(19 as Weight))
             .saturating_add(RocksDbWeight::get().writes(13 as Weight))
     }
+    fn exec_trx_request_approve() -> Weight {
+        (98_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_transfer_from() -> Weight {
+        (322_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(20 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(14 as Weight))
+    }
+    fn exec_trx_request_cancel() -> Weight {
+        (84_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn exec_trx_request_set_nonce_window() -> Weight {
+        (80_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn exec_trx_request_liquidate() -> Weight {
         (475_000_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(33 as Weight))
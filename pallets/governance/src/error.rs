@@ -0,0 +1,63 @@
+use codec::{Decode, Encode};
+use frame_support;
+use our_std::Debuggable;
+use pallet_cash::reason::Reason;
+
+use types_derive::Types;
+
+/// Errors coming from governance.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Debuggable, Types)]
+pub enum GovernanceError {
+    CashError(Reason),
+    NoExtrinsics,
+    BelowProposalThreshold,
+    ProposalNotFound,
+    ProposalNotActive,
+    VotingClosed,
+    AlreadyVoted,
+    NoVotingWeight,
+    InvalidVotingPeriod,
+    NoCompSnapshot,
+    CompSnapshotAlreadySet,
+    InvalidCompProof,
+    NotEthAccount,
+    InvalidQuorum,
+    InvalidProposalThreshold,
+    ProposalTooLarge,
+    CompProofTooLong,
+}
+
+impl From<Reason> for GovernanceError {
+    fn from(reason: Reason) -> Self {
+        GovernanceError::CashError(reason)
+    }
+}
+
+impl From<GovernanceError> for frame_support::dispatch::DispatchError {
+    fn from(err: GovernanceError) -> frame_support::dispatch::DispatchError {
+        let (index, error, message) = match err {
+            GovernanceError::CashError(_) => (0, 0, "CashError"),
+            GovernanceError::NoExtrinsics => (1, 0, "NoExtrinsics"),
+            GovernanceError::BelowProposalThreshold => (2, 0, "BelowProposalThreshold"),
+            GovernanceError::ProposalNotFound => (3, 0, "ProposalNotFound"),
+            GovernanceError::ProposalNotActive => (4, 0, "ProposalNotActive"),
+            GovernanceError::VotingClosed => (5, 0, "VotingClosed"),
+            GovernanceError::AlreadyVoted => (6, 0, "AlreadyVoted"),
+            GovernanceError::NoVotingWeight => (7, 0, "NoVotingWeight"),
+            GovernanceError::InvalidVotingPeriod => (8, 0, "InvalidVotingPeriod"),
+            GovernanceError::NoCompSnapshot => (9, 0, "NoCompSnapshot"),
+            GovernanceError::CompSnapshotAlreadySet => (10, 0, "CompSnapshotAlreadySet"),
+            GovernanceError::InvalidCompProof => (11, 0, "InvalidCompProof"),
+            GovernanceError::NotEthAccount => (12, 0, "NotEthAccount"),
+            GovernanceError::InvalidQuorum => (13, 0, "InvalidQuorum"),
+            GovernanceError::InvalidProposalThreshold => (14, 0, "InvalidProposalThreshold"),
+            GovernanceError::ProposalTooLarge => (15, 0, "ProposalTooLarge"),
+            GovernanceError::CompProofTooLong => (16, 0, "CompProofTooLong"),
+        };
+        frame_support::dispatch::DispatchError::Module {
+            index,
+            error,
+            message: Some(message),
+        }
+    }
+}
@@ -0,0 +1,462 @@
+use crate::{
+    error::GovernanceError,
+    snapshots::has_comp_passed,
+    types::{Nonce, Proposal, ProposalId, ProposalState, Timestamp, Vote},
+    ActiveProposals, CompQuorum, CompSnapshots, Config, Event, Module, NextProposalId,
+    ProposalThreshold, Proposals, Quorum, Votes, VotingPeriod, MAX_PROPOSAL_SIZE,
+};
+use codec::Encode;
+use frame_support::storage::{StorageDoubleMap, StorageMap, StorageValue};
+use our_std::vec::Vec;
+use pallet_cash::{
+    chains::{ChainAccount, ChainAccountSignature},
+    core::get_recent_timestamp,
+    internal::{
+        checkpoints::get_cash_principal_at,
+        exec_trx_request::prepend_nonce,
+        nonces::{check_nonce, use_nonce},
+    },
+    params::CASH_PRINCIPAL_HISTORY,
+    types::CashPrincipalAmount,
+};
+use pallet_timelock::timelock::queue_at;
+
+/// The payload a proposer signs, with a nonce prepended, to submit a proposal.
+pub fn propose_payload(extrinsics: &Vec<Vec<u8>>) -> Vec<u8> {
+    format!("(Propose 0x{})", hex::encode(extrinsics.encode())).into_bytes()
+}
+
+/// The payload a voter signs, with a nonce prepended, to cast a vote on a proposal.
+pub fn vote_payload(proposal_id: ProposalId, support: bool) -> Vec<u8> {
+    let choice = if support { "For" } else { "Against" };
+    format!("(Vote {} {})", proposal_id, choice).into_bytes()
+}
+
+/// Return the voting weight of an account, which is the CASH principal it held as of the given time.
+/// Votes are weighed as of when a proposal was made, so the same CASH can't be moved and voted again.
+pub fn get_voting_weight<T: Config>(
+    account: ChainAccount,
+    at: Timestamp,
+) -> Result<CashPrincipalAmount, GovernanceError> {
+    let now = get_recent_timestamp::<T>()?;
+    Ok(get_cash_principal_at(account, at, now)?
+        .amount_withdrawable()
+        .map_err(pallet_cash::reason::Reason::from)?)
+}
+
+/// Recover the signer of a proposal, checking it may propose and the nonce may be used.
+pub fn recover_proposer<T: Config>(
+    extrinsics: &Vec<Vec<u8>>,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<ChainAccount, GovernanceError> {
+    if extrinsics.is_empty() {
+        return Err(GovernanceError::NoExtrinsics);
+    }
+    if extrinsics.encoded_size() > MAX_PROPOSAL_SIZE {
+        return Err(GovernanceError::ProposalTooLarge);
+    }
+    let proposer =
+        signature.recover_account(&prepend_nonce(&propose_payload(extrinsics), nonce))?;
+    check_nonce::<T>(proposer, nonce)?;
+    if get_voting_weight::<T>(proposer, get_recent_timestamp::<T>()?)? < ProposalThreshold::get() {
+        return Err(GovernanceError::BelowProposalThreshold);
+    }
+    Ok(proposer)
+}
+
+/// Recover the signer of a vote and its voting weight, checking it may vote and the nonce may be used.
+pub fn recover_voter<T: Config>(
+    proposal_id: ProposalId,
+    support: bool,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(ChainAccount, CashPrincipalAmount), GovernanceError> {
+    let voter =
+        signature.recover_account(&prepend_nonce(&vote_payload(proposal_id, support), nonce))?;
+    check_nonce::<T>(voter, nonce)?;
+    let weight = check_vote::<T>(proposal_id, voter)?;
+    Ok((voter, weight))
+}
+
+/// Check a proposal is still open for votes.
+pub fn check_open<T: Config>(proposal_id: ProposalId) -> Result<Proposal, GovernanceError> {
+    let proposal = Proposals::get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+    if proposal.state != ProposalState::Active {
+        return Err(GovernanceError::ProposalNotActive);
+    }
+    if get_recent_timestamp::<T>()? >= proposal.end_at {
+        return Err(GovernanceError::VotingClosed);
+    }
+    Ok(proposal)
+}
+
+/// Check an account may vote on a proposal, returning its voting weight as of when it was proposed.
+pub fn check_vote<T: Config>(
+    proposal_id: ProposalId,
+    voter: ChainAccount,
+) -> Result<CashPrincipalAmount, GovernanceError> {
+    let proposal = check_open::<T>(proposal_id)?;
+    if Votes::contains_key(proposal_id, voter) {
+        return Err(GovernanceError::AlreadyVoted);
+    }
+    let weight = get_voting_weight::<T>(voter, proposal.start_at)?;
+    if weight == CashPrincipalAmount::ZERO {
+        return Err(GovernanceError::NoVotingWeight);
+    }
+    Ok(weight)
+}
+
+/// Submit a signed proposal, opening it for votes until the end of the voting period.
+/// Votes are weighed by the principal each voter held as of now.
+pub fn propose<T: Config>(
+    extrinsics: Vec<Vec<u8>>,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(), GovernanceError> {
+    let proposer = recover_proposer::<T>(&extrinsics, signature, nonce)?;
+    let now = get_recent_timestamp::<T>()?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    use_nonce::<T>(proposer, nonce);
+    submit_proposal::<T>(proposer, extrinsics, now);
+    Ok(())
+}
+
+/// Store a new proposal, returning its id.
+pub fn submit_proposal<T: Config>(
+    proposer: ChainAccount,
+    extrinsics: Vec<Vec<u8>>,
+    now: Timestamp,
+) -> ProposalId {
+    let proposal_id = NextProposalId::get();
+    let end_at = now.saturating_add(VotingPeriod::get());
+    Proposals::insert(
+        proposal_id,
+        Proposal {
+            proposer,
+            extrinsics,
+            start_at: now,
+            end_at,
+            for_votes: CashPrincipalAmount::ZERO,
+            against_votes: CashPrincipalAmount::ZERO,
+            comp_for_votes: 0,
+            comp_against_votes: 0,
+            state: ProposalState::Active,
+        },
+    );
+    NextProposalId::put(proposal_id + 1);
+    ActiveProposals::append(proposal_id);
+    <Module<T>>::deposit_event(Event::Proposed(proposal_id, proposer, end_at));
+    proposal_id
+}
+
+/// Cast a signed vote on an active proposal.
+pub fn cast_vote<T: Config>(
+    proposal_id: ProposalId,
+    support: bool,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(), GovernanceError> {
+    let (voter, weight) = recover_voter::<T>(proposal_id, support, signature, nonce)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    use_nonce::<T>(voter, nonce);
+    record_vote::<T>(proposal_id, voter, Vote { support, weight });
+    Ok(())
+}
+
+/// Record a vote on a proposal, and count its weight.
+pub fn record_vote<T: Config>(proposal_id: ProposalId, voter: ChainAccount, vote: Vote) {
+    Proposals::mutate(proposal_id, |proposal| {
+        if let Some(proposal) = proposal {
+            let votes = if vote.support {
+                &mut proposal.for_votes
+            } else {
+                &mut proposal.against_votes
+            };
+            *votes = CashPrincipalAmount(votes.0.saturating_add(vote.weight.0));
+        }
+    });
+    Votes::insert(proposal_id, voter, vote);
+    <Module<T>>::deposit_event(Event::VoteCast(
+        proposal_id,
+        voter,
+        vote.support,
+        vote.weight,
+    ));
+}
+
+/// Cancel an active proposal, so it is never dispatched.
+pub fn cancel_proposal<T: Config>(proposal_id: ProposalId) -> Result<(), GovernanceError> {
+    let mut proposal = Proposals::get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+    if proposal.state != ProposalState::Active {
+        return Err(GovernanceError::ProposalNotActive);
+    }
+    proposal.state = ProposalState::Canceled;
+    Proposals::insert(proposal_id, proposal);
+    ActiveProposals::mutate(|active| active.retain(|id| *id != proposal_id));
+    <Module<T>>::deposit_event(Event::ProposalCanceled(proposal_id));
+    Ok(())
+}
+
+/// Set the voting period, and the CASH principal needed to reach quorum and to propose.
+/// Neither may be zero, or any single holder could pass a proposal unopposed.
+/// Votes are weighed by principal as of when proposed, so voting may not outlast its history.
+pub fn set_governance_params<T: Config>(
+    voting_period: Timestamp,
+    quorum: CashPrincipalAmount,
+    proposal_threshold: CashPrincipalAmount,
+) -> Result<(), GovernanceError> {
+    if voting_period == 0 || voting_period > CASH_PRINCIPAL_HISTORY {
+        return Err(GovernanceError::InvalidVotingPeriod);
+    }
+    if quorum == CashPrincipalAmount::ZERO {
+        return Err(GovernanceError::InvalidQuorum);
+    }
+    if proposal_threshold == CashPrincipalAmount::ZERO {
+        return Err(GovernanceError::InvalidProposalThreshold);
+    }
+    VotingPeriod::put(voting_period);
+    Quorum::put(quorum);
+    ProposalThreshold::put(proposal_threshold);
+    <Module<T>>::deposit_event(Event::SetGovernanceParams(
+        voting_period,
+        quorum,
+        proposal_threshold,
+    ));
+    Ok(())
+}
+
+/// Whether a proposal passed, with more votes for than against and at least a quorum of votes for.
+pub fn has_passed(proposal: &Proposal, quorum: CashPrincipalAmount) -> bool {
+    proposal.for_votes > proposal.against_votes && proposal.for_votes >= quorum
+}
+
+/// Finish the proposals whose voting period has ended, queueing those which passed in the timelock.
+/// Proposals with a snapshot of COMP balances must also pass the vote of COMP holders.
+pub fn finalize_proposals<T: Config>(now: Timestamp) {
+    let quorum = Quorum::get();
+    let comp_quorum = CompQuorum::get();
+    let (ended, active): (Vec<ProposalId>, Vec<ProposalId>) = ActiveProposals::get()
+        .into_iter()
+        .partition(|id| Proposals::get(id).map_or(true, |proposal| proposal.end_at <= now));
+    if ended.is_empty() {
+        return;
+    }
+    ActiveProposals::put(active);
+
+    for proposal_id in ended {
+        if let Some(mut proposal) = Proposals::get(proposal_id) {
+            let comp_passed = !CompSnapshots::contains_key(proposal_id)
+                || has_comp_passed(&proposal, comp_quorum);
+            if has_passed(&proposal, quorum) && comp_passed {
+                let timelock_id = queue_at::<T>(proposal.extrinsics.clone(), now);
+                proposal.state = ProposalState::Queued(timelock_id);
+                Proposals::insert(proposal_id, proposal);
+                <Module<T>>::deposit_event(Event::ProposalQueued(proposal_id, timelock_id));
+            } else {
+                proposal.state = ProposalState::Defeated;
+                Proposals::insert(proposal_id, proposal);
+                <Module<T>>::deposit_event(Event::ProposalDefeated(proposal_id));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use pallet_cash::{pipeline::CashPipeline, types::CashPrincipal};
+    use pallet_timelock::timelock::execute_ready;
+
+    const PROPOSER: ChainAccount = ChainAccount::Eth([1; 20]);
+    const VOTER: ChainAccount = ChainAccount::Eth([2; 20]);
+    const OTHER_VOTER: ChainAccount = ChainAccount::Eth([3; 20]);
+
+    fn set_min_validator_bond(principal: u128) -> Vec<u8> {
+        mock::Call::Cash(pallet_cash::Call::set_min_validator_bond(
+            CashPrincipalAmount(principal),
+        ))
+        .encode()
+    }
+
+    #[test]
+    fn test_payloads() {
+        assert_eq!(vote_payload(3, true), b"(Vote 3 For)".to_vec());
+        assert_eq!(vote_payload(3, false), b"(Vote 3 Against)".to_vec());
+        assert!(propose_payload(&vec![vec![1, 2]]).starts_with(b"(Propose 0x04"));
+    }
+
+    #[test]
+    fn test_has_passed() {
+        let proposal = |for_votes, against_votes| Proposal {
+            proposer: PROPOSER,
+            extrinsics: vec![],
+            start_at: 0,
+            end_at: 0,
+            for_votes: CashPrincipalAmount(for_votes),
+            against_votes: CashPrincipalAmount(against_votes),
+            comp_for_votes: 0,
+            comp_against_votes: 0,
+            state: ProposalState::Active,
+        };
+        assert!(has_passed(&proposal(10, 5), CashPrincipalAmount(10)));
+        assert!(!has_passed(&proposal(9, 5), CashPrincipalAmount(10)));
+        assert!(!has_passed(&proposal(10, 10), CashPrincipalAmount(10)));
+    }
+
+    #[test]
+    fn test_proposal_passes_and_is_timelocked() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(set_governance_params::<Test>(
+                100,
+                CashPrincipalAmount(500),
+                CashPrincipalAmount(10)
+            ));
+            let proposal_id = submit_proposal::<Test>(PROPOSER, vec![set_min_validator_bond(7)], 0);
+            assert_eq!(ActiveProposals::get(), vec![proposal_id]);
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(50);
+            record_vote::<Test>(
+                proposal_id,
+                VOTER,
+                Vote {
+                    support: true,
+                    weight: CashPrincipalAmount(600),
+                },
+            );
+            assert_eq!(
+                check_vote::<Test>(proposal_id, VOTER),
+                Err(GovernanceError::AlreadyVoted)
+            );
+
+            finalize_proposals::<Test>(99);
+            assert_eq!(ActiveProposals::get(), vec![proposal_id]);
+
+            finalize_proposals::<Test>(100);
+            assert_eq!(ActiveProposals::get(), Vec::<ProposalId>::new());
+            assert_eq!(
+                Proposals::get(proposal_id).map(|proposal| proposal.state),
+                Some(ProposalState::Queued(0))
+            );
+            assert_eq!(CashModule::min_validator_bond(), CashPrincipalAmount::ZERO);
+
+            execute_ready::<Test>(100 + TimelockModule::timelock_delay());
+            assert_eq!(CashModule::min_validator_bond(), CashPrincipalAmount(7));
+        });
+    }
+
+    #[test]
+    fn test_set_governance_params_requires_quorum_and_threshold() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(GovernanceModule::quorum(), DEFAULT_QUORUM);
+            assert_eq!(
+                GovernanceModule::proposal_threshold(),
+                DEFAULT_PROPOSAL_THRESHOLD
+            );
+            assert_eq!(
+                set_governance_params::<Test>(
+                    100,
+                    CashPrincipalAmount::ZERO,
+                    CashPrincipalAmount(10)
+                ),
+                Err(GovernanceError::InvalidQuorum)
+            );
+            assert_eq!(
+                set_governance_params::<Test>(
+                    100,
+                    CashPrincipalAmount(500),
+                    CashPrincipalAmount::ZERO
+                ),
+                Err(GovernanceError::InvalidProposalThreshold)
+            );
+        });
+    }
+
+    #[test]
+    fn test_votes_weighed_as_of_proposal() {
+        new_test_ext().execute_with(|| {
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(10);
+            CashPipeline::new()
+                .unbond_cash::<Test>(VOTER, CashPrincipalAmount(600))
+                .unwrap()
+                .commit::<Test>();
+
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(20);
+            let proposal_id =
+                submit_proposal::<Test>(PROPOSER, vec![set_min_validator_bond(7)], 20);
+
+            // Principal moved to another account after the proposal can't be voted again
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(30);
+            CashPipeline::new()
+                .transfer_cash::<Test>(VOTER, OTHER_VOTER, CashPrincipalAmount(600))
+                .unwrap()
+                .commit::<Test>();
+            assert_eq!(CashModule::cash_principal(VOTER), CashPrincipal(0));
+            assert_eq!(
+                check_vote::<Test>(proposal_id, VOTER),
+                Ok(CashPrincipalAmount(600))
+            );
+            assert_eq!(
+                check_vote::<Test>(proposal_id, OTHER_VOTER),
+                Err(GovernanceError::NoVotingWeight)
+            );
+        });
+    }
+
+    #[test]
+    fn test_recover_proposer_rejects_large_proposal() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                recover_proposer::<Test>(
+                    &vec![vec![0; MAX_PROPOSAL_SIZE]],
+                    ChainAccountSignature::Eth([0; 20], [0; 65]),
+                    0
+                ),
+                Err(GovernanceError::ProposalTooLarge)
+            );
+            assert_eq!(
+                set_governance_params::<Test>(
+                    CASH_PRINCIPAL_HISTORY + 1,
+                    CashPrincipalAmount(500),
+                    CashPrincipalAmount(10)
+                ),
+                Err(GovernanceError::InvalidVotingPeriod)
+            );
+        });
+    }
+
+    #[test]
+    fn test_proposal_defeated_without_quorum() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(set_governance_params::<Test>(
+                100,
+                CashPrincipalAmount(500),
+                CashPrincipalAmount(10)
+            ));
+            let proposal_id = submit_proposal::<Test>(PROPOSER, vec![set_min_validator_bond(7)], 0);
+            record_vote::<Test>(
+                proposal_id,
+                VOTER,
+                Vote {
+                    support: true,
+                    weight: CashPrincipalAmount(499),
+                },
+            );
+
+            finalize_proposals::<Test>(100);
+            assert_eq!(
+                Proposals::get(proposal_id).map(|proposal| proposal.state),
+                Some(ProposalState::Defeated)
+            );
+            assert_eq!(CashModule::min_validator_bond(), CashPrincipalAmount::ZERO);
+            assert_eq!(
+                cancel_proposal::<Test>(proposal_id),
+                Err(GovernanceError::ProposalNotActive)
+            );
+        });
+    }
+}
@@ -0,0 +1,228 @@
+#[macro_use]
+extern crate alloc;
+
+use crate::{
+    error::GovernanceError,
+    types::{CompSnapshot, CompVote, EthAddress, Nonce, Proposal, ProposalId, Timestamp, Vote},
+};
+use codec::Encode;
+use frame_support::{
+    decl_event, decl_module, decl_storage, dispatch,
+    weights::{DispatchClass, Pays, Weight},
+};
+use frame_system::{ensure_none, ensure_root};
+use our_std::{convert::TryInto, log, vec::Vec};
+use pallet_cash::{
+    chains::{ChainAccount, ChainAccountSignature},
+    core::get_recent_timestamp,
+    types::{AssetAmount, CashPrincipalAmount},
+};
+use pallet_timelock::types::TimelockId;
+use sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionSource, TransactionValidity,
+};
+
+pub mod error;
+pub mod governance;
+pub mod snapshots;
+pub mod types;
+pub mod validate_trx;
+pub mod weights;
+
+#[cfg(test)]
+mod tests;
+
+/// Default length of time a proposal is open for votes.
+pub const DEFAULT_VOTING_PERIOD: Timestamp = 3 * 24 * 60 * 60 * 1000;
+
+/// Default CASH principal which must vote for a proposal for it to pass, which must never be zero.
+pub const DEFAULT_QUORUM: CashPrincipalAmount = CashPrincipalAmount::from_nominal("400000");
+
+/// Default CASH principal an account must hold to submit a proposal, which must never be zero.
+pub const DEFAULT_PROPOSAL_THRESHOLD: CashPrincipalAmount =
+    CashPrincipalAmount::from_nominal("65000");
+
+/// Maximum size of the encoded calls of a proposal.
+pub const MAX_PROPOSAL_SIZE: usize = 64 * 1024;
+
+/// Maximum number of nodes in a proof of a COMP balance, enough for a snapshot of 2^32 holders.
+pub const MAX_COMP_PROOF_LENGTH: usize = 32;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+/// Proposals which pass are queued in the timelock, and votes are weighed by principal in the CASH pallet as of when proposed.
+pub trait Config: frame_system::Config + pallet_timelock::Config {
+    /// Because this pallet emits events, it depends on the runtime's definition of an event.
+    type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
+}
+
+decl_storage! {
+    trait Store for Module<T: Config> as Governance {
+        /// Mapping of governance proposals, by id.
+        pub Proposals get(fn proposal): map hasher(blake2_128_concat) ProposalId => Option<Proposal>;
+
+        /// The id of the next governance proposal.
+        pub NextProposalId get(fn next_proposal_id): ProposalId;
+
+        /// The ids of the proposals still open for votes, or waiting to be finished.
+        pub ActiveProposals get(fn active_proposals): Vec<ProposalId>;
+
+        /// Mapping of the vote cast by each account on each proposal.
+        pub Votes get(fn vote): double_map hasher(blake2_128_concat) ProposalId, hasher(blake2_128_concat) ChainAccount => Option<Vote>;
+
+        /// Length of time a proposal is open for votes.
+        pub VotingPeriod get(fn voting_period): Timestamp = DEFAULT_VOTING_PERIOD;
+
+        /// The CASH principal which must vote for a proposal for it to pass.
+        pub Quorum get(fn quorum): CashPrincipalAmount = DEFAULT_QUORUM;
+
+        /// The CASH principal an account must hold to submit a proposal.
+        pub ProposalThreshold get(fn proposal_threshold): CashPrincipalAmount = DEFAULT_PROPOSAL_THRESHOLD;
+
+        /// Snapshots of COMP balances on Ethereum which proposals may be voted on with, by proposal.
+        pub CompSnapshots get(fn comp_snapshot): map hasher(blake2_128_concat) ProposalId => Option<CompSnapshot>;
+
+        /// Mapping of the vote cast by each COMP holder on each proposal with a snapshot.
+        pub CompVotes get(fn comp_vote): double_map hasher(blake2_128_concat) ProposalId, hasher(blake2_128_concat) EthAddress => Option<CompVote>;
+
+        /// The COMP which must vote for a proposal with a snapshot for it to pass.
+        pub CompQuorum get(fn comp_quorum): AssetAmount;
+    }
+}
+
+/* ::EVENTS:: */
+
+decl_event!(
+    pub enum Event {
+        /// Failed to process a given extrinsic. [reason]
+        Failure(GovernanceError),
+
+        /// A proposal was submitted, open for votes until the given time. [proposal_id, proposer, end_at]
+        Proposed(ProposalId, ChainAccount, Timestamp),
+
+        /// A vote was cast on a proposal. [proposal_id, voter, support, weight]
+        VoteCast(ProposalId, ChainAccount, bool, CashPrincipalAmount),
+
+        /// A proposal passed, and its calls were queued in the timelock. [proposal_id, timelock_id]
+        ProposalQueued(ProposalId, TimelockId),
+
+        /// A proposal failed to pass. [proposal_id]
+        ProposalDefeated(ProposalId),
+
+        /// A proposal was canceled. [proposal_id]
+        ProposalCanceled(ProposalId),
+
+        /// The governance parameters were set. [voting_period, quorum, proposal_threshold]
+        SetGovernanceParams(Timestamp, CashPrincipalAmount, CashPrincipalAmount),
+
+        /// The snapshot of COMP balances a proposal may be voted on with was set. [proposal_id, snapshot]
+        SetCompSnapshot(ProposalId, CompSnapshot),
+
+        /// A COMP holder voted its balance in the snapshot on a proposal. [proposal_id, holder, support, balance]
+        CompVoteCast(ProposalId, EthAddress, bool, AssetAmount),
+
+        /// The COMP needed to reach quorum was set. [quorum]
+        SetCompQuorum(AssetAmount),
+    }
+);
+
+/* ::ERRORS:: */
+
+fn check_failure<T: Config>(res: Result<(), GovernanceError>) -> Result<(), GovernanceError> {
+    if let Err(err) = res {
+        <Module<T>>::deposit_event(Event::Failure(err));
+        log!("Governance Failure {:#?}", err);
+    }
+    res
+}
+
+/* ::MODULE:: */
+/* ::EXTRINSICS:: */
+
+decl_module! {
+    pub struct Module<T: Config> for enum Call where origin: T::Origin {
+        // Events must be initialized if they are used by the pallet.
+        fn deposit_event() = default;
+
+        /// Called by substrate on block initialization.
+        fn on_initialize() -> Weight {
+            match get_recent_timestamp::<T>() {
+                Ok(now) => governance::finalize_proposals::<T>(now),
+                Err(reason) => log!("Governance not initialized: {:?}", reason),
+            }
+            0
+        }
+
+        /// Submit a proposal to dispatch the given calls as root, signed by the proposer. [User] [Free]
+        #[weight = (
+            weights::propose::<T>(extrinsics.encoded_size().try_into().unwrap_or(u32::MAX)),
+            DispatchClass::Normal,
+            Pays::No
+        )]
+        pub fn propose(origin, extrinsics: Vec<Vec<u8>>, signature: ChainAccountSignature, nonce: Nonce) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(governance::propose::<T>(extrinsics, signature, nonce))?)
+        }
+
+        /// Cast a vote for or against a proposal, signed by the voter. [User] [Free]
+        #[weight = (weights::cast_vote::<T>(), DispatchClass::Normal, Pays::No)]
+        pub fn cast_vote(origin, proposal_id: ProposalId, support: bool, signature: ChainAccountSignature, nonce: Nonce) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(governance::cast_vote::<T>(proposal_id, support, signature, nonce))?)
+        }
+
+        /// Cast a vote for or against a proposal with a COMP balance proven against its snapshot, signed by the holder. [User] [Free]
+        #[weight = (
+            weights::cast_comp_vote::<T>(proof.len().try_into().unwrap_or(u32::MAX)),
+            DispatchClass::Normal,
+            Pays::No
+        )]
+        pub fn cast_comp_vote(origin, proposal_id: ProposalId, support: bool, balance: AssetAmount, proof: Vec<[u8; 32]>, signature: ChainAccountSignature, nonce: Nonce) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(snapshots::cast_comp_vote::<T>(proposal_id, support, balance, proof, signature, nonce))?)
+        }
+
+        /// Set the snapshot of COMP balances a proposal may be voted on with, posted by governance on Ethereum. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_comp_snapshot(origin, proposal_id: ProposalId, snapshot: CompSnapshot) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(snapshots::set_comp_snapshot::<T>(proposal_id, snapshot))?)
+        }
+
+        /// Set the COMP which must vote for a proposal with a snapshot for it to pass. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_comp_quorum(origin, quorum: AssetAmount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(snapshots::set_comp_quorum::<T>(quorum))?)
+        }
+
+        /// Cancel an active proposal. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn cancel_proposal(origin, proposal_id: ProposalId) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(governance::cancel_proposal::<T>(proposal_id))?)
+        }
+
+        /// Set the voting period, and the CASH principal needed to reach quorum and to propose. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_governance_params(origin, voting_period: Timestamp, quorum: CashPrincipalAmount, proposal_threshold: CashPrincipalAmount) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(governance::set_governance_params::<T>(voting_period, quorum, proposal_threshold))?)
+        }
+    }
+}
+
+impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
+    type Call = Call<T>;
+
+    /// Validate unsigned call to this module.
+    ///
+    /// Proposals and votes are signed by chain accounts rather than substrate accounts,
+    /// so they are checked here before being accepted into the pool.
+    fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+        validate_trx::check_validation_failure(
+            call,
+            validate_trx::validate_unsigned::<T>(source, call),
+        )
+        .unwrap_or(InvalidTransaction::Call.into())
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    error::GovernanceError,
+    governance::check_open,
+    types::{CompSnapshot, CompVote, EthAddress, Nonce, Proposal, ProposalId},
+    CompQuorum, CompSnapshots, CompVotes, Config, Event, Module, Proposals, MAX_COMP_PROOF_LENGTH,
+};
+use frame_support::storage::{StorageDoubleMap, StorageMap, StorageValue};
+use gateway_crypto::keccak;
+use our_std::vec::Vec;
+use pallet_cash::{
+    chains::{ChainAccount, ChainAccountSignature},
+    internal::{
+        exec_trx_request::prepend_nonce,
+        nonces::{check_nonce, use_nonce},
+    },
+    types::AssetAmount,
+};
+
+/// The payload a COMP holder signs with its Eth key, with a nonce prepended, to vote its balance.
+pub fn comp_vote_payload(proposal_id: ProposalId, support: bool, balance: AssetAmount) -> Vec<u8> {
+    let choice = if support { "For" } else { "Against" };
+    format!("(CompVote {} {} {})", proposal_id, choice, balance).into_bytes()
+}
+
+/// The leaf committing to the COMP balance of a holder in a snapshot.
+/// This is keccak256(abi.encodePacked(address holder, uint256 balance)).
+pub fn comp_balance_leaf(holder: &EthAddress, balance: AssetAmount) -> [u8; 32] {
+    let mut packed = [0u8; 52];
+    packed[..20].copy_from_slice(&holder[..]);
+    packed[36..].copy_from_slice(&balance.to_be_bytes());
+    keccak(&packed)
+}
+
+/// Whether a Merkle proof shows a leaf is in the tree with the given root.
+/// Each pair of nodes is hashed in sorted order, matching OpenZeppelin's MerkleProof.
+pub fn verify_merkle_proof(root: [u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let mut pair = [0u8; 64];
+        let (left, right) = if node <= *sibling {
+            (node, *sibling)
+        } else {
+            (*sibling, node)
+        };
+        pair[..32].copy_from_slice(&left);
+        pair[32..].copy_from_slice(&right);
+        keccak(&pair)
+    });
+    computed == root
+}
+
+/// Set the snapshot of COMP balances a proposal may be voted on with, once only.
+/// The snapshot is posted by governance on Ethereum, through a Starport.
+pub fn set_comp_snapshot<T: Config>(
+    proposal_id: ProposalId,
+    snapshot: CompSnapshot,
+) -> Result<(), GovernanceError> {
+    check_open::<T>(proposal_id)?;
+    if CompSnapshots::contains_key(proposal_id) {
+        return Err(GovernanceError::CompSnapshotAlreadySet);
+    }
+    CompSnapshots::insert(proposal_id, snapshot);
+    <Module<T>>::deposit_event(Event::SetCompSnapshot(proposal_id, snapshot));
+    Ok(())
+}
+
+/// Set the COMP which must vote for a proposal with a snapshot, for it to pass.
+pub fn set_comp_quorum<T: Config>(quorum: AssetAmount) -> Result<(), GovernanceError> {
+    CompQuorum::put(quorum);
+    <Module<T>>::deposit_event(Event::SetCompQuorum(quorum));
+    Ok(())
+}
+
+/// Recover the COMP holder signing a vote, checking its balance is in the proposal's snapshot.
+pub fn recover_comp_voter<T: Config>(
+    proposal_id: ProposalId,
+    support: bool,
+    balance: AssetAmount,
+    proof: &[[u8; 32]],
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<EthAddress, GovernanceError> {
+    if proof.len() > MAX_COMP_PROOF_LENGTH {
+        return Err(GovernanceError::CompProofTooLong);
+    }
+    let signer = signature.recover_account(&prepend_nonce(
+        &comp_vote_payload(proposal_id, support, balance),
+        nonce,
+    ))?;
+    let holder = match signer {
+        ChainAccount::Eth(holder) => holder,
+        _ => return Err(GovernanceError::NotEthAccount),
+    };
+    check_nonce::<T>(signer, nonce)?;
+    check_open::<T>(proposal_id)?;
+    let snapshot = CompSnapshots::get(proposal_id).ok_or(GovernanceError::NoCompSnapshot)?;
+    if CompVotes::contains_key(proposal_id, holder) {
+        return Err(GovernanceError::AlreadyVoted);
+    }
+    if balance == 0 {
+        return Err(GovernanceError::NoVotingWeight);
+    }
+    if !verify_merkle_proof(snapshot.root, comp_balance_leaf(&holder, balance), proof) {
+        return Err(GovernanceError::InvalidCompProof);
+    }
+    Ok(holder)
+}
+
+/// Cast a vote for or against a proposal with a COMP balance proven against its snapshot.
+pub fn cast_comp_vote<T: Config>(
+    proposal_id: ProposalId,
+    support: bool,
+    balance: AssetAmount,
+    proof: Vec<[u8; 32]>,
+    signature: ChainAccountSignature,
+    nonce: Nonce,
+) -> Result<(), GovernanceError> {
+    let holder = recover_comp_voter::<T>(proposal_id, support, balance, &proof, signature, nonce)?;
+
+    // * BEGIN STORAGE ALL CHECKS AND FAILURES MUST HAPPEN ABOVE * //
+
+    use_nonce::<T>(ChainAccount::Eth(holder), nonce);
+    record_comp_vote::<T>(proposal_id, holder, CompVote { support, balance });
+    Ok(())
+}
+
+/// Record a COMP vote on a proposal, and count its balance.
+pub fn record_comp_vote<T: Config>(proposal_id: ProposalId, holder: EthAddress, vote: CompVote) {
+    Proposals::mutate(proposal_id, |proposal| {
+        if let Some(proposal) = proposal {
+            let votes = if vote.support {
+                &mut proposal.comp_for_votes
+            } else {
+                &mut proposal.comp_against_votes
+            };
+            *votes = votes.saturating_add(vote.balance);
+        }
+    });
+    CompVotes::insert(proposal_id, holder, vote);
+    <Module<T>>::deposit_event(Event::CompVoteCast(
+        proposal_id,
+        holder,
+        vote.support,
+        vote.balance,
+    ));
+}
+
+/// Whether the COMP vote on a proposal passed, with more for than against and at least a quorum for.
+pub fn has_comp_passed(proposal: &Proposal, quorum: AssetAmount) -> bool {
+    proposal.comp_for_votes > proposal.comp_against_votes && proposal.comp_for_votes >= quorum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{governance::submit_proposal, tests::*};
+
+    const HOLDERS: [(EthAddress, AssetAmount); 3] =
+        [([1; 20], 100), ([2; 20], 200), ([3; 20], 300)];
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let mut pair = [0u8; 64];
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        pair[..32].copy_from_slice(&left);
+        pair[32..].copy_from_slice(&right);
+        keccak(&pair)
+    }
+
+    fn leaves() -> Vec<[u8; 32]> {
+        HOLDERS
+            .iter()
+            .map(|(holder, balance)| comp_balance_leaf(holder, *balance))
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_merkle_proof() {
+        let leaves = leaves();
+        let left = hash_pair(leaves[0], leaves[1]);
+        let root = hash_pair(left, leaves[2]);
+
+        assert!(verify_merkle_proof(
+            root,
+            leaves[0],
+            &[leaves[1], leaves[2]]
+        ));
+        assert!(verify_merkle_proof(
+            root,
+            leaves[1],
+            &[leaves[0], leaves[2]]
+        ));
+        assert!(verify_merkle_proof(root, leaves[2], &[left]));
+        assert!(!verify_merkle_proof(root, leaves[2], &[leaves[0]]));
+        assert!(!verify_merkle_proof(
+            root,
+            comp_balance_leaf(&HOLDERS[2].0, 301),
+            &[left]
+        ));
+    }
+
+    #[test]
+    fn test_comp_votes_counted_once_snapshot_set() {
+        new_test_ext().execute_with(|| {
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(1);
+            let proposal_id = submit_proposal::<Test>(ChainAccount::Eth([9; 20]), vec![vec![]], 1);
+            let snapshot = CompSnapshot {
+                root: [7; 32],
+                block_number: 12,
+            };
+
+            assert_ok!(set_comp_snapshot::<Test>(proposal_id, snapshot));
+            assert_eq!(
+                set_comp_snapshot::<Test>(proposal_id, snapshot),
+                Err(GovernanceError::CompSnapshotAlreadySet)
+            );
+
+            record_comp_vote::<Test>(
+                proposal_id,
+                HOLDERS[0].0,
+                CompVote {
+                    support: true,
+                    balance: 100,
+                },
+            );
+            record_comp_vote::<Test>(
+                proposal_id,
+                HOLDERS[1].0,
+                CompVote {
+                    support: false,
+                    balance: 50,
+                },
+            );
+            let proposal = Proposals::get(proposal_id).unwrap();
+            assert!(has_comp_passed(&proposal, 100));
+            assert!(!has_comp_passed(&proposal, 101));
+        });
+    }
+}
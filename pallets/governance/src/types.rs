@@ -0,0 +1,63 @@
+use codec::{Decode, Encode};
+use our_std::{vec::Vec, RuntimeDebug};
+use pallet_cash::{
+    chains::ChainAccount,
+    types::{AssetAmount, CashPrincipalAmount},
+};
+use pallet_timelock::types::TimelockId;
+
+use types_derive::{type_alias, Types};
+
+pub use pallet_cash::types::{Nonce, Timestamp};
+
+/// Type for identifying a governance proposal.
+#[type_alias]
+pub type ProposalId = u64;
+
+/// The state of a governance proposal.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum ProposalState {
+    Active,
+    Queued(TimelockId),
+    Defeated,
+    Canceled,
+}
+
+/// A governance proposal to dispatch a list of SCALE-encoded calls as root, once passed.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct Proposal {
+    pub proposer: ChainAccount,
+    pub extrinsics: Vec<Vec<u8>>,
+    pub start_at: Timestamp,
+    pub end_at: Timestamp,
+    pub for_votes: CashPrincipalAmount,
+    pub against_votes: CashPrincipalAmount,
+    pub comp_for_votes: AssetAmount,
+    pub comp_against_votes: AssetAmount,
+    pub state: ProposalState,
+}
+
+/// A vote cast on a governance proposal, weighed by the CASH principal of the voter when it was proposed.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct Vote {
+    pub support: bool,
+    pub weight: CashPrincipalAmount,
+}
+
+/// Type for the Ethereum address of a COMP holder.
+#[type_alias("Governance__")]
+pub type EthAddress = [u8; 20];
+
+/// A snapshot of COMP balances on Ethereum, committed to by the root of a Merkle tree of balances.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct CompSnapshot {
+    pub root: [u8; 32],
+    pub block_number: u64,
+}
+
+/// A vote cast on a governance proposal, weighed by the COMP balance of the holder in its snapshot.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct CompVote {
+    pub support: bool,
+    pub balance: AssetAmount,
+}
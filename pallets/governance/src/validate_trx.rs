@@ -0,0 +1,74 @@
+use crate::{error::GovernanceError, governance, snapshots, Call, Config};
+use codec::{Decode, Encode};
+use our_std::{log, RuntimeDebug};
+use sp_runtime::transaction_validity::{TransactionSource, TransactionValidity, ValidTransaction};
+
+const UNSIGNED_TXS_PRIORITY: u64 = 100;
+const UNSIGNED_TXS_LONGEVITY: u64 = 32;
+
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug, Clone, Copy)]
+pub enum ValidationError {
+    InvalidProposal(GovernanceError),
+    InvalidVote(GovernanceError),
+    InvalidCompVote(GovernanceError),
+    InvalidCall,
+}
+
+pub fn check_validation_failure<T: Config>(
+    call: &Call<T>,
+    res: Result<TransactionValidity, ValidationError>,
+) -> Result<TransactionValidity, ValidationError> {
+    if let Err(err) = res {
+        log!("validate_unsigned: call = {:#?}, error = {:#?}", call, err);
+    }
+    res
+}
+
+pub fn validate_unsigned<T: Config>(
+    _source: TransactionSource,
+    call: &Call<T>,
+) -> Result<TransactionValidity, ValidationError> {
+    match call {
+        Call::propose(extrinsics, signature, nonce) => {
+            match governance::recover_proposer::<T>(extrinsics, *signature, *nonce) {
+                Ok(proposer) => Ok(ValidTransaction::with_tag_prefix("Gateway::propose")
+                    .priority(UNSIGNED_TXS_PRIORITY)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides((proposer, nonce))
+                    .propagate(true)
+                    .build()),
+                Err(err) => Err(ValidationError::InvalidProposal(err)),
+            }
+        }
+        Call::cast_vote(proposal_id, support, signature, nonce) => {
+            match governance::recover_voter::<T>(*proposal_id, *support, *signature, *nonce) {
+                Ok((voter, _weight)) => Ok(ValidTransaction::with_tag_prefix("Gateway::cast_vote")
+                    .priority(UNSIGNED_TXS_PRIORITY)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides((voter, nonce))
+                    .propagate(true)
+                    .build()),
+                Err(err) => Err(ValidationError::InvalidVote(err)),
+            }
+        }
+        Call::cast_comp_vote(proposal_id, support, balance, proof, signature, nonce) => {
+            match snapshots::recover_comp_voter::<T>(
+                *proposal_id,
+                *support,
+                *balance,
+                proof,
+                *signature,
+                *nonce,
+            ) {
+                Ok(holder) => Ok(ValidTransaction::with_tag_prefix("Gateway::cast_comp_vote")
+                    .priority(UNSIGNED_TXS_PRIORITY)
+                    .longevity(UNSIGNED_TXS_LONGEVITY)
+                    .and_provides((holder, nonce))
+                    .propagate(true)
+                    .build()),
+                Err(err) => Err(ValidationError::InvalidCompVote(err)),
+            }
+        }
+        _ => Err(ValidationError::InvalidCall),
+    }
+}
@@ -0,0 +1,24 @@
+use frame_support::{traits::Get, weights::Weight};
+
+/// Weight of submitting a proposal, which grows with the size of its encoded calls.
+pub fn propose<T: frame_system::Config>(size: u32) -> Weight {
+    (150_000_000 as Weight)
+        .saturating_add((2_000 as Weight).saturating_mul(size as Weight))
+        .saturating_add(T::DbWeight::get().reads(5 as Weight))
+        .saturating_add(T::DbWeight::get().writes(4 as Weight))
+}
+
+/// Weight of casting a vote, which looks up the principal of the voter when the proposal was made.
+pub fn cast_vote<T: frame_system::Config>() -> Weight {
+    (150_000_000 as Weight)
+        .saturating_add(T::DbWeight::get().reads(6 as Weight))
+        .saturating_add(T::DbWeight::get().writes(3 as Weight))
+}
+
+/// Weight of casting a COMP vote, which hashes each node of its proof.
+pub fn cast_comp_vote<T: frame_system::Config>(proof_len: u32) -> Weight {
+    (150_000_000 as Weight)
+        .saturating_add((5_000_000 as Weight).saturating_mul(proof_len as Weight))
+        .saturating_add(T::DbWeight::get().reads(5 as Weight))
+        .saturating_add(T::DbWeight::get().writes(3 as Weight))
+}
@@ -26,6 +26,15 @@ pub enum OracleError {
     NoPrice,
     InvalidValue,
     TimestampTooHigh,
+    ChainlinkFeedNotSet,
+    FallbackNotNeeded,
+    PriceFeedMismatch,
+    UniswapFeedNotSet,
+    InvalidWeight,
+    InvalidDerivedTicker,
+    PriceFrozen,
+    UnknownValidator,
+    KeyNotFound,
 }
 
 impl From<CryptoError> for OracleError {
@@ -55,6 +64,15 @@ impl From<OracleError> for frame_support::dispatch::DispatchError {
             OracleError::NoPrice => (15, 0, "NoPrice"),
             OracleError::InvalidValue => (16, 0, "InvalidValue"),
             OracleError::TimestampTooHigh => (17, 0, "TimestampTooHigh"),
+            OracleError::ChainlinkFeedNotSet => (18, 0, "ChainlinkFeedNotSet"),
+            OracleError::FallbackNotNeeded => (19, 0, "FallbackNotNeeded"),
+            OracleError::PriceFeedMismatch => (20, 0, "PriceFeedMismatch"),
+            OracleError::UniswapFeedNotSet => (21, 0, "UniswapFeedNotSet"),
+            OracleError::InvalidWeight => (22, 0, "InvalidWeight"),
+            OracleError::InvalidDerivedTicker => (23, 0, "InvalidDerivedTicker"),
+            OracleError::PriceFrozen => (24, 0, "PriceFrozen"),
+            OracleError::UnknownValidator => (25, 0, "UnknownValidator"),
+            OracleError::KeyNotFound => (26, 0, "KeyNotFound"),
         };
         frame_support::dispatch::DispatchError::Module {
             index,
@@ -1,4 +1,4 @@
-use crate::{oracle, validate_trx, Call, Config, Module};
+use crate::{error::OracleError, oracle, validate_trx, Call, Config, Module};
 use codec::{Decode, Encode};
 use frame_support::inherent::ProvideInherent;
 use our_std::{log, RuntimeDebug};
@@ -107,6 +107,14 @@ impl<T: Config> ProvideInherent for Module<T> {
 
         match inherent_data_res {
             Ok(InherentPriceData(pairs, _ts)) => {
+                // Leave out prices which are frozen, rather than holding back all the others
+                let pairs: Vec<_> = pairs
+                    .into_iter()
+                    .filter(|(payload, _)| {
+                        oracle::get_and_check_parsed_price::<T>(&payload)
+                            != Err(OracleError::PriceFrozen)
+                    })
+                    .collect();
                 if pairs
                     .iter()
                     .all(|(payload, _)| oracle::get_and_check_parsed_price::<T>(&payload).is_ok())
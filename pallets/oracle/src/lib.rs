@@ -2,8 +2,11 @@
 
 use crate::{
     error::OracleError,
-    ticker::{Ticker, CASH_TICKER, USD_TICKER},
-    types::{AssetPrice, Price, ReporterSet, Timestamp},
+    ticker::{DerivedTicker, Ticker, CASH_TICKER, USD_TICKER},
+    types::{
+        AssetPrice, ChainlinkFeed, PendingPrice, Price, PriceDeviationGuard, PriceSource,
+        ReporterSet, Timestamp, UniswapTwapFeed, ValidatorAddress, ValidatorSignature,
+    },
 };
 use frame_support::{
     decl_event, decl_module, decl_storage, dispatch,
@@ -11,12 +14,13 @@ use frame_support::{
     weights::{DispatchClass, GetDispatchInfo, Pays},
     Parameter,
 };
-use frame_system::{ensure_none, offchain::CreateSignedTransaction};
-use our_std::log;
+use frame_system::{ensure_none, ensure_root, offchain::CreateSignedTransaction};
+use our_std::{log, log::targets::ORACLE, vec::Vec};
 use pallet_timestamp;
 use sp_runtime::transaction_validity::{
     InvalidTransaction, TransactionSource, TransactionValidity,
 };
+use timestamp::GetConvertedTimestamp;
 
 pub mod error;
 pub mod inherent;
@@ -32,6 +36,21 @@ mod tests;
 /// Number of blocks between HTTP requests from offchain workers to open oracle price feed.
 pub const ORACLE_POLL_INTERVAL_BLOCKS: u32 = 10;
 
+/// Number of accepted prices kept for each ticker in its price history.
+pub const PRICE_HISTORY_LENGTH: u32 = 128;
+
+/// Age after which an open price feed price is stale, and Chainlink prices may be posted instead.
+pub const CHAINLINK_FALLBACK_THRESHOLD_MS: Timestamp = 60 * 60 * 1000;
+
+/// Age after which a DEX price, or a validator's report of one, no longer counts towards prices.
+pub const DEX_PRICE_MAX_AGE_MS: Timestamp = 15 * 60 * 1000;
+
+/// The validators, which are kept by another pallet, and must attest to prices read from Ethereum.
+pub trait ValidatorSet {
+    /// The Ethereum addresses the current validators sign with.
+    fn eth_addresses() -> Vec<ValidatorAddress>;
+}
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Config:
     frame_system::Config + CreateSignedTransaction<Call<Self>> + pallet_timestamp::Config
@@ -48,6 +67,9 @@ pub trait Config:
     type GetConvertedTimestamp: timestamp::GetConvertedTimestamp<
         <Self as pallet_timestamp::Config>::Moment,
     >;
+
+    /// The validators, a super majority of which must sign any price read from Ethereum.
+    type Validators: ValidatorSet;
 }
 
 decl_storage! {
@@ -58,6 +80,52 @@ decl_storage! {
         /// Mapping of assets to the last time their price was updated.
         pub PriceTimes get(fn price_time): map hasher(blake2_128_concat) Ticker => Option<Timestamp>;
 
+        /// Mapping of assets to the source their latest price was posted from.
+        pub PriceSources get(fn price_source): map hasher(blake2_128_concat) Ticker => Option<PriceSource>;
+
+        /// Chainlink aggregators to fall back on for each price ticker, when the open price feed is stale.
+        pub ChainlinkFeeds get(fn chainlink_feed): map hasher(blake2_128_concat) Ticker => Option<ChainlinkFeed>;
+
+        /// Validators which signed each Chainlink price for a ticker, until a super majority have.
+        pub ChainlinkTallies get(fn chainlink_tally): double_map hasher(blake2_128_concat) Ticker, hasher(blake2_128_concat) (AssetPrice, Timestamp) => Vec<ValidatorAddress>;
+
+        /// Whether all price updates are paused, pinning every price at its last value.
+        pub OraclePaused get(fn oracle_paused): bool;
+
+        /// Tickers whose prices are frozen at their last value.
+        pub FrozenTickers get(fn frozen_ticker): map hasher(blake2_128_concat) Ticker => bool;
+
+        /// Tickers whose prices are derived from the prices of other tickers.
+        pub DerivedTickers get(fn derived_ticker): map hasher(blake2_128_concat) Ticker => Option<DerivedTicker>;
+
+        /// Uniswap V3 pools to take time-weighted average prices from for each price ticker.
+        pub UniswapFeeds get(fn uniswap_feed): map hasher(blake2_128_concat) Ticker => Option<UniswapTwapFeed>;
+
+        /// Latest DEX time-weighted average price reported by each validator for each price ticker, and when.
+        pub DexReports get(fn dex_report): double_map hasher(blake2_128_concat) Ticker, hasher(blake2_128_concat) ValidatorAddress => Option<(AssetPrice, Timestamp)>;
+
+        /// Mapping of latest DEX time-weighted average prices for each price ticker.
+        /// This is the median of the validators' fresh reports, once a super majority of them have reported.
+        pub DexPrices get(fn dex_price): map hasher(blake2_128_concat) Ticker => Option<AssetPrice>;
+
+        /// Mapping of assets to the time of the oldest report their DEX price was taken from.
+        pub DexPriceTimes get(fn dex_price_time): map hasher(blake2_128_concat) Ticker => Option<Timestamp>;
+
+        /// Weight of the DEX price in the price of each ticker, in basis points.
+        pub DexWeights get(fn dex_weight): map hasher(blake2_128_concat) Ticker => u32;
+
+        /// The last accepted prices for each price ticker, oldest first, up to PRICE_HISTORY_LENGTH of them.
+        pub PriceHistory get(fn price_history): map hasher(blake2_128_concat) Ticker => Vec<(Timestamp, AssetPrice)>;
+
+        /// Time of the last confirmed price move for each price ticker, before which prices no longer count towards its TWAP.
+        pub PriceMoveTimes get(fn price_move_time): map hasher(blake2_128_concat) Ticker => Option<Timestamp>;
+
+        /// Deviating open price feed prices for each price ticker, held until confirmed.
+        pub PendingPrices get(fn pending_price): map hasher(blake2_128_concat) Ticker => Option<PendingPrice>;
+
+        /// The guard against open price feed updates which deviate too far from the recent average, if any.
+        pub DeviationGuard get(fn deviation_guard): Option<PriceDeviationGuard>;
+
         /// Ethereum addresses of open oracle price reporters.
         pub PriceReporters get(fn reporters): ReporterSet; // XXX if > 1, how are we combining?
     }
@@ -75,6 +143,36 @@ decl_event!(
     pub enum Event {
         /// Failed to process a given extrinsic. [reason]
         Failure(OracleError),
+
+        /// The Chainlink aggregator to fall back on for a ticker was set. [ticker, feed]
+        SetChainlinkFeed(Ticker, Option<ChainlinkFeed>),
+
+        /// A fallback price was posted from a Chainlink aggregator. [ticker, price]
+        PostedChainlinkPrice(Ticker, AssetPrice),
+
+        /// Price updates were paused or resumed. [paused]
+        SetOraclePaused(bool),
+
+        /// Price updates for a ticker were frozen or unfrozen. [ticker, frozen]
+        SetTickerFrozen(Ticker, bool),
+
+        /// The definition of a derived ticker was set. [ticker, derived]
+        SetDerivedTicker(Ticker, Option<DerivedTicker>),
+
+        /// The Uniswap V3 pool to take a DEX price from for a ticker was set. [ticker, feed]
+        SetUniswapFeed(Ticker, Option<UniswapTwapFeed>),
+
+        /// The weight of the DEX price in the price of a ticker was set. [ticker, weight_bps]
+        SetDexWeight(Ticker, u32),
+
+        /// A DEX time-weighted average price was posted. [ticker, price]
+        PostedDexPrice(Ticker, AssetPrice),
+
+        /// The guard against deviating price updates was set. [guard]
+        SetDeviationGuard(Option<PriceDeviationGuard>),
+
+        /// A deviating price was held pending further confirmation. [ticker, price, confirmations]
+        HeldPrice(Ticker, AssetPrice, u32),
     }
 );
 
@@ -83,7 +181,7 @@ decl_event!(
 fn check_failure<T: Config>(res: Result<(), OracleError>) -> Result<(), OracleError> {
     if let Err(err) = res {
         <Module<T>>::deposit_event(Event::Failure(err));
-        log!("Oracle Failure {:#?}", err);
+        log!(target: ORACLE, "Oracle Failure {:#?}", err);
     }
     res
 }
@@ -110,18 +208,103 @@ decl_module! {
         #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
         pub fn post_prices(origin, pairs: Vec<(Vec<u8>, Vec<u8>)>) -> dispatch::DispatchResult {
             ensure_none(origin)?;
-            Ok(pairs.into_iter().fold(Ok(()) as Result<(), OracleError>, |res, (payload, signature)| {
-                match res {
-                    Err(err) => Err(err),
-                    Ok(_) => check_failure::<T>(oracle::post_price::<T>(payload, signature)),
-                }
-            })?)
+            Ok(check_failure::<T>(oracle::post_prices::<T>(pairs))?)
+        }
+
+        /// Sign fallback prices from Chainlink aggregators, for tickers whose open price feed price is stale.
+        /// Each price is set once a super majority of validators have signed it. [Internal] [Free]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn post_chainlink_prices(origin, prices: Vec<(Ticker, AssetPrice, Timestamp)>, signature: ValidatorSignature) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(oracle::post_chainlink_prices::<T>(prices, signature))?)
+        }
+
+        /// Set or remove the Chainlink aggregator to fall back on for a ticker. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_chainlink_feed(origin, ticker: Ticker, feed: Option<ChainlinkFeed>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            match feed {
+                Some(feed) => ChainlinkFeeds::insert(ticker, feed),
+                None => ChainlinkFeeds::remove(ticker),
+            }
+            Self::deposit_event(Event::SetChainlinkFeed(ticker, feed));
+            Ok(())
+        }
+
+        /// Report time-weighted average prices read from Uniswap V3 pools, as a validator.
+        /// The DEX price of each ticker is set once a super majority of validators have reported it. [Internal] [Free]
+        #[weight = (1, DispatchClass::Operational, Pays::No)] // XXX
+        pub fn post_dex_prices(origin, prices: Vec<(Ticker, AssetPrice, Timestamp)>, signature: ValidatorSignature) -> dispatch::DispatchResult {
+            ensure_none(origin)?;
+            Ok(check_failure::<T>(oracle::post_dex_prices::<T>(prices, signature))?)
+        }
+
+        /// Set or remove the Uniswap V3 pool to take a DEX price from for a ticker. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_uniswap_feed(origin, ticker: Ticker, feed: Option<UniswapTwapFeed>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            match feed {
+                Some(feed) => UniswapFeeds::insert(ticker, feed),
+                None => UniswapFeeds::remove(ticker),
+            }
+            Self::deposit_event(Event::SetUniswapFeed(ticker, feed));
+            Ok(())
+        }
+
+        /// Pause or resume all price updates. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_oracle_paused(origin, paused: bool) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            OraclePaused::put(paused);
+            Self::deposit_event(Event::SetOraclePaused(paused));
+            Ok(())
+        }
+
+        /// Freeze or unfreeze price updates for a single ticker. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_ticker_frozen(origin, ticker: Ticker, frozen: bool) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            if frozen {
+                FrozenTickers::insert(ticker, true);
+            } else {
+                FrozenTickers::remove(ticker);
+            }
+            Self::deposit_event(Event::SetTickerFrozen(ticker, frozen));
+            Ok(())
+        }
+
+        /// Set or remove the definition of a ticker derived from the prices of other tickers. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_derived_ticker(origin, ticker: Ticker, derived: Option<DerivedTicker>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(set_derived_ticker_internal::<T>(ticker, derived))?)
+        }
+
+        /// Set the weight of the DEX price in the price of a ticker, in basis points. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_dex_weight(origin, ticker: Ticker, weight_bps: u32) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            if weight_bps > 10_000 {
+                Err(OracleError::InvalidWeight)?;
+            }
+            DexWeights::insert(ticker, weight_bps);
+            Self::deposit_event(Event::SetDexWeight(ticker, weight_bps));
+            Ok(())
+        }
+
+        /// Set or remove the guard against open price feed updates which deviate too far from the recent average. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_deviation_guard(origin, guard: Option<PriceDeviationGuard>) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            DeviationGuard::set(guard);
+            Self::deposit_event(Event::SetDeviationGuard(guard));
+            Ok(())
         }
 
         /// Offchain Worker entry point.
         fn offchain_worker(block_number: T::BlockNumber) {
             if let Err(e) = oracle::process_prices::<T>(block_number) {
-                log!("offchain_worker error during open price feed processing: {:?}", e);
+                log!(target: ORACLE, "offchain_worker error during open price feed processing: {:?}", e);
             }
         }
     }
@@ -132,7 +315,64 @@ pub fn get_price_by_ticker<T: Config>(ticker: Ticker) -> Option<Price> {
     match ticker {
         t if t == USD_TICKER => Some(Price::from_nominal(USD_TICKER, "1.0")),
         t if t == CASH_TICKER => Some(Price::from_nominal(CASH_TICKER, "1.0")),
-        _ => Prices::get(ticker).map(|price| Price::new(ticker, price)),
+        _ => match DerivedTickers::get(ticker) {
+            Some(derived) => get_derived_price::<T>(ticker, derived),
+            None => Prices::get(ticker)
+                .map(|price| Price::new(ticker, weigh_dex_price::<T>(ticker, price))),
+        },
+    }
+}
+
+/// Evaluate the price of a derived ticker from the current prices of its components.
+fn get_derived_price<T: Config>(ticker: Ticker, derived: DerivedTicker) -> Option<Price> {
+    let numerator = get_price_by_ticker::<T>(derived.numerator)?.value;
+    let denominator = get_price_by_ticker::<T>(derived.denominator)?.value;
+    let base = get_price_by_ticker::<T>(derived.base)?.value;
+    DerivedTicker::derive(numerator, denominator, base).map(|price| Price::new(ticker, price))
+}
+
+/// Set the definition of a derived ticker, which may only be derived from tickers that are not
+///  derived themselves, so that evaluating a price never recurses more than once.
+fn set_derived_ticker_internal<T: Config>(
+    ticker: Ticker,
+    derived: Option<DerivedTicker>,
+) -> Result<(), OracleError> {
+    match derived {
+        Some(derived) => {
+            let components = derived.components();
+            if ticker == USD_TICKER
+                || ticker == CASH_TICKER
+                || components.contains(&ticker)
+                || components.iter().any(|c| DerivedTickers::contains_key(c))
+                || DerivedTickers::iter_values().any(|d| d.components().contains(&ticker))
+            {
+                Err(OracleError::InvalidDerivedTicker)?;
+            }
+            DerivedTickers::insert(ticker, derived);
+        }
+        None => DerivedTickers::remove(ticker),
+    }
+    <Module<T>>::deposit_event(Event::SetDerivedTicker(ticker, derived));
+    Ok(())
+}
+
+/// Blend the open price feed price of a ticker with its DEX price, by the governed weight.
+/// A DEX price which is no longer fresh is not blended in at all.
+fn weigh_dex_price<T: Config>(ticker: Ticker, price: AssetPrice) -> AssetPrice {
+    let weight = DexWeights::get(ticker) as AssetPrice;
+    let now = T::GetConvertedTimestamp::get_recent_timestamp().ok();
+    let fresh = match (DexPriceTimes::get(ticker), now) {
+        (Some(time), Some(now)) => time.saturating_add(DEX_PRICE_MAX_AGE_MS) > now,
+        _ => false,
+    };
+    match DexPrices::get(ticker) {
+        Some(dex_price) if weight > 0 && fresh => {
+            let weighted = price
+                .saturating_mul(10_000 - weight)
+                .saturating_add(dex_price.saturating_mul(weight));
+            weighted / 10_000
+        }
+        _ => price,
     }
 }
 
@@ -155,6 +395,11 @@ impl<T: Config> Module<T> {
             .ok_or(OracleError::NoPrice)?
             .value)
     }
+
+    /// Get up to the given number of the most recent accepted prices for the given asset, newest first.
+    pub fn get_price_history(ticker: Ticker, count: u32) -> Vec<(Timestamp, AssetPrice)> {
+        oracle::get_price_history(ticker, count)
+    }
 }
 
 impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
@@ -1,4 +1,6 @@
-use frame_support::storage::{StorageMap, StorageValue};
+use codec::Encode;
+use frame_support::storage::{IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue};
+use frame_system::offchain::SubmitTransaction;
 use serde::Deserialize;
 use sp_runtime::offchain::{
     http,
@@ -10,11 +12,24 @@ use sp_runtime::offchain::{
 use crate::{
     error::OracleError,
     ticker::Ticker,
-    types::{AssetPrice, Timestamp},
+    types::{
+        AssetPrice, PendingPrice, Price, PriceDeviationGuard, PriceSource, Reporter, Timestamp,
+        UniswapTwapFeed, ValidatorAddress, ValidatorSignature,
+    },
 };
-use crate::{Config, PriceReporters, PriceTimes, Prices, ORACLE_POLL_INTERVAL_BLOCKS};
+use crate::{
+    Call, ChainlinkFeeds, ChainlinkTallies, Config, DeviationGuard, DexPriceTimes, DexPrices,
+    DexReports, Event, FrozenTickers, Module, OraclePaused, PendingPrices, PriceHistory,
+    PriceMoveTimes, PriceReporters, PriceSources, PriceTimes, Prices, UniswapFeeds, ValidatorSet,
+    CHAINLINK_FALLBACK_THRESHOLD_MS, DEX_PRICE_MAX_AGE_MS, ORACLE_POLL_INTERVAL_BLOCKS,
+    PRICE_HISTORY_LENGTH,
+};
+use ethereum_types::U512;
 use our_std::convert::TryInto;
-use our_std::{collections::btree_map::BTreeMap, str::FromStr, vec::Vec, RuntimeDebug};
+use our_std::{
+    collections::btree_map::BTreeMap, log, log::targets::ORACLE, str::FromStr, vec::Vec,
+    RuntimeDebug,
+};
 use timestamp::GetConvertedTimestamp;
 
 pub const MAX_PRICE_FUTURE_MS: Timestamp = 100000u64; //100 seconds
@@ -129,17 +144,27 @@ fn parse_open_price_feed_api_response(
     serde_json::from_slice(response).map_err(|_| OracleError::JsonParseError)
 }
 
-/// Make the open price feed API request to an unauthenticated http endpoint
+/// Make the open price feed API request to an http endpoint, with any headers configured for it
 pub fn open_price_feed_request(url: &str) -> Result<OpenPriceFeedApiResponse, OracleError> {
-    let response = open_price_feed_request_unchecked(url)?;
+    let start = sp_io::offchain::timestamp();
+    let response = open_price_feed_request_unchecked(url);
+    let elapsed = sp_io::offchain::timestamp().diff(&start);
+    runtime_interfaces::metrics_interface::observe_ms("opf_request", elapsed.millis());
 
-    Ok(response)
+    response
 }
 
-/// Make the open price feed HTTP API request to an unauthenticated endpoint using HTTP GET.
+/// Make the open price feed HTTP API request to an endpoint using HTTP GET.
 fn open_price_feed_request_unchecked(url: &str) -> Result<OpenPriceFeedApiResponse, OracleError> {
-    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(ORACLE_FETCH_DEADLINE));
-    let request = http::Request::get(url);
+    let (timeout, headers) = runtime_interfaces::validator_config_interface::get_http_options(url);
+    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(
+        timeout.unwrap_or(ORACLE_FETCH_DEADLINE),
+    ));
+    let request = headers
+        .iter()
+        .fold(http::Request::get(url), |request, (name, value)| {
+            request.add_header(name, value)
+        });
     let pending = request
         .deadline(deadline)
         .send()
@@ -187,16 +212,68 @@ pub fn check_signature<T: Config>(
     payload: &Vec<u8>,
     signature: &Vec<u8>,
 ) -> Result<bool, OracleError> {
-    // check signature
+    Ok(PriceReporters::get().contains(recover_reporter(payload, signature)?))
+}
+
+/// Recover the address of the reporter which signed an open price feed message.
+pub fn recover_reporter(payload: &Vec<u8>, signature: &Vec<u8>) -> Result<Reporter, OracleError> {
     let parsed_sig: [u8; 65] = gateway_crypto::eth_signature_from_bytes(&signature)?;
 
     // note that this is actually a double-hash situation but that is expected behavior
     // the hashed message is hashed again in the eth convention inside eth_recover
     let hashed = gateway_crypto::keccak(&payload);
-    let recovered =
-        runtime_interfaces::keyring_interface::eth_recover(hashed.into(), parsed_sig, true)?;
+    Ok(runtime_interfaces::keyring_interface::eth_recover(
+        hashed.into(),
+        parsed_sig,
+        true,
+    )?)
+}
+
+/// Recover the reporters which signed several open price feed messages, all at once.
+/// Gives a result for each message, in order.
+pub fn recover_reporters(pairs: &[(Vec<u8>, Vec<u8>)]) -> Vec<Result<Reporter, OracleError>> {
+    let parsed: Vec<Result<[u8; 65], OracleError>> = pairs
+        .iter()
+        .map(|(_, signature)| Ok(gateway_crypto::eth_signature_from_bytes(&signature)?))
+        .collect();
+    let (messages, signatures): (Vec<Vec<u8>>, Vec<[u8; 65]>) = pairs
+        .iter()
+        .zip(parsed.iter())
+        .filter_map(|((payload, _), sig)| match sig {
+            Ok(sig) => Some((gateway_crypto::keccak(&payload).to_vec(), *sig)),
+            Err(_) => None,
+        })
+        .unzip();
+    let mut recovered =
+        runtime_interfaces::keyring_interface::eth_recover_many(messages, signatures, true)
+            .into_iter();
+    parsed
+        .into_iter()
+        .map(|sig| match sig {
+            Ok(_) => match recovered.next() {
+                Some(res) => Ok(res?),
+                None => Err(OracleError::CryptoError),
+            },
+            Err(err) => Err(err),
+        })
+        .collect()
+}
 
-    Ok(PriceReporters::get().contains(recovered))
+/// Check the signatures of several open price feed messages are from known reporters, all at once.
+pub fn check_signatures<T: Config>(pairs: &[(Vec<u8>, Vec<u8>)]) -> Vec<Result<bool, OracleError>> {
+    let reporters = PriceReporters::get();
+    recover_reporters(pairs)
+        .into_iter()
+        .map(|reporter| Ok(reporters.contains(reporter?)))
+        .collect()
+}
+
+/// Check that price updates for a ticker are neither paused nor frozen.
+pub fn check_not_frozen(ticker: Ticker) -> Result<(), OracleError> {
+    if OraclePaused::get() || FrozenTickers::get(ticker) {
+        Err(OracleError::PriceFrozen)?;
+    }
+    Ok(())
 }
 
 pub fn get_and_check_parsed_price<T: Config>(
@@ -205,6 +282,7 @@ pub fn get_and_check_parsed_price<T: Config>(
     // parse message and check it
     let parsed = parse_message(payload)?;
     let ticker = Ticker::from_str(&parsed.key)?;
+    check_not_frozen(ticker)?;
 
     let current_timestamp = T::GetConvertedTimestamp::get_recent_timestamp()
         .map_err(|_| OracleError::InvalidTimestamp)?;
@@ -222,16 +300,565 @@ pub fn get_and_check_parsed_price<T: Config>(
     Ok((parsed, ticker))
 }
 pub fn post_price<T: Config>(payload: Vec<u8>, signature: Vec<u8>) -> Result<(), OracleError> {
-    if !check_signature::<T>(&payload, &signature)? {
+    let valid_signature = check_signature::<T>(&payload, &signature);
+    post_signed_price::<T>(payload, valid_signature)
+}
+
+/// Set several prices using the open price feed, recovering all of their reporters at once.
+/// Stops at the first price which fails, as each is posted in turn.
+pub fn post_prices<T: Config>(pairs: Vec<(Vec<u8>, Vec<u8>)>) -> Result<(), OracleError> {
+    let valid_signatures = check_signatures::<T>(&pairs);
+    pairs
+        .into_iter()
+        .zip(valid_signatures)
+        .try_for_each(|((payload, _), valid_signature)| {
+            post_signed_price::<T>(payload, valid_signature)
+        })
+}
+
+/// Set a price using the open price feed, given whether its signature is from a known reporter.
+fn post_signed_price<T: Config>(
+    payload: Vec<u8>,
+    valid_signature: Result<bool, OracleError>,
+) -> Result<(), OracleError> {
+    if !valid_signature? {
         Err(OracleError::InvalidReporter)?;
     }
 
     let (parsed, ticker) = get_and_check_parsed_price::<T>(&payload)?;
+    let value = parsed.value as AssetPrice;
+    let timestamp = parsed.timestamp as Timestamp;
+    let update = check_price_deviation(ticker, value, timestamp)?;
+
+    // * WARNING begin storage - all checks must happen above * //
+
+    match update {
+        GuardedUpdate::Accept => {
+            PendingPrices::remove(&ticker);
+        }
+        GuardedUpdate::Confirmed => {
+            // the price has moved, so the average from before the move no longer applies
+            PendingPrices::remove(&ticker);
+            PriceMoveTimes::insert(&ticker, timestamp);
+        }
+        GuardedUpdate::Hold(pending) => {
+            PendingPrices::insert(&ticker, pending);
+            <Module<T>>::deposit_event(Event::HeldPrice(ticker, value, pending.confirmations));
+            return Ok(());
+        }
+    }
+
+    store_price(ticker, value, timestamp, PriceSource::OpenPriceFeed);
+    Ok(())
+}
+
+/// Store an accepted price, also recording it in the ticker's price history.
+fn store_price(ticker: Ticker, value: AssetPrice, timestamp: Timestamp, source: PriceSource) {
+    // any Chainlink price still being signed is no longer newer than the price
+    ChainlinkTallies::remove_prefix(&ticker);
+    Prices::insert(&ticker, value);
+    PriceTimes::insert(&ticker, timestamp);
+    PriceSources::insert(&ticker, source);
+    PriceHistory::mutate(&ticker, |history| {
+        history.push((timestamp, value));
+        let excess = history.len().saturating_sub(PRICE_HISTORY_LENGTH as usize);
+        history.drain(..excess);
+    });
+}
+
+/// Get up to `count` of the most recent prices recorded for a ticker, newest first.
+pub fn get_price_history(ticker: Ticker, count: u32) -> Vec<(Timestamp, AssetPrice)> {
+    PriceHistory::get(&ticker)
+        .into_iter()
+        .rev()
+        .take(count as usize)
+        .collect()
+}
+
+/// The outcome of checking a price update against the deviation guard.
+#[derive(PartialEq, Eq, RuntimeDebug)]
+pub enum GuardedUpdate {
+    Accept,
+    Confirmed,
+    Hold(PendingPrice),
+}
+
+/// Compute the time-weighted average of prices, each holding until the next, as of the given time.
+pub fn time_weighted_average(
+    history: &[(Timestamp, AssetPrice)],
+    now: Timestamp,
+) -> Option<AssetPrice> {
+    let mut weighted_sum: AssetPrice = 0;
+    let mut total_time: AssetPrice = 0;
+    for (i, (time, price)) in history.iter().enumerate() {
+        let until = history.get(i + 1).map_or(now, |(next, _)| *next);
+        let duration = until.saturating_sub(*time) as AssetPrice;
+        weighted_sum = weighted_sum.checked_add(price.checked_mul(duration)?)?;
+        total_time += duration;
+    }
+    match (total_time, history.last()) {
+        (0, Some((_, price))) => Some(*price),
+        (0, None) => None,
+        _ => Some(weighted_sum / total_time),
+    }
+}
+
+fn within_deviation(value: AssetPrice, reference: AssetPrice, guard: &PriceDeviationGuard) -> bool {
+    let deviation = if value > reference {
+        value - reference
+    } else {
+        reference - value
+    };
+    deviation.saturating_mul(10_000)
+        <= reference.saturating_mul(guard.max_deviation_bps as AssetPrice)
+}
+
+/// Check a price update against the deviation guard. Updates which deviate too far from the
+///  recent average are held, until enough further updates confirm the new price.
+pub fn check_price_deviation(
+    ticker: Ticker,
+    value: AssetPrice,
+    timestamp: Timestamp,
+) -> Result<GuardedUpdate, OracleError> {
+    let guard = match DeviationGuard::get() {
+        Some(guard) => guard,
+        None => return Ok(GuardedUpdate::Accept),
+    };
+
+    let moved_at = PriceMoveTimes::get(&ticker).unwrap_or(0);
+    let history: Vec<_> = PriceHistory::get(&ticker)
+        .into_iter()
+        .filter(|(time, _)| {
+            *time >= moved_at && time.saturating_add(guard.twap_window_ms) > timestamp
+        })
+        .collect();
+    match time_weighted_average(&history, timestamp) {
+        None => return Ok(GuardedUpdate::Accept),
+        Some(average) if within_deviation(value, average, &guard) => {
+            return Ok(GuardedUpdate::Accept)
+        }
+        Some(_) => (),
+    }
+
+    let pending = match PendingPrices::get(&ticker) {
+        Some(pending) if timestamp <= pending.timestamp => Err(OracleError::StalePrice)?,
+        Some(pending) if within_deviation(value, pending.value, &guard) => PendingPrice {
+            value,
+            timestamp,
+            confirmations: pending.confirmations + 1,
+        },
+        _ => PendingPrice {
+            value,
+            timestamp,
+            confirmations: 0,
+        },
+    };
+    if pending.confirmations >= guard.confirmations {
+        Ok(GuardedUpdate::Confirmed)
+    } else {
+        Ok(GuardedUpdate::Hold(pending))
+    }
+}
+
+/// Convert a Chainlink answer with the given number of decimals into a price.
+pub fn scale_chainlink_answer(answer: u128, decimals: u8) -> Result<AssetPrice, OracleError> {
+    if decimals >= Price::DECIMALS {
+        let scale = 10u128
+            .checked_pow((decimals - Price::DECIMALS).into())
+            .ok_or(OracleError::InvalidValue)?;
+        Ok(answer / scale)
+    } else {
+        let scale = 10u128.pow((Price::DECIMALS - decimals).into());
+        answer.checked_mul(scale).ok_or(OracleError::InvalidValue)
+    }
+}
+
+/// Whether the current price of a ticker is missing or old enough to fall back on Chainlink.
+fn is_price_stale(ticker: Ticker, current_timestamp: Timestamp) -> bool {
+    PriceTimes::get(&ticker).map_or(true, |last_updated| {
+        last_updated.saturating_add(CHAINLINK_FALLBACK_THRESHOLD_MS) <= current_timestamp
+    })
+}
+
+/// Check that a Chainlink price may be posted for a ticker, which is only when the current
+///  price is stale, and the Chainlink price is both newer and not itself stale.
+pub fn check_chainlink_price<T: Config>(
+    ticker: Ticker,
+    updated_at: Timestamp,
+) -> Result<(), OracleError> {
+    if !ChainlinkFeeds::contains_key(ticker) {
+        Err(OracleError::ChainlinkFeedNotSet)?;
+    }
+    check_not_frozen(ticker)?;
+
+    let current_timestamp = T::GetConvertedTimestamp::get_recent_timestamp()
+        .map_err(|_| OracleError::InvalidTimestamp)?;
+    if !is_price_stale(ticker, current_timestamp) {
+        Err(OracleError::FallbackNotNeeded)?;
+    }
+    if PriceTimes::get(&ticker).map_or(false, |last_updated| updated_at <= last_updated) {
+        Err(OracleError::StalePrice)?;
+    }
+    if updated_at.saturating_add(CHAINLINK_FALLBACK_THRESHOLD_MS) <= current_timestamp {
+        Err(OracleError::StalePrice)?;
+    } else if updated_at > current_timestamp.saturating_add(MAX_PRICE_FUTURE_MS) {
+        Err(OracleError::TimestampTooHigh)?;
+    }
+    Ok(())
+}
+
+/// Prefix of the messages validators sign for Chainlink prices, so they can't be replayed elsewhere.
+pub const CHAINLINK_PRICES_PREFIX: &[u8] = b"gateway:chainlink_prices:";
+
+/// The message a validator signs to attest to prices read from Ethereum, of the kind given by the prefix.
+pub fn validator_price_message(
+    prefix: &[u8],
+    prices: &[(Ticker, AssetPrice, Timestamp)],
+) -> Vec<u8> {
+    let mut message = prefix.to_vec();
+    message.extend(prices.encode());
+    message
+}
+
+/// Sign a message as a validator, with the Ethereum key the validator is configured with.
+pub fn validator_sign(message: &[u8]) -> Result<ValidatorSignature, OracleError> {
+    let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id()
+        .ok_or(OracleError::KeyNotFound)?;
+    Ok(runtime_interfaces::keyring_interface::sign_one(
+        message.into(),
+        eth_key_id,
+    )?)
+}
+
+/// The Ethereum address this validator signs with.
+pub fn validator_address() -> Result<ValidatorAddress, OracleError> {
+    let eth_key_id = runtime_interfaces::validator_config_interface::get_eth_key_id()
+        .ok_or(OracleError::KeyNotFound)?;
+    let public_key = runtime_interfaces::keyring_interface::get_public_key(eth_key_id)?;
+    Ok(gateway_crypto::public_key_bytes_to_eth_address(&public_key))
+}
+
+/// Recover the validator which signed a message, failing if the signer is not a current validator.
+pub fn recover_validator<T: Config>(
+    message: &[u8],
+    signature: &ValidatorSignature,
+) -> Result<ValidatorAddress, OracleError> {
+    let signer =
+        runtime_interfaces::keyring_interface::eth_recover(message.into(), *signature, false)?;
+    if T::Validators::eth_addresses().contains(&signer) {
+        Ok(signer)
+    } else {
+        Err(OracleError::UnknownValidator)
+    }
+}
+
+/// Calculate whether the signers are a super majority of the given validators.
+pub fn has_super_majority(signers: &[ValidatorAddress], validators: &[ValidatorAddress]) -> bool {
+    // using ⌈j/m⌉ = ⌊(j+m-1)/m⌋
+    let valid_signers = signers.iter().filter(|s| validators.contains(s)).count();
+    valid_signers >= (2 * validators.len() + 3 - 1) / 3
+}
+
+/// Add a validator's signature to Chainlink prices, setting each once a super majority have signed it.
+pub fn post_chainlink_prices<T: Config>(
+    prices: Vec<(Ticker, AssetPrice, Timestamp)>,
+    signature: ValidatorSignature,
+) -> Result<(), OracleError> {
+    let message = validator_price_message(CHAINLINK_PRICES_PREFIX, &prices);
+    let validator = recover_validator::<T>(&message, &signature)?;
+    let validators = T::Validators::eth_addresses();
+    for (ticker, _, updated_at) in &prices {
+        check_chainlink_price::<T>(*ticker, *updated_at)?;
+    }
+
+    // * WARNING begin storage - all checks must happen above * //
+
+    for (ticker, price, updated_at) in prices {
+        let mut signers = ChainlinkTallies::get(ticker, (price, updated_at));
+        if !signers.contains(&validator) {
+            signers.push(validator);
+        }
+        if has_super_majority(&signers, &validators) {
+            store_price(ticker, price, updated_at, PriceSource::Chainlink);
+            <Module<T>>::deposit_event(Event::PostedChainlinkPrice(ticker, price));
+        } else {
+            ChainlinkTallies::insert(ticker, (price, updated_at), signers);
+        }
+    }
+    Ok(())
+}
+
+/// Read the latest answers of the Chainlink aggregators for tickers whose price is stale.
+pub fn fetch_chainlink_prices<T: Config>(
+    server: &str,
+) -> Result<Vec<(Ticker, AssetPrice, Timestamp)>, OracleError> {
+    let current_timestamp = T::GetConvertedTimestamp::get_recent_timestamp()
+        .map_err(|_| OracleError::InvalidTimestamp)?;
+    let mut prices = Vec::new();
+    for (ticker, feed) in ChainlinkFeeds::iter() {
+        if !is_price_stale(ticker, current_timestamp) {
+            continue;
+        }
+
+        let round = match ethereum_client::get_latest_round_data(server, &feed.aggregator) {
+            Ok(round) => round,
+            Err(err) => {
+                log!(target: ORACLE, "Failed to read Chainlink price for {:?}: {:?}", ticker, err);
+                continue;
+            }
+        };
+        let price = scale_chainlink_answer(round.answer, feed.decimals);
+        let updated_at = round.updated_at.checked_mul(1000);
+        match (price, updated_at) {
+            (Ok(price), Some(updated_at))
+                if check_chainlink_price::<T>(ticker, updated_at).is_ok() =>
+            {
+                prices.push((ticker, price, updated_at))
+            }
+            _ => log!(target: ORACLE, "Skipping Chainlink price for {:?}: {:?}", ticker, round),
+        }
+    }
+    Ok(prices)
+}
+
+/// Procedure for offchain worker to post Chainlink prices in place of stale open price feed prices
+pub fn process_chainlink_prices<T: Config>() -> Result<(), OracleError> {
+    if ChainlinkFeeds::iter().next().is_none() {
+        return Ok(());
+    }
+
+    let server = match runtime_interfaces::validator_config_interface::get_eth_rpc_url() {
+        Some(server) => server,
+        None => return Ok(()),
+    };
+
+    let prices = fetch_chainlink_prices::<T>(&server)?;
+    if prices.is_empty() {
+        return Ok(());
+    }
+
+    let signature = validator_sign(&validator_price_message(CHAINLINK_PRICES_PREFIX, &prices))?;
+    let call = Call::post_chainlink_prices(prices, signature);
+    SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+        .map_err(|_| OracleError::SubmitError)
+}
+
+/// The greatest tick a Uniswap V3 pool price may have, in either direction.
+const MAX_UNISWAP_TICK: i64 = 887272;
+
+/// Compute the average tick over a window from the tick cumulatives at its start and end,
+///  rounding towards negative infinity, as the Uniswap oracle library does.
+pub fn twap_tick(past: i64, now: i64, window_secs: u32) -> Result<i64, OracleError> {
+    if window_secs == 0 {
+        Err(OracleError::InvalidValue)?;
+    }
+    let delta = now.checked_sub(past).ok_or(OracleError::InvalidValue)?;
+    let window = window_secs as i64;
+    let tick = delta / window;
+    if delta < 0 && delta % window != 0 {
+        Ok(tick - 1)
+    } else {
+        Ok(tick)
+    }
+}
+
+/// Convert a Uniswap V3 tick into a price of the feed's asset, in terms of its stablecoin.
+///
+/// The raw price of token0 in terms of token1 is `1.0001^tick`, which is computed in Q128 fixed point.
+pub fn tick_to_price(tick: i64, feed: &UniswapTwapFeed) -> Result<AssetPrice, OracleError> {
+    let tick = if feed.asset_is_token0 { tick } else { -tick };
+    if tick.abs() > MAX_UNISWAP_TICK {
+        Err(OracleError::InvalidValue)?;
+    }
+
+    let one = U512::one() << 128;
+    let mut base = one + one / 10_000;
+    let mut ratio = one;
+    let mut exponent = tick.abs() as u64;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            ratio = ratio.checked_mul(base).ok_or(OracleError::InvalidValue)? >> 128;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = base.checked_mul(base).ok_or(OracleError::InvalidValue)? >> 128;
+        }
+    }
+    if tick < 0 {
+        ratio = (one << 128) / ratio;
+    }
+
+    let scale = U512::from(10)
+        .checked_pow((Price::DECIMALS as u32 + feed.asset_decimals as u32).into())
+        .ok_or(OracleError::InvalidValue)?;
+    let quote_scale = U512::from(10).pow(feed.quote_decimals.into());
+    let price = (ratio.checked_mul(scale).ok_or(OracleError::InvalidValue)? / quote_scale) >> 128;
+    price.try_into().map_err(|_| OracleError::InvalidValue)
+}
+
+/// Prefix of the messages validators sign for DEX prices, so they can't be replayed elsewhere.
+pub const DEX_PRICES_PREFIX: &[u8] = b"gateway:dex_prices:";
+
+/// Check that a validator may report a DEX price for a ticker, which must be fresh, and newer than its last.
+pub fn check_dex_price<T: Config>(
+    ticker: Ticker,
+    validator: &ValidatorAddress,
+    timestamp: Timestamp,
+) -> Result<(), OracleError> {
+    if !UniswapFeeds::contains_key(ticker) {
+        Err(OracleError::UniswapFeedNotSet)?;
+    }
+    check_not_frozen(ticker)?;
+
+    let current_timestamp = T::GetConvertedTimestamp::get_recent_timestamp()
+        .map_err(|_| OracleError::InvalidTimestamp)?;
+    if DexReports::get(&ticker, validator)
+        .map_or(false, |(_, reported_at)| timestamp <= reported_at)
+    {
+        Err(OracleError::StalePrice)?;
+    }
+    if timestamp.saturating_add(DEX_PRICE_MAX_AGE_MS) <= current_timestamp {
+        Err(OracleError::StalePrice)?;
+    } else if timestamp > current_timestamp.saturating_add(MAX_PRICE_FUTURE_MS) {
+        Err(OracleError::TimestampTooHigh)?;
+    }
+    Ok(())
+}
+
+/// Take the median of the fresh DEX price reports of the validators for a ticker, with the time of
+///  the oldest, but only once a super majority of the validators have a fresh report.
+fn aggregate_dex_reports(
+    ticker: Ticker,
+    validators: &[ValidatorAddress],
+    current_timestamp: Timestamp,
+) -> Option<(AssetPrice, Timestamp)> {
+    let mut reporters = Vec::new();
+    let mut prices = Vec::new();
+    let mut oldest = current_timestamp;
+    for validator in validators {
+        match DexReports::get(&ticker, validator) {
+            Some((price, reported_at))
+                if reported_at.saturating_add(DEX_PRICE_MAX_AGE_MS) > current_timestamp =>
+            {
+                reporters.push(*validator);
+                prices.push(price);
+                oldest = oldest.min(reported_at);
+            }
+            _ => (),
+        }
+    }
+    if prices.is_empty() || !has_super_majority(&reporters, validators) {
+        return None;
+    }
+    prices.sort();
+    Some((prices[prices.len() / 2], oldest))
+}
+
+/// Record a validator's reports of DEX prices, setting the DEX price of each ticker to the
+///  median of the validators' fresh reports, once a super majority have reported.
+pub fn post_dex_prices<T: Config>(
+    prices: Vec<(Ticker, AssetPrice, Timestamp)>,
+    signature: ValidatorSignature,
+) -> Result<(), OracleError> {
+    let message = validator_price_message(DEX_PRICES_PREFIX, &prices);
+    let validator = recover_validator::<T>(&message, &signature)?;
+    let validators = T::Validators::eth_addresses();
+    let current_timestamp = T::GetConvertedTimestamp::get_recent_timestamp()
+        .map_err(|_| OracleError::InvalidTimestamp)?;
+    for (ticker, _, timestamp) in &prices {
+        check_dex_price::<T>(*ticker, &validator, *timestamp)?;
+    }
 
     // * WARNING begin storage - all checks must happen above * //
 
-    Prices::insert(&ticker, parsed.value as AssetPrice);
-    PriceTimes::insert(&ticker, parsed.timestamp as Timestamp);
+    for (ticker, price, timestamp) in prices {
+        DexReports::insert(&ticker, &validator, (price, timestamp));
+        if let Some((dex_price, dex_price_time)) =
+            aggregate_dex_reports(ticker, &validators, current_timestamp)
+        {
+            DexPrices::insert(&ticker, dex_price);
+            DexPriceTimes::insert(&ticker, dex_price_time);
+            <Module<T>>::deposit_event(Event::PostedDexPrice(ticker, dex_price));
+        }
+    }
+    Ok(())
+}
+
+/// Read the time-weighted average prices of the configured Uniswap V3 pools.
+pub fn fetch_dex_prices<T: Config>(
+    server: &str,
+    validator: &ValidatorAddress,
+) -> Result<Vec<(Ticker, AssetPrice, Timestamp)>, OracleError> {
+    let current_timestamp = T::GetConvertedTimestamp::get_recent_timestamp()
+        .map_err(|_| OracleError::InvalidTimestamp)?;
+    let mut prices = Vec::new();
+    for (ticker, feed) in UniswapFeeds::iter() {
+        let (past, now) = match ethereum_client::get_uniswap_tick_cumulatives(
+            server,
+            &feed.pool,
+            feed.twap_window_secs,
+        ) {
+            Ok(cumulatives) => cumulatives,
+            Err(err) => {
+                log!(target: ORACLE, "Failed to read Uniswap TWAP for {:?}: {:?}", ticker, err);
+                continue;
+            }
+        };
+        match twap_tick(past, now, feed.twap_window_secs).and_then(|t| tick_to_price(t, &feed)) {
+            Ok(price) if check_dex_price::<T>(ticker, validator, current_timestamp).is_ok() => {
+                prices.push((ticker, price, current_timestamp))
+            }
+            result => log!(target: ORACLE, "Skipping Uniswap TWAP for {:?}: {:?}", ticker, result),
+        }
+    }
+    Ok(prices)
+}
+
+/// Procedure for offchain worker to post time-weighted average prices from Uniswap V3 pools
+pub fn process_dex_prices<T: Config>() -> Result<(), OracleError> {
+    if UniswapFeeds::iter().next().is_none() {
+        return Ok(());
+    }
+
+    let server = match runtime_interfaces::validator_config_interface::get_eth_rpc_url() {
+        Some(server) => server,
+        None => return Ok(()),
+    };
+
+    let validator = validator_address()?;
+    let prices = fetch_dex_prices::<T>(&server, &validator)?;
+    if prices.is_empty() {
+        return Ok(());
+    }
+
+    let signature = validator_sign(&validator_price_message(DEX_PRICES_PREFIX, &prices))?;
+    let call = Call::post_dex_prices(prices, signature);
+    SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+        .map_err(|_| OracleError::SubmitError)
+}
+
+/// Check that no message from one endpoint is contradicted by a message from another, which is
+///  a message signed by the same reporter for the same ticker and time, but with a different price.
+pub fn check_price_feeds_agree(
+    messages_and_signatures: &[(Vec<u8>, Vec<u8>)],
+    other_messages_and_signatures: &[(Vec<u8>, Vec<u8>)],
+) -> Result<(), OracleError> {
+    let mut seen = BTreeMap::new();
+    let reporters = recover_reporters(messages_and_signatures);
+    for ((payload, _), reporter) in messages_and_signatures.iter().zip(reporters) {
+        let message = parse_message(payload)?;
+        let reporter = reporter?;
+        seen.insert((reporter, message.key, message.timestamp), message.value);
+    }
+    let other_reporters = recover_reporters(other_messages_and_signatures);
+    for ((payload, _), reporter) in other_messages_and_signatures.iter().zip(other_reporters) {
+        let message = parse_message(payload)?;
+        let reporter = reporter?;
+        match seen.get(&(reporter, message.key, message.timestamp)) {
+            Some(value) if *value != message.value => Err(OracleError::PriceFeedMismatch)?,
+            _ => (),
+        }
+    }
     Ok(())
 }
 
@@ -243,13 +870,6 @@ pub fn process_prices<T: Config>(block_number: T::BlockNumber) -> Result<(), Ora
         return Ok(());
     }
 
-    // get the URL to poll, just return if there is no URL set up
-    let url =
-        runtime_interfaces::validator_config_interface::get_opf_url().unwrap_or(String::new());
-    if url.len() == 0 {
-        return Ok(());
-    }
-
     // check to see if it is time to poll or not
     let latest_price_feed_poll_block_number_storage =
         StorageValueRef::persistent(OCW_LATEST_BLOCK_NUMBER);
@@ -263,9 +883,40 @@ pub fn process_prices<T: Config>(block_number: T::BlockNumber) -> Result<(), Ora
         }
     }
 
-    // poll
-    let (messages_and_signatures, timestamp) =
-        open_price_feed_request(&url)?.to_message_signature_pairs()?;
+    // poll, falling back to chainlink for whatever is stale even if the open price feed fails
+    let urls = runtime_interfaces::validator_config_interface::get_opf_urls();
+    let open_price_feed_result = if urls.is_empty() {
+        Ok(())
+    } else {
+        poll_open_price_feeds(&urls)
+    };
+
+    latest_price_feed_poll_block_number_storage.set(&block_number);
+    process_chainlink_prices::<T>()?;
+    process_dex_prices::<T>()?;
+    open_price_feed_result
+}
+
+/// Poll every open price feed endpoint, failing over to later endpoints in order, and only
+///  accepting the prices of the first to respond once they agree with all the others.
+pub fn poll_open_price_feeds(urls: &[String]) -> Result<(), OracleError> {
+    let mut responses = Vec::with_capacity(urls.len());
+    let mut last_error = OracleError::HttpError;
+    for url in urls {
+        match open_price_feed_request(url).and_then(|r| r.to_message_signature_pairs()) {
+            Ok(response) => responses.push(response),
+            Err(err) => {
+                log!(target: ORACLE, "Open price feed request to {} failed: {:?}", url, err);
+                last_error = err;
+            }
+        }
+    }
+
+    let mut responses = responses.into_iter();
+    let (messages_and_signatures, timestamp) = responses.next().ok_or(last_error)?;
+    for (other_messages_and_signatures, _) in responses {
+        check_price_feeds_agree(&messages_and_signatures, &other_messages_and_signatures)?;
+    }
 
     let curr_ts = runtime_interfaces::price_feed_interface::get_price_data_ts();
     if curr_ts.map(|v| v < timestamp).unwrap_or(true) {
@@ -274,8 +925,6 @@ pub fn process_prices<T: Config>(block_number: T::BlockNumber) -> Result<(), Ora
             timestamp,
         );
     }
-
-    latest_price_feed_poll_block_number_storage.set(&block_number);
     Ok(())
 }
 
@@ -601,4 +1250,17 @@ pub mod tests {
             "85615b076615317c80f14cbad6501eec031cd51c"
         )
     }
+
+    #[test]
+    fn test_recover_reporters() {
+        let msg = hex_literal::hex!("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000688e4cda00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034254430000000000000000000000000000000000000000000000000000000000");
+        let sig = hex_literal::hex!("69538bfa1a2097ea206780654d7baac3a17ee57547ee3eeb5d8bcb58a2fcdf401ff8834f4a003193f24224437881276fe76c8e1c0a361081de854457d41d0690000000000000000000000000000000000000000000000000000000000000001c");
+        let pair = (msg.to_vec(), sig.to_vec());
+        let reporter = recover_reporter(&pair.0, &pair.1).unwrap();
+        let recovered = recover_reporters(&[pair.clone(), (msg.to_vec(), vec![]), pair]);
+        assert_eq!(recovered.len(), 3);
+        assert_eq!(recovered[0], Ok(reporter));
+        assert!(recovered[1].is_err());
+        assert_eq!(recovered[2], Ok(reporter));
+    }
 }
@@ -14,6 +14,7 @@ use sp_runtime::{
     traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
     MultiAddress, MultiSignature as Signature,
 };
+use std::cell::RefCell;
 
 pub type Extrinsic = TestXt<Call, ()>;
 pub type OracleModule = Module<Test>;
@@ -90,10 +91,29 @@ impl frame_system::offchain::SigningTypes for Test {
     type Signature = Signature;
 }
 
+thread_local! {
+    pub static VALIDATORS: RefCell<Vec<types::ValidatorAddress>> = RefCell::new(vec![]);
+}
+
+pub struct MockValidators;
+
+impl MockValidators {
+    pub fn set(validators: Vec<types::ValidatorAddress>) {
+        VALIDATORS.with(|v| *v.borrow_mut() = validators);
+    }
+}
+
+impl ValidatorSet for MockValidators {
+    fn eth_addresses() -> Vec<types::ValidatorAddress> {
+        VALIDATORS.with(|v| v.borrow().clone())
+    }
+}
+
 impl Config for Test {
     type Event = Event;
     type Call = Call;
     type GetConvertedTimestamp = timestamp::TimeConverter<Self>;
+    type Validators = MockValidators;
 }
 impl pallet_timestamp::Config for Test {
     /// A timestamp: milliseconds since the unix epoch.
@@ -1,4 +1,14 @@
-use crate::{error::OracleError, ticker::Ticker, *};
+use crate::{
+    error::OracleError,
+    ticker::{DerivedTicker, Ticker},
+    types::{
+        ChainlinkFeed, PendingPrice, PriceDeviationGuard, PriceSource, UniswapTwapFeed,
+        ValidatorAddress, ValidatorSignature,
+    },
+    *,
+};
+use gateway_crypto::{InMemoryKeyring, KeyId, Keyring};
+use sp_core::Pair;
 
 use sp_core::offchain::testing;
 
@@ -182,3 +192,653 @@ fn offchain_worker_test() {
         }
     });
 }
+
+const CHAINLINK_FEED: ChainlinkFeed = ChainlinkFeed {
+    aggregator: [95; 20],
+    decimals: 8,
+};
+
+pub const VALIDATOR_A_KEY: &str =
+    "6bc5ea78f041146e38233f5bc29c703c1cec8eaaa2214353ee8adf7fc598f23d";
+pub const VALIDATOR_B_KEY: &str =
+    "50f05592dc31bfc65a77c4cc80f2764ba8f9a7cce29c94a51fe2d70cb5599374";
+
+pub fn validator_keyring(private_key: &str) -> (KeyId, InMemoryKeyring) {
+    let key_id = KeyId::from("validator");
+    let pair = sp_core::ecdsa::Pair::from_seed_slice(&hex::decode(private_key).unwrap()).unwrap();
+    let mut keyring = InMemoryKeyring::new();
+    keyring.add(&key_id, pair);
+    (key_id, keyring)
+}
+
+pub fn validator_address(private_key: &str) -> ValidatorAddress {
+    let (key_id, keyring) = validator_keyring(private_key);
+    keyring.get_eth_address(&key_id).unwrap()
+}
+
+pub fn validator_sign_prices(
+    private_key: &str,
+    prefix: &[u8],
+    prices: &[(Ticker, AssetPrice, Timestamp)],
+) -> ValidatorSignature {
+    let (key_id, keyring) = validator_keyring(private_key);
+    let message = oracle::validator_price_message(prefix, prices);
+    keyring.sign_one(&message, &key_id).unwrap()
+}
+
+pub fn post_chainlink_prices_as(
+    private_key: &str,
+    prices: Vec<(Ticker, AssetPrice, Timestamp)>,
+) -> Result<(), DispatchError> {
+    let signature = validator_sign_prices(private_key, oracle::CHAINLINK_PRICES_PREFIX, &prices);
+    OracleModule::post_chainlink_prices(Origin::none(), prices, signature)
+}
+
+#[test]
+fn test_set_chainlink_feed() {
+    new_test_ext().execute_with(|| {
+        assert_err!(
+            OracleModule::set_chainlink_feed(Origin::none(), ETH_TICKER, Some(CHAINLINK_FEED)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(OracleModule::set_chainlink_feed(
+            Origin::root(),
+            ETH_TICKER,
+            Some(CHAINLINK_FEED)
+        ));
+        assert_eq!(
+            OracleModule::chainlink_feed(ETH_TICKER),
+            Some(CHAINLINK_FEED)
+        );
+        assert_ok!(OracleModule::set_chainlink_feed(
+            Origin::root(),
+            ETH_TICKER,
+            None
+        ));
+        assert_eq!(OracleModule::chainlink_feed(ETH_TICKER), None);
+    });
+}
+
+#[test]
+fn test_scale_chainlink_answer() {
+    assert_eq!(
+        oracle::scale_chainlink_answer(256_012_345_678, 8),
+        Ok(2_560_123_456)
+    );
+    assert_eq!(oracle::scale_chainlink_answer(2_560, 3), Ok(2_560_000));
+    assert_eq!(
+        oracle::scale_chainlink_answer(u128::MAX, 0),
+        Err(OracleError::InvalidValue)
+    );
+    assert_eq!(
+        oracle::scale_chainlink_answer(1, 255),
+        Err(OracleError::InvalidValue)
+    );
+}
+
+#[test]
+fn test_post_chainlink_prices() {
+    new_test_ext().execute_with(|| {
+        MockValidators::set(vec![
+            validator_address(VALIDATOR_A_KEY),
+            validator_address(VALIDATOR_B_KEY),
+        ]);
+        let now = 3 * CHAINLINK_FALLBACK_THRESHOLD_MS;
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(now);
+
+        assert_err!(
+            post_chainlink_prices_as(VALIDATOR_A_KEY, vec![(ETH_TICKER, 2_000_000_000, now)]),
+            OracleError::ChainlinkFeedNotSet
+        );
+
+        ChainlinkFeeds::insert(ETH_TICKER, CHAINLINK_FEED);
+        PriceTimes::insert(ETH_TICKER, now - 1);
+        assert_err!(
+            post_chainlink_prices_as(VALIDATOR_A_KEY, vec![(ETH_TICKER, 2_000_000_000, now)]),
+            OracleError::FallbackNotNeeded
+        );
+
+        PriceTimes::insert(ETH_TICKER, now - CHAINLINK_FALLBACK_THRESHOLD_MS);
+        assert_err!(
+            post_chainlink_prices_as(
+                VALIDATOR_A_KEY,
+                vec![(
+                    ETH_TICKER,
+                    2_000_000_000,
+                    now - CHAINLINK_FALLBACK_THRESHOLD_MS
+                )]
+            ),
+            OracleError::StalePrice
+        );
+        assert_err!(
+            post_chainlink_prices_as(
+                VALIDATOR_A_KEY,
+                vec![(
+                    ETH_TICKER,
+                    2_000_000_000,
+                    now + oracle::MAX_PRICE_FUTURE_MS + 1
+                )]
+            ),
+            OracleError::TimestampTooHigh
+        );
+
+        // prices must be signed by a validator
+        let not_a_validator = "0101010101010101010101010101010101010101010101010101010101010101";
+        assert_err!(
+            post_chainlink_prices_as(
+                not_a_validator,
+                vec![(ETH_TICKER, 2_000_000_000, now - 1_000)]
+            ),
+            OracleError::UnknownValidator
+        );
+        let prices = vec![(ETH_TICKER, 2_000_000_000, now - 1_000)];
+        let signature =
+            validator_sign_prices(VALIDATOR_A_KEY, oracle::CHAINLINK_PRICES_PREFIX, &prices);
+        assert_err!(
+            OracleModule::post_chainlink_prices(
+                Origin::none(),
+                vec![(ETH_TICKER, 1, now - 1_000)],
+                signature
+            ),
+            OracleError::UnknownValidator
+        );
+
+        // one of two validators is not a super majority
+        assert_ok!(post_chainlink_prices_as(VALIDATOR_A_KEY, prices.clone()));
+        assert_ok!(post_chainlink_prices_as(VALIDATOR_A_KEY, prices.clone()));
+        assert_eq!(
+            OracleModule::price_time(ETH_TICKER),
+            Some(now - CHAINLINK_FALLBACK_THRESHOLD_MS)
+        );
+        assert_eq!(
+            OracleModule::chainlink_tally(ETH_TICKER, (2_000_000_000, now - 1_000)),
+            vec![validator_address(VALIDATOR_A_KEY)]
+        );
+
+        assert_ok!(post_chainlink_prices_as(VALIDATOR_B_KEY, prices));
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(2_000_000_000));
+        assert_eq!(OracleModule::price_time(ETH_TICKER), Some(now - 1_000));
+        assert_eq!(
+            OracleModule::price_source(ETH_TICKER),
+            Some(PriceSource::Chainlink)
+        );
+        assert_eq!(
+            OracleModule::chainlink_tally(ETH_TICKER, (2_000_000_000, now - 1_000)),
+            vec![]
+        );
+    });
+}
+
+#[test]
+fn test_process_chainlink_prices() {
+    std::env::set_var("ETH_RPC_URL", "https://mainnet-eth.compound.finance");
+    let calls = vec![testing::PendingRequest {
+        method: "POST".into(),
+        uri: "https://mainnet-eth.compound.finance".into(),
+        headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+        body: br#"{"jsonrpc":"2.0","method":"eth_call","params":[{"to":"0x5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f","data":"0xfeaf968c"},"latest"],"id":1}"#.to_vec(),
+        response: Some(br#"{"jsonrpc":"2.0","id":1,"result":"0x000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000002e90edd000000000000000000000000000000000000000000000000000000000000000a8c000000000000000000000000000000000000000000000000000000000000000a8c0000000000000000000000000000000000000000000000000000000000000001"}"#.to_vec()),
+        sent: true,
+        ..Default::default()
+    }];
+
+    let (mut t, pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+    t.execute_with(|| {
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(2_700_000 + 1_000);
+        ChainlinkFeeds::insert(ETH_TICKER, CHAINLINK_FEED);
+
+        assert_ok!(oracle::process_chainlink_prices::<Test>());
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let ex: Extrinsic = codec::Decode::decode(&mut &*tx).unwrap();
+        let prices = vec![(ETH_TICKER, 125_000_000, 2_700_000)];
+        let signature =
+            validator_sign_prices(VALIDATOR_B_KEY, oracle::CHAINLINK_PRICES_PREFIX, &prices);
+        assert_eq!(
+            ex.call,
+            mock::Call::Oracle(crate::Call::post_chainlink_prices(prices, signature))
+        );
+    });
+}
+
+const DEVIATION_GUARD: PriceDeviationGuard = PriceDeviationGuard {
+    max_deviation_bps: 2_000,
+    twap_window_ms: 60_000,
+    confirmations: 2,
+};
+
+#[test]
+fn test_time_weighted_average() {
+    assert_eq!(oracle::time_weighted_average(&[], 100), None);
+    assert_eq!(oracle::time_weighted_average(&[(100, 7)], 100), Some(7));
+    assert_eq!(
+        oracle::time_weighted_average(&[(0, 100), (30, 200)], 40),
+        Some(125)
+    );
+}
+
+#[test]
+fn test_check_price_deviation() {
+    new_test_ext().execute_with(|| {
+        PriceHistory::insert(ETH_TICKER, vec![(0, 1_000_000_000)]);
+        assert_eq!(
+            oracle::check_price_deviation(ETH_TICKER, 2_000_000_000, 1_000),
+            Ok(oracle::GuardedUpdate::Accept)
+        );
+
+        DeviationGuard::put(DEVIATION_GUARD);
+        assert_eq!(
+            oracle::check_price_deviation(ETH_TICKER, 1_200_000_000, 1_000),
+            Ok(oracle::GuardedUpdate::Accept)
+        );
+        let held = PendingPrice {
+            value: 2_000_000_000,
+            timestamp: 1_000,
+            confirmations: 0,
+        };
+        assert_eq!(
+            oracle::check_price_deviation(ETH_TICKER, 2_000_000_000, 1_000),
+            Ok(oracle::GuardedUpdate::Hold(held))
+        );
+
+        PendingPrices::insert(ETH_TICKER, held);
+        assert_eq!(
+            oracle::check_price_deviation(ETH_TICKER, 2_000_000_000, 1_000),
+            Err(OracleError::StalePrice)
+        );
+        assert_eq!(
+            oracle::check_price_deviation(ETH_TICKER, 2_100_000_000, 2_000),
+            Ok(oracle::GuardedUpdate::Hold(PendingPrice {
+                value: 2_100_000_000,
+                timestamp: 2_000,
+                confirmations: 1,
+            }))
+        );
+        // a different deviating price starts over
+        assert_eq!(
+            oracle::check_price_deviation(ETH_TICKER, 500_000_000, 2_000),
+            Ok(oracle::GuardedUpdate::Hold(PendingPrice {
+                value: 500_000_000,
+                timestamp: 2_000,
+                confirmations: 0,
+            }))
+        );
+
+        PendingPrices::insert(
+            ETH_TICKER,
+            PendingPrice {
+                confirmations: 1,
+                ..held
+            },
+        );
+        assert_eq!(
+            oracle::check_price_deviation(ETH_TICKER, 2_000_000_000, 2_000),
+            Ok(oracle::GuardedUpdate::Confirmed)
+        );
+
+        // prices outside the window are forgotten
+        assert_eq!(
+            oracle::check_price_deviation(ETH_TICKER, 2_000_000_000, 61_000),
+            Ok(oracle::GuardedUpdate::Accept)
+        );
+
+        // as are prices from before a confirmed move
+        PriceMoveTimes::insert(ETH_TICKER, 1_000);
+        assert_eq!(
+            oracle::check_price_deviation(ETH_TICKER, 2_000_000_000, 2_000),
+            Ok(oracle::GuardedUpdate::Accept)
+        );
+    });
+}
+
+#[test]
+fn test_post_price_held_by_deviation_guard() {
+    // an eth price message
+    let test_payload = hex::decode("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000002baa48a00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000").unwrap();
+    let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
+    new_test_ext().execute_with(|| {
+        initialize_storage(); // sets up ETH
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(500);
+        assert_ok!(OracleModule::set_deviation_guard(
+            Origin::root(),
+            Some(DEVIATION_GUARD)
+        ));
+        PriceHistory::insert(ETH_TICKER, vec![(1609340750000, 500_000_000)]);
+
+        assert_ok!(OracleModule::post_price(
+            Origin::none(),
+            test_payload,
+            test_signature
+        ));
+        assert_eq!(OracleModule::price(ETH_TICKER), None);
+        assert_eq!(
+            OracleModule::pending_price(ETH_TICKER),
+            Some(PendingPrice {
+                value: 732580000,
+                timestamp: 1609340760000,
+                confirmations: 0,
+            })
+        );
+        assert!(System::events().iter().any(|r| r.event
+            == mock::Event::pallet_oracle(crate::Event::HeldPrice(ETH_TICKER, 732580000, 0))));
+    });
+}
+
+fn open_price_feed_call(uri: &str, response: &str) -> testing::PendingRequest {
+    testing::PendingRequest {
+        method: "GET".into(),
+        uri: uri.into(),
+        body: vec![],
+        response: Some(response.to_owned().into_bytes()),
+        headers: vec![],
+        sent: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_poll_open_price_feeds_fails_over() {
+    let calls = vec![
+        open_price_feed_call("http://first/", "not json"),
+        open_price_feed_call("http://second/", oracle::tests::API_RESPONSE_TEST_DATA),
+    ];
+
+    let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+    t.execute_with(|| {
+        assert_ok!(oracle::poll_open_price_feeds(&[
+            "http://first/".into(),
+            "http://second/".into()
+        ]));
+    });
+}
+
+#[test]
+fn test_poll_open_price_feeds_all_fail() {
+    let calls = vec![open_price_feed_call("http://first/", "not json")];
+
+    let (mut t, _pool_state, _offchain_state) = new_test_ext_with_http_calls(calls);
+    t.execute_with(|| {
+        assert_eq!(
+            oracle::poll_open_price_feeds(&["http://first/".into()]),
+            Err(OracleError::JsonParseError)
+        );
+    });
+}
+
+#[test]
+fn test_check_price_feeds_agree() {
+    let (pairs, _ts) = serde_json::from_str::<oracle::OpenPriceFeedApiResponse>(
+        oracle::tests::API_RESPONSE_TEST_DATA,
+    )
+    .unwrap()
+    .to_message_signature_pairs()
+    .unwrap();
+
+    assert_eq!(oracle::check_price_feeds_agree(&pairs, &pairs), Ok(()));
+    assert_eq!(
+        oracle::check_price_feeds_agree(&pairs, &pairs[2..4]),
+        Ok(())
+    );
+    assert_eq!(oracle::check_price_feeds_agree(&pairs[..1], &[]), Ok(()));
+}
+
+const UNISWAP_FEED: UniswapTwapFeed = UniswapTwapFeed {
+    pool: [138; 20],
+    twap_window_secs: 1800,
+    asset_is_token0: false,
+    asset_decimals: 18,
+    quote_decimals: 6,
+};
+
+#[test]
+fn test_twap_tick() {
+    assert_eq!(oracle::twap_tick(0, 3_600, 1800), Ok(2));
+    assert_eq!(oracle::twap_tick(0, -3_600, 1800), Ok(-2));
+    assert_eq!(oracle::twap_tick(0, -3_601, 1800), Ok(-3));
+    assert_eq!(oracle::twap_tick(0, 1, 0), Err(OracleError::InvalidValue));
+}
+
+#[test]
+fn test_tick_to_price() {
+    let stable = UniswapTwapFeed {
+        asset_is_token0: true,
+        asset_decimals: 6,
+        ..UNISWAP_FEED
+    };
+    assert_eq!(oracle::tick_to_price(0, &stable), Ok(1_000_000));
+    assert_eq!(oracle::tick_to_price(6932, &stable), Ok(2_000_036));
+    // USDC / WETH, with WETH as token1
+    assert_eq!(
+        oracle::tick_to_price(200311, &UNISWAP_FEED),
+        Ok(2_000_040_289)
+    );
+    assert_eq!(
+        oracle::tick_to_price(887273, &UNISWAP_FEED),
+        Err(OracleError::InvalidValue)
+    );
+}
+
+pub fn post_dex_prices_as(
+    private_key: &str,
+    prices: Vec<(Ticker, AssetPrice, Timestamp)>,
+) -> Result<(), DispatchError> {
+    let signature = validator_sign_prices(private_key, oracle::DEX_PRICES_PREFIX, &prices);
+    OracleModule::post_dex_prices(Origin::none(), prices, signature)
+}
+
+#[test]
+fn test_post_dex_prices_and_weighting() {
+    new_test_ext().execute_with(|| {
+        MockValidators::set(vec![
+            validator_address(VALIDATOR_A_KEY),
+            validator_address(VALIDATOR_B_KEY),
+        ]);
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(10_000);
+        Prices::insert(ETH_TICKER, 2_000_000_000);
+
+        assert_err!(
+            post_dex_prices_as(VALIDATOR_A_KEY, vec![(ETH_TICKER, 2_100_000_000, 10_000)]),
+            OracleError::UniswapFeedNotSet
+        );
+        assert_ok!(OracleModule::set_uniswap_feed(
+            Origin::root(),
+            ETH_TICKER,
+            Some(UNISWAP_FEED)
+        ));
+        let not_a_validator = "0101010101010101010101010101010101010101010101010101010101010101";
+        assert_err!(
+            post_dex_prices_as(not_a_validator, vec![(ETH_TICKER, 2_100_000_000, 10_000)]),
+            OracleError::UnknownValidator
+        );
+
+        // one of two validators is not a super majority
+        assert_ok!(post_dex_prices_as(
+            VALIDATOR_A_KEY,
+            vec![(ETH_TICKER, 2_100_000_000, 10_000)]
+        ));
+        assert_err!(
+            post_dex_prices_as(VALIDATOR_A_KEY, vec![(ETH_TICKER, 2_100_000_000, 10_000)]),
+            OracleError::StalePrice
+        );
+        assert_eq!(OracleModule::dex_price(ETH_TICKER), None);
+
+        assert_ok!(post_dex_prices_as(
+            VALIDATOR_B_KEY,
+            vec![(ETH_TICKER, 2_200_000_000, 9_000)]
+        ));
+        assert_eq!(OracleModule::dex_price(ETH_TICKER), Some(2_200_000_000));
+        assert_eq!(OracleModule::dex_price_time(ETH_TICKER), Some(9_000));
+
+        // without a weight, only the open price feed price is used
+        assert_eq!(OracleModule::get_price(ETH_TICKER), Ok(2_000_000_000));
+
+        assert_err!(
+            OracleModule::set_dex_weight(Origin::root(), ETH_TICKER, 10_001),
+            OracleError::InvalidWeight
+        );
+        assert_ok!(OracleModule::set_dex_weight(
+            Origin::root(),
+            ETH_TICKER,
+            2_500
+        ));
+        assert_eq!(OracleModule::get_price(ETH_TICKER), Ok(2_050_000_000));
+
+        // a DEX price which is no longer fresh is not blended in
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(9_000 + DEX_PRICE_MAX_AGE_MS);
+        assert_eq!(OracleModule::get_price(ETH_TICKER), Ok(2_000_000_000));
+        assert_err!(
+            post_dex_prices_as(VALIDATOR_A_KEY, vec![(ETH_TICKER, 2_100_000_000, 9_000)]),
+            OracleError::StalePrice
+        );
+    });
+}
+
+#[test]
+fn test_price_history_ring_buffer() {
+    new_test_ext().execute_with(|| {
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(10 * CHAINLINK_FALLBACK_THRESHOLD_MS);
+        ChainlinkFeeds::insert(ETH_TICKER, CHAINLINK_FEED);
+        assert_eq!(OracleModule::get_price_history(ETH_TICKER, 5), vec![]);
+
+        MockValidators::set(vec![validator_address(VALIDATOR_A_KEY)]);
+
+        let start = 10 * CHAINLINK_FALLBACK_THRESHOLD_MS - PRICE_HISTORY_LENGTH as u64 - 10;
+        for i in 0..(PRICE_HISTORY_LENGTH as u64 + 2) {
+            PriceTimes::remove(ETH_TICKER);
+            assert_ok!(post_chainlink_prices_as(
+                VALIDATOR_A_KEY,
+                vec![(ETH_TICKER, 1_000 + i as AssetPrice, start + i)]
+            ));
+        }
+
+        let last = PRICE_HISTORY_LENGTH as u64 + 1;
+        assert_eq!(
+            OracleModule::get_price_history(ETH_TICKER, 3),
+            vec![
+                (start + last, 1_000 + last as AssetPrice),
+                (start + last - 1, 1_000 + last as AssetPrice - 1),
+                (start + last - 2, 1_000 + last as AssetPrice - 2),
+            ]
+        );
+
+        // only the last PRICE_HISTORY_LENGTH prices are kept, the first two were dropped
+        let history = OracleModule::get_price_history(ETH_TICKER, 1_000);
+        assert_eq!(history.len(), PRICE_HISTORY_LENGTH as usize);
+        assert_eq!(history.last(), Some(&(start + 2, 1_002)));
+    });
+}
+
+#[test]
+fn test_derived_ticker() {
+    new_test_ext().execute_with(|| {
+        let wsteth = Ticker::new("WSTETH");
+        let wsteth_steth = Ticker::new("WSTETHSTETH");
+        let steth_eth = Ticker::new("STETHETH");
+        let derived = DerivedTicker {
+            numerator: wsteth_steth,
+            denominator: steth_eth,
+            base: ETH_TICKER,
+        };
+
+        assert_err!(
+            OracleModule::set_derived_ticker(Origin::none(), wsteth, Some(derived)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(OracleModule::set_derived_ticker(
+            Origin::root(),
+            wsteth,
+            Some(derived)
+        ));
+        assert_eq!(OracleModule::get_price(wsteth), Err(OracleError::NoPrice));
+
+        Prices::insert(wsteth_steth, 1_100_000);
+        Prices::insert(steth_eth, 1_000_000);
+        Prices::insert(ETH_TICKER, 2_000_000_000);
+        assert_eq!(OracleModule::get_price(wsteth), Ok(2_200_000_000));
+
+        // derived tickers may not be derived from, or be components of, other derived tickers
+        assert_err!(
+            OracleModule::set_derived_ticker(
+                Origin::root(),
+                Ticker::new("OTHER"),
+                Some(DerivedTicker {
+                    base: wsteth,
+                    ..derived
+                })
+            ),
+            OracleError::InvalidDerivedTicker
+        );
+        assert_err!(
+            OracleModule::set_derived_ticker(Origin::root(), ETH_TICKER, Some(derived)),
+            OracleError::InvalidDerivedTicker
+        );
+
+        assert_ok!(OracleModule::set_derived_ticker(
+            Origin::root(),
+            wsteth,
+            None
+        ));
+        assert_eq!(OracleModule::get_price(wsteth), Err(OracleError::NoPrice));
+    });
+}
+
+#[test]
+fn test_frozen_prices() {
+    // an eth price message
+    let test_payload = hex::decode("0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005fec975800000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000002baa48a00000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034554480000000000000000000000000000000000000000000000000000000000").unwrap();
+    let test_signature = hex::decode("41a3f89a526dee766049f3699e9e975bfbabda4db677c9f5c41fbcc0730fccb84d08b2208c4ffae0b87bb162e2791cc305ee4e9a1d936f9e6154356154e9a8e9000000000000000000000000000000000000000000000000000000000000001c").unwrap();
+    new_test_ext().execute_with(|| {
+        initialize_storage(); // sets up ETH
+        <pallet_timestamp::Pallet<Test>>::set_timestamp(500);
+
+        assert_err!(
+            OracleModule::set_ticker_frozen(Origin::none(), ETH_TICKER, true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(OracleModule::set_ticker_frozen(
+            Origin::root(),
+            ETH_TICKER,
+            true
+        ));
+        assert_err!(
+            OracleModule::post_price(Origin::none(), test_payload.clone(), test_signature.clone()),
+            OracleError::PriceFrozen
+        );
+        assert_ok!(OracleModule::set_ticker_frozen(
+            Origin::root(),
+            ETH_TICKER,
+            false
+        ));
+        assert!(!FrozenTickers::contains_key(ETH_TICKER));
+
+        assert_err!(
+            OracleModule::set_oracle_paused(Origin::none(), true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(OracleModule::set_oracle_paused(Origin::root(), true));
+        assert_err!(
+            OracleModule::post_price(Origin::none(), test_payload.clone(), test_signature.clone()),
+            OracleError::PriceFrozen
+        );
+        assert_eq!(OracleModule::price(ETH_TICKER), None);
+
+        assert_ok!(OracleModule::set_oracle_paused(Origin::root(), false));
+        assert_ok!(OracleModule::post_price(
+            Origin::none(),
+            test_payload,
+            test_signature
+        ));
+        assert_eq!(OracleModule::price(ETH_TICKER), Some(732580000));
+
+        let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+        assert_eq!(
+            &events[..4],
+            &[
+                mock::Event::pallet_oracle(crate::Event::SetTickerFrozen(ETH_TICKER, true)),
+                mock::Event::pallet_oracle(crate::Event::SetTickerFrozen(ETH_TICKER, false)),
+                mock::Event::pallet_oracle(crate::Event::SetOraclePaused(true)),
+                mock::Event::pallet_oracle(crate::Event::SetOraclePaused(false)),
+            ]
+        );
+    });
+}
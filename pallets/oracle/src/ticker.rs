@@ -7,7 +7,7 @@ use our_std::{
 
 use types_derive::Types;
 
-use crate::error::OracleError;
+use crate::{error::OracleError, types::AssetPrice};
 
 /// Type for an asset price ticker.
 #[derive(Copy, Clone, Eq, Encode, Decode, PartialEq, Ord, PartialOrd, RuntimeDebug, Types)]
@@ -19,6 +19,31 @@ impl Ticker {
     }
 }
 
+/// A ticker whose price is derived from the prices of others, as `numerator / denominator * base`.
+/// For instance wstETH may be derived as the wstETH/stETH rate, over the stETH/ETH rate, times ETH.
+#[derive(Copy, Clone, Eq, Encode, Decode, PartialEq, RuntimeDebug, Types)]
+pub struct DerivedTicker {
+    pub numerator: Ticker,
+    pub denominator: Ticker,
+    pub base: Ticker,
+}
+
+impl DerivedTicker {
+    /// The tickers whose prices this ticker is derived from.
+    pub fn components(&self) -> [Ticker; 3] {
+        [self.numerator, self.denominator, self.base]
+    }
+
+    /// Derive a price from the prices of the numerator, denominator and base.
+    pub fn derive(
+        numerator: AssetPrice,
+        denominator: AssetPrice,
+        base: AssetPrice,
+    ) -> Option<AssetPrice> {
+        numerator.checked_mul(base)?.checked_div(denominator)
+    }
+}
+
 impl our_std::str::FromStr for Ticker {
     type Err = OracleError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -8,6 +8,14 @@ use types_derive::{type_alias, Types};
 #[type_alias]
 pub type Reporter = [u8; 20];
 
+/// Type for the Ethereum address a validator signs with.
+#[type_alias]
+pub type ValidatorAddress = [u8; 20];
+
+/// Type for a validator's signature attesting to prices read from Ethereum.
+#[type_alias]
+pub type ValidatorSignature = [u8; 65];
+
 /// Type for representing time since current Unix epoch in milliseconds.
 #[type_alias("Oracle__")]
 pub type Timestamp = timestamp::Timestamp; //u64;
@@ -41,6 +49,50 @@ impl Price {
     }
 }
 
+/// A Chainlink aggregator to fall back on for a ticker, and the number of decimals in its answers.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct ChainlinkFeed {
+    pub aggregator: [u8; 20],
+    pub decimals: u8,
+}
+
+/// A Uniswap V3 pool to take a time-weighted average price from, for an asset against a USD stablecoin.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct UniswapTwapFeed {
+    pub pool: [u8; 20],
+    pub twap_window_secs: u32,
+    /// Whether the asset is token0 of the pool, otherwise it is token1 and the stablecoin token0.
+    pub asset_is_token0: bool,
+    pub asset_decimals: u8,
+    pub quote_decimals: u8,
+}
+
+/// Type for the source which a stored price was posted from.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum PriceSource {
+    OpenPriceFeed,
+    Chainlink,
+}
+
+/// Limits on how far a single price update may move from the recent time-weighted average price.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct PriceDeviationGuard {
+    /// The maximum deviation of an update from the average, in basis points.
+    pub max_deviation_bps: u32,
+    /// The window of time over which the average price is taken.
+    pub twap_window_ms: Timestamp,
+    /// The number of confirming updates needed before a deviating price is accepted.
+    pub confirmations: u32,
+}
+
+/// Type for a deviating price update being held until it is confirmed.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct PendingPrice {
+    pub value: AssetPrice,
+    pub timestamp: Timestamp,
+    pub confirmations: u32,
+}
+
 /// Type for a set of open price feed reporters.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, Types)]
 pub struct ReporterSet(pub Vec<Reporter>);
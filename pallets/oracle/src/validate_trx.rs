@@ -14,6 +14,7 @@ pub enum ValidationError {
     InvalidPrice(OracleError),
     InvalidCall,
     ExcessivePrices,
+    InvalidValidator,
 }
 
 pub fn check_validation_failure<T: Config>(
@@ -81,12 +82,13 @@ pub fn validate_unsigned<T: Config>(
                 }
             };
 
-            pairs
-                .into_iter()
-                .fold(if_valid, |acc, (payload, signature)| match acc {
+            let valid_signatures = oracle::check_signatures::<T>(pairs);
+            pairs.into_iter().zip(valid_signatures).fold(
+                if_valid,
+                |acc, ((payload, _), valid_signature)| match acc {
                     Err(err) => Err(err),
                     Ok(validation) => {
-                        if oracle::check_signature::<T>(&payload, &signature) == Ok(true) {
+                        if valid_signature == Ok(true) {
                             match source {
                                 TransactionSource::Local | TransactionSource::InBlock => {
                                     Ok(validation)
@@ -100,7 +102,44 @@ pub fn validate_unsigned<T: Config>(
                             Err(ValidationError::InvalidPriceSignature)
                         }
                     }
-                })
+                },
+            )
+        }
+        Call::post_chainlink_prices(prices, signature) => {
+            let message = oracle::validator_price_message(oracle::CHAINLINK_PRICES_PREFIX, prices);
+            let validator = oracle::recover_validator::<T>(&message, signature)
+                .map_err(|_| ValidationError::InvalidValidator)?;
+
+            let mut validity = ValidTransaction::with_tag_prefix("Gateway::post_chainlink_prices")
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .propagate(true);
+
+            for (ticker, _, updated_at) in prices {
+                oracle::check_chainlink_price::<T>(*ticker, *updated_at)
+                    .map_err(ValidationError::InvalidPrice)?;
+                validity = validity.and_provides((validator, ticker, updated_at));
+            }
+
+            Ok(validity.build())
+        }
+        Call::post_dex_prices(prices, signature) => {
+            let message = oracle::validator_price_message(oracle::DEX_PRICES_PREFIX, prices);
+            let validator = oracle::recover_validator::<T>(&message, signature)
+                .map_err(|_| ValidationError::InvalidValidator)?;
+
+            let mut validity = ValidTransaction::with_tag_prefix("Gateway::post_dex_prices")
+                .priority(UNSIGNED_TXS_PRIORITY)
+                .longevity(UNSIGNED_TXS_LONGEVITY)
+                .propagate(true);
+
+            for (ticker, _, timestamp) in prices {
+                oracle::check_dex_price::<T>(*ticker, &validator, *timestamp)
+                    .map_err(ValidationError::InvalidPrice)?;
+                validity = validity.and_provides((validator, ticker, timestamp));
+            }
+
+            Ok(validity.build())
         }
         _ => Err(ValidationError::InvalidCall),
     }
@@ -270,4 +309,21 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_post_chainlink_prices_not_a_validator() {
+        new_test_ext().execute_with(|| {
+            MockValidators::set(vec![validator_address(VALIDATOR_A_KEY)]);
+            let prices = vec![(ETH_TICKER, 2_000_000_000, 1_000)];
+            let signature =
+                validator_sign_prices(VALIDATOR_B_KEY, oracle::CHAINLINK_PRICES_PREFIX, &prices);
+            assert_eq!(
+                validate_unsigned(
+                    TransactionSource::External {},
+                    &Call::post_chainlink_prices::<Test>(prices, signature),
+                ),
+                Err(ValidationError::InvalidValidator)
+            );
+        });
+    }
 }
@@ -0,0 +1,24 @@
+use codec::{Decode, Encode};
+use frame_support;
+use our_std::Debuggable;
+
+use types_derive::Types;
+
+/// Errors coming from the parameters.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Debuggable, Types)]
+pub enum ParameterError {
+    OutOfBounds,
+}
+
+impl From<ParameterError> for frame_support::dispatch::DispatchError {
+    fn from(err: ParameterError) -> frame_support::dispatch::DispatchError {
+        let (index, error, message) = match err {
+            ParameterError::OutOfBounds => (0, 0, "OutOfBounds"),
+        };
+        frame_support::dispatch::DispatchError::Module {
+            index,
+            error,
+            message: Some(message),
+        }
+    }
+}
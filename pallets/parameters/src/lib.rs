@@ -0,0 +1,103 @@
+use crate::{error::ParameterError, types::ParameterValue};
+use frame_support::{
+    decl_event, decl_module, decl_storage, dispatch,
+    weights::{DispatchClass, Pays},
+};
+use frame_system::ensure_root;
+use our_std::log;
+
+pub mod error;
+pub mod parameters;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
+
+/// Default number of blocks in between periodic sessions.
+pub const DEFAULT_SESSION_PERIOD: u32 = 14400; // Assuming 6s blocks, ~1 period per day
+
+/// Bounds on the number of blocks in between periodic sessions.
+pub const MIN_SESSION_PERIOD: u32 = 600; // ~1 hour
+pub const MAX_SESSION_PERIOD: u32 = 100800; // ~1 week
+
+/// Default maximum size of the block queue before we back-off sending new blocks.
+pub const DEFAULT_INGRESS_SLACK: u32 = 50;
+
+/// Bounds on the maximum size of the block queue.
+pub const MIN_INGRESS_SLACK: u32 = 1;
+pub const MAX_INGRESS_SLACK: u32 = 1000;
+
+/// Default minimum value (USD) required across all protocol interactions, i.e. $1.
+pub const DEFAULT_MIN_TX_VALUE: u128 = 1_000000;
+
+/// Bounds on the minimum value (USD) required across all protocol interactions.
+pub const MIN_MIN_TX_VALUE: u128 = 1;
+pub const MAX_MIN_TX_VALUE: u128 = 1000_000000;
+
+/// Default liquidation incentive on seized collateral (e.g. 8% = 800 bips).
+pub const DEFAULT_GLOBAL_LIQUIDATION_INCENTIVE: u128 = 800;
+
+/// Maximum liquidation incentive on seized collateral (bips), i.e. seizing double the value repaid.
+pub const MAX_LIQUIDATION_INCENTIVE: u128 = 10000;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Config: frame_system::Config {
+    /// Because this pallet emits events, it depends on the runtime's definition of an event.
+    type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
+}
+
+decl_storage! {
+    trait Store for Module<T: Config> as Parameters {
+        /// The number of blocks in between periodic sessions.
+        pub SessionPeriod get(fn session_period): u32 = DEFAULT_SESSION_PERIOD;
+
+        /// The maximum number of underlying chain blocks queued for ingestion, before we back-off sending new ones.
+        pub IngressSlack get(fn ingress_slack): u32 = DEFAULT_INGRESS_SLACK;
+
+        /// The minimum value (USD) required across all protocol interactions.
+        pub MinTxValue get(fn min_tx_value): u128 = DEFAULT_MIN_TX_VALUE;
+
+        /// The liquidation incentive on seized collateral (bips).
+        /// Used for any collateral which does not set its own incentive.
+        pub GlobalLiquidationIncentive get(fn global_liquidation_incentive): u128 = DEFAULT_GLOBAL_LIQUIDATION_INCENTIVE;
+    }
+}
+
+/* ::EVENTS:: */
+
+decl_event!(
+    pub enum Event {
+        /// Failed to process a given extrinsic. [reason]
+        Failure(ParameterError),
+
+        /// A parameter was set. [value]
+        SetParameter(ParameterValue),
+    }
+);
+
+/* ::ERRORS:: */
+
+fn check_failure<T: Config>(res: Result<(), ParameterError>) -> Result<(), ParameterError> {
+    if let Err(err) = res {
+        <Module<T>>::deposit_event(Event::Failure(err));
+        log!("Parameters Failure {:#?}", err);
+    }
+    res
+}
+
+/* ::MODULE:: */
+/* ::EXTRINSICS:: */
+
+decl_module! {
+    pub struct Module<T: Config> for enum Call where origin: T::Origin {
+        // Events must be initialized if they are used by the pallet.
+        fn deposit_event() = default;
+
+        /// Set a tunable parameter, within its bounds. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_parameter(origin, value: ParameterValue) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(parameters::set_parameter::<T>(value))?)
+        }
+    }
+}
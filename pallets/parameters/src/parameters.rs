@@ -0,0 +1,93 @@
+use crate::{
+    error::ParameterError, types::ParameterValue, Config, Event, GlobalLiquidationIncentive,
+    IngressSlack, MinTxValue, Module, SessionPeriod, MAX_INGRESS_SLACK, MAX_LIQUIDATION_INCENTIVE,
+    MAX_MIN_TX_VALUE, MAX_SESSION_PERIOD, MIN_INGRESS_SLACK, MIN_MIN_TX_VALUE, MIN_SESSION_PERIOD,
+};
+use frame_support::storage::StorageValue;
+
+/// Check a parameter value is within the bounds for the parameter.
+pub fn check_bounds(value: ParameterValue) -> Result<(), ParameterError> {
+    let in_bounds = match value {
+        ParameterValue::SessionPeriod(period) => {
+            period >= MIN_SESSION_PERIOD && period <= MAX_SESSION_PERIOD
+        }
+        ParameterValue::IngressSlack(slack) => {
+            slack >= MIN_INGRESS_SLACK && slack <= MAX_INGRESS_SLACK
+        }
+        ParameterValue::MinTxValue(value) => value >= MIN_MIN_TX_VALUE && value <= MAX_MIN_TX_VALUE,
+        ParameterValue::GlobalLiquidationIncentive(bips) => bips <= MAX_LIQUIDATION_INCENTIVE,
+    };
+    if in_bounds {
+        Ok(())
+    } else {
+        Err(ParameterError::OutOfBounds)
+    }
+}
+
+/// Set a tunable parameter, within its bounds.
+pub fn set_parameter<T: Config>(value: ParameterValue) -> Result<(), ParameterError> {
+    check_bounds(value)?;
+    match value {
+        ParameterValue::SessionPeriod(period) => SessionPeriod::put(period),
+        ParameterValue::IngressSlack(slack) => IngressSlack::put(slack),
+        ParameterValue::MinTxValue(value) => MinTxValue::put(value),
+        ParameterValue::GlobalLiquidationIncentive(bips) => GlobalLiquidationIncentive::put(bips),
+    }
+    <Module<T>>::deposit_event(Event::SetParameter(value));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::*, DEFAULT_MIN_TX_VALUE, DEFAULT_SESSION_PERIOD};
+
+    #[test]
+    fn test_set_parameter_checks_bounds() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(ParametersModule::session_period(), DEFAULT_SESSION_PERIOD);
+            assert_eq!(
+                set_parameter::<Test>(ParameterValue::SessionPeriod(MIN_SESSION_PERIOD - 1)),
+                Err(ParameterError::OutOfBounds)
+            );
+            assert_eq!(
+                set_parameter::<Test>(ParameterValue::MinTxValue(0)),
+                Err(ParameterError::OutOfBounds)
+            );
+            assert_eq!(
+                set_parameter::<Test>(ParameterValue::GlobalLiquidationIncentive(
+                    MAX_LIQUIDATION_INCENTIVE + 1
+                )),
+                Err(ParameterError::OutOfBounds)
+            );
+            assert_eq!(ParametersModule::session_period(), DEFAULT_SESSION_PERIOD);
+            assert_eq!(ParametersModule::min_tx_value(), DEFAULT_MIN_TX_VALUE);
+            assert_eq!(System::events().len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_set_parameter() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(set_parameter::<Test>(ParameterValue::SessionPeriod(600)));
+            assert_ok!(set_parameter::<Test>(ParameterValue::IngressSlack(10)));
+            assert_ok!(set_parameter::<Test>(ParameterValue::MinTxValue(5_000000)));
+            assert_ok!(set_parameter::<Test>(
+                ParameterValue::GlobalLiquidationIncentive(1000)
+            ));
+            assert_eq!(ParametersModule::session_period(), 600);
+            assert_eq!(ParametersModule::ingress_slack(), 10);
+            assert_eq!(ParametersModule::min_tx_value(), 5_000000);
+            assert_eq!(ParametersModule::global_liquidation_incentive(), 1000);
+
+            let events = System::events();
+            assert_eq!(events.len(), 4);
+            assert_eq!(
+                events[0].event,
+                mock::Event::pallet_parameters(Event::SetParameter(ParameterValue::SessionPeriod(
+                    600
+                )))
+            );
+        });
+    }
+}
@@ -0,0 +1,6 @@
+use crate::*;
+
+pub use frame_support::{assert_err, assert_ok, dispatch::DispatchError};
+
+pub mod mock;
+pub use mock::*;
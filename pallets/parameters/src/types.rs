@@ -0,0 +1,17 @@
+use codec::{Decode, Encode};
+use our_std::RuntimeDebug;
+
+use types_derive::Types;
+
+/// A tunable parameter, and the value to set it to.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub enum ParameterValue {
+    /// The number of blocks in between periodic sessions.
+    SessionPeriod(u32),
+    /// The maximum number of underlying chain blocks queued for ingestion.
+    IngressSlack(u32),
+    /// The minimum value (USD, with 6 decimals) required across all protocol interactions.
+    MinTxValue(u128),
+    /// The liquidation incentive on seized collateral (bips).
+    GlobalLiquidationIncentive(u128),
+}
@@ -11,8 +11,19 @@ pub struct ValidatorConfig {
     pub map: HashMap<String, String>,
 }
 
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "std")]
+pub mod secrets;
+
 pub type PriceFeedData = (Vec<(Vec<u8>, Vec<u8>)>, u64);
 
+/// The timeout (milliseconds) and extra headers for HTTP requests to an endpoint, if configured.
+pub type HttpOptions = (Option<u64>, Vec<(String, String)>);
+
+/// Loads the validator config from wherever the node is configured, e.g. its command line and config file.
+pub type ValidatorConfigLoader = Box<dyn Fn() -> Result<HashMap<String, String>, String> + Send>;
+
 lazy_static! {
     static ref VALIDATOR_CONFIG_DEFAULTS: Mutex<ValidatorConfig> = Mutex::new(ValidatorConfig {
         map: HashMap::from_iter([
@@ -24,11 +35,16 @@ lazy_static! {
                 ETH_RPC_URL_ENV_VAR.to_string(),
                 ETH_RPC_URL_DEFAULT.to_string()
             ),
+            (
+                ED25519_KEY_ID_ENV_VAR.to_string(),
+                ED25519_KEY_ID_DEFAULT.to_string()
+            ),
             (MINER_ENV_VAR.to_string(), MINER_DEFAULT.to_string()),
             (OPF_URL_ENV_VAR.to_string(), OPF_URL_DEFAULT.to_string())
         ])
     });
     static ref VALIDATOR_CONFIG: Mutex<Option<ValidatorConfig>> = Mutex::new(None);
+    static ref VALIDATOR_CONFIG_LOADER: Mutex<Option<ValidatorConfigLoader>> = Mutex::new(None);
     static ref PRICE_FEED_DATA: Mutex<Option<PriceFeedData>> = Mutex::new(None);
 }
 
@@ -41,24 +57,83 @@ pub fn initialize_validator_config(map: HashMap<String, String>) {
     };
 }
 
+/// Set how the validator config is loaded, and load it.
+/// The config may then be loaded again while running with `reload_validator_config`.
+pub fn initialize_validator_config_loader(loader: ValidatorConfigLoader) -> Result<(), String> {
+    match VALIDATOR_CONFIG_LOADER.lock() {
+        Ok(mut loader_ref) => *loader_ref = Some(loader),
+        _ => return Err("Validator config loader poisoned".into()),
+    };
+    reload_validator_config().map(|_keys| ())
+}
+
+/// Load the validator config again, swapping in the whole config at once, and return the keys it sets.
+/// The config in use is kept if it fails to load, e.g. the config file has gone missing.
+pub fn reload_validator_config() -> Result<Vec<String>, String> {
+    let map = match VALIDATOR_CONFIG_LOADER.lock() {
+        Ok(loader_ref) => match loader_ref.as_ref() {
+            Some(loader) => loader()?,
+            None => return Err("Validator config loader not initialized".into()),
+        },
+        _ => return Err("Validator config loader poisoned".into()),
+    };
+    let mut keys: Vec<String> = map.keys().cloned().collect();
+    keys.sort();
+    initialize_validator_config(map);
+    Ok(keys)
+}
+
 const ETH_KEY_ID_ENV_VAR: &str = "ETH_KEY_ID";
+const ED25519_KEY_ID_ENV_VAR: &str = "ED25519_KEY_ID";
 const ETH_RPC_URL_ENV_VAR: &str = "ETH_RPC_URL";
 const MATIC_RPC_URL_ENV_VAR: &str = "MATIC_RPC_URL";
 const MINER_ENV_VAR: &str = "MINER";
 const OPF_URL_ENV_VAR: &str = "OPF_URL";
+const PKCS11_MODULE_ENV_VAR: &str = "PKCS11_MODULE";
+const PKCS11_SLOT_ENV_VAR: &str = "PKCS11_SLOT";
+const PKCS11_PIN_ENV_VAR: &str = "PKCS11_PIN";
+const THRESHOLD_KEY_ID_ENV_VAR: &str = "THRESHOLD_KEY_ID";
+const ETH_RPC_TIMEOUT_MS_ENV_VAR: &str = "ETH_RPC_TIMEOUT_MS";
+const ETH_RPC_HEADERS_ENV_VAR: &str = "ETH_RPC_HEADERS";
+const MATIC_RPC_TIMEOUT_MS_ENV_VAR: &str = "MATIC_RPC_TIMEOUT_MS";
+const MATIC_RPC_HEADERS_ENV_VAR: &str = "MATIC_RPC_HEADERS";
+const OPF_TIMEOUT_MS_ENV_VAR: &str = "OPF_TIMEOUT_MS";
+const OPF_HEADERS_ENV_VAR: &str = "OPF_HEADERS";
+const AUDIT_INTERVAL_ENV_VAR: &str = "AUDIT_INTERVAL";
 
 const ETH_KEY_ID_DEFAULT: &str = gateway_crypto::ETH_KEY_ID_ENV_VAR_DEV_DEFAULT;
+const ED25519_KEY_ID_DEFAULT: &str = gateway_crypto::ED25519_KEY_ID_ENV_VAR_DEV_DEFAULT;
 const MINER_DEFAULT: &str = "Eth:0x0000000000000000000000000000000000000000";
 const ETH_RPC_URL_DEFAULT: &str = "https://ropsten-eth.compound.finance";
 const OPF_URL_DEFAULT: &str = "https://prices.compound.finance/coinbase";
 
 fn validator_config_interface_get_internal(key: &str) -> Option<String> {
     // check env override
-    if let Ok(value_from_env) = std::env::var(key) {
-        if value_from_env.len() > 0 {
-            return Some(value_from_env);
-        }
+    if let Some(value) = validator_config_get_env(key) {
+        return Some(value);
+    }
+    // check secrets, from vault or a mounted file
+    #[cfg(feature = "std")]
+    if let Some(value) = secrets::get_secret(key, validator_config_get_unsecret) {
+        return Some(value);
     }
+    validator_config_get_config(key)
+}
+
+/// Get a validator config value without looking up secrets, as used to configure the secrets source.
+#[cfg(feature = "std")]
+fn validator_config_get_unsecret(key: &str) -> Option<String> {
+    validator_config_get_env(key).or_else(|| validator_config_get_config(key))
+}
+
+fn validator_config_get_env(key: &str) -> Option<String> {
+    match std::env::var(key) {
+        Ok(value_from_env) if value_from_env.len() > 0 => Some(value_from_env),
+        _ => None,
+    }
+}
+
+fn validator_config_get_config(key: &str) -> Option<String> {
     // check config
     if let Ok(config) = VALIDATOR_CONFIG.lock() {
         if let Some(inner) = config.as_ref() {
@@ -75,6 +150,76 @@ fn validator_config_interface_get_internal(key: &str) -> Option<String> {
     return None;
 }
 
+/// The PKCS#11 token to use for keys with pkcs11: key ids, if a module is configured.
+fn pkcs11_config() -> Option<gateway_crypto::Pkcs11Config> {
+    pkcs11_config_from(validator_config_interface_get_internal)
+}
+
+fn pkcs11_config_from(
+    get: impl Fn(&str) -> Option<String>,
+) -> Option<gateway_crypto::Pkcs11Config> {
+    let module = get(PKCS11_MODULE_ENV_VAR)?;
+    let slot = get(PKCS11_SLOT_ENV_VAR)
+        .and_then(|slot| slot.parse().ok())
+        .unwrap_or(0);
+    let pin = get(PKCS11_PIN_ENV_VAR).unwrap_or_default();
+    Some(gateway_crypto::Pkcs11Config { module, slot, pin })
+}
+
+/// The HTTP options for requests to the given URL, if it is one of the configured endpoints.
+fn http_options_from(url: &str, get: impl Fn(&str) -> Option<String>) -> HttpOptions {
+    let endpoints = [
+        (
+            ETH_RPC_URL_ENV_VAR,
+            ETH_RPC_TIMEOUT_MS_ENV_VAR,
+            ETH_RPC_HEADERS_ENV_VAR,
+        ),
+        (
+            MATIC_RPC_URL_ENV_VAR,
+            MATIC_RPC_TIMEOUT_MS_ENV_VAR,
+            MATIC_RPC_HEADERS_ENV_VAR,
+        ),
+        (OPF_URL_ENV_VAR, OPF_TIMEOUT_MS_ENV_VAR, OPF_HEADERS_ENV_VAR),
+    ];
+    for (url_key, timeout_key, headers_key) in endpoints.iter() {
+        let urls = get(url_key)
+            .map(|urls| split_urls(&urls))
+            .unwrap_or_default();
+        if urls.iter().any(|u| u == url) {
+            let timeout = get(timeout_key).and_then(|timeout| timeout.parse().ok());
+            let headers = get(headers_key)
+                .map(|headers| split_headers(&headers))
+                .unwrap_or_default();
+            return (timeout, headers);
+        }
+    }
+    (None, vec![])
+}
+
+/// Split headers given as `Name: value` pairs separated by `;`, e.g. `Authorization: Bearer abc`.
+fn split_headers(headers: &str) -> Vec<(String, String)> {
+    headers
+        .split(';')
+        .filter_map(|header| {
+            let i = header.find(':')?;
+            let (name, value) = header.split_at(i);
+            let name = name.trim();
+            if name.len() == 0 {
+                return None;
+            }
+            Some((name.to_string(), value[1..].trim().to_string()))
+        })
+        .collect()
+}
+
+fn split_urls(urls: &str) -> Vec<String> {
+    urls.split(',')
+        .map(str::trim)
+        .filter(|url| url.len() > 0)
+        .map(String::from)
+        .collect()
+}
+
 /// The ValidatorConfigInterface is designed to be modified as needed by the validators. This means
 /// that each validator should be modifying the values here. For example, the ETH_KEY_ID is set
 /// by each validator separately corresponding to their HSM configuration and key ID that they
@@ -85,6 +230,10 @@ fn validator_config_interface_get_internal(key: &str) -> Option<String> {
 /// It is also not a file based configuration but an environment variable based configuration.
 /// This allows for more straightforward configuration in some cases and separates values that
 /// are integral to the system from validator specific values that otherwise would be intermingled.
+///
+/// Secrets may instead be kept in HashiCorp Vault or a file mounted by a secrets manager, see
+/// the `secrets` module. These take precedence over the command line, and are read again as they
+/// change, so they can be rotated without restarting the node.
 #[sp_runtime_interface::runtime_interface]
 pub trait ValidatorConfigInterface {
     /// Generic get function for validator specific configuration
@@ -100,6 +249,18 @@ pub trait ValidatorConfigInterface {
         validator_config_interface_get_internal(ETH_KEY_ID_ENV_VAR).map(Into::into)
     }
 
+    /// Get the Key ID for the Ed25519 key, used for chains verifying Ed25519 signatures.
+    fn get_ed25519_key_id() -> Option<Vec<u8>> {
+        validator_config_interface_get_internal(ED25519_KEY_ID_ENV_VAR).map(Into::into)
+    }
+
+    /// Get the Key ID for this validator's share of the threshold key, if notices are signed by threshold.
+    ///
+    /// The keyring holding the share is expected to run the signing protocol with the other validators.
+    fn get_threshold_key_id() -> Option<Vec<u8>> {
+        validator_config_interface_get_internal(THRESHOLD_KEY_ID_ENV_VAR).map(Into::into)
+    }
+
     /// Get the Ethereum node RPC URL
     fn get_eth_rpc_url() -> Option<String> {
         validator_config_interface_get_internal(ETH_RPC_URL_ENV_VAR)
@@ -115,10 +276,34 @@ pub trait ValidatorConfigInterface {
         validator_config_interface_get_internal(OPF_URL_ENV_VAR)
     }
 
+    /// Get the open price feed URLs, given as a comma separated list, in order of preference
+    fn get_opf_urls() -> Vec<String> {
+        validator_config_interface_get_internal(OPF_URL_ENV_VAR)
+            .map(|urls| split_urls(&urls))
+            .unwrap_or_default()
+    }
+
+    /// Get the timeout and extra headers for HTTP requests to the given URL, if it is one of the
+    /// configured Ethereum, Matic or open price feed endpoints, e.g. to authenticate with an API key
+    fn get_http_options(url: &str) -> HttpOptions {
+        http_options_from(url, validator_config_interface_get_internal)
+    }
+
     /// Get the Miner address
     fn get_miner_address() -> Option<Vec<u8>> {
         validator_config_interface_get_internal(MINER_ENV_VAR).map(Into::into)
     }
+
+    /// Get the path to the PKCS#11 module of the HSM holding the Ethereum key, if any
+    fn get_pkcs11_module() -> Option<String> {
+        validator_config_interface_get_internal(PKCS11_MODULE_ENV_VAR)
+    }
+
+    /// Get the number of blocks between audits of the protocol's invariants, if auditing
+    fn get_audit_interval() -> Option<u32> {
+        validator_config_interface_get_internal(AUDIT_INTERVAL_ENV_VAR)
+            .and_then(|interval| interval.parse().ok())
+    }
 }
 
 #[sp_runtime_interface::runtime_interface]
@@ -131,24 +316,49 @@ pub trait KeyringInterface {
         messages: Vec<Vec<u8>>,
         key_id: Vec<u8>,
     ) -> Result<Vec<Result<gateway_crypto::SignatureBytes, CryptoError>>, CryptoError> {
-        let keyring = gateway_crypto::keyring();
+        let keyring = gateway_crypto::keyring_with_pkcs11(pkcs11_config());
         let key_id = gateway_crypto::KeyId::from_utf8(key_id)?;
         let messages: Vec<&[u8]> = messages.iter().map(|e| e.as_slice()).collect();
         keyring.sign(messages, &key_id)
     }
 
     fn sign_one(message: Vec<u8>, key_id: Vec<u8>) -> Result<[u8; 65], CryptoError> {
-        let keyring = gateway_crypto::keyring();
+        let keyring = gateway_crypto::keyring_with_pkcs11(pkcs11_config());
         let key_id = gateway_crypto::KeyId::from_utf8(key_id)?;
         keyring.sign_one(&message, &key_id)
     }
 
     fn get_public_key(key_id: Vec<u8>) -> Result<[u8; 64], CryptoError> {
-        let keyring = gateway_crypto::keyring();
+        let keyring = gateway_crypto::keyring_with_pkcs11(pkcs11_config());
         let key_id = gateway_crypto::KeyId::from_utf8(key_id)?;
         keyring.get_public_key(&key_id)
     }
 
+    fn sign_ed25519_one(
+        message: Vec<u8>,
+        key_id: Vec<u8>,
+    ) -> Result<gateway_crypto::Ed25519SignatureBytes, CryptoError> {
+        let keyring = gateway_crypto::keyring_with_pkcs11(pkcs11_config());
+        let key_id = gateway_crypto::KeyId::from_utf8(key_id)?;
+        keyring.sign_ed25519_one(&message, &key_id)
+    }
+
+    fn get_ed25519_public_key(
+        key_id: Vec<u8>,
+    ) -> Result<gateway_crypto::Ed25519PublicKeyBytes, CryptoError> {
+        let keyring = gateway_crypto::keyring_with_pkcs11(pkcs11_config());
+        let key_id = gateway_crypto::KeyId::from_utf8(key_id)?;
+        keyring.get_ed25519_public_key(&key_id)
+    }
+
+    fn ed25519_verify(
+        message: Vec<u8>,
+        sig: gateway_crypto::Ed25519SignatureBytes,
+        public_key: gateway_crypto::Ed25519PublicKeyBytes,
+    ) -> bool {
+        gateway_crypto::ed25519_verify(&message, &sig, &public_key)
+    }
+
     /// Note - it is possible to run gateway_crypto in no-std / wasm environment but it is simply
     /// too slow to be feasible. We moved it out of gateway_crypto::no_std and now access eth_recover
     /// via this runtime interface.
@@ -159,6 +369,20 @@ pub trait KeyringInterface {
     ) -> Result<gateway_crypto::AddressBytes, CryptoError> {
         gateway_crypto::eth_recover(&message, &sig, prepend_preamble)
     }
+
+    /// Recover the signer of each message, crossing into the host once for the whole batch.
+    /// Messages and signatures are paired up in order, giving a result for each pair.
+    fn eth_recover_many(
+        messages: Vec<Vec<u8>>,
+        sigs: Vec<gateway_crypto::SignatureBytes>,
+        prepend_preamble: bool,
+    ) -> Vec<Result<gateway_crypto::AddressBytes, CryptoError>> {
+        messages
+            .iter()
+            .zip(sigs.iter())
+            .map(|(message, sig)| gateway_crypto::eth_recover(message, sig, prepend_preamble))
+            .collect()
+    }
 }
 
 #[sp_runtime_interface::runtime_interface]
@@ -187,6 +411,21 @@ pub trait PriceFeedInterface {
     }
 }
 
+#[sp_runtime_interface::runtime_interface]
+pub trait MetricsInterface {
+    /// Add to the offchain worker counter with the given name, e.g. blocks fetched.
+    fn inc_counter(name: &str, by: u64) {
+        #[cfg(feature = "std")]
+        metrics::inc_counter(name, by);
+    }
+
+    /// Record the time (milliseconds) taken by an offchain worker request, e.g. an Ethereum RPC call.
+    fn observe_ms(name: &str, millis: u64) {
+        #[cfg(feature = "std")]
+        metrics::observe_latency(name, millis);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,5 +449,108 @@ mod tests {
         // nonsense value is none
         let actual_nonsense = validator_config_interface_get_internal("nonsense");
         assert!(actual_nonsense.is_none());
+
+        // reloaded value replaces the config
+        let miner = std::sync::Arc::new(Mutex::new("my miner"));
+        let loaded = miner.clone();
+        initialize_validator_config_loader(Box::new(move || {
+            let miner = *loaded.lock().unwrap();
+            Ok(HashMap::from_iter([(
+                MINER_ENV_VAR.to_string(),
+                miner.to_string(),
+            )]))
+        }))
+        .unwrap();
+        *miner.lock().unwrap() = "my other miner";
+        assert_eq!(
+            reload_validator_config(),
+            Ok(vec![MINER_ENV_VAR.to_string()])
+        );
+        let actual_miner = validator_config_interface_get_internal(MINER_ENV_VAR).unwrap();
+        assert_eq!(actual_miner, "my other miner".to_string());
+    }
+
+    #[test]
+    fn test_eth_recover_many() {
+        use gateway_crypto::Keyring;
+        let keyring = gateway_crypto::dev_keyring();
+        let key_id = gateway_crypto::KeyId::from_utf8(ETH_KEY_ID_DEFAULT.into()).unwrap();
+        let address = keyring.get_eth_address(&key_id).unwrap();
+        let messages: Vec<Vec<u8>> = vec![b"hello".to_vec(), b"goodbye".to_vec()];
+        let sig_a = keyring.sign_one(&messages[0], &key_id).unwrap();
+        let sig_b = keyring.sign_one(&messages[1], &key_id).unwrap();
+
+        let recovered =
+            keyring_interface::eth_recover_many(messages.clone(), vec![sig_a, sig_b], false);
+        assert_eq!(recovered, vec![Ok(address), Ok(address)]);
+
+        let recovered = keyring_interface::eth_recover_many(messages, vec![sig_b, sig_a], false);
+        assert_eq!(recovered.len(), 2);
+        assert!(recovered.iter().all(|r| r != &Ok(address)));
+    }
+
+    #[test]
+    fn test_pkcs11_config() {
+        let config: HashMap<String, String> = HashMap::from_iter([
+            (
+                PKCS11_MODULE_ENV_VAR.to_string(),
+                "/usr/lib/softhsm/libsofthsm2.so".to_string(),
+            ),
+            (PKCS11_SLOT_ENV_VAR.to_string(), "3".to_string()),
+            (PKCS11_PIN_ENV_VAR.to_string(), "1234".to_string()),
+        ]);
+        let pkcs11 = pkcs11_config_from(|key| config.get(key).cloned()).unwrap();
+        assert_eq!(pkcs11.module, "/usr/lib/softhsm/libsofthsm2.so");
+        assert_eq!(pkcs11.slot, 3);
+        assert_eq!(pkcs11.pin, "1234");
+
+        assert!(pkcs11_config_from(|_| None).is_none());
+    }
+
+    #[test]
+    fn test_http_options() {
+        let config: HashMap<String, String> = HashMap::from_iter([
+            (
+                ETH_RPC_URL_ENV_VAR.to_string(),
+                "https://mainnet.infura.io/v3".to_string(),
+            ),
+            (ETH_RPC_TIMEOUT_MS_ENV_VAR.to_string(), "5000".to_string()),
+            (
+                ETH_RPC_HEADERS_ENV_VAR.to_string(),
+                "Authorization: Bearer abc; X-Extra:1".to_string(),
+            ),
+            (
+                OPF_URL_ENV_VAR.to_string(),
+                "https://a.example.com,https://b.example.com".to_string(),
+            ),
+            (OPF_TIMEOUT_MS_ENV_VAR.to_string(), "nonsense".to_string()),
+        ]);
+        let get = |key: &str| config.get(key).cloned();
+
+        assert_eq!(
+            http_options_from("https://mainnet.infura.io/v3", get),
+            (
+                Some(5000),
+                vec![
+                    ("Authorization".to_string(), "Bearer abc".to_string()),
+                    ("X-Extra".to_string(), "1".to_string())
+                ]
+            )
+        );
+        assert_eq!(
+            http_options_from("https://b.example.com", get),
+            (None, vec![])
+        );
+        assert_eq!(http_options_from("https://other.com", get), (None, vec![]));
+    }
+
+    #[test]
+    fn test_split_urls() {
+        assert_eq!(split_urls(""), Vec::<String>::new());
+        assert_eq!(split_urls("http://a"), vec!["http://a"]);
+        assert_eq!(
+            split_urls("http://a, http://b,,http://c"),
+            vec!["http://a", "http://b", "http://c"]
+        );
     }
 }
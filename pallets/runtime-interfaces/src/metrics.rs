@@ -0,0 +1,100 @@
+use std::sync::Mutex;
+use substrate_prometheus_endpoint::{
+    exponential_buckets, register, CounterVec, HistogramOpts, HistogramVec, Opts, PrometheusError,
+    Registry, U64,
+};
+
+/// The metrics reported by the offchain workers, once registered with the node's Prometheus registry.
+struct Metrics {
+    counters: CounterVec<U64>,
+    latencies: HistogramVec,
+}
+
+lazy_static! {
+    static ref METRICS: Mutex<Option<Metrics>> = Mutex::new(None);
+}
+
+/// Register the offchain worker metrics, so that they are exposed to Prometheus.
+/// Metrics reported before they are registered are dropped.
+pub fn register_metrics(registry: &Registry) -> Result<(), PrometheusError> {
+    let counters = register(
+        CounterVec::new(
+            Opts::new(
+                "gateway_ocw_total",
+                "Number of things done by the offchain worker, e.g. blocks fetched",
+            ),
+            &["name"],
+        )?,
+        registry,
+    )?;
+    let latencies = register(
+        HistogramVec::new(
+            HistogramOpts::new(
+                "gateway_ocw_latency_seconds",
+                "Time taken by requests made by the offchain worker, e.g. Ethereum RPC calls",
+            )
+            .buckets(exponential_buckets(0.01, 2.0, 12)?),
+            &["name"],
+        )?,
+        registry,
+    )?;
+    if let Ok(mut metrics) = METRICS.lock() {
+        *metrics = Some(Metrics {
+            counters,
+            latencies,
+        });
+    }
+    Ok(())
+}
+
+/// Add to the counter with the given name.
+pub fn inc_counter(name: &str, by: u64) {
+    if let Ok(metrics) = METRICS.lock() {
+        if let Some(metrics) = metrics.as_ref() {
+            metrics.counters.with_label_values(&[name]).inc_by(by);
+        }
+    }
+}
+
+/// Record the time (milliseconds) taken by a request, in the histogram with the given name.
+pub fn observe_latency(name: &str, millis: u64) {
+    if let Ok(metrics) = METRICS.lock() {
+        if let Some(metrics) = metrics.as_ref() {
+            metrics
+                .latencies
+                .with_label_values(&[name])
+                .observe(millis as f64 / 1000.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        // dropped until registered
+        inc_counter("blocks_fetched", 1);
+
+        let registry = Registry::new();
+        register_metrics(&registry).unwrap();
+        inc_counter("blocks_fetched", 2);
+        inc_counter("blocks_fetched", 3);
+        observe_latency("eth_rpc", 250);
+
+        let families = registry.gather();
+        let counters = families
+            .iter()
+            .find(|family| family.get_name() == "gateway_ocw_total")
+            .unwrap();
+        assert_eq!(counters.get_metric()[0].get_counter().get_value(), 5.0);
+        let latencies = families
+            .iter()
+            .find(|family| family.get_name() == "gateway_ocw_latency_seconds")
+            .unwrap();
+        let histogram = latencies.get_metric()[0].get_histogram();
+        assert_eq!(histogram.get_sample_count(), 1);
+        assert_eq!(histogram.get_sample_sum(), 0.25);
+    }
+}
@@ -0,0 +1,274 @@
+use hyper::{client::HttpConnector, Body, Client, Request};
+use hyper_tls::HttpsConnector;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::{runtime::Runtime, time::timeout};
+
+pub const SECRETS_FILE_ENV_VAR: &str = "SECRETS_FILE";
+pub const VAULT_ADDR_ENV_VAR: &str = "VAULT_ADDR";
+pub const VAULT_TOKEN_ENV_VAR: &str = "VAULT_TOKEN";
+pub const VAULT_SECRET_PATH_ENV_VAR: &str = "VAULT_SECRET_PATH";
+pub const SECRETS_REFRESH_SECS_ENV_VAR: &str = "SECRETS_REFRESH_SECS";
+
+const SECRETS_REFRESH_SECS_DEFAULT: u64 = 300;
+
+/// How long to wait on Vault before giving up, and using the secrets last read instead.
+const VAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where validator config secrets are kept, outside of the node's environment and command line.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SecretsSource {
+    /// A file of `KEY=VALUE` lines, such as one mounted by a secrets manager.
+    File(PathBuf),
+
+    /// A secret in HashiCorp Vault, read from either a KV version 1 or version 2 engine.
+    Vault {
+        addr: String,
+        token: String,
+        path: String,
+    },
+}
+
+#[derive(Debug)]
+enum SecretsError {
+    Io,
+    Http,
+    Parse,
+    Timeout,
+}
+
+/// The secrets last read from a source, and when, so they are only read again once stale.
+struct SecretsCache {
+    source: SecretsSource,
+    secrets: HashMap<String, String>,
+    fetched_at: Instant,
+    modified_at: Option<SystemTime>,
+}
+
+lazy_static! {
+    static ref SECRETS: Mutex<Option<SecretsCache>> = Mutex::new(None);
+}
+
+/// The source of secrets configured for the validator, if any.
+/// A secrets file takes precedence over Vault, if both are configured.
+pub fn secrets_source_from(get: impl Fn(&str) -> Option<String>) -> Option<SecretsSource> {
+    if let Some(path) = get(SECRETS_FILE_ENV_VAR) {
+        return Some(SecretsSource::File(PathBuf::from(path)));
+    }
+    let addr = get(VAULT_ADDR_ENV_VAR)?;
+    let token = get(VAULT_TOKEN_ENV_VAR)?;
+    let path = get(VAULT_SECRET_PATH_ENV_VAR)?;
+    Some(SecretsSource::Vault { addr, token, path })
+}
+
+/// Get a secret from the configured source, reading the source again if the secrets have gone stale.
+/// If the source cannot be read, the secrets last read from it are used instead.
+/// The source is read without holding the lock, so a slow read doesn't hold up other lookups,
+///  which use the secrets last read in the meantime.
+///
+/// The source itself is configured with the given getter, which must not look up secrets.
+pub fn get_secret(key: &str, get: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let source = secrets_source_from(&get)?;
+    let refresh = Duration::from_secs(
+        get(SECRETS_REFRESH_SECS_ENV_VAR)
+            .and_then(|secs| secs.parse().ok())
+            .unwrap_or(SECRETS_REFRESH_SECS_DEFAULT),
+    );
+    if claim_refresh(&source, refresh) {
+        let fetched = fetch_secrets(&source);
+        let mut cache = SECRETS.lock().ok()?;
+        if let Ok(secrets) = fetched {
+            *cache = Some(SecretsCache {
+                modified_at: match &source {
+                    SecretsSource::File(path) => modified_at(path),
+                    SecretsSource::Vault { .. } => None,
+                },
+                source: source.clone(),
+                secrets,
+                fetched_at: Instant::now(),
+            });
+        }
+    }
+    let cache = SECRETS.lock().ok()?;
+    match cache.as_ref() {
+        Some(cached) if cached.source == source => cached.secrets.get(key).cloned(),
+        _ => None,
+    }
+}
+
+/// Whether the secrets from a source have gone stale and should be read again by this caller.
+/// Cached secrets are marked as just read, so other callers keep using them meanwhile,
+///  and a source which cannot be read is retried no sooner than the next refresh.
+fn claim_refresh(source: &SecretsSource, refresh: Duration) -> bool {
+    let mut cache = match SECRETS.lock() {
+        Ok(cache) => cache,
+        Err(_) => return false,
+    };
+    match cache.as_mut() {
+        Some(cached) if cached.source == *source => {
+            let stale = match source {
+                SecretsSource::File(path) => cached.modified_at != modified_at(path),
+                SecretsSource::Vault { .. } => cached.fetched_at.elapsed() >= refresh,
+            };
+            if stale {
+                cached.fetched_at = Instant::now();
+            }
+            stale
+        }
+        _ => true,
+    }
+}
+
+fn modified_at(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn fetch_secrets(source: &SecretsSource) -> Result<HashMap<String, String>, SecretsError> {
+    match source {
+        SecretsSource::File(path) => {
+            let contents = std::fs::read_to_string(path).map_err(|_| SecretsError::Io)?;
+            Ok(parse_secrets_file(&contents))
+        }
+        SecretsSource::Vault { addr, token, path } => {
+            let mut rt = Runtime::new().map_err(|_| SecretsError::Io)?;
+            let response = rt
+                .block_on(timeout(
+                    VAULT_TIMEOUT,
+                    fetch_vault_secret(addr, token, path),
+                ))
+                .map_err(|_| SecretsError::Timeout)??;
+            parse_vault_secret(&response)
+        }
+    }
+}
+
+/// Parse a secrets or config file of `KEY=VALUE` lines, skipping blank lines and `#` comments.
+pub fn parse_secrets_file(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.len() > 0 && !line.starts_with('#'))
+        .filter_map(|line| {
+            let i = line.find('=')?;
+            let (key, value) = line.split_at(i);
+            Some((key.trim().to_string(), value[1..].trim().to_string()))
+        })
+        .collect()
+}
+
+async fn fetch_vault_secret(addr: &str, token: &str, path: &str) -> Result<Value, SecretsError> {
+    let client: Client<HttpsConnector<HttpConnector>> =
+        Client::builder().build(HttpsConnector::new());
+    let uri = format!(
+        "{}/v1/{}",
+        addr.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    );
+    let request = Request::get(uri)
+        .header("X-Vault-Token", token)
+        .body(Body::empty())
+        .map_err(|_| SecretsError::Http)?;
+    let response = client
+        .request(request)
+        .await
+        .map_err(|_| SecretsError::Http)?;
+    if !response.status().is_success() {
+        return Err(SecretsError::Http);
+    }
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .map_err(|_| SecretsError::Http)?;
+    serde_json::from_slice(&body).map_err(|_| SecretsError::Parse)
+}
+
+/// Parse the secret read from Vault, whose values are nested under `data.data` for KV version 2,
+/// or under `data` for KV version 1.
+fn parse_vault_secret(response: &Value) -> Result<HashMap<String, String>, SecretsError> {
+    let data = match &response["data"]["data"] {
+        Value::Object(data) => data,
+        _ => response["data"].as_object().ok_or(SecretsError::Parse)?,
+    };
+    Ok(data
+        .iter()
+        .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_secrets_source_from() {
+        let config: HashMap<String, String> = [
+            (VAULT_ADDR_ENV_VAR, "https://vault:8200"),
+            (VAULT_TOKEN_ENV_VAR, "s.token"),
+            (VAULT_SECRET_PATH_ENV_VAR, "secret/data/gateway"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(
+            secrets_source_from(|key| config.get(key).cloned()),
+            Some(SecretsSource::Vault {
+                addr: "https://vault:8200".into(),
+                token: "s.token".into(),
+                path: "secret/data/gateway".into(),
+            })
+        );
+        assert_eq!(
+            secrets_source_from(|key| match key {
+                SECRETS_FILE_ENV_VAR => Some("/run/secrets/gateway".into()),
+                _ => config.get(key).cloned(),
+            }),
+            Some(SecretsSource::File("/run/secrets/gateway".into()))
+        );
+        assert_eq!(secrets_source_from(|_| None), None);
+    }
+
+    #[test]
+    fn test_parse_secrets_file() {
+        let secrets = parse_secrets_file(
+            "# gateway secrets\n\nETH_KEY_ID = my_key\nETH_RPC_URL=https://eth?key=abc\nnonsense\n",
+        );
+        assert_eq!(secrets.len(), 2);
+        assert_eq!(secrets.get("ETH_KEY_ID"), Some(&"my_key".to_string()));
+        assert_eq!(
+            secrets.get("ETH_RPC_URL"),
+            Some(&"https://eth?key=abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_vault_secret() {
+        let v2 = json!({"data": {"data": {"ETH_KEY_ID": "my_key"}, "metadata": {"version": 3}}});
+        let v1 = json!({"data": {"ETH_KEY_ID": "my_key", "VERSION": 1}});
+        for response in [v2, v1].iter() {
+            let secrets = parse_vault_secret(response).unwrap();
+            assert_eq!(secrets.len(), 1);
+            assert_eq!(secrets.get("ETH_KEY_ID"), Some(&"my_key".to_string()));
+        }
+        assert!(parse_vault_secret(&json!({"errors": []})).is_err());
+    }
+
+    #[test]
+    fn test_get_secret_from_file() {
+        let path = std::env::temp_dir().join("gateway_test_get_secret_from_file");
+        std::fs::write(&path, "ETH_KEY_ID=first\n").unwrap();
+        let file = path.to_str().unwrap().to_string();
+        let get = |key: &str| match key {
+            SECRETS_FILE_ENV_VAR => Some(file.clone()),
+            _ => None,
+        };
+        assert_eq!(get_secret("ETH_KEY_ID", get), Some("first".into()));
+        assert_eq!(get_secret("MINER", get), None);
+        std::fs::remove_file(&path).unwrap();
+
+        // the secrets last read are used once the source can't be read
+        assert_eq!(get_secret("ETH_KEY_ID", get), Some("first".into()));
+    }
+}
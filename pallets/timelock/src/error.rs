@@ -0,0 +1,37 @@
+use codec::{Decode, Encode};
+use frame_support;
+use our_std::Debuggable;
+use pallet_cash::reason::Reason;
+
+use types_derive::Types;
+
+/// Errors coming from the timelock.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Debuggable, Types)]
+pub enum TimelockError {
+    CashError(Reason),
+    NoExtrinsics,
+    NotQueued,
+    DelayTooShort,
+}
+
+impl From<Reason> for TimelockError {
+    fn from(reason: Reason) -> Self {
+        TimelockError::CashError(reason)
+    }
+}
+
+impl From<TimelockError> for frame_support::dispatch::DispatchError {
+    fn from(err: TimelockError) -> frame_support::dispatch::DispatchError {
+        let (index, error, message) = match err {
+            TimelockError::CashError(_) => (0, 0, "CashError"),
+            TimelockError::NoExtrinsics => (1, 0, "NoExtrinsics"),
+            TimelockError::NotQueued => (2, 0, "NotQueued"),
+            TimelockError::DelayTooShort => (3, 0, "DelayTooShort"),
+        };
+        frame_support::dispatch::DispatchError::Module {
+            index,
+            error,
+            message: Some(message),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    error::TimelockError,
+    types::{QueuedCalls, TimelockId, Timestamp},
+};
+use frame_support::{
+    decl_event, decl_module, decl_storage, dispatch,
+    traits::IsSubType,
+    weights::{DispatchClass, Pays, Weight},
+    Parameter,
+};
+use frame_system::ensure_root;
+use our_std::{log, vec::Vec};
+use pallet_cash::{core::get_recent_timestamp, reason::Reason, GovernanceQueue};
+
+pub mod error;
+pub mod timelock;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
+
+/// Default delay between queueing calls and dispatching them.
+pub const DEFAULT_TIMELOCK_DELAY: Timestamp = 2 * 24 * 60 * 60 * 1000;
+
+/// Shortest delay the timelock may be set to, leaving time to react to queued calls.
+pub const MIN_TIMELOCK_DELAY: Timestamp = 24 * 60 * 60 * 1000;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+/// Queued calls are dispatched through the CASH pallet, which in turn queues governance actions from Starports here.
+///
+/// Note: the runtime has no other source of root, so every privileged call waits out the timelock delay,
+///  whether it is passed by native governance or by Starport governance.
+pub trait Config: frame_system::Config + pallet_cash::Config {
+    /// Because this pallet emits events, it depends on the runtime's definition of an event.
+    type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
+
+    /// The overarching dispatch call type, so that governance actions canceling queued calls are recognized.
+    type TimelockCall: Parameter + IsSubType<Call<Self>>;
+}
+
+decl_storage! {
+    trait Store for Module<T: Config> as Timelock {
+        /// Mapping of calls queued in the timelock, by id.
+        pub Queue get(fn queued): map hasher(blake2_128_concat) TimelockId => Option<QueuedCalls>;
+
+        /// The id of the next calls queued in the timelock.
+        pub NextTimelockId get(fn next_timelock_id): TimelockId;
+
+        /// Delay between queueing calls and dispatching them.
+        pub TimelockDelay get(fn timelock_delay): Timestamp = DEFAULT_TIMELOCK_DELAY;
+    }
+}
+
+/* ::EVENTS:: */
+
+decl_event!(
+    pub enum Event {
+        /// Failed to process a given extrinsic. [reason]
+        Failure(TimelockError),
+
+        /// Calls were queued, to be dispatched once ready. [timelock_id, extrinsics, eta]
+        Queued(TimelockId, Vec<Vec<u8>>, Timestamp),
+
+        /// Queued calls were canceled before they were ready. [timelock_id]
+        Canceled(TimelockId),
+
+        /// Queued calls were dispatched. [timelock_id]
+        Executed(TimelockId),
+
+        /// The delay between queueing calls and dispatching them was set. [delay]
+        SetTimelockDelay(Timestamp),
+    }
+);
+
+/* ::GOVERNANCE:: */
+
+impl<T: Config> GovernanceQueue<T> for Module<T> {
+    fn queue_governance(extrinsics: Vec<Vec<u8>>) -> Result<(), Reason> {
+        timelock::queue_governance::<T>(extrinsics)
+    }
+}
+
+/* ::ERRORS:: */
+
+fn check_failure<T: Config>(res: Result<(), TimelockError>) -> Result<(), TimelockError> {
+    if let Err(err) = res {
+        <Module<T>>::deposit_event(Event::Failure(err));
+        log!("Timelock Failure {:#?}", err);
+    }
+    res
+}
+
+/* ::MODULE:: */
+/* ::EXTRINSICS:: */
+
+decl_module! {
+    pub struct Module<T: Config> for enum Call where origin: T::Origin {
+        // Events must be initialized if they are used by the pallet.
+        fn deposit_event() = default;
+
+        /// Called by substrate on block initialization.
+        fn on_initialize() -> Weight {
+            match get_recent_timestamp::<T>() {
+                Ok(now) => timelock::execute_ready::<T>(now),
+                Err(reason) => log!("Timelock not initialized: {:?}", reason),
+            }
+            0
+        }
+
+        /// Cancel queued calls before they are ready. [Root]
+        /// Starport governance cancels immediately, rather than waiting out the delay itself.
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn cancel(origin, timelock_id: TimelockId) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(timelock::cancel::<T>(timelock_id))?)
+        }
+
+        /// Set the delay between queueing calls and dispatching them. [Root]
+        #[weight = (0, DispatchClass::Operational, Pays::No)]
+        pub fn set_timelock_delay(origin, delay: Timestamp) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            Ok(check_failure::<T>(timelock::set_timelock_delay::<T>(delay))?)
+        }
+    }
+}
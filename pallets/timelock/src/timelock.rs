@@ -0,0 +1,186 @@
+use crate::{
+    error::TimelockError,
+    types::{QueuedCalls, TimelockId, Timestamp},
+    Call, Config, Event, Module, NextTimelockId, Queue, TimelockDelay, MIN_TIMELOCK_DELAY,
+};
+use codec::Decode;
+use frame_support::{
+    storage::{IterableStorageMap, StorageMap, StorageValue},
+    traits::IsSubType,
+};
+use our_std::{log, vec::Vec};
+use pallet_cash::{
+    core::{dispatch_extrinsics_internal, get_recent_timestamp},
+    reason::Reason,
+};
+
+/// Queue governance actions from Starports to be dispatched as root once the timelock delay has passed.
+/// Calls canceling queued calls are dispatched right away instead, since waiting out the delay would be too late.
+pub fn queue_governance<T: Config>(extrinsics: Vec<Vec<u8>>) -> Result<(), Reason> {
+    let now = get_recent_timestamp::<T>()?;
+    let mut delayed = vec![];
+    for payload in extrinsics {
+        match decode_cancel::<T>(&payload) {
+            Some(timelock_id) => {
+                if let Err(err) = cancel::<T>(timelock_id) {
+                    log!("Timelock {} failed to cancel: {:?}", timelock_id, err);
+                }
+            }
+            None => delayed.push(payload),
+        }
+    }
+    if !delayed.is_empty() {
+        queue_at::<T>(delayed, now);
+    }
+    Ok(())
+}
+
+/// Decode a call to cancel queued calls, returning the id of the calls to cancel.
+fn decode_cancel<T: Config>(payload: &[u8]) -> Option<TimelockId> {
+    let call = T::TimelockCall::decode(&mut &payload[..]).ok()?;
+    match call.is_sub_type()? {
+        Call::cancel(timelock_id) => Some(*timelock_id),
+        _ => None,
+    }
+}
+
+/// Queue calls at the given time, returning their id.
+pub fn queue_at<T: Config>(extrinsics: Vec<Vec<u8>>, now: Timestamp) -> TimelockId {
+    let timelock_id = NextTimelockId::get();
+    let eta = now.saturating_add(TimelockDelay::get());
+    Queue::insert(
+        timelock_id,
+        QueuedCalls {
+            extrinsics: extrinsics.clone(),
+            queued_at: now,
+            eta,
+        },
+    );
+    NextTimelockId::put(timelock_id + 1);
+    <Module<T>>::deposit_event(Event::Queued(timelock_id, extrinsics, eta));
+    timelock_id
+}
+
+/// Cancel queued calls, so they are never dispatched.
+pub fn cancel<T: Config>(timelock_id: TimelockId) -> Result<(), TimelockError> {
+    if !Queue::contains_key(timelock_id) {
+        return Err(TimelockError::NotQueued);
+    }
+    Queue::remove(timelock_id);
+    <Module<T>>::deposit_event(Event::Canceled(timelock_id));
+    Ok(())
+}
+
+/// Set the delay between queueing calls and dispatching them.
+/// Calls already queued keep the time they were due to be ready.
+pub fn set_timelock_delay<T: Config>(delay: Timestamp) -> Result<(), TimelockError> {
+    if delay < MIN_TIMELOCK_DELAY {
+        return Err(TimelockError::DelayTooShort);
+    }
+    TimelockDelay::put(delay);
+    <Module<T>>::deposit_event(Event::SetTimelockDelay(delay));
+    Ok(())
+}
+
+/// Dispatch the queued calls which are ready, in the order they were queued.
+pub fn execute_ready<T: Config>(now: Timestamp) {
+    let mut ready: Vec<(TimelockId, QueuedCalls)> = Queue::iter()
+        .filter(|(_, queued)| queued.eta <= now)
+        .collect();
+    ready.sort_by_key(|(timelock_id, _)| *timelock_id);
+
+    for (timelock_id, queued) in ready {
+        Queue::remove(timelock_id);
+        if let Err(reason) = dispatch_extrinsics_internal::<T>(queued.extrinsics) {
+            log!("Timelock {} failed to dispatch: {:?}", timelock_id, reason);
+        }
+        <Module<T>>::deposit_event(Event::Executed(timelock_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::*, DEFAULT_TIMELOCK_DELAY};
+    use codec::Encode;
+    use pallet_cash::types::CashPrincipalAmount;
+
+    fn set_min_validator_bond(principal: u128) -> Vec<u8> {
+        mock::Call::Cash(pallet_cash::Call::set_min_validator_bond(
+            CashPrincipalAmount(principal),
+        ))
+        .encode()
+    }
+
+    #[test]
+    fn test_queued_calls_wait_for_delay() {
+        new_test_ext().execute_with(|| {
+            let first = queue_at::<Test>(vec![set_min_validator_bond(7)], 10);
+            let second = queue_at::<Test>(vec![set_min_validator_bond(9)], 20);
+            assert_eq!(
+                TimelockModule::queued(first).map(|queued| queued.eta),
+                Some(10 + DEFAULT_TIMELOCK_DELAY)
+            );
+
+            execute_ready::<Test>(10 + DEFAULT_TIMELOCK_DELAY - 1);
+            assert_eq!(CashModule::min_validator_bond(), CashPrincipalAmount::ZERO);
+
+            execute_ready::<Test>(20 + DEFAULT_TIMELOCK_DELAY);
+            assert_eq!(CashModule::min_validator_bond(), CashPrincipalAmount(9));
+            assert_eq!(Queue::get(first), None);
+            assert_eq!(Queue::get(second), None);
+        });
+    }
+
+    #[test]
+    fn test_starport_governance_waits_for_delay() {
+        new_test_ext().execute_with(|| {
+            <pallet_timestamp::Pallet<Test>>::set_timestamp(10);
+            assert_ok!(queue_governance::<Test>(vec![set_min_validator_bond(7)]));
+            assert_eq!(CashModule::min_validator_bond(), CashPrincipalAmount::ZERO);
+            assert_eq!(
+                TimelockModule::queued(0).map(|queued| queued.eta),
+                Some(10 + DEFAULT_TIMELOCK_DELAY)
+            );
+
+            // cancels are dispatched right away, anything else still waits out the delay
+            let cancel_first = mock::Call::Timelock(crate::Call::cancel(0)).encode();
+            assert_ok!(queue_governance::<Test>(vec![
+                cancel_first,
+                set_min_validator_bond(9)
+            ]));
+            assert_eq!(Queue::get(0), None);
+            assert_eq!(
+                TimelockModule::queued(1).map(|queued| queued.extrinsics),
+                Some(vec![set_min_validator_bond(9)])
+            );
+
+            execute_ready::<Test>(10 + DEFAULT_TIMELOCK_DELAY);
+            assert_eq!(CashModule::min_validator_bond(), CashPrincipalAmount(9));
+        });
+    }
+
+    #[test]
+    fn test_cancel_before_ready() {
+        new_test_ext().execute_with(|| {
+            let timelock_id = queue_at::<Test>(vec![set_min_validator_bond(7)], 10);
+            assert_ok!(cancel::<Test>(timelock_id));
+            assert_eq!(cancel::<Test>(timelock_id), Err(TimelockError::NotQueued));
+
+            execute_ready::<Test>(10 + DEFAULT_TIMELOCK_DELAY);
+            assert_eq!(CashModule::min_validator_bond(), CashPrincipalAmount::ZERO);
+        });
+    }
+
+    #[test]
+    fn test_set_timelock_delay() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                set_timelock_delay::<Test>(MIN_TIMELOCK_DELAY - 1),
+                Err(TimelockError::DelayTooShort)
+            );
+            assert_ok!(set_timelock_delay::<Test>(MIN_TIMELOCK_DELAY));
+            assert_eq!(TimelockDelay::get(), MIN_TIMELOCK_DELAY);
+        });
+    }
+}
@@ -0,0 +1,18 @@
+use codec::{Decode, Encode};
+use our_std::{vec::Vec, RuntimeDebug};
+
+use types_derive::{type_alias, Types};
+
+pub use pallet_cash::types::Timestamp;
+
+/// Type for identifying calls queued in the timelock.
+#[type_alias]
+pub type TimelockId = u64;
+
+/// A list of SCALE-encoded calls queued to be dispatched as root, once ready.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Types)]
+pub struct QueuedCalls {
+    pub extrinsics: Vec<Vec<u8>>,
+    pub queued_at: Timestamp,
+    pub eta: Timestamp,
+}
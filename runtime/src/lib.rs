@@ -47,14 +47,19 @@ pub use pallet_timestamp::Call as TimestampCall;
 
 use our_std::warn;
 use pallet_cash::{
-    chains::{ChainAccount, ChainAsset},
+    chains::{ChainAccount, ChainAsset, ChainId, ChainSignatureList, ChainStarport},
     core::BTreeMap,
+    notices::{EncodedNotice, NoticeId},
     portfolio::Portfolio,
     rates::APR,
     reason::Reason,
     types::{
-        AssetAmount, AssetBalance, AssetInfo, Balance, CashIndex, CashPrincipal, ValidatorKeys,
+        ActivityEntry, AssetAmount, AssetBalance, AssetInfo, Balance, CashIndex, CashOrChainAsset,
+        CashPrincipal, ChainHealth, Factor, GovernanceResult, MarketSummary, ProtocolMetrics,
+        SessionEarnings, SubstrateId, TrxRequestEstimate, ValidatorKeys, ValidatorMetadata,
+        ValidatorParticipation,
     },
+    Event as CashEvent,
 };
 use pallet_oracle::{ticker::Ticker, types::AssetPrice};
 
@@ -281,6 +286,12 @@ impl pallet_oracle::Config for Runtime {
     type Call = Call;
     type Event = Event;
     type GetConvertedTimestamp = timestamp::TimeConverter<Self>;
+    type Validators = Cash;
+}
+
+/// Configure the parameters pallet in pallets/parameters.
+impl pallet_parameters::Config for Runtime {
+    type Event = Event;
 }
 
 /// Configure the CASH pallet in pallets/cash.
@@ -290,9 +301,21 @@ impl pallet_cash::Config for Runtime {
     type GetConvertedTimestamp = timestamp::TimeConverter<Self>;
     type AccountStore = System;
     type SessionInterface = Self;
+    type GovernanceQueue = Timelock;
     type WeightInfo = pallet_cash::weights::SubstrateWeight<Runtime>;
 }
 
+/// Configure the timelock pallet in pallets/timelock.
+impl pallet_timelock::Config for Runtime {
+    type Event = Event;
+    type TimelockCall = Call;
+}
+
+/// Configure the governance pallet in pallets/governance.
+impl pallet_governance::Config for Runtime {
+    type Event = Event;
+}
+
 // ---------------------- Recipe Pallet Configurations ----------------------
 //  https://github.com/substrate-developer-hub/recipes/blob/master/runtimes/ocw-runtime/src/lib.rs
 /// Payload data to be signed when making signed transaction from off-chain workers,
@@ -370,8 +393,11 @@ construct_runtime!(
         Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event},
 
         // Include the custom logic from the Cash and Oracle pallets in the runtime.
+        Parameters: pallet_parameters::{Pallet, Call, Storage, Event},
         Cash: pallet_cash::{Pallet, Call, Config, Storage, Event, ValidateUnsigned, Inherent},
         Oracle: pallet_oracle::{Pallet, Call, Config, Storage, Event, ValidateUnsigned, Inherent},
+        Timelock: pallet_timelock::{Pallet, Call, Storage, Event},
+        Governance: pallet_governance::{Pallet, Call, Storage, Event, ValidateUnsigned},
 
         // comes after CASH pallet bc it asks CASH for validators during initialization
         Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
@@ -547,10 +573,30 @@ impl_runtime_apis! {
             Cash::get_liquidity(account)
         }
 
+        fn get_logical_cash_balance(account: ChainAccount) -> Result<AssetBalance, Reason> {
+            Cash::get_logical_cash_balance(account)
+        }
+
+        fn get_logical_liquidity(account: ChainAccount) -> Result<AssetBalance, Reason> {
+            Cash::get_logical_liquidity(account)
+        }
+
+        fn get_health_factor(account: ChainAccount) -> Result<Option<Factor>, Reason> {
+            Cash::get_health_factor(account)
+        }
+
+        fn get_max_extract(account: ChainAccount, asset: CashOrChainAsset) -> Result<AssetAmount, Reason> {
+            Cash::get_max_extract(account, asset)
+        }
+
         fn get_market_totals(asset: ChainAsset) -> Result<(AssetAmount, AssetAmount), Reason> {
             Cash::get_market_totals(asset)
         }
 
+        fn get_markets_summary() -> Result<Vec<MarketSummary>, Reason> {
+            Cash::get_markets_summary()
+        }
+
         fn get_price(ticker_str: String) -> Result<AssetPrice, Reason> {
             Oracle::get_price(Ticker::from_str(&ticker_str).map_err(Reason::OracleError)?).map_err(Reason::OracleError)
         }
@@ -559,6 +605,10 @@ impl_runtime_apis! {
             Oracle::get_price(ticker).map_err(Reason::OracleError)
         }
 
+        fn get_price_history(ticker_str: String, count: u32) -> Result<Vec<(pallet_oracle::types::Timestamp, AssetPrice)>, Reason> {
+            Ok(Oracle::get_price_history(Ticker::from_str(&ticker_str).map_err(Reason::OracleError)?, count))
+        }
+
         fn get_rates(asset: ChainAsset) -> Result<(APR, APR), Reason> {
             Cash::get_rates(asset)
         }
@@ -571,6 +621,10 @@ impl_runtime_apis! {
             Cash::get_accounts()
         }
 
+        fn get_accounts_page(start_key: Option<ChainAccount>, limit: u32) -> Result<Vec<ChainAccount>, Reason> {
+            Cash::get_accounts_page(start_key, limit)
+        }
+
         fn get_asset_meta() -> Result<(BTreeMap<String, u32>, BTreeMap<String, u32>, u32, u32), Reason> {
           Cash::get_asset_meta()
         }
@@ -579,13 +633,85 @@ impl_runtime_apis! {
             Cash::get_accounts_liquidity()
         }
 
+        fn get_accounts_liquidity_page(start_key: Option<ChainAccount>, limit: u32, below: Option<AssetBalance>) -> Result<Vec<(ChainAccount, String)>, Reason> {
+            Cash::get_accounts_liquidity_page(start_key, limit, below)
+        }
+
+        fn get_liquidatable_accounts(max_results: u32) -> Result<Vec<(ChainAccount, AssetBalance, Option<CashOrChainAsset>, Option<CashOrChainAsset>)>, Reason> {
+            Cash::get_liquidatable_accounts(max_results)
+        }
+
         fn get_portfolio(account: ChainAccount) -> Result<Portfolio, Reason> {
             Cash::get_portfolio(account)
         }
 
+        fn get_logical_portfolio(account: ChainAccount) -> Result<Portfolio, Reason> {
+            Cash::get_logical_portfolio(account)
+        }
+
+        fn get_account_history(account: ChainAccount, from: u32, limit: u32) -> Result<Vec<ActivityEntry>, Reason> {
+            Cash::get_account_history(account, from, limit)
+        }
+
         fn get_validator_info() -> Result<(Vec<ValidatorKeys>, Vec<(ChainAccount, String)>), Reason> {
             Cash::get_validator_info()
         }
+
+        fn get_validator_participation() -> Result<(ValidatorParticipation, Vec<(ValidatorKeys, ValidatorParticipation)>), Reason> {
+            Cash::get_validator_participation()
+        }
+
+        fn get_validator_metadata() -> Result<Vec<(ValidatorKeys, Option<ValidatorMetadata>)>, Reason> {
+            Cash::get_validator_metadata()
+        }
+
+        fn get_validator_earnings(substrate_id: SubstrateId, last_n_sessions: u32) -> Result<Vec<SessionEarnings>, Reason> {
+            Cash::get_validator_earnings(substrate_id, last_n_sessions)
+        }
+
+        fn get_pending_notices(account: ChainAccount) -> Result<Vec<(NoticeId, EncodedNotice, ChainSignatureList)>, Reason> {
+            Cash::get_pending_notices(account)
+        }
+
+        fn simulate_notice(chain_id: ChainId, notice_id: NoticeId) -> Result<(ChainStarport, Vec<u8>, Option<u64>), Reason> {
+            Cash::simulate_notice(chain_id, notice_id)
+        }
+
+        fn simulate_governance(actions: Vec<Vec<u8>>) -> Result<Vec<GovernanceResult>, Reason> {
+            Cash::simulate_governance(actions)
+        }
+
+        fn estimate_trx_request(request: Vec<u8>, account: ChainAccount) -> Result<TrxRequestEstimate, Reason> {
+            Cash::estimate_trx_request(request, account)
+        }
+
+        fn get_cash_events() -> Result<Vec<CashEvent>, Reason> {
+            Ok(System::events()
+                .into_iter()
+                .filter_map(|record| match record.event {
+                    Event::pallet_cash(event) => Some(event),
+                    _ => None,
+                })
+                .collect())
+        }
+
+        fn get_chain_health() -> Result<Vec<ChainHealth>, Reason> {
+            Cash::get_chain_health()
+        }
+
+        fn get_protocol_metrics() -> Result<ProtocolMetrics, Reason> {
+            Cash::get_protocol_metrics()
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    impl frame_try_runtime::TryRuntime<Block> for Runtime {
+        fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString> {
+            Cash::pre_upgrade_checks()?;
+            let weight = Executive::try_runtime_upgrade()?;
+            Cash::post_upgrade_checks()?;
+            Ok((weight, BlockWeights::get().max_block))
+        }
     }
 
     #[cfg(feature = "runtime-benchmarks")]
@@ -39,6 +39,8 @@ cargo +nightly build --release --features runtime-debug
 
 bin="./target/release/gateway"
 types="./types.json"
+types_bundle="./types-bundle.json"
+types_ts="./types.d.ts"
 rpc="./rpc.json"
 wasm="./target/release/wbuild/gateway-runtime/gateway_runtime.compact.wasm"
 
@@ -47,6 +49,10 @@ if [ ! -f "$bin" -o ! -f "$wasm" -o ! -f "$types" -o ! -f "$rpc" -o ! -f "$contr
 	exit 1
 fi
 
+echo "*** Building type bundle ***"
+
+./scripts/build_types_bundle.sh
+
 echo "*** Building checksums ***"
 wasm_checksum="$(node ./ethereum/scripts/utils/keccak.js "$wasm")"
 bin_checksum="$(node ./ethereum/scripts/utils/keccak.js "$bin")"
@@ -60,6 +66,8 @@ cp "$bin" "$release_dir/gateway-$machine"
 echo "$bin_checksum" > "$release_dir/gateway-$machine.checksum"
 
 cp "$types" "$release_dir/types.json"
+cp "$types_bundle" "$release_dir/types-bundle.json"
+cp "$types_ts" "$release_dir/types.d.ts"
 cp "$rpc" "$release_dir/rpc.json"
 cp "$contracts" "$release_dir/contracts.json"
 
@@ -69,5 +77,7 @@ echo "  wasm.checksum: $release_dir/gateway.wasm.checksum"
 echo "  bin: $release_dir/gateway-$machine"
 echo "  bin.checksum: $release_dir/gateway-$machine.checksum"
 echo "  types: $release_dir/types.json"
+echo "  types-bundle: $release_dir/types-bundle.json"
+echo "  types.d.ts: $release_dir/types.d.ts"
 echo "  rpc: $release_dir/rpc.json"
 echo "  contracts: $release_dir/contracts.json"
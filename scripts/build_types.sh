@@ -8,20 +8,25 @@ types_json="./types.json"
 
 cp "$types_json" "$types_json.bak"
 
-projects=("gateway" "pallet-cash" "pallet-oracle" "ethereum-client" "gateway-crypto")
+projects=("gateway" "pallet-cash" "pallet-governance" "pallet-oracle" "pallet-parameters" "pallet-timelock" "ethereum-client" "gateway-crypto")
 
 set -x
 
-json_files=(./base_types.json)
+# Each crate writes its own types, so the projects can be built together and in parallel
+types_dir="$(mktemp -d)"
+packages=()
 
 for project in ${projects[@]}; do
-  json_file="$(mktemp)"
-  echo "Building $project to $json_file"
   cargo +nightly clean -p $project
-  TYPES_FILE="$json_file" cargo +nightly build -p $project
-  json_files[${#json_files[@]}]="$json_file"
+  packages[${#packages[@]}]="-p"
+  packages[${#packages[@]}]="$project"
 done
 
-jq -s 'add' ${json_files[@]} | jq -r 'to_entries|sort|from_entries' > $types_json
+# The runtime's wasm is skipped, as its crates are built without std and would write their types again
+SKIP_WASM_BUILD=1 TYPES_DIR="$types_dir" cargo +nightly build ${packages[@]}
+
+cargo +nightly run -p types-derive --bin types-merge -- "$types_json" ./base_types.json "$types_dir"
 
 echo "Built $types_json"
+
+./scripts/build_types_bundle.sh
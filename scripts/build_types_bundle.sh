@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+
+set -eo pipefail
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+types_json="./types.json"
+rpc_json="./rpc.json"
+types_bundle="./types-bundle.json"
+types_ts="./types.d.ts"
+
+spec_version="$(grep -oE 'spec_version: [0-9]+' runtime/src/lib.rs | grep -oE '[0-9]+')"
+
+# Releases pulled into ./releases (see scripts/pull_release.sh) give the types of earlier spec versions
+cargo +nightly run -p types-derive --bin types-bundle -- "$types_json" \
+  --spec-version "$spec_version" \
+  --rpc "$rpc_json" \
+  --releases ./releases \
+  --bundle "$types_bundle" \
+  --typescript "$types_ts"
+
+echo "Built $types_bundle and $types_ts for spec version $spec_version"
@@ -21,7 +21,7 @@ release_dir="./releases/$version"
 
 mkdir -p "$release_dir"
 
-files=("contracts.json" "gateway-darwin-arm64" "gateway-darwin-arm64.checksum" "gateway-linux-x86" "gateway.wasm" "gateway.wasm.checksum" "rpc.json" "types.json")
+files=("contracts.json" "gateway-darwin-arm64" "gateway-darwin-arm64.checksum" "gateway-linux-x86" "gateway.wasm" "gateway.wasm.checksum" "rpc.json" "types.json" "types-bundle.json" "types.d.ts")
 
 for file in ${files[@]}; do
   echo "Retreiving ${file}..."
@@ -1,4 +1,6 @@
+use crate::Decimal;
 use logos::{Lexer, Logos};
+use our_std::convert::TryInto;
 
 #[derive(Logos, Debug, PartialEq, Eq, Clone)]
 pub enum Token<'a> {
@@ -8,12 +10,27 @@ pub enum Token<'a> {
     #[token(")")]
     RightDelim,
 
+    #[token(":deadline")]
+    Deadline,
+
+    #[token(":fee")]
+    Fee,
+
+    #[token(":execfee")]
+    ExecutionFee,
+
     #[regex(r"0x[0-9a-fA-F]+", parse_hex)]
     Hex(Option<Vec<u8>>),
 
     #[regex(r"[0-9]+", parse_int)]
     Integer(Option<u128>),
 
+    #[regex(r"[0-9]+\.[0-9]+", parse_decimal)]
+    Decimal(Option<Decimal>),
+
+    #[regex(r"v[0-9]+", parse_version)]
+    Version(Option<u32>),
+
     #[regex(r"[a-zA-Z-]+")]
     Identifier(&'a str),
 
@@ -39,6 +56,20 @@ fn parse_int<'a>(lex: &mut Lexer<'a, Token<'a>>) -> Option<u128> {
     u128::from_str_radix(&lex.slice()[..], 10).ok()
 }
 
+fn parse_version<'a>(lex: &mut Lexer<'a, Token<'a>>) -> Option<u32> {
+    u32::from_str_radix(&lex.slice()[1..], 10).ok()
+}
+
+fn parse_decimal<'a>(lex: &mut Lexer<'a, Token<'a>>) -> Option<Decimal> {
+    let (whole, fraction) = lex.slice().split_once('.')?;
+    let decimals: u8 = fraction.len().try_into().ok()?;
+    let digits = [whole, fraction].concat();
+    Some(Decimal {
+        digits: u128::from_str_radix(&digits[..], 10).ok()?,
+        decimals,
+    })
+}
+
 pub fn lex<'source>(text: &'source str) -> Lexer<'source, Token> {
     Token::lexer(text)
 }
@@ -74,6 +105,15 @@ mod tests {
           Token::Integer(Some(50)),
           Token::RightDelim
         ],
+      version_lex:
+        "(v2 (vote))" => vec![
+          Token::LeftDelim,
+          Token::Version(Some(2)),
+          Token::LeftDelim,
+          Token::Identifier("vote"),
+          Token::RightDelim,
+          Token::RightDelim
+        ],
       simple_lex:
         "()" => vec![Token::LeftDelim, Token::RightDelim],
       simple_fun_call:
@@ -85,5 +125,22 @@ mod tests {
           Token::Pair(Some(("eth", "0x20"))),
           Token::RightDelim
         ],
+      deadline_lex:
+        "(x) :deadline 1000" => vec![
+          Token::LeftDelim,
+          Token::Identifier("x"),
+          Token::RightDelim,
+          Token::Deadline,
+          Token::Integer(Some(1000))
+        ],
+      decimal_lex:
+        "(1.5 0.001 1.)" => vec![
+          Token::LeftDelim,
+          Token::Decimal(Some(Decimal { digits: 15, decimals: 1 })),
+          Token::Decimal(Some(Decimal { digits: 1, decimals: 3 })),
+          Token::Integer(Some(1)),
+          Token::Error,
+          Token::RightDelim
+        ],
     }
 }
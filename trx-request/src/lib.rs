@@ -3,6 +3,7 @@
 
 mod hex_util;
 mod lex;
+mod ss58;
 use lex::{lex, Token};
 use logos::Lexer;
 
@@ -10,9 +11,81 @@ use our_std::convert::TryInto;
 
 pub type Amount = u128;
 
+/// The maximum number of recipients of a single TransferMany request.
+pub const MAX_TRANSFER_RECIPIENTS: usize = 32;
+
+/// The maximum number of collateral assets seized from by a single LiquidateMany request.
+pub const MAX_LIQUIDATION_COLLATERALS: usize = 8;
+
+pub type Nonce = u32;
+
+/// A version of the request grammar, given by an explicit `(vN <request>)` marker.
+/// Requests without a marker are read as `V1`, so a signed request never changes meaning.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
+pub enum Version {
+    V1,
+}
+
+/// The latest version of the request grammar.
+pub const LATEST_VERSION: Version = Version::V1;
+
+/// The compatibility table: the version of the grammar which introduced each function.
+const FUNCTION_VERSIONS: &[(&str, Version)] = &[
+    ("Extract", Version::V1),
+    ("Transfer", Version::V1),
+    ("TransferMany", Version::V1),
+    ("Supply", Version::V1),
+    ("Repay", Version::V1),
+    ("Approve", Version::V1),
+    ("TransferFrom", Version::V1),
+    ("Liquidate", Version::V1),
+    ("LiquidateMany", Version::V1),
+    ("Cancel", Version::V1),
+    ("NonceWindow", Version::V1),
+    ("ClaimRewards", Version::V1),
+    ("Bond", Version::V1),
+    ("Unbond", Version::V1),
+    ("Batch", Version::V1),
+];
+
+impl Version {
+    /// Whether a function may be used in requests of this version.
+    pub fn supports(self, fun: &str) -> bool {
+        FUNCTION_VERSIONS
+            .iter()
+            .any(|(name, introduced)| *name == fun && *introduced <= self)
+    }
+}
+
+/// A timestamp in milliseconds, after which a request may no longer be executed.
+pub type Deadline = u64;
+
+/// A fee in CASH principal which the signer pays upon execution, to the miner unless a relayer is named.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Fee {
+    pub principal: Amount,
+    pub recipient: Option<Account>,
+}
+
+/// The optional clauses which may follow the expression of a request.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Clauses {
+    pub deadline: Option<Deadline>,
+    pub fee: Option<Fee>,
+    pub execution_fee: Option<Amount>,
+}
+
+/// A decimal amount, given as all of its digits and the number of those after the decimal point.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Decimal {
+    pub digits: u128,
+    pub decimals: u8,
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub enum MaxAmount {
     Amount(Amount),
+    Decimal(Decimal),
     Max,
 }
 
@@ -28,6 +101,7 @@ pub enum Asset {
     Cash,
     Eth([u8; 20]),
     Matic([u8; 20]),
+    Symbol(String),
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -41,7 +115,19 @@ pub enum Account {
 pub enum TrxRequest {
     Extract(MaxAmount, Asset, Account),
     Transfer(MaxAmount, Asset, Account),
+    TransferMany(Asset, Vec<(MaxAmount, Account)>),
+    Supply(MaxAmount, Asset, Account),
+    Repay(MaxAmount, Asset, Account),
+    Approve(MaxAmount, Asset, Account),
+    TransferFrom(Amount, Asset, Account, Account),
     Liquidate(MaxAmount, Asset, Asset, Account),
+    LiquidateMany(MaxAmount, Asset, Vec<Asset>, Account),
+    Cancel(Nonce),
+    SetNonceWindow(Nonce),
+    ClaimRewards,
+    Bond(MaxAmount),
+    Unbond(MaxAmount),
+    Batch(Vec<TrxRequest>),
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -56,6 +142,22 @@ pub enum ParseError<'a> {
     InvalidExpression,
     InvalidChain(&'a str),
     InvalidChainAccount(Chain),
+    InvalidBatchOperation(&'a str),
+    InvalidDeadline,
+    InvalidNonce,
+    InvalidFee,
+    InvalidClause,
+    TooManyRecipients,
+    TooManyCollaterals,
+    UnknownVersion(u32),
+    UnsupportedFunction(&'a str, Version),
+}
+
+fn parse_version<'a>(number: u32) -> Result<Version, ParseError<'a>> {
+    match number {
+        1 => Ok(Version::V1),
+        _ => Err(ParseError::UnknownVersion(number)),
+    }
 }
 
 fn parse_amount<'a>(t: &Token) -> Result<Amount, ParseError<'a>> {
@@ -66,9 +168,17 @@ fn parse_amount<'a>(t: &Token) -> Result<Amount, ParseError<'a>> {
     }
 }
 
+fn parse_nonce<'a>(t: &Token) -> Result<Nonce, ParseError<'a>> {
+    match t {
+        Token::Integer(Some(v)) => Ok((*v).try_into().map_err(|_| ParseError::InvalidNonce)?),
+        _ => Err(ParseError::InvalidNonce),
+    }
+}
+
 fn parse_max_amount<'a>(t: &Token) -> Result<MaxAmount, ParseError<'a>> {
     match t {
         Token::Identifier("Max") | Token::Identifier("MAX") => Ok(MaxAmount::Max),
+        Token::Decimal(Some(decimal)) => Ok(MaxAmount::Decimal(*decimal)),
         els => Ok(MaxAmount::Amount(parse_amount(els)?)),
     }
 }
@@ -83,7 +193,6 @@ fn parse_chain<'a>(chain: &'a str) -> Result<Chain, ParseError<'a>> {
 }
 
 fn parse_gate_address<'a>(account: &'a str) -> Result<[u8; 32], ParseError<'a>> {
-    // TODO: handle SS58 encoded addresses as well
     if let Some(stripped) = account.strip_prefix("0x") {
         let decoded: Vec<u8> =
             hex::decode(stripped).map_err(|_| ParseError::InvalidChainAccount(Chain::Gate))?;
@@ -92,7 +201,7 @@ fn parse_gate_address<'a>(account: &'a str) -> Result<[u8; 32], ParseError<'a>>
             .map_err(|_| ParseError::InvalidChainAccount(Chain::Gate))?;
         Ok(address)
     } else {
-        Err(ParseError::InvalidChainAccount(Chain::Gate))?
+        ss58::decode_account(account).ok_or(ParseError::InvalidChainAccount(Chain::Gate))
     }
 }
 
@@ -147,6 +256,7 @@ fn parse_account<'a>(t: &Token<'a>) -> Result<Account, ParseError<'a>> {
 fn parse_asset<'a>(t: &Token<'a>) -> Result<Asset, ParseError<'a>> {
     match t {
         Token::Identifier("Cash") | Token::Identifier("CASH") => Ok(Asset::Cash),
+        Token::Identifier(symbol) => Ok(Asset::Symbol(String::from(*symbol))),
         Token::Pair(Some((chain_str, asset_str))) => {
             let chain = parse_chain(chain_str)?;
             Ok(parse_chain_asset(chain, asset_str)?)
@@ -181,6 +291,85 @@ fn parse_transfer<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>>
     }
 }
 
+fn parse_transfer_many<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [asset_token, transfer_tokens @ ..] if !transfer_tokens.is_empty() => {
+            let asset = parse_asset(asset_token)?;
+            let transfers = transfer_tokens
+                .chunks(4)
+                .map(|chunk| match chunk {
+                    [Token::LeftDelim, amount_token, account_token, Token::RightDelim] => {
+                        match parse_max_amount(amount_token)? {
+                            MaxAmount::Max => Err(ParseError::InvalidAmount),
+                            amount => Ok((amount, parse_account(account_token)?)),
+                        }
+                    }
+                    _ => Err(ParseError::InvalidExpression),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if transfers.len() > MAX_TRANSFER_RECIPIENTS {
+                return Err(ParseError::TooManyRecipients);
+            }
+
+            Ok(TrxRequest::TransferMany(asset, transfers))
+        }
+        _ => Err(ParseError::InvalidArgs("TransferMany", 2, args.len())),
+    }
+}
+
+fn parse_supply<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [amount_token, asset_token, account_token] => {
+            let max_amount = parse_max_amount(amount_token)?;
+            let asset = parse_asset(asset_token)?;
+            let account = parse_account(account_token)?;
+
+            Ok(TrxRequest::Supply(max_amount, asset, account))
+        }
+        _ => Err(ParseError::InvalidArgs("Supply", 3, args.len())),
+    }
+}
+
+fn parse_repay<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [amount_token, asset_token, account_token] => {
+            let max_amount = parse_max_amount(amount_token)?;
+            let asset = parse_asset(asset_token)?;
+            let account = parse_account(account_token)?;
+
+            Ok(TrxRequest::Repay(max_amount, asset, account))
+        }
+        _ => Err(ParseError::InvalidArgs("Repay", 3, args.len())),
+    }
+}
+
+fn parse_approve<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [amount_token, asset_token, spender_token] => {
+            let max_amount = parse_max_amount(amount_token)?;
+            let asset = parse_asset(asset_token)?;
+            let spender = parse_account(spender_token)?;
+
+            Ok(TrxRequest::Approve(max_amount, asset, spender))
+        }
+        _ => Err(ParseError::InvalidArgs("Approve", 3, args.len())),
+    }
+}
+
+fn parse_transfer_from<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [amount_token, asset_token, owner_token, recipient_token] => {
+            let amount = parse_amount(amount_token)?;
+            let asset = parse_asset(asset_token)?;
+            let owner = parse_account(owner_token)?;
+            let recipient = parse_account(recipient_token)?;
+
+            Ok(TrxRequest::TransferFrom(amount, asset, owner, recipient))
+        }
+        _ => Err(ParseError::InvalidArgs("TransferFrom", 4, args.len())),
+    }
+}
+
 fn parse_liquidate<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
     match args {
         [amount_token, borrowed_asset_token, collateral_asset_token, account_token] => {
@@ -200,40 +389,285 @@ fn parse_liquidate<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>>
     }
 }
 
-fn parse<'a>(tokens: Lexer<'a, Token<'a>>) -> Result<TrxRequest, ParseError<'a>> {
-    // TODO: I don't love having to clone here at all
-    tokens
-        .clone()
-        .spanned()
-        .fold(Ok(()) as Result<(), ParseError<'a>>, |acc, el| {
-            match (acc, el) {
-                (Err(err), _) => Err(err),
-                (_, (Token::Error, span)) => Err(ParseError::LexError(&tokens.source()[span])),
-                (_, _) => Ok(()),
+fn parse_liquidate_many<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [amount_token, borrowed_asset_token, Token::LeftDelim, collateral_asset_tokens @ .., Token::RightDelim, account_token]
+            if !collateral_asset_tokens.is_empty() =>
+        {
+            let max_amount = parse_max_amount(amount_token)?;
+            let borrowed_asset = parse_asset(borrowed_asset_token)?;
+            let collateral_assets = collateral_asset_tokens
+                .iter()
+                .map(parse_asset)
+                .collect::<Result<Vec<_>, _>>()?;
+            if collateral_assets.len() > MAX_LIQUIDATION_COLLATERALS {
+                return Err(ParseError::TooManyCollaterals);
             }
-        })?;
+            let account = parse_account(account_token)?;
 
-    let token_vec = tokens.collect::<Vec<Token<'a>>>();
+            Ok(TrxRequest::LiquidateMany(
+                max_amount,
+                borrowed_asset,
+                collateral_assets,
+                account,
+            ))
+        }
+        _ => Err(ParseError::InvalidArgs("LiquidateMany", 4, args.len())),
+    }
+}
+
+fn parse_cancel<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [nonce_token] => Ok(TrxRequest::Cancel(parse_nonce(nonce_token)?)),
+        _ => Err(ParseError::InvalidArgs("Cancel", 1, args.len())),
+    }
+}
+
+fn parse_nonce_window<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [window_token] => Ok(TrxRequest::SetNonceWindow(parse_nonce(window_token)?)),
+        _ => Err(ParseError::InvalidArgs("NonceWindow", 1, args.len())),
+    }
+}
+
+fn parse_claim_rewards<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [] => Ok(TrxRequest::ClaimRewards),
+        _ => Err(ParseError::InvalidArgs("ClaimRewards", 0, args.len())),
+    }
+}
+
+fn parse_bond<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [amount_token] => Ok(TrxRequest::Bond(parse_max_amount(amount_token)?)),
+        _ => Err(ParseError::InvalidArgs("Bond", 1, args.len())),
+    }
+}
+
+fn parse_unbond<'a>(args: &[Token<'a>]) -> Result<TrxRequest, ParseError<'a>> {
+    match args {
+        [amount_token] => Ok(TrxRequest::Unbond(parse_max_amount(amount_token)?)),
+        _ => Err(ParseError::InvalidArgs("Unbond", 1, args.len())),
+    }
+}
+
+fn parse_batch<'a>(args: &[Token<'a>], version: Version) -> Result<TrxRequest, ParseError<'a>> {
+    let mut operations = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, token) in args.iter().enumerate() {
+        match token {
+            Token::LeftDelim => depth += 1,
+            Token::RightDelim if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    operations.push(parse_batch_operation(&args[start..=i], version)?);
+                    start = i + 1;
+                }
+            }
+            _ if depth > 0 => (),
+            _ => return Err(ParseError::InvalidExpression),
+        }
+    }
+
+    match (depth, operations.len()) {
+        (0, 0) => Err(ParseError::InvalidArgs("Batch", 1, 0)),
+        (0, _) => Ok(TrxRequest::Batch(operations)),
+        _ => Err(ParseError::InvalidExpression),
+    }
+}
+
+fn parse_batch_operation<'a>(
+    tokens: &[Token<'a>],
+    version: Version,
+) -> Result<TrxRequest, ParseError<'a>> {
+    match tokens {
+        [Token::LeftDelim, Token::Identifier(fun @ "Batch"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "Liquidate"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "LiquidateMany"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "Approve"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "TransferFrom"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "TransferMany"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "Cancel"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "NonceWindow"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "ClaimRewards"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "Bond"), ..]
+        | [Token::LeftDelim, Token::Identifier(fun @ "Unbond"), ..] => {
+            Err(ParseError::InvalidBatchOperation(*fun))
+        }
+        operation => parse_operation(operation, version),
+    }
+}
 
-    match &token_vec[..] {
+fn parse_operation<'a>(
+    tokens: &[Token<'a>],
+    version: Version,
+) -> Result<TrxRequest, ParseError<'a>> {
+    match tokens {
+        [Token::LeftDelim, Token::Identifier(fun), ..]
+            if FUNCTION_VERSIONS.iter().any(|(name, _)| name == fun) && !version.supports(fun) =>
+        {
+            Err(ParseError::UnsupportedFunction(*fun, version))
+        }
         [Token::LeftDelim, Token::Identifier("Extract"), args @ .., Token::RightDelim] => {
             parse_extract(args)
         }
         [Token::LeftDelim, Token::Identifier("Transfer"), args @ .., Token::RightDelim] => {
             parse_transfer(args)
         }
+        [Token::LeftDelim, Token::Identifier("TransferMany"), args @ .., Token::RightDelim] => {
+            parse_transfer_many(args)
+        }
+        [Token::LeftDelim, Token::Identifier("Supply"), args @ .., Token::RightDelim] => {
+            parse_supply(args)
+        }
+        [Token::LeftDelim, Token::Identifier("Repay"), args @ .., Token::RightDelim] => {
+            parse_repay(args)
+        }
+        [Token::LeftDelim, Token::Identifier("Approve"), args @ .., Token::RightDelim] => {
+            parse_approve(args)
+        }
+        [Token::LeftDelim, Token::Identifier("TransferFrom"), args @ .., Token::RightDelim] => {
+            parse_transfer_from(args)
+        }
         [Token::LeftDelim, Token::Identifier("Liquidate"), args @ .., Token::RightDelim] => {
             parse_liquidate(args)
         }
+        [Token::LeftDelim, Token::Identifier("LiquidateMany"), args @ .., Token::RightDelim] => {
+            parse_liquidate_many(args)
+        }
+        [Token::LeftDelim, Token::Identifier("Cancel"), args @ .., Token::RightDelim] => {
+            parse_cancel(args)
+        }
+        [Token::LeftDelim, Token::Identifier("NonceWindow"), args @ .., Token::RightDelim] => {
+            parse_nonce_window(args)
+        }
+        [Token::LeftDelim, Token::Identifier("ClaimRewards"), args @ .., Token::RightDelim] => {
+            parse_claim_rewards(args)
+        }
+        [Token::LeftDelim, Token::Identifier("Bond"), args @ .., Token::RightDelim] => {
+            parse_bond(args)
+        }
+        [Token::LeftDelim, Token::Identifier("Unbond"), args @ .., Token::RightDelim] => {
+            parse_unbond(args)
+        }
         [Token::LeftDelim, Token::Identifier(fun), .., Token::RightDelim] => {
-            Err(ParseError::UnknownFunction(fun))
+            Err(ParseError::UnknownFunction(*fun))
         }
         _ => Err(ParseError::InvalidExpression),
     }
 }
 
+fn parse_deadline<'a>(t: &Token) -> Result<Deadline, ParseError<'a>> {
+    match t {
+        Token::Integer(Some(v)) => Ok((*v).try_into().map_err(|_| ParseError::InvalidDeadline)?),
+        _ => Err(ParseError::InvalidDeadline),
+    }
+}
+
+fn parse_fee<'a>(
+    amount_token: &Token<'a>,
+    recipient: Option<&Token<'a>>,
+) -> Result<Fee, ParseError<'a>> {
+    Ok(Fee {
+        principal: parse_amount(amount_token).map_err(|_| ParseError::InvalidFee)?,
+        recipient: recipient.map(parse_account).transpose()?,
+    })
+}
+
+fn parse_clauses<'a>(mut tokens: &[Token<'a>]) -> Result<Clauses, ParseError<'a>> {
+    let mut clauses = Clauses::default();
+    loop {
+        tokens = match tokens {
+            [] => return Ok(clauses),
+            [Token::Deadline, deadline_token, rest @ ..] if clauses.deadline.is_none() => {
+                clauses.deadline = Some(parse_deadline(deadline_token)?);
+                rest
+            }
+            [Token::Fee, amount_token, recipient_token @ Token::Pair(_), rest @ ..]
+                if clauses.fee.is_none() =>
+            {
+                clauses.fee = Some(parse_fee(amount_token, Some(recipient_token))?);
+                rest
+            }
+            [Token::Fee, amount_token, rest @ ..] if clauses.fee.is_none() => {
+                clauses.fee = Some(parse_fee(amount_token, None)?);
+                rest
+            }
+            [Token::ExecutionFee, amount_token, rest @ ..] if clauses.execution_fee.is_none() => {
+                clauses.execution_fee =
+                    Some(parse_amount(amount_token).map_err(|_| ParseError::InvalidFee)?);
+                rest
+            }
+            _ => return Err(ParseError::InvalidClause),
+        }
+    }
+}
+
+fn parse<'a>(
+    tokens: Lexer<'a, Token<'a>>,
+) -> Result<(Version, TrxRequest, Clauses), ParseError<'a>> {
+    // TODO: I don't love having to clone here at all
+    tokens
+        .clone()
+        .spanned()
+        .fold(Ok(()) as Result<(), ParseError<'a>>, |acc, el| {
+            match (acc, el) {
+                (Err(err), _) => Err(err),
+                (_, (Token::Error, span)) => Err(ParseError::LexError(&tokens.source()[span])),
+                (_, _) => Ok(()),
+            }
+        })?;
+
+    let token_vec = tokens.collect::<Vec<Token<'a>>>();
+
+    let clauses_start = token_vec
+        .iter()
+        .position(|token| matches!(token, Token::Deadline | Token::Fee | Token::ExecutionFee))
+        .unwrap_or(token_vec.len());
+    let (expression, clause_tokens) = token_vec.split_at(clauses_start);
+    let clauses = parse_clauses(clause_tokens)?;
+
+    let (version, expression) = match expression {
+        [Token::LeftDelim, Token::Version(Some(number)), inner @ .., Token::RightDelim] => {
+            (parse_version(*number)?, inner)
+        }
+        [Token::LeftDelim, Token::Version(_), ..] => return Err(ParseError::InvalidExpression),
+        _ => (Version::V1, expression),
+    };
+
+    let trx_request = match expression {
+        [Token::LeftDelim, Token::Identifier("Batch"), args @ .., Token::RightDelim]
+            if version.supports("Batch") =>
+        {
+            parse_batch(args, version)
+        }
+        operation => parse_operation(operation, version),
+    }?;
+
+    Ok((version, trx_request, clauses))
+}
+
+/// Parse a request, ignoring any clauses.
 pub fn parse_request<'a>(request: &'a str) -> Result<TrxRequest, ParseError<'a>> {
-    parse(lex(request))
+    Ok(parse(lex(request))?.1)
+}
+
+/// Parse a request along with the version of the grammar it was written in, ignoring any clauses.
+pub fn parse_versioned_request<'a>(
+    request: &'a str,
+) -> Result<(Version, TrxRequest), ParseError<'a>> {
+    let (version, trx_request, _) = parse(lex(request))?;
+    Ok((version, trx_request))
+}
+
+/// Parse a request along with its optional `:deadline <timestamp>`, `:fee <principal> [account]`
+/// and `:execfee <principal>` clauses.
+pub fn parse_request_with_clauses<'a>(
+    request: &'a str,
+) -> Result<(TrxRequest, Clauses), ParseError<'a>> {
+    let (_, trx_request, clauses) = parse(lex(request))?;
+    Ok((trx_request, clauses))
 }
 
 #[cfg(test)]
@@ -319,12 +753,102 @@ mod tests {
             Asset::Eth(ETH),
             Account::Gate(BERT)
         )),
+        parse_transfer_gate_ss58:
+        "(Transfer 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Gate:5C7LYpP2ZH3tpKbvVvwiVe54AapxErdPBbvkYhe6y9ZBkqWt)" => Ok(TrxRequest::Transfer(
+            MaxAmount::Amount(3),
+            Asset::Eth(ETH),
+            Account::Gate(BERT)
+        )),
+        parse_fail_transfer_gate_ss58_checksum:
+        "(Transfer 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Gate:5C7LYpP2ZH3tpKbvVvwiVe54AapxErdPBbvkYhe6y9ZBkqWu)" => Err(ParseError::InvalidChainAccount(Chain::Gate)),
+        parse_transfer_decimal:
+        "(Transfer 1.25 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Transfer(
+            MaxAmount::Decimal(Decimal { digits: 125, decimals: 2 }),
+            Asset::Eth(ETH),
+            Account::Eth(ALAN)
+        )),
+        parse_fail_transfer_decimal_overflow:
+        "(Transfer 1.000000000000000000000000000000000000000 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidAmount),
+        parse_fail_transfer_from_decimal:
+        "(TransferFrom 1.5 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101 Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidAmount),
+        parse_transfer_many:
+        "(TransferMany Cash (3 Eth:0x0101010101010101010101010101010101010101) \
+          (1.5 Gate:0x0202020202020202020202020202020202020202020202020202020202020202))" => Ok(TrxRequest::TransferMany(
+            Asset::Cash,
+            vec![
+                (MaxAmount::Amount(3), Account::Eth(ALAN)),
+                (MaxAmount::Decimal(Decimal { digits: 15, decimals: 1 }), Account::Gate(BERT)),
+            ]
+        )),
+        parse_fail_transfer_many_empty:
+        "(TransferMany Cash)" => Err(ParseError::InvalidArgs("TransferMany", 2, 1)),
+        parse_fail_transfer_many_max:
+        "(TransferMany Cash (Max Eth:0x0101010101010101010101010101010101010101))" => Err(ParseError::InvalidAmount),
+        parse_fail_transfer_many_bare:
+        "(TransferMany Cash 3 Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidExpression),
+        parse_fail_batch_transfer_many:
+        "(Batch (TransferMany Cash (3 Eth:0x0101010101010101010101010101010101010101)))" => Err(ParseError::InvalidBatchOperation("TransferMany")),
+        parse_transfer_symbol:
+        "(Transfer 3 USDC Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Transfer(
+            MaxAmount::Amount(3),
+            Asset::Symbol(String::from("USDC")),
+            Account::Eth(ALAN)
+        )),
         parse_transfer_max:
         "(Transfer Max Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Transfer(
             MaxAmount::Max,
             Asset::Eth(ETH),
             Account::Eth(ALAN)
         )),
+        parse_supply:
+        "(Supply 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Gate:0x0202020202020202020202020202020202020202020202020202020202020202)" => Ok(TrxRequest::Supply(
+            MaxAmount::Amount(3),
+            Asset::Eth(ETH),
+            Account::Gate(BERT)
+        )),
+        parse_supply_max_cash:
+        "(Supply Max Cash Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Supply(
+            MaxAmount::Max,
+            Asset::Cash,
+            Account::Eth(ALAN)
+        )),
+        parse_fail_supply_missing_account:
+        "(Supply 3 Cash)" => Err(ParseError::InvalidArgs("Supply", 3, 2)),
+        parse_repay:
+        "(Repay 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Repay(
+            MaxAmount::Amount(3),
+            Asset::Eth(ETH),
+            Account::Eth(ALAN)
+        )),
+        parse_repay_max:
+        "(Repay Max Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Repay(
+            MaxAmount::Max,
+            Asset::Eth(ETH),
+            Account::Eth(ALAN)
+        )),
+        parse_approve:
+        "(Approve 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Approve(
+            MaxAmount::Amount(3),
+            Asset::Eth(ETH),
+            Account::Eth(ALAN)
+        )),
+        parse_approve_max:
+        "(Approve Max Cash Gate:0x0202020202020202020202020202020202020202020202020202020202020202)" => Ok(TrxRequest::Approve(
+            MaxAmount::Max,
+            Asset::Cash,
+            Account::Gate(BERT)
+        )),
+        parse_transfer_from:
+        "(TransferFrom 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101 Gate:0x0202020202020202020202020202020202020202020202020202020202020202)" => Ok(TrxRequest::TransferFrom(
+            3,
+            Asset::Eth(ETH),
+            Account::Eth(ALAN),
+            Account::Gate(BERT)
+        )),
+        parse_fail_transfer_from_max:
+        "(TransferFrom Max Cash Eth:0x0101010101010101010101010101010101010101 Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidAmount),
+        parse_fail_batch_approve:
+        "(Batch (Approve 3 Cash Eth:0x0101010101010101010101010101010101010101))" => Err(ParseError::InvalidBatchOperation("Approve")),
         parse_liquidate_amount:
         "(Liquidate 55 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Cash Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::Liquidate(
             MaxAmount::Amount(55),
@@ -339,6 +863,36 @@ mod tests {
             Asset::Eth(ETH),
             Account::Eth(ALAN)
         )),
+        parse_liquidate_many:
+        "(LiquidateMany 55 Cash (Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101) Eth:0x0101010101010101010101010101010101010101)" => Ok(TrxRequest::LiquidateMany(
+            MaxAmount::Amount(55),
+            Asset::Cash,
+            vec![Asset::Eth(ETH), Asset::Eth(ALAN)],
+            Account::Eth(ALAN)
+        )),
+        parse_fail_liquidate_many_no_collateral:
+        "(LiquidateMany 55 Cash () Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidArgs("LiquidateMany", 4, 5)),
+        parse_fail_liquidate_many_bare_collateral:
+        "(LiquidateMany 55 Cash Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidArgs("LiquidateMany", 4, 3)),
+        parse_fail_batch_liquidate_many:
+        "(Batch (LiquidateMany 55 Cash (Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee) Eth:0x0101010101010101010101010101010101010101))" => Err(ParseError::InvalidBatchOperation("LiquidateMany")),
+        parse_batch:
+        "(Batch (Repay Max Cash Eth:0x0101010101010101010101010101010101010101) (Extract 3 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101))" => Ok(TrxRequest::Batch(vec![
+            TrxRequest::Repay(MaxAmount::Max, Asset::Cash, Account::Eth(ALAN)),
+            TrxRequest::Extract(MaxAmount::Amount(3), Asset::Eth(ETH), Account::Eth(ALAN)),
+        ])),
+        parse_fail_batch_empty:
+        "(Batch)" => Err(ParseError::InvalidArgs("Batch", 1, 0)),
+        parse_fail_batch_bare_args:
+        "(Batch 3 Cash Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidExpression),
+        parse_fail_batch_unbalanced:
+        "(Batch (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidExpression),
+        parse_fail_batch_nested:
+        "(Batch (Batch (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101)))" => Err(ParseError::InvalidBatchOperation("Batch")),
+        parse_fail_batch_liquidate:
+        "(Batch (Liquidate Max Cash Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101))" => Err(ParseError::InvalidBatchOperation("Liquidate")),
+        parse_fail_batch_invalid_operation:
+        "(Batch (Extract 3 Cash))" => Err(ParseError::InvalidArgs("Extract", 3, 2)),
         // TODO: Should we prohibit non-Cash from being Maxable?
         parse_fail_no_zero_ex:
         "(Extract 3 Eth:xxeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidChainAccount(Chain::Eth)),
@@ -352,5 +906,279 @@ mod tests {
         "(Extract 5 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeff Eth:0x0101010101010101010101010101010101010101)" => Err(ParseError::InvalidChainAccount(Chain::Eth)),
         parse_fail_invalid_recipient:
         "(Extract 5 Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee Eth:0x0101010101010101010101010101010101010101ff)" => Err(ParseError::InvalidChainAccount(Chain::Eth)),
+        parse_cancel:
+        "(Cancel 5)" => Ok(TrxRequest::Cancel(5)),
+        parse_fail_cancel_too_large:
+        "(Cancel 4294967296)" => Err(ParseError::InvalidNonce),
+        parse_fail_cancel_args:
+        "(Cancel 5 6)" => Err(ParseError::InvalidArgs("Cancel", 1, 2)),
+        parse_nonce_window:
+        "(NonceWindow 16)" => Ok(TrxRequest::SetNonceWindow(16)),
+        parse_fail_nonce_window_max:
+        "(NonceWindow Max)" => Err(ParseError::InvalidNonce),
+        parse_claim_rewards:
+        "(ClaimRewards)" => Ok(TrxRequest::ClaimRewards),
+        parse_fail_claim_rewards_args:
+        "(ClaimRewards 5)" => Err(ParseError::InvalidArgs("ClaimRewards", 0, 1)),
+        parse_fail_batch_claim_rewards:
+        "(Batch (ClaimRewards))" => Err(ParseError::InvalidBatchOperation("ClaimRewards")),
+        parse_fail_batch_cancel:
+        "(Batch (Cancel 5))" => Err(ParseError::InvalidBatchOperation("Cancel")),
+        parse_bond:
+        "(Bond 1000)" => Ok(TrxRequest::Bond(MaxAmount::Amount(1000))),
+        parse_unbond_max:
+        "(Unbond Max)" => Ok(TrxRequest::Unbond(MaxAmount::Max)),
+        parse_fail_bond_args:
+        "(Bond)" => Err(ParseError::InvalidArgs("Bond", 1, 0)),
+        parse_fail_batch_unbond:
+        "(Batch (Unbond 5))" => Err(ParseError::InvalidBatchOperation("Unbond")),
+        parse_ignores_deadline:
+        "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :deadline 1000" => Ok(TrxRequest::Extract(
+            MaxAmount::Amount(3),
+            Asset::Cash,
+            Account::Eth(ALAN)
+        )),
+    }
+
+    #[test]
+    fn parse_fail_transfer_many_too_many() {
+        let transfers = "(1 Eth:0x0101010101010101010101010101010101010101)"
+            .repeat(MAX_TRANSFER_RECIPIENTS + 1);
+        assert_eq!(
+            parse_request(&format!("(TransferMany Cash {})", transfers)),
+            Err(ParseError::TooManyRecipients)
+        );
+
+        let transfers =
+            "(1 Eth:0x0101010101010101010101010101010101010101)".repeat(MAX_TRANSFER_RECIPIENTS);
+        assert!(parse_request(&format!("(TransferMany Cash {})", transfers)).is_ok());
+    }
+
+    #[test]
+    fn parse_fail_liquidate_many_too_many() {
+        let collaterals = "Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee "
+            .repeat(MAX_LIQUIDATION_COLLATERALS + 1);
+        assert_eq!(
+            parse_request(&format!(
+                "(LiquidateMany 1 Cash ({}) Eth:0x0101010101010101010101010101010101010101)",
+                collaterals
+            )),
+            Err(ParseError::TooManyCollaterals)
+        );
+
+        let collaterals =
+            "Eth:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee ".repeat(MAX_LIQUIDATION_COLLATERALS);
+        assert!(parse_request(&format!(
+            "(LiquidateMany 1 Cash ({}) Eth:0x0101010101010101010101010101010101010101)",
+            collaterals
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn parse_deadline() {
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :deadline 1000"
+            ),
+            Ok((
+                TrxRequest::Extract(MaxAmount::Amount(3), Asset::Cash, Account::Eth(ALAN)),
+                Clauses {
+                    deadline: Some(1000),
+                    fee: None,
+                    execution_fee: None
+                }
+            ))
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101)"
+            ),
+            Ok((
+                TrxRequest::Extract(MaxAmount::Amount(3), Asset::Cash, Account::Eth(ALAN)),
+                Clauses::default()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_fail_deadline() {
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :deadline Max"
+            ),
+            Err(ParseError::InvalidDeadline)
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :deadline 18446744073709551616"
+            ),
+            Err(ParseError::InvalidDeadline)
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                ":deadline 1000 (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101)"
+            ),
+            Err(ParseError::InvalidClause)
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :deadline 1000 :deadline 2000"
+            ),
+            Err(ParseError::InvalidClause)
+        );
+    }
+
+    #[test]
+    fn parse_fee() {
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :fee 1000"
+            ),
+            Ok((
+                TrxRequest::Extract(MaxAmount::Amount(3), Asset::Cash, Account::Eth(ALAN)),
+                Clauses {
+                    deadline: None,
+                    fee: Some(Fee {
+                        principal: 1000,
+                        recipient: None
+                    }),
+                    execution_fee: None
+                }
+            ))
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) \
+                :fee 1000 Gate:0x0202020202020202020202020202020202020202020202020202020202020202 :deadline 5"
+            ),
+            Ok((
+                TrxRequest::Extract(MaxAmount::Amount(3), Asset::Cash, Account::Eth(ALAN)),
+                Clauses {
+                    deadline: Some(5),
+                    fee: Some(Fee {
+                        principal: 1000,
+                        recipient: Some(Account::Gate(BERT))
+                    }),
+                    execution_fee: None
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_fail_fee() {
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :fee Max"
+            ),
+            Err(ParseError::InvalidFee)
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :fee"
+            ),
+            Err(ParseError::InvalidClause)
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :fee 1 Eth:0x01"
+            ),
+            Err(ParseError::InvalidChainAccount(Chain::Eth))
+        );
+    }
+
+    #[test]
+    fn parse_execution_fee() {
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :execfee 500 :fee 1000"
+            ),
+            Ok((
+                TrxRequest::Extract(MaxAmount::Amount(3), Asset::Cash, Account::Eth(ALAN)),
+                Clauses {
+                    deadline: None,
+                    fee: Some(Fee {
+                        principal: 1000,
+                        recipient: None
+                    }),
+                    execution_fee: Some(500)
+                }
+            ))
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :execfee Max"
+            ),
+            Err(ParseError::InvalidFee)
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :execfee 1 :execfee 2"
+            ),
+            Err(ParseError::InvalidClause)
+        );
+    }
+
+    #[test]
+    fn parse_versioned() {
+        assert_eq!(
+            parse_versioned_request(
+                "(v1 (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101))"
+            ),
+            Ok((
+                Version::V1,
+                TrxRequest::Extract(MaxAmount::Amount(3), Asset::Cash, Account::Eth(ALAN))
+            ))
+        );
+        assert_eq!(
+            parse_versioned_request(
+                "(Extract 3 Cash Eth:0x0101010101010101010101010101010101010101)"
+            ),
+            Ok((
+                Version::V1,
+                TrxRequest::Extract(MaxAmount::Amount(3), Asset::Cash, Account::Eth(ALAN))
+            ))
+        );
+        assert_eq!(
+            parse_request_with_clauses(
+                "(v1 (Batch (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101))) :deadline 5"
+            ),
+            Ok((
+                TrxRequest::Batch(vec![TrxRequest::Extract(
+                    MaxAmount::Amount(3),
+                    Asset::Cash,
+                    Account::Eth(ALAN)
+                )]),
+                Clauses {
+                    deadline: Some(5),
+                    fee: None,
+                    execution_fee: None
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_fail_versioned() {
+        assert_eq!(
+            parse_request("(v99 (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101))"),
+            Err(ParseError::UnknownVersion(99))
+        );
+        assert_eq!(parse_request("(v1)"), Err(ParseError::InvalidExpression));
+        assert_eq!(
+            parse_request(
+                "(v1 (Extract 3 Cash Eth:0x0101010101010101010101010101010101010101) :deadline 5)"
+            ),
+            Err(ParseError::InvalidClause)
+        );
+    }
+
+    #[test]
+    fn version_supports() {
+        assert!(Version::V1.supports("Extract"));
+        assert!(Version::V1.supports("Batch"));
+        assert!(!Version::V1.supports("MyFun"));
+        assert!(LATEST_VERSION >= Version::V1);
     }
 }
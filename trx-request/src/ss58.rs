@@ -0,0 +1,65 @@
+use blake2_rfc::blake2b::Blake2b;
+
+const CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+const CHECKSUM_LEN: usize = 2;
+
+/// Decode an SS58 encoded 32-byte account, for any network prefix, validating the checksum.
+pub fn decode_account(address: &str) -> Option<[u8; 32]> {
+    let data = bs58::decode(address).into_vec().ok()?;
+    let prefix_len = match data.first()? {
+        0..=63 => 1,
+        64..=127 => 2,
+        _ => return None,
+    };
+    if data.len() != prefix_len + 32 + CHECKSUM_LEN {
+        return None;
+    }
+
+    let (body, checksum) = data.split_at(data.len() - CHECKSUM_LEN);
+    let mut hasher = Blake2b::new(64);
+    hasher.update(CHECKSUM_PREFIX);
+    hasher.update(body);
+    if &hasher.finalize().as_bytes()[..CHECKSUM_LEN] != checksum {
+        return None;
+    }
+
+    let mut account = [0u8; 32];
+    account.copy_from_slice(&body[prefix_len..]);
+    Some(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_account() {
+        // Alice, generic substrate prefix
+        assert_eq!(
+            decode_account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+            Some(hex_literal(
+                "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+            ))
+        );
+        // Polkadot prefix
+        assert_eq!(
+            decode_account("13dh9e6R4KNFrcSTZzidnuD2CpbwABXG6fEhzdTXEahwG9h"),
+            Some([2; 32])
+        );
+        // Bad checksum
+        assert_eq!(
+            decode_account("5C7LYpP2ZH3tpKbvVvwiVe54AapxErdPBbvkYhe6y9ZBkqWu"),
+            None
+        );
+        // Not base58
+        assert_eq!(decode_account("0x0202"), None);
+        // Too short
+        assert_eq!(decode_account("5C7LYpP2ZH3tpKbv"), None);
+    }
+
+    fn hex_literal(s: &str) -> [u8; 32] {
+        let mut account = [0u8; 32];
+        account.copy_from_slice(&hex::decode(s).unwrap());
+        account
+    }
+}
@@ -0,0 +1,449 @@
+//! Builds a polkadot-js type bundle and TypeScript definitions from the derived `types.json`.
+//!
+//! usage: types-bundle <types.json> --spec-version <n> [--rpc <rpc.json>] [--releases <dir>]
+//!                     [--bundle <types-bundle.json>] [--typescript <types.d.ts>]
+//!
+//! Each release pulled into the releases directory (e.g. `releases/m88/types.json`) covers the
+//! spec versions from its own until the next release's, and the given types the spec version given on.
+
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SPEC_NAME: &str = "gateway";
+
+const PRIMITIVES: &[&str] = &[
+    "bool", "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128",
+];
+
+struct Args {
+    types: PathBuf,
+    spec_version: u32,
+    rpc: Option<PathBuf>,
+    releases: Option<PathBuf>,
+    bundle: Option<PathBuf>,
+    typescript: Option<PathBuf>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut types = None;
+    let mut spec_version = None;
+    let mut rpc = None;
+    let mut releases = None;
+    let mut bundle = None;
+    let mut typescript = None;
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--spec-version" => {
+                spec_version = Some(
+                    value()?
+                        .parse()
+                        .map_err(|e| format!("Invalid spec version: {}", e))?,
+                )
+            }
+            "--rpc" => rpc = Some(PathBuf::from(value()?)),
+            "--releases" => releases = Some(PathBuf::from(value()?)),
+            "--bundle" => bundle = Some(PathBuf::from(value()?)),
+            "--typescript" => typescript = Some(PathBuf::from(value()?)),
+            _ if types.is_none() && !arg.starts_with("--") => types = Some(PathBuf::from(arg)),
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok(Args {
+        types: types.ok_or("Missing types.json")?,
+        spec_version: spec_version.ok_or("Missing --spec-version")?,
+        rpc,
+        releases,
+        bundle,
+        typescript,
+    })
+}
+
+fn read_json(path: &Path) -> Result<Value, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Invalid JSON in {:?}: {}", path, e))
+}
+
+/// Read the types of each release in the directory, by the spec version in its name.
+fn read_releases(dir: &Path) -> Result<Vec<(u32, Value)>, String> {
+    let mut releases = vec![];
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {:?}: {}", dir, e))?;
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let version = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix('m'))
+            .and_then(|version| version.parse().ok());
+        let types = path.join("types.json");
+        if let (Some(version), true) = (version, types.is_file()) {
+            releases.push((version, read_json(&types)?));
+        }
+    }
+    Ok(releases)
+}
+
+/// Give each set of types the spec versions it covers, as polkadot-js `minmax` ranges.
+///
+/// The earliest set also covers any earlier versions, and the latest any later ones.
+fn versioned_types(mut releases: Vec<(u32, Value)>, spec_version: u32, types: Value) -> Vec<Value> {
+    releases.retain(|(version, _)| *version < spec_version);
+    releases.push((spec_version, types));
+    releases.sort_by_key(|(version, _)| *version);
+
+    let mut ranges: Vec<(Option<u32>, Option<u32>, Value)> = vec![];
+    for (i, (version, types)) in releases.iter().enumerate() {
+        let max = releases.get(i + 1).map(|(next, _)| next - 1);
+        match ranges.last_mut() {
+            Some(last) if last.2 == *types => last.1 = max,
+            _ => ranges.push((
+                if i == 0 { None } else { Some(*version) },
+                max,
+                types.clone(),
+            )),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(min, max, types)| json!({ "minmax": [min, max], "types": types }))
+        .collect()
+}
+
+fn build_bundle(versioned: Vec<Value>, rpc: Option<Value>) -> Value {
+    let mut spec = Map::new();
+    spec.insert(String::from("types"), Value::Array(versioned));
+    if let Some(rpc) = rpc {
+        spec.insert(String::from("rpc"), rpc);
+    }
+    let mut specs = Map::new();
+    specs.insert(String::from(SPEC_NAME), Value::Object(spec));
+    json!({ "spec": specs })
+}
+
+/// A type as written in `types.json`, e.g. `Vec<(ChainId,[u8; 32])>`.
+#[derive(Debug, PartialEq)]
+enum TypeDef {
+    Named(String, Vec<TypeDef>),
+    Tuple(Vec<TypeDef>),
+    Array(Box<TypeDef>, usize),
+}
+
+fn parse_type(s: &str) -> Result<TypeDef, String> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let (ty, rest) = parse_type_at(&chars)?;
+    if rest.is_empty() {
+        Ok(ty)
+    } else {
+        Err(format!("Unexpected trailing characters in type: {}", s))
+    }
+}
+
+fn parse_list(mut s: &[char], close: char) -> Result<(Vec<TypeDef>, &[char]), String> {
+    let mut items = vec![];
+    loop {
+        if let Some(rest) = s.strip_prefix(&[close]) {
+            return Ok((items, rest));
+        }
+        let (item, rest) = parse_type_at(s)?;
+        items.push(item);
+        s = rest.strip_prefix(&[',']).unwrap_or(rest);
+    }
+}
+
+fn parse_type_at(s: &[char]) -> Result<(TypeDef, &[char]), String> {
+    match s.first() {
+        Some('(') => {
+            let (items, rest) = parse_list(&s[1..], ')')?;
+            Ok((TypeDef::Tuple(items), rest))
+        }
+        Some('[') => {
+            let (elem, rest) = parse_type_at(&s[1..])?;
+            let rest = rest
+                .strip_prefix(&[';'])
+                .ok_or("Expected ; in array type")?;
+            let end = rest
+                .iter()
+                .position(|c| *c == ']')
+                .ok_or("Expected ] in array type")?;
+            let len: String = rest[..end].iter().collect();
+            let len = len
+                .parse()
+                .map_err(|_| format!("Invalid array length: {}", len))?;
+            Ok((TypeDef::Array(Box::new(elem), len), &rest[end + 1..]))
+        }
+        Some(_) => {
+            let end = s
+                .iter()
+                .position(|c| !(c.is_alphanumeric() || *c == '_'))
+                .unwrap_or(s.len());
+            if end == 0 {
+                return Err(format!("Unexpected character in type: {}", s[0]));
+            }
+            let name: String = s[..end].iter().collect();
+            match s[end..].strip_prefix(&['<']) {
+                Some(rest) => {
+                    let (args, rest) = parse_list(rest, '>')?;
+                    Ok((TypeDef::Named(name, args), rest))
+                }
+                None => Ok((TypeDef::Named(name, vec![]), &s[end..])),
+            }
+        }
+        None => Err(String::from("Unexpected end of type")),
+    }
+}
+
+fn import(imports: &mut BTreeSet<String>, name: &str) -> String {
+    imports.insert(String::from(name));
+    String::from(name)
+}
+
+/// The TypeScript codec of a type, noting any polkadot-js types it needs imported.
+fn to_typescript(ty: &TypeDef, imports: &mut BTreeSet<String>) -> String {
+    let bytes = TypeDef::Named(String::from("u8"), vec![]);
+    match ty {
+        TypeDef::Tuple(items) if items.is_empty() => import(imports, "Null"),
+        TypeDef::Tuple(items) => {
+            import(imports, "ITuple");
+            let items: Vec<String> = items.iter().map(|t| to_typescript(t, imports)).collect();
+            format!("ITuple<[{}]>", items.join(", "))
+        }
+        TypeDef::Array(elem, _) if **elem == bytes => import(imports, "U8aFixed"),
+        TypeDef::Array(elem, _) => {
+            import(imports, "VecFixed");
+            format!("VecFixed<{}>", to_typescript(elem, imports))
+        }
+        TypeDef::Named(name, args) if name == "Vec" && args.len() == 1 && args[0] == bytes => {
+            import(imports, "Bytes")
+        }
+        TypeDef::Named(name, args) => {
+            let name = match name.as_str() {
+                "String" => import(imports, "Text"),
+                _ => import(imports, name),
+            };
+            if args.is_empty() {
+                name
+            } else {
+                let args: Vec<String> = args.iter().map(|t| to_typescript(t, imports)).collect();
+                format!("{}<{}>", name, args.join(", "))
+            }
+        }
+    }
+}
+
+fn camel_case(field: &str) -> String {
+    let mut parts = field.split('_');
+    let first = parts.next().unwrap_or_default().to_string();
+    parts.fold(first, |mut out, part| {
+        let mut chars = part.chars();
+        if let Some(c) = chars.next() {
+            out.extend(c.to_uppercase());
+            out.extend(chars);
+        }
+        out
+    })
+}
+
+fn field_type(def: &Value, imports: &mut BTreeSet<String>) -> Result<String, String> {
+    let def = def
+        .as_str()
+        .ok_or_else(|| format!("Expected a type name: {}", def))?;
+    Ok(to_typescript(&parse_type(def)?, imports))
+}
+
+fn interface(name: &str, def: &Value, imports: &mut BTreeSet<String>) -> Result<String, String> {
+    match def {
+        Value::String(alias) => {
+            let codec = if alias.is_empty() {
+                import(imports, "Null")
+            } else {
+                to_typescript(&parse_type(alias)?, imports)
+            };
+            Ok(format!(
+                "export interface {} extends {} {{}}\n",
+                name, codec
+            ))
+        }
+        Value::Object(fields) => match fields.get("_enum") {
+            Some(Value::Object(variants)) => {
+                imports.insert(String::from("Enum"));
+                let mut out = format!("export interface {} extends Enum {{\n", name);
+                for (variant, def) in variants {
+                    out.push_str(&format!("  readonly is{}: boolean;\n", variant));
+                    if def.as_str() != Some("") {
+                        let ty = field_type(def, imports)?;
+                        out.push_str(&format!("  readonly as{}: {};\n", variant, ty));
+                    }
+                }
+                let names: Vec<String> = variants.keys().map(|v| format!("'{}'", v)).collect();
+                out.push_str(&format!("  readonly type: {};\n}}\n", names.join(" | ")));
+                Ok(out)
+            }
+            Some(_) => Err(format!("Invalid enum: {}", name)),
+            None => {
+                imports.insert(String::from("Struct"));
+                let mut out = format!("export interface {} extends Struct {{\n", name);
+                for (field, def) in fields {
+                    let ty = field_type(def, imports)?;
+                    out.push_str(&format!("  readonly {}: {};\n", camel_case(field), ty));
+                }
+                out.push_str("}\n");
+                Ok(out)
+            }
+        },
+        _ => Err(format!("Invalid type definition for {}: {}", name, def)),
+    }
+}
+
+/// Write an interface for each type, importing whatever isn't defined here from polkadot-js,
+/// which is assumed to be one of its interfaces unless it's a codec.
+fn build_typescript(types: &Value) -> Result<String, String> {
+    let types = types.as_object().ok_or("Expected types to be an object")?;
+    let mut imports = BTreeSet::new();
+    let mut interfaces = vec![];
+    for (name, def) in types {
+        interfaces.push(interface(name, def, &mut imports)?);
+    }
+
+    let (mut codec, mut interfaces_import, mut types_import) = (vec![], vec![], vec![]);
+    for name in imports.iter().filter(|name| !types.contains_key(*name)) {
+        match name.as_str() {
+            "ITuple" => types_import.push(name.clone()),
+            "Bytes" | "BTreeMap" | "BTreeSet" | "Enum" | "Null" | "Option" | "Struct" | "Text"
+            | "U8aFixed" | "Vec" | "VecFixed" => codec.push(name.clone()),
+            _ if PRIMITIVES.contains(&name.as_str()) => codec.push(name.clone()),
+            _ => interfaces_import.push(name.clone()),
+        }
+    }
+
+    let mut out =
+        String::from("// Auto-generated by types-bundle from types.json, do not edit.\n\n");
+    for (names, module) in &[
+        (codec, "@polkadot/types"),
+        (interfaces_import, "@polkadot/types/interfaces"),
+        (types_import, "@polkadot/types/types"),
+    ] {
+        if !names.is_empty() {
+            out.push_str(&format!(
+                "import type {{ {} }} from '{}';\n",
+                names.join(", "),
+                module
+            ));
+        }
+    }
+    for interface in interfaces {
+        out.push('\n');
+        out.push_str(&interface);
+    }
+    Ok(out)
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    println!("Wrote {:?}", path);
+    Ok(())
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args(std::env::args().skip(1))?;
+    let types = read_json(&args.types)?;
+
+    if let Some(path) = &args.bundle {
+        let releases = match &args.releases {
+            Some(dir) if dir.is_dir() => read_releases(dir)?,
+            _ => vec![],
+        };
+        let rpc = args.rpc.as_deref().map(read_json).transpose()?;
+        let bundle = build_bundle(
+            versioned_types(releases, args.spec_version, types.clone()),
+            rpc,
+        );
+        let bundle = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+        write(path, &bundle)?;
+    }
+
+    if let Some(path) = &args.typescript {
+        write(path, &build_typescript(&types)?)?;
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> TypeDef {
+        TypeDef::Named(String::from(name), vec![])
+    }
+
+    #[test]
+    fn test_parse_type() {
+        assert_eq!(
+            parse_type("Vec<(ChainId,[u8; 32])>"),
+            Ok(TypeDef::Named(
+                String::from("Vec"),
+                vec![TypeDef::Tuple(vec![
+                    named("ChainId"),
+                    TypeDef::Array(Box::new(named("u8")), 32)
+                ])]
+            ))
+        );
+        assert_eq!(parse_type("()"), Ok(TypeDef::Tuple(vec![])));
+        assert!(parse_type("Vec<u8").is_err());
+        assert!(parse_type("[u8; x]").is_err());
+    }
+
+    #[test]
+    fn test_versioned_types() {
+        let releases = vec![
+            (12, json!({"A": "u8"})),
+            (10, json!({"A": "u8"})),
+            (14, json!({"A": "u32"})),
+        ];
+        assert_eq!(
+            versioned_types(releases, 16, json!({"A": "u32"})),
+            vec![
+                json!({"minmax": [null, 13], "types": {"A": "u8"}}),
+                json!({"minmax": [14, null], "types": {"A": "u32"}}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_typescript() {
+        let types = json!({
+            "AssetAmount": "Uint",
+            "Uint": "u128",
+            "Pair": "(ChainId,Option<AssetAmount>)",
+            "Quantity": {"value": "AssetAmount", "decimal_places": "[u8; 12]"},
+            "ChainId": {"_enum": {"Eth": "", "Dot": "Vec<u8>"}},
+        });
+        let ts = build_typescript(&types).unwrap();
+        assert!(ts.contains(
+            "import type { Bytes, Enum, Option, Struct, U8aFixed, u128 } from '@polkadot/types';"
+        ));
+        assert!(ts.contains("import type { ITuple } from '@polkadot/types/types';"));
+        assert!(ts.contains("export interface AssetAmount extends Uint {}"));
+        assert!(
+            ts.contains("export interface Pair extends ITuple<[ChainId, Option<AssetAmount>]> {}")
+        );
+        assert!(ts.contains("  readonly decimalPlaces: U8aFixed;"));
+        assert!(ts.contains(
+            "  readonly isEth: boolean;\n  readonly isDot: boolean;\n  readonly asDot: Bytes;"
+        ));
+        assert!(ts.contains("  readonly type: 'Eth' | 'Dot';"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Merges the types each crate wrote to `TYPES_DIR` with the base types into `types.json`.
+//!
+//! usage: types-merge <types.json> <base_types.json> <types dir>
+//!
+//! The types are sorted by name, so the output depends only on the definitions, and a type which
+//! two crates define differently is an error rather than whichever happened to be written last.
+//! A crate may redefine a base type.
+
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+fn read_types(path: &Path) -> Result<Map<String, Value>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Invalid types in {:?}: {}", path, e))
+}
+
+/// Read the types written by each crate, in the order of the crate names.
+fn read_crate_types(dir: &Path) -> Result<Vec<(String, Map<String, Value>)>, String> {
+    let mut crates = vec![];
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {:?}: {}", dir, e))?;
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().map_or(false, |ext| ext == "json") {
+            let name = path
+                .file_stem()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_string();
+            crates.push((name, read_types(&path)?));
+        }
+    }
+    crates.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(crates)
+}
+
+fn merge(
+    base: Map<String, Value>,
+    crates: Vec<(String, Map<String, Value>)>,
+) -> Result<BTreeMap<String, Value>, String> {
+    let mut defined_by: BTreeMap<String, String> = BTreeMap::new();
+    let mut merged: BTreeMap<String, Value> = base.into_iter().collect();
+    for (crate_name, types) in crates {
+        for (name, def) in types {
+            if let Some(other) = defined_by.get(&name) {
+                if merged.get(&name) != Some(&def) {
+                    return Err(format!(
+                        "{} is defined differently by {} and {}",
+                        name, other, crate_name
+                    ));
+                }
+            }
+            defined_by.insert(name.clone(), crate_name.clone());
+            merged.insert(name, def);
+        }
+    }
+    Ok(merged)
+}
+
+fn run() -> Result<(), String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (output, base, dir) = match &args[..] {
+        [output, base, dir] => (Path::new(output), Path::new(base), Path::new(dir)),
+        _ => {
+            return Err(String::from(
+                "usage: types-merge <types.json> <base_types.json> <types dir>",
+            ))
+        }
+    };
+
+    let crates = read_crate_types(dir)?;
+    if crates.is_empty() {
+        return Err(format!("No types in {:?}, were the crates rebuilt?", dir));
+    }
+    let merged = merge(read_types(base)?, crates)?;
+    let data = serde_json::to_string_pretty(&merged).map_err(|e| e.to_string())?;
+    fs::write(output, data + "\n").map_err(|e| format!("Failed to write {:?}: {}", output, e))?;
+    println!("Wrote {} types to {:?}", merged.len(), output);
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn types(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_merge() {
+        let base = types(json!({"Keys": "SessionKeys", "Address": "MultiAddress"}));
+        let crates = vec![
+            (
+                String::from("pallet_cash"),
+                types(
+                    json!({"SessionIndex": "u32", "SessionEarnings": {"paid": "u128", "index": "SessionIndex"}}),
+                ),
+            ),
+            (
+                String::from("pallet_oracle"),
+                types(json!({"Keys": "OracleKeys", "SessionIndex": "u32"})),
+            ),
+        ];
+        let merged = merge(base, crates).unwrap();
+        assert_eq!(
+            merged.keys().collect::<Vec<_>>(),
+            vec!["Address", "Keys", "SessionEarnings", "SessionIndex"]
+        );
+        assert_eq!(merged["Keys"], json!("OracleKeys"));
+        // Fields keep the order they are declared in
+        assert_eq!(
+            serde_json::to_string(&merged["SessionEarnings"]).unwrap(),
+            r#"{"paid":"u128","index":"SessionIndex"}"#
+        );
+    }
+
+    #[test]
+    fn test_merge_conflict() {
+        let crates = vec![
+            (String::from("a"), types(json!({"Index": "u32"}))),
+            (String::from("b"), types(json!({"Index": "u64"}))),
+        ];
+        assert_eq!(
+            merge(Map::new(), crates),
+            Err(String::from("Index is defined differently by a and b"))
+        );
+    }
+}
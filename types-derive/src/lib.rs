@@ -1,25 +1,35 @@
+//! Writes the polkadot-js definitions of the types deriving `Types` at build time, when `TYPES_DIR`
+//! is set, to a file per crate which `types-merge` then merges into `types.json`.
+//! Each crate is compiled by its own rustc process, so writing a file per crate is what keeps
+//! parallel builds from clobbering each other, and the merge sorts everything so it's reproducible.
+//!
+//! This stands in for `scale-info`'s `TypeInfo` until the chain serves metadata v14, which needs
+//! our substrate fork rebased onto a release whose `frame-support` and `frame-metadata` (v14) are
+//! built on `scale-info`: the fork is still on `frame-support` 3.0 and `frame-metadata` 13, whose
+//! metadata carries only type names. Deriving `TypeInfo` alongside `Types` before then wouldn't
+//! reach any tooling, so `types.json` (see `scripts/build_types.sh`) remains the source of truth.
 extern crate proc_macro;
 use lazy_static::lazy_static;
 use proc_macro::TokenStream;
 use serde_json;
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::env;
-use std::fs::File;
-use std::io::Write;
-use std::io::{Seek, SeekFrom};
+use std::fs;
+use std::path::PathBuf;
 use std::sync::Mutex;
 
 lazy_static! {
     static ref TYPES: Mutex<Vec<(String, serde_json::Value)>> = Mutex::new(vec![]);
-    static ref TYPES_JSON: Mutex<File> = Mutex::new(
-        File::create(env::var("TYPES_FILE").unwrap_or(String::from("/tmp/types.json")))
-            .expect("Unable to create file")
-    );
+}
+
+fn types_dir() -> Option<PathBuf> {
+    env::var_os("TYPES_DIR").map(PathBuf::from)
 }
 
 #[proc_macro_attribute]
 pub fn type_alias(attr: TokenStream, item: TokenStream) -> TokenStream {
-    if let Ok(_) = env::var("TYPES_FILE") {
+    if types_dir().is_some() {
         let prefix: Option<syn::LitStr> = syn::parse(attr).ok();
         let ast: syn::ItemType = syn::parse(item.clone()).expect("was parsing...");
 
@@ -42,7 +52,7 @@ pub fn type_alias(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(Types)]
 pub fn derive_types(input: TokenStream) -> TokenStream {
-    if let Ok(_) = env::var("TYPES_FILE") {
+    if types_dir().is_some() {
         let ast = syn::parse(input).unwrap();
 
         derive_and_write_types(&ast);
@@ -52,25 +62,21 @@ pub fn derive_types(input: TokenStream) -> TokenStream {
 }
 
 fn write_types(new_types: Vec<(String, serde_json::Value)>) {
-    let data = serde_json::to_string(&json!(new_types
-        .clone()
-        .into_iter()
-        .collect::<serde_json::Map<_, _>>()))
-    .expect("unable to serialize json");
-
-    let mut types_json = TYPES_JSON.lock().unwrap();
-
-    types_json
-        .set_len(0)
-        .expect("Unable to truncate types.json");
-
-    types_json
-        .seek(SeekFrom::Start(0))
-        .expect("Unable to seek to file start");
-
-    types_json
-        .write_all(data.as_bytes())
-        .expect("Unable to write data");
+    let dir = types_dir().expect("TYPES_DIR not set");
+    let crate_name = env::var("CARGO_CRATE_NAME")
+        .or_else(|_| env::var("CARGO_PKG_NAME"))
+        .expect("Unable to determine crate name");
+
+    let types = new_types.into_iter().collect::<BTreeMap<_, _>>();
+    let data = serde_json::to_string_pretty(&types).expect("unable to serialize json");
+
+    // A crate may still be compiled more than once at a time, e.g. by an editor during a build,
+    // so each write goes to its own file and is then moved into place whole
+    let path = dir.join(format!("{}.json", crate_name));
+    let tmp = dir.join(format!("{}.json.{}", crate_name, std::process::id()));
+    fs::create_dir_all(&dir).expect("Unable to create types dir");
+    fs::write(&tmp, data).expect("Unable to write data");
+    fs::rename(&tmp, &path).expect("Unable to move types into place");
 }
 
 fn show_arg(arg: &syn::GenericArgument) -> String {